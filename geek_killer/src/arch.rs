@@ -0,0 +1,167 @@
+// ═══════════════════════════════════════════════════════════════
+//  进程架构 - x86 / x64 / ARM64 以及是否处于模拟运行
+// ═══════════════════════════════════════════════════════════════
+//
+// IsWow64Process2 只能识别 WOW64 (x86 / ARM32 跑在 64 位系统上)；
+// ARM64 上的 x64 模拟进程不属于 WOW64，需要 GetProcessInformation(ProcessMachineTypeInfo)
+// (Windows 11 起提供)，旧系统上查询失败时按本机架构处理。
+//
+// 本程序自身为 32 位 (WOW64) 时：
+//   - System32 会被重定向到 SysWOW64，读其他进程的版本信息要改走 Sysnative
+//   - 无法读取 64 位进程的 PEB，安全检查据此跳过
+
+use crate::i18n::trf;
+use std::path::{Path, PathBuf};
+use windows_sys::Win32::Foundation::CloseHandle;
+use windows_sys::Win32::System::SystemInformation::{
+    IMAGE_FILE_MACHINE_AMD64, IMAGE_FILE_MACHINE_ARM64, IMAGE_FILE_MACHINE_ARMNT, IMAGE_FILE_MACHINE_I386,
+    IMAGE_FILE_MACHINE_UNKNOWN,
+};
+use windows_sys::Win32::System::Threading::{
+    GetCurrentProcess, GetProcessInformation, IsWow64Process2, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+};
+
+/// PROCESS_INFORMATION_CLASS::ProcessMachineTypeInfo
+const PROCESS_MACHINE_TYPE_INFO: i32 = 9;
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(dead_code)]
+struct ProcessMachineInformation {
+    process_machine: u16,
+    res0: u16,
+    machine_attributes: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Arch {
+    X86,
+    X64,
+    Arm,
+    Arm64,
+    Unknown,
+}
+
+impl Arch {
+    fn from_machine(machine: u16) -> Self {
+        match machine {
+            IMAGE_FILE_MACHINE_I386 => Arch::X86,
+            IMAGE_FILE_MACHINE_AMD64 => Arch::X64,
+            IMAGE_FILE_MACHINE_ARMNT => Arch::Arm,
+            IMAGE_FILE_MACHINE_ARM64 => Arch::Arm64,
+            _ => Arch::Unknown,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Arch::X86 => "x86",
+            Arch::X64 => "x64",
+            Arch::Arm => "ARM",
+            Arch::Arm64 => "ARM64",
+            Arch::Unknown => "?",
+        }
+    }
+
+    pub fn is_64bit(&self) -> bool {
+        matches!(self, Arch::X64 | Arch::Arm64)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProcessArch {
+    pub arch: Arch,
+    /// 本机架构
+    pub native: Arch,
+    /// 与本机架构不同 (WOW64 或 ARM64 上的 x64 模拟)
+    pub emulated: bool,
+}
+
+impl ProcessArch {
+    pub fn label(&self) -> String {
+        if self.emulated {
+            format!("{}*", self.arch.label())
+        } else {
+            self.arch.label().to_string()
+        }
+    }
+
+    pub fn tooltip(&self) -> String {
+        if self.emulated {
+            trf("{} 进程，在 {} 系统上模拟运行 (性能与耗电通常更差)", &[&self.arch.label(), &self.native.label()])
+        } else {
+            trf("{} 原生进程", &[&self.arch.label()])
+        }
+    }
+}
+
+unsafe fn query_handle(process: isize) -> Option<ProcessArch> {
+    let mut process_machine = IMAGE_FILE_MACHINE_UNKNOWN;
+    let mut native_machine = IMAGE_FILE_MACHINE_UNKNOWN;
+    if IsWow64Process2(process, &mut process_machine, &mut native_machine) == 0 {
+        return None;
+    }
+    let native = Arch::from_machine(native_machine);
+    if process_machine != IMAGE_FILE_MACHINE_UNKNOWN {
+        // WOW64
+        return Some(ProcessArch { arch: Arch::from_machine(process_machine), native, emulated: true });
+    }
+    let mut info = ProcessMachineInformation::default();
+    let arch = if GetProcessInformation(
+        process,
+        PROCESS_MACHINE_TYPE_INFO as _,
+        &mut info as *mut _ as _,
+        std::mem::size_of::<ProcessMachineInformation>() as u32,
+    ) != 0
+    {
+        Arch::from_machine(info.process_machine)
+    } else {
+        native
+    };
+    Some(ProcessArch { arch, native, emulated: arch != native })
+}
+
+/// 查询进程架构；无权访问时返回 None
+pub fn query(pid: u32) -> Option<ProcessArch> {
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process == 0 {
+            return None;
+        }
+        let result = query_handle(process);
+        CloseHandle(process);
+        result
+    }
+}
+
+/// 本程序自身的架构
+pub fn current() -> Option<ProcessArch> {
+    unsafe { query_handle(GetCurrentProcess()) }
+}
+
+/// 本程序是 64 位系统上的 32 位进程
+pub fn self_is_wow64() -> bool {
+    static WOW64: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *WOW64.get_or_init(|| cfg!(target_pointer_width = "32") && current().map(|a| a.native.is_64bit()).unwrap_or(false))
+}
+
+/// 能否按本进程的 PEB 布局读取目标进程 (32 位读 64 位不行)
+pub fn can_read_peb(pid: u32) -> bool {
+    !self_is_wow64() || query(pid).map(|a| !a.arch.is_64bit()).unwrap_or(false)
+}
+
+/// 其他进程看到的真实路径：WOW64 下 System32 改写为 Sysnative，绕过文件系统重定向
+pub fn native_path(path: &Path) -> PathBuf {
+    if !self_is_wow64() {
+        return path.to_path_buf();
+    }
+    let s = path.to_string_lossy();
+    let lower = s.to_ascii_lowercase();
+    match lower.find("\\windows\\system32\\") {
+        Some(i) => {
+            let start = i + "\\windows\\".len();
+            PathBuf::from(format!("{}sysnative{}", &s[..start], &s[start + "system32".len()..]))
+        }
+        None => path.to_path_buf(),
+    }
+}
@@ -0,0 +1,59 @@
+// ═══════════════════════════════════════════════════════════════
+//  修复声音 - 重启 Windows 音频服务与音频引擎进程
+// ═══════════════════════════════════════════════════════════════
+//
+// 没声音 / 爆音 / 设备列表不刷新时，手动的标准做法是到 services.msc 重启
+// “Windows Audio”，不行再结束 audiodg.exe。这里按依赖顺序一键完成：
+//   1. 停止 AudioSrv (依赖 AudioEndpointBuilder，必须先停)
+//   2. 停止 AudioEndpointBuilder
+//   3. 结束残留的 audiodg.exe (音频引擎，卡死时服务停了它仍在)
+//   4. 启动 AudioEndpointBuilder -> AudioSrv
+// 每步结果推送给界面，最后回读两个服务的状态。audiodg 在下次播放声音时才会重新启动。
+
+use crate::i18n::{tr, trf};
+use crate::service_control::{outcome, Progress, Service};
+
+pub const TITLE: &str = "修复声音";
+
+/// 按停止顺序排列；启动时倒序
+const SERVICES: [(&str, &str); 2] = [
+    ("AudioSrv", "Windows Audio"),
+    ("AudioEndpointBuilder", "Windows Audio Endpoint Builder"),
+];
+
+pub fn run(report: &dyn Fn(Progress)) -> Result<String, String> {
+    let manager = Service::manager()?;
+    let services = SERVICES
+        .iter()
+        .map(|(name, label)| Service::open(&manager, name).map(|s| (s, *label)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (svc, label) in &services {
+        let r = svc.stop();
+        report(Progress::Step(r.is_ok(), trf("停止 {}：{}", &[&label, &outcome(&r)])));
+    }
+
+    let engines = crate::process_tree::pids_by_name("audiodg.exe");
+    if !engines.is_empty() {
+        let killed = engines.iter().filter(|&&pid| rust_core_lib::process::kill(pid).is_ok()).count();
+        report(Progress::Step(
+            killed == engines.len(),
+            trf("结束 audiodg.exe：{}/{}", &[&killed, &engines.len()]),
+        ));
+    }
+
+    let mut failed = Vec::new();
+    for (svc, label) in services.iter().rev() {
+        let r = svc.start();
+        if r.is_err() {
+            failed.push(*label);
+        }
+        report(Progress::Step(r.is_ok(), trf("启动 {}：{}", &[&label, &outcome(&r)])));
+    }
+
+    if failed.is_empty() {
+        Ok(tr("音频服务已重新运行，请重新播放声音测试").to_string())
+    } else {
+        Err(trf("{} 未能启动", &[&failed.join("、")]))
+    }
+}
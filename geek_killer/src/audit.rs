@@ -0,0 +1,26 @@
+// ═══════════════════════════════════════════════════════════════
+//  审计日志 - 记录所有执行过的外部命令与强制操作
+// ═══════════════════════════════════════════════════════════════
+//
+// 这是一个会强杀进程、强制卸载卷的工具，做过什么必须可追溯。
+// 日志为纯文本，每行一条：[时间] 类别 | 内容
+
+use std::io::Write;
+use std::path::PathBuf;
+
+pub fn log_path() -> Option<PathBuf> {
+    crate::app_data_dir().map(|d| d.join("audit.log"))
+}
+
+pub fn record(category: &str, detail: &str) {
+    let Some(path) = log_path() else {
+        return;
+    };
+    if let Ok(mut f) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+    {
+        let _ = writeln!(f, "[{}] {} | {}", crate::local_timestamp(), category, detail);
+    }
+}
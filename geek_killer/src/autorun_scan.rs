@@ -0,0 +1,164 @@
+// ═══════════════════════════════════════════════════════════════
+//  插入检查 - autorun.inf / 隐藏可执行文件 / 快捷方式病毒
+// ═══════════════════════════════════════════════════════════════
+//
+// 只看根目录，不递归，几十毫秒内完成：
+//   1. autorun.inf                       -> 记录 open / shellexecute 指向的程序
+//   2. 带隐藏 / 系统属性的可执行文件      -> 典型的 U 盘蠕虫本体
+//   3. 伪装扩展名 (照片.jpg.exe)          -> 诱导双击
+//   4. 指向 cmd / wscript / rundll32 的 .lnk，或与隐藏文件夹同名的 .lnk
+//                                         -> “快捷方式病毒”：真文件夹被隐藏，用快捷方式冒充
+// 只做提示，不删除任何文件。
+
+use crate::i18n::{tr, trf};
+use std::io::Read;
+use std::os::windows::fs::MetadataExt;
+
+const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+const FILE_ATTRIBUTE_SYSTEM: u32 = 0x4;
+
+/// 读取文件内容的上限，autorun.inf 与 .lnk 都很小
+const MAX_READ: u64 = 64 * 1024;
+
+const EXECUTABLE_EXTS: &[&str] = &[
+    "exe", "scr", "com", "pif", "bat", "cmd", "vbs", "vbe", "js", "jse", "wsf", "hta",
+];
+
+/// 看起来像文档 / 图片的扩展名，出现在可执行扩展名之前即为伪装
+const DECOY_EXTS: &[&str] = &[
+    "jpg", "jpeg", "png", "gif", "bmp", "pdf", "doc", "docx", "xls", "xlsx", "ppt", "pptx",
+    "txt", "mp3", "mp4", "avi", "zip", "rar",
+];
+
+/// 快捷方式病毒常借用的系统程序
+const SCRIPT_HOSTS: &[&str] = &[
+    "cmd.exe", "wscript", "cscript", "rundll32", "powershell", "mshta", "regsvr32",
+];
+
+/// 根目录下的系统文件夹，本来就是隐藏的
+const SYSTEM_DIRS: &[&str] = &["system volume information", "$recycle.bin", "recycler", "recycled"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ThreatKind {
+    /// autorun.inf，target 为其中指定的启动命令
+    AutorunInf { target: String },
+    /// 带隐藏或系统属性的可执行文件
+    HiddenExecutable,
+    /// 双扩展名伪装
+    DisguisedExecutable,
+    /// 可疑快捷方式，reason 为命中的特征
+    SuspiciousShortcut { reason: String },
+}
+
+#[derive(Clone, Debug)]
+pub struct Threat {
+    /// 根目录下的文件名
+    pub name: String,
+    pub kind: ThreatKind,
+}
+
+impl Threat {
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            ThreatKind::AutorunInf { target } if target.is_empty() => {
+                trf("{}：自动运行配置文件", &[&self.name])
+            }
+            ThreatKind::AutorunInf { target } => trf("{}：尝试自动运行 {}", &[&self.name, &target]),
+            ThreatKind::HiddenExecutable => trf("{}：被隐藏的可执行文件", &[&self.name]),
+            ThreatKind::DisguisedExecutable => trf("{}：伪装成文档的可执行文件", &[&self.name]),
+            ThreatKind::SuspiciousShortcut { reason } => format!("{}：{}", self.name, reason),
+        }
+    }
+}
+
+fn extension(name: &str) -> String {
+    name.rsplit_once('.').map(|(_, e)| e.to_ascii_lowercase()).unwrap_or_default()
+}
+
+fn read_head(path: &std::path::Path) -> Vec<u8> {
+    let mut buf = Vec::new();
+    if let Ok(f) = std::fs::File::open(path) {
+        let _ = f.take(MAX_READ).read_to_end(&mut buf);
+    }
+    buf
+}
+
+/// autorun.inf 中的启动命令
+fn autorun_target(content: &[u8]) -> String {
+    String::from_utf8_lossy(content)
+        .lines()
+        .filter_map(|l| l.split_once('='))
+        .find(|(k, _)| {
+            let k = k.trim().to_ascii_lowercase();
+            k == "open" || k == "shellexecute" || (k.starts_with("shell\\") && k.ends_with("\\command"))
+        })
+        .map(|(_, v)| v.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// .lnk 中的路径多为 UTF-16，去掉 0 字节后按 ASCII 小写匹配
+fn shortcut_host(content: &[u8]) -> Option<&'static str> {
+    let text: String = content
+        .iter()
+        .filter(|&&b| b != 0)
+        .map(|&b| (b as char).to_ascii_lowercase())
+        .collect();
+    SCRIPT_HOSTS.iter().copied().find(|h| text.contains(h))
+}
+
+/// 扫描盘符根目录；盘不可读时返回空列表
+pub fn scan_root(drive: &str) -> Vec<Threat> {
+    let root = crate::mount_manager::volume_root(drive);
+    let Ok(entries) = std::fs::read_dir(&root) else {
+        return vec![];
+    };
+
+    let mut files = Vec::new();
+    let mut hidden_dirs = Vec::new();
+    for entry in entries.flatten() {
+        let Ok(meta) = entry.metadata() else { continue };
+        let name = entry.file_name().to_string_lossy().to_string();
+        let hidden = meta.file_attributes() & (FILE_ATTRIBUTE_HIDDEN | FILE_ATTRIBUTE_SYSTEM) != 0;
+        if meta.is_dir() {
+            if hidden && !SYSTEM_DIRS.contains(&name.to_lowercase().as_str()) {
+                hidden_dirs.push(name.to_lowercase());
+            }
+        } else {
+            files.push((entry.path(), name, hidden));
+        }
+    }
+
+    let mut threats = Vec::new();
+    for (path, name, hidden) in files {
+        let ext = extension(&name);
+        let kind = if name.eq_ignore_ascii_case("autorun.inf") {
+            Some(ThreatKind::AutorunInf { target: autorun_target(&read_head(&path)) })
+        } else if EXECUTABLE_EXTS.contains(&ext.as_str()) {
+            let stem = &name[..name.len() - ext.len() - 1];
+            if hidden {
+                Some(ThreatKind::HiddenExecutable)
+            } else if DECOY_EXTS.contains(&extension(stem).as_str()) {
+                Some(ThreatKind::DisguisedExecutable)
+            } else {
+                None
+            }
+        } else if ext == "lnk" {
+            let stem = name[..name.len() - 4].to_lowercase();
+            if let Some(host) = shortcut_host(&read_head(&path)) {
+                Some(ThreatKind::SuspiciousShortcut { reason: trf("快捷方式会调用 {}", &[&host]) })
+            } else if hidden_dirs.contains(&stem) {
+                Some(ThreatKind::SuspiciousShortcut {
+                    reason: tr("冒充被隐藏的同名文件夹").to_string(),
+                })
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+        if let Some(kind) = kind {
+            threats.push(Threat { name, kind });
+        }
+    }
+    threats
+}
@@ -0,0 +1,232 @@
+// ═══════════════════════════════════════════════════════════════
+//  插入即备份 - robocopy 镜像 -> 校验 -> 自动弹出
+// ═══════════════════════════════════════════════════════════════
+//
+// 按设备偏好配置：某个 U 盘插入后，把指定文件夹镜像 (/MIR) 到盘上的子目录。
+//   1. robocopy /L 预演，统计需要复制的文件数与字节数 (用于进度条)
+//   2. robocopy /MIR 实际复制，按输出行推进进度
+//   3. BLAKE3 逐个比对源文件与目标文件 (见 checksum)
+// 退出码 0~7 表示成功 (有无复制 / 是否删除多余文件)，>= 8 表示有文件复制失败。
+
+use crate::i18n::{tr, trf};
+use crate::tool_runner::{ToolRun, ToolSpec};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+/// 默认的盘上目标目录
+pub const DEFAULT_TARGET_DIR: &str = "GeekKiller备份";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupJob {
+    /// 本机要备份的文件夹
+    pub source: String,
+    /// 盘上的目标子目录 (相对根目录)
+    pub target_dir: String,
+    /// 校验通过后自动弹出
+    pub eject_after: bool,
+}
+
+impl Default for BackupJob {
+    fn default() -> Self {
+        Self {
+            source: String::new(),
+            target_dir: DEFAULT_TARGET_DIR.to_string(),
+            eject_after: true,
+        }
+    }
+}
+
+impl BackupJob {
+    pub fn is_configured(&self) -> bool {
+        !self.source.trim().is_empty()
+    }
+
+    /// 盘上的完整目标路径：<根>\<目标目录>\<源文件夹名>
+    pub fn destination(&self, drive: &str) -> PathBuf {
+        let source = Path::new(self.source.trim());
+        let name = source
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "backup".to_string());
+        let dir = self.target_dir.trim().trim_matches(['\\', '/']);
+        let mut dest = PathBuf::from(crate::mount_manager::volume_root(drive));
+        if !dir.is_empty() {
+            dest.push(dir);
+        }
+        dest.push(name);
+        dest
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum BackupEvent {
+    /// 当前阶段说明
+    Stage(String),
+    Progress {
+        done_files: u64,
+        total_files: u64,
+        done_bytes: u64,
+        total_bytes: u64,
+    },
+    /// 完成：成功时为摘要，失败时为原因
+    Finished(Result<String, String>),
+}
+
+/// 正在进行的备份 (UI 侧)
+pub struct BackupState {
+    pub stage: String,
+    pub done_bytes: u64,
+    pub total_bytes: u64,
+    pub done_files: u64,
+    pub total_files: u64,
+    pub eject_after: bool,
+    pub cancel: Arc<AtomicBool>,
+}
+
+impl BackupState {
+    pub fn new(eject_after: bool, cancel: Arc<AtomicBool>) -> Self {
+        Self {
+            stage: tr("准备中").to_string(),
+            done_bytes: 0,
+            total_bytes: 0,
+            done_files: 0,
+            total_files: 0,
+            eject_after,
+            cancel,
+        }
+    }
+
+    pub fn ratio(&self) -> f32 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            (self.done_bytes as f64 / self.total_bytes as f64).min(1.0) as f32
+        }
+    }
+}
+
+fn robocopy_spec(source: &str, dest: &Path, list_only: bool) -> ToolSpec {
+    let dest = dest.to_string_lossy().to_string();
+    let mut args = vec![
+        source, dest.as_str(), "/MIR", "/R:1", "/W:1", "/BYTES", "/FP", "/NP", "/NDL", "/NJH", "/NJS",
+    ];
+    if list_only {
+        args.push("/L");
+    }
+    let title = tr(if list_only { "robocopy 预演" } else { "robocopy 镜像备份" });
+    ToolSpec::new(title, "robocopy", &args)
+}
+
+/// 解析一行文件输出："\t    新文件  \t\t    1234\tC:\src\a.txt" -> 字节数
+/// 多余文件 (*EXTRA File) 是 /MIR 要删除的，不计入复制量
+fn parse_file_line(line: &str) -> Option<u64> {
+    let fields: Vec<&str> = line.split('\t').map(str::trim).filter(|f| !f.is_empty()).collect();
+    if fields.len() < 2 || fields[0].starts_with('*') {
+        return None;
+    }
+    fields[fields.len() - 2].parse().ok()
+}
+
+/// 运行 robocopy，每出现一个文件行调用一次 on_file；返回退出码
+fn run_robocopy(
+    spec: ToolSpec,
+    ctx: &egui::Context,
+    cancel: &AtomicBool,
+    mut on_file: impl FnMut(u64),
+) -> Result<i32, String> {
+    let mut run = ToolRun::start(spec, ctx.clone());
+    let mut seen = 0;
+    loop {
+        run.poll();
+        for line in &run.lines[seen..] {
+            if let Some(bytes) = parse_file_line(&line.text) {
+                on_file(bytes);
+            }
+        }
+        seen = run.lines.len();
+        if let Some(exit) = &run.exit {
+            return exit.clone();
+        }
+        if cancel.load(Ordering::Relaxed) {
+            run.cancel();
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+}
+
+/// 在后台线程执行一次备份，进度通过 tx 以 (盘符, 事件) 发回
+pub fn spawn(
+    drive: String,
+    job: BackupJob,
+    tx: mpsc::Sender<(String, BackupEvent)>,
+    cancel: Arc<AtomicBool>,
+    ctx: egui::Context,
+) {
+    std::thread::spawn(move || {
+        let send = |ev: BackupEvent| {
+            let _ = tx.send((drive.clone(), ev));
+            ctx.request_repaint();
+        };
+        let result = run(&drive, &job, &send, &cancel, &ctx);
+        crate::audit::record(
+            "插入备份",
+            &format!(
+                "{} -> {}: {}",
+                job.source,
+                job.destination(&drive).display(),
+                result.as_ref().map(|s| s.as_str()).unwrap_or_else(|e| e.as_str())
+            ),
+        );
+        send(BackupEvent::Finished(result));
+    });
+}
+
+fn run(
+    drive: &str,
+    job: &BackupJob,
+    send: &(dyn Fn(BackupEvent) + Sync),
+    cancel: &AtomicBool,
+    ctx: &egui::Context,
+) -> Result<String, String> {
+    let source = job.source.trim().trim_end_matches(['\\', '/']).to_string();
+    if !Path::new(&source).is_dir() {
+        return Err(trf("源文件夹不存在：{}", &[&source]));
+    }
+    let dest = job.destination(drive);
+
+    send(BackupEvent::Stage(tr("统计需要复制的文件").to_string()));
+    let (mut total_files, mut total_bytes) = (0u64, 0u64);
+    run_robocopy(robocopy_spec(&source, &dest, true), ctx, cancel, |bytes| {
+        total_files += 1;
+        total_bytes += bytes;
+    })?;
+
+    send(BackupEvent::Stage(trf("正在复制 {} 个文件", &[&total_files])));
+    let (mut done_files, mut done_bytes) = (0u64, 0u64);
+    let code = run_robocopy(robocopy_spec(&source, &dest, false), ctx, cancel, |bytes| {
+        done_files += 1;
+        done_bytes += bytes;
+        send(BackupEvent::Progress { done_files, total_files, done_bytes, total_bytes });
+    })?;
+    if code >= 8 {
+        return Err(trf("robocopy 有文件复制失败 (退出码 {})", &[&code]));
+    }
+
+    send(BackupEvent::Stage(tr("BLAKE3 校验").to_string()));
+    let report = crate::checksum::verify_trees(Path::new(&source), &dest, cancel, &|done, total| {
+        send(BackupEvent::Progress { done_files: total_files, total_files, done_bytes: done, total_bytes: total });
+    })?;
+    if !report.is_ok() {
+        let first = report.problems().next().unwrap_or_default();
+        return Err(trf("校验失败：{}，如 {}", &[&report.summary(), &first]));
+    }
+    Ok(trf(
+        "已同步 {} 个文件 ({} MB)，校验 {}",
+        &[&done_files, &format!("{:.1}", done_bytes as f64 / 1024.0 / 1024.0), &report.summary()],
+    ))
+}
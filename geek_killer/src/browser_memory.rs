@@ -0,0 +1,163 @@
+// ═══════════════════════════════════════════════════════════════
+//  浏览器内存 - 标签进程统计与“释放浏览器内存”
+// ═══════════════════════════════════════════════════════════════
+//
+// 现代浏览器每个站点 / 标签一个渲染进程，标签开得多时内存主要耗在这里：
+//   - Chromium 系 (Chrome / Edge / Brave / Vivaldi / Opera)：--type=renderer
+//     (扩展进程带 --extension-process，不计入)
+//   - Firefox：-contentproc … tab
+// 直接结束渲染进程会让对应标签崩溃 (“喔唷，崩溃啦”)，这里不这么做，而是：
+//   1. 裁剪渲染进程的工作集 (EmptyWorkingSet)：不关闭任何标签，后台标签的内存
+//      换出到备用列表 / 页面文件，切回时再读回
+//   2. 在浏览器中打开自带的标签丢弃页面 (chrome://discards、about:unloads)，
+//      由浏览器自己卸载标签并保留标签页本身，点击即可重新载入
+
+use crate::i18n::trf;
+use windows_sys::Win32::Foundation::CloseHandle;
+use windows_sys::Win32::System::ProcessStatus::{EmptyWorkingSet, GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+use windows_sys::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_QUOTA};
+
+/// 标签进程数达到该值视为“标签过多”
+const EXTREME_TABS: usize = 30;
+/// 标签进程内存占物理内存的比例达到该值 (%) 同样提示
+const EXTREME_RAM_PERCENT: u64 = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Browser {
+    Chrome,
+    Edge,
+    Brave,
+    Vivaldi,
+    Opera,
+    Firefox,
+}
+
+impl Browser {
+    pub fn from_exe(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "chrome.exe" => Some(Browser::Chrome),
+            "msedge.exe" => Some(Browser::Edge),
+            "brave.exe" => Some(Browser::Brave),
+            "vivaldi.exe" => Some(Browser::Vivaldi),
+            "opera.exe" => Some(Browser::Opera),
+            "firefox.exe" => Some(Browser::Firefox),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Browser::Chrome => "Chrome",
+            Browser::Edge => "Edge",
+            Browser::Brave => "Brave",
+            Browser::Vivaldi => "Vivaldi",
+            Browser::Opera => "Opera",
+            Browser::Firefox => "Firefox",
+        }
+    }
+
+    /// 浏览器自带的标签丢弃 / 卸载页面
+    pub fn discard_page(&self) -> &'static str {
+        match self {
+            Browser::Edge => "edge://discards",
+            Browser::Firefox => "about:unloads",
+            _ => "chrome://discards",
+        }
+    }
+
+    /// 命令行是否属于标签 (渲染) 进程
+    pub fn is_tab_process(&self, cmd: &str) -> bool {
+        match self {
+            Browser::Firefox => cmd.contains("-contentproc") && cmd.split_whitespace().any(|a| a == "tab"),
+            _ => cmd.contains("--type=renderer") && !cmd.contains("--extension-process"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BrowserUsage {
+    pub browser: Browser,
+    pub exe_path: String,
+    pub tab_pids: Vec<u32>,
+    /// 标签进程内存合计，字节
+    pub tab_memory: u64,
+    /// 整个浏览器的内存，字节
+    pub total_memory: u64,
+}
+
+impl BrowserUsage {
+    pub fn is_extreme(&self, total_ram: u64) -> bool {
+        self.tab_pids.len() >= EXTREME_TABS
+            || (total_ram > 0 && self.tab_memory * 100 / total_ram >= EXTREME_RAM_PERCENT)
+    }
+}
+
+/// 从进程分组中找出浏览器及其标签进程
+pub fn survey<'a>(groups: impl Iterator<Item = &'a crate::ProcessGroup>) -> Vec<BrowserUsage> {
+    let mut out: Vec<BrowserUsage> = groups
+        .filter_map(|g| {
+            let browser = Browser::from_exe(&g.name)?;
+            let tabs: Vec<_> = g.members.iter().filter(|m| browser.is_tab_process(&m.cmd)).collect();
+            Some(BrowserUsage {
+                browser,
+                exe_path: g.exe_path.clone(),
+                tab_pids: tabs.iter().map(|m| m.pid).collect(),
+                tab_memory: tabs.iter().map(|m| m.memory).sum(),
+                total_memory: g.total_memory,
+            })
+        })
+        .collect();
+    out.sort_by_key(|u| std::cmp::Reverse(u.total_memory));
+    out
+}
+
+/// 裁剪工作集，返回 (成功裁剪的进程数, 减少的工作集字节)；看门狗的“裁剪内存”与“内存整理”同样使用
+pub fn trim(pids: &[u32]) -> (usize, u64) {
+    let mut trimmed = 0;
+    let mut freed = 0u64;
+    for &pid in pids {
+        unsafe {
+            let h = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION | PROCESS_SET_QUOTA, 0, pid);
+            if h == 0 {
+                continue;
+            }
+            let working_set = || {
+                let mut pmc: PROCESS_MEMORY_COUNTERS = std::mem::zeroed();
+                pmc.cb = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+                (GetProcessMemoryInfo(h, &mut pmc, pmc.cb) != 0).then_some(pmc.WorkingSetSize as u64)
+            };
+            let before = working_set();
+            if EmptyWorkingSet(h) != 0 {
+                trimmed += 1;
+                if let (Some(b), Some(a)) = (before, working_set()) {
+                    freed += b.saturating_sub(a);
+                }
+            }
+            CloseHandle(h);
+        }
+    }
+    (trimmed, freed)
+}
+
+/// 释放浏览器内存：裁剪标签进程工作集并打开浏览器自带的丢弃页面，返回结果说明
+pub fn release(usage: &BrowserUsage) -> String {
+    let (trimmed, freed) = trim(&usage.tab_pids);
+    let page = usage.browser.discard_page();
+    // 以普通用户权限启动，避免浏览器继承本程序的管理员身份
+    let opened = crate::unelevated::spawn(&usage.exe_path, &[page.to_string()]);
+    let text = trf(
+        "{}：裁剪 {}/{} 个标签进程，工作集减少 {} MB；{}",
+        &[
+            &usage.browser.label(),
+            &trimmed,
+            &usage.tab_pids.len(),
+            &format!("{:.0}", freed as f64 / 1024.0 / 1024.0),
+            &match &opened {
+                Ok(()) => trf("已打开 {}，可在其中丢弃不用的标签", &[&page]),
+                Err(e) => trf("无法打开 {}：{}", &[&page, &e]),
+            },
+        ],
+    );
+    crate::audit::record("释放浏览器内存", &format!("{} -> {}", usage.exe_path, text));
+    text
+}
@@ -0,0 +1,226 @@
+// ═══════════════════════════════════════════════════════════════
+//  拷贝校验 - BLAKE3 对比源目录与 U 盘上的副本
+// ═══════════════════════════════════════════════════════════════
+//
+// 大小一致不代表内容一致：劣质 U 盘 / 扩容盘写满后会静默丢数据。
+// 这里逐个读取源文件与目标文件计算 BLAKE3，多个文件并行处理
+// (线程数不超过 CPU 核数，且最多 4 个，避免 U 盘随机读拖慢整体)。
+// 刚拷完的文件还在系统缓存里，普通读取拿到的是内存中的副本而不是 U 盘上的数据；
+// 目标文件以 FILE_FLAG_NO_BUFFERING 打开，用页对齐的缓冲按整块读取，绕过缓存直读设备。
+
+use crate::i18n::{tr, trf};
+use crate::image_writer::{AlignedBuf, CHUNK};
+use std::io::Read;
+use std::os::windows::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use windows_sys::Win32::Storage::FileSystem::{FILE_FLAG_NO_BUFFERING, FILE_FLAG_SEQUENTIAL_SCAN};
+
+const MAX_WORKERS: usize = 4;
+
+#[derive(Clone, Debug, Default)]
+pub struct VerifyReport {
+    pub files: u64,
+    pub bytes: u64,
+    /// 目标中不存在的文件 (相对路径)
+    pub missing: Vec<String>,
+    /// 内容不一致的文件
+    pub mismatched: Vec<String>,
+    /// 无法读取的文件及原因
+    pub errors: Vec<String>,
+}
+
+impl VerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.mismatched.is_empty() && self.errors.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        if self.is_ok() {
+            trf("{} 个文件 ({} MB) 全部一致", &[&self.files, &format!("{:.1}", self.bytes as f64 / 1024.0 / 1024.0)])
+        } else {
+            trf(
+                "{} 个不一致，{} 个缺失，{} 个无法读取 (共 {} 个文件)",
+                &[&self.mismatched.len(), &self.missing.len(), &self.errors.len(), &self.files],
+            )
+        }
+    }
+
+    /// 列表中显示的问题文件，每项带前缀说明
+    pub fn problems(&self) -> impl Iterator<Item = String> + '_ {
+        self.mismatched
+            .iter()
+            .map(|p| format!("≠ {}", p))
+            .chain(self.missing.iter().map(|p| format!("✗ {}", p)))
+            .chain(self.errors.iter().map(|p| format!("⚠ {}", p)))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum VerifyEvent {
+    Progress { done_bytes: u64, total_bytes: u64 },
+    Finished(Result<VerifyReport, String>),
+}
+
+/// 正在进行的校验 (UI 侧)
+pub struct VerifyState {
+    pub done_bytes: u64,
+    pub total_bytes: u64,
+    pub cancel: Arc<AtomicBool>,
+}
+
+impl VerifyState {
+    pub fn ratio(&self) -> f32 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            (self.done_bytes as f64 / self.total_bytes as f64).min(1.0) as f32
+        }
+    }
+}
+
+pub fn hash_file(path: &Path) -> std::io::Result<blake3::Hash> {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update_reader(std::fs::File::open(path)?)?;
+    Ok(hasher.finalize())
+}
+
+/// 绕过系统缓存读取文件计算哈希；无缓冲读取要求缓冲区与读取长度按扇区对齐
+fn hash_file_uncached(path: &Path) -> std::io::Result<blake3::Hash> {
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(FILE_FLAG_NO_BUFFERING | FILE_FLAG_SEQUENTIAL_SCAN)
+        .open(path)?;
+    let mut buf = AlignedBuf::new();
+    let mut hasher = blake3::Hasher::new();
+    loop {
+        let block = buf.get(CHUNK);
+        let n = file.read(block)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&block[..n]);
+    }
+    Ok(hasher.finalize())
+}
+
+/// 源目录下的全部文件 (相对路径, 大小)
+fn list_files(root: &Path) -> Vec<(PathBuf, u64)> {
+    let mut out = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(meta) = entry.metadata() else { continue };
+            if meta.is_dir() {
+                stack.push(path);
+            } else if let Ok(rel) = path.strip_prefix(root) {
+                out.push((rel.to_path_buf(), meta.len()));
+            }
+        }
+    }
+    out.sort();
+    out
+}
+
+/// 对比两个目录：以源为准，目标中多出的文件不计
+pub fn verify_trees(
+    source: &Path,
+    dest: &Path,
+    cancel: &AtomicBool,
+    progress: &(dyn Fn(u64, u64) + Sync),
+) -> Result<VerifyReport, String> {
+    if !source.is_dir() {
+        return Err(trf("源文件夹不存在：{}", &[&source.display()]));
+    }
+    if !dest.is_dir() {
+        return Err(trf("目标文件夹不存在：{}", &[&dest.display()]));
+    }
+    let files = list_files(source);
+    let total_bytes: u64 = files.iter().map(|(_, len)| len).sum();
+    let next = AtomicUsize::new(0);
+    let done_bytes = AtomicU64::new(0);
+    let report = Mutex::new(VerifyReport {
+        files: files.len() as u64,
+        bytes: total_bytes,
+        ..Default::default()
+    });
+
+    let workers = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1).clamp(1, MAX_WORKERS);
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                if cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some((rel, len)) = files.get(i) else { break };
+                let name = rel.to_string_lossy().to_string();
+                let target = dest.join(rel);
+                let outcome = if !target.is_file() {
+                    Err(None)
+                } else {
+                    match (hash_file(&source.join(rel)), hash_file_uncached(&target)) {
+                        (Ok(a), Ok(b)) => Ok(a == b),
+                        (Err(e), _) | (_, Err(e)) => Err(Some(e.to_string())),
+                    }
+                };
+                if let Ok(mut r) = report.lock() {
+                    match outcome {
+                        Ok(true) => {}
+                        Ok(false) => r.mismatched.push(name),
+                        Err(None) => r.missing.push(name),
+                        Err(Some(e)) => r.errors.push(format!("{}：{}", name, e)),
+                    }
+                }
+                let done = done_bytes.fetch_add(*len, Ordering::Relaxed) + len;
+                progress(done, total_bytes);
+            });
+        }
+    });
+
+    if cancel.load(Ordering::Relaxed) {
+        return Err(tr("已取消").to_string());
+    }
+    let mut report = report.into_inner().map_err(|_| tr("校验线程异常退出").to_string())?;
+    report.mismatched.sort();
+    report.missing.sort();
+    report.errors.sort();
+    Ok(report)
+}
+
+/// 在后台线程执行校验，进度通过 tx 以 (盘符, 事件) 发回
+pub fn spawn(
+    drive: String,
+    source: PathBuf,
+    dest: PathBuf,
+    tx: mpsc::Sender<(String, VerifyEvent)>,
+    cancel: Arc<AtomicBool>,
+    ctx: eframe::egui::Context,
+) {
+    std::thread::spawn(move || {
+        // 进度最多每 1% 发一次，避免刷爆 channel
+        let last_percent = AtomicU64::new(u64::MAX);
+        let progress = |done: u64, total: u64| {
+            let percent = (done * 100).checked_div(total).unwrap_or(100);
+            if last_percent.swap(percent, Ordering::Relaxed) != percent {
+                let _ = tx.send((drive.clone(), VerifyEvent::Progress { done_bytes: done, total_bytes: total }));
+                ctx.request_repaint();
+            }
+        };
+        let result = verify_trees(&source, &dest, &cancel, &progress);
+        crate::audit::record(
+            "拷贝校验",
+            &format!(
+                "{} <-> {}: {}",
+                source.display(),
+                dest.display(),
+                result.as_ref().map(|r| r.summary()).unwrap_or_else(|e| e.clone())
+            ),
+        );
+        let _ = tx.send((drive, VerifyEvent::Finished(result)));
+        ctx.request_repaint();
+    });
+}
@@ -0,0 +1,84 @@
+// ═══════════════════════════════════════════════════════════════
+//  清场确认 - 强力清场前列出将被终止的进程，并留 5 秒撤销窗口
+// ═══════════════════════════════════════════════════════════════
+//
+// 强力清场一旦发出就会强杀全部占用进程，误点会丢掉未保存的数据。
+//   1. 确认：列出将被终止的进程，用户确认后进入倒计时
+//   2. 倒计时：先挂起这些进程 (不再继续写盘)，期间可点“撤销”全部恢复
+//   3. 倒计时结束：先恢复再交给 ForceEject —— 清场流程会先向文档程序发送
+//      WM_CLOSE 触发保存提示，挂起的进程无法响应
+// 挂起失败 (权限不足等) 的进程照常参与清场，只是倒计时期间不会被冻结。
+// 系统 / 服务 / 显示关键进程 (dwm、csrss 等) 不挂起，冻结它们会让整个桌面停止响应。
+// 访客模式的检查由调用方在确认时完成，先于挂起。
+
+use std::time::{Duration, Instant};
+
+/// 撤销窗口
+pub const UNDO_WINDOW: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stage {
+    Confirm,
+    Countdown(Instant),
+}
+
+pub struct PendingClear {
+    pub drive: String,
+    /// (PID, 进程名)
+    pub targets: Vec<(u32, String)>,
+    /// 确认框中每个进程的说明
+    pub summary: Vec<String>,
+    pub stage: Stage,
+    suspended: Vec<(u32, String)>,
+}
+
+impl PendingClear {
+    pub fn new(drive: &str, targets: Vec<(u32, String)>, summary: Vec<String>) -> Self {
+        Self {
+            drive: drive.to_string(),
+            targets,
+            summary,
+            stage: Stage::Confirm,
+            suspended: Vec::new(),
+        }
+    }
+
+    /// 用户确认：挂起目标 (受保护的进程除外) 并开始倒计时
+    pub fn begin_countdown(&mut self) {
+        let own_pid = std::process::id();
+        self.suspended = self
+            .targets
+            .iter()
+            .filter(|(pid, name)| *pid != own_pid && !crate::is_protected_process(*pid, name))
+            .filter(|(pid, name)| crate::window_picker::suspend(*pid, name).is_ok())
+            .cloned()
+            .collect();
+        self.stage = Stage::Countdown(Instant::now());
+    }
+
+    /// 倒计时剩余时间；未进入倒计时返回 None
+    pub fn remaining(&self) -> Option<Duration> {
+        match self.stage {
+            Stage::Confirm => None,
+            Stage::Countdown(start) => Some(UNDO_WINDOW.saturating_sub(start.elapsed())),
+        }
+    }
+
+    pub fn suspended_count(&self) -> usize {
+        self.suspended.len()
+    }
+
+    /// 恢复挂起的进程 (撤销，或倒计时结束交给清场流程前)
+    pub fn release(&mut self) {
+        for (pid, name) in self.suspended.drain(..) {
+            let _ = crate::window_picker::resume(pid, &name);
+        }
+    }
+}
+
+impl Drop for PendingClear {
+    /// 窗口关闭或流程被替换时，不能把进程留在挂起状态
+    fn drop(&mut self) {
+        self.release();
+    }
+}
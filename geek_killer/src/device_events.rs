@@ -0,0 +1,68 @@
+// ═══════════════════════════════════════════════════════════════
+//  设备插拔通知 - CM_Register_Notification (卷设备接口)
+// ═══════════════════════════════════════════════════════════════
+//
+// 订阅 GUID_DEVINTERFACE_VOLUME 的到达 / 移除事件，唤醒 monitor_worker
+// 立即刷新磁盘列表，而不是等下一次 500ms~2s 的轮询。
+// 回调运行在系统线程池中，只做一次 channel 发送，不做任何耗时操作。
+
+use crate::i18n::trf;
+use std::sync::mpsc;
+use windows_sys::Win32::Devices::DeviceAndDriverInstallation::{
+    CM_Register_Notification, CM_NOTIFY_ACTION, CM_NOTIFY_ACTION_DEVICEINTERFACEARRIVAL,
+    CM_NOTIFY_ACTION_DEVICEINTERFACEREMOVAL, CM_NOTIFY_EVENT_DATA, CM_NOTIFY_FILTER,
+    CM_NOTIFY_FILTER_0, CM_NOTIFY_FILTER_0_2, CM_NOTIFY_FILTER_TYPE_DEVICEINTERFACE, CR_SUCCESS,
+    HCMNOTIFICATION,
+};
+use windows_sys::Win32::System::Ioctl::GUID_DEVINTERFACE_VOLUME;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeviceEvent {
+    VolumeArrived,
+    VolumeRemoved,
+}
+
+unsafe extern "system" fn on_notify(
+    _notify: HCMNOTIFICATION,
+    context: *const std::ffi::c_void,
+    action: CM_NOTIFY_ACTION,
+    _data: *const CM_NOTIFY_EVENT_DATA,
+    _size: u32,
+) -> u32 {
+    let event = match action {
+        CM_NOTIFY_ACTION_DEVICEINTERFACEARRIVAL => DeviceEvent::VolumeArrived,
+        CM_NOTIFY_ACTION_DEVICEINTERFACEREMOVAL => DeviceEvent::VolumeRemoved,
+        _ => return 0,
+    };
+    let tx = &*(context as *const mpsc::Sender<DeviceEvent>);
+    let _ = tx.send(event);
+    0
+}
+
+/// 开始监听卷设备插拔；注册在进程生命周期内一直有效
+pub fn subscribe(tx: mpsc::Sender<DeviceEvent>) -> Result<(), String> {
+    let filter = CM_NOTIFY_FILTER {
+        cbSize: std::mem::size_of::<CM_NOTIFY_FILTER>() as u32,
+        Flags: 0,
+        FilterType: CM_NOTIFY_FILTER_TYPE_DEVICEINTERFACE,
+        Reserved: 0,
+        u: CM_NOTIFY_FILTER_0 {
+            DeviceInterface: CM_NOTIFY_FILTER_0_2 {
+                ClassGuid: GUID_DEVINTERFACE_VOLUME,
+            },
+        },
+    };
+    // 回调上下文与通知句柄都随进程存活，故意泄漏
+    let context = Box::into_raw(Box::new(tx));
+    let mut handle: HCMNOTIFICATION = 0;
+    let rc = unsafe {
+        CM_Register_Notification(&filter, context as *const _, Some(on_notify), &mut handle)
+    };
+    if rc != CR_SUCCESS {
+        unsafe {
+            drop(Box::from_raw(context));
+        }
+        return Err(trf("CM_Register_Notification 失败 (CONFIGRET {})", &[&rc]));
+    }
+    Ok(())
+}
@@ -0,0 +1,101 @@
+// ═══════════════════════════════════════════════════════════════
+//  设备偏好 - 按物理设备 (序列号) 记住盘符、弹出策略与昵称
+// ═══════════════════════════════════════════════════════════════
+//
+// 保存在 %APPDATA%\GeekKiller\device_prefs.json，键为 usb_info::device_key。
+// 设备再次插入时由 UI 线程自动应用。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DevicePrefs {
+    /// 昵称，替代卷名显示
+    pub nickname: String,
+    /// 显示在昵称前的表情 / 符号 (为空时用总线图标)
+    pub emoji: String,
+    /// 盘符行的标识颜色 (sRGB)，None 用总线配色
+    pub color: Option<[u8; 3]>,
+    /// 插入后自动改用的盘符
+    pub preferred_letter: Option<char>,
+    /// 清场前是否先请求文档程序保存 (None 跟随全局设置)
+    pub graceful_close: Option<bool>,
+    /// 等待文档程序自行退出的秒数 (None 跟随全局设置)
+    pub graceful_wait_secs: Option<u64>,
+    /// 无读写持续多少分钟后自动弹出 (None 不自动弹出)
+    pub auto_eject_idle_mins: Option<u64>,
+    /// 插入后自动镜像备份的任务 (None 不备份)
+    pub backup: Option<crate::backup::BackupJob>,
+}
+
+impl DevicePrefs {
+    pub fn is_empty(&self) -> bool {
+        *self == DevicePrefs::default()
+    }
+
+    /// 列表与提示中的显示名："🔐 公司加密盘"；没有昵称时返回 None
+    pub fn label(&self) -> Option<String> {
+        let nickname = self.nickname.trim();
+        if nickname.is_empty() {
+            return None;
+        }
+        let emoji = self.emoji.trim();
+        Some(if emoji.is_empty() {
+            nickname.to_string()
+        } else {
+            format!("{} {}", emoji, nickname)
+        })
+    }
+
+    /// 在全局弹出策略上叠加本设备的设置
+    pub fn eject_policy(&self, global: &crate::EjectPolicy) -> crate::EjectPolicy {
+        crate::EjectPolicy {
+            graceful_close: self.graceful_close.unwrap_or(global.graceful_close),
+            graceful_wait: self
+                .graceful_wait_secs
+                .map(std::time::Duration::from_secs)
+                .unwrap_or(global.graceful_wait),
+            mode: global.mode,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PrefStore {
+    pub devices: HashMap<String, DevicePrefs>,
+}
+
+impl PrefStore {
+    fn path() -> Option<PathBuf> {
+        crate::app_data_dir().map(|d| d.join("device_prefs.json"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let (Some(p), Ok(json)) = (Self::path(), serde_json::to_string_pretty(self)) {
+            let _ = std::fs::write(p, json);
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&DevicePrefs> {
+        self.devices.get(key)
+    }
+
+    /// 更新并立即保存；空偏好直接删除条目
+    pub fn set(&mut self, key: &str, prefs: DevicePrefs) {
+        if prefs.is_empty() {
+            self.devices.remove(key);
+        } else {
+            self.devices.insert(key.to_string(), prefs);
+        }
+        self.save();
+    }
+}
@@ -0,0 +1,111 @@
+// ═══════════════════════════════════════════════════════════════
+//  弹出历史 - 每次弹出尝试一条记录 (JSONL)
+// ═══════════════════════════════════════════════════════════════
+//
+// 保存在 %APPDATA%\GeekKiller\eject_history.jsonl，只追加不改写。
+// 公用电脑上强力清场杀掉了谁、是哪次弹出，都能查到。
+// 卷序列号在弹出前读取，卷卸载后就查不到了。
+
+use crate::eject_stats::EjectMethod;
+use crate::long_path::wide;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+use windows_sys::Win32::Storage::FileSystem::GetVolumeInformationW;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KilledProcess {
+    pub pid: u32,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EjectRecord {
+    pub time: String,
+    pub drive: String,
+    /// 卷序列号 "1A2B-3C4D"
+    #[serde(default)]
+    pub volume_serial: Option<String>,
+    /// 物理设备标识 (usb_info::device_key)
+    #[serde(default)]
+    pub device: Option<String>,
+    pub method: EjectMethod,
+    #[serde(default)]
+    pub killed: Vec<KilledProcess>,
+    pub ok: bool,
+    pub message: String,
+}
+
+/// 一次弹出操作开始时采集的卷信息
+#[derive(Clone, Debug, Default)]
+pub struct Target {
+    pub drive: String,
+    pub volume_serial: Option<String>,
+    pub device: Option<String>,
+}
+
+impl Target {
+    pub fn capture(drive: &str) -> Self {
+        Self {
+            drive: crate::mount_manager::display_name(drive),
+            volume_serial: volume_serial(drive),
+            device: crate::usb_info::device_key(drive),
+        }
+    }
+
+    pub fn record(&self, method: EjectMethod, killed: &[KilledProcess], ok: bool, message: &str) {
+        append(&EjectRecord {
+            time: crate::local_timestamp(),
+            drive: self.drive.clone(),
+            volume_serial: self.volume_serial.clone(),
+            device: self.device.clone(),
+            method,
+            killed: killed.to_vec(),
+            ok,
+            message: message.to_string(),
+        });
+    }
+}
+
+pub fn log_path() -> Option<PathBuf> {
+    crate::app_data_dir().map(|d| d.join("eject_history.jsonl"))
+}
+
+pub fn volume_serial(drive: &str) -> Option<String> {
+    let mut serial = 0u32;
+    let ok = unsafe {
+        GetVolumeInformationW(
+            wide(&crate::mount_manager::volume_root(drive)).as_ptr(),
+            std::ptr::null_mut(),
+            0,
+            &mut serial,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    (ok != 0).then(|| format!("{:04X}-{:04X}", serial >> 16, serial & 0xFFFF))
+}
+
+pub fn append(record: &EjectRecord) {
+    let (Some(path), Ok(line)) = (log_path(), serde_json::to_string(record)) else {
+        return;
+    };
+    if let Ok(mut f) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
+        let _ = writeln!(f, "{}", line);
+    }
+}
+
+/// 最近的若干条记录，新的在前；无法解析的行直接跳过
+pub fn load_recent(limit: usize) -> Vec<EjectRecord> {
+    let Some(content) = log_path().and_then(|p| std::fs::read_to_string(p).ok()) else {
+        return vec![];
+    };
+    content
+        .lines()
+        .rev()
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .take(limit)
+        .collect()
+}
@@ -0,0 +1,97 @@
+// ═══════════════════════════════════════════════════════════════
+//  弹出成功率统计 - 用数据决定默认的升级顺序
+// ═══════════════════════════════════════════════════════════════
+
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// 弹出阶梯上的各个方法
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EjectMethod {
+    Quick,      // CM_Request_Device_EjectW 直接弹出
+    RmShutdown, // RM 强制释放 + 终止进程后弹出
+    SmartEject, // Flush -> Lock -> Dismount -> 父设备弹出
+    Fsutil,     // fsutil volume dismount 辅助
+}
+
+impl EjectMethod {
+    pub const ALL: [EjectMethod; 4] = [
+        EjectMethod::Quick,
+        EjectMethod::RmShutdown,
+        EjectMethod::SmartEject,
+        EjectMethod::Fsutil,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EjectMethod::Quick => tr("极速弹出"),
+            EjectMethod::RmShutdown => tr("RM 强制释放"),
+            EjectMethod::SmartEject => tr("智能卸载弹出"),
+            EjectMethod::Fsutil => tr("fsutil 卸载"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct MethodStats {
+    pub attempts: u32,
+    pub successes: u32,
+}
+
+impl MethodStats {
+    pub fn success_rate(&self) -> f32 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.successes as f32 / self.attempts as f32
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EjectStats {
+    pub methods: HashMap<EjectMethod, MethodStats>,
+    /// 所有方法都失败、最终没能弹出的次数
+    pub unresolved: u32,
+}
+
+impl EjectStats {
+    fn path() -> Option<PathBuf> {
+        crate::app_data_dir().map(|d| d.join("eject_stats.json"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let (Some(p), Ok(json)) = (Self::path(), serde_json::to_string_pretty(self)) {
+            let _ = std::fs::write(p, json);
+        }
+    }
+
+    pub fn record(&mut self, method: EjectMethod, ok: bool) {
+        let entry = self.methods.entry(method).or_default();
+        entry.attempts += 1;
+        if ok {
+            entry.successes += 1;
+        }
+    }
+
+    pub fn record_unresolved(&mut self) {
+        self.unresolved += 1;
+    }
+
+    pub fn get(&self, method: EjectMethod) -> MethodStats {
+        self.methods.get(&method).copied().unwrap_or_default()
+    }
+
+    pub fn total_attempts(&self) -> u32 {
+        self.methods.values().map(|m| m.attempts).sum()
+    }
+}
@@ -0,0 +1,77 @@
+// ═══════════════════════════════════════════════════════════════
+//  提权预检 - 必然因权限失败的操作先提示，而不是执行后报通用错误
+// ═══════════════════════════════════════════════════════════════
+//
+// 非管理员时：
+//   - 终止进程：先用 OpenProcess(PROCESS_TERMINATE) 试探，打不开的 (服务、
+//     其他用户、更高完整性级别) 归为“需要提权”
+//   - 强力弹出：以读写方式打开卷 (\\.\E:) 失败时，卸载卷与 CM 弹出都会失败
+//   - 挂载点 / 只读 / 写保护 / fsutil：一律需要管理员
+// 提权方式：ShellExecuteW("runas") 以管理员身份重新启动本程序，成功后当前实例退出。
+
+use crate::i18n::trf;
+use crate::long_path::wide;
+use windows_sys::Win32::Foundation::{CloseHandle, GetLastError, ERROR_ACCESS_DENIED, INVALID_HANDLE_VALUE};
+use windows_sys::Win32::Storage::FileSystem::{CreateFileW, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING};
+use windows_sys::Win32::System::Threading::{OpenProcess, PROCESS_TERMINATE};
+use windows_sys::Win32::UI::Shell::ShellExecuteW;
+use windows_sys::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+/// 当前令牌下无法终止的进程 (拒绝访问)；已退出的不算
+pub fn denied_pids(pids: &[u32]) -> Vec<u32> {
+    pids.iter()
+        .copied()
+        .filter(|&pid| unsafe {
+            let process = OpenProcess(PROCESS_TERMINATE, 0, pid);
+            if process == 0 {
+                GetLastError() == ERROR_ACCESS_DENIED
+            } else {
+                CloseHandle(process);
+                false
+            }
+        })
+        .collect()
+}
+
+/// 能否以读写方式打开卷 (卸载卷、锁卷与按设备号弹出的前提)
+pub fn can_open_volume(drive: &str) -> bool {
+    let path = wide(&crate::mount_manager::device_path(drive));
+    unsafe {
+        let h = CreateFileW(
+            path.as_ptr(),
+            0x80000000 | 0x40000000, // GENERIC_READ | GENERIC_WRITE
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            std::ptr::null(),
+            OPEN_EXISTING,
+            0,
+            0,
+        );
+        if h == INVALID_HANDLE_VALUE {
+            return GetLastError() != ERROR_ACCESS_DENIED;
+        }
+        CloseHandle(h);
+        true
+    }
+}
+
+/// 以管理员身份重新启动本程序；用户在 UAC 中取消时返回 Err
+pub fn relaunch_as_admin() -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let args: Vec<String> = std::env::args()
+        .skip(1)
+        .map(|a| if a.contains(' ') { format!("\"{}\"", a) } else { a })
+        .collect();
+    let file = wide(&exe.to_string_lossy());
+    let params = wide(&args.join(" "));
+    let verb = wide("runas");
+    let rc = unsafe {
+        ShellExecuteW(0, verb.as_ptr(), file.as_ptr(), params.as_ptr(), std::ptr::null(), SW_SHOWNORMAL)
+    };
+    // ShellExecuteW 返回值大于 32 表示成功
+    if rc > 32 {
+        crate::audit::record("提权", &format!("以管理员身份重新启动 {}", exe.display()));
+        Ok(())
+    } else {
+        Err(trf("提权失败或已取消 (错误 {})", &[&rc]))
+    }
+}
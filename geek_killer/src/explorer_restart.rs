@@ -0,0 +1,96 @@
+// ═══════════════════════════════════════════════════════════════
+//  重启资源管理器 - 缩略图 / 压缩包预览等外壳扩展的隐形占用
+// ═══════════════════════════════════════════════════════════════
+//
+// 占用者只有 explorer.exe 时，关窗口没用：句柄握在 explorer 进程里的外壳扩展手中。
+//   1. 向任务栏窗口 (Shell_TrayWnd) 投递 0x5B4 —— “退出资源管理器”的内部消息，
+//      explorer 会保存状态后正常退出，且不会被 Winlogon 当作崩溃立即拉起
+//   2. 等待退出，超时的实例再强制终止
+//   3. 由调用方在 explorer 不在时完成弹出，最后重新启动 explorer.exe
+// 强杀时 Winlogon 的 AutoRestartShell 可能已自动拉起外壳，重新启动前先检查任务栏是否存在。
+// 以管理员运行时，结束前先保存外壳的用户令牌 (SavedShell)，用它重新启动，
+// 外壳不会继承本程序的管理员权限；取不到令牌时宁可不重启也不提权启动。
+
+use crate::i18n::trf;
+use crate::long_path::wide;
+use crate::unelevated::UserToken;
+use std::time::Duration;
+use windows_sys::Win32::UI::WindowsAndMessaging::{FindWindowW, PostMessageW};
+
+/// 任务栏窗口收到后让 explorer 正常退出
+const WM_EXIT_EXPLORER: u32 = 0x5B4;
+const EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn tray_window() -> isize {
+    unsafe { FindWindowW(wide("Shell_TrayWnd").as_ptr(), std::ptr::null()) }
+}
+
+pub fn is_explorer(pid: u32) -> bool {
+    crate::handles::process_image_name(pid)
+        .map(|p| p.to_lowercase().ends_with("\\explorer.exe"))
+        .unwrap_or(false)
+}
+
+/// 占用列表非空且全部是 explorer.exe
+pub fn only_explorer(pids: &[u32]) -> bool {
+    !pids.is_empty() && pids.iter().all(|&pid| is_explorer(pid))
+}
+
+/// 让 explorer 退出：先请求正常退出，超时后强制终止
+pub fn stop(pids: &[u32]) -> Result<(), String> {
+    let tray = tray_window();
+    if tray != 0 {
+        unsafe {
+            PostMessageW(tray, WM_EXIT_EXPLORER, 0, 0);
+        }
+    }
+    let mut forced = Vec::new();
+    for &pid in pids {
+        if !crate::graceful::wait_exit(pid, EXIT_TIMEOUT) {
+            let _ = rust_core_lib::process::kill(pid);
+            forced.push(pid);
+        }
+    }
+    let still_running: Vec<u32> = forced
+        .into_iter()
+        .filter(|&pid| !crate::graceful::wait_exit(pid, Duration::from_secs(1)))
+        .collect();
+    let result = if still_running.is_empty() {
+        Ok(())
+    } else {
+        Err(trf("资源管理器 (PID {}) 无法结束", &[&format!("{:?}", still_running)]))
+    };
+    crate::audit::record(
+        "重启资源管理器",
+        &format!("结束 PID {:?} -> {}", pids, result.as_ref().err().map(|e| e.as_str()).unwrap_or("已退出")),
+    );
+    result
+}
+
+/// 结束外壳前保存的用户令牌；未提权时为 None (直接启动即为普通权限)
+pub struct SavedShell(Option<Result<UserToken, String>>);
+
+impl SavedShell {
+    pub fn capture() -> Self {
+        Self(rust_core_lib::security::is_admin().then(crate::unelevated::capture_shell_token))
+    }
+}
+
+/// 用保存的令牌重新启动 explorer.exe；外壳已被自动拉起时什么也不做
+pub fn relaunch(saved: SavedShell) -> Result<(), String> {
+    if tray_window() != 0 {
+        return Ok(());
+    }
+    let explorer = crate::unelevated::explorer_path();
+    let result = match saved.0 {
+        None => crate::unelevated::spawn(&explorer, &[]),
+        Some(Ok(token)) => crate::unelevated::spawn_with_token(&token, &explorer),
+        Some(Err(e)) => Err(trf("未取得用户令牌 ({})，为避免外壳以管理员身份运行未自动重启，请在任务管理器中运行 explorer.exe", &[&e])),
+    }
+    .map_err(|e| trf("无法重新启动资源管理器：{}", &[&e]));
+    crate::audit::record(
+        "重启资源管理器",
+        &format!("启动 explorer.exe -> {}", result.as_ref().err().map(|e| e.as_str()).unwrap_or("成功")),
+    );
+    result
+}
@@ -0,0 +1,183 @@
+// ═══════════════════════════════════════════════════════════════
+//  资源管理器窗口 (IShellWindows) - 弹出前关掉停在目标盘上的窗口
+// ═══════════════════════════════════════════════════════════════
+//
+// 打开着 E:\ 的资源管理器窗口是最常见的“静默否决”来源，RM 也查不出来。
+// windows-sys 没有 COM 包装，这里按 vtable 槽位手动调用所需的几个方法。
+
+use std::ffi::c_void;
+use windows_sys::core::{GUID, HRESULT};
+use windows_sys::Win32::Foundation::{SysFreeString, SysStringLen};
+use windows_sys::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_ALL, COINIT_APARTMENTTHREADED,
+};
+
+const CLSID_SHELL_WINDOWS: GUID = GUID {
+    data1: 0x9ba05972,
+    data2: 0xf6a8,
+    data3: 0x11cf,
+    data4: [0xa4, 0x42, 0x00, 0xa0, 0xc9, 0x0a, 0x8f, 0x39],
+};
+const IID_ISHELL_WINDOWS: GUID = GUID {
+    data1: 0x85cb6900,
+    data2: 0x4d95,
+    data3: 0x11cf,
+    data4: [0x96, 0x0c, 0x00, 0x80, 0xc7, 0xf4, 0xee, 0x85],
+};
+const IID_IWEB_BROWSER2: GUID = GUID {
+    data1: 0xd30c1661,
+    data2: 0xcdaf,
+    data3: 0x11d0,
+    data4: [0x8a, 0x3e, 0x00, 0xc0, 0x4f, 0xc9, 0xe2, 0x6e],
+};
+
+// vtable 槽位 (IUnknown 0-2, IDispatch 3-6)
+const SLOT_QUERY_INTERFACE: usize = 0;
+const SLOT_RELEASE: usize = 2;
+const SLOT_SHELL_WINDOWS_COUNT: usize = 7;
+const SLOT_SHELL_WINDOWS_ITEM: usize = 8;
+const SLOT_BROWSER_LOCATION_URL: usize = 30;
+const SLOT_BROWSER_QUIT: usize = 32;
+
+const VT_I4: u16 = 3;
+
+/// 最小化的 VARIANT 布局 (只用到 VT_I4)
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Variant {
+    vt: u16,
+    reserved: [u16; 3],
+    value: [u64; 2],
+}
+
+type QueryInterfaceFn = unsafe extern "system" fn(*mut c_void, *const GUID, *mut *mut c_void) -> HRESULT;
+type ReleaseFn = unsafe extern "system" fn(*mut c_void) -> u32;
+type CountFn = unsafe extern "system" fn(*mut c_void, *mut i32) -> HRESULT;
+type ItemFn = unsafe extern "system" fn(*mut c_void, Variant, *mut *mut c_void) -> HRESULT;
+type LocationUrlFn = unsafe extern "system" fn(*mut c_void, *mut *mut u16) -> HRESULT;
+type QuitFn = unsafe extern "system" fn(*mut c_void) -> HRESULT;
+
+/// 取 COM 对象 vtable 中第 slot 个函数指针
+unsafe fn vfn<F: Copy>(obj: *mut c_void, slot: usize) -> F {
+    let vtbl = *(obj as *const *const usize);
+    let f = *vtbl.add(slot);
+    std::mem::transmute_copy(&f)
+}
+
+/// 自动 Release 的接口指针
+struct ComPtr(*mut c_void);
+impl Drop for ComPtr {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe {
+                vfn::<ReleaseFn>(self.0, SLOT_RELEASE)(self.0);
+            }
+        }
+    }
+}
+
+/// file:///E:/%E6%8A%A5%E5%91%8A -> E:\报告
+/// LocationURL 里也可能直接出现未转义的中文，逐字符处理，不按固定字节偏移切片
+fn url_to_path(url: &str) -> Option<String> {
+    let rest = url.strip_prefix("file:///")?;
+    let mut out = Vec::with_capacity(rest.len());
+    let mut chars = rest.char_indices();
+    while let Some((i, c)) = chars.next() {
+        let escaped = (c == '%')
+            .then_some(i)
+            .and_then(|i| rest.get(i + 1..i + 3))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(b) => {
+                out.push(b);
+                chars.next();
+                chars.next();
+            }
+            None => out.extend_from_slice(c.encode_utf8(&mut [0u8; 4]).as_bytes()),
+        }
+    }
+    Some(String::from_utf8_lossy(&out).replace('/', "\\"))
+}
+
+unsafe fn location_path(browser: &ComPtr) -> Option<String> {
+    let mut bstr: *mut u16 = std::ptr::null_mut();
+    if vfn::<LocationUrlFn>(browser.0, SLOT_BROWSER_LOCATION_URL)(browser.0, &mut bstr) < 0
+        || bstr.is_null()
+    {
+        return None;
+    }
+    let len = SysStringLen(bstr) as usize;
+    let url = String::from_utf16_lossy(std::slice::from_raw_parts(bstr, len));
+    SysFreeString(bstr);
+    url_to_path(&url)
+}
+
+/// 关闭当前目录位于目标盘上的资源管理器窗口，返回关闭的窗口数
+pub fn close_windows_on_drive(drive_letter: &str) -> usize {
+    let volume = crate::mount_manager::VolumeMatcher::new(drive_letter);
+    let mut closed = 0;
+
+    unsafe {
+        let init = CoInitializeEx(std::ptr::null(), COINIT_APARTMENTTHREADED as _);
+        {
+            let mut raw = std::ptr::null_mut();
+            if CoCreateInstance(
+                &CLSID_SHELL_WINDOWS,
+                std::ptr::null_mut(),
+                CLSCTX_ALL,
+                &IID_ISHELL_WINDOWS,
+                &mut raw,
+            ) >= 0
+            {
+                let windows = ComPtr(raw);
+                let mut count = 0i32;
+                vfn::<CountFn>(windows.0, SLOT_SHELL_WINDOWS_COUNT)(windows.0, &mut count);
+
+                // 倒序遍历：关闭窗口会让后面的索引前移
+                for i in (0..count).rev() {
+                    let index = Variant {
+                        vt: VT_I4,
+                        reserved: [0; 3],
+                        value: [i as u32 as u64, 0],
+                    };
+                    let mut disp = std::ptr::null_mut();
+                    if vfn::<ItemFn>(windows.0, SLOT_SHELL_WINDOWS_ITEM)(windows.0, index, &mut disp) < 0
+                        || disp.is_null()
+                    {
+                        continue;
+                    }
+                    let disp = ComPtr(disp);
+
+                    let mut browser = std::ptr::null_mut();
+                    if vfn::<QueryInterfaceFn>(disp.0, SLOT_QUERY_INTERFACE)(
+                        disp.0,
+                        &IID_IWEB_BROWSER2,
+                        &mut browser,
+                    ) < 0
+                        || browser.is_null()
+                    {
+                        continue;
+                    }
+                    let browser = ComPtr(browser);
+
+                    if let Some(path) = location_path(&browser) {
+                        if volume.contains(std::ffi::OsStr::new(&path)) {
+                            vfn::<QuitFn>(browser.0, SLOT_BROWSER_QUIT)(browser.0);
+                            closed += 1;
+                        }
+                    }
+                }
+            }
+        }
+        // S_OK / S_FALSE 都需要配对 CoUninitialize
+        if init >= 0 {
+            CoUninitialize();
+        }
+    }
+    closed
+}
+
+/// 在资源管理器中打开驱动器
+pub fn open_drive(mount_point: &str) {
+    crate::unelevated::open_in_explorer(mount_point);
+}
@@ -0,0 +1,40 @@
+// ═══════════════════════════════════════════════════════════════
+//  防火墙 - 按程序路径添加 / 移除出站阻止规则 (netsh advfirewall)
+// ═══════════════════════════════════════════════════════════════
+//
+// 同一用途的规则共用一个名称：netsh 按名称删除时会删掉全部同名规则，
+// 撤销时不必记住加过哪些。规则名不含空格，命令行不必再加引号。
+// 修改防火墙需要管理员权限；命令经 run_to_completion 执行并写入审计日志。
+
+use crate::tool_runner::{run_to_completion, ToolSpec};
+
+fn netsh(title: &str, args: &[&str]) -> Result<(), String> {
+    let mut all = vec!["advfirewall", "firewall"];
+    all.extend_from_slice(args);
+    let (ok, stdout, stderr) = run_to_completion(&ToolSpec::new(title, "netsh", &all))?;
+    if ok {
+        Ok(())
+    } else {
+        let text = if stderr.trim().is_empty() { stdout } else { stderr };
+        Err(text.trim().to_string())
+    }
+}
+
+/// 阻止 program (完整路径) 的全部出站连接
+pub fn block_program(rule: &str, program: &str) -> Result<(), String> {
+    let name = format!("name={}", rule);
+    let program = format!("program={}", program);
+    netsh(
+        "添加防火墙规则",
+        &["add", "rule", &name, "dir=out", "action=block", &program, "enable=yes"],
+    )
+}
+
+/// 删除名为 rule 的全部规则；本来就没有时也视为成功
+pub fn remove_rules(rule: &str) -> Result<(), String> {
+    let name = format!("name={}", rule);
+    match netsh("删除防火墙规则", &["delete", "rule", &name]) {
+        Err(e) if e.contains("No rules match") || e.contains("没有与指定标准相匹配的规则") => Ok(()),
+        other => other,
+    }
+}
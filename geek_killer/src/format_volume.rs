@@ -0,0 +1,191 @@
+// ═══════════════════════════════════════════════════════════════
+//  格式化与卷标 - 不打开资源管理器完成基本的 U 盘维护
+// ═══════════════════════════════════════════════════════════════
+//
+// 格式化交给 format.com (需要管理员)：
+//   format E: /FS:exFAT /V:标签 /Q /X /Y
+//   - /X 先强制卸载卷，/Q 快速格式化
+//   - 卷已有卷标时 format 会先要求输入当前卷标，连同 "Y" 一起从 stdin 喂给它
+//   - stdout 中的 "NN percent completed" / "已完成 NN%" 解析为进度
+// 改卷标直接调用 SetVolumeLabelW，不经过外部工具。
+
+use crate::i18n::trf;
+use crate::long_path::wide;
+use crate::tool_runner::{decode_console, ToolSpec};
+use std::io::{Read, Write};
+use std::process::Stdio;
+use windows_sys::Win32::Storage::FileSystem::SetVolumeLabelW;
+
+/// format.com 不允许把大于 32 GB 的卷格式化为 FAT32
+pub const FAT32_MAX_BYTES: u64 = 32 * 1024 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileSystem {
+    Fat32,
+    ExFat,
+    Ntfs,
+}
+
+impl FileSystem {
+    pub const ALL: [FileSystem; 3] = [FileSystem::Fat32, FileSystem::ExFat, FileSystem::Ntfs];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileSystem::Fat32 => "FAT32",
+            FileSystem::ExFat => "exFAT",
+            FileSystem::Ntfs => "NTFS",
+        }
+    }
+
+    /// 卷标最大长度 (FAT 系列 11 个字符，NTFS 32 个)
+    pub fn max_label_len(&self) -> usize {
+        match self {
+            FileSystem::Fat32 | FileSystem::ExFat => 11,
+            FileSystem::Ntfs => 32,
+        }
+    }
+
+    /// 该容量的卷能否用此文件系统格式化
+    pub fn fits(&self, total_bytes: u64) -> bool {
+        *self != FileSystem::Fat32 || total_bytes <= FAT32_MAX_BYTES
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormatJob {
+    pub fs: FileSystem,
+    pub label: String,
+    pub quick: bool,
+}
+
+impl Default for FormatJob {
+    fn default() -> Self {
+        Self {
+            fs: FileSystem::ExFat,
+            label: String::new(),
+            quick: true,
+        }
+    }
+}
+
+/// 卷标长度与非法字符检查
+fn check_label(label: &str, fs: FileSystem) -> Result<(), String> {
+    if label.chars().count() > fs.max_label_len() {
+        return Err(trf("{} 卷标最多 {} 个字符", &[&fs.label(), &fs.max_label_len()]));
+    }
+    if let Some(c) = label.chars().find(|c| "*?/\\|.,;:+=[]<>\"".contains(*c)) {
+        return Err(trf("卷标不能包含 '{}'", &[&c]));
+    }
+    Ok(())
+}
+
+pub fn spec(drive: &str, job: &FormatJob) -> ToolSpec {
+    let target = crate::mount_manager::display_name(drive);
+    let fs = format!("/FS:{}", job.fs.label());
+    let label = format!("/V:{}", job.label.trim());
+    let mut args = vec![target.as_str(), fs.as_str(), label.as_str(), "/X", "/Y"];
+    if job.quick {
+        args.push("/Q");
+    }
+    ToolSpec::new(&trf("格式化 {}", &[&target]), "format", &args)
+}
+
+/// 从一行输出中解析进度百分比
+fn parse_percent(line: &str) -> Option<u8> {
+    let digits_before = |idx: usize| {
+        let head = line[..idx].trim_end();
+        let start = head.rfind(|c: char| !c.is_ascii_digit()).map(|i| i + 1).unwrap_or(0);
+        head[start..].parse::<u8>().ok()
+    };
+    if let Some(idx) = line.find('%') {
+        return digits_before(idx);
+    }
+    line.find("percent").and_then(digits_before)
+}
+
+/// 运行 format.com，每解析到新的百分比回调一次；返回给用户看的结果
+pub fn format(drive: &str, job: &FormatJob, mut on_progress: impl FnMut(u8)) -> Result<String, String> {
+    let d = crate::mount_manager::display_name(drive);
+    let spec = spec(drive, job);
+    let result = (|| {
+        check_label(job.label.trim(), job.fs)?;
+        let current_label = crate::mount_manager::volume_label(&crate::mount_manager::volume_root(drive));
+
+        let mut child = spec
+            .command()
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| trf("无法启动 format：{}", &[&e]))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // 无卷标时第一行是空回答，format 会重新询问并读到下一行的 Y
+            let _ = write!(stdin, "{}\r\nY\r\n", current_label);
+        }
+
+        let mut output = String::new();
+        if let Some(mut stdout) = child.stdout.take() {
+            let mut pending: Vec<u8> = Vec::new();
+            let mut chunk = [0u8; 1024];
+            let mut last = None;
+            loop {
+                let n = match stdout.read(&mut chunk) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => n,
+                };
+                pending.extend_from_slice(&chunk[..n]);
+                // 进度行以 \r 原地刷新
+                while let Some(pos) = pending.iter().position(|&b| b == b'\r' || b == b'\n') {
+                    let line = decode_console(&pending[..pos]);
+                    pending.drain(..=pos);
+                    if let Some(p) = parse_percent(&line).filter(|p| Some(*p) != last) {
+                        last = Some(p);
+                        on_progress(p);
+                    }
+                    if !line.trim().is_empty() {
+                        output = line.trim().to_string();
+                    }
+                }
+            }
+        }
+        let mut stderr = Vec::new();
+        if let Some(mut e) = child.stderr.take() {
+            let _ = e.read_to_end(&mut stderr);
+        }
+        let status = child.wait().map_err(|e| e.to_string())?;
+        if status.success() {
+            Ok(trf("{} 已格式化为 {}", &[&d, &job.fs.label()]))
+        } else {
+            let err = decode_console(&stderr);
+            let err = if err.trim().is_empty() { output } else { err.trim().to_string() };
+            Err(trf("{} 格式化失败：{}", &[&d, &err]))
+        }
+    })();
+    crate::audit::record(
+        "格式化",
+        &format!("{} -> {}", spec.command_line(), result.as_ref().map(|s| s.as_str()).unwrap_or_else(|e| e.as_str())),
+    );
+    result
+}
+
+/// 修改卷标 (长度按 NTFS 上限检查，FAT 卷超长时由系统报错)
+pub fn relabel(drive: &str, label: &str) -> Result<String, String> {
+    let d = crate::mount_manager::display_name(drive);
+    let label = label.trim();
+    let result = check_label(label, FileSystem::Ntfs).and_then(|_| {
+        let root = wide(&crate::mount_manager::volume_root(drive));
+        let ok = unsafe { SetVolumeLabelW(root.as_ptr(), wide(label).as_ptr()) };
+        if ok == 0 {
+            Err(trf("{} 修改卷标失败：{}", &[&d, &std::io::Error::last_os_error()]))
+        } else if label.is_empty() {
+            Ok(trf("{} 已清除卷标", &[&d]))
+        } else {
+            Ok(trf("{} 卷标已改为 {}", &[&d, &label]))
+        }
+    });
+    crate::audit::record(
+        "卷标",
+        &format!("{} \"{}\" -> {}", d, label, result.as_ref().map(|s| s.as_str()).unwrap_or_else(|e| e.as_str())),
+    );
+    result
+}
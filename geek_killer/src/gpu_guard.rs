@@ -0,0 +1,78 @@
+// ═══════════════════════════════════════════════════════════════
+//  显示保护 - 禁止终止桌面合成 / 显卡驱动进程，改为提供“重启显卡驱动”
+// ═══════════════════════════════════════════════════════════════
+//
+// 画面卡死时用户的第一反应往往是结束 dwm.exe 或显卡驱动的服务进程：
+// dwm 会被系统重新拉起但期间黑屏闪烁，驱动服务被杀后控制面板 / 热键失效，
+// 都解决不了卡死本身。正确做法是 Win+Ctrl+Shift+B：由 win32k 通知显卡内核驱动
+// 重置 (TDR 同款流程)，屏幕闪一下、窗口与程序都保留。
+// 这里用 SendInput 模拟该组合键；注入的按键同样会被系统热键处理识别。
+
+use crate::i18n::trf;
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+    SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP, VIRTUAL_KEY, VK_CONTROL, VK_LWIN,
+    VK_SHIFT,
+};
+
+/// 显示相关的关键进程 (小写，含 .exe)
+const DISPLAY_CRITICAL: &[&str] = &[
+    "dwm.exe",
+    // NVIDIA
+    "nvdisplay.container.exe",
+    // AMD
+    "atiesrxx.exe",
+    "atieclxx.exe",
+    // Intel
+    "igfxem.exe",
+    "igfxcuiservice.exe",
+    "intelcphdcpsvc.exe",
+];
+
+/// 进程名 (不区分大小写) 是否属于桌面合成或显卡驱动
+pub fn is_display_critical(name: &str) -> bool {
+    let name = name.to_lowercase();
+    DISPLAY_CRITICAL.contains(&name.as_str())
+}
+
+fn key(vk: VIRTUAL_KEY, up: bool) -> INPUT {
+    INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: vk,
+                wScan: 0,
+                dwFlags: if up { KEYEVENTF_KEYUP } else { 0 },
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    }
+}
+
+/// 模拟 Win+Ctrl+Shift+B 重置显卡驱动
+pub fn restart_graphics_driver() -> Result<(), String> {
+    let keys = [VK_LWIN, VK_CONTROL, VK_SHIFT, b'B' as VIRTUAL_KEY];
+    let inputs: Vec<INPUT> = keys
+        .iter()
+        .map(|&vk| key(vk, false))
+        .chain(keys.iter().rev().map(|&vk| key(vk, true)))
+        .collect();
+    let sent = unsafe { SendInput(inputs.len() as u32, inputs.as_ptr(), std::mem::size_of::<INPUT>() as i32) };
+    let result = if sent as usize == inputs.len() {
+        Ok(())
+    } else {
+        // 通常是前台为更高完整性级别的窗口 (UIPI 拦截注入)
+        Err(trf("按键注入被拦截 ({}/{})", &[&sent, &inputs.len()]))
+    };
+    crate::audit::record(
+        "重启显卡驱动",
+        &format!(
+            "Win+Ctrl+Shift+B -> {}",
+            match &result {
+                Ok(()) => "已发送".to_string(),
+                Err(e) => e.clone(),
+            }
+        ),
+    );
+    result
+}
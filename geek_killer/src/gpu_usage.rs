@@ -0,0 +1,100 @@
+// ═══════════════════════════════════════════════════════════════
+//  进程 GPU 占用 - PDH "\GPU Engine(*)\Utilization Percentage"
+// ═══════════════════════════════════════════════════════════════
+//
+// 每个实例对应“某进程在某个 GPU 引擎上”的占用，实例名形如
+//   pid_1234_luid_0x00000000_0x0000D1B5_phys_0_eng_0_engtype_3D
+// 与任务管理器的口径一致：同一进程按引擎类型 (3D / Copy / VideoDecode …) 分别求和，
+// 取其中最大的一类作为该进程的 GPU 占用。通配符实例每次采集时重新展开，
+// 新启动的进程下一轮即可出现。采集一次要遍历全部引擎实例，只在显示 GPU 列时调用。
+
+use crate::long_path::wide;
+use std::collections::HashMap;
+use windows_sys::Win32::System::Performance::{
+    PdhAddEnglishCounterW, PdhCloseQuery, PdhCollectQueryData, PdhGetFormattedCounterArrayW, PdhOpenQueryW,
+    PDH_FMT_COUNTERVALUE_ITEM_W, PDH_FMT_DOUBLE,
+};
+
+/// 缓冲区不足时 PdhGetFormattedCounterArrayW 的返回值
+const PDH_MORE_DATA: u32 = 0x800007D2;
+
+/// 从实例名中取出 (pid, 引擎类型)
+fn parse_instance(name: &str) -> Option<(u32, &str)> {
+    let rest = name.strip_prefix("pid_")?;
+    let pid = rest.split('_').next()?.parse().ok()?;
+    let engine = name.rsplit_once("engtype_").map(|(_, t)| t).unwrap_or("");
+    Some((pid, engine))
+}
+
+pub struct Sampler {
+    query: isize,
+    counter: isize,
+}
+
+impl Sampler {
+    /// 系统没有 GPU 计数器 (旧驱动 / 虚拟机) 时为 None
+    pub fn new() -> Option<Self> {
+        let path = wide("\\GPU Engine(*)\\Utilization Percentage");
+        unsafe {
+            let mut query = 0isize;
+            if PdhOpenQueryW(std::ptr::null(), 0, &mut query) != 0 {
+                return None;
+            }
+            let mut counter = 0isize;
+            if PdhAddEnglishCounterW(query, path.as_ptr(), 0, &mut counter) != 0 {
+                PdhCloseQuery(query);
+                return None;
+            }
+            // 占用率需要两次采样，先收集一次作为基准
+            PdhCollectQueryData(query);
+            Some(Self { query, counter })
+        }
+    }
+
+    /// 采样一次，返回各进程的 GPU 占用 (%)
+    pub fn sample(&mut self) -> HashMap<u32, f32> {
+        let mut per_engine: HashMap<(u32, String), f64> = HashMap::new();
+        unsafe {
+            if PdhCollectQueryData(self.query) != 0 {
+                return HashMap::new();
+            }
+            let mut size = 0u32;
+            let mut count = 0u32;
+            let status =
+                PdhGetFormattedCounterArrayW(self.counter, PDH_FMT_DOUBLE, &mut size, &mut count, std::ptr::null_mut());
+            if status != PDH_MORE_DATA || size == 0 {
+                return HashMap::new();
+            }
+            // 缓冲区里先是条目数组，后面紧跟实例名字符串；按 8 字节对齐分配
+            let mut buf = vec![0u64; (size as usize).div_ceil(8)];
+            let items = buf.as_mut_ptr() as *mut PDH_FMT_COUNTERVALUE_ITEM_W;
+            if PdhGetFormattedCounterArrayW(self.counter, PDH_FMT_DOUBLE, &mut size, &mut count, items) != 0 {
+                return HashMap::new();
+            }
+            for item in std::slice::from_raw_parts(items, count as usize) {
+                if item.szName.is_null() || item.FmtValue.CStatus != 0 {
+                    continue;
+                }
+                let len = (0..).take_while(|&i| *item.szName.add(i) != 0).count();
+                let name = String::from_utf16_lossy(std::slice::from_raw_parts(item.szName, len));
+                if let Some((pid, engine)) = parse_instance(&name) {
+                    *per_engine.entry((pid, engine.to_string())).or_default() += item.FmtValue.Anonymous.doubleValue;
+                }
+            }
+        }
+        let mut usage: HashMap<u32, f32> = HashMap::new();
+        for ((pid, _), value) in per_engine {
+            let v = usage.entry(pid).or_default();
+            *v = v.max(value.min(100.0) as f32);
+        }
+        usage
+    }
+}
+
+impl Drop for Sampler {
+    fn drop(&mut self) {
+        unsafe {
+            PdhCloseQuery(self.query);
+        }
+    }
+}
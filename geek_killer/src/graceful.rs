@@ -0,0 +1,80 @@
+// ═══════════════════════════════════════════════════════════════
+//  先礼后兵 (Graceful Close) - 让文档编辑器有机会保存
+// ═══════════════════════════════════════════════════════════════
+//
+// 强杀 Word/记事本 会直接丢掉未保存的内容。对这类进程先向其顶层窗口
+// 投递 WM_CLOSE，由程序自己弹出“是否保存”，用户处理完后再继续弹出流程。
+
+use std::time::Duration;
+use windows_sys::Win32::Foundation::{CloseHandle, BOOL, HWND, LPARAM, WAIT_OBJECT_0};
+use windows_sys::Win32::System::Threading::{OpenProcess, WaitForSingleObject, PROCESS_SYNCHRONIZE};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    EnumWindows, GetWindow, GetWindowThreadProcessId, IsWindowVisible, PostMessageW, GW_OWNER,
+    WM_CLOSE,
+};
+
+/// 已知会弹出保存提示的文档编辑器 (小写 exe 名)
+const DOCUMENT_EDITORS: &[&str] = &[
+    "winword.exe",
+    "excel.exe",
+    "powerpnt.exe",
+    "wps.exe",
+    "et.exe",
+    "wpp.exe",
+    "notepad.exe",
+    "notepad++.exe",
+    "wordpad.exe",
+    "write.exe",
+    "soffice.bin",
+    "acrobat.exe",
+];
+
+pub fn is_document_editor(exe_name: &str) -> bool {
+    let lower = exe_name.to_lowercase();
+    DOCUMENT_EDITORS.iter().any(|e| *e == lower)
+}
+
+struct FindCtx {
+    pid: u32,
+    windows: Vec<HWND>,
+}
+
+unsafe extern "system" fn collect_windows(hwnd: HWND, lparam: LPARAM) -> BOOL {
+    let ctx = &mut *(lparam as *mut FindCtx);
+    let mut owner_pid = 0u32;
+    GetWindowThreadProcessId(hwnd, &mut owner_pid);
+    // 只关心可见的顶层主窗口，工具窗/子窗口交给主窗口处理
+    if owner_pid == ctx.pid && IsWindowVisible(hwnd) != 0 && GetWindow(hwnd, GW_OWNER) == 0 {
+        ctx.windows.push(hwnd);
+    }
+    1
+}
+
+/// 向进程的所有顶层窗口投递 WM_CLOSE，返回是否找到了窗口
+pub fn request_close(pid: u32) -> bool {
+    let mut ctx = FindCtx {
+        pid,
+        windows: Vec::new(),
+    };
+    unsafe {
+        EnumWindows(Some(collect_windows), &mut ctx as *mut FindCtx as LPARAM);
+        for hwnd in &ctx.windows {
+            PostMessageW(*hwnd, WM_CLOSE, 0, 0);
+        }
+    }
+    !ctx.windows.is_empty()
+}
+
+/// 等待进程自行退出，超时返回 false
+pub fn wait_exit(pid: u32, timeout: Duration) -> bool {
+    unsafe {
+        let process = OpenProcess(PROCESS_SYNCHRONIZE, 0, pid);
+        if process == 0 {
+            // 打不开多半是已经退出了
+            return true;
+        }
+        let rc = WaitForSingleObject(process, timeout.as_millis() as u32);
+        CloseHandle(process);
+        rc == WAIT_OBJECT_0
+    }
+}
@@ -0,0 +1,116 @@
+// ═══════════════════════════════════════════════════════════════
+//  访客模式 - 借出电脑期间禁用破坏性操作、锁定设置
+// ═══════════════════════════════════════════════════════════════
+//
+// 开启时设定时长与解锁密码，状态写入 %APPDATA%\GeekKiller\guest_mode.json，
+// 重启程序不会解除。期间：
+//   - 终止 / 强制弹出 / 格式化 / 挂载修改等命令在分发层 (send_usb_cmd 与 USB 线程)
+//     一律拒绝，托盘、快捷键、看门狗与操作宏发来的命令同样被挡下
+//   - 外部工具、一键修复、卸载不可用
+//   - 设置不再写入磁盘 (AppSettings::save 直接返回)
+// 到期自动结束，或输入密码提前结束。密码只保存加盐的 BLAKE3 摘要。
+// 普通弹出 (有占用时只报告、不终止) 与测速仍然可用，访客可以安全拔出自己的 U 盘。
+
+use crate::i18n::{tr, trf};
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const STATE_FILE: &str = "guest_mode.json";
+/// 可设定的最长时长 (小时)
+pub const MAX_HOURS: u32 = 72;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Lock {
+    /// 到期时间，Unix 秒
+    until: u64,
+    salt: String,
+    hash: String,
+}
+
+static STATE: OnceLock<Mutex<Option<Lock>>> = OnceLock::new();
+
+fn state() -> &'static Mutex<Option<Lock>> {
+    STATE.get_or_init(|| Mutex::new(load()))
+}
+
+fn path() -> Option<std::path::PathBuf> {
+    crate::app_data_dir().map(|d| d.join(STATE_FILE))
+}
+
+fn load() -> Option<Lock> {
+    path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn digest(salt: &str, password: &str) -> String {
+    blake3::hash(format!("{}:{}", salt, password).as_bytes()).to_hex().to_string()
+}
+
+fn clear(lock: &mut Option<Lock>, reason: &str) {
+    *lock = None;
+    if let Some(p) = path() {
+        let _ = std::fs::remove_file(p);
+    }
+    crate::audit::record("访客模式", reason);
+}
+
+/// 开启访客模式，hours 小时后自动结束
+pub fn start(hours: u32, password: &str) -> Result<(), String> {
+    if password.is_empty() {
+        return Err(tr("请设置解锁密码").to_string());
+    }
+    if !(1..=MAX_HOURS).contains(&hours) {
+        return Err(trf("时长需在 1 到 {} 小时之间", &[&MAX_HOURS]));
+    }
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0);
+    let salt = format!("{:x}{:x}", nanos, std::process::id());
+    let lock = Lock {
+        until: now_secs() + hours as u64 * 3600,
+        hash: digest(&salt, password),
+        salt,
+    };
+    let path = path().ok_or(tr("无法定位数据目录"))?;
+    let json = serde_json::to_string_pretty(&lock).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| trf("无法保存状态：{}", &[&e]))?;
+    if let Ok(mut state) = state().lock() {
+        *state = Some(lock);
+    }
+    crate::audit::record("访客模式", &format!("开启 {} 小时", hours));
+    Ok(())
+}
+
+/// 剩余时长；未开启时为 None，到期时顺带结束
+pub fn remaining() -> Option<Duration> {
+    let mut state = state().lock().ok()?;
+    let until = state.as_ref()?.until;
+    let now = now_secs();
+    if now >= until {
+        clear(&mut state, "到期自动结束");
+        return None;
+    }
+    Some(Duration::from_secs(until - now))
+}
+
+pub fn is_active() -> bool {
+    remaining().is_some()
+}
+
+/// 输入密码提前结束
+pub fn unlock(password: &str) -> Result<(), String> {
+    let mut state = state().lock().map_err(|_| tr("状态不可用").to_string())?;
+    let Some(lock) = state.as_ref() else {
+        return Ok(());
+    };
+    if digest(&lock.salt, password) != lock.hash {
+        crate::audit::record("访客模式", "解锁密码错误");
+        return Err(tr("密码错误").to_string());
+    }
+    clear(&mut state, "输入密码提前结束");
+    Ok(())
+}
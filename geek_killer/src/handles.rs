@@ -0,0 +1,425 @@
+// ═══════════════════════════════════════════════════════════════
+//  句柄扫描 (Handle Scanner) - 找出进程在目标盘上打开的具体文件
+// ═══════════════════════════════════════════════════════════════
+//
+// RestartManager 只告诉我们“谁”占用，不告诉“占用了什么”。
+// 这里枚举系统句柄表，把指定 PID 的文件句柄复制到本进程，
+// 再用 GetFinalPathNameByHandleW 还原出完整路径。
+//
+// RM 与 exe/cwd 检查都找不到占用者时，scan_drive 会扫描全部进程的文件句柄。
+// 对同步打开的命名管道等对象，查询名称可能永久阻塞，因此 NtQueryObject
+// 放在独立的工作线程里执行，超时即放弃该句柄并换一个新线程。
+
+use crate::i18n::tr;
+use std::collections::HashMap;
+use std::os::windows::io::AsRawHandle;
+use std::sync::mpsc;
+use std::time::Duration;
+use windows_sys::Win32::Foundation::{
+    CloseHandle, DuplicateHandle, GetLastError, DUPLICATE_SAME_ACCESS, ERROR_INSUFFICIENT_BUFFER, HANDLE,
+    STATUS_INFO_LENGTH_MISMATCH,
+};
+use windows_sys::Win32::Storage::FileSystem::{
+    GetFileType, GetFinalPathNameByHandleW, FILE_NAME_NORMALIZED, FILE_TYPE_DISK,
+    VOLUME_NAME_DOS,
+};
+use windows_sys::Win32::System::Threading::{
+    GetCurrentProcess, OpenProcess, QueryFullProcessImageNameW, PROCESS_DUP_HANDLE,
+    PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+};
+use windows_sys::Win32::Storage::FileSystem::QueryDosDeviceW;
+
+const SYSTEM_EXTENDED_HANDLE_INFORMATION: u32 = 64;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct HandleEntry {
+    object: *mut std::ffi::c_void,
+    unique_process_id: usize,
+    handle_value: usize,
+    granted_access: u32,
+    creator_back_trace_index: u16,
+    object_type_index: u16,
+    handle_attributes: u32,
+    reserved: u32,
+}
+
+#[repr(C)]
+struct HandleInfoHeader {
+    number_of_handles: usize,
+    reserved: usize,
+}
+
+#[link(name = "ntdll")]
+extern "system" {
+    fn NtQuerySystemInformation(
+        class: u32,
+        info: *mut std::ffi::c_void,
+        len: u32,
+        ret_len: *mut u32,
+    ) -> i32;
+    fn NtQueryObject(
+        handle: HANDLE,
+        class: u32,
+        info: *mut std::ffi::c_void,
+        len: u32,
+        ret_len: *mut u32,
+    ) -> i32;
+}
+
+const OBJECT_NAME_INFORMATION: u32 = 1;
+/// 单个句柄名称查询的最长等待时间
+const NAME_QUERY_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// 读取系统句柄表快照 (SystemExtendedHandleInformation)
+fn snapshot_handles() -> Vec<HandleEntry> {
+    let mut size: u32 = 1 << 20;
+    loop {
+        let mut buf = vec![0u8; size as usize];
+        let mut needed = 0u32;
+        let status = unsafe {
+            NtQuerySystemInformation(
+                SYSTEM_EXTENDED_HANDLE_INFORMATION,
+                buf.as_mut_ptr() as _,
+                size,
+                &mut needed,
+            )
+        };
+        if status == STATUS_INFO_LENGTH_MISMATCH {
+            // 句柄数在两次调用之间会增长，多留一些余量
+            size = needed.max(size) + (1 << 16);
+            if size > 256 << 20 {
+                return vec![];
+            }
+            continue;
+        }
+        if status < 0 {
+            return vec![];
+        }
+
+        unsafe {
+            let header = &*(buf.as_ptr() as *const HandleInfoHeader);
+            let first = buf
+                .as_ptr()
+                .add(std::mem::size_of::<HandleInfoHeader>()) as *const HandleEntry;
+            let max = (buf.len() - std::mem::size_of::<HandleInfoHeader>())
+                / std::mem::size_of::<HandleEntry>();
+            let count = header.number_of_handles.min(max);
+            return (0..count).map(|i| first.add(i).read_unaligned()).collect();
+        }
+    }
+}
+
+fn from_wide(buf: &[u16]) -> String {
+    let end = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    String::from_utf16_lossy(&buf[..end])
+}
+
+
+/// 复制远程句柄并解析为磁盘文件路径 (非磁盘文件返回 None，避免管道阻塞)
+fn resolve_file_path(process: HANDLE, handle_value: usize) -> Option<Vec<u16>> {
+    unsafe {
+        let mut dup: HANDLE = 0;
+        if DuplicateHandle(
+            process,
+            handle_value as HANDLE,
+            GetCurrentProcess(),
+            &mut dup,
+            0,
+            0,
+            DUPLICATE_SAME_ACCESS,
+        ) == 0
+        {
+            return None;
+        }
+
+        let mut result = None;
+        if GetFileType(dup) == FILE_TYPE_DISK {
+            // 缓冲区不足时返回所需长度，超长路径自动扩容
+            result = crate::long_path::read_growing(|buf| {
+                GetFinalPathNameByHandleW(dup, buf.as_mut_ptr(), buf.len() as u32, FILE_NAME_NORMALIZED | VOLUME_NAME_DOS)
+            })
+            .map(|p| crate::long_path::normalize(&p));
+        }
+        CloseHandle(dup);
+        result
+    }
+}
+
+/// 列出指定进程在目标盘上打开的文件 (Key: PID)
+pub fn locked_files(drive_letter: &str, pids: &[u32]) -> HashMap<u32, Vec<String>> {
+    let mut out: HashMap<u32, Vec<String>> = HashMap::new();
+    if pids.is_empty() {
+        return out;
+    }
+
+    let volume = crate::mount_manager::VolumeMatcher::new(drive_letter);
+
+    let handles = snapshot_handles();
+    for &pid in pids {
+        let process = unsafe { OpenProcess(PROCESS_DUP_HANDLE, 0, pid) };
+        if process == 0 {
+            continue;
+        }
+        for entry in handles.iter().filter(|h| h.unique_process_id == pid as usize) {
+            if let Some(wide) = resolve_file_path(process, entry.handle_value) {
+                if volume.contains_wide(&wide) {
+                    let path = String::from_utf16_lossy(&wide);
+                    let list = out.entry(pid).or_default();
+                    if !list.contains(&path) {
+                        list.push(path);
+                    }
+                }
+            }
+        }
+        unsafe {
+            CloseHandle(process);
+        }
+    }
+    out
+}
+
+/// 被占用文件的性质：决定强制关闭是否可能丢数据
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockKind {
+    /// 正在编辑的文档，强制关闭可能丢失未保存内容
+    Document,
+    /// 日志 / 临时 / 锁文件，强制关闭通常无害
+    Disposable,
+    Other,
+}
+
+const DOCUMENT_EXTS: &[&str] = &[
+    "doc", "docx", "xls", "xlsx", "ppt", "pptx", "wps", "et", "dps", "pdf", "txt", "md", "rtf",
+    "odt", "ods", "odp", "csv", "psd", "ai", "dwg", "blend", "kra", "mp4", "mov", "mkv",
+];
+const DISPOSABLE_EXTS: &[&str] = &["log", "tmp", "temp", "etl", "lock", "lck", "bak", "cache", "db-journal", "db-wal"];
+
+impl LockKind {
+    pub fn classify(path: &str) -> Self {
+        let file = path.rsplit(['\\', '/']).next().unwrap_or(path).to_lowercase();
+        // Office 的 ~$ 所有者文件说明对应文档正在编辑
+        if file.starts_with("~$") {
+            return LockKind::Document;
+        }
+        let ext = file.rsplit_once('.').map(|(_, e)| e).unwrap_or("");
+        if DOCUMENT_EXTS.contains(&ext) {
+            LockKind::Document
+        } else if DISPOSABLE_EXTS.contains(&ext) || file.starts_with('~') {
+            LockKind::Disposable
+        } else {
+            LockKind::Other
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LockKind::Document => tr("📝 文档"),
+            LockKind::Disposable => tr("🗒 日志/临时"),
+            LockKind::Other => tr("📄 文件"),
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            LockKind::Document => tr("可能正在编辑，强制关闭会丢失未保存的修改"),
+            LockKind::Disposable => tr("日志或临时文件，强制关闭通常无害"),
+            LockKind::Other => tr("无法判断用途，强制关闭前请确认"),
+        }
+    }
+
+    pub fn color(&self) -> eframe::egui::Color32 {
+        use eframe::egui::Color32;
+        match self {
+            LockKind::Document => Color32::GOLD,
+            LockKind::Disposable => Color32::from_rgb(120, 180, 120),
+            LockKind::Other => Color32::LIGHT_GRAY,
+        }
+    }
+}
+
+/// 进程映像文件名 (如 WINWORD.EXE)，RM 只给出应用显示名时用于补全
+pub fn process_image_name(pid: u32) -> Option<String> {
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process == 0 {
+            return None;
+        }
+        let full = query_image_path(process);
+        CloseHandle(process);
+        let full = full?;
+        let name = full.rsplit(|&c| c == b'\\' as u16).next()?;
+        Some(String::from_utf16_lossy(name))
+    }
+}
+
+/// QueryFullProcessImageNameW，缓冲区不足时扩容重试
+fn query_image_path(process: HANDLE) -> Option<Vec<u16>> {
+    crate::long_path::read_growing(|buf| unsafe {
+        let mut len = buf.len() as u32;
+        if QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, buf.as_mut_ptr(), &mut len) != 0 {
+            len
+        } else if GetLastError() == ERROR_INSUFFICIENT_BUFFER {
+            buf.len() as u32
+        } else {
+            0
+        }
+    })
+}
+
+/// NtQueryObject(ObjectNameInformation)：返回内核路径，如 \Device\HarddiskVolume5\a.txt
+fn query_object_name(handle: HANDLE) -> Option<String> {
+    // OBJECT_NAME_INFORMATION 即一个 UNICODE_STRING，字符数据紧随其后
+    let mut buf = vec![0usize; 1024];
+    let mut ret = 0u32;
+    let status = unsafe {
+        NtQueryObject(
+            handle,
+            OBJECT_NAME_INFORMATION,
+            buf.as_mut_ptr() as _,
+            (buf.len() * std::mem::size_of::<usize>()) as u32,
+            &mut ret,
+        )
+    };
+    if status < 0 {
+        return None;
+    }
+    unsafe {
+        let len = *(buf.as_ptr() as *const u16) as usize / 2;
+        let data = *buf.as_ptr().add(1) as *const u16;
+        if len == 0 || data.is_null() {
+            return None;
+        }
+        Some(String::from_utf16_lossy(std::slice::from_raw_parts(data, len)))
+    }
+}
+
+/// 名称查询工作线程：主线程逐个发送复制来的句柄，等待结果或超时
+struct NameWorker {
+    tx: mpsc::Sender<usize>,
+    rx: mpsc::Receiver<Option<String>>,
+}
+
+impl NameWorker {
+    fn spawn() -> Self {
+        let (tx, worker_rx) = mpsc::channel::<usize>();
+        let (worker_tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for handle in worker_rx {
+                if worker_tx.send(query_object_name(handle as HANDLE)).is_err() {
+                    break;
+                }
+            }
+        });
+        NameWorker { tx, rx }
+    }
+
+    /// 超时返回 Err：工作线程已卡死，调用方应丢弃它 (句柄随线程一起泄漏)
+    fn query(&self, handle: HANDLE) -> Result<Option<String>, ()> {
+        self.tx.send(handle as usize).map_err(|_| ())?;
+        self.rx.recv_timeout(NAME_QUERY_TIMEOUT).map_err(|_| ())
+    }
+}
+
+/// 文件对象在本机上的类型编号：打开本程序文件，在句柄表里找到它
+fn file_type_index(handles: &[HandleEntry]) -> Option<u16> {
+    let exe = std::env::current_exe().ok()?;
+    let file = std::fs::File::open(exe).ok()?;
+    let me = std::process::id() as usize;
+    let raw = file.as_raw_handle() as usize;
+    handles
+        .iter()
+        .find(|h| h.unique_process_id == me && h.handle_value == raw)
+        .map(|h| h.object_type_index)
+        .or_else(|| {
+            // 快照取自打开文件之前，重新取一次
+            snapshot_handles()
+                .into_iter()
+                .find(|h| h.unique_process_id == me && h.handle_value == raw)
+                .map(|h| h.object_type_index)
+        })
+}
+
+/// 卷对应的内核设备路径，如 "E:" -> \Device\HarddiskVolume5；
+/// 文件夹挂载的卷用 "Volume{GUID}" 这个 DOS 名查询
+fn dos_device(target: &str) -> Option<String> {
+    let dos_name = if crate::mount_manager::is_drive_letter(target) {
+        format!("{}:", crate::norm_drive(target))
+    } else {
+        let volume = if crate::mount_manager::is_volume_guid(target) {
+            target.to_string()
+        } else {
+            crate::mount_manager::volume_name(target)?
+        };
+        volume.trim_start_matches("\\\\?\\").trim_end_matches('\\').to_string()
+    };
+    let name: Vec<u16> = dos_name.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut buf = vec![0u16; 512];
+    let len = unsafe { QueryDosDeviceW(name.as_ptr(), buf.as_mut_ptr(), buf.len() as u32) };
+    (len > 0).then(|| from_wide(&buf))
+}
+
+/// 扫描所有进程的文件句柄，找出在目标盘上打开文件的进程 (Key: PID)
+///
+/// 比 locked_files 慢得多，只在其他检测手段都没有结果时使用。
+pub fn scan_drive(drive_letter: &str) -> HashMap<u32, Vec<String>> {
+    let mut out: HashMap<u32, Vec<String>> = HashMap::new();
+    let root = crate::mount_manager::display_name(drive_letter);
+    let Some(device) = dos_device(drive_letter) else {
+        return out;
+    };
+    let device_prefix = format!("{}\\", device);
+
+    let handles = snapshot_handles();
+    let Some(file_type) = file_type_index(&handles) else {
+        return out;
+    };
+    let me = std::process::id() as usize;
+
+    let mut by_pid: HashMap<usize, Vec<usize>> = HashMap::new();
+    for h in handles.iter().filter(|h| h.object_type_index == file_type && h.unique_process_id != me) {
+        by_pid.entry(h.unique_process_id).or_default().push(h.handle_value);
+    }
+
+    let mut worker = NameWorker::spawn();
+    for (pid, values) in by_pid {
+        let process = unsafe { OpenProcess(PROCESS_DUP_HANDLE, 0, pid as u32) };
+        if process == 0 {
+            continue;
+        }
+        for value in values {
+            let mut dup: HANDLE = 0;
+            let ok = unsafe {
+                DuplicateHandle(process, value as HANDLE, GetCurrentProcess(), &mut dup, 0, 0, DUPLICATE_SAME_ACCESS)
+            };
+            if ok == 0 {
+                continue;
+            }
+            match worker.query(dup) {
+                Ok(name) => {
+                    unsafe {
+                        CloseHandle(dup);
+                    }
+                    let Some(name) = name else { continue };
+                    let matched = name
+                        .get(..device_prefix.len())
+                        .map(|head| head.eq_ignore_ascii_case(&device_prefix))
+                        .unwrap_or(false);
+                    if matched {
+                        let rest = &name[device_prefix.len()..];
+                        let path = format!("{}\\{}", root, rest);
+                        let list = out.entry(pid as u32).or_default();
+                        if !list.contains(&path) {
+                            list.push(path);
+                        }
+                    }
+                }
+                Err(()) => worker = NameWorker::spawn(),
+            }
+        }
+        unsafe {
+            CloseHandle(process);
+        }
+    }
+    out
+}
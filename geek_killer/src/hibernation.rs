@@ -0,0 +1,90 @@
+// ═══════════════════════════════════════════════════════════════
+//  休眠与快速启动 - hiberfil.sys 大小与开关
+// ═══════════════════════════════════════════════════════════════
+//
+// “快速启动”(Hiberboot) 关机时并不真正关闭内核，而是把内核会话休眠到 hiberfil.sys，
+// 下次开机直接恢复。已挂载卷的状态也随之保留：关机前没弹出的 U 盘 / 移动硬盘
+// 开机后可能显示“被占用”、卷被标记为脏，或在另一台电脑上改过的内容丢失。
+// 选择“重启”不受影响 (重启总是完整启动)。
+//   - 快速启动：HKLM\...\Session Manager\Power\HiberbootEnabled (依赖休眠已开启)
+//   - 休眠：powercfg /h on|off，关闭休眠会删除 hiberfil.sys 并连带关闭快速启动
+// 开关均经 tool_runner 运行 (需要管理员，启动前确认并写入审计日志)。
+
+use crate::i18n::tr;
+use crate::long_path::wide;
+use crate::tool_runner::ToolSpec;
+use windows_sys::Win32::System::Registry::{RegGetValueW, HKEY_LOCAL_MACHINE, RRF_RT_REG_DWORD};
+
+const POWER_KEY: &str = "SYSTEM\\CurrentControlSet\\Control\\Power";
+const HIBERBOOT_KEY: &str = "SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Power";
+
+fn read_dword(key: &str, value: &str) -> Option<u32> {
+    let mut data = 0u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_LOCAL_MACHINE,
+            wide(key).as_ptr(),
+            wide(value).as_ptr(),
+            RRF_RT_REG_DWORD,
+            std::ptr::null_mut(),
+            &mut data as *mut u32 as _,
+            &mut size,
+        )
+    };
+    (status == 0).then_some(data)
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Status {
+    pub hibernate: bool,
+    /// 快速启动开关本身 (休眠关闭时不生效)
+    pub hiberboot: bool,
+    /// hiberfil.sys 大小，字节；文件不存在时为 None
+    pub hiberfil_size: Option<u64>,
+}
+
+impl Status {
+    /// 快速启动实际生效
+    pub fn fast_startup(&self) -> bool {
+        self.hibernate && self.hiberboot
+    }
+}
+
+/// 系统盘根目录下 hiberfil.sys 的大小。
+/// 该文件被系统独占打开，用目录枚举的信息读取大小，不打开文件本身
+fn hiberfil_size() -> Option<u64> {
+    let drive = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
+    std::fs::read_dir(format!("{}\\", drive))
+        .ok()?
+        .flatten()
+        .find(|e| e.file_name().to_string_lossy().eq_ignore_ascii_case("hiberfil.sys"))
+        .and_then(|e| e.metadata().ok())
+        .map(|m| m.len())
+}
+
+pub fn status() -> Status {
+    Status {
+        hibernate: read_dword(POWER_KEY, "HibernateEnabled").unwrap_or(0) != 0,
+        // 未设置时 Windows 默认开启快速启动
+        hiberboot: read_dword(HIBERBOOT_KEY, "HiberbootEnabled").unwrap_or(1) != 0,
+        hiberfil_size: hiberfil_size(),
+    }
+}
+
+pub fn set_hibernate_spec(on: bool) -> ToolSpec {
+    if on {
+        ToolSpec::new(tr("开启休眠"), "powercfg.exe", &["/h", "on"])
+    } else {
+        ToolSpec::new(tr("关闭休眠 (删除 hiberfil.sys)"), "powercfg.exe", &["/h", "off"])
+    }
+}
+
+pub fn set_fast_startup_spec(on: bool) -> ToolSpec {
+    let key = format!("HKLM\\{}", HIBERBOOT_KEY);
+    ToolSpec::new(
+        tr(if on { "开启快速启动" } else { "关闭快速启动" }),
+        "reg.exe",
+        &["add", &key, "/v", "HiberbootEnabled", "/t", "REG_DWORD", "/d", if on { "1" } else { "0" }, "/f"],
+    )
+}
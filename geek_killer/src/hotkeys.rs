@@ -0,0 +1,188 @@
+// ═══════════════════════════════════════════════════════════════
+//  全局快捷键 - 窗口不在前台 (甚至隐藏到托盘) 时也能呼出 / 弹出 U 盘
+// ═══════════════════════════════════════════════════════════════
+//
+// RegisterHotKey 注册到专用线程的消息队列 (hwnd 为 0)，线程收到 WM_HOTKEY 后
+// 回调调用方，由调用方把命令发进现有的 mpsc 通道。
+// 修改绑定后向该线程投递 WM_RELOAD，先全部注销再按新配置注册
+// (RegisterHotKey 只能在拥有消息队列的线程上调用)。
+// 组合键被其他程序占用时注册失败，结果写入 status 供界面显示。
+
+use crate::i18n::{tr, trf};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use windows_sys::Win32::System::Threading::GetCurrentThreadId;
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+    RegisterHotKey, UnregisterHotKey, MOD_ALT, MOD_CONTROL, MOD_NOREPEAT, MOD_SHIFT, MOD_WIN,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    GetMessageW, PeekMessageW, PostThreadMessageW, PM_NOREMOVE, WM_APP, WM_HOTKEY,
+};
+
+const WM_RELOAD: u32 = WM_APP + 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HotkeyAction {
+    /// 把主窗口带到前台
+    ShowWindow,
+    /// 弹出最近插入的可移动磁盘
+    EjectLatest,
+    /// 执行操作宏 (settings.macros 的下标)
+    RunMacro(usize),
+}
+
+impl HotkeyAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HotkeyAction::ShowWindow => tr("显示主窗口"),
+            HotkeyAction::EjectLatest => tr("弹出最近插入的 U 盘"),
+            HotkeyAction::RunMacro(_) => tr("执行操作宏"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Binding {
+    pub action: HotkeyAction,
+    /// 形如 "Ctrl+Alt+K"；为空表示不注册
+    pub keys: String,
+}
+
+pub fn default_bindings() -> Vec<Binding> {
+    vec![
+        Binding {
+            action: HotkeyAction::ShowWindow,
+            keys: "Ctrl+Alt+K".to_string(),
+        },
+        Binding {
+            action: HotkeyAction::EjectLatest,
+            keys: "Ctrl+Alt+E".to_string(),
+        },
+    ]
+}
+
+/// 解析 "Ctrl+Alt+K" 为 (修饰键, 虚拟键码)；至少要有一个修饰键
+pub fn parse(keys: &str) -> Result<(u32, u32), String> {
+    let mut modifiers = 0;
+    let mut vk = None;
+    for part in keys.split('+').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => modifiers |= MOD_CONTROL,
+            "alt" => modifiers |= MOD_ALT,
+            "shift" => modifiers |= MOD_SHIFT,
+            "win" => modifiers |= MOD_WIN,
+            key => {
+                if vk.is_some() {
+                    return Err(trf("“{}”包含多个按键", &[&keys]));
+                }
+                vk = Some(parse_key(key).ok_or_else(|| trf("无法识别的按键“{}”", &[&part]))?);
+            }
+        }
+    }
+    let vk = vk.ok_or_else(|| trf("“{}”缺少按键", &[&keys]))?;
+    if modifiers == 0 {
+        return Err(trf("“{}”至少需要 Ctrl / Alt / Shift / Win 之一", &[&keys]));
+    }
+    Ok((modifiers, vk))
+}
+
+/// A-Z、0-9 与 F1-F24
+fn parse_key(key: &str) -> Option<u32> {
+    let upper = key.to_ascii_uppercase();
+    let mut chars = upper.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphanumeric() => Some(c as u32),
+        (Some('F'), Some(_)) => upper[1..].parse::<u32>().ok().filter(|n| (1..=24).contains(n)).map(|n| 0x70 + n - 1),
+        _ => None,
+    }
+}
+
+/// 每个绑定的注册结果
+pub type Status = Vec<(HotkeyAction, Result<(), String>)>;
+
+pub struct HotkeyService {
+    thread_id: u32,
+    bindings: Arc<Mutex<Vec<Binding>>>,
+    pub status: Arc<Mutex<Status>>,
+}
+
+impl HotkeyService {
+    /// 启动快捷键线程并注册 bindings
+    pub fn spawn(bindings: Vec<Binding>, on_action: impl Fn(HotkeyAction) + Send + 'static) -> Self {
+        let bindings = Arc::new(Mutex::new(bindings));
+        let status = Arc::new(Mutex::new(Vec::new()));
+        let (tx, rx) = std::sync::mpsc::channel();
+        {
+            let bindings = bindings.clone();
+            let status = status.clone();
+            std::thread::spawn(move || unsafe {
+                // 先建立消息队列，之后 PostThreadMessageW 才不会丢
+                let mut msg = std::mem::zeroed();
+                PeekMessageW(&mut msg, 0, 0, 0, PM_NOREMOVE);
+                let _ = tx.send(GetCurrentThreadId());
+
+                let mut active = register(&bindings, &status);
+                while GetMessageW(&mut msg, 0, 0, 0) > 0 {
+                    match msg.message {
+                        WM_HOTKEY => {
+                            if let Some(action) = active.get((msg.wParam as usize).wrapping_sub(1)).copied().flatten() {
+                                on_action(action);
+                            }
+                        }
+                        WM_RELOAD => {
+                            for id in 1..=active.len() {
+                                UnregisterHotKey(0, id as i32);
+                            }
+                            active = register(&bindings, &status);
+                        }
+                        _ => {}
+                    }
+                }
+            });
+        }
+        Self {
+            thread_id: rx.recv().unwrap_or(0),
+            bindings,
+            status,
+        }
+    }
+
+    /// 换成新的绑定并重新注册
+    pub fn update(&self, bindings: Vec<Binding>) {
+        if let Ok(mut b) = self.bindings.lock() {
+            *b = bindings;
+        }
+        unsafe {
+            PostThreadMessageW(self.thread_id, WM_RELOAD, 0, 0);
+        }
+    }
+}
+
+/// 按顺序注册，热键 ID 为下标 + 1；返回 ID -> 动作 (注册失败的为 None)
+fn register(bindings: &Mutex<Vec<Binding>>, status: &Mutex<Status>) -> Vec<Option<HotkeyAction>> {
+    let bindings = bindings.lock().map(|b| b.clone()).unwrap_or_default();
+    let mut active = Vec::with_capacity(bindings.len());
+    let mut results = Vec::with_capacity(bindings.len());
+    for (i, b) in bindings.iter().enumerate() {
+        if b.keys.trim().is_empty() {
+            active.push(None);
+            continue;
+        }
+        let result = parse(&b.keys).and_then(|(modifiers, vk)| {
+            if unsafe { RegisterHotKey(0, (i + 1) as i32, modifiers | MOD_NOREPEAT, vk) } == 0 {
+                Err(trf("{} 已被其他程序占用", &[&b.keys]))
+            } else {
+                Ok(())
+            }
+        });
+        active.push(result.is_ok().then_some(b.action));
+        if let Err(e) = &result {
+            crate::audit::record("快捷键", &format!("{} {} -> {}", b.action.label(), b.keys, e));
+        }
+        results.push((b.action, result));
+    }
+    if let Ok(mut s) = status.lock() {
+        *s = results;
+    }
+    active
+}
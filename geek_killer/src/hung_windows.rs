@@ -0,0 +1,92 @@
+// ═══════════════════════════════════════════════════════════════
+//  无响应检测 - 探测顶层窗口是否真的卡死
+// ═══════════════════════════════════════════════════════════════
+//
+// Windows 上进程几乎不会处于 UninterruptibleDiskSleep / Dead 状态，
+// “未响应”其实是窗口线程不再处理消息。对每个可见的顶层窗口：
+//   1. IsHungAppWindow —— 系统判定 (5 秒未取消息)，零开销
+//   2. SendMessageTimeout(WM_NULL, SMTO_ABORTIFHUNG) —— 短超时探测，
+//      能提前发现刚卡住、尚未达到系统阈值的窗口；一次超时可能只是窗口正忙，
+//      连续 MISSES_REQUIRED 轮探测都超时才算卡死
+// 幽灵窗口 (已被 DWM 替换的卡死窗口) 还原为背后的真实窗口再归属进程。
+// 只看可见窗口：后台线程的隐藏窗口不泵消息很常见，不代表程序卡死。
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use windows_sys::Win32::Foundation::{BOOL, HWND, LPARAM};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    EnumWindows, GetWindow, GetWindowThreadProcessId, IsHungAppWindow, IsWindowVisible, SendMessageTimeoutW,
+    GW_OWNER, SMTO_ABORTIFHUNG, SMTO_BLOCK, WM_NULL,
+};
+
+/// 单个窗口的探测超时
+const PROBE_TIMEOUT: Duration = Duration::from_millis(100);
+/// 两次探测的最小间隔 (每轮都探测时卡死的窗口会拖慢采集)
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(2);
+/// 连续多少轮短超时探测失败才判定为卡死
+const MISSES_REQUIRED: u32 = 3;
+
+unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> BOOL {
+    let windows = &mut *(lparam as *mut Vec<HWND>);
+    if IsWindowVisible(hwnd) != 0 && GetWindow(hwnd, GW_OWNER) == 0 {
+        windows.push(hwnd);
+    }
+    1
+}
+
+/// 短超时探测：窗口线程在 PROBE_TIMEOUT 内处理了消息即为正常
+unsafe fn responds(hwnd: HWND) -> bool {
+    let mut result = 0usize;
+    SendMessageTimeoutW(
+        hwnd,
+        WM_NULL,
+        0,
+        0,
+        SMTO_ABORTIFHUNG | SMTO_BLOCK,
+        PROBE_TIMEOUT.as_millis() as u32,
+        &mut result,
+    ) != 0
+}
+
+/// 跨轮次记录每个窗口连续探测失败的次数
+#[derive(Default)]
+pub struct HungProbe {
+    misses: HashMap<HWND, u32>,
+}
+
+impl HungProbe {
+    /// 拥有卡死窗口的进程
+    pub fn hung_pids(&mut self) -> HashSet<u32> {
+        let mut out = HashSet::new();
+        let mut misses = HashMap::new();
+        unsafe {
+            let mut windows: Vec<HWND> = Vec::new();
+            EnumWindows(Some(collect), &mut windows as *mut _ as LPARAM);
+            let own_pid = std::process::id();
+            for hwnd in windows {
+                let real = crate::window_picker::unghost(hwnd);
+                let mut pid = 0;
+                GetWindowThreadProcessId(real, &mut pid);
+                if pid == 0 || pid == own_pid || out.contains(&pid) {
+                    continue;
+                }
+                // 幽灵窗口本身就说明背后的窗口已卡死；系统判定同样可信
+                if real != hwnd || IsHungAppWindow(real) != 0 {
+                    out.insert(pid);
+                    continue;
+                }
+                if responds(real) {
+                    continue;
+                }
+                let count = self.misses.get(&real).copied().unwrap_or(0) + 1;
+                misses.insert(real, count);
+                if count >= MISSES_REQUIRED {
+                    out.insert(pid);
+                }
+            }
+        }
+        // 恢复响应或已关闭的窗口清零
+        self.misses = misses;
+        out
+    }
+}
@@ -0,0 +1,1464 @@
+// ═══════════════════════════════════════════════════════════════
+//  界面语言 - 以中文原文为键的翻译表
+// ═══════════════════════════════════════════════════════════════
+//
+// 界面字符串仍直接写中文，包一层 tr("…") 即可切换语言；英文表里没有的条目
+// 原样显示中文，逐步补齐翻译不会让界面出现空白或键名。带参数的文本用 trf，
+// 模板里的 {} 按顺序替换 (翻译可以调整语序，但 {} 的个数与含义要一致)。
+// 当前语言是进程级的全局状态：托盘、USB 线程发出的提示同样随之切换，
+// 在设置里切换后下一帧生效 (进程名数据库仍需重启)。
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+static ENGLISH: AtomicBool = AtomicBool::new(false);
+
+/// 按设置中的语言代码切换 (LANGUAGES 之一，未知代码按中文)
+pub fn set_language(code: &str) {
+    ENGLISH.store(code.starts_with("en"), Ordering::Relaxed);
+}
+
+/// 翻译一条界面文本；当前为中文或缺少翻译时返回原文
+pub fn tr(zh: &'static str) -> &'static str {
+    if !ENGLISH.load(Ordering::Relaxed) {
+        return zh;
+    }
+    english().get(zh).copied().unwrap_or(zh)
+}
+
+/// 翻译后依次替换模板中的 {}
+pub fn trf(zh: &'static str, args: &[&dyn Display]) -> String {
+    let mut out = String::new();
+    let mut args = args.iter();
+    let mut parts = tr(zh).split("{}").peekable();
+    while let Some(part) = parts.next() {
+        out.push_str(part);
+        if parts.peek().is_some() {
+            if let Some(a) = args.next() {
+                out.push_str(&a.to_string());
+            }
+        }
+    }
+    out
+}
+
+fn english() -> &'static HashMap<&'static str, &'static str> {
+    static TABLE: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    TABLE.get_or_init(|| EN.iter().copied().collect())
+}
+
+const EN: &[(&str, &str)] = &[
+    // 顶栏
+    ("⚡ 极简模式", "⚡ Low-power mode"),
+    ("进程 {} · 线程 {} · 句柄 {} · 已开机 {}", "Processes {} · Threads {} · Handles {} · Uptime {}"),
+    ("⚙ 设置", "⚙ Settings"),
+    ("🗗 悬浮窗", "🗗 HUD"),
+    (
+        "无边框置顶的小窗口，显示 CPU / 内存 / 网速与占用最高的进程；单击悬浮窗回到主窗口",
+        "Small frameless always-on-top window with CPU, memory, network and the top process; click it to return to the main window",
+    ),
+    ("扫描器:", "Scanner:"),
+    ("搜索进程...", "Search processes..."),
+    ("按进程名、说明、分类或 PID 过滤下方三组进程", "Filter the process lists below by name, description, category or PID"),
+    ("使用正则表达式 (不区分大小写)", "Use a regular expression (case-insensitive)"),
+    ("⚠ 正则有误", "⚠ Invalid regex"),
+    ("{} 个匹配", "{} matches"),
+    ("性能监测", "Performance"),
+    ("智能诊断", "Diagnostics"),
+    ("U盘管理", "USB drives"),
+    ("安全检查", "Security"),
+    ("启动项", "Startup"),
+    ("网络连接", "Network"),
+    ("连同子进程", "Include children"),
+    (
+        "终止时自底向上结束整棵进程树，防止子进程重新拉起父进程",
+        "Terminate the whole process tree bottom-up so children cannot relaunch the parent",
+    ),
+    ("⌖ 点击目标窗口…", "⌖ Click a window…"),
+    ("⌖ 点选窗口", "⌖ Pick window"),
+    (
+        "光标变为十字后点击任意窗口 (如全屏卡死的程序)，可终止或挂起其进程；右键或 Esc 取消",
+        "Click any window once the cursor turns into a crosshair (e.g. a frozen full-screen app) to terminate or suspend its process; right-click or Esc cancels",
+    ),
+    ("关闭到托盘", "Close to tray"),
+    (
+        "关闭窗口时隐藏到系统托盘并继续监控，右键托盘图标可一键弹出 U 盘",
+        "Hide to the system tray on close and keep monitoring; right-click the tray icon to eject drives",
+    ),
+    ("▶️ 恢复刷新", "▶️ Resume"),
+    ("⏸️ 锁定视图", "⏸️ Freeze view"),
+    // 视图菜单
+    ("🗔 视图", "🗔 View"),
+    ("完整视图", "Full view"),
+    ("U 盘精简视图", "USB-only view"),
+    ("🔒 访客模式中，设置已锁定", "🔒 Guest mode is on; settings are locked"),
+    ("启动配置 (● 为默认)", "Launch profiles (● = default)"),
+    (
+        "命令行可用 --profile 名称 选择配置，--view usb|full 覆盖视图",
+        "Use --profile NAME on the command line to pick a profile, --view usb|full to override the view",
+    ),
+    ("启动时默认使用此配置", "Use this profile at startup"),
+    ("切换到此配置", "Switch to this profile"),
+    ("删除此配置", "Delete this profile"),
+    ("保存当前面板到「{}」", "Save current panels to \"{}\""),
+    ("用当前视图与展开的面板覆盖此配置", "Overwrite this profile with the current view and open panels"),
+    ("新配置名", "New profile name"),
+    ("另存为", "Save as"),
+    ("全局快捷键", "Global hotkeys"),
+    (
+        "窗口在后台或隐藏到托盘时也有效；格式如 Ctrl+Alt+K，留空表示不注册",
+        "Work even when the window is in the background or hidden to the tray; format like Ctrl+Alt+K, leave empty to disable",
+    ),
+    ("已注册", "Registered"),
+    ("应用", "Apply"),
+    ("恢复默认", "Restore defaults"),
+    // 设置窗口
+    ("刷新", "Refresh"),
+    ("刷新间隔", "Refresh interval"),
+    (
+        "后台采集进程与磁盘信息的间隔；插拔后的连刷与极简模式不受影响",
+        "How often processes and disks are sampled; the burst after plugging a device and low-power mode are unaffected",
+    ),
+    ("降低刷新频率 (间隔加倍)", "Reduce refresh rate (double the interval)"),
+    ("资源紧张阈值", "Resource pressure thresholds"),
+    (
+        "满足任一条件持续几轮后进入极简模式 (刷新放缓到 2 秒)",
+        "Low-power mode starts (refresh slows to 2 s) after either condition holds for a few rounds",
+    ),
+    ("CPU 高于", "CPU above"),
+    ("可用内存低于", "Free memory below"),
+    ("资源紧张时自动折叠面板", "Collapse panels under resource pressure"),
+    (
+        "进入极简模式时收起性能监测与智能诊断，减少绘制开销",
+        "Close Performance and Diagnostics in low-power mode to save drawing time",
+    ),
+    ("弹出方式", "Eject mode"),
+    ("快速：失败时列出占用", "Quick: list blockers on failure"),
+    ("智能：无占用时自动卸载弹出", "Smart: dismount and eject when nothing is found"),
+    ("强制：自动结束占用进程", "Force: terminate blockers automatically"),
+    (
+        "快速弹出被拒绝后扫描占用并列出，由你选择结束哪些进程",
+        "When a quick eject is refused, scan and list the blocking processes so you choose what to end",
+    ),
+    (
+        "未找到占用进程 (多为缓存或驱动未释放) 时自动执行卸载卷 + 弹出；有占用时仍列出",
+        "If no blocking process is found (usually caches or drivers), dismount the volume and eject; otherwise list them",
+    ),
+    (
+        "快速弹出被拒绝后直接对占用进程执行强力清场 (文档程序仍先请求保存；开启“清场前确认”时同样先倒计时)",
+        "When a quick eject is refused, force-clear the blockers right away (document editors are still asked to save; the confirmation countdown still applies when enabled)",
+    ),
+    ("界面", "Interface"),
+    ("语言", "Language"),
+    ("进程说明在重启后切换", "Process descriptions switch after a restart"),
+    ("主题", "Theme"),
+    ("深色", "Dark"),
+    ("浅色", "Light"),
+    ("启动", "Startup"),
+    ("默认配置", "Default profile"),
+    ("启动时恢复上次的面板与窗口位置", "Restore last panels and window position"),
+    ("指定了 --profile 时仍按配置启动", "--profile still takes precedence"),
+    ("启动后直接隐藏到托盘", "Start hidden in the tray"),
+    (
+        "开机自启时不弹出主窗口，双击托盘图标或按快捷键显示",
+        "Don't show the main window at startup; double-click the tray icon or use the hotkey to show it",
+    ),
+    // 访客模式
+    ("🔒 访客模式", "🔒 Guest mode"),
+    (
+        "借出电脑时禁用终止、强制弹出、格式化等操作并锁定设置",
+        "Disable terminate, force eject, format and similar actions and lock settings while lending the PC",
+    ),
+    ("时长", "Duration"),
+    (" 小时", " h"),
+    ("解锁密码", "Unlock password"),
+    ("开启", "Start"),
+    ("🔒 访客模式已开启，{} 小时后自动结束", "🔒 Guest mode is on and ends in {} h"),
+    ("🔒 访客模式下已禁用此操作", "🔒 Disabled in guest mode"),
+    // U 盘管理
+    ("💾 外部存储管理", "💾 External storage"),
+    ("插入时检查自动运行威胁", "Check for autorun threats on insert"),
+    (
+        "新插入的盘会检查根目录下的 autorun.inf、隐藏的可执行文件和快捷方式病毒，只提示不删除",
+        "Newly inserted drives are checked for autorun.inf, hidden executables and shortcut viruses; nothing is deleted",
+    ),
+    ("全局写保护", "Global write protection"),
+    ("未检测到外部驱动器", "No external drives detected"),
+    ("⚠️ {} 被占用", "⚠️ {} is in use"),
+    ("取消", "Cancel"),
+    (" 强力清场 ", " Force clear "),
+    ("强制终止相关进程并弹出", "Terminate the blocking processes and eject"),
+    (" 强制卸载 ", " Force dismount "),
+    ("使用系统 fsutil 工具强制卸载卷", "Force-dismount the volume with fsutil"),
+    (" 重启资源管理器 ", " Restart Explorer "),
+    ("等待结果...", "Waiting for results..."),
+    ("清场前先请求文档程序保存", "Ask document editors to save first"),
+    (
+        "对 Word/WPS/记事本 等先发送关闭请求，触发其保存提示，等待后再强制终止",
+        "Send Word/WPS/Notepad a close request so they prompt to save, then terminate after a wait",
+    ),
+    ("清场前确认 (5 秒内可撤销)", "Confirm before clearing (5 s to undo)"),
+    ("检测到以下占用进程：", "These processes are using the drive:"),
+    ("终止", "Terminate"),
+    ("      …还有 {} 个文件", "      …and {} more files"),
+    (
+        "⚠️ 未检测到用户程序占用，可能是系统核心组件或驱动锁定。",
+        "⚠️ No user program is using the drive; a system component or driver may be holding it.",
+    ),
+    ("建议关闭所有窗口，或点击上方【强力清场】。", "Close all windows, or click [Force clear] above."),
+    // USB 线程的提示
+    ("✅ 驱动器 {}: 已安全弹出 (卸载卷后)", "✅ Drive {}: safely ejected (after dismount)"),
+    // 托盘
+    ("显示主窗口", "Show main window"),
+    ("迷你悬浮窗", "Mini HUD"),
+    ("内存", "RAM"),
+    ("单击回到主窗口，拖动可移动位置", "Click to return to the main window, drag to move"),
+    ("没有可移动磁盘", "No removable drives"),
+    ("弹出 {}", "Eject {}"),
+    ("终止 CPU 占用最高：{} ({}%)", "Terminate top CPU user: {} ({}%)"),
+    ("没有明显占用 CPU 的进程", "No process is using much CPU"),
+    ("退出", "Exit"),
+    ("可以安全拔出", "Safe to remove"),
+    ("{} 已弹出", "{} was ejected"),
+    ("未能弹出", "Eject failed"),
+    ("{} 仍被占用，点击打开窗口处理", "{} is still in use; click to open the window"),
+    ("已终止", "Terminated"),
+    ("终止失败", "Terminate failed"),
+    ("{} 有 {} 个进程未退出，可能需要管理员权限", "{}: {} processes did not exit; administrator rights may be required"),
+    ("正在执行操作宏", "Running macro"),
+    ("已最小化到托盘，后台继续监控；右键托盘图标可弹出 U 盘", "Minimized to the tray and still monitoring; right-click the tray icon to eject drives"),
+    // 进程表与通用
+    ("RestartManager：{} (服务:{})", "RestartManager: {} (service: {})"),
+    ("fsutil 卸载卷", "fsutil dismount volume"),
+    ("检查磁盘 {}", "Check disk {}"),
+    ("刚刚", "just now"),
+    ("{} 分钟前", "{} min ago"),
+    ("{} 小时前", "{} h ago"),
+    ("{} 天前", "{} days ago"),
+    ("访客模式下已禁用", "Disabled in guest mode"),
+    ("需要以管理员身份运行", "Requires running as administrator"),
+    ("清除", "Clear"),
+    ("SMART ({})：未发现异常", "SMART ({}): no problems found"),
+    ("SMART ({})：{}\n{}", "SMART ({}): {}\n{}"),
+    ("最近 {} 轮：CPU 平均 {}% · 峰值 {}%\n内存 {} MB → {} MB", "Last {} samples: CPU avg {}% · peak {}%\nMemory {} MB → {} MB"),
+    ("点击排序，再次点击切换升序 / 降序", "Click to sort, click again to toggle ascending / descending"),
+    ("数字签名:", "Signature:"),
+    ("校验中…", "Verifying…"),
+    ("{}，且从临时目录运行", "{}, and running from a temp directory"),
+    ("📷 截图并复制", "📷 Capture & copy"),
+    (
+        "截取本面板并复制到剪贴板，同时保存带主机名与时间水印的 PNG (右键设置)",
+        "Capture this panel to the clipboard and save a PNG watermarked with host name and time (right-click for options)",
+    ),
+    (
+        "截取本面板并复制到剪贴板，带主机名与时间水印 (右键设置)",
+        "Capture this panel to the clipboard, watermarked with host name and time (right-click for options)",
+    ),
+    ("同时保存 PNG", "Also save PNG"),
+    // USB 线程
+    ("无法打开驱动器 (权限不足或不存在)", "Cannot open the drive (access denied or it does not exist)"),
+    ("无法枚举磁盘设备列表", "Cannot enumerate disk devices"),
+    ("未找到对应的磁盘设备", "The matching disk device was not found"),
+    (
+        "硬件拒绝弹出 (VetoType 6)。请尝试关闭所有窗口后重试。",
+        "The hardware refused to eject (VetoType 6). Close all windows and try again.",
+    ),
+    ("{} (打开了 {} 个文件)", "{} ({} files open)"),
+    ("正在运行", "running from drive"),
+    ("工作目录", "working directory"),
+    ("✅ 驱动器 {}: 已安全弹出", "✅ Drive {}: safely ejected"),
+    (
+        "无法弹出：系统核心组件或驱动锁定。请尝试关闭所有窗口。",
+        "Cannot eject: a system component or driver is holding the drive. Close all windows and try again.",
+    ),
+    ("弹出失败：{}", "Eject failed: {}"),
+    ("弹出失败：{} (发现占用)", "Eject failed: {} (drive in use)"),
+    ("{}: 正在重启资源管理器...", "{}: restarting Explorer..."),
+    ("✅ 驱动器 {}: 已安全弹出 (资源管理器已重启){}", "✅ Drive {}: safely ejected (Explorer restarted){}"),
+    ("{}: 正在终止占用进程...", "{}: terminating processes using the drive..."),
+    ("准备清场", "Preparing to clear"),
+    ("已请求 {} 个文档程序保存并关闭，请在其窗口中确认...", "Asked {} document editors to save and close; confirm in their windows..."),
+    ("正在强制清场...", "Force clearing..."),
+    ("系统或服务进程，未自动终止", "System or service process, not terminated automatically"),
+    ("正在弹出...", "Ejecting..."),
+    ("✅ 驱动器 {}: 已强制弹出{}", "✅ Drive {}: force ejected{}"),
+    (
+        "系统核心组件锁定，强制移除失败。请重启电脑。",
+        "A system component is holding the drive and forced removal failed. Restart the computer.",
+    ),
+    ("{} 正在执行 fsutil dismount...", "{} running fsutil dismount..."),
+    ("{} 卷已强制卸载，尝试弹出...", "{} volume force-dismounted, trying to eject..."),
+    ("✅ 驱动器 {}: 已安全弹出 (fsutil)", "✅ Drive {}: safely ejected (fsutil)"),
+    ("❌ fsutil 成功但弹出失败：{}", "❌ fsutil succeeded but eject failed: {}"),
+    ("❌ fsutil 执行失败：{}", "❌ fsutil failed: {}"),
+    ("{}: 正在重新挂载...", "{}: remounting..."),
+    ("{} 正在格式化...", "{} formatting..."),
+    ("{} 正在格式化... {}%", "{} formatting... {}%"),
+    ("{} 正在锁定卷...", "{} locking volume..."),
+    ("❌ 测速失败：{}", "❌ Speed test failed: {}"),
+    ("✅ {} 已重启", "✅ {} restarted"),
+    ("❌ 重启 {} 失败：{}", "❌ Failed to restart {}: {}"),
+    ("被 {} 占用", "In use by {}"),
+    ("、", ", "),
+    ("已安全弹出", "Safely ejected"),
+    ("操作宏已执行", "Macro finished"),
+    // 诊断面板
+    ("没有可弹出的磁盘", "No drive to eject"),
+    ("未检测到可移动磁盘", "No removable drive detected"),
+    ("⏺ 已录制：{}", "⏺ Recorded: {}"),
+    (
+        "{} 个进程属于服务或其他用户，终止需要管理员权限",
+        "{} processes belong to services or other users; terminating them requires administrator rights",
+    ),
+    ("修改挂载点需要管理员权限", "Changing mount points requires administrator rights"),
+    ("只读挂载需要管理员权限", "Read-only mounting requires administrator rights"),
+    ("写保护需要管理员权限", "Write protection requires administrator rights"),
+    ("fsutil 卸载卷需要管理员权限", "fsutil dismount requires administrator rights"),
+    ("格式化需要管理员权限", "Formatting requires administrator rights"),
+    ("写入镜像需要管理员权限", "Writing an image requires administrator rights"),
+    ("安全擦除需要管理员权限", "Secure wipe requires administrator rights"),
+    ("修改删除策略需要管理员权限", "Changing the removal policy requires administrator rights"),
+    ("操作宏“{}”包含重启服务，需要管理员权限", "Macro \"{}\" restarts a service and requires administrator rights"),
+    (
+        "{}: 无权打开卷，强制卸载与设备弹出需要管理员权限",
+        "{}: cannot open the volume; force dismount and device eject require administrator rights",
+    ),
+    ("模块 · {} (PID {})", "Modules · {} (PID {})"),
+    ("重试", "Retry"),
+    ("搜索路径或文件名，如 E:\\", "Search path or file name, e.g. E:\\"),
+    ("只看可移动磁盘上的 ({})", "Only on removable drives ({})"),
+    ("共 {} 个模块，显示 {} 个", "{} modules, showing {}"),
+    ("{}\n基址 0x{} · {} KB", "{}\nBase 0x{} · {} KB"),
+    ("在资源管理器中显示", "Show in Explorer"),
+    ("确认强力清场", "Confirm force clear"),
+    ("{} 上的以下 {} 个进程将被强制终止：", "On {}, these {} processes will be terminated:"),
+    ("未保存的数据可能丢失", "Unsaved data may be lost"),
+    ("终止并弹出", "Terminate and eject"),
+    ("已挂起 {}/{} 个进程，{} 秒后终止", "Suspended {}/{} processes, terminating in {} s"),
+    ("↩ 撤销", "↩ Undo"),
+    ("恢复已挂起的进程，不做任何终止", "Resume the suspended processes without terminating anything"),
+    ("🖥 {} 属于桌面合成 / 显卡驱动，已阻止终止", "🖥 {} belongs to desktop composition / the graphics driver; termination was blocked"),
+    (
+        "结束它们只会黑屏或丢失驱动功能；画面卡死时请重启显卡驱动 (窗口与程序都会保留)",
+        "Ending them only causes a black screen or lost driver features; if the screen freezes, restart the graphics driver (windows and programs are kept)",
+    ),
+    ("🔄 重启显卡驱动", "🔄 Restart graphics driver"),
+    ("相当于按下 Win+Ctrl+Shift+B，屏幕会闪烁一下", "Same as pressing Win+Ctrl+Shift+B; the screen will flicker once"),
+    ("✅ 已发送重启显卡驱动 (Win+Ctrl+Shift+B)", "✅ Sent graphics driver restart (Win+Ctrl+Shift+B)"),
+    ("❌ 重启显卡驱动失败：{}", "❌ Failed to restart the graphics driver: {}"),
+    ("🔊 修复声音", "🔊 Fix audio"),
+    (
+        "依次重启 Windows Audio / Audio Endpoint Builder 服务，并结束卡住的 audiodg.exe",
+        "Restart the Windows Audio / Audio Endpoint Builder services in turn and end a stuck audiodg.exe",
+    ),
+    ("🖨 重置打印后台", "🖨 Reset print spooler"),
+    (
+        "停止 Print Spooler，清空卡住的打印作业后重新启动，并列出被移除的作业",
+        "Stop Print Spooler, clear stuck print jobs, restart it and list the removed jobs",
+    ),
+    ("🧹 清理临时文件", "🧹 Clean temp files"),
+    (
+        "清理 %TEMP% 与 Windows\\Temp，跳过正在使用的文件并列出占用进程，最后汇总释放的空间",
+        "Clean %TEMP% and Windows\\Temp, skip files in use and list the processes holding them, then sum up the space freed",
+    ),
+    ("💤 休眠与快速启动", "💤 Hibernation & Fast Startup"),
+    ("休眠:", "Hibernation:"),
+    ("已开启 · hiberfil.sys {} GB", "On · hiberfil.sys {} GB"),
+    ("已开启", "On"),
+    ("已关闭", "Off"),
+    ("关闭休眠", "Turn off hibernation"),
+    (
+        "powercfg /h off：删除 hiberfil.sys 释放空间，同时关闭快速启动与休眠",
+        "powercfg /h off: delete hiberfil.sys to free space; also turns off Fast Startup and hibernation",
+    ),
+    ("开启休眠", "Turn on hibernation"),
+    ("powercfg /h on：重新创建 hiberfil.sys", "powercfg /h on: recreate hiberfil.sys"),
+    ("快速启动:", "Fast Startup:"),
+    ("未生效 (休眠已关闭)", "Inactive (hibernation is off)"),
+    (
+        "快速启动关机时会保留卷的挂载状态：未弹出的 U 盘开机后可能仍被占用、卷被标记为需要修复。“重启”不受影响",
+        "Fast Startup keeps volume mount state across shutdown: a USB drive not ejected may still be in use after boot or be marked as needing repair. \"Restart\" is not affected",
+    ),
+    ("关闭快速启动", "Turn off Fast Startup"),
+    ("开启快速启动", "Turn on Fast Startup"),
+    (
+        "⚠ 快速启动已开启：关机前请先弹出 U 盘，否则开机后可能提示被占用或需要修复",
+        "⚠ Fast Startup is on: eject USB drives before shutting down, or they may be reported in use or needing repair after boot",
+    ),
+    ("🌐 {}：{} 个标签进程，占用 {} MB (浏览器合计 {} MB)", "🌐 {}: {} tab processes using {} MB (browser total {} MB)"),
+    ("释放浏览器内存", "Release browser memory"),
+    (
+        "不结束任何标签：裁剪标签进程的工作集，并打开 {} 由浏览器自行丢弃不用的标签",
+        "Closes no tabs: trims the tab processes' working sets and opens {} so the browser discards unused tabs",
+    ),
+    ("全部非系统进程", "all non-system processes"),
+    ("选中的 {} 个分组", "the {} selected groups"),
+    ("🧽 整理中…", "🧽 Trimming…"),
+    ("🧽 内存整理", "🧽 Trim memory"),
+    (
+        "裁剪{}的工作集 ({} 个进程)，不结束任何进程；在进程表中选中分组可只整理这些",
+        "Trim the working sets of {} ({} processes) without ending any; select groups in the process table to trim only those",
+    ),
+    ("同时清空备用列表", "Also purge the standby list"),
+    (
+        "丢弃系统文件缓存，释放为空闲内存；之后打开文件会稍慢。需要管理员权限",
+        "Discard the system file cache into free memory; opening files will be slightly slower afterwards. Requires administrator rights",
+    ),
+    ("清空备用列表需要管理员权限", "Purging the standby list requires administrator rights"),
+    ("需要管理员权限 — 点击提权", "Administrator rights required — click to elevate"),
+    ("以管理员身份重新启动本程序，当前窗口随后关闭", "Restart this program as administrator; this window will then close"),
+    ("仍然尝试", "Try anyway"),
+    ("部分操作可能失败", "Some operations may fail"),
+    // 进程表、控制台与命令确认
+    (
+        "留存当前配置后依次释放 / 重新获取 IP 地址并清空 DNS 缓存，期间会短暂断网",
+        "Save the current configuration, then release / renew IP addresses and flush the DNS cache; the network drops briefly",
+    ),
+    ("🔁 网络重置", "🔁 Network reset"),
+    ("同时重置 Winsock", "Also reset Winsock"),
+    (
+        "修复被代理 / 安全软件破坏的网络栈；需要重启计算机才生效",
+        "Repairs a network stack broken by proxy / security software; takes effect after a restart",
+    ),
+    ("⚠ 完成后需重启", "⚠ Restart required afterwards"),
+    ("⚠ Winsock 已重置，请重启计算机使其生效", "⚠ Winsock was reset; restart the computer for it to take effect"),
+    ("打开", "Open"),
+    ("清除网络重置结果", "Clear network reset results"),
+    ("🧾 该操作将执行以下外部命令：", "🧾 This action will run the following external commands:"),
+    ("执行", "Run"),
+    ("总是允许", "Always allow"),
+    ("本次运行内不再询问", "Don't ask again until the program restarts"),
+    ("所有外部命令都会记录到审计日志 (audit.log)", "All external commands are recorded in the audit log (audit.log)"),
+    ("未响应", "Not responding"),
+    ("重启", "Restart"),
+    ("结束后按原命令行与工作目录重新启动", "Terminate, then relaunch with the original command line and working directory"),
+    ("挂起", "Suspend"),
+    ("暂停进程的所有线程，释放 CPU；可随时恢复", "Pause all threads of the process to free the CPU; can be resumed at any time"),
+    ("恢复", "Resume"),
+    ("关闭", "Close"),
+    ("数量", "Count"),
+    ("进程名称", "Process name"),
+    ("架构", "Arch"),
+    ("按内存排序", "Sort by memory"),
+    ("总CPU", "Total CPU"),
+    ("磁盘 I/O", "Disk I/O"),
+    ("路径", "Path"),
+    ("操作", "Actions"),
+    ("显示 / 隐藏列", "Show / hide columns"),
+    ("展开查看每个进程", "Expand to see each process"),
+    (
+        "点击选中，Ctrl+点击多选；Ctrl+C 复制为文本表格，Ctrl+Shift+C 复制为 Markdown",
+        "Click to select, Ctrl+click to multi-select; Ctrl+C copies as a text table, Ctrl+Shift+C as Markdown",
+    ),
+    ("取消隐藏", "Unhide"),
+    ("隐藏此分组", "Hide this group"),
+    ("隐藏后仍计入列表总数", "Hidden groups still count toward the list total"),
+    ("窗口无响应 (不再处理消息)", "Window is not responding (no longer processing messages)"),
+    ("他人", "Other user"),
+    (
+        "包含其他已登录用户的进程，终止前请展开确认所属账户",
+        "Contains processes of other signed-in users; expand to check the account before terminating",
+    ),
+    ("未签名·TEMP", "Unsigned·TEMP"),
+    ("重启驱动", "Restart driver"),
+    (
+        "桌面合成 / 显卡驱动进程不可终止；画面卡死时重启显卡驱动 (Win+Ctrl+Shift+B)",
+        "Desktop composition / graphics driver processes cannot be terminated; if the screen freezes, restart the graphics driver (Win+Ctrl+Shift+B)",
+    ),
+    ("将连同其子进程一起终止", "Its child processes will be terminated too"),
+    ("进程数量", "Process count"),
+    ("各进程读写速率之和", "Sum of each process's read/write rates"),
+    (
+        "GPU 引擎占用 (与任务管理器口径一致)，显示此列时才采样",
+        "GPU engine usage (same as Task Manager); sampled only while this column is shown",
+    ),
+    ("确认终止？", "Confirm terminate?"),
+    ("终止全部", "Terminate all"),
+    (
+        "终止选中分组的全部进程；勾选了“连同子进程”时自底向上结束进程树",
+        "Terminate every process in the selected groups; with \"Include children\" checked, process trees are ended bottom-up",
+    ),
+    (
+        "暂停选中分组的全部进程，可在上方的挂起列表中逐个恢复；系统进程不会挂起",
+        "Pause every process in the selected groups; resume them one by one from the suspended list above. System processes are not suspended",
+    ),
+    ("优先级", "Priority"),
+    ("清除选择", "Clear selection"),
+    ("⏸ 已挂起 {} (PID {})", "⏸ Suspended {} (PID {})"),
+    ("❌ 挂起失败：{}", "❌ Suspend failed: {}"),
+    ("❌ 恢复失败：{}", "❌ Resume failed: {}"),
+    ("运行中...", "Running..."),
+    ("已结束 (退出码 {})", "Finished (exit code {})"),
+    ("🖥 控制台 — {} [{}]", "🖥 Console — {} [{}]"),
+    (
+        "结束 PID {} 后按原命令行与工作目录重新启动",
+        "Terminate PID {}, then relaunch with the original command line and working directory",
+    ),
+    ("未通过数字签名校验，且从临时目录运行：\n{}", "Failed signature verification and runs from a temp directory:\n{}"),
+    ("已选 {} 个分组 · {} 个进程", "{} groups selected · {} processes"),
+    // 网络、局域网、安全与启动项
+    ("{} 已将 {} 个进程的优先级设为“{}”", "{} Set the priority of {} processes to \"{}\""),
+    ("{} 已挂起 {} 个进程", "{} Suspended {} processes"),
+    ("，{} 个失败 (可能需要管理员权限)", ", {} failed (administrator rights may be required)"),
+    ("，跳过 {} 个系统进程", ", skipped {} system processes"),
+    ("文本表格", "text table"),
+    ("启动于", "Started"),
+    ("计费网络时阻止下列程序联网", "Block these programs on metered networks"),
+    (
+        "连接按流量计费 (手机热点等) 时用防火墙阻止名单内程序的出站连接，恢复后自动解除；需要管理员权限",
+        "On a metered connection (phone hotspot etc.) block outbound connections of the listed programs with the firewall, lifted automatically afterwards; requires administrator rights",
+    ),
+    ("● 计费连接", "● Metered connection"),
+    ("● 非计费连接", "● Unmetered connection"),
+    ("● 计费状态未知", "● Metered state unknown"),
+    ("进程名，逗号分隔，支持 * 通配", "Process names, comma separated, * wildcard supported"),
+    ("保存名单", "Save list"),
+    ("⏻ 唤醒", "⏻ Wake"),
+    (
+        "发送网络唤醒魔术包 (需与目标机在同一网段，且目标机已开启网络唤醒)",
+        "Send a Wake-on-LAN magic packet (the target must be on the same subnet with Wake-on-LAN enabled)",
+    ),
+    ("取消关机", "Cancel shutdown"),
+    ("从列表移除", "Remove from list"),
+    ("正在执行远程命令…", "Running remote command…"),
+    ("名称", "Name"),
+    ("主机名或 IP", "Host name or IP"),
+    ("MAC (唤醒用，可留空)", "MAC (for wake, optional)"),
+    ("添加", "Add"),
+    ("🌐 网络连接", "🌐 Network connections"),
+    ("端口，如 8080", "Port, e.g. 8080"),
+    ("终止进程", "Terminate process"),
+    ("断开", "Disconnect"),
+    ("保存", "Save"),
+    ("已配置", "Configured"),
+    ("未配置 (查询时改为打开网页)", "Not configured (lookups open the web page instead)"),
+    ("✏ 修改", "✏ Edit"),
+    (
+        "在 virustotal.com 注册后于个人资料页获取；免费 Key 每分钟可查询 4 次",
+        "Get it from your profile page after registering at virustotal.com; a free key allows 4 lookups per minute",
+    ),
+    ("🛡 安全检查", "🛡 Security check"),
+    ("正在扫描进程内存...", "Scanning process memory..."),
+    ("扫描进程", "Scan processes"),
+    (
+        "对比映像路径与 PEB、检查映像基址与私有 RWX 内存，识别进程镂空与代码注入",
+        "Compare image paths with the PEB and check image bases and private RWX memory to detect process hollowing and code injection",
+    ),
+    ("非管理员模式下无法检查系统与其他用户的进程", "System and other users' processes cannot be checked without administrator rights"),
+    ("✨ 未发现可疑进程", "✨ No suspicious processes found"),
+    ("🚀 启动项", "🚀 Startup items"),
+    ("开机自启来源:", "Autostart sources:"),
+    ("无", "None"),
+    ("(已禁用)", "(disabled)"),
+    ("去禁用", "Disable"),
+    ("服务管理器", "Services console"),
+    ("计算中…", "Computing…"),
+    ("复制", "Copy"),
+    (
+        "未配置 VirusTotal API Key：在浏览器中打开该哈希的 VirusTotal 页面 (Key 可在安全检查面板中设置)",
+        "No VirusTotal API key: open this hash's VirusTotal page in the browser (set the key in the Security panel)",
+    ),
+    (
+        "用 VirusTotal API 查询该哈希的检出率 (只发送哈希，不上传文件)",
+        "Query this hash's detection rate with the VirusTotal API (only the hash is sent, no file is uploaded)",
+    ),
+    ("查询", "Look up"),
+    ("📋 已复制 {} 行 ({})", "📋 Copied {} rows ({})"),
+    ("已阻止 {} 个程序", "{} programs blocked"),
+    ("🖧 局域网主机 ({})", "🖧 LAN hosts ({})"),
+    ("⏻ 已向 {} 发送唤醒包", "⏻ Sent a wake packet to {}"),
+    ("确认{}？", "Confirm {}?"),
+    (
+        "shutdown /m \\\\{}：{} 秒后{}，期间可取消；需要对方的管理员权限",
+        "shutdown /m \\\\{}: after {} s, {}; can be cancelled meanwhile; requires administrator rights on the target",
+    ),
+    ("✅ 已取消 {} 的关机", "✅ Cancelled the shutdown of {}"),
+    ("✅ {} 将在 {} 秒后{}", "✅ {}: in {} s, {}"),
+    ("{} / {} 条", "{} / {} entries"),
+    ("已断开 {}:{}", "Disconnected {}:{}"),
+    ("已检查 {} 个进程，{} 个无权访问，发现 {} 项", "Checked {} processes, {} inaccessible, {} findings"),
+    ("{} 项，已启用 {}", "{} items, {} enabled"),
+    ("已启用 {}", "Enabled {}"),
+    ("已禁用 {}", "Disabled {}"),
+    ("自动启动服务 · {}", "Automatic service · {}"),
+    ("无法读取：{}", "Cannot read: {}"),
+    // 分组覆盖、安装条目、插入与备份
+    ("点击在浏览器中查看详情", "Click to view details in the browser"),
+    ("分组覆盖:", "Group override:"),
+    ("显示名", "Display name"),
+    ("分类", "Category"),
+    ("自动识别", "Auto-detected"),
+    ("恢复自动", "Revert to auto"),
+    ("安装条目:", "Installed entry:"),
+    ("卸载该软件", "Uninstall this program"),
+    ("先结束该程序的所有进程，再启动它的卸载程序", "End all of this program's processes first, then launch its uninstaller"),
+    ("winget 升级", "winget upgrade"),
+    (
+        "结束该程序的所有进程后，通过 winget 升级到最新版本",
+        "End all of this program's processes, then upgrade to the latest version with winget",
+    ),
+    ("winget 卸载", "winget uninstall"),
+    ("结束该程序的所有进程后，通过 winget 卸载", "End all of this program's processes, then uninstall with winget"),
+    ("可用版本", "Available versions"),
+    ("可移动磁盘", "Removable disk"),
+    ("🔍 拷贝校验 (BLAKE3)", "🔍 Copy verification (BLAKE3)"),
+    ("本机文件夹", "Local folder"),
+    ("盘上文件夹", "Folder on drive"),
+    ("开始校验", "Start verification"),
+    ("逐个读取两边的文件计算哈希，弹出前确认拷贝完整", "Hash every file on both sides to confirm the copy is complete before ejecting"),
+    ("外部显示", "External display"),
+    (
+        "本机 JSON 接口，供 Stream Deck 插件、键盘小屏等硬件面板轮询显示",
+        "Local JSON endpoint for hardware panels such as Stream Deck plugins or keyboard screens to poll",
+    ),
+    ("启用", "Enable"),
+    ("端口", "Port"),
+    ("📋 复制地址", "📋 Copy URL"),
+    ("返回的字段 (也可在地址后加 ?fields=cpu,mem 按请求指定)", "Returned fields (or append ?fields=cpu,mem to the URL per request)"),
+    ("按路径保存，优先于自动识别：\n{}", "Saved by path, takes precedence over auto-detection:\n{}"),
+    ("已更新 {} 的分组覆盖，下一轮刷新生效", "Updated the group override for {}; takes effect on the next refresh"),
+    ("源：{}", "Source: {}"),
+    ("无 (本机安装)", "none (installed locally)"),
+    (" 等 {} 个", " of {}"),
+    ("可用版本：{}{}", "Available versions: {}{}"),
+    ("版本查询失败：{}", "Version lookup failed: {}"),
+    ("💾 已插入 {}: {} · 正在检查自动运行威胁…", "💾 Inserted {}: {} · checking for autorun threats…"),
+    ("💾 已插入 {}: {}", "💾 Inserted {}: {}"),
+    ("🔤 按设备偏好改为 {}:", "🔤 Changing to {}: per device preference"),
+    ("⏏ {}: {} 已移除", "⏏ {}: {} removed"),
+    ("🛡 {}: 未发现自动运行威胁", "🛡 {}: no autorun threats found"),
+    ("⚠ {}: {}，打开前请确认", "⚠ {}: {}; check before opening"),
+    ("⚠ {}: {} 等 {} 个可疑项，打开前请确认", "⚠ {}: {} and others, {} suspicious items; check before opening"),
+    ("🗄 {}: 开始备份 {} -> {}", "🗄 {}: backing up {} -> {}"),
+    ("✅ {}: 备份完成，{}", "✅ {}: backup finished, {}"),
+    ("⏏ {}: 备份完成，自动弹出", "⏏ {}: backup finished, ejecting"),
+    ("❌ {}: 备份失败，{}", "❌ {}: backup failed, {}"),
+    ("✅ {}: 校验通过，{}", "✅ {}: verification passed, {}"),
+    ("❌ {}: 校验发现问题，{}", "❌ {}: verification found problems, {}"),
+    ("❌ {}: 校验未完成，{}", "❌ {}: verification did not finish, {}"),
+    ("D:\\照片\\2024-05", "D:\\Photos\\2024-05"),
+    ("⏏ {}: 已空闲 {} 分钟，自动弹出", "⏏ {}: idle for {} min, ejecting"),
+    ("● 已处理 {} 次请求", "● {} requests served"),
+    // 访客、监测曲线、看门狗、操作宏、使用时长、对比与历史
+    ("密码", "Password"),
+    ("解锁", "Unlock"),
+    ("🔓 访客模式已结束", "🔓 Guest mode ended"),
+    ("条形", "Bars"),
+    ("热力图", "Heatmap"),
+    ("最近 10 分钟", "Last 10 minutes"),
+    ("接收", "Received"),
+    ("发送", "Sent"),
+    (
+        "名称支持 * 通配；系统进程不执行作用于进程的动作。触发时弹出托盘通知并写入审计日志",
+        "Names support * wildcards; process actions are never applied to system processes. Triggers show a tray notification and are written to the audit log",
+    ),
+    ("进程名，如 chrome*", "Process name, e.g. chrome*"),
+    ("持续", "for"),
+    (" 秒", " s"),
+    ("命令，如 notify.bat", "Command, e.g. notify.bat"),
+    (
+        "经 cmd /c 以普通用户权限运行 (不继承管理员权限)；环境变量 GK_PROCESS / GK_PIDS / GK_CPU 为触发的进程信息",
+        "Runs via cmd /c as a normal user (administrator rights are not inherited); GK_PROCESS / GK_PIDS / GK_CPU hold the triggering process info",
+    ),
+    ("盘符，如 E:", "Drive letter, e.g. E:"),
+    ("先扫描占用再弹出，与托盘菜单中的弹出相同", "Scan for blockers, then eject, the same as ejecting from the tray menu"),
+    ("➕ 添加规则", "➕ Add rule"),
+    ("放弃修改", "Discard changes"),
+    ("最近触发", "Recent triggers"),
+    (
+        "录制期间在界面上终止进程、弹出磁盘都会记为步骤；重启服务需手动添加。执行时逐步进行，失败的步骤不影响后续步骤",
+        "While recording, terminating processes and ejecting disks in the UI are recorded as steps; add service restarts by hand. Steps run in order and a failed step does not stop the rest",
+    ),
+    ("⏺ 录制中", "⏺ Recording"),
+    ("停止录制", "Stop recording"),
+    ("⏺ 开始录制", "⏺ Start recording"),
+    ("快捷键", "Hotkey"),
+    ("格式如 Ctrl+Alt+1，留空表示不绑定；保存后生效", "Format like Ctrl+Alt+1, leave empty for none; takes effect after saving"),
+    ("托盘菜单", "Tray menu"),
+    ("▶ 执行", "▶ Run"),
+    ("重启服务", "Restart service"),
+    ("服务名，如 Spooler", "Service name, e.g. Spooler"),
+    ("➕ 重启服务", "➕ Restart service"),
+    ("⏱ 使用时长", "⏱ Screen time"),
+    ("记录前台应用的使用时长", "Record foreground app usage time"),
+    (
+        "按前台窗口计时，键鼠空闲 5 分钟以上或锁屏时不计；数据只保存在本机的进程历史数据库",
+        "Timed by the foreground window; not counted after 5 minutes without input or while locked. Data stays in the local process history database",
+    ),
+    ("前一天", "Previous day"),
+    ("后一天", "Next day"),
+    ("今天", "Today"),
+    ("这一天没有记录", "No records for this day"),
+    ("⇄ 两台机器对比", "⇄ Compare two machines"),
+    ("📤 导出本机快照", "📤 Export local snapshot"),
+    (
+        "保存本机的进程与自动服务列表，拷到另一台机器上载入即可对比",
+        "Save this machine's process and automatic service lists; load them on another machine to compare",
+    ),
+    ("快照目录为空", "Snapshot folder is empty"),
+    ("从快照目录选择", "Choose from the snapshot folder"),
+    ("对比", "Compare"),
+    ("进程", "Process"),
+    ("本机", "Local"),
+    ("内存差", "Memory diff"),
+    ("本机独有的服务", "Services only on this machine"),
+    ("对方独有的服务", "Services only on the other machine"),
+    ("🕘 进程历史", "🕘 Process history"),
+    ("进程名或路径，如 install.exe", "Process name or path, e.g. install.exe"),
+    ("搜索", "Search"),
+    ("没有记录", "No records"),
+    ("结束", "Ended"),
+    ("峰值内存", "Peak memory"),
+    ("峰值 CPU", "Peak CPU"),
+    ("运行中", "Running"),
+    ("⚙ 高级：采集耗时", "⚙ Advanced: sampling cost"),
+    ("阶段", "Stage"),
+    ("平均", "Average"),
+    ("最大", "Max"),
+    ("进程刷新", "Process refresh"),
+    ("分组分类", "Grouping"),
+    ("整轮采集", "Whole round"),
+    ("界面帧", "UI frame"),
+    ("快照拷贝", "Snapshot copy"),
+    ("降低刷新频率", "Reduce refresh rate"),
+    (
+        "后台刷新间隔加倍 (默认 0.5 秒 -> 1 秒)，慢机器上减少 CPU 占用",
+        "Double the background refresh interval (0.5 s -> 1 s by default) to save CPU on slow machines",
+    ),
+    ("📋 复制耗时报告", "📋 Copy timing report"),
+    ("📋 已复制耗时报告", "📋 Timing report copied"),
+    ("🔒 访客模式 剩余 {}", "🔒 Guest mode, {} left"),
+    ("⏸ 数据已暂停 · {}", "⏸ Data paused · {}"),
+    ("⏳ 数据延迟 {}s · {}", "⏳ Data {}s behind · {}"),
+    ("快照采集于 {}，上一轮采集耗时 {} ms", "Snapshot taken at {}; the last round took {} ms"),
+    ("各核心占用 ({} 个逻辑处理器)", "Per-core usage ({} logical processors)"),
+    ("{} {}%\n{} 秒前", "{} {}%\n{} s ago"),
+    ("{} {} KB/s\n{} 秒前", "{} {} KB/s\n{} s ago"),
+    ("🐕 看门狗规则 ({} 条启用)", "🐕 Watchdog rules ({} enabled)"),
+    ("⏺ 操作宏 (录制中 · {} 步)", "⏺ Macros (recording · {} steps)"),
+    ("⏺ 操作宏 ({} 个)", "⏺ Macros ({})"),
+    ("宏 {}", "Macro {}"),
+    ("上次执行 · {}", "Last run · {}"),
+    ("正在执行 {} ({}/{})", "Running {} ({}/{})"),
+    ("合计 {} · {} 个应用", "Total {} · {} apps"),
+    ("📤 快照已保存到 {}", "📤 Snapshot saved to {}"),
+    ("对方快照路径，如 \\\\PC-2\\share\\PC-2.json", "Path of the other snapshot, e.g. \\\\PC-2\\share\\PC-2.json"),
+    ("本机 {} ⇄ {} (快照于 {})", "Local {} ⇄ {} (snapshot from {})"),
+    ("本机独有 {} 个进程", "{} processes only here"),
+    ("{} 独有 {} 个进程", "{}: {} processes only there"),
+    ("自动服务：两边都有 {} 个", "Automatic services: {} on both"),
+    ("📄 周报 ({})", "📄 Weekly report ({})"),
+    (
+        "汇总最近 {} 天的 CPU / 内存排行、网络流量、弹出记录与看门狗告警",
+        "Summarize the last {} days: CPU / memory rankings, network traffic, eject records and watchdog alerts",
+    ),
+    ("📄 周报已保存到 {}", "📄 Weekly report saved to {}"),
+    ("❌ 周报生成失败：{}", "❌ Weekly report failed: {}"),
+    ("{} 最近一次启动于 {}{}", "{} last started at {}{}"),
+    ("，仅显示最近 {} 条", ", showing the latest {}"),
+    ("，共 {} 条", ", {} in total"),
+    ("当前刷新间隔 {} ms", "Current refresh interval {} ms"),
+    // 挂载、格式化与设备偏好、U 盘卡片、遥测
+    ("❌ 截图超时，当前渲染后端可能不支持", "❌ Screenshot timed out; the current renderer may not support it"),
+    ("⚠ 修改挂载点需要管理员权限", "⚠ Changing mount points requires administrator rights"),
+    ("更改为", "Change to"),
+    ("更改盘符", "Change letter"),
+    ("移除盘符", "Remove letter"),
+    (
+        "卷不会被卸载，只是不再分配盘符；可在下方“未分配盘符的卷”中加回",
+        "The volume is not dismounted, it just loses its letter; add it back under \"Volumes without a letter\" below",
+    ),
+    ("挂载到", "Mount at"),
+    ("挂载", "Mount"),
+    ("卸下", "Unmount"),
+    ("卷标", "Label"),
+    ("改卷标", "Relabel"),
+    ("格式化为", "Format as"),
+    ("format 不支持把大于 32 GB 的卷格式化为 FAT32", "format cannot create FAT32 on volumes larger than 32 GB"),
+    ("新卷标", "New label"),
+    ("快速", "Quick"),
+    ("确认清除全部数据？", "Erase all data?"),
+    ("格式化", "Format"),
+    ("卷上的所有文件都会被删除；再点一次确认", "Every file on the volume will be deleted; click again to confirm"),
+    ("写入镜像", "Write image"),
+    (".img 或混合 ISO 的完整路径", "Full path of an .img or hybrid ISO"),
+    (
+        "适用于 Linux 发行版等混合 ISO 与 .img；Windows 安装 ISO 原样写入后无法启动",
+        "For hybrid ISOs such as Linux distributions and .img files; a Windows setup ISO written as-is will not boot",
+    ),
+    ("写后校验", "Verify after writing"),
+    ("确认覆盖整块磁盘？", "Overwrite the whole disk?"),
+    ("写入", "Write"),
+    (
+        "整块 U 盘 (含同一设备上的其他分区) 都会被镜像覆盖；再点一次确认",
+        "The whole USB drive (including other partitions on the device) will be overwritten by the image; click again to confirm",
+    ),
+    ("安全擦除", "Secure wipe"),
+    (" 遍", " passes"),
+    (
+        "依次以 0x00 / 0xFF / 随机覆写，最后一遍固定写零",
+        "Overwrite with 0x00 / 0xFF / random in turn; the last pass always writes zeros",
+    ),
+    ("校验", "Verify"),
+    ("擦除", "Wipe"),
+    (
+        "整块 U 盘 (含同一设备上的其他分区) 的数据将被覆写且无法恢复，完成后需重新分区或格式化。闪存的磨损均衡可能保留少量旧数据，涉密介质请物理销毁",
+        "All data on the whole USB drive (including other partitions on the device) will be overwritten and cannot be recovered; repartition or format afterwards. Flash wear leveling may keep some old data, so physically destroy media holding secrets",
+    ),
+    ("该设备没有序列号，无法按设备记住偏好", "This device has no serial number, so preferences cannot be remembered for it"),
+    ("📌 设备偏好 (下次插入自动应用)", "📌 Device preferences (applied on next insert)"),
+    ("昵称", "Nickname"),
+    ("如：公司加密盘", "e.g. Work encrypted drive"),
+    ("标识", "Badge"),
+    ("显示在昵称前的表情或符号，留空使用总线图标", "Emoji or symbol shown before the nickname; leave empty for the bus icon"),
+    ("自定义颜色", "Custom color"),
+    ("首选盘符", "Preferred letter"),
+    ("强力清场", "Force clear"),
+    ("跟随全局设置", "Follow global setting"),
+    ("先请求文档程序保存", "Ask document editors to save first"),
+    ("直接终止", "Terminate directly"),
+    ("保存等待", "Save wait"),
+    ("0 表示跟随全局设置", "0 follows the global setting"),
+    ("空闲自动弹出", "Auto-eject when idle"),
+    (" 分钟", " min"),
+    ("持续无读写达到该时长后自动安全弹出；0 表示关闭", "Safely eject automatically after this long without reads or writes; 0 turns it off"),
+    ("插入即备份", "Back up on insert"),
+    ("插入后把文件夹镜像到盘上", "Mirror a folder onto the drive when inserted"),
+    (
+        "robocopy /MIR：盘上的备份目录会与源文件夹保持一致，多余文件会被删除",
+        "robocopy /MIR: the backup folder on the drive mirrors the source folder and extra files are deleted",
+    ),
+    ("源文件夹", "Source folder"),
+    ("盘上目录", "Folder on drive"),
+    ("校验通过后自动弹出", "Eject automatically after verification passes"),
+    ("保存设备偏好", "Save device preferences"),
+    ("立即备份", "Back up now"),
+    ("✅ 设备偏好已保存", "✅ Device preferences saved"),
+    ("未分配盘符的卷", "Volumes without a letter"),
+    ("(无卷标)", "(no label)"),
+    ("终止此进程", "Terminate this process"),
+    ("模块", "Modules"),
+    ("列出已加载的 DLL：路径、版本与签名", "List loaded DLLs: path, version and signature"),
+    (
+        "StorageDevicePolicies\\WriteProtect：之后插入的所有 USB 存储均为只读，已插入的需重新插拔 (需要管理员权限)",
+        "StorageDevicePolicies\\WriteProtect: every USB storage device inserted afterwards is read-only; replug drives already inserted (requires administrator rights)",
+    ),
+    ("取消擦除", "Cancel wipe"),
+    ("当前块写完后停止；已擦除的部分无法恢复", "Stops after the current block; the part already wiped cannot be recovered"),
+    (
+        "占用来自资源管理器 (多为缩略图或压缩包预览)：让其正常退出后弹出，再重新启动资源管理器",
+        "Explorer is holding the drive (usually thumbnails or archive previews): let it exit cleanly, eject, then start Explorer again",
+    ),
+    (
+        "先列出将被终止的进程；确认后挂起它们并倒计时，期间可撤销",
+        "List the processes to be terminated first; after confirming they are suspended during a countdown that can be undone",
+    ),
+    ("🔒 只读", "🔒 Read-only"),
+    ("该卷以只读方式挂载，不会产生任何写入", "The volume is mounted read-only; nothing will be written"),
+    ("⚡ 写缓存", "⚡ Write caching"),
+    ("快速删除", "Quick removal"),
+    (
+        "该设备支持 USB 3.x，但当前以 2.0 速率运行。换到蓝色接口或更换线材可大幅提速。",
+        "This device supports USB 3.x but is running at 2.0 speed. A blue port or another cable can make it much faster.",
+    ),
+    ("⚠ 可提速", "⚠ Could be faster"),
+    ("建议先“只读挂载”，并用杀毒软件检查后再打开", "Consider \"Read-only mount\" first and scan with antivirus before opening"),
+    ("停止 robocopy，已复制的文件保留", "Stop robocopy; files already copied are kept"),
+    ("⏳ 刚刚还在写入，请稍候再弹出", "⏳ Still writing a moment ago; wait before ejecting"),
+    ("  仍要弹出？ ", "  Eject anyway? "),
+    ("  安全弹出  ", "  Safely eject  "),
+    ("正在处理该驱动器，请稍候...", "This drive is being processed, please wait..."),
+    (
+        "拷贝校验未通过或尚未完成，确认不需要这些文件后再点一次",
+        "Copy verification failed or has not finished; click again once you are sure these files are not needed",
+    ),
+    ("该盘几秒前还在写入，确认数据已拷贝完成后再点一次", "This drive was writing a few seconds ago; click again once the copy has finished"),
+    ("检查", "Check"),
+    ("chkdsk 只读检查文件系统错误 (不做修改)", "chkdsk read-only scan for file system errors (no changes)"),
+    ("测速", "Speed test"),
+    (
+        "绕过系统缓存顺序写入再读回最多 256 MB 临时文件，测得真实读写速度并核对读回的数据",
+        "Write then read back up to 256 MB of temp files bypassing the system cache to measure real speeds and check the data read back",
+    ),
+    ("仍要打开？", "Open anyway?"),
+    ("⚠ 打开", "⚠ Open"),
+    (
+        "根目录有可疑文件，不要双击其中的程序或快捷方式；再点一次确认打开",
+        "The root folder has suspicious files; don't double-click programs or shortcuts there. Click again to open",
+    ),
+    ("在资源管理器中打开 (弹出时会自动关闭这些窗口)", "Open in Explorer (these windows are closed automatically on eject)"),
+    ("只读挂载", "Read-only mount"),
+    (
+        "以只读方式重新挂载，浏览来路不明的 U 盘时不会写入任何数据 (拔出后失效，需要管理员权限)",
+        "Remount read-only so nothing is written while browsing an unknown drive (lost when unplugged; requires administrator rights)",
+    ),
+    ("🔒 写保护", "🔒 Write protect"),
+    (
+        "为整块磁盘设置持久的只读属性 (同 diskpart attributes disk set readonly)，本机重新插入后仍然只读；换到别的电脑不生效",
+        "Set a persistent read-only attribute on the whole disk (same as diskpart attributes disk set readonly); stays read-only when reinserted here but not on other PCs",
+    ),
+    ("设置", "Settings"),
+    ("盘符、挂载到文件夹、按设备记住的偏好", "Drive letter, folder mounts and per-device preferences"),
+    ("📈 弹出成功率统计", "📈 Eject success rates"),
+    ("📜 弹出历史", "📜 Eject history"),
+    ("暂无记录", "No records yet"),
+    ("🔍 智能诊断", "🔍 Diagnostics"),
+    ("⚠️ 资源紧张，已进入极简模式", "⚠️ Resources are tight; low-power mode is on"),
+    ("✨ 系统运行流畅", "✨ System running smoothly"),
+    (
+        "导出未收录进程的名称与文件描述，可分享给维护者或放入 packs 目录",
+        "Export names and file descriptions of unknown processes to share with the maintainers or drop into the packs folder",
+    ),
+    ("🧠 内存与页面文件", "🧠 Memory & page file"),
+    ("页面文件:", "Page file:"),
+    ("提交内存:", "Committed:"),
+    ("硬错误:", "Hard faults:"),
+    (
+        "下次重启时运行 Windows 内存诊断 (约 10-20 分钟)，用于排查随机蓝屏与程序崩溃",
+        "Run Windows Memory Diagnostic at next restart (about 10-20 minutes) to track down random blue screens and crashes",
+    ),
+    ("🧪 安排内存诊断", "🧪 Schedule memory diagnostic"),
+    (
+        "先修复组件存储 (DISM)，再检查系统文件 (sfc)，通常需要 10-30 分钟",
+        "Repair the component store (DISM), then check system files (sfc); usually takes 10-30 minutes",
+    ),
+    ("🛠 系统修复", "🛠 System repair"),
+    ("清除修复结果", "Clear repair results"),
+    ("📊 系统遥测面板", "📊 System telemetry"),
+    ("中央处理器 (CPU):", "Processor (CPU):"),
+    ("物理内存 (RAM):", "Physical memory (RAM):"),
+    ("网络流量 (NET):", "Network traffic (NET):"),
+    ("磁盘存储 (DISK):", "Disk storage (DISK):"),
+    ("磁盘健康 (SMART):", "Disk health (SMART):"),
+    ("不支持", "Not supported"),
+    ("🕒 最近启动", "🕒 Recently started"),
+    ("右键进程名可隐藏或取消隐藏分组", "Right-click a process name to hide or unhide its group"),
+    ("只显示有窗口卡死 (不再处理消息) 的程序", "Only show programs with a hung window (no longer processing messages)"),
+    (
+        "只显示含其他已登录用户进程的程序；展开后每个 PID 显示所属账户",
+        "Only show programs with processes of other signed-in users; expand to see each PID's account",
+    ),
+    ("🔥 极高负载任务", "🔥 Heavy tasks"),
+    ("❌ {} 不在窗口可见范围内，无法截图", "❌ {} is outside the visible window area and cannot be captured"),
+    ("📷 {} 已复制到剪贴板", "📷 {} copied to the clipboard"),
+    ("❌ {} 截图复制失败：{}", "❌ Failed to copy {} screenshot: {}"),
+    (" · 已保存 {}", " · saved {}"),
+    (" · 保存失败：{}", " · save failed: {}"),
+    ("NTFS 上的空文件夹，如 C:\\Mount\\U盘", "Empty folder on NTFS, e.g. C:\\Mount\\USB"),
+    ("输入 {} 确认", "Type {} to confirm"),
+    ("在左侧输入 {} 以确认", "Type {} on the left to confirm"),
+    ("总是使用 {}:", "Always use {}:"),
+    ("D:\\工作资料", "D:\\Work"),
+    ("备份到 {}", "Back up to {}"),
+    ("分配 {}:", "Assign {}:"),
+    ("启动 {}", "Started {}"),
+    ("只终止 PID {}", "Terminate only PID {}"),
+    ("{} 测速 · {} {}/{} MB · {} MB/s", "{} speed test · {} {}/{} MB · {} MB/s"),
+    ("删除策略：{}\n{}", "Removal policy: {}\n{}"),
+    ("\n已改为“{}”，重新插拔后生效", "\nChanged to \"{}\"; takes effect after replugging"),
+    ("\n点击切换为“{}” (需要管理员权限)", "\nClick to switch to \"{}\" (requires administrator rights)"),
+    ("{} → 待生效", "{} → pending"),
+    ("⚠ 根目录有 {} 个可疑项，打开前请确认", "⚠ {} suspicious items in the root folder; check before opening"),
+    ("🗄 {} · {}/{} 个文件", "🗄 {} · {}/{} files"),
+    ("名称：{}\nVID/PID：{}\n序列号：{}\n连接：{}", "Name: {}\nVID/PID: {}\nSerial: {}\nConnection: {}"),
+    ("⏱ 写 {} MB/s · 读 {} MB/s", "⏱ Write {} MB/s · Read {} MB/s"),
+    ("✍ 写入 {} MB/s · 读取 {} MB/s", "✍ Writing {} MB/s · Reading {} MB/s"),
+    ("✅ 可以安全弹出 (上次写入 {} 秒前)", "✅ Safe to eject (last write {} s ago)"),
+    ("全部方法均失败：{} 次", "All methods failed: {} times"),
+    ("卷序列号：{}\n设备：{}", "Volume serial: {}\nDevice: {}"),
+    ("📤 提交未知进程 ({})", "📤 Submit unknown processes ({})"),
+    ("已导出到 {}", "Exported to {}"),
+    ("❌ 导出失败：{}", "❌ Export failed: {}"),
+    ("{} 页/秒 (10 分钟内风暴 {} 次)", "{} pages/s ({} storms in 10 min)"),
+    ("{}GB 可用 / {}GB 总计", "{}GB free / {}GB total"),
+    ("显示已隐藏 ({})", "Show hidden ({})"),
+    ("只看无响应 ({})", "Only not responding ({})"),
+    ("只看其他用户 ({})", "Only other users ({})"),
+    ("👤 活动用户任务 ({})", "👤 User tasks ({})"),
+    ("🛡️ 系统核心服务 ({})", "🛡️ Core system services ({})"),
+    // U 盘卡片 (续)
+    ("🔗 与 {} 同属一个物理设备，弹出时一并卸载", "🔗 Same physical device as {}; they are unmounted together on eject"),
+    // 周报
+    ("运行次数", "Runs"),
+    ("累计时长", "Total time"),
+    ("网络流量", "Network traffic"),
+    ("弹出记录", "Eject records"),
+    ("时间", "Time"),
+    ("驱动器", "Drive"),
+    ("方式", "Method"),
+    ("结果", "Result"),
+    ("结束的进程", "Terminated processes"),
+    ("成功", "Succeeded"),
+    ("看门狗告警", "Watchdog alerts"),
+    ("内容", "Details"),
+    ("CPU 占用最高", "Top CPU usage"),
+    ("内存占用最高", "Top memory usage"),
+    ("无法定位数据目录", "Cannot locate the data folder"),
+    ("期间没有进程记录", "No process records in this period"),
+    ("无法读取进程历史：{}", "Cannot read process history: {}"),
+    ("接收 {}，发送 {}，合计 {}", "Received {}, sent {}, total {}"),
+    ("无法读取流量记录：{}", "Cannot read traffic records: {}"),
+    ("共 {} 次，失败 {} 次，为弹出结束进程 {} 个{}", "{} in total, {} failed, {} processes terminated for ejecting{}"),
+    ("；仅列出最近 {} 次", "; only the latest {} are listed"),
+    ("失败：{}", "Failed: {}"),
+    ("共 {} 次{}", "{} in total{}"),
+    ("{} 周报 ({} ~ {})", "{} weekly report ({} ~ {})"),
+    // 看门狗
+    ("通知", "Notify"),
+    ("降低优先级", "Lower priority"),
+    ("裁剪内存", "Trim memory"),
+    ("运行脚本", "Run script"),
+    ("弹出磁盘", "Eject drive"),
+    ("受保护进程，仅通知", "Protected process, notify only"),
+    ("未填写命令", "No command entered"),
+    ("访客模式中，仅通知", "Guest mode active, notify only"),
+    ("已通知", "Notified"),
+    ("未填写盘符", "No drive letter entered"),
+    ("{} CPU > {}% 持续 {} 秒 -> {}{}", "{} CPU > {}% for {} s -> {}{}"),
+    ("{} CPU {}% 持续 {} 秒 -> {}：{}", "{} CPU {}% for {} s -> {}: {}"),
+    ("看门狗", "Watchdog"),
+    ("已启动 {}", "Started {}"),
+    ("无法运行脚本：{}", "Cannot run script: {}"),
+    ("已请求弹出 {}", "Eject requested for {}"),
+    ("{}/{} 个进程已裁剪，工作集减少 {} MB", "{}/{} processes trimmed, working set reduced by {} MB"),
+    ("{}/{} 个进程成功", "{}/{} processes succeeded"),
+    // 只读挂载与系统修复
+    ("无法打开 {}：{}", "Cannot open {}: {}"),
+    ("{}: 已切换为只读挂载 (拔出后失效)", "{}: remounted read-only (lost when unplugged)"),
+    ("{}: 已恢复可写", "{}: writable again"),
+    ("{}: 已开启写保护 (本机记住，重新插入后仍只读)", "{}: write protection on (remembered on this PC; stays read-only when reinserted)"),
+    ("{}: 已解除写保护", "{}: write protection removed"),
+    ("刷新 {} 的写入缓存失败：{}", "Failed to flush the write cache of {}: {}"),
+    ("{} 正被其他程序使用，无法独占锁定；请先关闭占用程序", "{} is in use by another program and cannot be locked; close it first"),
+    ("卸载 {} 失败：{}", "Failed to dismount {}: {}"),
+    ("无法确定所在物理磁盘", "Cannot determine the physical disk"),
+    ("设置磁盘属性失败：{}", "Failed to set disk attributes: {}"),
+    (
+        "无法打开 StorageDevicePolicies (错误 {})，需要管理员权限",
+        "Cannot open StorageDevicePolicies (error {}); requires administrator rights",
+    ),
+    ("写入 WriteProtect 失败 (错误 {})", "Failed to write WriteProtect (error {})"),
+    ("已开启全局 USB 写保护，重新插拔后生效", "Global USB write protection on; takes effect after replugging"),
+    ("已关闭全局 USB 写保护，重新插拔后恢复可写", "Global USB write protection off; drives are writable after replugging"),
+    ("DISM 组件存储修复", "DISM component store repair"),
+    ("SFC 系统文件检查", "SFC system file check"),
+    (
+        "找不到修复源文件，请联网或挂载同版本 ISO 后重试 (/Source)",
+        "Repair source files not found; connect to the internet or mount an ISO of the same version and retry (/Source)",
+    ),
+    ("组件存储的损坏已修复", "Component store corruption repaired"),
+    ("组件存储健康", "Component store is healthy"),
+    ("DISM 已完成", "DISM finished"),
+    ("未发现系统文件损坏", "No system file corruption found"),
+    ("发现损坏文件并已全部修复，建议重启", "Corrupt files found and all repaired; restart recommended"),
+    ("部分文件无法修复，详见 %WINDIR%\\Logs\\CBS\\CBS.log", "Some files could not be repaired; see %WINDIR%\\Logs\\CBS\\CBS.log"),
+    ("sfc 无法执行，可尝试在安全模式下运行", "sfc could not run; try it in Safe Mode"),
+    ("有挂起的系统修复，请先重启", "A system repair is pending; restart first"),
+    ("退出码 {}", "exit code {}"),
+    ("DISM 失败 ({})", "DISM failed ({})"),
+    ("sfc 已结束 (退出码 {})，未能识别结果", "sfc ended (exit code {}) with an unrecognized result"),
+    // 镜像写入、SMART 与启动项
+    ("正在{}镜像 {}% · {} MB/s", "{} image {}% · {} MB/s"),
+    ("无法打开卷 {}：{}", "Cannot open volume {}: {}"),
+    ("无法读取磁盘容量：{}", "Cannot read disk capacity: {}"),
+    ("目标与系统盘位于同一物理磁盘，已拒绝", "Refused: the target is on the same physical disk as the system drive"),
+    ("无法打开 PhysicalDrive{}：{}", "Cannot open PhysicalDrive{}: {}"),
+    ("无法打开镜像 {}：{}", "Cannot open image {}: {}"),
+    ("镜像文件为空", "The image file is empty"),
+    ("镜像 {} GB 大于磁盘容量 {} GB", "The {} GB image is larger than the {} GB disk"),
+    ("读取镜像失败：{}", "Failed to read the image: {}"),
+    ("写入偏移 {} MB 处失败：{}", "Write failed at offset {} MB: {}"),
+    ("读回偏移 {} MB 处失败：{}", "Read-back failed at offset {} MB: {}"),
+    (
+        "校验失败：偏移 {} MB 处的数据与镜像不一致，U 盘可能已损坏",
+        "Verification failed: data at offset {} MB does not match the image; the drive may be damaged",
+    ),
+    ("刷新写入缓存失败：{}", "Failed to flush the write cache: {}"),
+    ("{}: 已写入 {} MB{}", "{}: wrote {} MB{}"),
+    ("，校验通过", ", verified"),
+    ("良好", "Good"),
+    ("注意", "Warning"),
+    ("危险", "Failing"),
+    ("固件报告即将故障", "Firmware predicts imminent failure"),
+    ("{} 个待映射扇区", "{} pending sectors"),
+    ("{} 个无法校正的扇区", "{} uncorrectable sectors"),
+    ("{} 次介质错误", "{} media errors"),
+    ("已重映射 {} 个扇区", "{} sectors reallocated"),
+    ("剩余寿命 {}%", "{}% life left"),
+    ("温度过高 {}°C", "Temperature too high {}°C"),
+    ("重映射 {}", "Reallocated {}"),
+    ("介质错误 {}", "Media errors {}"),
+    ("寿命 {}%", "Life {}%"),
+    ("预警", "Failure predicted"),
+    ("无异常", "No issues"),
+    ("无法打开 PhysicalDrive{}", "Cannot open PhysicalDrive{}"),
+    (
+        "设备不支持 SMART (多数 U 盘 / 读卡器不转发)",
+        "The device does not support SMART (most USB drives and card readers do not pass it through)",
+    ),
+    ("HKLM Run (32 位)", "HKLM Run (32-bit)"),
+    ("启动文件夹", "Startup folder"),
+    ("公共启动文件夹", "Common Startup folder"),
+    ("计划任务", "Scheduled task"),
+    ("高影响", "High impact"),
+    ("中等影响", "Medium impact"),
+    ("低影响", "Low impact"),
+    ("当前未运行", "Not running"),
+    ("计划任务需通过 schtasks 修改", "Scheduled tasks must be changed with schtasks"),
+    ("需要管理员权限", "Requires administrator rights"),
+    ("无法打开注册表 (错误 {})", "Cannot open the registry (error {})"),
+    ("写入失败 (错误 {})", "Write failed (error {})"),
+    ("启用计划任务", "Enable scheduled task"),
+    ("禁用计划任务", "Disable scheduled task"),
+    // 打印后台、挂载点、外部命令与操作宏
+    ("{} · {} ({}，{} 页)", "{} · {} ({}, {} pages)"),
+    ("无法打开打印机：{}", "Cannot open the printer: {}"),
+    ("队列中共 {} 个作业", "{} jobs in the queue"),
+    ("已移除：{}", "Removed: {}"),
+    ("无法移除：{} ({})", "Cannot remove: {} ({})"),
+    ("停止 Print Spooler：{}", "Stop Print Spooler: {}"),
+    ("无法停止 Print Spooler：{}", "Cannot stop Print Spooler: {}"),
+    ("清空 {}：删除 {} 个文件", "Clear {}: deleted {} files"),
+    ("无法删除 {}", "Cannot delete {}"),
+    ("可能未移除：{}", "May not have been removed: {}"),
+    ("启动 Print Spooler：{}", "Start Print Spooler: {}"),
+    ("Print Spooler 未能启动：{}", "Print Spooler failed to start: {}"),
+    ("打印后台已重置，移除 {} 个作业 / {} 个残留文件", "Print spooler reset; removed {} jobs / {} leftover files"),
+    ("盘符 {} 已被占用", "Drive letter {} is already in use"),
+    ("无法获取 {} 的卷名", "Cannot get the volume name of {}"),
+    ("移除 {} 失败：{}", "Failed to remove {}: {}"),
+    ("分配 {} 失败：{}", "Failed to assign {}: {}"),
+    ("{} 已改为 {}", "{} changed to {}"),
+    ("请填写目标文件夹", "Enter a target folder"),
+    ("无法创建文件夹：{}", "Cannot create the folder: {}"),
+    ("挂载目标必须是 NTFS 上的空文件夹", "The mount target must be an empty folder on NTFS"),
+    ("无法获取卷名", "Cannot get the volume name"),
+    ("挂载失败：{}", "Mount failed: {}"),
+    ("{}: 已挂载到 {}", "{}: mounted at {}"),
+    ("{}: 盘符已移除，可在“未分配盘符的卷”中重新分配", "{}: drive letter removed; reassign it under \"Volumes without a letter\""),
+    ("已分配盘符 {}", "Assigned drive letter {}"),
+    ("已卸下 {}", "Unmounted {}"),
+    ("无法启动：{}", "Cannot start: {}"),
+    ("已取消", "Cancelled"),
+    ("无法启动 {}: {}", "Cannot start {}: {}"),
+    ("终止 {}", "Terminate {}"),
+    ("重启服务 {}", "Restart service {}"),
+    ("{}：{} 步完成", "{}: {} steps done"),
+    ("{}：{} 步完成，{} 步失败", "{}: {} steps done, {} failed"),
+    ("本会话中没有可终止的进程", "No processes to terminate in this session"),
+    ("已终止 {} 个进程", "Terminated {} processes"),
+    ("{}/{} 个进程未退出{}", "{}/{} processes did not exit{}"),
+    ("，可能需要管理员权限", "; administrator rights may be required"),
+    ("停止失败：{}", "Stop failed: {}"),
+    ("启动失败：{}", "Start failed: {}"),
+    ("已重启", "Restarted"),
+    // 访客模式、信誉查询、外部显示、插入备份、内存建议与安全擦除
+    ("请设置解锁密码", "Set an unlock password"),
+    ("时长需在 1 到 {} 小时之间", "Duration must be between 1 and {} hours"),
+    ("无法保存状态：{}", "Cannot save state: {}"),
+    ("状态不可用", "State unavailable"),
+    ("密码错误", "Wrong password"),
+    ("无法初始化 SHA-256", "Cannot initialize SHA-256"),
+    ("计算 SHA-256 失败", "SHA-256 computation failed"),
+    ("检出 {} / {} (另有 {} 个可疑)", "{} / {} detections ({} more suspicious)"),
+    ("检出 {} / {}", "{} / {} detections"),
+    ("VirusTotal 上没有该文件的记录", "VirusTotal has no record of this file"),
+    ("无法连接 VirusTotal：{}", "Cannot connect to VirusTotal: {}"),
+    ("API Key 无效", "Invalid API key"),
+    ("查询过于频繁 (免费 Key 每分钟 4 次)", "Too many lookups (free keys allow 4 per minute)"),
+    ("VirusTotal 返回 HTTP {}", "VirusTotal returned HTTP {}"),
+    ("无法解析结果：{}", "Cannot parse the result: {}"),
+    ("CPU 占用 (%)", "CPU usage (%)"),
+    ("内存占用 (%)", "Memory usage (%)"),
+    ("已用内存 (MB)", "Memory used (MB)"),
+    ("下载速率 (KB/s)", "Download rate (KB/s)"),
+    ("上传速率 (KB/s)", "Upload rate (KB/s)"),
+    ("CPU 占用最高的进程", "Top CPU process"),
+    ("该进程的 CPU (%)", "That process's CPU (%)"),
+    ("可移动磁盘数", "Removable drives"),
+    ("进程数", "Processes"),
+    ("资源紧张", "Resources tight"),
+    ("无法监听端口 {}：{}", "Cannot listen on port {}: {}"),
+    ("准备中", "Preparing"),
+    ("robocopy 预演", "robocopy dry run"),
+    ("robocopy 镜像备份", "robocopy mirror backup"),
+    ("源文件夹不存在：{}", "Source folder does not exist: {}"),
+    ("统计需要复制的文件", "Counting files to copy"),
+    ("正在复制 {} 个文件", "Copying {} files"),
+    ("robocopy 有文件复制失败 (退出码 {})", "robocopy failed to copy some files (exit code {})"),
+    ("BLAKE3 校验", "BLAKE3 verification"),
+    ("校验失败：{}，如 {}", "Verification failed: {}, e.g. {}"),
+    ("已同步 {} 个文件 ({} MB)，校验 {}", "Synced {} files ({} MB), verification {}"),
+    ("无法读取", "Unreadable"),
+    ("未启用", "Disabled"),
+    ("系统管理大小", "System managed"),
+    ("自定义 {} - {} MB", "Custom {} - {} MB"),
+    (
+        "未启用页面文件：内存耗尽时程序会直接崩溃，且无法生成蓝屏转储。建议改为“系统管理的大小”",
+        "No page file: programs crash outright when memory runs out and no crash dump can be written. Switch to \"System managed size\"",
+    ),
+    (
+        "页面文件上限仅 {} MB，不足以保存内核转储，建议至少 1 GB",
+        "The page file is capped at {} MB, too small for a kernel dump; use at least 1 GB",
+    ),
+    (
+        "内存 {} GB 偏小而页面文件上限只有 {} MB，容易出现“内存不足”提示，建议不低于物理内存大小",
+        "{} GB of RAM is small and the page file is capped at {} MB, so \"out of memory\" warnings are likely; make it at least the size of physical memory",
+    ),
+    ("无法读取页面文件配置", "Cannot read the page file configuration"),
+    (
+        "提交内存已用 {}%，即将触发“内存不足”，请关闭大型程序或增大页面文件",
+        "{}% of commit memory used and \"out of memory\" is imminent; close large programs or enlarge the page file",
+    ),
+    (
+        "最近 10 分钟出现 {} 次硬错误风暴：物理内存不足，系统在频繁读写页面文件。建议减少常驻程序或加装内存",
+        "{} hard fault storms in the last 10 minutes: physical memory is short and the system is paging heavily. Run fewer background programs or add RAM",
+    ),
+    ("正在发生大量硬错误 (换页)，卡顿可能来自内存不足", "Heavy hard faults (paging) right now; stutter may come from low memory"),
+    ("Windows 内存诊断", "Windows Memory Diagnostic"),
+    ("随机", "Random"),
+    ("正在擦除 第 {}/{} 遍 ({})", "Wiping pass {}/{} ({})"),
+    ("正在校验", "Verifying"),
+    (
+        "{}: 已取消，磁盘处于部分擦除状态，需要重新分区或再次擦除",
+        "{}: cancelled; the disk is partially wiped and needs repartitioning or another wipe",
+    ),
+    ("第 {} 遍写入偏移 {} MB 处失败：{}", "Pass {} write failed at offset {} MB: {}"),
+    ("校验失败：偏移 {} MB 处仍有残留数据，磁盘可能已损坏", "Verification failed: data remains at offset {} MB; the disk may be damaged"),
+    (
+        "{} (PhysicalDrive{}): 已覆写 {} 遍 {} GB{}；请重新分区或格式化后使用",
+        "{} (PhysicalDrive{}): overwritten {} times, {} GB{}; repartition or format before use",
+    ),
+    // 格式化、计费网络、截图、拾取、清场、删除策略与测速
+    ("{} 卷标最多 {} 个字符", "{} labels are at most {} characters"),
+    ("卷标不能包含 '{}'", "The label cannot contain '{}'"),
+    ("格式化 {}", "Format {}"),
+    ("无法启动 format：{}", "Cannot start format: {}"),
+    ("{} 已格式化为 {}", "{} formatted as {}"),
+    ("{} 格式化失败：{}", "Formatting {} failed: {}"),
+    ("{} 修改卷标失败：{}", "Failed to relabel {}: {}"),
+    ("{} 已清除卷标", "{} label cleared"),
+    ("{} 卷标已改为 {}", "{} label changed to {}"),
+    ("无法读取网络计费状态 (HRESULT 0x{})", "Cannot read the network cost state (HRESULT 0x{})"),
+    ("阻止程序联网需要以管理员身份运行", "Blocking programs from the network requires running as administrator"),
+    ("计费网络", "Metered network"),
+    ("当前连接按流量计费，已阻止 {} 联网", "The current connection is metered; blocked {} from the network"),
+    ("无法阻止 {}：{}", "Cannot block {}: {}"),
+    ("无法移除防火墙规则：{}", "Cannot remove the firewall rule: {}"),
+    ("外部存储管理", "External storage"),
+    ("系统遥测", "System telemetry"),
+    ("无法分配剪贴板内存", "Cannot allocate clipboard memory"),
+    ("剪贴板被占用：{}", "The clipboard is busy: {}"),
+    ("写入剪贴板失败：{}", "Failed to write to the clipboard: {}"),
+    ("图像尺寸错误", "Invalid image size"),
+    ("无法安装鼠标钩子", "Cannot install the mouse hook"),
+    ("已取消拾取", "Picking cancelled"),
+    ("该位置没有窗口", "No window at that position"),
+    ("无法获取窗口所属进程", "Cannot get the window's process"),
+    ("点中的是本程序自己的窗口", "That is this program's own window"),
+    ("系统不支持挂起进程", "The system does not support suspending processes"),
+    ("无法打开进程 {} (可能需要管理员权限)", "Cannot open process {} (administrator rights may be required)"),
+    ("已退出", "Exited"),
+    ("拒绝访问", "Access denied"),
+    ("未退出", "Did not exit"),
+    ("失败", "Failed"),
+    ("更好的性能", "Better performance"),
+    ("固定磁盘", "Fixed disk"),
+    ("未开启写缓存，复制完成即可拔出；写入速度稍慢", "Write caching off: unplug as soon as copying finishes; writes are a bit slower"),
+    (
+        "已开启写缓存：必须先安全弹出再拔，否则可能丢数据；弹出也更容易被占用否决",
+        "Write caching on: safely eject before unplugging or data may be lost; ejecting is also more easily vetoed by open files",
+    ),
+    ("系统视其为固定磁盘", "Windows treats it as a fixed disk"),
+    ("无法定位所在磁盘设备", "Cannot locate the disk device"),
+    (
+        "{} 设置删除策略失败 (CONFIGRET {})，需要管理员权限",
+        "Failed to set the removal policy of {} (CONFIGRET {}); requires administrator rights",
+    ),
+    ("{} 删除策略改为“{}”，重新插拔后生效", "{} removal policy changed to \"{}\"; takes effect after replugging"),
+    ("读取", "Read"),
+    ("{} 顺序写 {} MB/s · 顺序读 {} MB/s ({} MB)", "{} sequential write {} MB/s · sequential read {} MB/s ({} MB)"),
+    (" · ⚠ {} 个扇区读回不一致，U 盘可能有坏块", " · ⚠ {} sectors read back differently; the drive may have bad blocks"),
+    ("无法分配缓冲区", "Cannot allocate the buffer"),
+    ("无法创建测试文件：{}", "Cannot create the test file: {}"),
+    ("{} 剩余空间不足，至少需要 {} MB", "{} does not have enough free space; at least {} MB is needed"),
+    ("写入失败：{}", "Write failed: {}"),
+    ("读取失败：{}", "Read failed: {}"),
+    // 资源管理器、快捷键、远程电源、签名、采集耗时、降权启动、网络重置、进程属主与优先级、临时文件
+    ("资源管理器 (PID {}) 无法结束", "Explorer (PID {}) could not be terminated"),
+    (
+        "未取得用户令牌 ({})，为避免外壳以管理员身份运行未自动重启，请在任务管理器中运行 explorer.exe",
+        "No user token ({}); to avoid running the shell as administrator it was not restarted automatically. Run explorer.exe from Task Manager",
+    ),
+    ("无法重新启动资源管理器：{}", "Cannot restart Explorer: {}"),
+    ("弹出最近插入的 U 盘", "Eject the most recently inserted drive"),
+    ("执行操作宏", "Run macro"),
+    ("“{}”包含多个按键", "\"{}\" contains more than one key"),
+    ("无法识别的按键“{}”", "Unrecognized key \"{}\""),
+    ("“{}”缺少按键", "\"{}\" is missing a key"),
+    ("“{}”至少需要 Ctrl / Alt / Shift / Win 之一", "\"{}\" needs at least one of Ctrl / Alt / Shift / Win"),
+    ("{} 已被其他程序占用", "{} is already used by another program"),
+    ("关机", "Shut down"),
+    ("MAC 地址格式不正确：{}", "Invalid MAC address: {}"),
+    ("发送唤醒包失败：{}", "Failed to send the wake-up packet: {}"),
+    ("{} 未填写主机名或 IP", "{} has no host name or IP"),
+    ("Geek Killer 远程{}，{} 秒后执行", "Geek Killer remote action: {} in {} seconds"),
+    ("远程{}", "Remote: {}"),
+    ("已签名", "Signed"),
+    ("未签名", "Unsigned"),
+    ("签名无效：{}", "Invalid signature: {}"),
+    ("文件内容与签名不符 (可能被篡改)", "File content does not match the signature (possibly tampered with)"),
+    ("证书已被明确禁止", "The certificate is explicitly distrusted"),
+    ("证书链不受信任", "The certificate chain is not trusted"),
+    ("证书已过期", "The certificate has expired"),
+    ("证书已吊销", "The certificate has been revoked"),
+    ("校验失败 0x{}", "Verification failed 0x{}"),
+    (
+        "每轮采集平均 {} ms，占刷新间隔 {} ms 的 {}%，建议降低刷新频率",
+        "Each sampling round averages {} ms, {} ms interval ({}%); lower the refresh rate",
+    ),
+    ("界面每帧平均 {} ms，建议降低刷新频率并收起不用的面板", "Frames average {} ms; lower the refresh rate and collapse unused panels"),
+    ("刷新间隔 {} ms\r\n", "Refresh interval {} ms\r\n"),
+    ("{}: 平均 {} ms, 最大 {} ms\r\n", "{}: avg {} ms, max {} ms\r\n"),
+    ("资源管理器外壳未运行", "The Explorer shell is not running"),
+    ("无法打开外壳进程 (错误 {})", "Cannot open the shell process (error {})"),
+    ("无法读取外壳令牌 (错误 {})", "Cannot read the shell token (error {})"),
+    ("无法复制外壳令牌 (错误 {})", "Cannot duplicate the shell token (error {})"),
+    ("CreateProcessWithTokenW 失败 (错误 {})", "CreateProcessWithTokenW failed (error {})"),
+    ("留存当前配置", "Save current configuration"),
+    ("释放 IP 地址", "Release IP address"),
+    ("重新获取 IP 地址", "Renew IP address"),
+    ("清空 DNS 缓存", "Flush DNS cache"),
+    ("重置 Winsock", "Reset Winsock"),
+    ("已重置，重启计算机后生效", "Reset; takes effect after restarting the computer"),
+    ("完成", "Done"),
+    ("不受信任", "Untrusted"),
+    ("低", "Low"),
+    ("中", "Medium"),
+    ("高", "High"),
+    ("系统", "System"),
+    ("浏览器", "Browser"),
+    ("通讯", "Messaging"),
+    ("办公", "Office"),
+    ("开发", "Development"),
+    ("游戏", "Games"),
+    ("驱动", "Drivers"),
+    ("第三方应用", "Third-party apps"),
+    ("应用程序", "Applications"),
+    ("{} · 会话 {}", "{} · session {}"),
+    (" · 完整性 {}", " · integrity {}"),
+    (" (管理员)", " (administrator)"),
+    ("低于正常", "Below normal"),
+    ("正常", "Normal"),
+    ("高于正常", "Above normal"),
+    ("{} (PID {}) 拒绝修改优先级", "{} (PID {}) refused the priority change"),
+    ("{}：删除 {} 个文件，释放 {}", "{}: deleted {} files, freed {}"),
+    ("；{} 个正在使用", "; {} in use"),
+    ("；{} 个最近修改，保留", "; {} recently modified, kept"),
+    ("；{} 个无权限", "; {} access denied"),
+    ("已跳过 {} 个被 {} 占用的文件", "Skipped {} files held by {}"),
+    ("另有 {} 个被占用的文件未查询占用进程", "{} more files in use whose holders were not looked up"),
+    ("共释放 {}，删除 {} 个文件，跳过 {} 个正在使用的文件", "Freed {} in total, deleted {} files, skipped {} files in use"),
+    // 拷贝校验、进程内存、重启进程、声音修复、自动运行、句柄、内存整理、进程对比、安全检查与服务控制
+    ("{} 个文件 ({} MB) 全部一致", "All {} files ({} MB) match"),
+    ("{} 个不一致，{} 个缺失，{} 个无法读取 (共 {} 个文件)", "{} mismatched, {} missing, {} unreadable ({} files in total)"),
+    ("目标文件夹不存在：{}", "Target folder does not exist: {}"),
+    ("校验线程异常退出", "The verification thread exited abnormally"),
+    ("工作集", "Working set"),
+    ("私有工作集", "Private working set"),
+    ("提交大小", "Commit size"),
+    (
+        "驻留物理内存的全部页面，含与其他进程共享的部分 (多进程应用会重复计算)",
+        "All pages resident in physical memory, including those shared with other processes (multi-process apps are counted more than once)",
+    ),
+    (
+        "驻留物理内存、只属于该进程的页面，最接近结束进程后能释放的内存",
+        "Resident pages that belong only to this process; closest to what terminating it would free",
+    ),
+    ("已提交的私有内存 (私有字节)，含已换出到页面文件的部分", "Committed private memory (private bytes), including what has been paged out"),
+    ("工作集 {} MB\n私有工作集 {} MB\n提交大小 {} MB", "Working set {} MB\nPrivate working set {} MB\nCommit size {} MB"),
+    ("无法读取进程信息 (可能需要管理员权限)", "Cannot read process information (administrator rights may be required)"),
+    ("无法读取命令行 (位数不同或受保护的进程)", "Cannot read the command line (different bitness or a protected process)"),
+    ("无法结束进程", "Cannot terminate the process"),
+    ("进程未在 5 秒内退出", "The process did not exit within 5 seconds"),
+    ("重新启动失败：{}", "Relaunch failed: {}"),
+    ("停止 {}：{}", "Stop {}: {}"),
+    ("结束 audiodg.exe：{}/{}", "Terminate audiodg.exe: {}/{}"),
+    ("启动 {}：{}", "Start {}: {}"),
+    ("音频服务已重新运行，请重新播放声音测试", "Audio services are running again; play a sound to test"),
+    ("{} 未能启动", "{} failed to start"),
+    ("{}：自动运行配置文件", "{}: autorun configuration file"),
+    ("{}：尝试自动运行 {}", "{}: tries to autorun {}"),
+    ("{}：被隐藏的可执行文件", "{}: hidden executable"),
+    ("{}：伪装成文档的可执行文件", "{}: executable disguised as a document"),
+    ("快捷方式会调用 {}", "The shortcut invokes {}"),
+    ("冒充被隐藏的同名文件夹", "Impersonates a hidden folder with the same name"),
+    ("📝 文档", "📝 Document"),
+    ("🗒 日志/临时", "🗒 Log/temp"),
+    ("📄 文件", "📄 File"),
+    ("可能正在编辑，强制关闭会丢失未保存的修改", "May be open for editing; force-closing loses unsaved changes"),
+    ("日志或临时文件，强制关闭通常无害", "Log or temp file; force-closing is usually harmless"),
+    ("无法判断用途，强制关闭前请确认", "Purpose unknown; check before force-closing"),
+    ("内存整理：裁剪 {}/{} 个进程，工作集减少 {}", "Memory cleanup: trimmed {}/{} processes, working set reduced by {}"),
+    ("；备用列表释放 {}", "; standby list freed {}"),
+    ("；已清空备用列表", "; standby list cleared"),
+    ("；清空备用列表失败：{}", "; failed to clear the standby list: {}"),
+    ("无法创建快照目录", "Cannot create the snapshot folder"),
+    ("写入 {} 失败：{}", "Failed to write {}: {}"),
+    ("读取 {} 失败：{}", "Failed to read {}: {}"),
+    ("{} 不是有效的快照：{}", "{} is not a valid snapshot: {}"),
+    (
+        "映像基址处为私有内存，原映像可能已被卸载替换 (疑似进程镂空)",
+        "Private memory at the image base; the original image may have been unmapped and replaced (possible process hollowing)",
+    ),
+    ("映像基址映射的文件与进程文件不一致：{}", "The file mapped at the image base differs from the process file: {}"),
+    ("PEB 记录的路径与实际文件不一致：{}", "The path recorded in the PEB differs from the actual file: {}"),
+    ("{} 个可读写可执行的私有内存区域 (共 {} KB){}", "{} private read-write-execute memory regions ({} KB in total){}"),
+    ("，JIT 宿主中较常见", ", common in JIT hosts"),
+    ("，可能存在注入代码", ", possibly injected code"),
+    ("无法连接服务管理器：{}", "Cannot connect to the service manager: {}"),
+    ("无法打开服务 {}：{}", "Cannot open service {}: {}"),
+    ("等待停止超时", "Timed out waiting for the service to stop"),
+    ("等待启动超时", "Timed out waiting for the service to start"),
+    // 浏览器内存、弹出方式、休眠、缓解措施、网络、提权、显卡、使用时长、托盘、winget、架构、模块与其他
+    ("{}：裁剪 {}/{} 个标签进程，工作集减少 {} MB；{}", "{}: trimmed {}/{} tab processes, working set reduced by {} MB; {}"),
+    ("已打开 {}，可在其中丢弃不用的标签", "Opened {}, where unused tabs can be discarded"),
+    ("极速弹出", "Quick eject"),
+    ("RM 强制释放", "RM forced release"),
+    ("智能卸载弹出", "Smart dismount eject"),
+    ("fsutil 卸载", "fsutil dismount"),
+    ("关闭休眠 (删除 hiberfil.sys)", "Turn off hibernation (delete hiberfil.sys)"),
+    (" (高熵)", " (high entropy)"),
+    (
+        "DEP 数据执行保护：{}\nASLR 地址随机化：{}{}\nCFG 控制流保护：{}\nCET 硬件影子栈：{}",
+        "DEP data execution prevention: {}\nASLR address randomization: {}{}\nCFG control flow guard: {}\nCET hardware shadow stack: {}",
+    ),
+    ("只能断开 IPv4 TCP 连接", "Only IPv4 TCP connections can be closed"),
+    ("SetTcpEntry 失败 (错误 {})", "SetTcpEntry failed (error {})"),
+    ("提权失败或已取消 (错误 {})", "Elevation failed or was cancelled (error {})"),
+    ("按键注入被拦截 ({}/{})", "Key injection was blocked ({}/{})"),
+    ("{} 秒", "{} s"),
+    ("{} 分", "{} min"),
+    ("{} 小时 {} 分", "{} h {} min"),
+    ("无法创建托盘窗口：{}", "Cannot create the tray window: {}"),
+    ("无法添加托盘图标", "Cannot add the tray icon"),
+    ("托盘线程异常退出", "The tray thread exited abnormally"),
+    ("winget 未返回任何软件", "winget returned no software"),
+    ("winget 升级 {}", "winget upgrade {}"),
+    ("winget 卸载 {}", "winget uninstall {}"),
+    ("{} 进程，在 {} 系统上模拟运行 (性能与耗电通常更差)", "{} process emulated on a {} system (usually slower and less power efficient)"),
+    ("{} 原生进程", "Native {} process"),
+    (
+        "无权读取该进程的模块 (服务或其他用户的进程需要管理员权限)",
+        "No permission to read this process's modules (services and other users' processes need administrator rights)",
+    ),
+    ("无法枚举模块：{}", "Cannot enumerate modules: {}"),
+    ("SQL 中包含 NUL 字符", "The SQL contains a NUL character"),
+    ("无法打开数据库 (错误 {})", "Cannot open the database (error {})"),
+    ("CM_Register_Notification 失败 (CONFIGRET {})", "CM_Register_Notification failed (CONFIGRET {})"),
+    ("{} 天 {}:{}:{}", "{} d {}:{}:{}"),
+    ("卸载 {}", "Uninstall {}"),
+    ("SD 读卡器", "SD card reader"),
+];
@@ -0,0 +1,291 @@
+// ═══════════════════════════════════════════════════════════════
+//  镜像写入 - 把 .img / 混合 ISO 原样写到 U 盘，制作启动盘
+// ═══════════════════════════════════════════════════════════════
+//
+// 与智能弹出相同的独占流程，但锁要一直持有到写完：
+//   1. 同一物理磁盘上的每个卷 Flush -> FSCTL_LOCK_VOLUME -> FSCTL_DISMOUNT_VOLUME，
+//      任何一个锁不上 (有程序占用) 就放弃，不做部分写入
+//   2. 以 1 MB 为块顺序写入 \\.\PhysicalDriveN (WRITE_THROUGH)，最后一块按扇区补零
+//   3. 可选校验：重新读回与镜像逐块比较
+//   4. IOCTL_DISK_UPDATE_PROPERTIES 让系统重新读取分区表，关闭卷句柄即解锁；
+//      取消或出错时同样执行，磁盘不会停留在锁定、分区表过期的状态
+// 系统盘所在的物理磁盘一律拒绝。只适用于 .img 与 Linux 等“混合 ISO”；
+// Windows 安装 ISO 不是混合镜像，原样写入后无法启动。需要管理员权限。
+// 步骤 1 与 4 封装为 ExclusiveDisk，安全擦除 (secure_wipe) 共用。
+
+use crate::i18n::{tr, trf};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::os::windows::fs::OpenOptionsExt;
+use std::os::windows::io::AsRawHandle;
+use std::time::{Duration, Instant};
+use windows_sys::Win32::Storage::FileSystem::{FILE_FLAG_WRITE_THROUGH, FILE_SHARE_READ, FILE_SHARE_WRITE};
+use windows_sys::Win32::System::Ioctl::{
+    DISK_GEOMETRY_EX, FSCTL_DISMOUNT_VOLUME, FSCTL_LOCK_VOLUME, IOCTL_DISK_GET_DRIVE_GEOMETRY_EX,
+    IOCTL_DISK_UPDATE_PROPERTIES,
+};
+use windows_sys::Win32::System::IO::DeviceIoControl;
+
+pub(crate) const CHUNK: usize = 1024 * 1024;
+/// 缓冲区按页对齐，满足各类 USB 控制器的对齐要求
+const ALIGN: usize = 4096;
+/// 进度回报的最小间隔
+pub(crate) const REPORT_EVERY: Duration = Duration::from_millis(250);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageJob {
+    pub path: String,
+    pub verify: bool,
+}
+
+impl Default for ImageJob {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            verify: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Write,
+    Verify,
+}
+
+impl Phase {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Phase::Write => tr("写入"),
+            Phase::Verify => tr("校验"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Progress {
+    pub phase: Phase,
+    pub done: u64,
+    pub total: u64,
+    /// 本阶段的平均速率 (字节/秒)
+    pub bytes_per_sec: f64,
+}
+
+impl Progress {
+    pub fn text(&self) -> String {
+        trf(
+            "正在{}镜像 {}% · {} MB/s",
+            &[
+                &self.phase.label(),
+                &format!("{:.0}", self.done as f64 / self.total.max(1) as f64 * 100.0),
+                &format!("{:.1}", self.bytes_per_sec / 1024.0 / 1024.0),
+            ],
+        )
+    }
+}
+
+/// 页对齐的块缓冲
+pub(crate) struct AlignedBuf {
+    raw: Vec<u8>,
+    offset: usize,
+}
+
+impl AlignedBuf {
+    pub(crate) fn new() -> Self {
+        let raw = vec![0u8; CHUNK + ALIGN];
+        let offset = raw.as_ptr().align_offset(ALIGN);
+        Self { raw, offset }
+    }
+
+    pub(crate) fn get(&mut self, len: usize) -> &mut [u8] {
+        &mut self.raw[self.offset..self.offset + len]
+    }
+}
+
+fn ioctl(file: &File, code: u32, out: *mut std::ffi::c_void, out_len: u32) -> bool {
+    let mut bytes = 0u32;
+    unsafe {
+        DeviceIoControl(
+            file.as_raw_handle() as isize,
+            code,
+            std::ptr::null(),
+            0,
+            out,
+            out_len,
+            &mut bytes,
+            std::ptr::null_mut(),
+        ) != 0
+    }
+}
+
+fn open_device(path: &str, write_through: bool) -> std::io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.read(true).write(true).share_mode(FILE_SHARE_READ | FILE_SHARE_WRITE);
+    if write_through {
+        options.custom_flags(FILE_FLAG_WRITE_THROUGH);
+    }
+    options.open(path)
+}
+
+/// 锁定并卸载一个卷；返回的句柄关闭时自动解锁
+fn lock_volume(target: &str) -> Result<File, String> {
+    let name = crate::mount_manager::display_name(target);
+    let volume = open_device(&crate::mount_manager::device_path(target), false)
+        .map_err(|e| trf("无法打开卷 {}：{}", &[&name, &e]))?;
+    let _ = volume.sync_all();
+    let locked = (0..5).any(|i| {
+        if i > 0 {
+            std::thread::sleep(Duration::from_millis(200));
+        }
+        ioctl(&volume, FSCTL_LOCK_VOLUME, std::ptr::null_mut(), 0)
+    });
+    if !locked {
+        return Err(trf("{} 正被其他程序使用，无法独占锁定；请先关闭占用程序", &[&name]));
+    }
+    ioctl(&volume, FSCTL_DISMOUNT_VOLUME, std::ptr::null_mut(), 0);
+    Ok(volume)
+}
+
+/// 磁盘容量与扇区大小
+fn geometry(disk: &File) -> Result<(u64, usize), String> {
+    let mut buf = [0u64; 32];
+    if !ioctl(disk, IOCTL_DISK_GET_DRIVE_GEOMETRY_EX, buf.as_mut_ptr() as _, std::mem::size_of_val(&buf) as u32) {
+        return Err(trf("无法读取磁盘容量：{}", &[&std::io::Error::last_os_error()]));
+    }
+    let geo = unsafe { &*(buf.as_ptr() as *const DISK_GEOMETRY_EX) };
+    Ok((geo.DiskSize as u64, (geo.Geometry.BytesPerSector as usize).clamp(512, ALIGN)))
+}
+
+/// 独占打开的整块物理磁盘：同一磁盘上的卷全部锁定并卸载，
+/// 锁在对象存活期间一直持有
+pub(crate) struct ExclusiveDisk {
+    // 字段按声明顺序释放：先关闭磁盘句柄，再解锁卷
+    pub file: File,
+    pub number: u32,
+    pub size: u64,
+    /// 扇区大小，设备读写必须按整扇区进行
+    pub sector: usize,
+    _locks: Vec<File>,
+}
+
+impl ExclusiveDisk {
+    /// d 为已规范化的盘符；拒绝系统盘所在的物理磁盘
+    pub(crate) fn open(d: &str) -> Result<Self, String> {
+        let number = crate::usb_info::disk_number(d).ok_or(tr("无法确定所在物理磁盘"))?;
+        let system_drive = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
+        if crate::usb_info::disk_number(&system_drive) == Some(number) {
+            return Err(tr("目标与系统盘位于同一物理磁盘，已拒绝").to_string());
+        }
+        // 锁在整个写入期间保持，防止系统或其他程序重新挂载卷
+        let locks = crate::usb_info::volumes_on_same_disk(d)
+            .iter()
+            .map(|v| lock_volume(v))
+            .collect::<Result<Vec<File>, String>>()?;
+        let file = open_device(&format!("\\\\.\\PhysicalDrive{}", number), true)
+            .map_err(|e| trf("无法打开 PhysicalDrive{}：{}", &[&number, &e]))?;
+        let (size, sector) = geometry(&file)?;
+        Ok(Self { file, number, size, sector, _locks: locks })
+    }
+}
+
+impl Drop for ExclusiveDisk {
+    /// 无论成功、取消还是出错，都让系统按新的分区表重新识别；
+    /// 随后字段释放，解锁后卷会重新出现
+    fn drop(&mut self) {
+        ioctl(&self.file, IOCTL_DISK_UPDATE_PROPERTIES, std::ptr::null_mut(), 0);
+    }
+}
+
+/// 尽量读满 buf，返回读到的字节数 (文件末尾时小于 buf 长度)
+fn read_full(file: &mut File, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut n = 0;
+    while n < buf.len() {
+        match file.read(&mut buf[n..])? {
+            0 => break,
+            read => n += read,
+        }
+    }
+    Ok(n)
+}
+
+pub fn write(drive: &str, job: &ImageJob, mut on_progress: impl FnMut(Progress)) -> Result<String, String> {
+    let d = crate::norm_drive(drive);
+    let result = write_inner(&d, job, &mut on_progress);
+    crate::audit::record(
+        "写入镜像",
+        &format!("{} -> {}: {}", job.path, d, result.as_ref().map(|s| s.as_str()).unwrap_or_else(|e| e.as_str())),
+    );
+    result
+}
+
+fn write_inner(d: &str, job: &ImageJob, on_progress: &mut impl FnMut(Progress)) -> Result<String, String> {
+    let mut image = File::open(job.path.trim()).map_err(|e| trf("无法打开镜像 {}：{}", &[&job.path, &e]))?;
+    let image_len = image.metadata().map_err(|e| e.to_string())?.len();
+    if image_len == 0 {
+        return Err(tr("镜像文件为空").to_string());
+    }
+
+    let mut exclusive = ExclusiveDisk::open(d)?;
+    let (disk_size, sector) = (exclusive.size, exclusive.sector);
+    if image_len > disk_size {
+        return Err(trf(
+            "镜像 {} GB 大于磁盘容量 {} GB",
+            &[&format!("{:.1}", image_len as f64 / 1e9), &format!("{:.1}", disk_size as f64 / 1e9)],
+        ));
+    }
+
+    let mut buf = AlignedBuf::new();
+    let mut run = |phase: Phase, disk: &mut File, image: &mut File| -> Result<(), String> {
+        image.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+        disk.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+        let started = Instant::now();
+        let mut reported = Instant::now();
+        let mut done = 0u64;
+        let mut check = vec![0u8; CHUNK];
+        while done < image_len {
+            let n = read_full(image, &mut check).map_err(|e| trf("读取镜像失败：{}", &[&e]))?;
+            if n == 0 {
+                break;
+            }
+            // 设备读写必须是整扇区，最后一块补零
+            let padded = n.div_ceil(sector) * sector;
+            let block = buf.get(padded);
+            match phase {
+                Phase::Write => {
+                    block[..n].copy_from_slice(&check[..n]);
+                    block[n..].fill(0);
+                    disk.write_all(block)
+                        .map_err(|e| trf("写入偏移 {} MB 处失败：{}", &[&(done / 1024 / 1024), &e]))?;
+                }
+                Phase::Verify => {
+                    disk.read_exact(block)
+                        .map_err(|e| trf("读回偏移 {} MB 处失败：{}", &[&(done / 1024 / 1024), &e]))?;
+                    if block[..n] != check[..n] {
+                        return Err(trf("校验失败：偏移 {} MB 处的数据与镜像不一致，U 盘可能已损坏", &[&(done / 1024 / 1024)]));
+                    }
+                }
+            }
+            done += n as u64;
+            if reported.elapsed() >= REPORT_EVERY || done == image_len {
+                reported = Instant::now();
+                on_progress(Progress {
+                    phase,
+                    done,
+                    total: image_len,
+                    bytes_per_sec: done as f64 / started.elapsed().as_secs_f64().max(0.001),
+                });
+            }
+        }
+        Ok(())
+    };
+    let disk = &mut exclusive.file;
+    run(Phase::Write, disk, &mut image)?;
+    disk.sync_all().map_err(|e| trf("刷新写入缓存失败：{}", &[&e]))?;
+    if job.verify {
+        run(Phase::Verify, disk, &mut image)?;
+    }
+    Ok(trf(
+        "{}: 已写入 {} MB{}",
+        &[&d, &format!("{:.1}", image_len as f64 / 1024.0 / 1024.0), &if job.verify { tr("，校验通过") } else { "" }],
+    ))
+}
@@ -0,0 +1,142 @@
+// ═══════════════════════════════════════════════════════════════
+//  批量终止 - 逐个回报结果，等待进程句柄而非固定休眠
+// ═══════════════════════════════════════════════════════════════
+//
+// 强力清场对每个 PID：OpenProcess(TERMINATE | SYNCHRONIZE) -> TerminateProcess。
+// 打不开时按错误码区分“已退出”与“拒绝访问”。全部发出后统一等待进程句柄，
+// 进程真正退出 (文件句柄随之释放) 才进入弹出，超时的单独标记。
+
+use crate::i18n::{tr, trf};
+use std::time::{Duration, Instant};
+use windows_sys::Win32::Foundation::{
+    CloseHandle, GetLastError, ERROR_ACCESS_DENIED, ERROR_INVALID_PARAMETER, HANDLE, WAIT_OBJECT_0,
+};
+use windows_sys::Win32::System::Threading::{
+    OpenProcess, TerminateProcess, WaitForSingleObject, PROCESS_SYNCHRONIZE, PROCESS_TERMINATE,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum KillOutcome {
+    Killed,
+    /// 执行前已经退出 (或被 RM 关掉)
+    AlreadyGone,
+    /// 权限不足，通常是服务或更高完整性级别的进程
+    AccessDenied,
+    /// 已发出终止，但等待超时仍未退出
+    TimedOut,
+    Failed(String),
+}
+
+impl KillOutcome {
+    pub fn label(&self) -> String {
+        match self {
+            KillOutcome::Killed => tr("已终止").to_string(),
+            KillOutcome::AlreadyGone => tr("已退出").to_string(),
+            KillOutcome::AccessDenied => tr("拒绝访问").to_string(),
+            KillOutcome::TimedOut => tr("未退出").to_string(),
+            KillOutcome::Failed(e) => trf("失败：{}", &[&e]),
+        }
+    }
+
+    /// 进程已不在 (不会再占用磁盘)
+    pub fn is_gone(&self) -> bool {
+        matches!(self, KillOutcome::Killed | KillOutcome::AlreadyGone)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KillResult {
+    pub pid: u32,
+    pub name: String,
+    pub outcome: KillOutcome,
+}
+
+/// 发给 UI 的清场进度
+#[derive(Clone, Debug, PartialEq)]
+pub struct KillProgress {
+    pub drive: String,
+    pub stage: String,
+    pub total: usize,
+    pub results: Vec<KillResult>,
+}
+
+impl KillProgress {
+    pub fn new(drive: &str, stage: &str) -> Self {
+        Self {
+            drive: drive.to_string(),
+            stage: stage.to_string(),
+            total: 0,
+            results: Vec::new(),
+        }
+    }
+
+    /// 一行摘要："3/5 · 已终止 2 · 已退出 0 · 拒绝访问 1"
+    pub fn summary(&self) -> String {
+        let count = |f: fn(&KillOutcome) -> bool| self.results.iter().filter(|r| f(&r.outcome)).count();
+        let mut parts = vec![format!("{}/{}", self.results.len(), self.total)];
+        for (label, n) in [
+            (tr("已终止"), count(|o| *o == KillOutcome::Killed)),
+            (tr("已退出"), count(|o| *o == KillOutcome::AlreadyGone)),
+            (tr("拒绝访问"), count(|o| *o == KillOutcome::AccessDenied)),
+            (tr("未退出"), count(|o| *o == KillOutcome::TimedOut)),
+            (tr("失败"), count(|o| matches!(o, KillOutcome::Failed(_)))),
+        ] {
+            if n > 0 {
+                parts.push(format!("{} {}", label, n));
+            }
+        }
+        parts.join(" · ")
+    }
+}
+
+/// 按 OpenProcess / TerminateProcess 的错误码归类
+fn classify_error() -> KillOutcome {
+    match unsafe { GetLastError() } {
+        ERROR_INVALID_PARAMETER => KillOutcome::AlreadyGone,
+        ERROR_ACCESS_DENIED => KillOutcome::AccessDenied,
+        code => KillOutcome::Failed(std::io::Error::from_raw_os_error(code as i32).to_string()),
+    }
+}
+
+/// 依次终止 targets (PID, 进程名)，每得出一个结果就回调一次；
+/// 所有进程的等待共享同一个 timeout
+pub fn kill_all(targets: &[(u32, String)], timeout: Duration, mut on_result: impl FnMut(KillResult)) {
+    let mut pending: Vec<(u32, String, HANDLE)> = Vec::new();
+    for (pid, name) in targets {
+        let result = |outcome| KillResult { pid: *pid, name: name.clone(), outcome };
+        unsafe {
+            let process = OpenProcess(PROCESS_TERMINATE | PROCESS_SYNCHRONIZE, 0, *pid);
+            if process == 0 {
+                on_result(result(classify_error()));
+                continue;
+            }
+            if TerminateProcess(process, 1) == 0 {
+                // 终止失败时进程可能恰好已退出
+                let outcome = if WaitForSingleObject(process, 0) == WAIT_OBJECT_0 {
+                    KillOutcome::AlreadyGone
+                } else {
+                    classify_error()
+                };
+                CloseHandle(process);
+                on_result(result(outcome));
+                continue;
+            }
+            pending.push((*pid, name.clone(), process));
+        }
+    }
+
+    let deadline = Instant::now() + timeout;
+    for (pid, name, process) in pending {
+        let wait = deadline.saturating_duration_since(Instant::now()).as_millis() as u32;
+        let outcome = unsafe {
+            let rc = WaitForSingleObject(process, wait);
+            CloseHandle(process);
+            if rc == WAIT_OBJECT_0 {
+                KillOutcome::Killed
+            } else {
+                KillOutcome::TimedOut
+            }
+        };
+        on_result(KillResult { pid, name, outcome });
+    }
+}
@@ -0,0 +1,101 @@
+// ═══════════════════════════════════════════════════════════════
+//  局域网主机电源 - 网络唤醒 (Wake-on-LAN) 与远程关机 / 重启
+// ═══════════════════════════════════════════════════════════════
+//
+// 家庭实验室里的几台机器记在设置里 (名称、主机名或 IP、MAC)：
+//   - 唤醒：向广播地址的 UDP 9 端口发送魔术包 (6 个 0xFF + MAC 重复 16 次)，
+//     目标机需在 BIOS / 网卡驱动中开启网络唤醒，且与本机处于同一广播域
+//   - 关机 / 重启：调用系统自带的 shutdown /m \\主机，留 DELAY_SECS 秒给对方保存，
+//     期间可“取消”(shutdown /a)。需要对方的管理员权限 (同一域账户或相同的本地
+//     管理员账户密码)，并允许远程关机 (防火墙放行“远程关机”/ RPC)
+// 命令经 run_to_completion 执行并写入审计日志；远程主机不可达时 RPC 超时较长，
+// 调用方应放到后台线程。
+
+use crate::i18n::{tr, trf};
+use crate::tool_runner::{run_to_completion, ToolSpec};
+use serde::{Deserialize, Serialize};
+use std::net::{Ipv4Addr, UdpSocket};
+
+/// 远程关机 / 重启前的倒计时 (秒)
+pub const DELAY_SECS: u32 = 30;
+const WOL_PORT: u16 = 9;
+
+/// 一台受管主机
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Machine {
+    pub name: String,
+    /// 主机名或 IP，用于远程关机
+    pub host: String,
+    /// 网卡 MAC，用于唤醒；格式 AA:BB:CC:DD:EE:FF 或 AA-BB-…
+    pub mac: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PowerAction {
+    Shutdown,
+    Restart,
+    Cancel,
+}
+
+impl PowerAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PowerAction::Shutdown => tr("关机"),
+            PowerAction::Restart => tr("重启"),
+            PowerAction::Cancel => tr("取消关机"),
+        }
+    }
+}
+
+pub fn parse_mac(s: &str) -> Result<[u8; 6], String> {
+    let hex: String = s.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+    if hex.len() != 12 || s.chars().any(|c| !c.is_ascii_hexdigit() && !matches!(c, ':' | '-' | '.' | ' ')) {
+        return Err(trf("MAC 地址格式不正确：{}", &[&s]));
+    }
+    let mut mac = [0u8; 6];
+    for (i, byte) in mac.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|e| e.to_string())?;
+    }
+    Ok(mac)
+}
+
+/// 发送网络唤醒魔术包
+pub fn wake(machine: &Machine) -> Result<(), String> {
+    let mac = parse_mac(&machine.mac)?;
+    let mut packet = vec![0xFFu8; 6];
+    for _ in 0..16 {
+        packet.extend_from_slice(&mac);
+    }
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).map_err(|e| e.to_string())?;
+    socket.set_broadcast(true).map_err(|e| e.to_string())?;
+    socket
+        .send_to(&packet, (Ipv4Addr::BROADCAST, WOL_PORT))
+        .map_err(|e| trf("发送唤醒包失败：{}", &[&e]))?;
+    crate::audit::record("网络唤醒", &format!("{} ({})", machine.name, machine.mac));
+    Ok(())
+}
+
+/// 远程关机 / 重启 / 取消；阻塞直到 shutdown.exe 返回
+pub fn remote_power(machine: &Machine, action: PowerAction) -> Result<(), String> {
+    let host = machine.host.trim().trim_start_matches('\\');
+    if host.is_empty() {
+        return Err(trf("{} 未填写主机名或 IP", &[&machine.name]));
+    }
+    let target = format!("\\\\{}", host);
+    let delay = DELAY_SECS.to_string();
+    let comment = trf("Geek Killer 远程{}，{} 秒后执行", &[&action.label(), &DELAY_SECS]);
+    let args: Vec<&str> = match action {
+        PowerAction::Shutdown => vec!["/s", "/m", &target, "/t", &delay, "/c", &comment],
+        PowerAction::Restart => vec!["/r", "/m", &target, "/t", &delay, "/c", &comment],
+        PowerAction::Cancel => vec!["/a", "/m", &target],
+    };
+    let title = trf("远程{}", &[&action.label()]);
+    let (ok, stdout, stderr) = run_to_completion(&ToolSpec::new(&title, "shutdown", &args))?;
+    if ok {
+        Ok(())
+    } else {
+        let text = if stderr.trim().is_empty() { stdout } else { stderr };
+        Err(format!("{}：{}", machine.name, text.trim()))
+    }
+}
@@ -0,0 +1,123 @@
+// ═══════════════════════════════════════════════════════════════
+//  长路径 / Unicode 路径 - UTF-16 比较与 \\?\ 前缀
+// ═══════════════════════════════════════════════════════════════
+//
+// Windows 路径本质是 UTF-16，可能含孤立代理项，转成 String 会变成 U+FFFD，
+// Path::to_str() 则直接返回 None (回退扫描器曾因此漏掉进程)。这里约定：
+//   - 比较一律在 UTF-16 上做 (CompareStringOrdinal 忽略大小写)，不经过 String
+//   - 交给 Win32 API 的超长路径加 \\?\ / \\?\UNC\ 前缀
+//   - 读取路径的 API 按所需长度扩容重试，不再截断在固定缓冲区
+// 只有显示给用户时才做有损转换。
+
+use std::ffi::OsStr;
+use std::os::windows::ffi::OsStrExt;
+use windows_sys::Win32::Globalization::CompareStringOrdinal;
+
+const MAX_PATH: usize = 260;
+/// \\?\ 形式路径的长度上限
+const MAX_LONG_PATH: usize = 32_768;
+const CSTR_EQUAL: i32 = 2;
+
+const BACKSLASH: u16 = b'\\' as u16;
+const SLASH: u16 = b'/' as u16;
+const COLON: u16 = b':' as u16;
+
+fn units(s: &str) -> Vec<u16> {
+    s.encode_utf16().collect()
+}
+
+fn is_sep(c: u16) -> bool {
+    c == BACKSLASH || c == SLASH
+}
+
+fn has_prefix(path: &[u16], prefix: &str) -> bool {
+    let prefix = units(prefix);
+    path.len() >= prefix.len() && eq_ignore_case(&path[..prefix.len()], &prefix)
+}
+
+/// UTF-16 序列忽略大小写比较 (与文件系统的比较规则一致)
+pub fn eq_ignore_case(a: &[u16], b: &[u16]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    if a.is_empty() {
+        return true;
+    }
+    unsafe { CompareStringOrdinal(a.as_ptr(), a.len() as i32, b.as_ptr(), b.len() as i32, 1) == CSTR_EQUAL }
+}
+
+/// 去掉 \\?\ / \??\ 前缀，\\?\UNC\server 还原为 \\server；卷 GUID 路径保持不变
+pub fn normalize(path: &[u16]) -> Vec<u16> {
+    for verbatim in ["\\\\?\\", "\\??\\"] {
+        if !has_prefix(path, verbatim) {
+            continue;
+        }
+        let rest = &path[verbatim.len()..];
+        if has_prefix(rest, "UNC\\") {
+            return [BACKSLASH, BACKSLASH].iter().chain(&rest[4..]).copied().collect();
+        }
+        if rest.len() >= 2 && rest[1] == COLON {
+            return rest.to_vec();
+        }
+    }
+    path.to_vec()
+}
+
+/// 路径是否位于某个卷根目录 (盘符、文件夹挂载点或卷 GUID 路径) 之下
+pub fn is_under_wide(path: &[u16], root: &str) -> bool {
+    let path = normalize(path);
+    let root = units(root.trim_end_matches(['\\', '/']));
+    if root.is_empty() || path.len() < root.len() || !eq_ignore_case(&path[..root.len()], &root) {
+        return false;
+    }
+    // 必须在分隔符处结束，避免 C:\mnt\usb 误匹配 C:\mnt\usb2
+    path.get(root.len()).is_none_or(|&c| is_sep(c))
+}
+
+pub fn is_under(path: &OsStr, root: &str) -> bool {
+    is_under_wide(&path.encode_wide().collect::<Vec<_>>(), root)
+}
+
+/// 普通字符串 (非路径) 转成以 0 结尾的 UTF-16，供 Win32 API 的 PCWSTR 参数
+pub(crate) fn wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// 交给 Win32 API 的宽字符路径 (以 0 结尾)；超过 MAX_PATH 的绝对路径加 \\?\ 前缀
+pub fn to_wide(path: &OsStr) -> Vec<u16> {
+    let wide: Vec<u16> = path.encode_wide().collect();
+    let verbatim = has_prefix(&wide, "\\\\?\\") || has_prefix(&wide, "\\\\.\\");
+    let mut out = if wide.len() < MAX_PATH || verbatim {
+        wide
+    } else if wide.len() >= 3 && wide[1] == COLON && is_sep(wide[2]) {
+        // \\?\ 路径不做任何解析，斜杠必须统一成反斜杠
+        units("\\\\?\\").into_iter().chain(wide.iter().map(|&c| if c == SLASH { BACKSLASH } else { c })).collect()
+    } else if wide.len() >= 2 && is_sep(wide[0]) && is_sep(wide[1]) {
+        units("\\\\?\\UNC\\").into_iter().chain(wide[2..].iter().map(|&c| if c == SLASH { BACKSLASH } else { c })).collect()
+    } else {
+        wide
+    };
+    out.push(0);
+    out
+}
+
+/// 读取路径类 API 的通用重试：read 返回写入的字符数，0 表示失败，
+/// 大于等于缓冲区长度表示空间不足 (多数 API 此时返回所需长度)
+pub fn read_growing(mut read: impl FnMut(&mut [u16]) -> u32) -> Option<Vec<u16>> {
+    let mut size = MAX_PATH;
+    loop {
+        let mut buf = vec![0u16; size];
+        let len = read(&mut buf) as usize;
+        if len == 0 {
+            return None;
+        }
+        if len < buf.len() {
+            buf.truncate(len);
+            return Some(buf);
+        }
+        if size >= MAX_LONG_PATH {
+            return None;
+        }
+        size = (len + 1).max(size * 2).min(MAX_LONG_PATH);
+    }
+}
@@ -0,0 +1,194 @@
+// ═══════════════════════════════════════════════════════════════
+//  操作宏 - 录制一串操作 (终止进程 / 重启服务 / 弹出磁盘) 并一键重放
+// ═══════════════════════════════════════════════════════════════
+//
+// 录制期间实际下发的终止与弹出命令被记为步骤 (终止按完整映像路径记录，重放时
+// 只匹配同一路径、本会话中的进程；系统 / 服务 / 显示关键进程一律跳过)；重启服务
+// 只能在编辑器中手动添加。宏保存在 settings.json 中，可绑定全局快捷键或放进托盘
+// 菜单，执行前与界面操作一样经过访客模式与提权预检，统一交给 usb_worker：
+// 步骤依次执行，某一步失败不影响后续步骤，每一步的结果逐条回报界面并写入审计日志。
+
+use crate::i18n::{tr, trf};
+use crate::hotkeys::{Binding, HotkeyAction};
+use crate::kill_batch::{self, KillOutcome};
+use crate::service_control::Service;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 终止一步中等待进程退出的时长
+const KILL_WAIT: Duration = Duration::from_secs(3);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Step {
+    /// 按完整映像路径终止本会话中的进程 (旧版本录制的只有进程名)
+    KillProcess(String),
+    /// 服务名 (短名，如 Spooler)
+    RestartService(String),
+    /// 盘符
+    Eject(String),
+}
+
+impl Step {
+    pub fn describe(&self) -> String {
+        match self {
+            Step::KillProcess(image) => trf("终止 {}", &[&image.rsplit(['\\', '/']).next().unwrap_or(image)]),
+            Step::RestartService(name) => trf("重启服务 {}", &[&name]),
+            Step::Eject(drive) => trf("弹出 {}", &[&drive]),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Macro {
+    pub name: String,
+    pub steps: Vec<Step>,
+    /// 形如 "Ctrl+Alt+1"；为空表示不绑定
+    pub hotkey: String,
+    /// 显示在托盘右键菜单中
+    pub in_tray: bool,
+}
+
+/// 全局快捷键绑定加上各宏的快捷键 (动作为宏的下标)
+pub fn with_hotkeys(base: &[Binding], macros: &[Macro]) -> Vec<Binding> {
+    let mut out = base.to_vec();
+    out.extend(
+        macros
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| !m.hotkey.trim().is_empty())
+            .map(|(i, m)| Binding {
+                action: HotkeyAction::RunMacro(i),
+                keys: m.hotkey.clone(),
+            }),
+    );
+    out
+}
+
+/// 托盘菜单中的宏：(下标, 名称)
+pub fn tray_entries(macros: &[Macro]) -> Vec<(usize, String)> {
+    macros
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| m.in_tray && !m.steps.is_empty())
+        .map(|(i, m)| (i, m.name.clone()))
+        .collect()
+}
+
+/// 一次执行的逐步结果，发给界面显示
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunProgress {
+    pub name: String,
+    pub total: usize,
+    /// (步骤说明, 结果)
+    pub results: Vec<(String, Result<String, String>)>,
+}
+
+impl RunProgress {
+    pub fn is_finished(&self) -> bool {
+        self.results.len() >= self.total
+    }
+
+    pub fn failed(&self) -> usize {
+        self.results.iter().filter(|(_, r)| r.is_err()).count()
+    }
+
+    /// 一行摘要："清理会议：3 步完成，1 步失败"
+    pub fn summary(&self) -> String {
+        match self.failed() {
+            0 => trf("{}：{} 步完成", &[&self.name, &self.results.len()]),
+            n => trf("{}：{} 步完成，{} 步失败", &[&self.name, &(self.results.len() - n), &n]),
+        }
+    }
+}
+
+/// 终止步骤匹配到的进程：映像路径相同 (旧步骤只有进程名时按名称)、与本程序位于
+/// 同一会话，且不是系统 / 服务 / 显示关键进程
+pub fn kill_targets(image: &str) -> Vec<(u32, String)> {
+    let by_path = image.contains(['\\', '/']);
+    let own_session = crate::process_owner::session_of(std::process::id());
+    let mut sys = sysinfo::System::new();
+    sys.refresh_processes_specifics(
+        sysinfo::ProcessesToUpdate::All,
+        true,
+        sysinfo::ProcessRefreshKind::new().with_exe(sysinfo::UpdateKind::Always),
+    );
+    sys.processes()
+        .iter()
+        .filter(|(_, p)| {
+            if by_path {
+                p.exe().is_some_and(|exe| exe.to_string_lossy().eq_ignore_ascii_case(image))
+            } else {
+                p.name().to_string_lossy().eq_ignore_ascii_case(image)
+            }
+        })
+        .map(|(pid, p)| (pid.as_u32(), p.name().to_string_lossy().to_string()))
+        .filter(|(pid, name)| {
+            crate::process_owner::session_of(*pid) == own_session && !crate::is_protected_process(*pid, name)
+        })
+        .collect()
+}
+
+fn kill_image(image: &str) -> Result<String, String> {
+    let targets = kill_targets(image);
+    if targets.is_empty() {
+        return Ok(tr("本会话中没有可终止的进程").to_string());
+    }
+    let mut results = Vec::new();
+    kill_batch::kill_all(&targets, KILL_WAIT, |r| results.push(r));
+    let gone = results.iter().filter(|r| r.outcome.is_gone()).count();
+    if gone == results.len() {
+        Ok(trf("已终止 {} 个进程", &[&gone]))
+    } else {
+        let denied = results.iter().filter(|r| r.outcome == KillOutcome::AccessDenied).count();
+        Err(trf(
+            "{}/{} 个进程未退出{}",
+            &[&(results.len() - gone), &results.len(), &if denied > 0 { tr("，可能需要管理员权限") } else { "" }],
+        ))
+    }
+}
+
+/// 停止再启动服务 (需要管理员)
+fn restart_service(name: &str) -> Result<String, String> {
+    let manager = Service::manager()?;
+    let service = Service::open(&manager, name)?;
+    service.stop().map_err(|e| trf("停止失败：{}", &[&e]))?;
+    service.start().map_err(|e| trf("启动失败：{}", &[&e]))?;
+    Ok(tr("已重启").to_string())
+}
+
+/// 依次执行宏的步骤，每完成一步回调一次；弹出由调用方完成 (与界面的弹出共用流程)
+pub fn run(
+    m: &Macro,
+    mut eject: impl FnMut(&str) -> Result<String, String>,
+    mut on_step: impl FnMut(&RunProgress),
+) -> RunProgress {
+    let mut progress = RunProgress {
+        name: m.name.clone(),
+        total: m.steps.len(),
+        results: Vec::new(),
+    };
+    on_step(&progress);
+    for step in &m.steps {
+        let result = match step {
+            Step::KillProcess(image) => kill_image(image),
+            Step::RestartService(name) => restart_service(name),
+            Step::Eject(drive) => eject(drive),
+        };
+        crate::audit::record(
+            "操作宏",
+            &format!(
+                "{} · {} -> {}",
+                m.name,
+                step.describe(),
+                match &result {
+                    Ok(t) => t.clone(),
+                    Err(e) => format!("失败：{}", e),
+                }
+            ),
+        );
+        progress.results.push((step.describe(), result));
+        on_step(&progress);
+    }
+    progress
+}
@@ -2,8 +2,8 @@
 
 use eframe::egui;
 use rust_core_lib::{device, meta::STAR_TAP_BRAND, security, ui};
-use std::collections::HashMap;
-use std::sync::{mpsc, Arc, RwLock};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{mpsc, Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
 use sysinfo::{Disks, Networks, ProcessRefreshKind, System};
 
@@ -18,6 +18,91 @@ use windows_sys::Win32::System::Ioctl::{
 };
 use windows_sys::Win32::UI::Shell::SHChangeNotify;
 
+mod arch;
+mod audio_fix;
+mod audit;
+mod autorun_scan;
+mod backup;
+mod browser_memory;
+mod checksum;
+mod clear_confirm;
+mod device_events;
+mod device_prefs;
+mod eject_history;
+mod eject_stats;
+mod elevation;
+mod explorer_restart;
+mod explorer_windows;
+mod firewall;
+mod format_volume;
+mod gpu_guard;
+mod gpu_usage;
+mod guest_mode;
+mod graceful;
+mod handles;
+mod hibernation;
+mod hotkeys;
+mod hung_windows;
+mod i18n;
+mod image_writer;
+mod kill_batch;
+mod lan_power;
+mod long_path;
+mod macros;
+mod mitigations;
+mod modules;
+mod net_connections;
+mod net_reset;
+mod panel_capture;
+mod perf_history;
+mod print_spooler;
+mod memory_advisor;
+mod memory_trim;
+mod metered;
+mod mount_manager;
+mod process_compare;
+mod process_db;
+mod process_history;
+mod process_memory;
+mod process_overrides;
+mod process_owner;
+mod process_priority;
+mod process_restart;
+mod process_tree;
+mod read_only;
+mod removal_policy;
+mod repaint;
+mod reputation;
+mod screen_time;
+mod secure_wipe;
+mod security_scan;
+mod service_control;
+mod settings;
+mod signature;
+mod smart;
+mod speed_test;
+mod sqlite;
+mod startup_items;
+mod system_counters;
+mod system_repair;
+mod temp_cleanup;
+mod table_export;
+mod tick_health;
+mod tool_runner;
+mod tray;
+mod unelevated;
+mod uninstall;
+mod usb_info;
+mod volume_perf;
+mod watchdog;
+mod weekly_report;
+mod widget_api;
+mod window_picker;
+mod winget;
+
+use eject_stats::{EjectMethod, EjectStats};
+use i18n::{tr, trf};
+
 const GUID_DEVINTERFACE_DISK: windows_sys::core::GUID = windows_sys::core::GUID {
     data1: 0x53f56307,
     data2: 0xb6bf,
@@ -34,6 +119,7 @@ struct Occupant {
     pid: u32,
     name: String,
     desc: String,
+    locked_paths: Vec<String>, // 该进程在目标盘上打开的文件 (句柄枚举)
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -43,21 +129,162 @@ enum UsbState {
     Occupied { drive: String, list: Vec<Occupant> },
     Ejecting(String), // 正在弹出的盘符
     Done(String),     // 成功/失败消息
+    SpeedTesting(speed_test::SpeedProgress), // 测速进行中
+    SpeedTested(speed_test::SpeedResult),    // 测速完成
 }
 
 enum UsbMsg {
     State(UsbState),
+    Busy(String, bool), // 盘符 (已规范化) 是否有操作在执行
+    Stats(EjectStats),  // 弹出成功率统计更新
+    KillProgress(kill_batch::KillProgress), // 强力清场逐个进程的结果
+    MacroProgress(macros::RunProgress),     // 操作宏逐步的结果
+    ForceClear(String, Vec<Occupant>),      // 强制模式请求强力清场，与点“强力清场”走同一确认
+}
+
+/// 弹出升级策略：强力清场前如何对待占用进程
+#[derive(Clone, Debug)]
+struct EjectPolicy {
+    graceful_close: bool,    // 文档编辑器先收到 WM_CLOSE，触发其保存提示
+    graceful_wait: Duration, // 等待编辑器自行退出的时长，超时后再强杀
+    mode: settings::EjectMode, // 快速弹出被否决后的处理方式
+}
+
+impl Default for EjectPolicy {
+    fn default() -> Self {
+        Self {
+            graceful_close: true,
+            graceful_wait: Duration::from_secs(20),
+            mode: settings::EjectMode::default(),
+        }
+    }
 }
 
+#[derive(Clone, Debug)]
 enum UsbCmd {
     Scan(String),                    // 扫描占用并弹出
     ForceEject(String, Vec<u32>),    // 强制弹出
     FsutilDismount(String),          // 极客命令：fsutil
     KillOne(u32, String),            // 终止单个
+    SetPolicy(EjectPolicy),          // 更新弹出策略
+    KillGroup(Vec<u32>, bool),       // 进程表终止整组 (是否连同子进程)
+    Mount(mount_manager::MountOp),   // 更改 / 移除盘符、挂载到文件夹
+    DevicePrefs(String, Option<device_prefs::DevicePrefs>), // 盘符对应设备的偏好 (插入 / 修改时下发)
+    SetReadOnly(String, bool),       // 只读挂载开关
+    SetWriteProtect(String, bool),   // 磁盘写保护 (持久)
+    SetWriteProtectPolicy(bool),     // 全局 USB 存储写保护策略
+    Format(String, format_volume::FormatJob), // 格式化卷
+    WriteImage(String, image_writer::ImageJob), // 把磁盘镜像原样写入整块 U 盘
+    Wipe(String, secure_wipe::WipeJob, Arc<std::sync::atomic::AtomicBool>), // 多遍覆写整块 U 盘 (可取消)
+    Relabel(String, String),         // 修改卷标
+    SpeedTest(String),               // 顺序读写测速
+    SetRemovalPolicy(String, removal_policy::Policy), // 快速删除 / 更好的性能
+    RestartExplorer(String, Vec<u32>), // 占用者只有 explorer 时：重启资源管理器后弹出
+    RestartProcess(u32),             // 结束后按原命令行与工作目录重新启动
+    RunMacro(macros::Macro),         // 依次执行操作宏的步骤
 }
 
-#[derive(Clone, Debug)]
+impl UsbCmd {
+    /// 命令作用的盘符 (已规范化)，进程表发起的无盘符命令返回 None
+    fn drive(&self) -> Option<String> {
+        let d = match self {
+            UsbCmd::Scan(d)
+            | UsbCmd::ForceEject(d, _)
+            | UsbCmd::FsutilDismount(d)
+            | UsbCmd::SetReadOnly(d, _)
+            | UsbCmd::SetWriteProtect(d, _)
+            | UsbCmd::Format(d, _)
+            | UsbCmd::WriteImage(d, _)
+            | UsbCmd::Wipe(d, _, _)
+            | UsbCmd::Relabel(d, _)
+            | UsbCmd::SpeedTest(d)
+            | UsbCmd::SetRemovalPolicy(d, _)
+            | UsbCmd::RestartExplorer(d, _)
+            | UsbCmd::KillOne(_, d) => norm_drive(d),
+            UsbCmd::Mount(op) => return op.drive(),
+            UsbCmd::SetPolicy(_)
+            | UsbCmd::KillGroup(_, _)
+            | UsbCmd::RestartProcess(_)
+            | UsbCmd::RunMacro(_)
+            | UsbCmd::DevicePrefs(_, _)
+            | UsbCmd::SetWriteProtectPolicy(_) => return None,
+        };
+        if d.is_empty() {
+            None
+        } else {
+            Some(d)
+        }
+    }
+
+    /// 访客模式下禁止的命令：终止进程、强制卸载、改动磁盘。
+    /// 普通弹出、测速、插入时下发的设备偏好与启动时下发的弹出策略不受限
+    /// (弹出策略为“强制”时，由此产生的强制弹出仍会被 USB 线程拦下)
+    fn is_destructive(&self) -> bool {
+        !matches!(
+            self,
+            UsbCmd::Scan(_) | UsbCmd::SpeedTest(_) | UsbCmd::DevicePrefs(_, _) | UsbCmd::SetPolicy(_)
+        )
+    }
+
+    /// 该命令可能调用的外部命令行，用于执行前向用户展示
+    fn external_commands(&self) -> Vec<String> {
+        match self {
+            UsbCmd::FsutilDismount(_) | UsbCmd::ForceEject(_, _) => match self.drive() {
+                Some(d) => vec![geek_commands::fsutil_spec(&d).command_line()],
+                None => vec![],
+            },
+            UsbCmd::Format(d, job) => vec![format_volume::spec(d, job).command_line()],
+            _ => vec![],
+        }
+    }
+
+    /// 可合并的重复命令：同一盘符上的扫描 / 强制弹出 / fsutil，或终止同一 PID；
+    /// 其余命令带有各自的参数 (卷标、格式化选项、开关等)，一律不合并
+    fn same_as(&self, other: &UsbCmd) -> bool {
+        match (self, other) {
+            (UsbCmd::Scan(_), UsbCmd::Scan(_))
+            | (UsbCmd::ForceEject(_, _), UsbCmd::ForceEject(_, _))
+            | (UsbCmd::FsutilDismount(_), UsbCmd::FsutilDismount(_)) => {
+                self.drive().is_some() && self.drive() == other.drive()
+            }
+            (UsbCmd::KillOne(a, _), UsbCmd::KillOne(b, _)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// 需要用户确认后才会执行外部命令的操作
+enum ExternalAction {
+    Usb(UsbCmd),
+    Tool(tool_runner::ToolSpec),
+    Repair,
+    NetReset(Vec<net_reset::NetStep>),
+    /// 先结束进程，再启动卸载程序 (或 winget 升级 / 卸载)
+    Uninstall(tool_runner::ToolSpec, Vec<u32>),
+}
+
+impl ExternalAction {
+    fn command_lines(&self) -> Vec<String> {
+        match self {
+            ExternalAction::Usb(cmd) => cmd.external_commands(),
+            ExternalAction::Tool(spec) => vec![spec.command_line()],
+            ExternalAction::Repair => system_repair::RepairSession::command_lines(),
+            ExternalAction::NetReset(steps) => net_reset::NetResetSession::command_lines(steps),
+            ExternalAction::Uninstall(spec, _) => vec![spec.command_line()],
+        }
+    }
+}
+
+/// 入队时合并重复命令，避免连点“安全弹出”反复跑完整的慢速流程
+fn enqueue_usb_cmd(pending: &mut VecDeque<UsbCmd>, cmd: UsbCmd) {
+    if !pending.iter().any(|p| p.same_as(&cmd)) {
+        pending.push_back(cmd);
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 struct ProcessInfo {
+    #[serde(rename = "name")]
     chinese_name: String,
     category: String,
 }
@@ -71,18 +298,183 @@ impl ProcessInfo {
     }
 }
 
+/// 组内单个进程的明细 (展开分组时显示)
+#[derive(Clone, Debug)]
+struct PidDetail {
+    pid: u32,
+    memory: u64,
+    usage: process_memory::Usage, // 工作集 / 私有工作集 / 提交大小
+    cpu: f32,
+    cmd: String,
+    start_time: u64, // Unix 时间戳 (秒)
+    arch: Option<arch::ProcessArch>, // 无权访问时为 None
+    owner: Option<process_owner::Owner>, // 无权访问时为 None
+    disk_bps: u64, // 读写速率合计 (字节/秒)
+    gpu: f32,      // GPU 占用 (%)，未采样时为 0
+}
+
 #[derive(Clone, Debug)]
 struct ProcessGroup {
     name: String,
     friendly_name: String,
     category: String,
     total_memory: u64,
+    usage: process_memory::Usage, // 组内各进程三种内存口径之和
     total_cpu: f32,
+    disk_bps: u64, // 组内各进程读写速率之和 (字节/秒)
+    gpu: f32,      // 组内各进程 GPU 占用之和 (%)
     pids: Vec<u32>,
+    members: Vec<PidDetail>, // 与 pids 对应，按内存降序
+    exe_path: String,        // 首个实例的可执行文件路径
+    arch: Option<arch::ProcessArch>, // 首个实例的架构
     is_system: bool,
     is_not_responding: bool,
+    other_users: bool, // 含其他交互用户 (非本账户、非服务会话) 的进程
+    signature: Option<signature::Signature>, // 首个实例 exe 的签名，None 表示尚在校验
+    history: Vec<(f32, u64)>, // 最近若干轮的 (CPU %, 内存字节)，旧在前，用于迷你曲线
+}
+
+/// 进程表的排序列
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SortKey {
+    Name,
+    Count,
+    Memory,
+    Cpu,
+    DiskIo,
+    Gpu,
+}
+
+/// 进程表的排序状态，保存在界面；后台线程只负责采集，不决定顺序
+#[derive(Clone, Copy, Debug)]
+struct ProcessSort {
+    key: SortKey,
+    descending: bool,
+}
+
+impl Default for ProcessSort {
+    fn default() -> Self {
+        Self {
+            key: SortKey::Memory,
+            descending: true,
+        }
+    }
+}
+
+impl ProcessSort {
+    fn compare(&self, a: &ProcessGroup, b: &ProcessGroup, metric: process_memory::Metric) -> std::cmp::Ordering {
+        let display = |g: &ProcessGroup| {
+            if g.friendly_name.is_empty() { g.name.to_lowercase() } else { g.friendly_name.to_lowercase() }
+        };
+        let ord = match self.key {
+            SortKey::Name => display(a).cmp(&display(b)),
+            SortKey::Count => a.pids.len().cmp(&b.pids.len()),
+            SortKey::Memory => a.usage.get(metric).cmp(&b.usage.get(metric)),
+            SortKey::Cpu => a.total_cpu.total_cmp(&b.total_cpu),
+            SortKey::DiskIo => a.disk_bps.cmp(&b.disk_bps),
+            SortKey::Gpu => a.gpu.total_cmp(&b.gpu),
+        };
+        if self.descending {
+            ord.reverse()
+        } else {
+            ord
+        }
+    }
+
+    /// 点击表头：同一列切换方向；换列时名称默认升序，数值默认降序
+    fn click(&mut self, key: SortKey) {
+        if self.key == key {
+            self.descending = !self.descending;
+        } else {
+            self.key = key;
+            self.descending = key != SortKey::Name;
+        }
+    }
+
+    fn arrow(&self, key: SortKey) -> &'static str {
+        match (self.key == key, self.descending) {
+            (false, _) => "",
+            (true, true) => " ▼",
+            (true, false) => " ▲",
+        }
+    }
+}
+
+/// 搜索框编译后的条件
+enum SearchMatcher {
+    /// 不区分大小写的子串；纯数字时还按 PID 精确匹配
+    Text(String, Option<u32>),
+    Regex(regex::Regex),
+}
+
+/// 进程表的搜索过滤：按进程名、友好名、分类或 PID 匹配三组进程。
+/// 搜索框内容或正则开关变化时才重新编译，正则写错时不过滤并提示错误
+struct ProcessSearch {
+    query: String,
+    regex: bool,
+    matcher: Result<Option<SearchMatcher>, String>,
+}
+
+impl Default for ProcessSearch {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            regex: false,
+            matcher: Ok(None),
+        }
+    }
+}
+
+impl ProcessSearch {
+    /// 同步搜索框内容，条件有变化时返回 true
+    fn update(&mut self, query: &str, regex: bool) -> bool {
+        if self.query == query && self.regex == regex {
+            return false;
+        }
+        self.query = query.to_string();
+        self.regex = regex;
+        let q = query.trim();
+        self.matcher = if q.is_empty() {
+            Ok(None)
+        } else if regex {
+            regex::RegexBuilder::new(q)
+                .case_insensitive(true)
+                .build()
+                .map(|r| Some(SearchMatcher::Regex(r)))
+                .map_err(|e| e.to_string())
+        } else {
+            Ok(Some(SearchMatcher::Text(q.to_lowercase(), q.parse().ok())))
+        };
+        true
+    }
+
+    fn is_active(&self) -> bool {
+        matches!(self.matcher, Ok(Some(_)))
+    }
+
+    fn error(&self) -> Option<&str> {
+        self.matcher.as_ref().err().map(|e| e.as_str())
+    }
+
+    fn matches(&self, group: &ProcessGroup) -> bool {
+        let category = process_db::category_label(&group.category);
+        let fields = [group.name.as_str(), group.friendly_name.as_str(), group.category.as_str(), category];
+        match &self.matcher {
+            Ok(Some(SearchMatcher::Text(text, pid))) => {
+                fields.iter().any(|f| f.to_lowercase().contains(text.as_str()))
+                    || pid.is_some_and(|pid| group.pids.contains(&pid))
+            }
+            Ok(Some(SearchMatcher::Regex(re))) => {
+                fields.iter().any(|f| re.is_match(f)) || group.pids.iter().any(|pid| re.is_match(&pid.to_string()))
+            }
+            Ok(None) | Err(_) => true,
+        }
+    }
 }
 
+/// 每个进程分组保留的历史轮数 (正常刷新下约 1 分钟)
+const GROUP_HISTORY_LEN: usize = 120;
+
 #[derive(Clone, Debug, Default)]
 struct DiskData {
     mount_point: String,
@@ -90,8 +482,29 @@ struct DiskData {
     available_space: u64,
     total_space: u64,
     is_removable: bool,
+    bus: Option<usb_info::BusInfo>, // 仅可移动磁盘查询
+    identity: Option<usb_info::DeviceIdentity>,
+    device_key: Option<String>,     // 物理设备标识 (厂商 型号 #序列号)，用于设备偏好
+    read_only: bool,                // 卷以只读方式挂载
+    write_protected: bool,          // 所在磁盘带只读属性 (写保护或只读挂载)
+    disk_number: Option<u32>,       // 所在物理磁盘编号，多分区设备据此分组
+    read_bps: u64,                  // 实时读取速率 (字节/秒)
+    write_bps: u64,                 // 实时写入速率 (字节/秒)
+    since_last_write: Option<Duration>, // 距上次观察到写入的时间 (本次运行内)
+    smart: Option<Result<smart::SmartHealth, String>>, // 所在物理磁盘的 SMART 健康
+    removal_policy: Option<removal_policy::RemovalPolicy>, // 快速删除 / 更好的性能 (仅可移动磁盘)
+    first_seen: Option<Instant>,    // 本次运行内首次出现的时间，用于找“最近插入”的盘
 }
 
+/// 最近这段时间内有写入时，弹出前需要二次确认
+const RECENT_WRITE_GUARD: Duration = Duration::from_secs(5);
+
+/// SMART 数值变化很慢，按物理磁盘缓存这么久再重新查询
+const SMART_REFRESH: Duration = Duration::from_secs(300);
+
+/// 主窗口标题，托盘据此找到主窗口
+const APP_TITLE: &str = "Geek Killer Pro";
+
 /// 共享给 UI 的数据快照（解决 UI 卡顿的核心）
 #[derive(Clone, Default)]
 struct AppSnapshot {
@@ -100,6 +513,7 @@ struct AppSnapshot {
     system_groups: Vec<ProcessGroup>,
 
     global_cpu: f32,
+    per_core: Vec<f32>, // 各逻辑核心占用 (%)
     used_memory: u64,
     total_memory: u64,
 
@@ -109,8 +523,30 @@ struct AppSnapshot {
     disks: Vec<DiskData>,
 
     is_resource_tight: bool,
+
+    // 数据库中没有收录的进程，供“提交未知进程”导出
+    unknown_processes: Vec<process_db::UnknownProcess>,
+
+    // 内存诊断：页面文件 / 提交内存 / 硬错误
+    memory_report: memory_advisor::MemoryReport,
+
+    // 采集完成的时刻 (用于提示数据陈旧) 与本轮采集耗时
+    taken_at: Option<Instant>,
+    taken_at_local: String,
+    tick_cost: Duration,
+    timings: tick_health::TickTimings, // 分阶段耗时，供诊断面板
+
+    // 看门狗最近的触发记录 (新在前)
+    watchdog_log: Vec<watchdog::Event>,
+
+    // 进程 / 线程 / 句柄总数与开机时长 (标题栏下方)
+    counters: Option<system_counters::Counters>,
 }
 
+/// 快照超过这么久没更新即提示“数据延迟” (正常 0.5s 一轮，极简模式 2s)
+const STALE_AFTER: Duration = Duration::from_secs(3);
+const STALE_AFTER_TIGHT: Duration = Duration::from_secs(5);
+
 // ═══════════════════════════════════════════════════════════════
 //  Win32 API 封装 (FileDescription & RestartManager)
 // ═══════════════════════════════════════════════════════════════
@@ -133,12 +569,16 @@ extern "system" {
 }
 
 fn get_exe_file_description(exe_path: &std::path::Path) -> Option<String> {
-    use std::os::windows::ffi::OsStrExt;
-    let path_wide: Vec<u16> = exe_path
-        .as_os_str()
-        .encode_wide()
-        .chain(std::iter::once(0))
-        .collect();
+    get_exe_version_string(exe_path, "FileDescription")
+}
+
+/// 文件版本信息中的公司名，用于匹配安装条目的发布者
+fn get_exe_company_name(exe_path: &std::path::Path) -> Option<String> {
+    get_exe_version_string(exe_path, "CompanyName")
+}
+
+fn get_exe_version_string(exe_path: &std::path::Path, key: &str) -> Option<String> {
+    let path_wide = long_path::to_wide(arch::native_path(exe_path).as_os_str());
 
     unsafe {
         let mut _handle = 0;
@@ -171,8 +611,8 @@ fn get_exe_file_description(exe_path: &std::path::Path) -> Option<String> {
                 let lang_id = langs[i];
                 let charset_id = langs[i + 1];
                 let sub_block = format!(
-                    "\\StringFileInfo\\{:04x}{:04x}\\FileDescription",
-                    lang_id, charset_id
+                    "\\StringFileInfo\\{:04x}{:04x}\\{}",
+                    lang_id, charset_id, key
                 );
                 if let Some(desc) = query_string_value(&buffer, &sub_block) {
                     description = Some(desc);
@@ -182,13 +622,10 @@ fn get_exe_file_description(exe_path: &std::path::Path) -> Option<String> {
         }
 
         if description.is_none() {
-            let fallbacks = [
-                "\\StringFileInfo\\080404b0\\FileDescription",
-                "\\StringFileInfo\\040904b0\\FileDescription",
-                "\\StringFileInfo\\000004b0\\FileDescription",
-            ];
+            let fallbacks = ["080404b0", "040904b0", "000004b0"];
             for fb in fallbacks {
-                if let Some(desc) = query_string_value(&buffer, fb) {
+                let sub_block = format!("\\StringFileInfo\\{}\\{}", fb, key);
+                if let Some(desc) = query_string_value(&buffer, &sub_block) {
                     description = Some(desc);
                     break;
                 }
@@ -240,8 +677,10 @@ mod rm {
     }
 
     fn volume_guid_root(drive_letter: &str) -> Option<String> {
-        let letter = drive_letter.trim_end_matches(':').to_uppercase();
-        let mount = format!("{}:\\", letter);
+        if crate::mount_manager::is_volume_guid(drive_letter) {
+            return Some(crate::mount_manager::volume_root(drive_letter));
+        }
+        let mount = crate::mount_manager::volume_root(drive_letter);
         let mut out = [0u16; 128];
         let ok = unsafe {
             GetVolumeNameForVolumeMountPointW(
@@ -284,14 +723,20 @@ mod rm {
     }
 
     fn register_drive(session: &Session, drive_letter: &str) -> Result<(), String> {
-        let letter = drive_letter.trim_end_matches(':').to_uppercase();
-        let root = format!("{}:\\", letter);
-        let vol = volume_guid_root(&letter);
+        let root = crate::mount_manager::volume_root(drive_letter);
+        let vol = volume_guid_root(drive_letter);
 
-        let mut paths: Vec<Vec<u16>> = vec![w(&root)];
+        // 同一个卷的盘符、文件夹挂载点与 GUID 路径都要登记，任一路径下的占用都会被报告
+        let mut roots = vec![root];
         if let Some(v) = vol {
-            paths.push(w(&v));
+            for path in crate::mount_manager::mount_paths(&v) {
+                if !roots.iter().any(|r| r.eq_ignore_ascii_case(&path)) {
+                    roots.push(path);
+                }
+            }
+            roots.push(v);
         }
+        let paths: Vec<Vec<u16>> = roots.iter().map(|r| w(r)).collect();
 
         let ptrs: Vec<*const u16> = paths.iter().map(|p| p.as_ptr()).collect();
         unsafe {
@@ -314,7 +759,32 @@ mod rm {
     pub fn list_occupants(drive_letter: &str) -> Result<Vec<Occupant>, String> {
         let s = start_session()?;
         register_drive(&s, drive_letter)?;
+        list(&s)
+    }
+
+    /// 占用指定文件的进程 (清理临时文件时用于说明跳过原因)
+    pub fn file_holders(paths: &[String]) -> Result<Vec<Occupant>, String> {
+        let s = start_session()?;
+        let wide: Vec<Vec<u16>> = paths.iter().map(|p| w(p)).collect();
+        let ptrs: Vec<*const u16> = wide.iter().map(|p| p.as_ptr()).collect();
+        unsafe {
+            let rc = RmRegisterResources(
+                s.0,
+                ptrs.len() as u32,
+                ptrs.as_ptr(),
+                0,
+                std::ptr::null(),
+                0,
+                std::ptr::null(),
+            );
+            if rc != 0 {
+                return Err(format!("RmRegisterResources rc={}", rc));
+            }
+        }
+        list(&s)
+    }
 
+    fn list(s: &Session) -> Result<Vec<Occupant>, String> {
         unsafe {
             let mut needed: u32 = 0;
             let mut count: u32 = 0;
@@ -360,12 +830,17 @@ mod rm {
                     "Unknown".into()
                 };
                 let desc = if !svc.is_empty() {
-                    format!("RestartManager：{} (服务:{})", app, svc)
+                    trf("RestartManager：{} (服务:{})", &[&app, &svc])
                 } else {
                     format!("RestartManager：{}", app)
                 };
 
-                out.push(Occupant { pid, name, desc });
+                out.push(Occupant {
+                    pid,
+                    name,
+                    desc,
+                    locked_paths: vec![],
+                });
             }
             Ok(out)
         }
@@ -390,10 +865,7 @@ mod rm {
 //  极客命令封装 (Geek Commands) - 调用系统原生工具
 // ═══════════════════════════════════════════════════════════════
 mod geek_commands {
-    use std::process::Command;
-    use std::os::windows::process::CommandExt;
-
-    const CREATE_NO_WINDOW: u32 = 0x08000000;
+    use crate::tool_runner::{run_to_completion, ToolSpec};
 
     /// 辅助函数：尝试刷新卷缓冲区（最大限度保护数据）
     pub fn try_flush(drive: &str) {
@@ -403,7 +875,7 @@ mod geek_commands {
             OPEN_EXISTING,
         };
         
-        let drive_path = format!("\\\\.\\{}:", drive);
+        let drive_path = crate::mount_manager::device_path(drive);
         let path_wide: Vec<u16> = drive_path.encode_utf16().chain(std::iter::once(0)).collect();
         
         unsafe {
@@ -423,6 +895,16 @@ mod geek_commands {
         }
     }
 
+    pub fn fsutil_spec(drive_letter: &str) -> ToolSpec {
+        ToolSpec::new(tr("fsutil 卸载卷"), "fsutil", &["volume", "dismount", &crate::mount_manager::display_name(drive_letter)])
+    }
+
+    /// chkdsk 只读检查 (不带 /f，不修改卷)
+    pub fn chkdsk_spec(drive_letter: &str) -> ToolSpec {
+        let drive = crate::mount_manager::display_name(drive_letter);
+        ToolSpec::new(&trf("检查磁盘 {}", &[&drive]), "chkdsk", &[&drive])
+    }
+
     /// 方法 1: fsutil dismount (推荐！最干净)
     /// 相当于 FSCTL_DISMOUNT_VOLUME，但由系统工具执行，更稳定
     pub fn eject_by_fsutil(drive_letter: &str) -> Result<(), String> {
@@ -432,16 +914,13 @@ mod geek_commands {
         try_flush(drive);
 
         // fsutil volume dismount E:
-        let output = Command::new("fsutil")
-            .args(["volume", "dismount", &format!("{}:", drive)])
-            .creation_flags(CREATE_NO_WINDOW)
-            .output()
-            .map_err(|e| format!("无法启动 fsutil: {}", e))?;
+        let (success, stdout, stderr) = run_to_completion(&fsutil_spec(drive))?;
 
-        if output.status.success() {
+        if success {
             Ok(())
         } else {
-            let err = String::from_utf8_lossy(&output.stderr).to_string();
+            // fsutil 的错误有时输出到 stdout
+            let err = if stderr.trim().is_empty() { stdout } else { stderr };
             // 即使报错，有时候也可能生效，或者是 "没有装载卷" 之类的错误
             if err.contains("没有装载") || err.contains("not mounted") {
                 Ok(())
@@ -459,40 +938,661 @@ mod geek_commands {
 struct GeekKillerApp {
     // UI 状态
     search_query: String,
+    search_regex: bool,
+    search: ProcessSearch,
     is_admin: bool,
     show_performance: bool,
     show_diagnostics: bool,
     show_usb_manager: bool,
+    show_security: bool,
+    show_startup: bool,
+    show_network: bool,
+    view: settings::View,    // 完整视图 / U 盘精简视图
+    active_profile: String,  // 当前使用的启动配置
+    new_profile_name: String, // “另存为新配置”输入框
 
     // USB 状态
     usb_state: UsbState,
     usb_tx: mpsc::Sender<UsbCmd>,
     usb_rx: mpsc::Receiver<UsbMsg>,
+    gated_rx: mpsc::Receiver<UsbCmd>, // 托盘 / 快捷键发起、需经 send_usb_cmd 检查的命令
     usb_status_msg: String,
     usb_msg_time: Option<Instant>,
+    usb_busy: HashSet<String>, // 正在执行操作的盘符，按钮据此禁用
+    kill_progress: Option<kill_batch::KillProgress>, // 强力清场进度，显示在占用面板中
+    eject_policy: EjectPolicy,
+    eject_stats: EjectStats,
+    eject_history: Option<Vec<eject_history::EjectRecord>>, // 弹出历史缓存，后台状态变化时失效
 
     // 数据快照（从后台线程获取）
     snapshot: Arc<RwLock<AppSnapshot>>,
 
-    // 配置
-    #[allow(dead_code)]
-    auto_low_power: bool,
-    #[allow(dead_code)]
-    enhanced_mode: bool,
-
     // 视图控制
     paused: bool,
     cached_snapshot: Arc<AppSnapshot>,
     last_tight_state: bool, // 记录上一次的负载状态，用于边缘触发
+    export_msg: Option<String>,
+    memory_trim_rx: Option<mpsc::Receiver<String>>, // 进行中的内存整理
+    purge_standby: bool,                             // 内存整理时同时清空备用列表
+    eject_confirm: Option<String>, // 刚写入过、等待二次确认弹出的盘符
+    pending_external: Option<ExternalAction>, // 等待用户确认外部命令的操作
+    always_allow_external: bool,              // 用户选择了“总是允许”
+    pending_admin: Option<(String, UsbCmd)>,  // 预检发现需要管理员权限的操作 (原因, 命令)
+    pending_gpu: Option<Vec<String>>,         // 被拦下的显示关键进程，提示改用重启显卡驱动
+    console: Option<tool_runner::ToolRun>,    // 外部工具控制台
+    uninstall_rx: Option<mpsc::Receiver<tool_runner::ToolSpec>>, // 卸载前正在结束进程，结束后收到卸载命令
+    repair: Option<system_repair::RepairSession>, // 系统修复流程
+    net_reset: Option<net_reset::NetResetSession>, // 网络重置流程
+    net_reset_winsock: bool,                       // 网络重置包含 Winsock 重置 (需重启)
+    kill_tree: bool,                  // 终止时连同子进程
+    expanded_groups: HashSet<String>, // 展开显示单个 PID 的分组
+    selected_groups: HashSet<String>, // 选中的分组 (Ctrl+C 复制、底部批量操作栏)
+    batch_kill_confirm: Option<HashSet<String>>, // 批量终止已点过一次时的选择，选择变化后需重新确认
+    process_sort: ProcessSort,        // 进程表当前的排序列与方向
+    mitigation_cache: HashMap<(u32, u64), Option<mitigations::Mitigations>>, // (pid, 启动时间)
+
+    // 安全检查 (后台扫描)
+    security_report: Option<security_scan::ScanReport>,
+    security_rx: Option<mpsc::Receiver<security_scan::ScanReport>>,
+    hibernation: Option<(Instant, hibernation::Status)>, // 休眠 / 快速启动状态及读取时间
+    exe_hashes: reputation::HashCache, // exe 路径 -> SHA-256
+    reputation: reputation::Lookups,    // SHA-256 -> VirusTotal 查询结果
+    vt_key_draft: Option<String>,       // 编辑中的 VirusTotal API Key
+
+    // 启动项管理
+    startup_items: Option<Vec<startup_items::StartupItem>>,
+    startup_dirty: bool, // 计划任务修改后，等 schtasks 结束再重新读取
+    startup_rx: Option<mpsc::Receiver<Vec<startup_items::StartupItem>>>, // 后台读取中的启动项
+    startup_msg: Option<String>,
+    startup_highlight: Option<(startup_items::StartupSource, String)>, // 从进程明细跳转过来的条目
+    startup_scroll_pending: bool,
+    autostart_services: Option<(Instant, Vec<startup_items::AutostartService>)>,
+    autostart_services_rx: Option<mpsc::Receiver<Vec<startup_items::AutostartService>>>, // 后台枚举中的自动启动服务
+    installed_apps: Option<(Instant, Vec<uninstall::InstalledApp>)>,
+    company_cache: HashMap<String, Option<String>>, // exe 路径 -> 公司名
+    winget_packages: Option<(Instant, Result<Vec<winget::WingetPackage>, String>)>,
+    winget_rx: Option<mpsc::Receiver<Result<Vec<winget::WingetPackage>, String>>>,
+    winget_versions: HashMap<String, winget::Versions>, // 包 Id -> 可用版本
+    winget_versions_rx: Option<mpsc::Receiver<(String, winget::Versions)>>,
+
+    // 网络连接
+    net_conns: Vec<net_connections::Connection>,
+    net_refreshed_at: Option<Instant>,
+    net_port_filter: String,
+    net_msg: Option<String>,
+
+    // 挂载点管理
+    mount_editor: Option<String>, // 展开挂载设置的盘符 (已规范化)
+    mount_letter: char,
+    mount_folder: String,
+    format_job: format_volume::FormatJob,
+    format_confirm: Option<String>, // 等待二次确认格式化的盘符
+    image_job: image_writer::ImageJob,
+    image_confirm: Option<String>, // 等待二次确认写入镜像的盘符
+    wipe_job: secure_wipe::WipeJob,
+    wipe_confirm: String, // 安全擦除前需手动输入盘符
+    wipe_cancel: Option<Arc<std::sync::atomic::AtomicBool>>, // 已下发的擦除，状态栏提供取消
+    relabel: String,
+    unmounted_volumes: Option<(Instant, Vec<mount_manager::UnmountedVolume>)>,
+
+    // 插拔提示
+    known_removable: Option<HashMap<String, String>>, // 盘符 -> 显示名 (昵称或卷名)
+    toasts: Vec<(Instant, String)>,
+
+    // 插入检查 (autorun / 快捷方式病毒)
+    scan_on_insert: bool,
+    usb_write_policy: bool, // StorageDevicePolicies\WriteProtect 当前值
+    autorun_tx: mpsc::Sender<(String, Vec<autorun_scan::Threat>)>,
+    autorun_rx: mpsc::Receiver<(String, Vec<autorun_scan::Threat>)>,
+    autorun_threats: HashMap<String, Vec<autorun_scan::Threat>>, // 盘符 -> 可疑项
+    open_confirm: Option<String>,                                // 有可疑项时“打开”需要二次确认
+
+    // 插入即备份
+    backup_tx: mpsc::Sender<(String, backup::BackupEvent)>,
+    backup_rx: mpsc::Receiver<(String, backup::BackupEvent)>,
+    backups: HashMap<String, backup::BackupState>, // 盘符 -> 进行中的备份
+    speed_results: HashMap<String, speed_test::SpeedResult>, // 盘符 -> 最近一次测速结果
+    backup_ran: HashSet<String>,                   // 本次插入已执行过备份的设备标识
+
+    // 拷贝校验 (BLAKE3)
+    verify_tx: mpsc::Sender<(String, checksum::VerifyEvent)>,
+    verify_rx: mpsc::Receiver<(String, checksum::VerifyEvent)>,
+    verifies: HashMap<String, checksum::VerifyState>,        // 盘符 -> 进行中的校验
+    verify_reports: HashMap<String, Result<checksum::VerifyReport, String>>, // 盘符 -> 最近一次结果
+    verify_source: String,
+    verify_dest: String,
+
+    // 设备偏好 (按序列号)
+    device_prefs: device_prefs::PrefStore,
+    pref_draft: Option<(String, device_prefs::DevicePrefs)>, // 编辑中的 (设备标识, 偏好)
+    last_io: HashMap<String, Instant>,                       // 盘符 -> 最近一次读写
+    auto_ejected: HashSet<String>,                           // 已因空闲自动弹出过的盘符
+
+    // 面板截图
+    capture: Option<panel_capture::Capture>,
+    capture_save: bool, // 截图同时保存 PNG
+
+    // 托盘与持久设置
+    settings: settings::AppSettings,
+    quit_requested: Arc<std::sync::atomic::AtomicBool>, // 托盘菜单点了“退出”
+    hud_open: Arc<std::sync::atomic::AtomicBool>,       // 迷你悬浮窗，托盘菜单也可切换
+    hud_shown: bool,                                    // 上一帧悬浮窗是否已打开
+
+    // 全局快捷键
+    hotkeys: hotkeys::HotkeyService,
+    hotkey_draft: Vec<hotkeys::Binding>, // 视图菜单中编辑中的绑定，点“应用”后生效
+
+    // 点选窗口
+    picker_rx: Option<mpsc::Receiver<Result<window_picker::Picked, String>>>, // 拾取进行中
+    guest_hours: u32,      // 访客模式时长输入
+    guest_password: String, // 开启访客模式时设定的密码
+    guest_unlock: String,   // 提前结束访客模式时输入的密码
+    picked: Option<window_picker::Picked>,
+    suspended: Vec<(u32, String)>, // 本程序挂起的 (PID, 名称)，供随时恢复
+    watchdog_suspended_rx: mpsc::Receiver<(u32, String)>, // 看门狗规则挂起的进程
+
+    // 采集耗时统计
+    tick_health: tick_health::TickHealth,
+    tuning: Arc<RwLock<tick_health::Tuning>>, // 与后台线程共享的刷新间隔与资源紧张阈值
+    show_settings: bool,                      // 设置窗口
+    start_hidden: bool,                       // 启动后隐藏到托盘 (首帧执行)
+
+    // 最近 10 分钟的性能曲线 (后台线程追加)
+    perf_history: Arc<Mutex<perf_history::History>>,
+
+    // 分组覆盖 (与监控线程共享)
+    overrides: Arc<RwLock<process_overrides::OverrideStore>>,
+    override_draft: Option<(String, process_overrides::Override)>, // 编辑中的 (可执行文件路径, 覆盖)
+    show_hidden: bool, // 进程表中显示已隐藏的分组
+    only_hung: bool,   // 进程表只显示窗口无响应的分组
+    only_other_users: bool, // 进程表只显示含其他用户进程的分组
+    current_sid: String, // 本程序运行所用账户的 SID，用于标出其他用户的进程
+
+    // 看门狗规则 (与监控线程共享) 与编辑中的副本
+    watchdog_rules: Arc<RwLock<watchdog::RuleSet>>,
+    watchdog_draft: watchdog::RuleSet,
+
+    // 计费网络断网：设置 (与后台线程共享)、后台状态与名单编辑框
+    metered_config: Arc<RwLock<metered::Config>>,
+    metered_status: Arc<Mutex<metered::Status>>,
+    metered_programs: String,
+
+    // 局域网主机：新增表单、等待二次确认的电源操作、进行中的远程命令
+    lan_draft: lan_power::Machine,
+    lan_confirm: Option<(usize, lan_power::PowerAction)>,
+    lan_rx: Option<mpsc::Receiver<String>>,
+
+    // 外部显示接口：设置 (与服务线程共享) 与服务状态
+    widget_config: Arc<RwLock<widget_api::Config>>,
+    widget_status: Arc<Mutex<widget_api::Status>>,
+
+    // 操作宏 (与快捷键 / 托盘共享)、编辑中的副本、正在录制的宏与最近一次执行的结果
+    macros: Arc<RwLock<Vec<macros::Macro>>>,
+    macro_draft: Vec<macros::Macro>,
+    macro_recording: Option<macros::Macro>,
+    macro_progress: Option<macros::RunProgress>,
+
+    // 进程历史搜索
+    history_query: String,
+    history_results: Option<Result<Vec<process_history::Run>, String>>,
+
+    // 两台机器对比：载入的快照路径与对方快照 + 对比结果
+    compare_path: String,
+    compare: Option<Result<(process_compare::MachineSnapshot, process_compare::Diff), String>>,
+
+    // 使用时长：查看的日期 (本地零点) 与该日的统计 (每分钟重新读取)
+    screen_time_day: u64,
+    screen_time_rows: Option<(u64, Instant, Result<Vec<(String, u64)>, String>)>,
+
+    // 一键修复：修复声音 / 重置打印后台 / 清理临时文件
+    audio_fix: service_control::QuickFix,
+    spooler_fix: service_control::QuickFix,
+    temp_cleanup: service_control::QuickFix,
+
+    // 强力清场确认 / 撤销倒计时
+    pending_clear: Option<clear_confirm::PendingClear>,
+    module_view: Option<modules::View>, // 进程的模块 (DLL) 列表窗口
 }
 
 fn norm_drive(d: &str) -> String {
     d.trim_end_matches([':', '\\', '/']).to_uppercase()
 }
 
-/// 智能弹出：尝试刷新驱动器文件缓冲 (Sync) 并强制卸载卷 (Dismount)
-/// 并尝试弹出物理设备（解决 VetoType 6）
-fn smart_eject(drive: &str) -> Result<(), String> {
+/// 本地时间 "YYYY-MM-DD HH:MM:SS"，用于日志与历史记录
+fn local_timestamp() -> String {
+    use windows_sys::Win32::System::SystemInformation::GetLocalTime;
+    let t = unsafe {
+        let mut t = std::mem::zeroed();
+        GetLocalTime(&mut t);
+        t
+    };
+    format_systemtime(&t)
+}
+
+fn format_systemtime(t: &windows_sys::Win32::Foundation::SYSTEMTIME) -> String {
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        t.wYear, t.wMonth, t.wDay, t.wHour, t.wMinute, t.wSecond
+    )
+}
+
+/// Unix 秒 -> 本地时间字符串
+fn format_unix_local(secs: u64) -> String {
+    use windows_sys::Win32::Foundation::FILETIME;
+    use windows_sys::Win32::System::Time::FileTimeToSystemTime;
+    use windows_sys::Win32::Storage::FileSystem::FileTimeToLocalFileTime;
+
+    // FILETIME 以 1601-01-01 起的 100ns 为单位
+    let ticks = (secs + 11_644_473_600) * 10_000_000;
+    let utc = FILETIME {
+        dwLowDateTime: ticks as u32,
+        dwHighDateTime: (ticks >> 32) as u32,
+    };
+    unsafe {
+        let mut local = std::mem::zeroed();
+        let mut t = std::mem::zeroed();
+        if FileTimeToLocalFileTime(&utc, &mut local) == 0 || FileTimeToSystemTime(&local, &mut t) == 0 {
+            return String::new();
+        }
+        format_systemtime(&t)
+    }
+}
+
+/// Unix 秒 -> “2 分钟前” 形式的相对时间
+fn format_relative(secs: u64) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let ago = now.saturating_sub(secs);
+    match ago {
+        0..=59 => tr("刚刚").to_string(),
+        60..=3599 => trf("{} 分钟前", &[&(ago / 60)]),
+        3600..=86399 => trf("{} 小时前", &[&(ago / 3600)]),
+        _ => trf("{} 天前", &[&(ago / 86400)]),
+    }
+}
+
+/// 进程的可执行文件名，进程已退出时显示 PID
+fn process_name(pid: u32) -> String {
+    handles::process_image_name(pid)
+        .map(|p| p.rsplit(['\\', '/']).next().unwrap_or(&p).to_string())
+        .unwrap_or_else(|| format!("PID {}", pid))
+}
+
+/// 终止进程表中的一组进程 (可连同子进程)；显示关键进程一律拒绝
+fn kill_group(pids: &[u32], tree: bool) {
+    for &pid in pids {
+        let name = process_name(pid);
+        if gpu_guard::is_display_critical(&name) {
+            audit::record("终止进程", &format!("{} (PID {}) -> 显示关键进程，已拒绝", name, pid));
+            continue;
+        }
+        if tree {
+            process_tree::kill_tree(pid);
+        } else {
+            let _ = rust_core_lib::process::kill(pid);
+        }
+    }
+}
+
+/// 未经用户逐个确认不得终止 / 挂起的进程：系统进程 (与进程表 is_system 口径一致)、
+/// 服务会话 0 中的进程、桌面合成与显卡驱动相关进程
+fn is_protected_process(pid: u32, name: &str) -> bool {
+    pid < 1000 || process_owner::session_of(pid) == Some(0) || gpu_guard::is_display_critical(name)
+}
+
+/// 一键修复按钮与其逐步结果
+fn quick_fix_row(
+    ui: &mut egui::Ui,
+    ctx: &egui::Context,
+    fix: &mut service_control::QuickFix,
+    is_admin: bool,
+    label: &str,
+    hint: &str,
+) {
+    fix.poll();
+    let running = fix.is_running();
+    let guest = guest_mode::is_active();
+    let hint = if guest {
+        tr("访客模式下已禁用")
+    } else if is_admin {
+        hint
+    } else {
+        tr("需要以管理员身份运行")
+    };
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(is_admin && !running && !guest, egui::Button::new(label))
+            .on_hover_text(hint)
+            .on_disabled_hover_text(hint)
+            .clicked()
+        {
+            fix.start(ctx.clone());
+        }
+        if running {
+            ui.spinner();
+        } else if !fix.log.is_empty() && ui.small_button(tr("清除")).clicked() {
+            fix.log.clear();
+        }
+    });
+    for (ok, text) in &fix.log {
+        let color = if *ok { egui::Color32::GRAY } else { egui::Color32::from_rgb(255, 80, 80) };
+        ui.label(egui::RichText::new(text).small().color(color));
+    }
+}
+
+/// SMART 摘要：按评级着色，悬停显示原因
+fn smart_label(ui: &mut egui::Ui, health: &smart::SmartHealth) {
+    let (level, reasons) = health.assess();
+    let color = match level {
+        smart::Health::Good => egui::Color32::from_rgb(120, 200, 120),
+        smart::Health::Warning => egui::Color32::GOLD,
+        smart::Health::Failing => egui::Color32::from_rgb(255, 80, 80),
+    };
+    let icon = if level == smart::Health::Good { "🩺" } else { "⚠" };
+    let hover = if reasons.is_empty() {
+        trf("SMART ({})：未发现异常", &[&health.protocol])
+    } else {
+        trf("SMART ({})：{}\n{}", &[&health.protocol, &level.label(), &reasons.join("\n")])
+    };
+    ui.label(
+        egui::RichText::new(format!("{} {} · {}", icon, level.label(), health.summary()))
+            .small()
+            .color(color),
+    )
+    .on_hover_text(hover);
+}
+
+/// 进程表的迷你曲线：CPU 实线 (0-100%)，内存淡线 (按自身峰值缩放)
+fn sparkline(ui: &mut egui::Ui, history: &[(f32, u64)]) -> egui::Response {
+    let (rect, resp) = ui.allocate_exact_size(egui::vec2(48.0, 16.0), egui::Sense::hover());
+    if history.len() < 2 {
+        return resp;
+    }
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, egui::Color32::from_rgb(30, 26, 22));
+    let step = rect.width() / (GROUP_HISTORY_LEN - 1) as f32;
+    let x0 = rect.right() - step * (history.len() - 1) as f32;
+    let mem_peak = history.iter().map(|(_, m)| *m).max().unwrap_or(1).max(1) as f32;
+    let line = |value: &dyn Fn(&(f32, u64)) -> f32| -> Vec<egui::Pos2> {
+        history
+            .iter()
+            .enumerate()
+            .map(|(i, h)| egui::pos2(x0 + step * i as f32, rect.bottom() - value(h).clamp(0.0, 1.0) * rect.height()))
+            .collect()
+    };
+    painter.add(egui::Shape::line(
+        line(&|(_, m)| *m as f32 / mem_peak),
+        egui::Stroke::new(1.0, egui::Color32::from_rgb(100, 180, 255).gamma_multiply(0.5)),
+    ));
+    painter.add(egui::Shape::line(line(&|(c, _)| c / 100.0), egui::Stroke::new(1.0, egui::Color32::GOLD)));
+
+    let n = history.len() as f32;
+    let avg = history.iter().map(|(c, _)| c).sum::<f32>() / n;
+    let peak = history.iter().map(|(c, _)| *c).fold(0.0, f32::max);
+    let mb = |b: u64| b as f32 / 1024.0 / 1024.0;
+    resp.on_hover_text(trf(
+        "最近 {} 轮：CPU 平均 {}% · 峰值 {}%\n内存 {} MB → {} MB",
+        &[
+            &history.len(),
+            &format!("{:.1}", avg),
+            &format!("{:.1}", peak),
+            &format!("{:.1}", mb(history[0].1)),
+            &format!("{:.1}", mb(history[history.len() - 1].1)),
+        ],
+    ))
+}
+
+/// 进程表的架构列：模拟运行的进程标黄
+fn arch_cell(ui: &mut egui::Ui, process_arch: Option<arch::ProcessArch>) {
+    let Some(a) = process_arch else {
+        ui.add_sized([50.0, 20.0], egui::Label::new(egui::RichText::new("-").small().color(egui::Color32::DARK_GRAY)));
+        return;
+    };
+    let color = if a.emulated { egui::Color32::GOLD } else { egui::Color32::GRAY };
+    ui.add_sized([50.0, 20.0], egui::Label::new(egui::RichText::new(a.label()).small().monospace().color(color)))
+        .on_hover_text(a.tooltip());
+}
+
+/// 可点击排序的表头
+fn sort_header(
+    ui: &mut egui::Ui,
+    sort: &mut ProcessSort,
+    key: SortKey,
+    text: &str,
+    width: f32,
+    color: egui::Color32,
+) {
+    let label = egui::RichText::new(format!("{}{}", text, sort.arrow(key))).strong().color(color);
+    if ui
+        .add_sized([width, 20.0], egui::Label::new(label).sense(egui::Sense::click()))
+        .on_hover_text(tr("点击排序，再次点击切换升序 / 降序"))
+        .clicked()
+    {
+        sort.click(key);
+    }
+}
+
+fn format_rate(bps: u64) -> String {
+    if bps >= 1024 * 1024 {
+        format!("{:.1} MB/s", bps as f64 / 1024.0 / 1024.0)
+    } else {
+        format!("{:.0} KB/s", bps as f64 / 1024.0)
+    }
+}
+
+/// 进程表中按设置显示的磁盘 I/O、GPU 与路径列
+fn optional_cells(ui: &mut egui::Ui, cols: settings::ProcessColumns, disk_bps: u64, gpu: f32, path: &str) {
+    let dim = |active: bool| if active { egui::Color32::GOLD } else { egui::Color32::DARK_GRAY };
+    if cols.disk_io {
+        ui.add_sized(
+            [80.0, 20.0],
+            egui::Label::new(egui::RichText::new(format_rate(disk_bps)).monospace().small().color(dim(disk_bps > 0))),
+        );
+    }
+    if cols.gpu {
+        ui.add_sized(
+            [60.0, 20.0],
+            egui::Label::new(egui::RichText::new(format!("{:.1}%", gpu)).monospace().small().color(dim(gpu >= 0.1))),
+        );
+    }
+    if cols.path {
+        ui.add_sized([200.0, 20.0], |ui: &mut egui::Ui| {
+            ui.add(egui::Label::new(egui::RichText::new(path).small().color(egui::Color32::GRAY)).truncate())
+                .on_hover_text(path)
+        });
+    }
+}
+
+/// 展开行中的数字签名：签名者、未签名或校验失败原因
+fn render_signature_row(ui: &mut egui::Ui, group: &ProcessGroup) {
+    if group.exe_path.is_empty() {
+        return;
+    }
+    ui.label(egui::RichText::new("└").color(egui::Color32::DARK_GRAY).monospace());
+    ui.horizontal_wrapped(|ui| {
+        ui.label(egui::RichText::new(tr("数字签名:")).small().color(egui::Color32::from_rgb(150, 140, 120)));
+        let (text, color) = match &group.signature {
+            None => (tr("校验中…").to_string(), egui::Color32::GRAY),
+            Some(s) if s.is_suspicious(&group.exe_path) => (trf("{}，且从临时目录运行", &[&s.label()]), egui::Color32::RED),
+            Some(s @ signature::Signature::Signed(_)) => (format!("✔ {}", s.label()), egui::Color32::from_rgb(120, 180, 120)),
+            Some(s) => (s.label(), egui::Color32::GOLD),
+        };
+        ui.label(egui::RichText::new(text).small().color(color)).on_hover_text(&group.exe_path);
+    });
+    ui.end_row();
+}
+
+fn tray_drive(d: &DiskData) -> tray::TrayDrive {
+    let name = mount_manager::display_name(&d.mount_point);
+    tray::TrayDrive {
+        drive: d.mount_point.clone(),
+        label: if d.name.is_empty() { name } else { format!("{} {}", name, d.name) },
+    }
+}
+
+/// 最近插入的可移动磁盘 (快捷键弹出的目标)
+fn latest_removable(snapshot: &AppSnapshot) -> Option<tray::TrayDrive> {
+    snapshot
+        .disks
+        .iter()
+        .filter(|d| d.is_removable)
+        .max_by_key(|d| d.first_seen)
+        .map(tray_drive)
+}
+
+/// 托盘右键菜单：可移动磁盘与 CPU 占用最高的非系统进程
+fn tray_menu(snapshot: &AppSnapshot, macros: Vec<(usize, String)>, hud: bool) -> tray::TrayMenu {
+    let drives = snapshot.disks.iter().filter(|d| d.is_removable).map(tray_drive).collect();
+    let own_pid = std::process::id();
+    let top_cpu = snapshot
+        .high_resource
+        .iter()
+        .chain(&snapshot.other_groups)
+        .filter(|g| !g.is_system && g.total_cpu >= 5.0 && !g.pids.contains(&own_pid))
+        .max_by(|a, b| a.total_cpu.total_cmp(&b.total_cpu))
+        .map(|g| tray::TopProcess {
+            name: g.name.clone(),
+            cpu: g.total_cpu,
+            pids: g.pids.clone(),
+        });
+    tray::TrayMenu { drives, top_cpu, macros, hud }
+}
+
+/// 迷你悬浮窗：无边框、置顶，只显示 CPU / 内存 / 网速与占用最高的进程。
+/// 拖动可移动位置，单击关闭悬浮窗并回到主窗口。主窗口隐藏时 update() 不运行，
+/// 悬浮窗自行按秒刷新，数据直接读共享快照。
+fn show_mini_hud(ctx: &egui::Context, snapshot: Arc<RwLock<AppSnapshot>>, open: Arc<std::sync::atomic::AtomicBool>) {
+    use std::sync::atomic::Ordering;
+    let builder = egui::ViewportBuilder::default()
+        .with_title(format!("{} HUD", APP_TITLE))
+        .with_decorations(false)
+        .with_always_on_top()
+        .with_resizable(false)
+        .with_taskbar(false)
+        .with_inner_size([230.0, 96.0]);
+    ctx.show_viewport_deferred(egui::ViewportId::from_hash_of("mini_hud"), builder, move |ctx, _| {
+        let metrics = snapshot.read().map(|s| widget_metrics(&s)).unwrap_or_default();
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.style_mut().interaction.selectable_labels = false;
+            let color = |pct: f32| {
+                if pct >= 90.0 {
+                    egui::Color32::from_rgb(255, 100, 100)
+                } else if pct >= 70.0 {
+                    egui::Color32::GOLD
+                } else {
+                    egui::Color32::from_rgb(100, 180, 255)
+                }
+            };
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(format!("CPU {:.0}%", metrics.cpu)).strong().color(color(metrics.cpu)));
+                ui.label(
+                    egui::RichText::new(format!("{} {:.0}%", tr("内存"), metrics.memory_percent))
+                        .strong()
+                        .color(color(metrics.memory_percent)),
+                );
+            });
+            ui.label(
+                egui::RichText::new(format!("↓ {}  ↑ {}", format_rate(metrics.net_down), format_rate(metrics.net_up)))
+                    .monospace(),
+            );
+            let top = match &metrics.top_process {
+                Some((name, cpu)) => format!("🔥 {} {:.0}%", name, cpu),
+                None => tr("没有明显占用 CPU 的进程").to_string(),
+            };
+            ui.label(egui::RichText::new(top).small().color(egui::Color32::GRAY));
+
+            let response = ui.interact(ui.max_rect(), ui.id().with("hud_drag"), egui::Sense::click_and_drag());
+            if response.drag_started() {
+                ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
+            }
+            if response.clicked() || ctx.input(|i| i.viewport().close_requested()) {
+                open.store(false, Ordering::SeqCst);
+                tray::show_main_window();
+                ctx.request_repaint_of(egui::ViewportId::ROOT);
+            }
+            response.on_hover_text(tr("单击回到主窗口，拖动可移动位置"));
+        });
+        ctx.request_repaint_after(Duration::from_secs(1));
+    });
+}
+
+/// 外部显示接口的读数
+fn widget_metrics(snapshot: &AppSnapshot) -> widget_api::Metrics {
+    let own_pid = std::process::id();
+    let top_process = snapshot
+        .high_resource
+        .iter()
+        .chain(&snapshot.other_groups)
+        .filter(|g| !g.is_system && !g.pids.contains(&own_pid))
+        .max_by(|a, b| a.total_cpu.total_cmp(&b.total_cpu))
+        .map(|g| (g.friendly_name.clone(), g.total_cpu));
+    widget_api::Metrics {
+        cpu: snapshot.global_cpu,
+        memory_percent: if snapshot.total_memory > 0 {
+            snapshot.used_memory as f32 / snapshot.total_memory as f32 * 100.0
+        } else {
+            0.0
+        },
+        memory_used: snapshot.used_memory,
+        net_down: snapshot.network_in,
+        net_up: snapshot.network_out,
+        top_process,
+        removable: snapshot.disks.iter().filter(|d| d.is_removable).count(),
+        processes: snapshot.counters.map(|c| c.processes),
+        tight: snapshot.is_resource_tight,
+    }
+}
+
+/// 本机当前的进程 / 服务快照，供两台机器对比
+fn machine_snapshot(snapshot: &AppSnapshot) -> process_compare::MachineSnapshot {
+    let processes = snapshot
+        .high_resource
+        .iter()
+        .chain(&snapshot.other_groups)
+        .chain(&snapshot.system_groups)
+        .map(|g| process_compare::ProcessEntry {
+            name: g.name.clone(),
+            count: g.pids.len(),
+            memory: g.total_memory,
+            cpu: g.total_cpu,
+        })
+        .collect();
+    process_compare::MachineSnapshot::capture(panel_capture::hostname(), snapshot.taken_at_local.clone(), processes)
+}
+
+/// 面板标题栏的“截图并复制”按钮，右键可切换是否同时保存 PNG
+fn capture_button(
+    ui: &mut egui::Ui,
+    panel: panel_capture::Panel,
+    capture: &mut Option<panel_capture::Capture>,
+    save: &mut bool,
+) {
+    let resp = ui
+        .add_enabled(capture.is_none(), egui::Button::new(tr("📷 截图并复制")).small())
+        .on_hover_text(if *save {
+            tr("截取本面板并复制到剪贴板，同时保存带主机名与时间水印的 PNG (右键设置)")
+        } else {
+            tr("截取本面板并复制到剪贴板，带主机名与时间水印 (右键设置)")
+        });
+    resp.context_menu(|ui| {
+        ui.checkbox(save, tr("同时保存 PNG"));
+    });
+    if resp.clicked() {
+        *capture = Some(panel_capture::Capture::new(panel, *save, &local_timestamp()));
+    }
+}
+
+/// 程序数据目录 (%APPDATA%\GeekKiller)，不存在时自动创建
+fn app_data_dir() -> Option<std::path::PathBuf> {
+    let dir = std::path::PathBuf::from(std::env::var_os("APPDATA")?).join("GeekKiller");
+    std::fs::create_dir_all(&dir).ok()?;
+    Some(dir)
+}
+
+/// Flush -> Lock -> Dismount 单个卷，返回其所在磁盘的设备号
+fn dismount_volume(drive_letter: &str) -> Result<Option<STORAGE_DEVICE_NUMBER>, String> {
     use windows_sys::Win32::Foundation::{CloseHandle, INVALID_HANDLE_VALUE};
     use windows_sys::Win32::Storage::FileSystem::{
         CreateFileW, FlushFileBuffers, FILE_SHARE_READ, FILE_SHARE_WRITE,
@@ -501,8 +1601,7 @@ fn smart_eject(drive: &str) -> Result<(), String> {
     use windows_sys::Win32::System::Ioctl::{FSCTL_DISMOUNT_VOLUME, FSCTL_LOCK_VOLUME};
     use windows_sys::Win32::System::IO::DeviceIoControl;
 
-    let drive_letter = drive.trim_end_matches([':', '\\', '/']);
-    let drive_path = format!("\\\\.\\{}:", drive_letter);
+    let drive_path = mount_manager::device_path(drive_letter);
     let path_wide: Vec<u16> = drive_path.encode_utf16().chain(std::iter::once(0)).collect();
 
     // 1. 打开设备句柄
@@ -517,7 +1616,7 @@ fn smart_eject(drive: &str) -> Result<(), String> {
             0,
         );
         if h == INVALID_HANDLE_VALUE {
-            return Err("无法打开驱动器 (权限不足或不存在)".to_string());
+            return Err(tr("无法打开驱动器 (权限不足或不存在)").to_string());
         }
         
         // 获取设备号以便后续 PnP 弹出
@@ -561,7 +1660,20 @@ fn smart_eject(drive: &str) -> Result<(), String> {
         // 必须确保关闭句柄
         CloseHandle(handle);
     }
-    
+    Ok(sdn)
+}
+
+/// 智能弹出：尝试刷新驱动器文件缓冲 (Sync) 并强制卸载卷 (Dismount)
+/// 并尝试弹出物理设备（解决 VetoType 6）
+fn smart_eject(drive: &str) -> Result<(), String> {
+    let drive_letter = drive.trim_end_matches([':', '\\', '/']);
+    // 多分区设备：同一物理磁盘上的其他卷也要卸载，否则父设备弹出会被否决
+    let siblings = usb_info::volumes_on_same_disk(drive_letter);
+    let sdn = dismount_volume(drive_letter)?;
+    for other in siblings.iter().filter(|v| !v.eq_ignore_ascii_case(drive_letter)) {
+        let _ = dismount_volume(other);
+    }
+
     // 给系统一点时间反应 Dismount
     std::thread::sleep(std::time::Duration::from_millis(500));
     
@@ -582,10 +1694,8 @@ fn smart_eject(drive: &str) -> Result<(), String> {
     }
 }
 
-fn find_and_eject_device(
-    target_device_number: u32,
-    target_device_type: u32,
-) -> Result<(), String> {
+/// 按 STORAGE_DEVICE_NUMBER 找到对应磁盘的设备实例 (DEVINST)
+fn find_disk_devinst(target_device_number: u32, target_device_type: u32) -> Result<u32, String> {
     use windows_sys::Win32::Foundation::{CloseHandle, INVALID_HANDLE_VALUE};
     use windows_sys::Win32::Storage::FileSystem::{
         CreateFileW, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
@@ -599,12 +1709,12 @@ fn find_and_eject_device(
             0,
             DIGCF_PRESENT | DIGCF_DEVICEINTERFACE,
         );
-        if dev_info_set == -1isize as _ {
-            return Err("无法枚举磁盘设备列表".to_string());
+        if dev_info_set == -1isize {
+            return Err(tr("无法枚举磁盘设备列表").to_string());
         }
 
         let mut member_index = 0u32;
-        let mut found = false;
+        let mut found = None;
 
         loop {
             let mut iface_data: SP_DEVICE_INTERFACE_DATA = std::mem::zeroed();
@@ -687,41 +1797,8 @@ fn find_and_eject_device(
                         if ok != 0 && sdn.DeviceNumber == target_device_number
                             && sdn.DeviceType == target_device_type
                         {
-                            // 尝试弹出父设备 (关键修复：解决 VetoType 6)
-                            let mut parent_inst = 0u32;
-                            if CM_Get_Parent(&mut parent_inst, devinfo.DevInst, 0)
-                                == CR_SUCCESS
-                            {
-                                let mut veto_type = 0i32;
-                                let mut veto_name = [0u16; 260];
-                                if CM_Request_Device_EjectW(
-                                    parent_inst,
-                                    &mut veto_type,
-                                    veto_name.as_mut_ptr(),
-                                    260,
-                                    0,
-                                ) == CR_SUCCESS
-                                {
-                                    found = true;
-                                }
-                            }
-                            // 如果父设备弹出失败，尝试弹出当前设备
-                            if !found {
-                                let mut veto_type = 0i32;
-                                if CM_Request_Device_EjectW(
-                                    devinfo.DevInst,
-                                    &mut veto_type,
-                                    std::ptr::null_mut(),
-                                    0,
-                                    0,
-                                ) == CR_SUCCESS
-                                {
-                                    found = true;
-                                }
-                            }
-                            if found {
-                                break;
-                            }
+                            found = Some(devinfo.DevInst);
+                            break;
                         }
                     }
                 }
@@ -730,28 +1807,110 @@ fn find_and_eject_device(
         }
 
         SetupDiDestroyDeviceInfoList(dev_info_set);
+        found.ok_or_else(|| tr("未找到对应的磁盘设备").to_string())
+    }
+}
+
+fn find_and_eject_device(
+    target_device_number: u32,
+    target_device_type: u32,
+) -> Result<(), String> {
+    let dev_inst = find_disk_devinst(target_device_number, target_device_type)?;
+
+    unsafe {
+        let mut found = false;
+
+        // 尝试弹出父设备 (关键修复：解决 VetoType 6)
+        let mut parent_inst = 0u32;
+        if CM_Get_Parent(&mut parent_inst, dev_inst, 0) == CR_SUCCESS {
+            let mut veto_type = 0i32;
+            let mut veto_name = [0u16; 260];
+            if CM_Request_Device_EjectW(
+                parent_inst,
+                &mut veto_type,
+                veto_name.as_mut_ptr(),
+                260,
+                0,
+            ) == CR_SUCCESS
+            {
+                found = true;
+            }
+        }
+        // 如果父设备弹出失败，尝试弹出当前设备
+        if !found {
+            let mut veto_type = 0i32;
+            if CM_Request_Device_EjectW(
+                dev_inst,
+                &mut veto_type,
+                std::ptr::null_mut(),
+                0,
+                0,
+            ) == CR_SUCCESS
+            {
+                found = true;
+            }
+        }
 
         if found {
             SHChangeNotify(0x00002000, 0x0005, std::ptr::null(), std::ptr::null());
             Ok(())
         } else {
-            Err("硬件拒绝弹出 (VetoType 6)。请尝试关闭所有窗口后重试。".to_string())
+            Err(tr("硬件拒绝弹出 (VetoType 6)。请尝试关闭所有窗口后重试。").to_string())
+        }
+    }
+}
+
+/// 将占用进程与句柄扫描结果对照，填入具体被占用的文件，让用户知道终止的代价
+fn annotate_locked_files(drive: &str, list: &mut [Occupant]) {
+    let pids: Vec<u32> = list.iter().map(|o| o.pid).collect();
+    let mut files = handles::locked_files(drive, &pids);
+    for occ in list.iter_mut() {
+        if let Some(paths) = files.remove(&occ.pid) {
+            // 多分区设备会对每个卷各调用一次，路径累加
+            occ.locked_paths.extend(paths);
+            let exe = handles::process_image_name(occ.pid).unwrap_or_else(|| occ.name.clone());
+            occ.desc = trf("{} (打开了 {} 个文件)", &[&exe, &occ.locked_paths.len()]);
+        }
+    }
+}
+
+/// 最后的手段：扫描全部进程的文件句柄，补上 RM 与 exe/cwd 检查都漏掉的占用者
+fn merge_handle_scan(drive: &str, list: &mut Vec<Occupant>) {
+    for (pid, paths) in handles::scan_drive(drive) {
+        if list.iter().any(|o| o.pid == pid) {
+            continue;
         }
+        let name = handles::process_image_name(pid).unwrap_or_else(|| format!("PID {}", pid));
+        list.push(Occupant {
+            pid,
+            desc: trf("{} (打开了 {} 个文件)", &[&name, &paths.len()]),
+            name,
+            locked_paths: paths,
+        });
     }
 }
 
 /// 后台 USB 工作线程
 fn usb_worker(cmd_rx: mpsc::Receiver<UsbCmd>, msg_tx: mpsc::Sender<UsbMsg>, ctx: egui::Context) {
+    // 连续推送合并为一次重绘；与上一次相同的进行中状态不再推送
+    // (Done / SpeedTested 每次都是一条新结果，即使文字相同也要送达)
+    let repaint = repaint::Throttle::new(ctx);
+    let last_state: std::cell::RefCell<Option<UsbState>> = std::cell::RefCell::new(None);
     let send = |s: UsbState| {
+        let result = matches!(s, UsbState::Done(_) | UsbState::SpeedTested(_));
+        if !result && last_state.borrow().as_ref() == Some(&s) {
+            return;
+        }
+        *last_state.borrow_mut() = Some(s.clone());
         let _ = msg_tx.send(UsbMsg::State(s));
-        ctx.request_repaint();
+        repaint.request();
     };
 
     // 辅助函数：手动扫描进程占用 (fallback)
     // 当 RM 失败时，尝试通过 sysinfo 扫描进程的 exe/cwd 是否在目标驱动器上
     let scan_processes_fallback = |drive: &str| -> Vec<Occupant> {
-        let drive_upper = drive.trim_end_matches([':', '\\', '/']).to_uppercase();
-        let drive_prefix = format!("{}:", drive_upper); // "I:"
+        // 卷的全部挂载路径 / GUID 路径 / subst 盘符；按 UTF-16 比较，含孤立代理项的路径也不会被跳过
+        let volume = mount_manager::VolumeMatcher::new(drive);
 
         let mut list = Vec::new();
         let mut sys = System::new();
@@ -770,22 +1929,18 @@ fn usb_worker(cmd_rx: mpsc::Receiver<UsbCmd>, msg_tx: mpsc::Sender<UsbMsg>, ctx:
 
             // Check EXE path
             if let Some(exe) = proc.exe() {
-                if let Some(exe_str) = exe.to_str() {
-                    if exe_str.to_uppercase().starts_with(&drive_prefix) {
-                        is_occupying = true;
-                        reason = "正在运行".to_string();
-                    }
+                if volume.contains(exe.as_os_str()) {
+                    is_occupying = true;
+                    reason = tr("正在运行").to_string();
                 }
             }
 
             // Check CWD
             if !is_occupying {
                 if let Some(cwd) = proc.cwd() {
-                    if let Some(cwd_str) = cwd.to_str() {
-                        if cwd_str.to_uppercase().starts_with(&drive_prefix) {
-                            is_occupying = true;
-                            reason = "工作目录".to_string();
-                        }
+                    if volume.contains(cwd.as_os_str()) {
+                        is_occupying = true;
+                        reason = tr("工作目录").to_string();
                     }
                 }
             }
@@ -807,34 +1962,107 @@ fn usb_worker(cmd_rx: mpsc::Receiver<UsbCmd>, msg_tx: mpsc::Sender<UsbMsg>, ctx:
                     pid: pid.as_u32(),
                     name,
                     desc,
+                    locked_paths: vec![],
                 });
             }
         }
         list
     };
 
-    while let Ok(cmd) = cmd_rx.recv() {
+    let set_busy = |drive: &str, busy: bool| {
+        let _ = msg_tx.send(UsbMsg::Busy(drive.to_string(), busy));
+        repaint.request();
+    };
+    let report_kills = |progress: &kill_batch::KillProgress| {
+        let _ = msg_tx.send(UsbMsg::KillProgress(progress.clone()));
+        repaint.request();
+    };
+
+    // 弹出成功率统计：每次尝试都记录并持久化，同时推送给 UI
+    let mut stats = EjectStats::load();
+    let _ = msg_tx.send(UsbMsg::Stats(stats.clone()));
+    let record = |stats: &mut EjectStats, method: EjectMethod, ok: bool| {
+        stats.record(method, ok);
+        stats.save();
+        let _ = msg_tx.send(UsbMsg::Stats(stats.clone()));
+    };
+
+    let mut policy = EjectPolicy::default();
+    let mut drive_prefs: HashMap<String, device_prefs::DevicePrefs> = HashMap::new();
+    let mut pending: VecDeque<UsbCmd> = VecDeque::new();
+    loop {
+        if pending.is_empty() {
+            match cmd_rx.recv() {
+                Ok(cmd) => pending.push_back(cmd),
+                Err(_) => break,
+            }
+        }
+        // 合并通道里积压的命令
+        while let Ok(cmd) = cmd_rx.try_recv() {
+            enqueue_usb_cmd(&mut pending, cmd);
+        }
+        let Some(cmd) = pending.pop_front() else {
+            continue;
+        };
+        // 托盘、快捷键、看门狗与操作宏直接发到这里，访客模式在此兜底
+        if cmd.is_destructive() && guest_mode::is_active() {
+            send(UsbState::Done(tr("🔒 访客模式下已禁用此操作").to_string()));
+            continue;
+        }
+        let current = cmd.clone();
+        let busy_drive = current.drive();
+        if let Some(d) = &busy_drive {
+            set_busy(d, true);
+        }
+
         match cmd {
             UsbCmd::Scan(drive) => {
                 let d = norm_drive(&drive);
-                send(UsbState::Ejecting(format!("{}:", d)));
+                send(UsbState::Ejecting(mount_manager::display_name(&d)));
+
+                // 多分区设备的其他卷一样会否决弹出
+                let siblings = usb_info::volumes_on_same_disk(&d);
+
+                // 停在该盘上的资源管理器窗口是最常见的静默否决来源，先关掉
+                let closed: usize = siblings.iter().map(|v| explorer_windows::close_windows_on_drive(v)).sum();
+                if closed > 0 {
+                    std::thread::sleep(Duration::from_millis(300));
+                }
+
+                // 弹出后卷就不在了，序列号要先读
+                let target = eject_history::Target::capture(&d);
 
                 // 快速尝试：简单弹出 (CM_Request_Device_EjectW)
                 // 不做 Dismount/Lock，追求秒开
                 match device::eject(&d) {
-                    Ok(_) => send(UsbState::Done(format!("✅ 驱动器 {}: 已安全弹出", d))),
+                    Ok(_) => {
+                        record(&mut stats, EjectMethod::Quick, true);
+                        target.record(EjectMethod::Quick, &[], true, "已安全弹出");
+                        send(UsbState::Done(trf("✅ 驱动器 {}: 已安全弹出", &[&d])));
+                    }
                     Err(e) => {
+                        record(&mut stats, EjectMethod::Quick, false);
                         // 失败才扫描占用
-                        send(UsbState::Scanning(format!("{}:", d)));
-
-                        // 1. 尝试 RM 扫描
-                        let mut list = rm::list_occupants(&d).unwrap_or_default();
-
-                        // 2. 如果 RM 没找到，尝试手动 fallback 扫描
-                        let fallback_list = scan_processes_fallback(&d);
-                        for item in fallback_list {
-                            if !list.iter().any(|x| x.pid == item.pid) {
-                                list.push(item);
+                        send(UsbState::Scanning(mount_manager::display_name(&d)));
+
+                        let mut list: Vec<Occupant> = Vec::new();
+                        for v in &siblings {
+                            // 1. 尝试 RM 扫描
+                            // 2. 如果 RM 没找到，尝试手动 fallback 扫描
+                            let found = rm::list_occupants(v).unwrap_or_default();
+                            for item in found.into_iter().chain(scan_processes_fallback(v)) {
+                                if !list.iter().any(|x| x.pid == item.pid) {
+                                    list.push(item);
+                                }
+                            }
+                        }
+                        for v in &siblings {
+                            annotate_locked_files(v, &mut list);
+                        }
+                        // 3. 仍然一无所获，扫描系统句柄表
+                        if list.is_empty() {
+                            for v in &siblings {
+                                merge_handle_scan(v, &mut list);
                             }
                         }
 
@@ -843,58 +2071,152 @@ fn usb_worker(cmd_rx: mpsc::Receiver<UsbCmd>, msg_tx: mpsc::Sender<UsbMsg>, ctx:
                         let friendly_err = if list.is_empty() {
                             if err_msg.contains("VetoType: 6") || err_msg.contains("CONFIGRET(23)")
                             {
-                                "无法弹出：系统核心组件或驱动锁定。请尝试关闭所有窗口。".to_string()
+                                tr("无法弹出：系统核心组件或驱动锁定。请尝试关闭所有窗口。").to_string()
                             } else {
-                                format!("弹出失败：{}", err_msg)
+                                trf("弹出失败：{}", &[&err_msg])
                             }
                         } else {
-                            format!("弹出失败：{} (发现占用)", err_msg)
+                            trf("弹出失败：{} (发现占用)", &[&err_msg])
                         };
-
-                        if list.is_empty() {
-                            // 列表为空，可能是窗口未关闭或资源管理器锁定
-                            send(UsbState::Done(format!("❌ {}", friendly_err)));
-                            send(UsbState::Occupied {
-                                drive: format!("{}:", d),
-                                list: vec![],
-                            });
-                        } else {
-                            send(UsbState::Occupied {
-                                drive: format!("{}:", d),
-                                list,
-                            });
+                        target.record(EjectMethod::Quick, &[], false, &friendly_err);
+
+                        match policy.mode {
+                            // 强制：交给界面，与用户点“强力清场”相同 (含清场前确认)
+                            settings::EjectMode::Force => {
+                                let _ = msg_tx.send(UsbMsg::ForceClear(d.clone(), list.clone()));
+                                send(UsbState::Occupied {
+                                    drive: mount_manager::display_name(&d),
+                                    list,
+                                });
+                            }
+                            // 智能：找不到占用进程 (多为缓存或驱动未释放)，卸载卷后再弹出
+                            settings::EjectMode::Smart if list.is_empty() => {
+                                send(UsbState::Ejecting(mount_manager::display_name(&d)));
+                                let result = smart_eject(&d);
+                                record(&mut stats, EjectMethod::SmartEject, result.is_ok());
+                                target.record(
+                                    EjectMethod::SmartEject,
+                                    &[],
+                                    result.is_ok(),
+                                    result.as_ref().map(|_| "卸载卷后已安全弹出").unwrap_or_else(|e| e.as_str()),
+                                );
+                                match result {
+                                    Ok(_) => send(UsbState::Done(trf("✅ 驱动器 {}: 已安全弹出 (卸载卷后)", &[&d]))),
+                                    Err(e) => {
+                                        send(UsbState::Done(format!("❌ {}", e)));
+                                        send(UsbState::Occupied {
+                                            drive: mount_manager::display_name(&d),
+                                            list: vec![],
+                                        });
+                                    }
+                                }
+                            }
+                            _ if list.is_empty() => {
+                                // 列表为空，可能是窗口未关闭或资源管理器锁定
+                                send(UsbState::Done(format!("❌ {}", friendly_err)));
+                                send(UsbState::Occupied {
+                                    drive: mount_manager::display_name(&d),
+                                    list: vec![],
+                                });
+                            }
+                            _ => {
+                                send(UsbState::Occupied {
+                                    drive: mount_manager::display_name(&d),
+                                    list,
+                                });
+                            }
                         }
                     }
                 }
             }
 
+            UsbCmd::RestartExplorer(drive, pids) => {
+                let d = norm_drive(&drive);
+                send(UsbState::Ejecting(trf("{}: 正在重启资源管理器...", &[&mount_manager::display_name(&d)])));
+                let target = eject_history::Target::capture(&d);
+                let killed: Vec<eject_history::KilledProcess> = pids
+                    .iter()
+                    .map(|&pid| eject_history::KilledProcess { pid, name: process_name(pid) })
+                    .collect();
+
+                // explorer 不在时弹出，结束后无论成败都把外壳拉起来 (用结束前保存的用户令牌)
+                let shell = explorer_restart::SavedShell::capture();
+                let result = explorer_restart::stop(&pids).and_then(|_| {
+                    std::thread::sleep(Duration::from_millis(300));
+                    send(UsbState::Ejecting(mount_manager::display_name(&d)));
+                    smart_eject(&d)
+                });
+                let relaunched = explorer_restart::relaunch(shell);
+                record(&mut stats, EjectMethod::SmartEject, result.is_ok());
+                target.record(
+                    EjectMethod::SmartEject,
+                    &killed,
+                    result.is_ok(),
+                    result.as_ref().map(|_| "重启资源管理器后已安全弹出").unwrap_or_else(|e| e.as_str()),
+                );
+                let relaunch_note = relaunched.err().map(|e| format!(" · ⚠ {}", e)).unwrap_or_default();
+                match result {
+                    Ok(_) => send(UsbState::Done(trf(
+                        "✅ 驱动器 {}: 已安全弹出 (资源管理器已重启){}",
+                        &[&d, &relaunch_note],
+                    ))),
+                    Err(e) => {
+                        send(UsbState::Done(format!("❌ {}{}", e, relaunch_note)));
+                        let mut list = rm::list_occupants(&d).unwrap_or_default();
+                        annotate_locked_files(&d, &mut list);
+                        send(UsbState::Occupied {
+                            drive: mount_manager::display_name(&d),
+                            list,
+                        });
+                    }
+                }
+            }
+
             UsbCmd::KillOne(pid, drive) => {
-                send(UsbState::Scanning(format!(
-                    "{}: 正在终止占用进程...",
-                    drive
-                )));
-                let _ = rust_core_lib::process::kill(pid);
-                std::thread::sleep(Duration::from_millis(200));
+                send(UsbState::Scanning(trf("{}: 正在终止占用进程...", &[&drive])));
+                let d = norm_drive(&drive);
+                let target = eject_history::Target::capture(&d);
+                let killed = [eject_history::KilledProcess { pid, name: process_name(pid) }];
+                if gpu_guard::is_display_critical(&killed[0].name) {
+                    audit::record("终止进程", &format!("{} (PID {}) -> 显示关键进程，已拒绝", killed[0].name, pid));
+                } else {
+                    let _ = rust_core_lib::process::kill(pid);
+                    std::thread::sleep(Duration::from_millis(200));
+                }
 
                 // 杀完一个后，重新扫描占用
-                let d = norm_drive(&drive);
-                let list = rm::list_occupants(&d).unwrap_or_default();
+                let mut list = rm::list_occupants(&d).unwrap_or_default();
+                annotate_locked_files(&d, &mut list);
                 // 自动尝试弹出
                 if list.is_empty() {
-                    send(UsbState::Ejecting(format!("{}:", d)));
-                    match smart_eject(&d) {
-                        Ok(_) => send(UsbState::Done(format!("✅ 驱动器 {}: 已安全弹出", d))),
+                    send(UsbState::Ejecting(mount_manager::display_name(&d)));
+                    let result = smart_eject(&d);
+                    record(&mut stats, EjectMethod::SmartEject, result.is_ok());
+                    target.record(
+                        EjectMethod::SmartEject,
+                        &killed,
+                        result.is_ok(),
+                        result.as_ref().map(|_| "已安全弹出").unwrap_or_else(|e| e.as_str()),
+                    );
+                    match result {
+                        Ok(_) => send(UsbState::Done(trf("✅ 驱动器 {}: 已安全弹出", &[&d]))),
                         Err(_) => {
                             // 如果还是失败，回到 Occupied 状态让用户强制弹出
                             send(UsbState::Occupied {
-                                drive: format!("{}:", d),
+                                drive: mount_manager::display_name(&d),
                                 list: vec![],
                             });
                         }
                     }
                 } else {
+                    target.record(
+                        EjectMethod::SmartEject,
+                        &killed,
+                        false,
+                        &format!("仍有 {} 个进程占用，未尝试弹出", list.len()),
+                    );
                     send(UsbState::Occupied {
-                        drive: format!("{}:", d),
+                        drive: mount_manager::display_name(&d),
                         list,
                     });
                 }
@@ -902,31 +2224,114 @@ fn usb_worker(cmd_rx: mpsc::Receiver<UsbCmd>, msg_tx: mpsc::Sender<UsbMsg>, ctx:
 
             UsbCmd::ForceEject(drive, pids) => {
                 let d = norm_drive(&drive);
-                send(UsbState::Scanning(format!("{}: 正在强制清场...", d)));
+                let target = eject_history::Target::capture(&d);
+                let mut killed: Vec<eject_history::KilledProcess> = Vec::new();
+                let policy = drive_prefs
+                    .get(&d)
+                    .map(|p| p.eject_policy(&policy))
+                    .unwrap_or_else(|| policy.clone());
+                // 清场期间占用面板保持显示，进度通过 KillProgress 推送
+                let mut progress = kill_batch::KillProgress::new(&mount_manager::display_name(&d), tr("准备清场"));
+
+                // 0. 先礼后兵：打开了该盘文件的文档编辑器先请求关闭，给用户保存的机会
+                if policy.graceful_close && !d.is_empty() {
+                    let files = handles::locked_files(&d, &pids);
+                    let closing: Vec<u32> = pids
+                        .iter()
+                        .copied()
+                        .filter(|pid| files.contains_key(pid))
+                        .filter(|pid| {
+                            handles::process_image_name(*pid)
+                                .map(|n| graceful::is_document_editor(&n))
+                                .unwrap_or(false)
+                        })
+                        .filter(|pid| graceful::request_close(*pid))
+                        .collect();
+                    if !closing.is_empty() {
+                        progress.stage = trf("已请求 {} 个文档程序保存并关闭，请在其窗口中确认...", &[&closing.len()]);
+                        report_kills(&progress);
+                        let deadline = Instant::now() + policy.graceful_wait;
+                        for pid in closing {
+                            graceful::wait_exit(pid, deadline.saturating_duration_since(Instant::now()));
+                        }
+                    }
+                }
 
-                // 1. RM 强制释放 (Force Shutdown)
+                progress.stage = tr("正在强制清场...").to_string();
+                report_kills(&progress);
+                if !d.is_empty() {
+                    explorer_windows::close_windows_on_drive(&d);
+                }
+
+                // 1. RM 强制释放 (Force Shutdown)；先记下会被关闭的进程
+                for occ in rm::list_occupants(&d).unwrap_or_default() {
+                    killed.push(eject_history::KilledProcess { pid: occ.pid, name: occ.name });
+                }
                 let _ = rm::shutdown_occupants(&d, true);
 
-                // 2. Kill 指定 PID (以及重新扫描到的残留)
+                // 2. 逐个终止指定 PID，再扫描一次漏网之鱼；等待进程句柄确认退出
+                let mut targets: Vec<(u32, String)> = Vec::new();
                 for pid in &pids {
-                    let _ = rust_core_lib::process::kill(*pid);
+                    if !targets.iter().any(|(p, _)| p == pid) {
+                        let name = killed.iter().find(|k| k.pid == *pid).map(|k| k.name.clone());
+                        targets.push((*pid, name.unwrap_or_else(|| process_name(*pid))));
+                    }
                 }
-                
-                // 再次扫描是否有漏网之鱼
-                let fallback = scan_processes_fallback(&d);
-                for p in fallback {
-                    let _ = rust_core_lib::process::kill(p.pid);
+                for round in 0..2 {
+                    if round == 1 {
+                        let mut fallback = scan_processes_fallback(&d);
+                        merge_handle_scan(&d, &mut fallback);
+                        targets = fallback
+                            .into_iter()
+                            .filter(|p| !progress.results.iter().any(|r| r.pid == p.pid))
+                            .map(|p| (p.pid, p.name))
+                            .collect();
+                        // 第二轮是用户没见过的新占用者：系统 / 服务进程不自动终止，留给占用列表确认
+                        let (protected, rest): (Vec<_>, Vec<_>) =
+                            targets.into_iter().partition(|(pid, name)| is_protected_process(*pid, name));
+                        targets = rest;
+                        progress.total += protected.len();
+                        for (pid, name) in protected {
+                            progress.results.push(kill_batch::KillResult {
+                                pid,
+                                name,
+                                outcome: kill_batch::KillOutcome::Failed(tr("系统或服务进程，未自动终止").to_string()),
+                            });
+                        }
+                    }
+                    // 桌面合成 / 显卡驱动进程不参与清场
+                    targets.retain(|(_, name)| !gpu_guard::is_display_critical(name));
+                    progress.total += targets.len();
+                    report_kills(&progress);
+                    kill_batch::kill_all(&targets, Duration::from_secs(3), |result| {
+                        if result.outcome == kill_batch::KillOutcome::Killed && !killed.iter().any(|k| k.pid == result.pid) {
+                            killed.push(eject_history::KilledProcess { pid: result.pid, name: result.name.clone() });
+                        }
+                        progress.results.push(result);
+                        report_kills(&progress);
+                    });
                 }
-
-                std::thread::sleep(Duration::from_millis(300));
+                progress.stage = tr("正在弹出...").to_string();
+                report_kills(&progress);
+                let kill_summary = if progress.total > 0 { format!(" ({})", progress.summary()) } else { String::new() };
 
                 // 3. 强力弹出 (Smart Eject: Flush -> Lock -> Dismount -> ParentEject)
                 let mut last_err = String::new();
                 let mut success = false;
 
+                let tracked = !d.is_empty();
+                let mut method = EjectMethod::RmShutdown;
+
                 if smart_eject(&d).is_ok() {
                     success = true;
+                    if tracked {
+                        record(&mut stats, EjectMethod::RmShutdown, true);
+                    }
                 } else {
+                    method = EjectMethod::Fsutil;
+                    if tracked {
+                        record(&mut stats, EjectMethod::RmShutdown, false);
+                    }
                     // 如果失败，尝试 fsutil 辅助
                     let _ = geek_commands::eject_by_fsutil(&d);
                     std::thread::sleep(Duration::from_millis(500));
@@ -935,21 +2340,35 @@ fn usb_worker(cmd_rx: mpsc::Receiver<UsbCmd>, msg_tx: mpsc::Sender<UsbMsg>, ctx:
                         Ok(_) => success = true,
                         Err(e) => last_err = e,
                     }
+                    if tracked {
+                        record(&mut stats, EjectMethod::Fsutil, success);
+                        if !success {
+                            stats.record_unresolved();
+                            stats.save();
+                            let _ = msg_tx.send(UsbMsg::Stats(stats.clone()));
+                        }
+                    }
                 }
 
                 if success {
                     // 尝试刷新资源管理器 (通知系统)
                     unsafe { SHChangeNotify(0x00002000, 0x0005, std::ptr::null(), std::ptr::null()); }
-                    send(UsbState::Done(format!("✅ 驱动器 {}: 已强制弹出", d)));
+                    if tracked {
+                        target.record(method, &killed, true, "已强制弹出");
+                    }
+                    send(UsbState::Done(trf("✅ 驱动器 {}: 已强制弹出{}", &[&d, &kill_summary])));
                 } else {
                     let friendly =
                         if last_err.contains("VetoType: 6") || last_err.contains("CONFIGRET(23)") {
-                            "系统核心组件锁定，强制移除失败。请重启电脑。"
+                            tr("系统核心组件锁定，强制移除失败。请重启电脑。")
                         } else {
                             &last_err
                         };
 
-                    send(UsbState::Done(format!("❌ {}", friendly)));
+                    if tracked {
+                        target.record(method, &killed, false, friendly);
+                    }
+                    send(UsbState::Done(format!("❌ {}{}", friendly, kill_summary)));
                 }
                 
                 // 刷新系统磁盘列表
@@ -959,37 +2378,224 @@ fn usb_worker(cmd_rx: mpsc::Receiver<UsbCmd>, msg_tx: mpsc::Sender<UsbMsg>, ctx:
 
             UsbCmd::FsutilDismount(drive) => {
                 let d = norm_drive(&drive);
-                send(UsbState::Scanning(format!("{}: 正在执行 fsutil dismount...", d)));
+                send(UsbState::Scanning(trf("{} 正在执行 fsutil dismount...", &[&mount_manager::display_name(&d)])));
+                let target = eject_history::Target::capture(&d);
                 
                 match geek_commands::eject_by_fsutil(&d) {
                     Ok(_) => {
-                        send(UsbState::Ejecting(format!("{}: 卷已强制卸载，尝试弹出...", d)));
+                        send(UsbState::Ejecting(trf("{} 卷已强制卸载，尝试弹出...", &[&mount_manager::display_name(&d)])));
                         std::thread::sleep(Duration::from_millis(500));
-                        match smart_eject(&d) {
-                            Ok(_) => send(UsbState::Done(format!("✅ 驱动器 {}: 已安全弹出 (fsutil)", d))),
+                        let result = smart_eject(&d);
+                        record(&mut stats, EjectMethod::Fsutil, result.is_ok());
+                        target.record(
+                            EjectMethod::Fsutil,
+                            &[],
+                            result.is_ok(),
+                            result.as_ref().map(|_| "已安全弹出 (fsutil)").unwrap_or_else(|e| e.as_str()),
+                        );
+                        match result {
+                            Ok(_) => send(UsbState::Done(trf("✅ 驱动器 {}: 已安全弹出 (fsutil)", &[&d]))),
                             Err(e) => {
                                 // 失败才扫描占用
-                                send(UsbState::Done(format!("❌ fsutil 成功但弹出失败：{}", e)));
-                                let list = rm::list_occupants(&d).unwrap_or_default();
-                                send(UsbState::Occupied { drive: format!("{}:", d), list });
+                                send(UsbState::Done(trf("❌ fsutil 成功但弹出失败：{}", &[&e])));
+                                let mut list = rm::list_occupants(&d).unwrap_or_default();
+                                annotate_locked_files(&d, &mut list);
+                                send(UsbState::Occupied { drive: mount_manager::display_name(&d), list });
                             }
                         }
                     }
-                    Err(e) => send(UsbState::Done(format!("❌ fsutil 执行失败：{}", e))),
+                    Err(e) => {
+                        record(&mut stats, EjectMethod::Fsutil, false);
+                        target.record(EjectMethod::Fsutil, &[], false, &format!("fsutil 执行失败：{}", e));
+                        send(UsbState::Done(trf("❌ fsutil 执行失败：{}", &[&e])));
+                    }
                 }
                 
                 // 刷新系统磁盘列表
                 let mut disks = Disks::new_with_refreshed_list();
                 disks.refresh_list();
             }
+
+            UsbCmd::SetPolicy(p) => policy = p,
+
+            UsbCmd::DevicePrefs(drive, prefs) => match prefs {
+                Some(p) => {
+                    drive_prefs.insert(norm_drive(&drive), p);
+                }
+                None => {
+                    drive_prefs.remove(&norm_drive(&drive));
+                }
+            },
+
+            UsbCmd::SetReadOnly(drive, on) => {
+                send(UsbState::Ejecting(trf("{}: 正在重新挂载...", &[&norm_drive(&drive)])));
+                match read_only::set_read_only(&drive, on) {
+                    Ok(msg) => send(UsbState::Done(format!("✅ {}", msg))),
+                    Err(e) => send(UsbState::Done(format!("❌ {}", e))),
+                }
+            }
+
+            UsbCmd::SetWriteProtect(drive, on) => {
+                send(UsbState::Ejecting(trf("{}: 正在重新挂载...", &[&norm_drive(&drive)])));
+                match read_only::set_write_protect(&drive, on) {
+                    Ok(msg) => send(UsbState::Done(format!("✅ {}", msg))),
+                    Err(e) => send(UsbState::Done(format!("❌ {}", e))),
+                }
+            }
+
+            UsbCmd::Format(drive, job) => {
+                let d = mount_manager::display_name(&drive);
+                send(UsbState::Ejecting(trf("{} 正在格式化...", &[&d])));
+                // 停在该盘上的资源管理器窗口会占住卷，先关掉
+                explorer_windows::close_windows_on_drive(&drive);
+                let result = format_volume::format(&drive, &job, |p| {
+                    send(UsbState::Ejecting(trf("{} 正在格式化... {}%", &[&d, &p])));
+                });
+                match result {
+                    Ok(msg) => send(UsbState::Done(format!("✅ {}", msg))),
+                    Err(e) => send(UsbState::Done(format!("❌ {}", e))),
+                }
+            }
+
+            UsbCmd::WriteImage(drive, job) => {
+                let d = mount_manager::display_name(&drive);
+                send(UsbState::Ejecting(trf("{} 正在锁定卷...", &[&d])));
+                explorer_windows::close_windows_on_drive(&drive);
+                let result = image_writer::write(&drive, &job, |p| {
+                    send(UsbState::Ejecting(format!("{} {}", d, p.text())));
+                });
+                match result {
+                    Ok(msg) => send(UsbState::Done(format!("✅ {}", msg))),
+                    Err(e) => send(UsbState::Done(format!("❌ {}", e))),
+                }
+            }
+
+            UsbCmd::Wipe(drive, job, cancel) => {
+                let d = mount_manager::display_name(&drive);
+                send(UsbState::Ejecting(trf("{} 正在锁定卷...", &[&d])));
+                explorer_windows::close_windows_on_drive(&drive);
+                let result = secure_wipe::wipe(&drive, &job, &cancel, |p| {
+                    send(UsbState::Ejecting(format!("{} {}", d, p.text())));
+                });
+                match result {
+                    Ok(msg) => send(UsbState::Done(format!("✅ {}", msg))),
+                    Err(e) => send(UsbState::Done(format!("❌ {}", e))),
+                }
+            }
+
+            UsbCmd::SetRemovalPolicy(drive, policy) => match removal_policy::set(&drive, policy) {
+                Ok(msg) => send(UsbState::Done(format!("✅ {}", msg))),
+                Err(e) => send(UsbState::Done(format!("❌ {}", e))),
+            },
+
+            UsbCmd::SpeedTest(drive) => {
+                match speed_test::run(&drive, |p| send(UsbState::SpeedTesting(p))) {
+                    Ok(result) => send(UsbState::SpeedTested(result)),
+                    Err(e) => send(UsbState::Done(trf("❌ 测速失败：{}", &[&e]))),
+                }
+            }
+
+            UsbCmd::Relabel(drive, label) => match format_volume::relabel(&drive, &label) {
+                Ok(msg) => send(UsbState::Done(format!("✅ {}", msg))),
+                Err(e) => send(UsbState::Done(format!("❌ {}", e))),
+            },
+
+            UsbCmd::SetWriteProtectPolicy(on) => match read_only::set_policy_write_protect(on) {
+                Ok(msg) => send(UsbState::Done(format!("✅ {}", msg))),
+                Err(e) => send(UsbState::Done(format!("❌ {}", e))),
+            },
+
+            UsbCmd::Mount(op) => match mount_manager::apply(&op) {
+                Ok(msg) => send(UsbState::Done(format!("✅ {}", msg))),
+                Err(e) => send(UsbState::Done(format!("❌ {}", e))),
+            },
+
+            UsbCmd::RestartProcess(pid) => {
+                let name = process_name(pid);
+                send(UsbState::Done(match process_restart::restart(pid) {
+                    Ok(_) => trf("✅ {} 已重启", &[&name]),
+                    Err(e) => trf("❌ 重启 {} 失败：{}", &[&name, &e]),
+                }));
+            }
+
+            UsbCmd::RunMacro(m) => {
+                // 弹出步骤：无占用时直接弹出，有占用则记为失败，不自动清场
+                let eject = |drive: &str| {
+                    let d = norm_drive(drive);
+                    let list = rm::list_occupants(&d).unwrap_or_default();
+                    if !list.is_empty() {
+                        let names: Vec<&str> = list.iter().map(|o| o.name.as_str()).collect();
+                        return Err(trf("被 {} 占用", &[&names.join(tr("、"))]));
+                    }
+                    set_busy(&d, true);
+                    let result = smart_eject(&d);
+                    set_busy(&d, false);
+                    record(&mut stats, EjectMethod::SmartEject, result.is_ok());
+                    result.map(|_| tr("已安全弹出").to_string())
+                };
+                let progress = macros::run(&m, eject, |p| {
+                    let _ = msg_tx.send(UsbMsg::MacroProgress(p.clone()));
+                    repaint.request();
+                });
+                tray::balloon(tr("操作宏已执行"), &progress.summary(), progress.failed() > 0);
+                send(UsbState::Done(format!(
+                    "{} {}",
+                    if progress.failed() == 0 { "✅" } else { "⚠" },
+                    progress.summary()
+                )));
+            }
+
+            UsbCmd::KillGroup(pids, tree) => kill_group(&pids, tree),
+        }
+
+        // 执行期间重复点击堆积的同类命令直接丢弃
+        while let Ok(cmd) = cmd_rx.try_recv() {
+            if !cmd.same_as(&current) {
+                enqueue_usb_cmd(&mut pending, cmd);
+            }
+        }
+        if let Some(d) = &busy_drive {
+            set_busy(d, false);
         }
     }
 }
 
+/// 深色主题沿用暖色底；浅色主题用 egui 自带配色
+fn apply_theme(ctx: &egui::Context, theme: settings::Theme) {
+    let visuals = match theme {
+        settings::Theme::Dark => {
+            let mut visuals = egui::Visuals::dark();
+            visuals.panel_fill = egui::Color32::from_rgb(20, 18, 15);
+            visuals
+        }
+        settings::Theme::Light => egui::Visuals::light(),
+    };
+    ctx.set_visuals(visuals);
+}
+
+/// 智能休眠：根据负载自适应调整刷新率
+/// 正常模式: 默认 500ms (2Hz，可在设置中调整) - 保证流畅；用户选择降频时加倍
+/// 极简模式: 2000ms (0.5Hz) - 让出 CPU 资源
+/// 插拔后: 300ms 连刷几次，让新盘尽快出现在列表中
+fn next_interval(burst: u32, is_tight: bool, tuning: &tick_health::Tuning) -> Duration {
+    if burst > 0 {
+        Duration::from_millis(300)
+    } else if is_tight {
+        Duration::from_millis(2000).max(tuning.effective_interval())
+    } else {
+        tuning.effective_interval()
+    }
+}
+
 /// 后台监控线程：解决 UI 卡顿的关键
 fn monitor_worker(
     snapshot: Arc<RwLock<AppSnapshot>>,
     process_db: HashMap<String, ProcessInfo>,
+    device_rx: mpsc::Receiver<device_events::DeviceEvent>,
+    tuning: Arc<RwLock<tick_health::Tuning>>,
+    history: Arc<Mutex<perf_history::History>>,
+    overrides: Arc<RwLock<process_overrides::OverrideStore>>,
+    mut watchdog: watchdog::Watchdog,
     ctx: egui::Context,
 ) {
     let mut sys = System::new_all();
@@ -1000,10 +2606,61 @@ fn monitor_worker(
     let mut groups_buffer: HashMap<String, ProcessGroup> = HashMap::with_capacity(512);
     // 缓存文件描述，避免重复 I/O (Key: exe_path string)
     let mut desc_cache: HashMap<String, String> = HashMap::with_capacity(512);
+    // 数字签名校验较慢，后台进行并按 exe 路径缓存
+    let mut signatures = signature::SignatureCache::new();
+    // 进程架构不会变，按 (pid, 启动时间) 缓存
+    let mut arch_cache: HashMap<(u32, u64), Option<arch::ProcessArch>> = HashMap::with_capacity(512);
+    // 进程所有者同样不会变；本程序账户的 SID 用于区分“其他用户”
+    let mut owner_cache: HashMap<(u32, u64), Option<process_owner::Owner>> = HashMap::with_capacity(512);
+    let current_sid = process_owner::current_sid();
+    // 进程历史写入本地 SQLite；打不开时不记录
+    let mut history_db = process_history::Recorder::open()
+        .map_err(|e| audit::record("进程历史", &format!("无法打开数据库：{}", e)))
+        .ok();
+    // 缓存总线/速率信息，只在新盘插入时查询一次 (Key: 盘符)
+    let mut bus_cache: HashMap<String, Option<usb_info::BusInfo>> = HashMap::new();
+    let mut identity_cache: HashMap<String, Option<usb_info::DeviceIdentity>> = HashMap::new();
+    let mut devinst_cache: HashMap<String, Option<u32>> = HashMap::new();
+    let mut key_cache: HashMap<String, Option<String>> = HashMap::new();
+    let mut number_cache: HashMap<String, Option<u32>> = HashMap::new();
+    // 磁盘只读属性只在插入或本程序修改时变化，修改计数变化时整体重查
+    let mut write_protect_cache: HashMap<String, bool> = HashMap::new();
+    let mut write_protect_generation = read_only::attributes_generation();
+    // SMART 健康 (Key: 物理磁盘编号)
+    let mut smart_cache: HashMap<u32, (Instant, Result<smart::SmartHealth, String>)> = HashMap::new();
+    // 文件夹挂载点是否为该卷的首选入口 (卷同时有盘符时只按盘符显示一次)
+    let mut preferred_cache: HashMap<String, bool> = HashMap::new();
+    // 上一次的卷 I/O 计数，用于计算实时速率 (Key: 盘符)
+    let mut io_prev: HashMap<String, (volume_perf::IoCounters, Instant)> = HashMap::new();
+    // 每个盘最后一次观察到写入的时间 (Key: 盘符)
+    let mut last_write: HashMap<String, Instant> = HashMap::new();
+    // 每个盘首次出现的时间 (Key: 盘符)，拔出后清掉，重新插入即为“最近插入”
+    let mut first_seen: HashMap<String, Instant> = HashMap::new();
+    // 硬错误采样 (PDH 不可用时为 None)；页面文件配置很少变化，每分钟重读一次
+    let mut hard_faults = memory_advisor::HardFaultMonitor::new();
+    let mut pagefile = memory_advisor::read_pagefile_config();
+    let mut pagefile_read_at = Instant::now();
+
+    // 插拔事件后的快速刷新次数：新卷的盘符通常要几百毫秒后才分配好
+    let mut burst = 0u32;
 
     // 资源紧张模式的滞后计数器 (0..=5)
     // >= 3 进入紧张模式, < 3 退出
     let mut tight_counter = 0;
+    // 每个进程分组的 CPU / 内存历史 (Key: 分组名)，分组消失后丢弃
+    let mut group_history: HashMap<String, VecDeque<(f32, u64)>> = HashMap::new();
+    // 上次刷新网络计数的时间，换算每秒速率
+    let mut net_refreshed_at = Instant::now();
+    // 上次刷新进程的时间，把 sysinfo 的“自上次刷新以来的读写字节”换算为速率
+    let mut proc_refreshed_at = Instant::now();
+    // 进程 GPU 占用，只在进程表显示 GPU 列时创建
+    let mut gpu_sampler: Option<gpu_usage::Sampler> = None;
+    // 前台应用计时 (设置中开启时)
+    let mut foreground = screen_time::Tracker::default();
+    // 拥有卡死窗口的进程，按 PROBE_INTERVAL 重新探测
+    let mut hung_pids: HashSet<u32> = HashSet::new();
+    let mut hung_probe = hung_windows::HungProbe::default();
+    let mut hung_probed_at: Option<Instant> = None;
 
     // 快照版本号，用于减少 UI 锁竞争
     #[allow(unused_assignments)]
@@ -1011,6 +2668,7 @@ fn monitor_worker(
 
     loop {
         let start_time = Instant::now();
+        let tuning_now = tuning.read().map(|t| *t).unwrap_or_default();
 
         // 1. 刷新数据 (耗时操作)
         sys.refresh_cpu_usage();
@@ -1021,20 +2679,50 @@ fn monitor_worker(
             .with_cpu()
             .with_memory()
             .with_exe(sysinfo::UpdateKind::Always)
+            .with_cmd(sysinfo::UpdateKind::OnlyIfNotSet)
             .with_disk_usage();
         sys.refresh_processes_specifics(sysinfo::ProcessesToUpdate::All, true, refresh_kind);
+        let proc_secs = proc_refreshed_at.elapsed().as_secs_f64().max(0.001);
+        proc_refreshed_at = Instant::now();
+        if !tuning_now.sample_gpu {
+            gpu_sampler = None;
+        } else if gpu_sampler.is_none() {
+            gpu_sampler = gpu_usage::Sampler::new();
+        }
+        let gpu_by_pid = gpu_sampler.as_mut().map(|s| s.sample()).unwrap_or_default();
+        let refresh_cost = start_time.elapsed();
 
         networks.refresh();
+        let net_secs = net_refreshed_at.elapsed().as_secs_f64().max(0.001);
+        net_refreshed_at = Instant::now();
         disks.refresh_list(); // 刷新磁盘列表以检测插拔
 
         // 2. 处理进程分组
+        let classify_start = Instant::now();
+        if hung_probed_at.map_or(true, |t| t.elapsed() >= hung_windows::PROBE_INTERVAL) {
+            hung_pids = hung_probe.hung_pids();
+            hung_probed_at = Some(Instant::now());
+        }
         groups_buffer.clear();
+        arch_cache.retain(|(pid, start), _| {
+            sys.process(sysinfo::Pid::from_u32(*pid))
+                .map(|p| p.start_time() == *start)
+                .unwrap_or(false)
+        });
+        owner_cache.retain(|(pid, start), _| {
+            sys.process(sysinfo::Pid::from_u32(*pid))
+                .map(|p| p.start_time() == *start)
+                .unwrap_or(false)
+        });
+        let mut unknown: HashMap<String, process_db::UnknownProcess> = HashMap::new();
+        // 用户的手动覆盖，本轮内不变
+        let overrides_now = overrides.read().map(|o| o.clone()).unwrap_or_default();
         for (pid, proc) in sys.processes() {
             let name = proc.name().to_string_lossy().to_string();
             let name_lower = name.to_lowercase();
 
             // 识别逻辑
-            let info = {
+            let mut info = {
                 let mut found = None;
 
                 // 0. 优先匹配硬编码映射 (解决部分国产软件/浏览器 FileDescription 不友好的问题)
@@ -1096,38 +2784,120 @@ fn monitor_worker(
                     ProcessInfo::new(friendly, cat)
                 })
             };
+            // 手动覆盖优先于以上所有规则
+            if let Some(exe) = proc.exe() {
+                overrides_now.apply(&exe.to_string_lossy(), &mut info.chinese_name, &mut info.category);
+            }
+
+            if !process_db.contains_key(&name_lower) && !unknown.contains_key(&name_lower) {
+                let path = proc
+                    .exe()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                unknown.insert(
+                    name_lower.clone(),
+                    process_db::UnknownProcess {
+                        exe: name_lower.clone(),
+                        description: desc_cache.get(&path).cloned().unwrap_or_default(),
+                        path,
+                    },
+                );
+            }
+
+            let process_arch = *arch_cache
+                .entry((pid.as_u32(), proc.start_time()))
+                .or_insert_with(|| arch::query(pid.as_u32()));
+            let owner = owner_cache
+                .entry((pid.as_u32(), proc.start_time()))
+                .or_insert_with(|| process_owner::query(pid.as_u32()))
+                .clone();
 
             let entry = groups_buffer.entry(name.clone()).or_insert(ProcessGroup {
                 name,
                 friendly_name: info.chinese_name,
                 category: info.category,
                 total_memory: 0,
+                usage: process_memory::Usage::default(),
                 total_cpu: 0.0,
+                disk_bps: 0,
+                gpu: 0.0,
                 pids: Vec::new(),
+                members: Vec::new(),
+                exe_path: proc
+                    .exe()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                arch: process_arch,
                 is_system: false,
                 is_not_responding: false,
+                other_users: false,
+                signature: None,
+                history: Vec::new(),
             });
 
+            let usage = process_memory::Usage {
+                working_set: proc.memory(),
+                private_working_set: process_memory::private_working_set(pid.as_u32()).unwrap_or(proc.memory()),
+                commit: proc.virtual_memory(),
+            };
+            let io = proc.disk_usage();
+            let disk_bps = ((io.read_bytes + io.written_bytes) as f64 / proc_secs) as u64;
+            let gpu = gpu_by_pid.get(&pid.as_u32()).copied().unwrap_or(0.0);
             entry.total_memory += proc.memory();
+            entry.usage.add(&usage);
             entry.total_cpu += proc.cpu_usage();
+            entry.disk_bps += disk_bps;
+            entry.gpu += gpu;
             entry.pids.push(pid.as_u32());
+            entry.members.push(PidDetail {
+                pid: pid.as_u32(),
+                memory: proc.memory(),
+                usage,
+                cpu: proc.cpu_usage(),
+                cmd: proc
+                    .cmd()
+                    .iter()
+                    .map(|a| a.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                start_time: proc.start_time(),
+                arch: process_arch,
+                owner: owner.clone(),
+                disk_bps,
+                gpu,
+            });
+            if owner.is_some_and(|o| o.is_other_user(&current_sid)) {
+                entry.other_users = true;
+            }
 
             if pid.as_u32() < 1000 || entry.category == "系统" {
                 entry.is_system = true;
             }
-            if matches!(
-                proc.status(),
-                sysinfo::ProcessStatus::UninterruptibleDiskSleep | sysinfo::ProcessStatus::Dead
-            ) {
+            if hung_pids.contains(&pid.as_u32()) {
                 entry.is_not_responding = true;
             }
         }
 
-        // 3. 排序与分类
+        // 3. 分类 (显示顺序由界面按用户选择的列排序)
         let mut all_groups: Vec<ProcessGroup> = groups_buffer.values().cloned().collect();
-        all_groups.sort_by(|a, b| b.total_memory.cmp(&a.total_memory));
+        for g in &mut all_groups {
+            g.members.sort_by_key(|m| std::cmp::Reverse(m.memory));
+            g.signature = signatures.get(&g.exe_path);
+            let h = group_history.entry(g.name.clone()).or_default();
+            if h.len() == GROUP_HISTORY_LEN {
+                h.pop_front();
+            }
+            h.push_back((g.total_cpu, g.total_memory));
+            g.history = h.iter().copied().collect();
+        }
+        group_history.retain(|name, _| groups_buffer.contains_key(name));
 
-        let mut new_snapshot = AppSnapshot::default();
+        let mut unknown_processes: Vec<_> = unknown.into_values().collect();
+        unknown_processes.sort_by(|a, b| a.exe.cmp(&b.exe));
+        let mut new_snapshot = AppSnapshot {
+            unknown_processes,
+            ..Default::default()
+        };
 
         for group in all_groups {
             if group.total_cpu > 10.0 || group.total_memory > 500 * 1024 * 1024 {
@@ -1138,15 +2908,73 @@ fn monitor_worker(
                 new_snapshot.other_groups.push(group);
             }
         }
+        let classify_cost = classify_start.elapsed();
+
+        // 看门狗：按分组评估规则 (无规则时跳过)
+        if watchdog.has_rules() {
+            let samples: Vec<watchdog::Sample> = new_snapshot
+                .high_resource
+                .iter()
+                .chain(&new_snapshot.other_groups)
+                .chain(&new_snapshot.system_groups)
+                .map(|g| watchdog::Sample {
+                    name: &g.name,
+                    friendly_name: &g.friendly_name,
+                    cpu: g.total_cpu,
+                    pids: &g.pids,
+                    is_system: g.is_system,
+                })
+                .collect();
+            watchdog.evaluate(&samples);
+        }
+        new_snapshot.watchdog_log = watchdog.log.iter().cloned().collect();
+
+        if let Some(recorder) = &mut history_db {
+            recorder.observe(sys.processes().iter().map(|(pid, p)| process_history::Sample {
+                pid: pid.as_u32(),
+                start_time: p.start_time(),
+                name: p.name().to_string_lossy().to_string(),
+                path: p.exe().map(|e| e.to_string_lossy().to_string()).unwrap_or_default(),
+                memory: p.memory(),
+                cpu: p.cpu_usage(),
+            }));
+            if tuning_now.track_foreground {
+                if let Some((pid, elapsed)) = foreground.sample() {
+                    if let Some(p) = sys.process(sysinfo::Pid::from_u32(pid)) {
+                        recorder.add_foreground(&p.name().to_string_lossy(), elapsed);
+                    }
+                }
+            }
+        }
 
         // 4. 全局数据
         new_snapshot.global_cpu = sys.global_cpu_usage();
+        new_snapshot.per_core = sys.cpus().iter().map(|c| c.cpu_usage()).collect();
+        new_snapshot.counters = system_counters::query();
         new_snapshot.used_memory = sys.used_memory();
         new_snapshot.total_memory = sys.total_memory();
 
+        // 内存诊断
+        if pagefile_read_at.elapsed() > Duration::from_secs(60) {
+            pagefile = memory_advisor::read_pagefile_config();
+            pagefile_read_at = Instant::now();
+        }
+        let (commit_used, commit_limit) = memory_advisor::commit_charge().unwrap_or((0, 0));
+        let mut memory_report = memory_advisor::MemoryReport {
+            pagefile: pagefile.clone(),
+            commit_used,
+            commit_limit,
+            hard_faults_per_sec: hard_faults.as_mut().and_then(|h| h.sample()),
+            in_storm: hard_faults.as_ref().map(|h| h.in_storm()).unwrap_or(false),
+            recent_storms: hard_faults.as_ref().map(|h| h.recent_storms()).unwrap_or(0),
+            advice: vec![],
+        };
+        memory_advisor::evaluate(&mut memory_report, new_snapshot.total_memory);
+        new_snapshot.memory_report = memory_report;
+
         // 智能资源模式判定 (滞后处理)
         let is_tight_now =
-            new_snapshot.global_cpu > 90.0 || sys.available_memory() < 500 * 1024 * 1024;
+            new_snapshot.global_cpu > tuning_now.tight_cpu || sys.available_memory() < tuning_now.tight_free_bytes;
         if is_tight_now {
             if tight_counter < 5 {
                 tight_counter += 1;
@@ -1165,8 +2993,26 @@ fn monitor_worker(
         }
         new_snapshot.network_in = net_in;
         new_snapshot.network_out = net_out;
+        if let Some(recorder) = &mut history_db {
+            recorder.add_traffic(net_in, net_out);
+        }
+
+        if let Ok(mut h) = history.lock() {
+            h.push(perf_history::Sample {
+                at: Instant::now(),
+                cpu: new_snapshot.global_cpu,
+                memory: new_snapshot.used_memory as f32 / new_snapshot.total_memory.max(1) as f32 * 100.0,
+                net_in: net_in as f64 / net_secs,
+                net_out: net_out as f64 / net_secs,
+            });
+        }
 
         // 磁盘
+        let generation = read_only::attributes_generation();
+        if generation != write_protect_generation {
+            write_protect_generation = generation;
+            write_protect_cache.clear();
+        }
         for disk in &disks {
             let mp = disk.mount_point().to_string_lossy().to_string();
             let mp_clean = mp.trim_end_matches(['\\', '/']).to_string();
@@ -1179,7 +3025,75 @@ fn monitor_worker(
                 mp_clean.to_uppercase().starts_with('C')
             };
 
-            let is_removable = device::is_removable(&mp_clean) && !is_sys;
+            let is_removable = device::is_removable(&mp_clean)
+                && !is_sys
+                && *preferred_cache
+                    .entry(mp_clean.clone())
+                    .or_insert_with(|| mount_manager::is_preferred_mount(&mp_clean));
+            let bus = if is_removable {
+                bus_cache
+                    .entry(mp_clean.clone())
+                    .or_insert_with(|| usb_info::query(&mp_clean))
+                    .clone()
+            } else {
+                None
+            };
+            let identity = if is_removable {
+                identity_cache
+                    .entry(mp_clean.clone())
+                    .or_insert_with(|| usb_info::identity(&mp_clean))
+                    .clone()
+            } else {
+                None
+            };
+            // 固定磁盘也要编号：SMART 按物理磁盘查询
+            let disk_number = *number_cache
+                .entry(mp_clean.clone())
+                .or_insert_with(|| usb_info::disk_number(&mp_clean));
+            let smart = disk_number.map(|n| {
+                let stale = smart_cache.get(&n).map(|(t, _)| t.elapsed() >= SMART_REFRESH).unwrap_or(true);
+                if stale {
+                    smart_cache.insert(n, (Instant::now(), smart::query_disk(n)));
+                }
+                smart_cache[&n].1.clone()
+            });
+            // 设备节点不变，策略属性每轮重读，修改后立即能看到“待生效”
+            let removal_policy = if is_removable {
+                devinst_cache
+                    .entry(mp_clean.clone())
+                    .or_insert_with(|| usb_info::disk_devinst(&mp_clean))
+                    .and_then(removal_policy::query)
+            } else {
+                None
+            };
+            let device_key = if is_removable {
+                key_cache
+                    .entry(mp_clean.clone())
+                    .or_insert_with(|| usb_info::device_key(&mp_clean))
+                    .clone()
+            } else {
+                None
+            };
+
+            // 实时读写速率 (仅可移动磁盘)
+            let (mut read_bps, mut write_bps) = (0, 0);
+            if is_removable {
+                if let Some(now) = volume_perf::query(&mp_clean) {
+                    let t = Instant::now();
+                    if let Some((prev, prev_t)) = io_prev.get(&mp_clean) {
+                        let secs = t.duration_since(*prev_t).as_secs_f64().max(0.001);
+                        read_bps = (now.bytes_read.saturating_sub(prev.bytes_read) as f64 / secs) as u64;
+                        write_bps =
+                            (now.bytes_written.saturating_sub(prev.bytes_written) as f64 / secs) as u64;
+                    }
+                    io_prev.insert(mp_clean.clone(), (now, t));
+                    if write_bps > 0 {
+                        last_write.insert(mp_clean.clone(), t);
+                    }
+                }
+            }
+            let since_last_write = last_write.get(&mp_clean).map(|t| t.elapsed());
+            let first_seen = *first_seen.entry(mp_clean.clone()).or_insert_with(Instant::now);
 
             new_snapshot.disks.push(DiskData {
                 mount_point: mp,
@@ -1187,8 +3101,51 @@ fn monitor_worker(
                 available_space: disk.available_space(),
                 total_space: disk.total_space(),
                 is_removable,
+                bus,
+                identity,
+                device_key,
+                read_only: is_removable && read_only::is_read_only(&mp_clean),
+                write_protected: is_removable
+                    && *write_protect_cache
+                        .entry(mp_clean.clone())
+                        .or_insert_with(|| read_only::is_write_protected(&mp_clean)),
+                disk_number,
+                read_bps,
+                write_bps,
+                since_last_write,
+                smart,
+                removal_policy,
+                first_seen: Some(first_seen),
             });
         }
+        // 拔出的盘清掉缓存，下次插入 (可能换了口) 重新查询
+        let mounted = |k: &String| {
+            new_snapshot
+                .disks
+                .iter()
+                .any(|d| d.mount_point.trim_end_matches(['\\', '/']) == k)
+        };
+        bus_cache.retain(|k, _| mounted(k));
+        identity_cache.retain(|k, _| mounted(k));
+        devinst_cache.retain(|k, _| mounted(k));
+        key_cache.retain(|k, _| mounted(k));
+        number_cache.retain(|k, _| mounted(k));
+        write_protect_cache.retain(|k, _| mounted(k));
+        smart_cache.retain(|n, _| new_snapshot.disks.iter().any(|d| d.disk_number == Some(*n)));
+        preferred_cache.retain(|k, _| mounted(k));
+        io_prev.retain(|k, _| mounted(k));
+        last_write.retain(|k, _| mounted(k));
+        first_seen.retain(|k, _| mounted(k));
+
+        new_snapshot.taken_at = Some(Instant::now());
+        new_snapshot.taken_at_local = local_timestamp();
+        new_snapshot.tick_cost = start_time.elapsed();
+        new_snapshot.timings = tick_health::TickTimings {
+            refresh: refresh_cost,
+            classify: classify_cost,
+            total: new_snapshot.tick_cost,
+            interval: next_interval(burst, is_tight_now, &tuning_now),
+        };
 
         // 5. 更新共享状态
         // 仅在数据真正准备好后获取写锁
@@ -1200,18 +3157,16 @@ fn monitor_worker(
         // 6. 通知 UI
         ctx.request_repaint();
 
-        // 智能休眠：根据负载自适应调整刷新率
-        // 正常模式: 500ms (2Hz) - 保证流畅
-        // 极简模式: 2000ms (0.5Hz) - 让出 CPU 资源
-        let target_interval = if is_tight_now {
-            Duration::from_millis(2000)
-        } else {
-            Duration::from_millis(500)
-        };
+        let target_interval = next_interval(burst, is_tight_now, &tuning_now);
+        burst = burst.saturating_sub(1);
 
+        // 休眠期间收到插拔事件立即开始下一轮
         let elapsed = start_time.elapsed();
         if elapsed < target_interval {
-            std::thread::sleep(target_interval - elapsed);
+            if let Ok(_event) = device_rx.recv_timeout(target_interval - elapsed) {
+                while device_rx.try_recv().is_ok() {}
+                burst = 3;
+            }
         }
     }
 }
@@ -1220,31 +3175,11 @@ fn monitor_worker(
 //  UI 实现
 // ═══════════════════════════════════════════════════════════════
 
-// 构建已知进程数据库
-fn build_known_processes() -> HashMap<String, ProcessInfo> {
-    let mut m = HashMap::new();
-    m.insert("svchost.exe".into(), ProcessInfo::new("系统服务宿主", "系统"));
-    m.insert("explorer.exe".into(), ProcessInfo::new("资源管理器", "系统"));
-    m.insert("dwm.exe".into(), ProcessInfo::new("桌面窗口管理器", "系统"));
-    m.insert("searchindexer.exe".into(), ProcessInfo::new("Windows 搜索索引", "系统"));
-    m.insert("msedge.exe".into(), ProcessInfo::new("Edge 浏览器", "浏览器"));
-    m.insert("chrome.exe".into(), ProcessInfo::new("Chrome 浏览器", "浏览器"));
-    m.insert("wechat.exe".into(), ProcessInfo::new("微信", "通讯"));
-    m.insert("qq.exe".into(), ProcessInfo::new("QQ", "通讯"));
-    m.insert("dingtalk.exe".into(), ProcessInfo::new("钉钉", "办公"));
-    m.insert("feishu.exe".into(), ProcessInfo::new("飞书", "办公"));
-    m.insert("code.exe".into(), ProcessInfo::new("VS Code", "开发"));
-    m.insert("steam.exe".into(), ProcessInfo::new("Steam", "游戏"));
-    m
-}
-
 impl GeekKillerApp {
-    fn new(cc: &eframe::CreationContext<'_>) -> Self {
+    fn new(cc: &eframe::CreationContext<'_>, settings: settings::AppSettings, launch: settings::Profile) -> Self {
         ui::setup_custom_fonts(&cc.egui_ctx);
 
-        let mut visuals = egui::Visuals::dark();
-        visuals.panel_fill = egui::Color32::from_rgb(20, 18, 15);
-        cc.egui_ctx.set_visuals(visuals);
+        apply_theme(&cc.egui_ctx, settings.theme);
 
         let (usb_tx, app_rx) = mpsc::channel();
         let (app_tx, usb_rx) = mpsc::channel();
@@ -1259,180 +3194,5006 @@ impl GeekKillerApp {
         let snapshot = Arc::new(RwLock::new(AppSnapshot::default()));
         let snapshot_clone = snapshot.clone();
         let ctx_clone2 = cc.egui_ctx.clone();
-        let db = build_known_processes();
+        let db = process_db::load_merged(&settings.language);
 
+        // 设备插拔通知 (注册失败时退化为纯轮询)
+        let (device_tx, device_rx) = mpsc::channel();
+        if let Err(e) = device_events::subscribe(device_tx) {
+            audit::record("设备通知", &e);
+        }
+
+        let tuning = Arc::new(RwLock::new(settings.tuning()));
+        let start_hidden = settings.start_in_tray;
+        let tuning_clone = tuning.clone();
+        let perf_history = Arc::new(Mutex::new(perf_history::History::default()));
+        let history_clone = perf_history.clone();
+        let overrides = Arc::new(RwLock::new(process_overrides::OverrideStore::load()));
+        let overrides_clone = overrides.clone();
+        let metered_config = Arc::new(RwLock::new(settings.metered.clone()));
+        let metered_status = Arc::new(Mutex::new(metered::Status::default()));
+        metered::spawn(metered_config.clone(), metered_status.clone());
+        let metered_programs = settings.metered.programs.join(", ");
+        let widget_config = Arc::new(RwLock::new(settings.widget.clone()));
+        let widget_status = Arc::new(Mutex::new(widget_api::Status::default()));
+        let widget_snapshot = snapshot.clone();
+        widget_api::spawn(
+            widget_config.clone(),
+            widget_status.clone(),
+            Box::new(move || widget_snapshot.read().map(|s| widget_metrics(&s)).unwrap_or_default()),
+        );
+        let watchdog_rules = Arc::new(RwLock::new(watchdog::RuleSet::load()));
+        // 看门狗的“弹出磁盘”与托盘菜单一样交给 USB 线程
+        let watchdog_eject_tx = usb_tx.clone();
+        // 看门狗挂起的进程与点选窗口挂起的一样进入可恢复列表
+        let (watchdog_suspended_tx, watchdog_suspended_rx) = mpsc::channel();
+        let watchdog_ctx = cc.egui_ctx.clone();
+        let watchdog = watchdog::Watchdog::new(
+            watchdog_rules.clone(),
+            Box::new(move |drive| {
+                let _ = watchdog_eject_tx.send(UsbCmd::Scan(drive.to_string()));
+            }),
+            Box::new(move |pid, name| {
+                let _ = watchdog_suspended_tx.send((pid, name.to_string()));
+                watchdog_ctx.request_repaint();
+            }),
+        );
         std::thread::spawn(move || {
-            monitor_worker(snapshot_clone, db, ctx_clone2);
+            monitor_worker(
+                snapshot_clone,
+                db,
+                device_rx,
+                tuning_clone,
+                history_clone,
+                overrides_clone,
+                watchdog,
+                ctx_clone2,
+            );
+        });
+        let (autorun_tx, autorun_rx) = mpsc::channel();
+        let (backup_tx, backup_rx) = mpsc::channel();
+        let (verify_tx, verify_rx) = mpsc::channel();
+
+        // 托盘：主窗口隐藏后 UI 线程不再运行，弹出直接发给后台线程；终止与操作宏经下方的 gated 通道
+        let quit_requested = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let menu_snapshot = snapshot.clone();
+        let eject_tx = usb_tx.clone();
+        let macros = Arc::new(RwLock::new(settings.macros.clone()));
+        let menu_macros = macros.clone();
+        let tray_macros = macros.clone();
+        // 托盘 / 快捷键发起的破坏性命令回到 UI 线程，与界面操作走同样的访客、显示保护与提权检查
+        let (gated_tx, gated_rx) = mpsc::channel::<UsbCmd>();
+        let kill_gated = gated_tx.clone();
+        let kill_ctx = cc.egui_ctx.clone();
+        let macro_tx = gated_tx.clone();
+        let macro_ctx = cc.egui_ctx.clone();
+        let quit_flag = quit_requested.clone();
+        let quit_ctx = cc.egui_ctx.clone();
+        let hud_open = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let menu_hud = hud_open.clone();
+        let hud_flag = hud_open.clone();
+        let hud_ctx = cc.egui_ctx.clone();
+        if let Err(e) = tray::spawn(
+            APP_TITLE,
+            tray::TrayHandlers {
+                menu: Box::new(move || {
+                    let macros = menu_macros.read().map(|m| macros::tray_entries(&m)).unwrap_or_default();
+                    let hud = menu_hud.load(std::sync::atomic::Ordering::SeqCst);
+                    menu_snapshot.read().map(|s| tray_menu(&s, macros.clone(), hud)).unwrap_or(tray::TrayMenu {
+                        drives: Vec::new(),
+                        top_cpu: None,
+                        macros,
+                        hud,
+                    })
+                }),
+                eject: Box::new(move |drive| {
+                    let _ = eject_tx.send(UsbCmd::Scan(drive.to_string()));
+                }),
+                kill: Box::new(move |pids| {
+                    // 主窗口隐藏时 update() 不运行，先显示出来，提示与确认才看得到
+                    tray::show_main_window();
+                    let _ = kill_gated.send(UsbCmd::KillGroup(pids.to_vec(), false));
+                    kill_ctx.request_repaint();
+                }),
+                run_macro: Box::new(move |index| {
+                    if let Some(m) = tray_macros.read().ok().and_then(|m| m.get(index).cloned()) {
+                        let _ = macro_tx.send(UsbCmd::RunMacro(m));
+                        macro_ctx.request_repaint();
+                    }
+                }),
+                quit: Box::new(move || {
+                    quit_flag.store(true, std::sync::atomic::Ordering::SeqCst);
+                    quit_ctx.request_repaint();
+                }),
+                toggle_hud: Box::new(move || {
+                    hud_flag.fetch_xor(true, std::sync::atomic::Ordering::SeqCst);
+                    hud_ctx.request_repaint();
+                }),
+            },
+        ) {
+            audit::record("托盘", &e);
+        }
+
+        // 全局快捷键：同样不经过 UI 线程，窗口隐藏时也可用
+        let hotkey_snapshot = snapshot.clone();
+        let hotkey_tx = usb_tx.clone();
+        let hotkey_macros = macros.clone();
+        let hotkey_gated = gated_tx.clone();
+        let hotkey_ctx = cc.egui_ctx.clone();
+        let bindings = macros::with_hotkeys(&settings.hotkeys, &settings.macros);
+        let hotkeys = hotkeys::HotkeyService::spawn(bindings, move |action| match action {
+            hotkeys::HotkeyAction::ShowWindow => tray::show_main_window(),
+            hotkeys::HotkeyAction::EjectLatest => {
+                match hotkey_snapshot.read().ok().and_then(|s| latest_removable(&s)) {
+                    Some(d) => {
+                        audit::record("快捷键", &format!("弹出 {} -> 已发送", d.drive));
+                        let _ = hotkey_tx.send(UsbCmd::Scan(d.drive.clone()));
+                        tray::watch_eject(d.drive, d.label);
+                    }
+                    None => tray::balloon(tr("没有可弹出的磁盘"), tr("未检测到可移动磁盘"), false),
+                }
+            }
+            hotkeys::HotkeyAction::RunMacro(index) => {
+                if let Some(m) = hotkey_macros.read().ok().and_then(|m| m.get(index).cloned()) {
+                    audit::record("快捷键", &format!("操作宏 {} -> 已发送", m.name));
+                    // 主窗口隐藏时 update() 不运行，先显示出来，提示与确认才看得到
+                    tray::show_main_window();
+                    let _ = hotkey_gated.send(UsbCmd::RunMacro(m));
+                    hotkey_ctx.request_repaint();
+                }
+            }
         });
+        let hotkey_draft = settings.hotkeys.clone();
+        let macro_draft = settings.macros.clone();
+        let eject_policy = EjectPolicy {
+            mode: settings.eject_mode,
+            ..EjectPolicy::default()
+        };
+        let _ = usb_tx.send(UsbCmd::SetPolicy(eject_policy.clone()));
 
         Self {
             search_query: String::new(),
+            search_regex: false,
+            search: ProcessSearch::default(),
             is_admin: security::is_admin(),
-            show_performance: false,
-            show_diagnostics: false,
-            show_usb_manager: false, // 默认折叠
+            show_performance: launch.panels.performance,
+            show_diagnostics: launch.panels.diagnostics,
+            show_security: launch.panels.security,
+            show_startup: launch.panels.startup,
+            show_network: launch.panels.network,
+            show_usb_manager: launch.panels.usb_manager,
+            view: launch.view,
+            active_profile: launch.name.clone(),
+            new_profile_name: String::new(),
             usb_state: UsbState::Idle,
             usb_tx,
             usb_rx,
+            gated_rx,
             usb_status_msg: String::new(),
             usb_msg_time: None,
+            usb_busy: HashSet::new(),
+            kill_progress: None,
+            eject_policy,
+            eject_stats: EjectStats::default(),
+            eject_history: None,
             snapshot,
-            auto_low_power: true,
-            enhanced_mode: false,
             paused: false,
             cached_snapshot: Arc::new(AppSnapshot::default()),
             last_tight_state: false,
+            export_msg: None,
+            memory_trim_rx: None,
+            purge_standby: false,
+            eject_confirm: None,
+            pending_external: None,
+            always_allow_external: false,
+            pending_admin: None,
+            pending_gpu: None,
+            console: None,
+            uninstall_rx: None,
+            repair: None,
+            net_reset: None,
+            net_reset_winsock: false,
+            kill_tree: true,
+            expanded_groups: HashSet::new(),
+            process_sort: ProcessSort::default(),
+            selected_groups: HashSet::new(),
+            batch_kill_confirm: None,
+            mitigation_cache: HashMap::new(),
+            security_report: None,
+            security_rx: None,
+            hibernation: None,
+            exe_hashes: reputation::HashCache::new(),
+            reputation: reputation::Lookups::default(),
+            vt_key_draft: None,
+            startup_items: None,
+            startup_dirty: false,
+            startup_rx: None,
+            startup_msg: None,
+            startup_highlight: None,
+            startup_scroll_pending: false,
+            autostart_services: None,
+            autostart_services_rx: None,
+            installed_apps: None,
+            company_cache: HashMap::new(),
+            winget_packages: None,
+            winget_rx: None,
+            winget_versions: HashMap::new(),
+            winget_versions_rx: None,
+            net_conns: Vec::new(),
+            net_refreshed_at: None,
+            net_port_filter: String::new(),
+            net_msg: None,
+            mount_editor: None,
+            mount_letter: 'Z',
+            mount_folder: String::new(),
+            format_job: format_volume::FormatJob::default(),
+            format_confirm: None,
+            image_job: image_writer::ImageJob::default(),
+            image_confirm: None,
+            wipe_job: secure_wipe::WipeJob::default(),
+            wipe_confirm: String::new(),
+            wipe_cancel: None,
+            relabel: String::new(),
+            unmounted_volumes: None,
+            known_removable: None,
+            toasts: Vec::new(),
+            scan_on_insert: true,
+            usb_write_policy: read_only::policy_write_protect(),
+            autorun_tx,
+            autorun_rx,
+            autorun_threats: HashMap::new(),
+            open_confirm: None,
+            backup_tx,
+            backup_rx,
+            backups: HashMap::new(),
+            speed_results: HashMap::new(),
+            backup_ran: HashSet::new(),
+            verify_tx,
+            verify_rx,
+            verifies: HashMap::new(),
+            verify_reports: HashMap::new(),
+            verify_source: String::new(),
+            verify_dest: String::new(),
+            device_prefs: device_prefs::PrefStore::load(),
+            pref_draft: None,
+            last_io: HashMap::new(),
+            auto_ejected: HashSet::new(),
+            capture: None,
+            capture_save: false,
+            settings,
+            quit_requested,
+            hud_open,
+            hud_shown: false,
+            hotkeys,
+            hotkey_draft,
+            picker_rx: None,
+            guest_hours: 2,
+            guest_password: String::new(),
+            guest_unlock: String::new(),
+            picked: None,
+            suspended: Vec::new(),
+            watchdog_suspended_rx,
+            tick_health: tick_health::TickHealth::default(),
+            tuning,
+            show_settings: false,
+            start_hidden,
+            perf_history,
+            overrides,
+            override_draft: None,
+            show_hidden: false,
+            only_hung: false,
+            only_other_users: false,
+            current_sid: process_owner::current_sid(),
+            watchdog_draft: watchdog_rules.read().map(|r| r.clone()).unwrap_or_default(),
+            audio_fix: service_control::QuickFix::new(audio_fix::TITLE, audio_fix::run),
+            spooler_fix: service_control::QuickFix::new(print_spooler::TITLE, print_spooler::run),
+            temp_cleanup: service_control::QuickFix::new(temp_cleanup::TITLE, temp_cleanup::run),
+            pending_clear: None,
+            module_view: None,
+            watchdog_rules,
+            metered_config,
+            metered_status,
+            metered_programs,
+            lan_draft: lan_power::Machine::default(),
+            lan_confirm: None,
+            lan_rx: None,
+            widget_config,
+            widget_status,
+            macro_draft,
+            macros,
+            macro_recording: None,
+            macro_progress: None,
+            history_query: String::new(),
+            history_results: None,
+            compare_path: String::new(),
+            compare: None,
+            screen_time_day: screen_time::today(),
+            screen_time_rows: None,
         }
     }
 
-    fn render_process_table(
-        &self,
-        ui: &mut egui::Ui,
-        ctx: &egui::Context,
-        groups: &[ProcessGroup],
-        is_high: bool,
+    /// 录制操作宏时把实际下发的终止 / 弹出命令记为步骤 (终止按完整映像路径记录)
+    fn record_macro_step(&mut self, cmd: &UsbCmd) {
+        let Some(recording) = &mut self.macro_recording else {
+            return;
+        };
+        // 取不到映像路径 (已退出) 的 PID 无法重放，不记录；系统 / 服务进程不录制
+        let image = |pid: u32| {
+            handles::process_image_name(pid).filter(|p| !is_protected_process(pid, p.rsplit(['\\', '/']).next().unwrap_or(p)))
+        };
+        let steps: Vec<macros::Step> = match cmd {
+            UsbCmd::KillOne(pid, _) => image(*pid).map(macros::Step::KillProcess).into_iter().collect(),
+            UsbCmd::KillGroup(pids, _) => {
+                let mut names: Vec<String> = pids.iter().filter_map(|&pid| image(pid)).collect();
+                names.sort_by_key(|n| n.to_lowercase());
+                names.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+                names.into_iter().map(macros::Step::KillProcess).collect()
+            }
+            UsbCmd::Scan(d) | UsbCmd::ForceEject(d, _) => vec![macros::Step::Eject(norm_drive(d))],
+            _ => return,
+        };
+        for step in steps {
+            self.toasts.push((Instant::now(), trf("⏺ 已录制：{}", &[&step.describe()])));
+            recording.steps.push(step);
+        }
+    }
+
+    /// 发送 USB 命令；必然因权限失败的先提示提权，会调用外部工具的命令先展示命令行等待确认
+    fn send_usb_cmd(&mut self, cmd: UsbCmd) {
+        if cmd.is_destructive() && self.guest_blocked() {
+            return;
+        }
+        let pids: &[u32] = match &cmd {
+            UsbCmd::KillOne(pid, _) | UsbCmd::RestartProcess(pid) => std::slice::from_ref(pid),
+            UsbCmd::KillGroup(pids, _) => pids,
+            _ => &[],
+        };
+        let critical: Vec<String> = pids
+            .iter()
+            .map(|&pid| process_name(pid))
+            .filter(|n| gpu_guard::is_display_critical(n))
+            .collect();
+        if !critical.is_empty() {
+            self.pending_gpu = Some(critical);
+            return;
+        }
+        if let Some(reason) = self.admin_preflight(&cmd) {
+            self.pending_admin = Some((reason, cmd));
+            return;
+        }
+        self.confirm_usb_cmd(cmd);
+    }
+
+    fn confirm_usb_cmd(&mut self, cmd: UsbCmd) {
+        if !self.always_allow_external && !cmd.external_commands().is_empty() {
+            self.pending_external = Some(ExternalAction::Usb(cmd));
+        } else {
+            self.dispatch_usb_cmd(cmd);
+        }
+    }
+
+    /// 所有检查与确认都已通过：录制宏步骤并交给 USB 线程
+    fn dispatch_usb_cmd(&mut self, cmd: UsbCmd) {
+        self.record_macro_step(&cmd);
+        if let UsbCmd::Wipe(_, _, cancel) = &cmd {
+            self.wipe_cancel = Some(cancel.clone());
+        }
+        let _ = self.usb_tx.send(cmd);
+    }
+
+    /// 访客模式中：提示并返回 true，调用方放弃操作
+    fn guest_blocked(&mut self) -> bool {
+        if !guest_mode::is_active() {
+            return false;
+        }
+        self.toasts.push((Instant::now(), tr("🔒 访客模式下已禁用此操作").to_string()));
+        true
+    }
+
+    /// 提权预检：非管理员下注定失败的命令返回原因，其余返回 None
+    fn admin_preflight(&self, cmd: &UsbCmd) -> Option<String> {
+        if self.is_admin {
+            return None;
+        }
+        let denied = |pids: &[u32]| {
+            let n = elevation::denied_pids(pids).len();
+            (n > 0).then(|| trf("{} 个进程属于服务或其他用户，终止需要管理员权限", &[&n]))
+        };
+        match cmd {
+            UsbCmd::Mount(_) => Some(tr("修改挂载点需要管理员权限").to_string()),
+            UsbCmd::SetReadOnly(_, _) => Some(tr("只读挂载需要管理员权限").to_string()),
+            UsbCmd::SetWriteProtect(_, _) | UsbCmd::SetWriteProtectPolicy(_) => {
+                Some(tr("写保护需要管理员权限").to_string())
+            }
+            UsbCmd::FsutilDismount(_) => Some(tr("fsutil 卸载卷需要管理员权限").to_string()),
+            UsbCmd::Format(_, _) => Some(tr("格式化需要管理员权限").to_string()),
+            UsbCmd::WriteImage(_, _) => Some(tr("写入镜像需要管理员权限").to_string()),
+            UsbCmd::Wipe(_, _, _) => Some(tr("安全擦除需要管理员权限").to_string()),
+            UsbCmd::SetRemovalPolicy(_, _) => Some(tr("修改删除策略需要管理员权限").to_string()),
+            UsbCmd::KillOne(pid, _) => denied(&[*pid]),
+            UsbCmd::KillGroup(pids, _) => denied(pids),
+            UsbCmd::RestartProcess(pid) => denied(&[*pid]),
+            UsbCmd::RunMacro(m) => {
+                let kill_pids: Vec<u32> = m
+                    .steps
+                    .iter()
+                    .filter_map(|s| match s {
+                        macros::Step::KillProcess(image) => Some(macros::kill_targets(image)),
+                        _ => None,
+                    })
+                    .flatten()
+                    .map(|(pid, _)| pid)
+                    .collect();
+                m.steps
+                    .iter()
+                    .any(|s| matches!(s, macros::Step::RestartService(_)))
+                    .then(|| trf("操作宏“{}”包含重启服务，需要管理员权限", &[&m.name]))
+                    .or_else(|| denied(&kill_pids))
+            }
+            UsbCmd::ForceEject(d, pids) | UsbCmd::RestartExplorer(d, pids) => denied(pids).or_else(|| {
+                (!elevation::can_open_volume(d))
+                    .then(|| trf("{}: 无权打开卷，强制卸载与设备弹出需要管理员权限", &[&norm_drive(d)]))
+            }),
+            UsbCmd::Scan(_)
+            | UsbCmd::SetPolicy(_)
+            | UsbCmd::DevicePrefs(_, _)
+            | UsbCmd::Relabel(_, _)
+            | UsbCmd::SpeedTest(_) => None,
+        }
+    }
+
+    /// 模块列表窗口：按路径搜索，可只看可移动磁盘上的 DLL (找出占用 U 盘的模块)
+    fn render_module_view(&mut self, ctx: &egui::Context, snapshot: &AppSnapshot) {
+        let Some(view) = &mut self.module_view else {
+            return;
+        };
+        let removable: Vec<String> = snapshot
+            .disks
+            .iter()
+            .filter(|d| d.is_removable)
+            .map(|d| format!("{}\\", d.mount_point.trim_end_matches('\\').to_lowercase()))
+            .collect();
+        let on_removable = |path: &str| {
+            let path = path.to_lowercase();
+            removable.iter().any(|root| path.starts_with(root))
+        };
+        let mut open = true;
+        let mut pending = false;
+        egui::Window::new(trf("模块 · {} (PID {})", &[&view.name, &view.pid]))
+            .open(&mut open)
+            .default_size([680.0, 420.0])
+            .show(ctx, |ui| {
+                let modules = match &view.modules {
+                    Ok(m) => m,
+                    Err(e) => {
+                        ui.colored_label(egui::Color32::RED, e);
+                        if ui.button(tr("重试")).clicked() {
+                            view.reload();
+                        }
+                        return;
+                    }
+                };
+                let on_usb = modules.iter().filter(|m| on_removable(&m.path)).count();
+                let mut reload = false;
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut view.filter)
+                            .hint_text(tr("搜索路径或文件名，如 E:\\"))
+                            .desired_width(220.0),
+                    );
+                    ui.checkbox(&mut view.only_removable, trf("只看可移动磁盘上的 ({})", &[&on_usb]));
+                    reload = ui.small_button(tr("刷新")).clicked();
+                });
+                let shown: Vec<&modules::Module> = modules
+                    .iter()
+                    .filter(|m| m.matches(view.filter.trim()))
+                    .filter(|m| !view.only_removable || on_removable(&m.path))
+                    .collect();
+                ui.label(
+                    egui::RichText::new(trf("共 {} 个模块，显示 {} 个", &[&modules.len(), &shown.len()]))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+                egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    egui::Grid::new("module_list").striped(true).spacing([10.0, 2.0]).show(ui, |ui| {
+                        for m in shown {
+                            let name = egui::RichText::new(m.file_name()).small().monospace();
+                            let name = if on_removable(&m.path) {
+                                name.color(egui::Color32::from_rgb(255, 140, 0))
+                            } else {
+                                name
+                            };
+                            ui.label(name)
+                                .on_hover_text(trf("{}\n基址 0x{} · {} KB", &[&m.path, &format!("{:X}", m.base), &(m.size / 1024)]));
+                            ui.label(egui::RichText::new(m.version.as_deref().unwrap_or("-")).small());
+                            match view.signatures.get(&m.path) {
+                                None => {
+                                    pending = true;
+                                    ui.label(egui::RichText::new(tr("校验中…")).small().color(egui::Color32::GRAY));
+                                }
+                                Some(s @ signature::Signature::Signed(_)) => {
+                                    let green = egui::Color32::from_rgb(120, 180, 120);
+                                    ui.label(egui::RichText::new(format!("✔ {}", s.label())).small().color(green));
+                                }
+                                Some(s) => {
+                                    ui.label(egui::RichText::new(s.label()).small().color(egui::Color32::GOLD));
+                                }
+                            }
+                            ui.add(
+                                egui::Label::new(egui::RichText::new(&m.path).small().color(egui::Color32::GRAY))
+                                    .truncate(),
+                            );
+                            if ui.small_button("📂").on_hover_text(tr("在资源管理器中显示")).clicked() {
+                                unelevated::reveal_in_explorer(&m.path);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+                if reload {
+                    view.reload();
+                }
+            });
+        if pending {
+            ctx.request_repaint_after(Duration::from_millis(500));
+        }
+        if !open {
+            self.module_view = None;
+        }
+    }
+
+    /// 强力清场确认框：列出将被终止的进程；确认后挂起并倒计时，结束时发出清场
+    fn render_clear_confirm(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &mut self.pending_clear else {
+            return;
+        };
+        let mut confirm = false;
+        let mut cancel = false;
+        let remaining = pending.remaining();
+        egui::Window::new(tr("确认强力清场"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(
+                    egui::RichText::new(trf("{} 上的以下 {} 个进程将被强制终止：", &[&pending.drive, &pending.targets.len()]))
+                        .color(egui::Color32::GOLD),
+                );
+                egui::ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                    for line in &pending.summary {
+                        ui.label(format!("• {}", line));
+                    }
+                });
+                ui.label(
+                    egui::RichText::new(tr("未保存的数据可能丢失"))
+                        .small()
+                        .color(egui::Color32::from_rgb(255, 80, 80)),
+                );
+                ui.add_space(6.0);
+                match remaining {
+                    None => {
+                        ui.horizontal(|ui| {
+                            let btn = egui::Button::new(egui::RichText::new(tr("终止并弹出")).color(egui::Color32::WHITE))
+                                .fill(egui::Color32::from_rgb(200, 60, 60));
+                            if ui.add(btn).clicked() {
+                                confirm = true;
+                            }
+                            if ui.button(tr("取消")).clicked() {
+                                cancel = true;
+                            }
+                        });
+                    }
+                    Some(left) => {
+                        ui.label(trf(
+                            "已挂起 {}/{} 个进程，{} 秒后终止",
+                            &[&pending.suspended_count(), &pending.targets.len(), &format!("{:.0}", left.as_secs_f32().ceil())],
+                        ));
+                        ui.add(
+                            egui::ProgressBar::new(left.as_secs_f32() / clear_confirm::UNDO_WINDOW.as_secs_f32())
+                                .desired_width(240.0),
+                        );
+                        if ui.button(tr("↩ 撤销")).on_hover_text(tr("恢复已挂起的进程，不做任何终止")).clicked() {
+                            cancel = true;
+                        }
+                    }
+                }
+            });
+
+        // 访客模式在挂起之前检查，确认框可能在开启访客模式前就已弹出
+        let blocked = confirm && guest_mode::is_active();
+        if confirm && !blocked {
+            pending.begin_countdown();
+        }
+        let expired = remaining.is_some_and(|left| left.is_zero());
+        if blocked {
+            self.pending_clear = None;
+            self.guest_blocked();
+        } else if cancel {
+            // Drop 时恢复挂起的进程
+            self.pending_clear = None;
+            audit::record("强力清场", "用户在倒计时内撤销");
+        } else if expired {
+            if let Some(mut pending) = self.pending_clear.take() {
+                pending.release();
+                let pids = pending.targets.iter().map(|(pid, _)| *pid).collect();
+                self.send_usb_cmd(UsbCmd::ForceEject(pending.drive.clone(), pids));
+            }
+        } else if remaining.is_some() || confirm {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+    }
+
+    /// 强力清场：开启了清场前确认时先弹出倒计时确认框，否则直接下发
+    fn request_force_clear(&mut self, drive: &str, list: &[Occupant]) {
+        if self.pending_clear.is_some() {
+            return;
+        }
+        if self.settings.confirm_force_clear && !list.is_empty() {
+            self.pending_clear = Some(clear_confirm::PendingClear::new(
+                drive,
+                list.iter().map(|o| (o.pid, o.name.clone())).collect(),
+                list.iter().map(|o| o.desc.clone()).collect(),
+            ));
+        } else {
+            let pids = list.iter().map(|o| o.pid).collect();
+            self.send_usb_cmd(UsbCmd::ForceEject(drive.to_string(), pids));
+        }
+    }
+
+    /// 显示保护提示：拒绝终止桌面合成 / 显卡驱动进程，改为提供重启显卡驱动
+    fn render_gpu_prompt(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let Some(names) = &self.pending_gpu else {
+            return;
+        };
+        let rounding = ui::UiConstants::ROUNDING * ctx.pixels_per_point();
+        let mut restart = false;
+        let mut dismiss = false;
+        egui::Frame::group(ui.style())
+            .fill(egui::Color32::from_rgb(40, 30, 25))
+            .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 140, 0)))
+            .rounding(rounding)
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(trf("🖥 {} 属于桌面合成 / 显卡驱动，已阻止终止", &[&names.join(tr("、"))]))
+                        .color(egui::Color32::from_rgb(255, 140, 0))
+                        .strong(),
+                );
+                ui.label(
+                    egui::RichText::new(tr("结束它们只会黑屏或丢失驱动功能；画面卡死时请重启显卡驱动 (窗口与程序都会保留)"))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(tr("🔄 重启显卡驱动"))
+                        .on_hover_text(tr("相当于按下 Win+Ctrl+Shift+B，屏幕会闪烁一下"))
+                        .clicked()
+                    {
+                        restart = true;
+                    }
+                    if ui.button(tr("取消")).clicked() {
+                        dismiss = true;
+                    }
+                });
+            });
+        ui.add_space(10.0);
+
+        if restart {
+            self.restart_graphics_driver();
+        }
+        if restart || dismiss {
+            self.pending_gpu = None;
+        }
+    }
+
+    fn restart_graphics_driver(&mut self) {
+        self.usb_status_msg = match gpu_guard::restart_graphics_driver() {
+            Ok(()) => tr("✅ 已发送重启显卡驱动 (Win+Ctrl+Shift+B)").to_string(),
+            Err(e) => trf("❌ 重启显卡驱动失败：{}", &[&e]),
+        };
+        self.usb_msg_time = Some(Instant::now());
+    }
+
+    /// 一键修复：修复声音 / 重置打印后台，各自逐步显示结果
+    fn render_quick_fixes(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let is_admin = self.is_admin;
+        quick_fix_row(
+            ui,
+            ctx,
+            &mut self.audio_fix,
+            is_admin,
+            tr("🔊 修复声音"),
+            tr("依次重启 Windows Audio / Audio Endpoint Builder 服务，并结束卡住的 audiodg.exe"),
+        );
+        quick_fix_row(
+            ui,
+            ctx,
+            &mut self.spooler_fix,
+            is_admin,
+            tr("🖨 重置打印后台"),
+            tr("停止 Print Spooler，清空卡住的打印作业后重新启动，并列出被移除的作业"),
+        );
+        // 用户临时目录无需管理员；无权限的文件 (Windows\Temp) 跳过并计数
+        quick_fix_row(
+            ui,
+            ctx,
+            &mut self.temp_cleanup,
+            true,
+            tr("🧹 清理临时文件"),
+            tr("清理 %TEMP% 与 Windows\\Temp，跳过正在使用的文件并列出占用进程，最后汇总释放的空间"),
+        );
+    }
+
+    /// 休眠文件与快速启动：快速启动会让关机前未弹出的 U 盘开机后仍显示被占用
+    fn render_hibernation(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        // 开关命令运行后在下一次读取时反映出来
+        if self.hibernation.as_ref().map(|(t, _)| t.elapsed() > Duration::from_secs(5)).unwrap_or(true) {
+            self.hibernation = Some((Instant::now(), hibernation::status()));
+        }
+        let Some((_, status)) = self.hibernation.clone() else {
+            return;
+        };
+        ui.separator();
+        ui.label(egui::RichText::new(tr("💤 休眠与快速启动")).strong().color(egui::Color32::from_rgb(0, 206, 209)));
+        let can_run = self.is_admin && !self.tool_running();
+        let is_admin = self.is_admin;
+        let hint = move |text: &'static str| if is_admin { tr(text) } else { tr("需要以管理员身份运行") };
+        let mut spec: Option<tool_runner::ToolSpec> = None;
+        egui::Grid::new("hibernation_grid").num_columns(3).spacing([10.0, 4.0]).show(ui, |ui| {
+            ui.label(tr("休眠:"));
+            ui.label(match status.hiberfil_size {
+                Some(size) if status.hibernate => {
+                    trf("已开启 · hiberfil.sys {} GB", &[&format!("{:.1}", size as f32 / 1024.0 / 1024.0 / 1024.0)])
+                }
+                _ if status.hibernate => tr("已开启").to_string(),
+                _ => tr("已关闭").to_string(),
+            });
+            let (label, text) = if status.hibernate {
+                (tr("关闭休眠"), "powercfg /h off：删除 hiberfil.sys 释放空间，同时关闭快速启动与休眠")
+            } else {
+                (tr("开启休眠"), "powercfg /h on：重新创建 hiberfil.sys")
+            };
+            let tip = hint(text);
+            if ui
+                .add_enabled(can_run, egui::Button::new(label).small())
+                .on_hover_text(tip)
+                .on_disabled_hover_text(tip)
+                .clicked()
+            {
+                spec = Some(hibernation::set_hibernate_spec(!status.hibernate));
+            }
+            ui.end_row();
+
+            ui.label(tr("快速启动:"));
+            let (text, color) = if status.fast_startup() {
+                (tr("已开启"), egui::Color32::GOLD)
+            } else if status.hiberboot {
+                (tr("未生效 (休眠已关闭)"), egui::Color32::GRAY)
+            } else {
+                (tr("已关闭"), egui::Color32::GRAY)
+            };
+            ui.label(egui::RichText::new(text).color(color));
+            let tip = hint("快速启动关机时会保留卷的挂载状态：未弹出的 U 盘开机后可能仍被占用、卷被标记为需要修复。“重启”不受影响");
+            let label = if status.hiberboot { tr("关闭快速启动") } else { tr("开启快速启动") };
+            if ui
+                .add_enabled(can_run && (status.hibernate || status.hiberboot), egui::Button::new(label).small())
+                .on_hover_text(tip)
+                .on_disabled_hover_text(tip)
+                .clicked()
+            {
+                spec = Some(hibernation::set_fast_startup_spec(!status.hiberboot));
+            }
+            ui.end_row();
+        });
+        if status.fast_startup() {
+            ui.label(
+                egui::RichText::new(tr("⚠ 快速启动已开启：关机前请先弹出 U 盘，否则开机后可能提示被占用或需要修复"))
+                    .small()
+                    .color(egui::Color32::GOLD),
+            );
+        }
+        if let Some(spec) = spec {
+            self.start_tool(spec, ctx);
+        }
+    }
+
+    /// 浏览器标签进程统计；标签过多时标红并提供“释放浏览器内存”
+    fn render_browser_memory(&mut self, ui: &mut egui::Ui, snapshot: &AppSnapshot) {
+        let browsers = browser_memory::survey(
+            snapshot.high_resource.iter().chain(&snapshot.other_groups).chain(&snapshot.system_groups),
+        );
+        for usage in &browsers {
+            let extreme = usage.is_extreme(snapshot.total_memory);
+            let color = if extreme { egui::Color32::from_rgb(255, 80, 80) } else { egui::Color32::GRAY };
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(trf(
+                        "🌐 {}：{} 个标签进程，占用 {} MB (浏览器合计 {} MB)",
+                        &[
+                            &usage.browser.label(),
+                            &usage.tab_pids.len(),
+                            &format!("{:.0}", usage.tab_memory as f32 / 1024.0 / 1024.0),
+                            &format!("{:.0}", usage.total_memory as f32 / 1024.0 / 1024.0),
+                        ],
+                    ))
+                    .small()
+                    .color(color),
+                );
+                if extreme
+                    && ui
+                        .small_button(tr("释放浏览器内存"))
+                        .on_hover_text(trf(
+                            "不结束任何标签：裁剪标签进程的工作集，并打开 {} 由浏览器自行丢弃不用的标签",
+                            &[&usage.browser.discard_page()],
+                        ))
+                        .clicked()
+                {
+                    self.usb_status_msg = browser_memory::release(usage);
+                    self.usb_msg_time = Some(Instant::now());
+                }
+            });
+        }
+    }
+
+    /// 内存整理：裁剪选中分组 (未选中时为全部非系统进程) 的工作集，可选清空备用列表
+    fn render_memory_trim(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, snapshot: &AppSnapshot) {
+        if let Some(rx) = &self.memory_trim_rx {
+            match rx.try_recv() {
+                Ok(text) => {
+                    self.usb_status_msg = text;
+                    self.usb_msg_time = Some(Instant::now());
+                    self.memory_trim_rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.memory_trim_rx = None,
+            }
+        }
+        let groups = snapshot.high_resource.iter().chain(&snapshot.other_groups);
+        let (scope, pids): (String, Vec<u32>) = if self.selected_groups.is_empty() {
+            let pids = groups.filter(|g| !g.is_system).flat_map(|g| g.pids.iter().copied()).collect();
+            (tr("全部非系统进程").to_string(), pids)
+        } else {
+            let pids = groups
+                .chain(&snapshot.system_groups)
+                .filter(|g| self.selected_groups.contains(&g.name))
+                .flat_map(|g| g.pids.iter().copied())
+                .collect();
+            (trf("选中的 {} 个分组", &[&self.selected_groups.len()]), pids)
+        };
+        let running = self.memory_trim_rx.is_some();
+        ui.horizontal(|ui| {
+            let label = if running { tr("🧽 整理中…") } else { tr("🧽 内存整理") };
+            if ui
+                .add_enabled(!running && !pids.is_empty(), egui::Button::new(label))
+                .on_hover_text(trf(
+                    "裁剪{}的工作集 ({} 个进程)，不结束任何进程；在进程表中选中分组可只整理这些",
+                    &[&scope, &pids.len()],
+                ))
+                .clicked()
+            {
+                let (tx, rx) = mpsc::channel();
+                let purge = self.purge_standby && self.is_admin;
+                let ctx = ctx.clone();
+                std::thread::spawn(move || {
+                    let _ = tx.send(memory_trim::run(&pids, purge));
+                    ctx.request_repaint();
+                });
+                self.memory_trim_rx = Some(rx);
+            }
+            ui.add_enabled(self.is_admin, egui::Checkbox::new(&mut self.purge_standby, tr("同时清空备用列表")))
+                .on_hover_text(tr("丢弃系统文件缓存，释放为空闲内存；之后打开文件会稍慢。需要管理员权限"))
+                .on_disabled_hover_text(tr("清空备用列表需要管理员权限"));
+        });
+    }
+
+    /// 提权提示：说明为何需要管理员，可一键以管理员身份重启，或仍然尝试
+    fn render_admin_prompt(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let Some((reason, _)) = &self.pending_admin else {
+            return;
+        };
+        let rounding = ui::UiConstants::ROUNDING * ctx.pixels_per_point();
+        let mut decision: Option<bool> = None;
+        let mut elevate = false;
+        egui::Frame::group(ui.style())
+            .fill(egui::Color32::from_rgb(40, 30, 25))
+            .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 140, 0)))
+            .rounding(rounding)
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(format!("🔐 {}", reason))
+                        .color(egui::Color32::from_rgb(255, 140, 0))
+                        .strong(),
+                );
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(tr("需要管理员权限 — 点击提权"))
+                        .on_hover_text(tr("以管理员身份重新启动本程序，当前窗口随后关闭"))
+                        .clicked()
+                    {
+                        elevate = true;
+                    }
+                    if ui.button(tr("仍然尝试")).on_hover_text(tr("部分操作可能失败")).clicked() {
+                        decision = Some(true);
+                    }
+                    if ui.button(tr("取消")).clicked() {
+                        decision = Some(false);
+                    }
+                });
+            });
+        ui.add_space(10.0);
+
+        if elevate {
+            match elevation::relaunch_as_admin() {
+                Ok(()) => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+                Err(e) => {
+                    self.usb_status_msg = format!("❌ {}", e);
+                    self.usb_msg_time = Some(Instant::now());
+                }
+            }
+            return;
+        }
+        match decision {
+            Some(true) => {
+                if let Some((_, cmd)) = self.pending_admin.take() {
+                    self.confirm_usb_cmd(cmd);
+                }
+            }
+            Some(false) => {
+                self.pending_admin = None;
+                // 复选框已先行切换，取消时以注册表实际值为准
+                self.usb_write_policy = read_only::policy_write_protect();
+            }
+            None => {}
+        }
+    }
+
+    /// 在控制台中启动外部工具 (同样需要确认)
+    fn start_tool(&mut self, spec: tool_runner::ToolSpec, ctx: &egui::Context) {
+        if self.console.as_ref().map(|c| c.is_running()).unwrap_or(false) || self.guest_blocked() {
+            return;
+        }
+        if self.always_allow_external {
+            self.console = Some(tool_runner::ToolRun::start(spec, ctx.clone()));
+        } else {
+            self.pending_external = Some(ExternalAction::Tool(spec));
+        }
+    }
+
+    /// 系统修复：确认后依次运行 DISM 与 sfc
+    fn request_repair(&mut self, ctx: &egui::Context) {
+        if self.tool_running() || self.guest_blocked() {
+            return;
+        }
+        if self.always_allow_external {
+            self.begin_repair(ctx);
+        } else {
+            self.pending_external = Some(ExternalAction::Repair);
+        }
+    }
+
+    fn begin_repair(&mut self, ctx: &egui::Context) {
+        let mut session = system_repair::RepairSession::new();
+        if let Some(spec) = session.next_step() {
+            self.console = Some(tool_runner::ToolRun::start(spec, ctx.clone()));
+        }
+        self.repair = Some(session);
+    }
+
+    /// 每帧推进修复流程：更新进度，上一步结束后启动下一步
+    fn advance_repair(&mut self, ctx: &egui::Context) {
+        let (Some(session), Some(run)) = (&mut self.repair, &mut self.console) else {
+            return;
+        };
+        if session.current.is_none() {
+            return;
+        }
+        run.poll();
+        session.update_progress(&run.lines);
+        if let Some(exit) = &run.exit {
+            session.finish_step(&run.lines, exit);
+            if let Some(spec) = session.next_step() {
+                self.console = Some(tool_runner::ToolRun::start(spec, ctx.clone()));
+            }
+        }
+    }
+
+    /// 网络重置：确认后依次运行 ipconfig / netsh
+    fn request_net_reset(&mut self, ctx: &egui::Context) {
+        if self.tool_running() || self.guest_blocked() {
+            return;
+        }
+        let steps = net_reset::NetStep::plan(self.net_reset_winsock);
+        if self.always_allow_external {
+            self.begin_net_reset(&steps, ctx);
+        } else {
+            self.pending_external = Some(ExternalAction::NetReset(steps));
+        }
+    }
+
+    fn begin_net_reset(&mut self, steps: &[net_reset::NetStep], ctx: &egui::Context) {
+        let mut session = net_reset::NetResetSession::new(steps);
+        if let Some(spec) = session.next_step() {
+            self.console = Some(tool_runner::ToolRun::start(spec, ctx.clone()));
+        }
+        self.net_reset = Some(session);
+    }
+
+    /// 每帧推进网络重置：上一步结束后启动下一步
+    fn advance_net_reset(&mut self, ctx: &egui::Context) {
+        let (Some(session), Some(run)) = (&mut self.net_reset, &mut self.console) else {
+            return;
+        };
+        if session.current.is_none() {
+            return;
+        }
+        run.poll();
+        if let Some(exit) = &run.exit {
+            session.finish_step(&run.lines, exit);
+            if let Some(spec) = session.next_step() {
+                self.console = Some(tool_runner::ToolRun::start(spec, ctx.clone()));
+            }
+        }
+    }
+
+    /// 网络重置：选项、逐步结果 (可展开查看输出) 与回退说明
+    fn render_net_reset(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let running = self.net_reset.as_ref().map(|s| !s.is_done()).unwrap_or(false);
+        let mut clear = false;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                let hint = if self.is_admin {
+                    tr("留存当前配置后依次释放 / 重新获取 IP 地址并清空 DNS 缓存，期间会短暂断网")
+                } else {
+                    tr("需要以管理员身份运行")
+                };
+                if ui
+                    .add_enabled(self.is_admin && !running && !self.tool_running(), egui::Button::new(tr("🔁 网络重置")))
+                    .on_hover_text(hint)
+                    .on_disabled_hover_text(hint)
+                    .clicked()
+                {
+                    self.request_net_reset(ctx);
+                }
+                ui.add_enabled(!running, egui::Checkbox::new(&mut self.net_reset_winsock, tr("同时重置 Winsock")))
+                    .on_hover_text(tr("修复被代理 / 安全软件破坏的网络栈；需要重启计算机才生效"));
+                if self.net_reset_winsock {
+                    ui.label(egui::RichText::new(tr("⚠ 完成后需重启")).small().color(egui::Color32::GOLD));
+                }
+            });
+            let Some(session) = &self.net_reset else {
+                return;
+            };
+            if let Some(step) = session.current {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(egui::RichText::new(step.label()).small().color(egui::Color32::GOLD));
+                });
+            }
+            for r in &session.results {
+                let color = if r.ok { egui::Color32::GRAY } else { egui::Color32::KHAKI };
+                egui::CollapsingHeader::new(
+                    egui::RichText::new(format!("{}：{}", r.step.label(), r.summary)).small().color(color),
+                )
+                .id_source(("net_reset_step", r.step.label()))
+                .show(ui, |ui| {
+                    for line in &r.output {
+                        ui.label(egui::RichText::new(line).small().monospace());
+                    }
+                });
+            }
+            if session.is_done() {
+                if session.needs_reboot() {
+                    ui.label(
+                        egui::RichText::new(tr("⚠ Winsock 已重置，请重启计算机使其生效"))
+                            .color(egui::Color32::GOLD)
+                            .strong(),
+                    );
+                }
+                ui.label(egui::RichText::new(tr(net_reset::ROLLBACK_NOTE)).small().color(egui::Color32::GRAY));
+                if let Some(path) = &session.snapshot_file {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(path.display().to_string()).small().monospace());
+                        if ui.small_button(tr("打开")).clicked() {
+                            unelevated::open_in_explorer(&path.to_string_lossy());
+                        }
+                    });
+                }
+                if ui.small_button(tr("清除网络重置结果")).clicked() {
+                    clear = true;
+                }
+            }
+        });
+        if clear {
+            self.net_reset = None;
+        }
+    }
+
+    fn tool_running(&self) -> bool {
+        self.uninstall_rx.is_some() || self.console.as_ref().map(|c| c.is_running()).unwrap_or(false)
+    }
+
+    /// 卸载程序常因文件被占用而失败：工作线程先结束进程树并等它们真正退出，再启动卸载程序
+    fn begin_uninstall(&mut self, spec: tool_runner::ToolSpec, pids: Vec<u32>, ctx: &egui::Context) {
+        let (tx, rx) = mpsc::channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            kill_group(&pids, true);
+            for &pid in &pids {
+                graceful::wait_exit(pid, Duration::from_secs(5));
+            }
+            let _ = tx.send(spec);
+            ctx.request_repaint();
+        });
+        self.uninstall_rx = Some(rx);
+    }
+
+    /// 外部命令确认：执行 fsutil / chkdsk 等系统工具前展示完整命令行
+    fn render_external_confirm(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let Some(action) = &self.pending_external else {
+            return;
+        };
+        let rounding = ui::UiConstants::ROUNDING * ctx.pixels_per_point();
+        let mut decision: Option<bool> = None;
+        let mut allow_always = false;
+        egui::Frame::group(ui.style())
+            .fill(egui::Color32::from_rgb(40, 35, 25))
+            .stroke(egui::Stroke::new(1.0, egui::Color32::GOLD))
+            .rounding(rounding)
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(tr("🧾 该操作将执行以下外部命令："))
+                        .color(egui::Color32::GOLD)
+                        .strong(),
+                );
+                for line in action.command_lines() {
+                    ui.label(egui::RichText::new(line).monospace().color(egui::Color32::WHITE));
+                }
+                ui.horizontal(|ui| {
+                    if ui.button(tr("执行")).clicked() {
+                        decision = Some(true);
+                    }
+                    if ui.button(tr("总是允许")).on_hover_text(tr("本次运行内不再询问")).clicked() {
+                        allow_always = true;
+                        decision = Some(true);
+                    }
+                    if ui.button(tr("取消")).clicked() {
+                        decision = Some(false);
+                    }
+                });
+                ui.label(
+                    egui::RichText::new(tr("所有外部命令都会记录到审计日志 (audit.log)"))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+            });
+        ui.add_space(10.0);
+
+        if allow_always {
+            self.always_allow_external = true;
+        }
+        // 确认框在开启访客模式前就已弹出
+        if decision == Some(true) && self.guest_blocked() {
+            decision = Some(false);
+        }
+        match decision {
+            Some(true) => match self.pending_external.take() {
+                Some(ExternalAction::Usb(cmd)) => self.dispatch_usb_cmd(cmd),
+                Some(ExternalAction::Tool(spec)) => {
+                    self.console = Some(tool_runner::ToolRun::start(spec, ctx.clone()));
+                }
+                Some(ExternalAction::Repair) => self.begin_repair(ctx),
+                Some(ExternalAction::NetReset(steps)) => self.begin_net_reset(&steps, ctx),
+                Some(ExternalAction::Uninstall(spec, pids)) => self.begin_uninstall(spec, pids, ctx),
+                None => {}
+            },
+            Some(false) => self.pending_external = None,
+            None => {}
+        }
+    }
+
+    /// 点选窗口的结果：终止 / 挂起拾取到的进程，以及恢复此前挂起的进程
+    fn render_picked(&mut self, ui: &mut egui::Ui) {
+        if let Some(rx) = &self.picker_rx {
+            match rx.try_recv() {
+                Ok(Ok(p)) => {
+                    self.picked = Some(p);
+                    self.picker_rx = None;
+                }
+                Ok(Err(e)) => {
+                    self.toasts.push((Instant::now(), format!("⌖ {}", e)));
+                    self.picker_rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.picker_rx = None,
+            }
+        }
+        if self.picked.is_none() && self.suspended.is_empty() {
+            return;
+        }
+        let rounding = ui::UiConstants::ROUNDING * ui.ctx().pixels_per_point();
+        let mut kill: Option<u32> = None;
+        let mut restart: Option<u32> = None;
+        let mut suspend = false;
+        let mut resume: Option<usize> = None;
+        let mut close = false;
+        egui::Frame::group(ui.style())
+            .fill(egui::Color32::from_rgb(40, 30, 25))
+            .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 140, 0)))
+            .rounding(rounding)
+            .show(ui, |ui| {
+                if let Some(p) = &self.picked {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(format!("⌖ {} (PID {})", p.name, p.pid))
+                                .color(egui::Color32::from_rgb(255, 140, 0))
+                                .strong(),
+                        );
+                        if p.hung {
+                            ui.colored_label(egui::Color32::RED, tr("未响应"));
+                        }
+                    });
+                    if !p.title.is_empty() {
+                        ui.label(egui::RichText::new(&p.title).small().color(egui::Color32::GRAY));
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("终止")).clicked() {
+                            kill = Some(p.pid);
+                        }
+                        if ui.button(tr("重启")).on_hover_text(tr("结束后按原命令行与工作目录重新启动")).clicked() {
+                            restart = Some(p.pid);
+                        }
+                        if ui
+                            .button(tr("挂起"))
+                            .on_hover_text(tr("暂停进程的所有线程，释放 CPU；可随时恢复"))
+                            .clicked()
+                        {
+                            suspend = true;
+                        }
+                        if ui.button(tr("取消")).clicked() {
+                            close = true;
+                        }
+                    });
+                }
+                for (i, (pid, name)) in self.suspended.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(trf("⏸ 已挂起 {} (PID {})", &[&name, &pid])).small());
+                        if ui.small_button(tr("恢复")).clicked() {
+                            resume = Some(i);
+                        }
+                    });
+                }
+            });
+        ui.add_space(10.0);
+
+        if let Some(pid) = kill {
+            self.picked = None;
+            self.send_usb_cmd(UsbCmd::KillGroup(vec![pid], self.kill_tree));
+        }
+        if let Some(pid) = restart {
+            self.picked = None;
+            self.send_usb_cmd(UsbCmd::RestartProcess(pid));
+        }
+        if suspend && !self.guest_blocked() {
+            if let Some(p) = self.picked.take() {
+                // 挂起桌面合成 / 显卡驱动进程同样会让屏幕冻结，与终止一样改为提示重启显卡驱动
+                if gpu_guard::is_display_critical(&p.name) {
+                    self.pending_gpu = Some(vec![p.name]);
+                } else {
+                    match window_picker::suspend(p.pid, &p.name) {
+                        Ok(()) => self.suspended.push((p.pid, p.name)),
+                        Err(e) => self.toasts.push((Instant::now(), trf("❌ 挂起失败：{}", &[&e]))),
+                    }
+                }
+            }
+        }
+        if let Some(i) = resume {
+            let (pid, name) = self.suspended.remove(i);
+            if let Err(e) = window_picker::resume(pid, &name) {
+                self.toasts.push((Instant::now(), trf("❌ 恢复失败：{}", &[&e])));
+            }
+        }
+        if close {
+            self.picked = None;
+        }
+    }
+
+    /// 外部工具控制台：实时输出 + 取消
+    fn render_console(&mut self, ui: &mut egui::Ui) {
+        if let Some(rx) = &self.uninstall_rx {
+            match rx.try_recv() {
+                Ok(spec) => {
+                    self.console = Some(tool_runner::ToolRun::start(spec, ui.ctx().clone()));
+                    self.uninstall_rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.uninstall_rx = None,
+            }
+        }
+        let Some(run) = &mut self.console else {
+            return;
+        };
+        run.poll();
+
+        let mut close = false;
+        let status = match &run.exit {
+            None => tr("运行中...").to_string(),
+            Some(Ok(code)) => trf("已结束 (退出码 {})", &[&code]),
+            Some(Err(e)) => e.clone(),
+        };
+        egui::CollapsingHeader::new(
+            egui::RichText::new(trf("🖥 控制台 — {} [{}]", &[&run.spec.title, &status]))
+                .color(egui::Color32::from_rgb(0, 206, 209))
+                .strong(),
+        )
+        .id_source("tool_console")
+        .default_open(true)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("> {}", run.spec.command_line()))
+                        .monospace()
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if run.is_running() {
+                        if ui.button(tr("取消")).clicked() {
+                            run.cancel();
+                        }
+                    } else if ui.button(tr("关闭")).clicked() {
+                        close = true;
+                    }
+                });
+            });
+            egui::Frame::none()
+                .fill(egui::Color32::from_rgb(12, 12, 12))
+                .inner_margin(egui::Margin::same(6.0))
+                .show(ui, |ui| {
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
+                        .stick_to_bottom(true)
+                        .auto_shrink([false, true])
+                        .show(ui, |ui| {
+                            for line in &run.lines {
+                                let color = line.color.unwrap_or(if line.is_stderr {
+                                    egui::Color32::from_rgb(255, 120, 120)
+                                } else {
+                                    egui::Color32::from_rgb(200, 200, 200)
+                                });
+                                ui.label(egui::RichText::new(&line.text).monospace().small().color(color));
+                            }
+                        });
+                });
+        });
+        ui.add_space(10.0);
+
+        if close {
+            self.console = None;
+        }
+    }
+
+    fn render_process_table(
+        &mut self,
+        ui: &mut egui::Ui,
+        ctx: &egui::Context,
+        groups: &[ProcessGroup],
+        is_high: bool,
     ) {
         let scale = ctx.pixels_per_point();
         let rounding = ui::UiConstants::ROUNDING * scale;
         let text_color = egui::Color32::from_rgb(218, 165, 32);
 
-        let available_width = ui.available_width() - 40.0;
-        let name_col_width = (available_width - 320.0).max(150.0);
+        let cols = self.settings.process_columns;
+        // 固定列 (展开、内存、CPU、操作) 加上已显示的可选列，余下的宽度给名称列
+        let optional: f32 = [(cols.count, 28.0), (cols.arch, 50.0), (cols.disk_io, 80.0), (cols.gpu, 60.0), (cols.path, 200.0)]
+            .iter()
+            .filter(|(on, _)| *on)
+            .map(|(_, w)| w)
+            .sum();
+        let available_width = ui.available_width() - 40.0;
+        let name_col_width = (available_width - 356.0 - optional).max(150.0);
+
+        egui::Grid::new(format!("grid_{}", if is_high { "high" } else { "norm" }))
+            .num_columns(5 + [cols.arch, cols.disk_io, cols.gpu, cols.path].iter().filter(|on| **on).count())
+            .spacing([15.0, 10.0])
+            .striped(true)
+            .show(ui, |ui| {
+                // Headers：点击排序，再次点击切换方向
+                let sort = &mut self.process_sort;
+                if cols.count {
+                    sort_header(ui, sort, SortKey::Count, tr("数量"), 40.0, text_color);
+                } else {
+                    ui.add_sized([12.0, 20.0], egui::Label::new(""));
+                }
+                sort_header(ui, sort, SortKey::Name, tr("进程名称"), name_col_width, text_color);
+                if cols.arch {
+                    ui.add_sized(
+                        [50.0, 20.0],
+                        egui::Label::new(egui::RichText::new(tr("架构")).strong().color(text_color)),
+                    );
+                }
+                // 内存列可切换口径，按所选口径排序
+                let mut metric = self.settings.memory_metric;
+                ui.add_sized([90.0, 20.0], |ui: &mut egui::Ui| {
+                    ui.horizontal(|ui| {
+                        let arrow = match sort.arrow(SortKey::Memory) {
+                            "" => "⇅",
+                            a => a.trim(),
+                        };
+                        if ui
+                            .add(egui::Label::new(egui::RichText::new(arrow).strong().color(text_color)).sense(egui::Sense::click()))
+                            .on_hover_text(tr("按内存排序"))
+                            .clicked()
+                        {
+                            sort.click(SortKey::Memory);
+                        }
+                        egui::ComboBox::from_id_source(format!("mem_metric_{}", is_high))
+                            .width(72.0)
+                            .selected_text(egui::RichText::new(metric.label()).strong().color(text_color))
+                            .show_ui(ui, |ui| {
+                                for m in process_memory::Metric::ALL {
+                                    ui.selectable_value(&mut metric, m, m.label()).on_hover_text(m.hint());
+                                }
+                            })
+                            .response
+                            .on_hover_text(metric.hint());
+                    })
+                    .response
+                });
+                sort_header(ui, sort, SortKey::Cpu, tr("总CPU"), 124.0, text_color);
+                if cols.disk_io {
+                    sort_header(ui, sort, SortKey::DiskIo, tr("磁盘 I/O"), 80.0, text_color);
+                }
+                if cols.gpu {
+                    sort_header(ui, sort, SortKey::Gpu, "GPU", 60.0, text_color);
+                }
+                if cols.path {
+                    ui.add_sized(
+                        [200.0, 20.0],
+                        egui::Label::new(egui::RichText::new(tr("路径")).strong().color(text_color)),
+                    );
+                }
+                ui.add_sized([80.0, 20.0], |ui: &mut egui::Ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(tr("操作")).strong().color(text_color));
+                        ui.menu_button("☰", |ui| self.render_column_menu(ui)).response.on_hover_text(tr("显示 / 隐藏列"));
+                    })
+                    .response
+                });
+                ui.end_row();
+                if metric != self.settings.memory_metric {
+                    self.settings.memory_metric = metric;
+                    self.settings.save();
+                }
+
+                let mut sorted: Vec<&ProcessGroup> = groups.iter().collect();
+                let order = self.process_sort;
+                sorted.sort_by(|a, b| order.compare(a, b, metric));
+                for group in sorted {
+                    let hidden = self.settings.is_hidden(&group.name);
+                    if (hidden && !self.show_hidden) || (self.only_hung && !group.is_not_responding)
+                        || (self.only_other_users && !group.other_users)
+                        || !self.search.matches(group)
+                    {
+                        continue;
+                    }
+                    // 数量：点击展开进程明细 (单个 PID / 缓解策略)
+                    let expanded = self.expanded_groups.contains(&group.name);
+                    let arrow = if expanded { "▾" } else { "▸" };
+                    let res = if cols.count {
+                        ui.add_sized(
+                            [40.0, 20.0],
+                            egui::SelectableLabel::new(
+                                expanded,
+                                egui::RichText::new(format!("{}x{}", arrow, group.pids.len())).monospace(),
+                            ),
+                        )
+                    } else {
+                        ui.add_sized([12.0, 20.0], egui::SelectableLabel::new(expanded, arrow))
+                    };
+                    if res.on_hover_text(tr("展开查看每个进程")).clicked() {
+                        if expanded {
+                            self.expanded_groups.remove(&group.name);
+                        } else {
+                            self.expanded_groups.insert(group.name.clone());
+                        }
+                    }
+
+                    // Name
+                    ui.add_sized([name_col_width, 20.0], |ui: &mut egui::Ui| {
+                        ui.horizontal(|ui| {
+                            let name_color = if hidden {
+                                egui::Color32::DARK_GRAY
+                            } else if is_high {
+                                egui::Color32::from_rgb(255, 140, 0)
+                            } else {
+                                egui::Color32::from_rgb(200, 180, 150)
+                            };
+                            let display = if group.friendly_name.is_empty() {
+                                group.name.clone()
+                            } else {
+                                format!("{} ({})", group.friendly_name, group.name)
+                            };
+
+                            if !group.category.is_empty() {
+                                ui.label(
+                                    egui::RichText::new(format!("[{}]", process_db::category_label(&group.category)))
+                                        .color(egui::Color32::GRAY)
+                                        .small(),
+                                );
+                            }
+                            let selected = self.selected_groups.contains(&group.name);
+                            let mut text = egui::RichText::new(display).color(name_color).strong();
+                            if selected {
+                                text = text.background_color(egui::Color32::from_rgb(50, 60, 90));
+                            }
+                            let res = ui
+                                .add(egui::Label::new(text).truncate().sense(egui::Sense::click()))
+                                .on_hover_text(tr("点击选中，Ctrl+点击多选；Ctrl+C 复制为文本表格，Ctrl+Shift+C 复制为 Markdown"));
+                            res.context_menu(|ui| {
+                                let label = if hidden { tr("取消隐藏") } else { tr("隐藏此分组") };
+                                if ui.button(label).on_hover_text(tr("隐藏后仍计入列表总数")).clicked() {
+                                    self.settings.set_hidden(&group.name, !hidden);
+                                    self.settings.save();
+                                    ui.close_menu();
+                                }
+                                // 多进程程序 (浏览器等) 重启最早启动的主进程，子进程随之退出
+                                if let Some(main) = group.members.iter().min_by_key(|m| m.start_time) {
+                                    if ui
+                                        .button(tr("重启"))
+                                        .on_hover_text(trf("结束 PID {} 后按原命令行与工作目录重新启动", &[&main.pid]))
+                                        .clicked()
+                                    {
+                                        self.send_usb_cmd(UsbCmd::RestartProcess(main.pid));
+                                        ui.close_menu();
+                                    }
+                                }
+                            });
+                            if res.clicked() {
+                                if ui.input(|i| i.modifiers.command) {
+                                    if !self.selected_groups.remove(&group.name) {
+                                        self.selected_groups.insert(group.name.clone());
+                                    }
+                                } else if selected && self.selected_groups.len() == 1 {
+                                    self.selected_groups.clear();
+                                } else {
+                                    self.selected_groups = HashSet::from([group.name.clone()]);
+                                }
+                            }
+
+                            if group.is_system {
+                                ui.label(
+                                    egui::RichText::new("SYS")
+                                        .small()
+                                        .color(egui::Color32::BROWN),
+                                );
+                            }
+                            if group.is_not_responding {
+                                ui.label(
+                                    egui::RichText::new("DEAD")
+                                        .small()
+                                        .color(egui::Color32::RED),
+                                )
+                                .on_hover_text(tr("窗口无响应 (不再处理消息)"));
+                            }
+                            if group.other_users {
+                                ui.label(
+                                    egui::RichText::new(tr("他人"))
+                                        .small()
+                                        .color(egui::Color32::from_rgb(180, 120, 255)),
+                                )
+                                .on_hover_text(tr("包含其他已登录用户的进程，终止前请展开确认所属账户"));
+                            }
+                            if group.signature.as_ref().is_some_and(|s| s.is_suspicious(&group.exe_path)) {
+                                ui.label(
+                                    egui::RichText::new(tr("未签名·TEMP"))
+                                        .small()
+                                        .strong()
+                                        .color(egui::Color32::RED),
+                                )
+                                .on_hover_text(trf("未通过数字签名校验，且从临时目录运行：\n{}", &[&group.exe_path]));
+                            }
+                        })
+                        .response
+                    });
+
+                    if cols.arch {
+                        arch_cell(ui, group.arch);
+                    }
+
+                    // Mem
+                    ui.add_sized(
+                        [90.0, 20.0],
+                        egui::Label::new(format!(
+                            "{:.1} MB",
+                            group.usage.get(metric) as f32 / 1024.0 / 1024.0
+                        )),
+                    )
+                    .on_hover_text(group.usage.describe());
+
+                    // CPU
+                    let cpu_c = if group.total_cpu > 20.0 {
+                        egui::Color32::RED
+                    } else {
+                        egui::Color32::GOLD
+                    };
+                    ui.add_sized([124.0, 20.0], |ui: &mut egui::Ui| {
+                        ui.horizontal(|ui| {
+                            sparkline(ui, &group.history);
+                            ui.add_sized(
+                                [70.0, 20.0],
+                                egui::Label::new(
+                                    egui::RichText::new(format!("{:.1}%", group.total_cpu))
+                                        .color(cpu_c)
+                                        .monospace(),
+                                ),
+                            );
+                        })
+                        .response
+                    });
+                    optional_cells(ui, cols, group.disk_bps, group.gpu, &group.exe_path);
+
+                    // Action
+                    ui.add_sized([80.0, 24.0 * scale], |ui: &mut egui::Ui| {
+                        if gpu_guard::is_display_critical(&group.name) {
+                            let res = ui
+                                .add(
+                                    egui::Button::new(egui::RichText::new(tr("重启驱动")).color(egui::Color32::WHITE))
+                                        .fill(egui::Color32::from_rgb(40, 90, 160))
+                                        .rounding(rounding / 2.0),
+                                )
+                                .on_hover_text(tr("桌面合成 / 显卡驱动进程不可终止；画面卡死时重启显卡驱动 (Win+Ctrl+Shift+B)"));
+                            if res.clicked() {
+                                self.restart_graphics_driver();
+                            }
+                            return res;
+                        }
+                        let btn = egui::Button::new(
+                            egui::RichText::new(tr("终止")).color(egui::Color32::WHITE),
+                        )
+                        .fill(egui::Color32::from_rgb(180, 40, 40))
+                        .rounding(rounding / 2.0);
+                        let res = ui.add(btn);
+                        let res = if self.kill_tree {
+                            res.on_hover_text(tr("将连同其子进程一起终止"))
+                        } else {
+                            res
+                        };
+                        if res.clicked() {
+                            self.send_usb_cmd(UsbCmd::KillGroup(group.pids.clone(), self.kill_tree));
+                        }
+                        res
+                    });
+                    ui.end_row();
+
+                    if expanded {
+                        self.render_pid_rows(ui, group, name_col_width, cols);
+                        render_signature_row(ui, group);
+                        self.render_reputation_row(ui, ctx, group);
+                        self.render_override_row(ui, group);
+                        self.render_autostart_row(ui, group);
+                        self.render_uninstall_row(ui, group, ctx);
+                    }
+                }
+            });
+    }
+
+    /// 进程表的列选择菜单；GPU 列开关同时决定后台是否采样 GPU
+    fn render_column_menu(&mut self, ui: &mut egui::Ui) {
+        let before = self.settings.process_columns;
+        let cols = &mut self.settings.process_columns;
+        ui.checkbox(&mut cols.count, tr("进程数量"));
+        ui.checkbox(&mut cols.arch, tr("架构"));
+        ui.checkbox(&mut cols.disk_io, tr("磁盘 I/O")).on_hover_text(tr("各进程读写速率之和"));
+        ui.checkbox(&mut cols.gpu, "GPU").on_hover_text(tr("GPU 引擎占用 (与任务管理器口径一致)，显示此列时才采样"));
+        ui.checkbox(&mut cols.path, tr("路径"));
+        let after = *cols;
+        if after.gpu != before.gpu {
+            self.apply_tuning();
+        } else if after != before {
+            self.settings.save();
+        }
+    }
+
+    /// 选中分组后浮在窗口底部的批量操作栏：终止 / 挂起 / 调整优先级
+    fn render_batch_bar(&mut self, ctx: &egui::Context, snapshot: &AppSnapshot) {
+        if self.batch_kill_confirm.as_ref().is_some_and(|s| *s != self.selected_groups) {
+            self.batch_kill_confirm = None;
+        }
+        if self.selected_groups.is_empty() {
+            return;
+        }
+        let mut seen = HashSet::new();
+        let selected: Vec<&ProcessGroup> = snapshot
+            .high_resource
+            .iter()
+            .chain(&snapshot.other_groups)
+            .chain(&snapshot.system_groups)
+            .filter(|g| self.selected_groups.contains(&g.name) && seen.insert(g.name.as_str()))
+            .collect();
+        let pid_count: usize = selected.iter().map(|g| g.pids.len()).sum();
+        let locked = guest_mode::is_active();
+        let mut kill = false;
+        let mut suspend = false;
+        let mut priority: Option<process_priority::Priority> = None;
+        let mut clear = false;
+        egui::Area::new(egui::Id::new("batch_bar"))
+            .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -16.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(trf("已选 {} 个分组 · {} 个进程", &[&selected.len(), &pid_count])).strong(),
+                        );
+                        ui.separator();
+                        let confirming = self.batch_kill_confirm.is_some();
+                        let text = if confirming { tr("确认终止？") } else { tr("终止全部") };
+                        let mut button = egui::Button::new(text);
+                        if confirming {
+                            button = button.fill(egui::Color32::from_rgb(180, 40, 40));
+                        }
+                        if ui
+                            .add_enabled(!locked && pid_count > 0, button)
+                            .on_hover_text(tr("终止选中分组的全部进程；勾选了“连同子进程”时自底向上结束进程树"))
+                            .clicked()
+                        {
+                            if confirming {
+                                kill = true;
+                            } else {
+                                self.batch_kill_confirm = Some(self.selected_groups.clone());
+                            }
+                        }
+                        if ui
+                            .add_enabled(!locked && pid_count > 0, egui::Button::new(tr("挂起")))
+                            .on_hover_text(tr("暂停选中分组的全部进程，可在上方的挂起列表中逐个恢复；系统进程不会挂起"))
+                            .clicked()
+                        {
+                            suspend = true;
+                        }
+                        ui.add_enabled_ui(!locked && pid_count > 0, |ui| {
+                            ui.menu_button(tr("优先级"), |ui| {
+                                for p in process_priority::Priority::ALL {
+                                    if ui.button(p.label()).clicked() {
+                                        priority = Some(p);
+                                        ui.close_menu();
+                                    }
+                                }
+                            });
+                        });
+                        if ui.button(tr("清除选择")).clicked() {
+                            clear = true;
+                        }
+                    });
+                });
+            });
+
+        if clear {
+            self.selected_groups.clear();
+            self.batch_kill_confirm = None;
+            return;
+        }
+        if !(kill || suspend || priority.is_some()) || self.guest_blocked() {
+            return;
+        }
+        let own_pid = std::process::id();
+        let targets: Vec<(u32, String, bool)> = selected
+            .iter()
+            .flat_map(|g| g.pids.iter().map(|&pid| (pid, g.name.clone(), g.is_system)))
+            .filter(|(pid, _, _)| *pid != own_pid)
+            .collect();
+        if kill {
+            self.batch_kill_confirm = None;
+            self.selected_groups.clear();
+            let pids = targets.into_iter().map(|(pid, _, _)| pid).collect();
+            self.send_usb_cmd(UsbCmd::KillGroup(pids, self.kill_tree));
+            return;
+        }
+        let mut failed = 0;
+        let mut skipped = 0;
+        let mut done = 0;
+        for (pid, name, is_system) in targets {
+            // 挂起与调整优先级都不碰系统进程和桌面合成 / 显卡驱动进程
+            if is_system || gpu_guard::is_display_critical(&name) {
+                skipped += 1;
+                continue;
+            }
+            let result = match priority {
+                Some(p) => process_priority::set(pid, &name, p),
+                None => window_picker::suspend(pid, &name).map(|()| self.suspended.push((pid, name))),
+            };
+            match result {
+                Ok(()) => done += 1,
+                Err(_) => failed += 1,
+            }
+        }
+        let icon = if failed == 0 { "✅" } else { "⚠" };
+        let mut text = match priority {
+            Some(p) => trf("{} 已将 {} 个进程的优先级设为“{}”", &[&icon, &done, &p.label()]),
+            None => trf("{} 已挂起 {} 个进程", &[&icon, &done]),
+        };
+        if failed > 0 {
+            text.push_str(&trf("，{} 个失败 (可能需要管理员权限)", &[&failed]));
+        }
+        if skipped > 0 {
+            text.push_str(&trf("，跳过 {} 个系统进程", &[&skipped]));
+        }
+        self.toasts.push((Instant::now(), text));
+    }
+
+    /// Ctrl+C 复制选中分组为纯文本表格，Ctrl+Shift+C 复制为 Markdown
+    fn copy_selected_rows(&mut self, ctx: &egui::Context, snapshot: &AppSnapshot) {
+        if self.selected_groups.is_empty() || ctx.wants_keyboard_input() {
+            return;
+        }
+        // Windows 上 Ctrl+C 由 egui-winit 转成 Event::Copy，不再产生按键事件
+        let (copy, markdown) = ctx.input(|i| {
+            (i.events.iter().any(|e| matches!(e, egui::Event::Copy)), i.modifiers.shift)
+        });
+        if !copy {
+            return;
+        }
+        let mut seen = HashSet::new();
+        let mut selected: Vec<&ProcessGroup> = snapshot
+            .high_resource
+            .iter()
+            .chain(&snapshot.other_groups)
+            .chain(&snapshot.system_groups)
+            .filter(|g| self.selected_groups.contains(&g.name) && seen.insert(g.name.as_str()))
+            .collect();
+        // 与表格显示的顺序一致
+        selected.sort_by(|a, b| self.process_sort.compare(a, b, self.settings.memory_metric));
+        let rows: Vec<Vec<String>> = selected
+            .into_iter()
+            .map(|g| {
+                let name = if g.friendly_name.is_empty() {
+                    g.name.clone()
+                } else {
+                    format!("{} ({})", g.friendly_name, g.name)
+                };
+                vec![
+                    name,
+                    g.pids.len().to_string(),
+                    format!("{:.1} MB", g.total_memory as f32 / 1024.0 / 1024.0),
+                    format!("{:.1}%", g.total_cpu),
+                    g.exe_path.clone(),
+                ]
+            })
+            .collect();
+        if rows.is_empty() {
+            return;
+        }
+        let headers = [tr("进程名称"), tr("数量"), tr("内存"), "CPU", tr("路径")];
+        let numeric = [false, true, true, true, false];
+        let (text, kind) = if markdown {
+            (table_export::markdown(&headers, &numeric, &rows), "Markdown")
+        } else {
+            (table_export::plain(&headers, &numeric, &rows), tr("文本表格"))
+        };
+        ctx.output_mut(|o| o.copied_text = text);
+        self.toasts.push((Instant::now(), trf("📋 已复制 {} 行 ({})", &[&rows.len(), &kind])));
+    }
+
+    /// 最近启动的进程 (前 50 个)
+    fn render_recent_table(&mut self, ui: &mut egui::Ui, snapshot: &AppSnapshot) {
+        let mut recent: Vec<(&PidDetail, &ProcessGroup)> = snapshot
+            .high_resource
+            .iter()
+            .chain(&snapshot.other_groups)
+            .chain(&snapshot.system_groups)
+            .flat_map(|g| g.members.iter().map(move |m| (m, g)))
+            .collect();
+        recent.sort_by_key(|(m, _)| std::cmp::Reverse(m.start_time));
+
+        let text_color = egui::Color32::from_rgb(218, 165, 32);
+        let mut kill: Option<u32> = None;
+        egui::Grid::new("recent_grid")
+            .num_columns(6)
+            .spacing([15.0, 6.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for h in [tr("启动于"), tr("进程名称"), "PID", tr("内存"), "CPU", tr("操作")] {
+                    ui.label(egui::RichText::new(h).strong().color(text_color));
+                }
+                ui.end_row();
+
+                for (m, g) in recent.into_iter().take(50) {
+                    ui.label(egui::RichText::new(format_relative(m.start_time)).monospace())
+                        .on_hover_text(format_unix_local(m.start_time));
+                    let display = if g.friendly_name.is_empty() {
+                        g.name.clone()
+                    } else {
+                        format!("{} ({})", g.friendly_name, g.name)
+                    };
+                    ui.label(egui::RichText::new(display).color(egui::Color32::from_rgb(200, 180, 150)))
+                        .on_hover_text(&m.cmd);
+                    ui.label(egui::RichText::new(m.pid.to_string()).monospace().small());
+                    ui.label(format!("{:.1} MB", m.memory as f32 / 1024.0 / 1024.0));
+                    let cpu_c = if m.cpu > 20.0 {
+                        egui::Color32::RED
+                    } else {
+                        egui::Color32::GOLD
+                    };
+                    ui.label(egui::RichText::new(format!("{:.1}%", m.cpu)).color(cpu_c).monospace());
+                    if ui.small_button(tr("终止")).clicked() {
+                        kill = Some(m.pid);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(pid) = kill {
+            self.send_usb_cmd(UsbCmd::KillGroup(vec![pid], self.kill_tree));
+        }
+    }
+
+    /// 网络连接面板：端点 -> 进程，可按端口过滤
+    /// 计费网络断网：开关、当前状态与程序名单
+    fn render_metered(&mut self, ui: &mut egui::Ui) {
+        let status = self.metered_status.lock().map(|s| s.clone()).unwrap_or_default();
+        let mut changed = false;
+        let locked = guest_mode::is_active();
+        ui.horizontal(|ui| {
+            changed |= ui
+                .add_enabled(
+                    !locked,
+                    egui::Checkbox::new(&mut self.settings.metered.enabled, tr("计费网络时阻止下列程序联网")),
+                )
+                .on_hover_text(tr("连接按流量计费 (手机热点等) 时用防火墙阻止名单内程序的出站连接，恢复后自动解除；需要管理员权限"))
+                .changed();
+            let (text, color) = match status.metered {
+                Some(true) => (tr("● 计费连接"), egui::Color32::GOLD),
+                Some(false) => (tr("● 非计费连接"), egui::Color32::GRAY),
+                None => (tr("● 计费状态未知"), egui::Color32::DARK_GRAY),
+            };
+            ui.label(egui::RichText::new(text).small().color(color));
+            if !status.blocked.is_empty() {
+                ui.label(
+                    egui::RichText::new(trf("已阻止 {} 个程序", &[&status.blocked.len()]))
+                        .small()
+                        .color(egui::Color32::GOLD),
+                )
+                .on_hover_text(status.blocked.join("\n"));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.metered_programs)
+                    .hint_text(tr("进程名，逗号分隔，支持 * 通配"))
+                    .desired_width(320.0),
+            );
+            let programs: Vec<String> = self
+                .metered_programs
+                .split([',', '，'])
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty())
+                .collect();
+            let dirty = programs != self.settings.metered.programs;
+            if ui.add_enabled(dirty && !locked, egui::Button::new(tr("保存名单"))).clicked() {
+                self.settings.metered.programs = programs;
+                changed = true;
+            }
+        });
+        if let Some(e) = &status.error {
+            ui.label(egui::RichText::new(format!("⚠ {}", e)).small().color(egui::Color32::YELLOW));
+        }
+        if changed {
+            if let Ok(mut config) = self.metered_config.write() {
+                *config = self.settings.metered.clone();
+            }
+            self.settings.save();
+        }
+    }
+
+    /// 局域网主机：网络唤醒与远程关机 / 重启 (关机与重启需二次确认)
+    fn render_lan_machines(&mut self, ui: &mut egui::Ui) {
+        if let Some(rx) = &self.lan_rx {
+            match rx.try_recv() {
+                Ok(text) => {
+                    self.toasts.push((Instant::now(), text));
+                    self.lan_rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.lan_rx = None,
+            }
+        }
+        let locked = guest_mode::is_active();
+        let mut run: Option<(usize, lan_power::PowerAction)> = None;
+        let mut remove: Option<usize> = None;
+        let mut changed = false;
+        egui::CollapsingHeader::new(
+            egui::RichText::new(trf("🖧 局域网主机 ({})", &[&self.settings.lan_machines.len()])).small(),
+        )
+        .id_source("lan_machines")
+        .show(ui, |ui| {
+            let busy = self.lan_rx.is_some();
+            egui::Grid::new("lan_grid").striped(true).spacing([10.0, 4.0]).show(ui, |ui| {
+                for (i, m) in self.settings.lan_machines.iter().enumerate() {
+                    ui.label(egui::RichText::new(&m.name).strong());
+                    ui.label(egui::RichText::new(&m.host).small().monospace().color(egui::Color32::GRAY));
+                    ui.label(egui::RichText::new(&m.mac).small().monospace().color(egui::Color32::GRAY));
+                    if ui
+                        .add_enabled(!m.mac.is_empty(), egui::Button::new(tr("⏻ 唤醒")).small())
+                        .on_hover_text(tr("发送网络唤醒魔术包 (需与目标机在同一网段，且目标机已开启网络唤醒)"))
+                        .clicked()
+                    {
+                        let text = match lan_power::wake(m) {
+                            Ok(()) => trf("⏻ 已向 {} 发送唤醒包", &[&m.name]),
+                            Err(e) => format!("❌ {}", e),
+                        };
+                        self.toasts.push((Instant::now(), text));
+                    }
+                    let remote_ok = !m.host.is_empty() && !busy && !locked;
+                    for action in [lan_power::PowerAction::Shutdown, lan_power::PowerAction::Restart] {
+                        let confirming = self.lan_confirm == Some((i, action));
+                        let text = if confirming { trf("确认{}？", &[&action.label()]) } else { action.label().to_string() };
+                        let mut button = egui::Button::new(text).small();
+                        if confirming {
+                            button = button.fill(egui::Color32::from_rgb(180, 40, 40));
+                        }
+                        if ui
+                            .add_enabled(remote_ok, button)
+                            .on_hover_text(trf(
+                                "shutdown /m \\\\{}：{} 秒后{}，期间可取消；需要对方的管理员权限",
+                                &[&m.host, &lan_power::DELAY_SECS, &action.label()],
+                            ))
+                            .clicked()
+                        {
+                            if confirming {
+                                run = Some((i, action));
+                            } else {
+                                self.lan_confirm = Some((i, action));
+                            }
+                        }
+                    }
+                    if ui.add_enabled(remote_ok, egui::Button::new(tr("取消关机")).small()).clicked() {
+                        run = Some((i, lan_power::PowerAction::Cancel));
+                    }
+                    if ui.add_enabled(!locked, egui::Button::new("🗑").small()).on_hover_text(tr("从列表移除")).clicked() {
+                        remove = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+            if busy {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(egui::RichText::new(tr("正在执行远程命令…")).small().color(egui::Color32::GRAY));
+                });
+            }
+            ui.add_enabled_ui(!locked, |ui| {
+                ui.horizontal(|ui| {
+                    let d = &mut self.lan_draft;
+                    ui.add(egui::TextEdit::singleline(&mut d.name).hint_text(tr("名称")).desired_width(80.0));
+                    ui.add(egui::TextEdit::singleline(&mut d.host).hint_text(tr("主机名或 IP")).desired_width(110.0));
+                    ui.add(egui::TextEdit::singleline(&mut d.mac).hint_text(tr("MAC (唤醒用，可留空)")).desired_width(140.0));
+                    let mac_error = (!d.mac.trim().is_empty()).then(|| lan_power::parse_mac(&d.mac).err()).flatten();
+                    let valid = !d.name.trim().is_empty() && (!d.host.trim().is_empty() || !d.mac.trim().is_empty());
+                    let add = ui.add_enabled(valid && mac_error.is_none(), egui::Button::new(tr("添加")));
+                    if let Some(e) = &mac_error {
+                        ui.colored_label(egui::Color32::YELLOW, "⚠").on_hover_text(e);
+                    }
+                    if add.clicked() {
+                        let mut machine = std::mem::take(d);
+                        machine.name = machine.name.trim().to_string();
+                        machine.host = machine.host.trim().to_string();
+                        machine.mac = machine.mac.trim().to_uppercase();
+                        self.settings.lan_machines.push(machine);
+                        changed = true;
+                    }
+                });
+            });
+        });
+
+        if let Some(i) = remove {
+            self.settings.lan_machines.remove(i);
+            self.lan_confirm = None;
+            changed = true;
+        }
+        if changed {
+            self.settings.save();
+        }
+        if let Some((i, action)) = run {
+            self.lan_confirm = None;
+            if self.guest_blocked() {
+                return;
+            }
+            let Some(machine) = self.settings.lan_machines.get(i).cloned() else {
+                return;
+            };
+            let (tx, rx) = mpsc::channel();
+            let ctx = ui.ctx().clone();
+            std::thread::spawn(move || {
+                let text = match lan_power::remote_power(&machine, action) {
+                    Ok(()) if action == lan_power::PowerAction::Cancel => trf("✅ 已取消 {} 的关机", &[&machine.name]),
+                    Ok(()) => trf("✅ {} 将在 {} 秒后{}", &[&machine.name, &lan_power::DELAY_SECS, &action.label()]),
+                    Err(e) => format!("❌ {}", e),
+                };
+                let _ = tx.send(text);
+                ctx.request_repaint();
+            });
+            self.lan_rx = Some(rx);
+        }
+    }
+
+    fn render_network_panel(&mut self, ui: &mut egui::Ui, snapshot: &AppSnapshot) {
+        // 面板打开时每 2 秒刷新一次
+        if self.net_refreshed_at.map(|t| t.elapsed() > Duration::from_secs(2)).unwrap_or(true) {
+            self.net_conns = net_connections::list();
+            self.net_refreshed_at = Some(Instant::now());
+        }
+        let names: HashMap<u32, &str> = snapshot
+            .high_resource
+            .iter()
+            .chain(&snapshot.other_groups)
+            .chain(&snapshot.system_groups)
+            .flat_map(|g| g.pids.iter().map(move |&pid| (pid, g.name.as_str())))
+            .collect();
+        let port_filter: Option<u16> = self.net_port_filter.trim().parse().ok();
+        let shown: Vec<&net_connections::Connection> = self
+            .net_conns
+            .iter()
+            .filter(|c| port_filter.map(|p| c.matches_port(p)).unwrap_or(true))
+            .collect();
+
+        let mut kill: Option<u32> = None;
+        let mut close: Option<net_connections::Connection> = None;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(tr("🌐 网络连接")).strong().color(egui::Color32::GOLD));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.net_port_filter)
+                        .hint_text(tr("端口，如 8080"))
+                        .desired_width(100.0),
+                );
+                ui.label(
+                    egui::RichText::new(trf("{} / {} 条", &[&shown.len(), &self.net_conns.len()]))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+                if let Some(msg) = &self.net_msg {
+                    ui.label(egui::RichText::new(msg).small().color(egui::Color32::GRAY));
+                }
+                capture_button(ui, panel_capture::Panel::Network, &mut self.capture, &mut self.capture_save);
+            });
+            self.render_metered(ui);
+            self.render_lan_machines(ui);
+
+            egui::ScrollArea::vertical()
+                .id_source("net_scroll")
+                .max_height(260.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("net_grid").num_columns(6).spacing([12.0, 4.0]).striped(true).show(ui, |ui| {
+                        for c in shown.iter().take(500) {
+                            ui.label(egui::RichText::new(c.protocol.label()).monospace().small());
+                            ui.label(egui::RichText::new(format!("{}:{}", c.local, c.local_port)).monospace().small());
+                            ui.label(
+                                egui::RichText::new(
+                                    c.remote.map(|(ip, p)| format!("{}:{}", ip, p)).unwrap_or_default(),
+                                )
+                                .monospace()
+                                .small()
+                                .color(egui::Color32::GRAY),
+                            );
+                            let state_color = match c.state {
+                                2 => egui::Color32::from_rgb(0, 206, 209),
+                                5 => egui::Color32::GREEN,
+                                _ => egui::Color32::GRAY,
+                            };
+                            ui.label(egui::RichText::new(c.state_label()).small().color(state_color));
+                            let name = names.get(&c.pid).copied().unwrap_or("?");
+                            ui.label(egui::RichText::new(format!("{} ({})", name, c.pid)).color(egui::Color32::from_rgb(200, 180, 150)));
+                            ui.horizontal(|ui| {
+                                if c.pid > 4 && ui.small_button(tr("终止进程")).clicked() {
+                                    kill = Some(c.pid);
+                                }
+                                if c.can_close()
+                                    && ui
+                                        .add_enabled(self.is_admin, egui::Button::new(tr("断开")).small())
+                                        .on_disabled_hover_text(tr("需要以管理员身份运行"))
+                                        .clicked()
+                                {
+                                    close = Some((*c).clone());
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                });
+        });
+
+        if let Some(pid) = kill {
+            self.send_usb_cmd(UsbCmd::KillGroup(vec![pid], self.kill_tree));
+            self.net_refreshed_at = None;
+        }
+        if let Some(conn) = close {
+            self.net_msg = Some(match net_connections::close(&conn) {
+                Ok(()) => trf("已断开 {}:{}", &[&conn.local, &conn.local_port]),
+                Err(e) => format!("❌ {}", e),
+            });
+            self.net_refreshed_at = None;
+        }
+    }
+
+    /// VirusTotal API Key：用于进程展开行中的“查询”
+    fn render_vt_key(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("VirusTotal API Key:").small().color(egui::Color32::GRAY));
+            match &mut self.vt_key_draft {
+                Some(draft) => {
+                    ui.add(egui::TextEdit::singleline(draft).password(true).desired_width(220.0));
+                    if ui.small_button(tr("保存")).clicked() {
+                        self.settings.virustotal_api_key = draft.trim().to_string();
+                        self.settings.save();
+                        self.vt_key_draft = None;
+                    }
+                    if ui.small_button(tr("取消")).clicked() {
+                        self.vt_key_draft = None;
+                    }
+                }
+                None => {
+                    let configured = !self.settings.virustotal_api_key.is_empty();
+                    ui.label(
+                        egui::RichText::new(if configured { tr("已配置") } else { tr("未配置 (查询时改为打开网页)") })
+                            .small()
+                            .color(if configured { egui::Color32::from_rgb(120, 180, 120) } else { egui::Color32::GRAY }),
+                    );
+                    if ui
+                        .small_button(tr("✏ 修改"))
+                        .on_hover_text(tr("在 virustotal.com 注册后于个人资料页获取；免费 Key 每分钟可查询 4 次"))
+                        .clicked()
+                    {
+                        self.vt_key_draft = Some(self.settings.virustotal_api_key.clone());
+                    }
+                }
+            }
+        });
+    }
+
+    /// 安全检查面板：镂空 / 注入启发式扫描
+    fn render_security_panel(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, snapshot: &AppSnapshot) {
+        if let Some(rx) = &self.security_rx {
+            if let Ok(report) = rx.try_recv() {
+                self.security_report = Some(report);
+                self.security_rx = None;
+            }
+        }
+        let scanning = self.security_rx.is_some();
+        let mut kill: Option<u32> = None;
+
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(tr("🛡 安全检查")).strong().color(egui::Color32::GOLD));
+                if scanning {
+                    ui.spinner();
+                    ui.label(egui::RichText::new(tr("正在扫描进程内存...")).small().color(egui::Color32::GRAY));
+                } else if ui
+                    .button(tr("扫描进程"))
+                    .on_hover_text(tr("对比映像路径与 PEB、检查映像基址与私有 RWX 内存，识别进程镂空与代码注入"))
+                    .clicked()
+                {
+                    let processes: Vec<(u32, String)> = snapshot
+                        .high_resource
+                        .iter()
+                        .chain(&snapshot.other_groups)
+                        .chain(&snapshot.system_groups)
+                        .flat_map(|g| g.pids.iter().map(move |&pid| (pid, g.name.clone())))
+                        .collect();
+                    let (tx, rx) = mpsc::channel();
+                    let ctx = ctx.clone();
+                    std::thread::spawn(move || {
+                        let _ = tx.send(security_scan::scan(&processes));
+                        ctx.request_repaint();
+                    });
+                    self.security_rx = Some(rx);
+                }
+                capture_button(ui, panel_capture::Panel::Security, &mut self.capture, &mut self.capture_save);
+            });
+            self.render_vt_key(ui);
+            if !self.is_admin {
+                ui.label(
+                    egui::RichText::new(tr("非管理员模式下无法检查系统与其他用户的进程"))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+            }
+
+            let Some(report) = &self.security_report else {
+                return;
+            };
+            ui.label(
+                egui::RichText::new(trf(
+                    "已检查 {} 个进程，{} 个无权访问，发现 {} 项",
+                    &[&report.scanned, &report.skipped, &report.findings.len()],
+                ))
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            if report.findings.is_empty() {
+                ui.label(egui::RichText::new(tr("✨ 未发现可疑进程")).color(egui::Color32::GREEN));
+                return;
+            }
+            egui::Grid::new("security_grid").num_columns(3).spacing([10.0, 4.0]).striped(true).show(ui, |ui| {
+                for f in &report.findings {
+                    let color = if f.is_high() {
+                        egui::Color32::from_rgb(255, 80, 80)
+                    } else {
+                        egui::Color32::GRAY
+                    };
+                    ui.label(egui::RichText::new(format!("{} ({})", f.name, f.pid)).strong())
+                        .on_hover_text(&f.image_path);
+                    ui.label(egui::RichText::new(f.describe()).small().color(color));
+                    if ui.small_button(tr("终止")).clicked() {
+                        kill = Some(f.pid);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+        if let Some(pid) = kill {
+            self.send_usb_cmd(UsbCmd::KillGroup(vec![pid], self.kill_tree));
+        }
+    }
+
+    /// 启动项面板：按启动影响分组，支持启用 / 禁用
+    fn render_startup_panel(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, snapshot: &AppSnapshot) {
+        use startup_items::{Impact, StartupSource};
+
+        self.poll_startup_items(ctx);
+        let items = self.startup_items.clone().unwrap_or_default();
+
+        // 按可执行文件名匹配运行中的进程，估算启动影响
+        let usage: HashMap<String, (u64, f32)> = snapshot
+            .high_resource
+            .iter()
+            .chain(&snapshot.other_groups)
+            .chain(&snapshot.system_groups)
+            .map(|g| (g.name.to_lowercase(), (g.total_memory, g.total_cpu)))
+            .collect();
+        let impact_of = |item: &startup_items::StartupItem| {
+            item.exe_name()
+                .and_then(|exe| usage.get(&exe).copied())
+                .map(|(mem, cpu)| (Impact::estimate(mem, cpu), mem))
+                .unwrap_or((Impact::NotRunning, 0))
+        };
+
+        let mut toggled: Option<(usize, bool)> = None;
+        let mut scrolled = false;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(tr("🚀 启动项")).strong().color(egui::Color32::GOLD));
+                ui.label(
+                    egui::RichText::new(trf(
+                        "{} 项，已启用 {}",
+                        &[&items.len(), &items.iter().filter(|i| i.enabled).count()],
+                    ))
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+                if self.startup_rx.is_some() {
+                    ui.spinner();
+                }
+                if ui.small_button(tr("刷新")).clicked() {
+                    self.startup_dirty = true;
+                }
+                if let Some(msg) = &self.startup_msg {
+                    ui.label(egui::RichText::new(msg).small().color(egui::Color32::GRAY));
+                }
+                capture_button(ui, panel_capture::Panel::Startup, &mut self.capture, &mut self.capture_save);
+            });
+
+            for impact in Impact::ALL {
+                let group: Vec<(usize, u64)> = items
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, item)| {
+                        let (imp, mem) = impact_of(item);
+                        (imp == impact).then_some((i, mem))
+                    })
+                    .collect();
+                if group.is_empty() {
+                    continue;
+                }
+                let color = match impact {
+                    Impact::High => egui::Color32::from_rgb(255, 80, 80),
+                    Impact::Medium => egui::Color32::GOLD,
+                    Impact::Low => egui::Color32::GREEN,
+                    Impact::NotRunning => egui::Color32::GRAY,
+                };
+                let has_target = self.startup_scroll_pending
+                    && group.iter().any(|(i, _)| {
+                        let item = &items[*i];
+                        self.startup_highlight.as_ref() == Some(&(item.source, item.name.clone()))
+                    });
+                egui::CollapsingHeader::new(
+                    egui::RichText::new(format!("{} ({})", impact.label(), group.len())).color(color),
+                )
+                .id_source(("startup_impact", impact as u8))
+                .default_open(impact == Impact::High)
+                .open(has_target.then_some(true))
+                .show(ui, |ui| {
+                    egui::Grid::new(("startup_grid", impact as u8))
+                        .num_columns(4)
+                        .spacing([10.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (i, mem) in group {
+                                let item = &items[i];
+                                let editable = self.is_admin || !item.source.needs_admin();
+                                let mut enabled = item.enabled;
+                                let res = ui
+                                    .add_enabled(editable, egui::Checkbox::new(&mut enabled, ""))
+                                    .on_disabled_hover_text(tr("需要以管理员身份运行"));
+                                if res.changed() {
+                                    toggled = Some((i, enabled));
+                                }
+                                let highlighted =
+                                    self.startup_highlight.as_ref() == Some(&(item.source, item.name.clone()));
+                                let name_color = if highlighted {
+                                    egui::Color32::GOLD
+                                } else if item.enabled {
+                                    egui::Color32::from_rgb(200, 180, 150)
+                                } else {
+                                    egui::Color32::DARK_GRAY
+                                };
+                                let res = ui.label(egui::RichText::new(&item.name).color(name_color).strong());
+                                if highlighted && self.startup_scroll_pending {
+                                    res.scroll_to_me(Some(egui::Align::Center));
+                                    scrolled = true;
+                                }
+                                ui.label(
+                                    egui::RichText::new(if mem > 0 {
+                                        format!("{} · {:.0} MB", item.source.label(), mem as f32 / 1024.0 / 1024.0)
+                                    } else {
+                                        item.source.label().to_string()
+                                    })
+                                    .small()
+                                    .color(egui::Color32::GRAY),
+                                );
+                                ui.add(
+                                    egui::Label::new(
+                                        egui::RichText::new(&item.command).small().color(egui::Color32::GRAY),
+                                    )
+                                    .truncate(),
+                                )
+                                .on_hover_text(&item.command);
+                                ui.end_row();
+                            }
+                        });
+                });
+            }
+        });
+
+        if scrolled {
+            self.startup_scroll_pending = false;
+        }
+        if toggled.is_some() && self.guest_blocked() {
+            return;
+        }
+        if let Some((i, enabled)) = toggled {
+            let item = &items[i];
+            if item.source == StartupSource::ScheduledTask {
+                self.start_tool(startup_items::task_toggle_spec(item, enabled), ctx);
+                self.startup_dirty = true;
+            } else {
+                self.startup_msg = Some(match startup_items::set_enabled(item, enabled) {
+                    Ok(()) if enabled => trf("已启用 {}", &[&item.name]),
+                    Ok(()) => trf("已禁用 {}", &[&item.name]),
+                    Err(e) => format!("❌ {}", e),
+                });
+                self.startup_dirty = true;
+            }
+        }
+    }
+
+    /// 展开的分组：开机自启来源 (Run 键 / 启动文件夹 / 计划任务 / 服务)
+    fn render_autostart_row(&mut self, ui: &mut egui::Ui, group: &ProcessGroup) {
+        use startup_items::Origin;
+
+        self.poll_startup_items(ui.ctx());
+        if let Some(rx) = &self.autostart_services_rx {
+            if let Ok(services) = rx.try_recv() {
+                self.autostart_services = Some((Instant::now(), services));
+                self.autostart_services_rx = None;
+            }
+        }
+        // 服务多的系统上枚举 + 逐个查询配置要上百毫秒，放到后台线程
+        let stale = self
+            .autostart_services
+            .as_ref()
+            .map(|(t, _)| t.elapsed() > Duration::from_secs(30))
+            .unwrap_or(true);
+        if stale && self.autostart_services_rx.is_none() {
+            let (tx, rx) = mpsc::channel();
+            let ctx = ui.ctx().clone();
+            std::thread::spawn(move || {
+                let _ = tx.send(startup_items::auto_services());
+                ctx.request_repaint();
+            });
+            self.autostart_services_rx = Some(rx);
+        }
+        let items = self.startup_items.as_deref().unwrap_or_default();
+        let services = self.autostart_services.as_ref().map(|(_, s)| s.as_slice()).unwrap_or_default();
+        let origins = startup_items::origins_for(&group.name, &group.pids, items, services);
+
+        let mut jump: Option<(startup_items::StartupSource, String)> = None;
+        ui.label(egui::RichText::new("└").color(egui::Color32::DARK_GRAY).monospace());
+        ui.horizontal_wrapped(|ui| {
+            ui.label(egui::RichText::new(tr("开机自启来源:")).small().color(egui::Color32::from_rgb(150, 140, 120)));
+            if origins.is_empty() {
+                ui.label(egui::RichText::new(tr("无")).small().color(egui::Color32::GRAY));
+            }
+            for origin in &origins {
+                match origin {
+                    Origin::Item(i) => {
+                        let item = &items[*i];
+                        ui.label(
+                            egui::RichText::new(format!("{} · {}", item.source.label(), item.name))
+                                .small()
+                                .color(egui::Color32::GOLD),
+                        )
+                        .on_hover_text(&item.command);
+                        if !item.enabled {
+                            ui.label(egui::RichText::new(tr("(已禁用)")).small().color(egui::Color32::GRAY));
+                        } else if ui.small_button(tr("去禁用")).clicked() {
+                            jump = Some((item.source, item.name.clone()));
+                        }
+                    }
+                    Origin::Service { name, display_name } => {
+                        ui.label(
+                            egui::RichText::new(trf("自动启动服务 · {}", &[&display_name]))
+                                .small()
+                                .color(egui::Color32::GOLD),
+                        )
+                        .on_hover_text(name);
+                        if ui.small_button(tr("服务管理器")).clicked() {
+                            let _ = std::process::Command::new("mmc").arg("services.msc").spawn();
+                        }
+                    }
+                }
+            }
+        });
+        ui.end_row();
+
+        if let Some(target) = jump {
+            self.show_startup = true;
+            self.startup_highlight = Some(target);
+            self.startup_scroll_pending = true;
+        }
+    }
+
+    /// 展开行中的 SHA-256 与信誉查询结果
+    fn render_reputation_row(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, group: &ProcessGroup) {
+        if group.exe_path.is_empty() {
+            return;
+        }
+        let dim = egui::Color32::from_rgb(150, 140, 120);
+        let hash = self.exe_hashes.get(&group.exe_path);
+        ui.label(egui::RichText::new("└").color(egui::Color32::DARK_GRAY).monospace());
+        ui.horizontal_wrapped(|ui| {
+            ui.label(egui::RichText::new("SHA-256:").small().color(dim));
+            let sha256 = match hash {
+                None => {
+                    ui.label(egui::RichText::new(tr("计算中…")).small().color(egui::Color32::GRAY));
+                    return;
+                }
+                Some(Err(e)) => {
+                    ui.label(egui::RichText::new(trf("无法读取：{}", &[&e])).small().color(egui::Color32::GRAY));
+                    return;
+                }
+                Some(Ok(h)) => h,
+            };
+            ui.label(egui::RichText::new(format!("{}…", &sha256[..16])).small().monospace())
+                .on_hover_text(&sha256);
+            if ui.small_button(tr("复制")).clicked() {
+                ui.output_mut(|o| o.copied_text = sha256.clone());
+            }
+            let api_key = self.settings.virustotal_api_key.trim().to_string();
+            let state = self.reputation.get(&sha256);
+            let pending = matches!(state, Some(reputation::LookupState::Pending));
+            let hint = if api_key.is_empty() {
+                tr("未配置 VirusTotal API Key：在浏览器中打开该哈希的 VirusTotal 页面 (Key 可在安全检查面板中设置)")
+            } else {
+                tr("用 VirusTotal API 查询该哈希的检出率 (只发送哈希，不上传文件)")
+            };
+            if ui.add_enabled(!pending, egui::Button::new(tr("查询")).small()).on_hover_text(hint).clicked() {
+                if api_key.is_empty() {
+                    unelevated::open_in_explorer(&reputation::web_url(&sha256));
+                } else {
+                    self.reputation.start(api_key, sha256.clone(), group.exe_path.clone(), ctx.clone());
+                }
+            }
+            match state {
+                Some(reputation::LookupState::Pending) => {
+                    ui.spinner();
+                }
+                Some(reputation::LookupState::Done(Ok(verdict))) => {
+                    let color = if verdict.is_flagged() { egui::Color32::RED } else { egui::Color32::from_rgb(120, 180, 120) };
+                    let text = egui::RichText::new(verdict.describe()).small().strong().color(color);
+                    let res = ui.add(egui::Label::new(text).sense(egui::Sense::click()));
+                    if res.on_hover_text(tr("点击在浏览器中查看详情")).clicked() {
+                        unelevated::open_in_explorer(&reputation::web_url(&sha256));
+                    }
+                }
+                Some(reputation::LookupState::Done(Err(e))) => {
+                    ui.label(egui::RichText::new(e).small().color(egui::Color32::GOLD));
+                }
+                None => {}
+            }
+        });
+        ui.end_row();
+    }
+
+    /// 展开的分组下：手动指定显示名与分类，按可执行文件路径保存
+    fn render_override_row(&mut self, ui: &mut egui::Ui, group: &ProcessGroup) {
+        if group.exe_path.is_empty() {
+            return;
+        }
+        let dim = egui::Color32::from_rgb(150, 140, 120);
+        let current = self.overrides.read().ok().and_then(|o| o.get(&group.exe_path).cloned());
+        let mut save: Option<process_overrides::Override> = None;
+        let mut cancel = false;
+        ui.label(egui::RichText::new("└").color(egui::Color32::DARK_GRAY).monospace());
+        ui.horizontal_wrapped(|ui| {
+            ui.label(egui::RichText::new(tr("分组覆盖:")).small().color(dim))
+                .on_hover_text(trf("按路径保存，优先于自动识别：\n{}", &[&group.exe_path]));
+            match &mut self.override_draft {
+                Some((path, draft)) if *path == group.exe_path => {
+                    ui.add(egui::TextEdit::singleline(&mut draft.friendly_name).hint_text(tr("显示名")).desired_width(110.0));
+                    ui.add(egui::TextEdit::singleline(&mut draft.category).hint_text(tr("分类")).desired_width(70.0));
+                    if ui.small_button(tr("保存")).clicked() {
+                        save = Some(draft.clone());
+                    }
+                    if ui.small_button(tr("取消")).clicked() {
+                        cancel = true;
+                    }
+                }
+                _ => {
+                    match &current {
+                        Some(o) => {
+                            let text = match (o.friendly_name.is_empty(), o.category.is_empty()) {
+                                (false, false) => {
+                                    format!("[{}] {}", process_db::category_label(&o.category), o.friendly_name)
+                                }
+                                (true, _) => format!("[{}]", process_db::category_label(&o.category)),
+                                (_, true) => o.friendly_name.clone(),
+                            };
+                            ui.label(egui::RichText::new(text).small().color(egui::Color32::GOLD));
+                        }
+                        None => {
+                            ui.label(egui::RichText::new(tr("自动识别")).small().color(egui::Color32::GRAY));
+                        }
+                    }
+                    if ui.small_button(tr("✏ 修改")).clicked() {
+                        let draft = current.clone().unwrap_or_else(|| process_overrides::Override {
+                            friendly_name: group.friendly_name.clone(),
+                            category: group.category.clone(),
+                        });
+                        self.override_draft = Some((group.exe_path.clone(), draft));
+                    }
+                    if current.is_some() && ui.small_button(tr("恢复自动")).clicked() {
+                        save = Some(process_overrides::Override::default());
+                    }
+                }
+            }
+        });
+        ui.end_row();
+
+        if cancel {
+            self.override_draft = None;
+        }
+        if let Some(o) = save {
+            if let Ok(mut store) = self.overrides.write() {
+                store.set(&group.exe_path, o);
+                store.save();
+            }
+            self.override_draft = None;
+            self.toasts.push((Instant::now(), trf("已更新 {} 的分组覆盖，下一轮刷新生效", &[&group.name])));
+        }
+    }
+
+    /// 展开的分组：对应的安装条目与“卸载该软件”
+    fn render_uninstall_row(&mut self, ui: &mut egui::Ui, group: &ProcessGroup, ctx: &egui::Context) {
+        if self
+            .installed_apps
+            .as_ref()
+            .map(|(t, _)| t.elapsed() > Duration::from_secs(60))
+            .unwrap_or(true)
+        {
+            self.installed_apps = Some((Instant::now(), uninstall::installed_apps()));
+        }
+        self.poll_winget(ctx);
+        let company = self
+            .company_cache
+            .entry(group.exe_path.clone())
+            .or_insert_with(|| get_exe_company_name(std::path::Path::new(&group.exe_path)))
+            .clone();
+        let apps = self.installed_apps.as_ref().map(|(_, a)| a.as_slice()).unwrap_or_default();
+        let Some(app) = uninstall::find_for_exe(&group.exe_path, company.as_deref(), apps) else {
+            return;
+        };
+
+        let mut request: Option<tool_runner::ToolSpec> = None;
+        let mut fetch_versions: Option<String> = None;
+        let busy = group.is_system || self.tool_running();
+        let package = match &self.winget_packages {
+            Some((_, Ok(packages))) => winget::find_for_app(app, packages),
+            _ => None,
+        };
+        ui.label(egui::RichText::new("└").color(egui::Color32::DARK_GRAY).monospace());
+        ui.horizontal_wrapped(|ui| {
+            ui.label(egui::RichText::new(tr("安装条目:")).small().color(egui::Color32::from_rgb(150, 140, 120)));
+            let text = if app.publisher.is_empty() {
+                app.display_name.clone()
+            } else {
+                format!("{} · {}", app.display_name, app.publisher)
+            };
+            ui.label(egui::RichText::new(text).small().color(egui::Color32::GOLD))
+                .on_hover_text(&app.uninstall_string);
+            if ui
+                .add_enabled(!group.is_system && !self.tool_running(), egui::Button::new(tr("卸载该软件")).small())
+                .on_hover_text(tr("先结束该程序的所有进程，再启动它的卸载程序"))
+                .clicked()
+            {
+                request = Some(uninstall::uninstall_spec(app));
+            }
+        });
+        ui.end_row();
+
+        if let Some(pkg) = package {
+            let dim = egui::Color32::from_rgb(150, 140, 120);
+            ui.label("");
+            ui.horizontal_wrapped(|ui| {
+                ui.label(egui::RichText::new("winget:").small().color(dim));
+                ui.label(egui::RichText::new(&pkg.id).small().monospace().color(egui::Color32::LIGHT_BLUE))
+                    .on_hover_text(trf("源：{}", &[&if pkg.source.is_empty() { tr("无 (本机安装)") } else { pkg.source.as_str() }]));
+                let version = if pkg.available.is_empty() {
+                    format!("v{}", pkg.version)
+                } else {
+                    format!("v{} → v{}", pkg.version, pkg.available)
+                };
+                ui.label(egui::RichText::new(version).small().color(if pkg.available.is_empty() {
+                    dim
+                } else {
+                    egui::Color32::GREEN
+                }));
+                if ui
+                    .add_enabled(!busy && pkg.can_upgrade(), egui::Button::new(tr("winget 升级")).small())
+                    .on_hover_text(tr("结束该程序的所有进程后，通过 winget 升级到最新版本"))
+                    .clicked()
+                {
+                    request = Some(winget::upgrade_spec(pkg));
+                }
+                if ui
+                    .add_enabled(!busy, egui::Button::new(tr("winget 卸载")).small())
+                    .on_hover_text(tr("结束该程序的所有进程后，通过 winget 卸载"))
+                    .clicked()
+                {
+                    request = Some(winget::uninstall_spec(pkg));
+                }
+                if !pkg.source.is_empty() {
+                    match self.winget_versions.get(&pkg.id) {
+                        Some(Ok(versions)) => {
+                            let shown: Vec<&str> = versions.iter().take(8).map(|v| v.as_str()).collect();
+                            let more = if versions.len() > shown.len() {
+                                trf(" 等 {} 个", &[&versions.len()])
+                            } else {
+                                String::new()
+                            };
+                            ui.label(
+                                egui::RichText::new(trf("可用版本：{}{}", &[&shown.join(", "), &more]))
+                                    .small()
+                                    .color(dim),
+                            );
+                        }
+                        Some(Err(e)) => {
+                            ui.label(egui::RichText::new(trf("版本查询失败：{}", &[&e])).small().color(egui::Color32::RED));
+                        }
+                        None if self.winget_versions_rx.is_some() => {
+                            ui.spinner();
+                        }
+                        None => {
+                            if ui.small_button(tr("可用版本")).clicked() {
+                                fetch_versions = Some(pkg.id.clone());
+                            }
+                        }
+                    }
+                }
+            });
+            ui.end_row();
+        }
+
+        if let Some(id) = fetch_versions {
+            let (tx, rx) = mpsc::channel();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                let versions = winget::versions(&id);
+                let _ = tx.send((id, versions));
+                ctx.request_repaint();
+            });
+            self.winget_versions_rx = Some(rx);
+        }
+
+        if request.is_some() && self.guest_blocked() {
+            return;
+        }
+        if let Some(spec) = request {
+            // 升级 / 卸载后软件列表已变化
+            self.winget_packages = None;
+            self.winget_versions.clear();
+            let pids = group.pids.clone();
+            if self.always_allow_external {
+                self.begin_uninstall(spec, pids, ctx);
+            } else {
+                self.pending_external = Some(ExternalAction::Uninstall(spec, pids));
+            }
+        }
+    }
+
+    /// 盘符对应设备的已保存偏好
+    fn prefs_for(&self, disk: &DiskData) -> Option<&device_prefs::DevicePrefs> {
+        disk.device_key.as_deref().and_then(|k| self.device_prefs.get(k))
+    }
+
+    /// 对比前后两次快照中的可移动盘，插入 / 拔出时弹出提示并应用设备偏好
+    fn track_removable_changes(&mut self, snapshot: &AppSnapshot, ctx: &egui::Context) {
+        let current: HashMap<String, String> = snapshot
+            .disks
+            .iter()
+            .filter(|d| d.is_removable)
+            .map(|d| {
+                let label = self.prefs_for(d).and_then(|p| p.label()).unwrap_or_else(|| d.name.clone());
+                (norm_drive(&d.mount_point), label)
+            })
+            .collect();
+        let first_run = self.known_removable.is_none();
+        let known = self.known_removable.take().unwrap_or_default();
+        for disk in snapshot.disks.iter().filter(|d| d.is_removable) {
+            let drive = norm_drive(&disk.mount_point);
+            if known.contains_key(&drive) {
+                continue;
+            }
+            let prefs = self.prefs_for(disk).cloned();
+            self.last_io.insert(drive.clone(), Instant::now());
+            // 启动时已插着的盘只下发弹出策略，不改盘符、不提示
+            let _ = self.usb_tx.send(UsbCmd::DevicePrefs(drive.clone(), prefs.clone()));
+            if first_run {
+                continue;
+            }
+            let label = match prefs.as_ref().and_then(|p| p.label()) {
+                Some(label) => label,
+                None if disk.name.is_empty() => tr("可移动磁盘").to_string(),
+                None => disk.name.clone(),
+            };
+            if self.scan_on_insert {
+                self.toasts.push((Instant::now(), trf("💾 已插入 {}: {} · 正在检查自动运行威胁…", &[&drive, &label])));
+                let tx = self.autorun_tx.clone();
+                let drive = drive.clone();
+                std::thread::spawn(move || {
+                    let threats = autorun_scan::scan_root(&drive);
+                    let _ = tx.send((drive, threats));
+                });
+            } else {
+                self.toasts.push((Instant::now(), trf("💾 已插入 {}: {}", &[&drive, &label])));
+            }
+            let mut letter_pending = false;
+            if let Some(letter) = prefs.as_ref().and_then(|p| p.preferred_letter) {
+                let taken_by_other = !mount_manager::free_letters().contains(&letter);
+                if drive != letter.to_string() && !taken_by_other {
+                    self.toasts.push((Instant::now(), trf("🔤 按设备偏好改为 {}:", &[&letter])));
+                    self.send_usb_cmd(UsbCmd::Mount(mount_manager::MountOp::ChangeLetter {
+                        drive: drive.clone(),
+                        letter,
+                    }));
+                    letter_pending = true;
+                }
+            }
+            // 正在改盘符时等它以新盘符重新出现后再备份
+            let job = prefs.and_then(|p| p.backup).filter(|j| j.is_configured());
+            if let (false, Some(job), Some(key)) = (letter_pending, job, disk.device_key.clone()) {
+                if self.backup_ran.insert(key) {
+                    self.start_backup(&drive, job, ctx);
+                }
+            }
+        }
+        let present_keys: HashSet<&String> = snapshot.disks.iter().filter_map(|d| d.device_key.as_ref()).collect();
+        self.backup_ran.retain(|k| present_keys.contains(k));
+        self.poll_backups(snapshot);
+        self.poll_verifies();
+        for (drive, label) in &known {
+            if !current.contains_key(drive) {
+                self.toasts.push((Instant::now(), trf("⏏ {}: {} 已移除", &[&drive, &label])));
+                self.last_io.remove(drive);
+                self.auto_ejected.remove(drive);
+                self.autorun_threats.remove(drive);
+                self.verify_reports.remove(drive);
+            }
+        }
+        while let Ok((drive, threats)) = self.autorun_rx.try_recv() {
+            if !current.contains_key(&drive) {
+                continue;
+            }
+            let text = match threats.first() {
+                None => trf("🛡 {}: 未发现自动运行威胁", &[&drive]),
+                Some(first) if threats.len() == 1 => trf("⚠ {}: {}，打开前请确认", &[&drive, &first.describe()]),
+                Some(first) => trf("⚠ {}: {} 等 {} 个可疑项，打开前请确认", &[&drive, &first.describe(), &threats.len()]),
+            };
+            self.toasts.push((Instant::now(), text));
+            if !threats.is_empty() {
+                audit::record(
+                    "插入检查",
+                    &format!("{}: {}", drive, threats.iter().map(|t| t.describe()).collect::<Vec<_>>().join("; ")),
+                );
+                self.autorun_threats.insert(drive, threats);
+            }
+        }
+        self.known_removable = Some(current);
+        self.auto_eject_idle(snapshot);
+    }
+
+    fn start_backup(&mut self, drive: &str, job: backup::BackupJob, ctx: &egui::Context) {
+        if self.backups.contains_key(drive) {
+            return;
+        }
+        self.toasts.push((
+            Instant::now(),
+            trf("🗄 {}: 开始备份 {} -> {}", &[&drive, &job.source, &job.destination(drive).display()]),
+        ));
+        let cancel = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        self.backups.insert(drive.to_string(), backup::BackupState::new(job.eject_after, cancel.clone()));
+        backup::spawn(drive.to_string(), job, self.backup_tx.clone(), cancel, ctx.clone());
+    }
+
+    /// 收取备份进度；完成后按设置自动弹出
+    fn poll_backups(&mut self, snapshot: &AppSnapshot) {
+        while let Ok((drive, event)) = self.backup_rx.try_recv() {
+            let Some(state) = self.backups.get_mut(&drive) else { continue };
+            match event {
+                backup::BackupEvent::Stage(stage) => state.stage = stage,
+                backup::BackupEvent::Progress { done_files, total_files, done_bytes, total_bytes } => {
+                    state.done_files = done_files;
+                    state.total_files = total_files;
+                    state.done_bytes = done_bytes;
+                    state.total_bytes = total_bytes;
+                }
+                backup::BackupEvent::Finished(result) => {
+                    let eject_after = state.eject_after;
+                    self.backups.remove(&drive);
+                    match result {
+                        Ok(summary) => {
+                            self.toasts.push((Instant::now(), trf("✅ {}: 备份完成，{}", &[&drive, &summary])));
+                            let disk = snapshot.disks.iter().find(|d| norm_drive(&d.mount_point) == drive);
+                            if let (true, Some(disk)) = (eject_after, disk) {
+                                self.toasts.push((Instant::now(), trf("⏏ {}: 备份完成，自动弹出", &[&drive])));
+                                let _ = self.usb_tx.send(UsbCmd::Scan(disk.mount_point.clone()));
+                            }
+                        }
+                        Err(e) => self.toasts.push((Instant::now(), trf("❌ {}: 备份失败，{}", &[&drive, &e]))),
+                    }
+                }
+            }
+        }
+    }
+
+    fn poll_verifies(&mut self) {
+        while let Ok((drive, event)) = self.verify_rx.try_recv() {
+            match event {
+                checksum::VerifyEvent::Progress { done_bytes, total_bytes } => {
+                    if let Some(state) = self.verifies.get_mut(&drive) {
+                        state.done_bytes = done_bytes;
+                        state.total_bytes = total_bytes;
+                    }
+                }
+                checksum::VerifyEvent::Finished(result) => {
+                    self.verifies.remove(&drive);
+                    let text = match &result {
+                        Ok(r) if r.is_ok() => trf("✅ {}: 校验通过，{}", &[&drive, &r.summary()]),
+                        Ok(r) => trf("❌ {}: 校验发现问题，{}", &[&drive, &r.summary()]),
+                        Err(e) => trf("❌ {}: 校验未完成，{}", &[&drive, &e]),
+                    };
+                    self.toasts.push((Instant::now(), text));
+                    self.verify_reports.insert(drive, result);
+                }
+            }
+        }
+    }
+
+    /// 拷贝校验：对比本机文件夹与盘上的副本 (BLAKE3)
+    fn render_verify(&mut self, ui: &mut egui::Ui, mount_point: &str) {
+        let drive = norm_drive(mount_point);
+        let running = self.verifies.contains_key(&drive);
+        let mut start = false;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.label(egui::RichText::new(tr("🔍 拷贝校验 (BLAKE3)")).small().strong());
+            egui::Grid::new(("verify", &drive)).num_columns(2).spacing([10.0, 4.0]).show(ui, |ui| {
+                ui.label(tr("本机文件夹"));
+                ui.add(egui::TextEdit::singleline(&mut self.verify_source).hint_text(tr("D:\\照片\\2024-05")).desired_width(220.0));
+                ui.end_row();
+                ui.label(tr("盘上文件夹"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.verify_dest)
+                        .hint_text(format!("{}2024-05", mount_manager::volume_root(&drive)))
+                        .desired_width(220.0),
+                );
+                ui.end_row();
+            });
+            ui.horizontal(|ui| {
+                let ready = !self.verify_source.trim().is_empty() && !self.verify_dest.trim().is_empty();
+                if ui
+                    .add_enabled(ready && !running, egui::Button::new(tr("开始校验")))
+                    .on_hover_text(tr("逐个读取两边的文件计算哈希，弹出前确认拷贝完整"))
+                    .clicked()
+                {
+                    start = true;
+                }
+                if let Some(state) = self.verifies.get(&drive) {
+                    ui.add(egui::ProgressBar::new(state.ratio()).desired_width(160.0).show_percentage());
+                    if ui.small_button(tr("取消")).clicked() {
+                        state.cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+                    }
+                }
+            });
+            match self.verify_reports.get(&drive) {
+                Some(Ok(report)) if report.is_ok() => {
+                    ui.label(egui::RichText::new(format!("✅ {}", report.summary())).small().color(egui::Color32::GREEN));
+                }
+                Some(Ok(report)) => {
+                    ui.label(
+                        egui::RichText::new(format!("❌ {}", report.summary()))
+                            .small()
+                            .color(egui::Color32::from_rgb(255, 80, 80)),
+                    );
+                    egui::ScrollArea::vertical().max_height(120.0).id_source(("verify_problems", &drive)).show(ui, |ui| {
+                        for problem in report.problems() {
+                            ui.label(egui::RichText::new(problem).small().monospace());
+                        }
+                    });
+                }
+                Some(Err(e)) => {
+                    ui.label(egui::RichText::new(format!("⚠ {}", e)).small().color(egui::Color32::GOLD));
+                }
+                None => {}
+            }
+        });
+        if start {
+            let cancel = Arc::new(std::sync::atomic::AtomicBool::new(false));
+            self.verifies.insert(
+                drive.clone(),
+                checksum::VerifyState { done_bytes: 0, total_bytes: 0, cancel: cancel.clone() },
+            );
+            self.verify_reports.remove(&drive);
+            checksum::spawn(
+                drive,
+                std::path::PathBuf::from(self.verify_source.trim()),
+                std::path::PathBuf::from(self.verify_dest.trim()),
+                self.verify_tx.clone(),
+                cancel,
+                ui.ctx().clone(),
+            );
+        }
+    }
+
+    /// 设备偏好中设置了空闲自动弹出：持续无读写达到时长后发起一次弹出
+    fn auto_eject_idle(&mut self, snapshot: &AppSnapshot) {
+        for disk in snapshot.disks.iter().filter(|d| d.is_removable) {
+            let drive = norm_drive(&disk.mount_point);
+            if disk.read_bps > 0 || disk.write_bps > 0 {
+                self.last_io.insert(drive.clone(), Instant::now());
+                continue;
+            }
+            let Some(mins) = self.prefs_for(disk).and_then(|p| p.auto_eject_idle_mins) else {
+                continue;
+            };
+            let idle = self.last_io.get(&drive).map(|t| t.elapsed()).unwrap_or_default();
+            if mins == 0
+                || idle < Duration::from_secs(mins * 60)
+                || self.usb_busy.contains(&drive)
+                || self.auto_ejected.contains(&drive)
+            {
+                continue;
+            }
+            self.auto_ejected.insert(drive.clone());
+            self.toasts.push((Instant::now(), trf("⏏ {}: 已空闲 {} 分钟，自动弹出", &[&drive, &mins])));
+            let _ = self.usb_tx.send(UsbCmd::Scan(disk.mount_point.clone()));
+        }
+    }
+
+    /// 右下角的短暂提示，4 秒后消失
+    fn render_toasts(&mut self, ctx: &egui::Context) {
+        const TOAST_TTL: Duration = Duration::from_secs(4);
+        self.toasts.retain(|(t, _)| t.elapsed() < TOAST_TTL);
+        // 截图期间不显示，免得盖住面板
+        if self.toasts.is_empty() || self.capture.is_some() {
+            return;
+        }
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -16.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for (_, text) in &self.toasts {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        // 跟随主题：浅色主题的弹出层是白底
+                        let color = ui.visuals().strong_text_color();
+                        ui.label(egui::RichText::new(text).color(color));
+                    });
+                }
+            });
+        ctx.request_repaint_after(Duration::from_millis(250));
+    }
+
+    /// 当前面板开关与视图，用于保存为启动配置
+    fn current_profile(&self, name: &str) -> settings::Profile {
+        settings::Profile {
+            name: name.to_string(),
+            view: self.view,
+            panels: settings::Panels {
+                performance: self.show_performance,
+                diagnostics: self.show_diagnostics,
+                usb_manager: self.show_usb_manager,
+                security: self.show_security,
+                startup: self.show_startup,
+                network: self.show_network,
+            },
+        }
+    }
+
+    /// 切换到某个配置：面板开关与视图，视图变化时同时调整窗口大小
+    fn apply_profile(&mut self, profile: &settings::Profile, ctx: &egui::Context) {
+        self.show_performance = profile.panels.performance;
+        self.show_diagnostics = profile.panels.diagnostics;
+        self.show_usb_manager = profile.panels.usb_manager;
+        self.show_security = profile.panels.security;
+        self.show_startup = profile.panels.startup;
+        self.show_network = profile.panels.network;
+        self.active_profile = profile.name.clone();
+        self.set_view(profile.view, ctx);
+    }
+
+    fn set_view(&mut self, view: settings::View, ctx: &egui::Context) {
+        if self.view == view {
+            return;
+        }
+        self.view = view;
+        let [w, h] = view.min_window_size();
+        ctx.send_viewport_cmd(egui::ViewportCommand::MinInnerSize(egui::vec2(w, h)));
+        let [w, h] = view.window_size();
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::vec2(w, h)));
+    }
+
+    /// 视图切换与启动配置：默认打开哪个配置，保存当前面板
+    fn render_view_menu(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let mut apply: Option<settings::Profile> = None;
+        let mut changed = false;
+        ui.menu_button(tr("🗔 视图"), |ui| {
+            for view in [settings::View::Full, settings::View::Usb] {
+                if ui.radio(self.view == view, view.label()).clicked() {
+                    self.set_view(view, ctx);
+                    ui.close_menu();
+                }
+            }
+            ui.separator();
+            if guest_mode::is_active() {
+                ui.label(egui::RichText::new(tr("🔒 访客模式中，设置已锁定")).small().color(egui::Color32::GOLD));
+                return;
+            }
+            ui.label(egui::RichText::new(tr("启动配置 (● 为默认)")).small().color(egui::Color32::GRAY))
+                .on_hover_text(tr("命令行可用 --profile 名称 选择配置，--view usb|full 覆盖视图"));
+            let mut remove: Option<usize> = None;
+            let count = self.settings.profiles.len();
+            for (i, p) in self.settings.profiles.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui
+                        .radio(self.settings.default_profile == p.name, "")
+                        .on_hover_text(tr("启动时默认使用此配置"))
+                        .clicked()
+                    {
+                        self.settings.default_profile = p.name.clone();
+                        changed = true;
+                    }
+                    let text = format!("{} · {}", p.name, p.view.label());
+                    let text = if p.name == self.active_profile {
+                        egui::RichText::new(text).strong()
+                    } else {
+                        egui::RichText::new(text)
+                    };
+                    if ui.button(text).on_hover_text(tr("切换到此配置")).clicked() {
+                        apply = Some(p.clone());
+                        ui.close_menu();
+                    }
+                    if count > 1 && ui.small_button("🗑").on_hover_text(tr("删除此配置")).clicked() {
+                        remove = Some(i);
+                    }
+                });
+            }
+            if let Some(i) = remove {
+                let removed = self.settings.profiles.remove(i);
+                if self.settings.default_profile == removed.name {
+                    self.settings.default_profile = self.settings.profiles[0].name.clone();
+                }
+                changed = true;
+            }
+            ui.separator();
+            if ui
+                .button(trf("保存当前面板到「{}」", &[&self.active_profile]))
+                .on_hover_text(tr("用当前视图与展开的面板覆盖此配置"))
+                .clicked()
+            {
+                let profile = self.current_profile(&self.active_profile);
+                self.settings.upsert(profile);
+                changed = true;
+            }
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.new_profile_name).hint_text(tr("新配置名")).desired_width(100.0));
+                let name = self.new_profile_name.trim().to_string();
+                if ui.add_enabled(!name.is_empty(), egui::Button::new(tr("另存为"))).clicked() {
+                    let profile = self.current_profile(&name);
+                    self.settings.upsert(profile);
+                    self.active_profile = name;
+                    self.new_profile_name.clear();
+                    changed = true;
+                }
+            });
+            ui.separator();
+            ui.label(egui::RichText::new(tr("全局快捷键")).small().color(egui::Color32::GRAY))
+                .on_hover_text(tr("窗口在后台或隐藏到托盘时也有效；格式如 Ctrl+Alt+K，留空表示不注册"));
+            let status = self.hotkeys.status.lock().map(|s| s.clone()).unwrap_or_default();
+            for b in self.hotkey_draft.iter_mut() {
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut b.keys).desired_width(90.0));
+                    ui.label(b.action.label());
+                    let parsed = hotkeys::parse(&b.keys);
+                    match status.iter().find(|(a, _)| *a == b.action).map(|(_, r)| r) {
+                        _ if b.keys.trim().is_empty() => {}
+                        _ if parsed.is_err() => {
+                            ui.colored_label(egui::Color32::YELLOW, "⚠")
+                                .on_hover_text(parsed.err().unwrap_or_default());
+                        }
+                        Some(Err(e)) => {
+                            ui.colored_label(egui::Color32::RED, "✖").on_hover_text(e);
+                        }
+                        Some(Ok(())) => {
+                            ui.colored_label(egui::Color32::GREEN, "✔").on_hover_text(tr("已注册"));
+                        }
+                        None => {}
+                    }
+                });
+            }
+            let dirty = self.hotkey_draft != self.settings.hotkeys;
+            ui.horizontal(|ui| {
+                if ui.add_enabled(dirty, egui::Button::new(tr("应用"))).clicked() {
+                    self.settings.hotkeys = self.hotkey_draft.clone();
+                    self.hotkeys.update(macros::with_hotkeys(&self.hotkey_draft, &self.settings.macros));
+                    changed = true;
+                }
+                if ui.button(tr("恢复默认")).clicked() {
+                    self.hotkey_draft = hotkeys::default_bindings();
+                }
+            });
+        });
+        if changed {
+            self.settings.save();
+        }
+        if let Some(p) = apply {
+            self.apply_profile(&p, ctx);
+        }
+    }
+
+    /// 刷新间隔或资源紧张阈值改动后同步给监控线程并保存
+    fn apply_tuning(&mut self) {
+        if let Ok(mut t) = self.tuning.write() {
+            *t = self.settings.tuning();
+        }
+        self.settings.save();
+    }
+
+    /// 设置窗口：刷新与资源紧张阈值、弹出方式、语言、启动行为
+    fn render_settings_window(&mut self, ctx: &egui::Context) {
+        if !self.show_settings {
+            return;
+        }
+        let mut open = true;
+        let locked = guest_mode::is_active();
+        egui::Window::new(tr("⚙ 设置"))
+            .id(egui::Id::new("settings_window"))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                if locked {
+                    ui.label(egui::RichText::new(tr("🔒 访客模式中，设置已锁定")).small().color(egui::Color32::GOLD));
+                }
+                ui.add_enabled_ui(!locked, |ui| {
+                    let mut tuning_changed = false;
+                    let mut changed = false;
+
+                    ui.heading(tr("刷新"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr("刷新间隔"));
+                        tuning_changed |= ui
+                            .add(
+                                egui::Slider::new(&mut self.settings.refresh_ms, settings::MIN_REFRESH_MS..=settings::MAX_REFRESH_MS)
+                                    .suffix(" ms")
+                                    .logarithmic(true),
+                            )
+                            .on_hover_text(tr("后台采集进程与磁盘信息的间隔；插拔后的连刷与极简模式不受影响"))
+                            .changed();
+                    });
+                    tuning_changed |= ui
+                        .checkbox(&mut self.settings.reduced_cadence, tr("降低刷新频率 (间隔加倍)"))
+                        .changed();
+
+                    ui.separator();
+                    ui.heading(tr("资源紧张阈值"));
+                    ui.label(
+                        egui::RichText::new(tr("满足任一条件持续几轮后进入极简模式 (刷新放缓到 2 秒)"))
+                            .small()
+                            .color(egui::Color32::GRAY),
+                    );
+                    ui.horizontal(|ui| {
+                        ui.label(tr("CPU 高于"));
+                        tuning_changed |= ui
+                            .add(egui::Slider::new(&mut self.settings.tight_cpu_percent, 50.0..=100.0).suffix(" %"))
+                            .changed();
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("可用内存低于"));
+                        tuning_changed |= ui
+                            .add(egui::DragValue::new(&mut self.settings.tight_free_mb).range(0..=65536).suffix(" MB"))
+                            .changed();
+                    });
+                    changed |= ui
+                        .checkbox(&mut self.settings.auto_low_power, tr("资源紧张时自动折叠面板"))
+                        .on_hover_text(tr("进入极简模式时收起性能监测与智能诊断，减少绘制开销"))
+                        .changed();
+
+                    ui.separator();
+                    ui.heading(tr("弹出方式"));
+                    for mode in settings::EjectMode::ALL {
+                        if ui
+                            .radio(self.settings.eject_mode == mode, mode.label())
+                            .on_hover_text(mode.hint())
+                            .clicked()
+                            && self.settings.eject_mode != mode
+                        {
+                            self.settings.eject_mode = mode;
+                            self.eject_policy.mode = mode;
+                            let _ = self.usb_tx.send(UsbCmd::SetPolicy(self.eject_policy.clone()));
+                            audit::record("设置", &format!("弹出方式 -> {}", mode.label()));
+                            changed = true;
+                        }
+                    }
+
+                    ui.separator();
+                    ui.heading(tr("界面"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr("语言"));
+                        let current = settings::LANGUAGES
+                            .iter()
+                            .find(|(code, _)| *code == self.settings.language)
+                            .map(|(_, name)| *name)
+                            .unwrap_or(self.settings.language.as_str())
+                            .to_string();
+                        egui::ComboBox::from_id_source("settings_language")
+                            .selected_text(current)
+                            .show_ui(ui, |ui| {
+                                for (code, name) in settings::LANGUAGES {
+                                    if ui.selectable_label(self.settings.language == code, name).clicked()
+                                        && self.settings.language != code
+                                    {
+                                        self.settings.language = code.to_string();
+                                        i18n::set_language(code);
+                                        changed = true;
+                                    }
+                                }
+                            });
+                        ui.label(egui::RichText::new(tr("进程说明在重启后切换")).small().color(egui::Color32::GRAY));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("主题"));
+                        for theme in [settings::Theme::Dark, settings::Theme::Light] {
+                            if ui.radio(self.settings.theme == theme, theme.label()).clicked() && self.settings.theme != theme {
+                                self.settings.theme = theme;
+                                apply_theme(ctx, theme);
+                                changed = true;
+                            }
+                        }
+                    });
+
+                    ui.separator();
+                    ui.heading(tr("启动"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr("默认配置"));
+                        egui::ComboBox::from_id_source("settings_default_profile")
+                            .selected_text(self.settings.default_profile.clone())
+                            .show_ui(ui, |ui| {
+                                let names: Vec<String> = self.settings.profiles.iter().map(|p| p.name.clone()).collect();
+                                for name in names {
+                                    if ui.selectable_label(self.settings.default_profile == name, &name).clicked() {
+                                        self.settings.default_profile = name;
+                                        changed = true;
+                                    }
+                                }
+                            });
+                    });
+                    changed |= ui
+                        .checkbox(&mut self.settings.restore_session, tr("启动时恢复上次的面板与窗口位置"))
+                        .on_hover_text(tr("指定了 --profile 时仍按配置启动"))
+                        .changed();
+                    changed |= ui
+                        .add_enabled(tray::is_active(), egui::Checkbox::new(&mut self.settings.close_to_tray, tr("关闭到托盘")))
+                        .changed();
+                    changed |= ui
+                        .add_enabled(tray::is_active(), egui::Checkbox::new(&mut self.settings.start_in_tray, tr("启动后直接隐藏到托盘")))
+                        .on_hover_text(tr("开机自启时不弹出主窗口，双击托盘图标或按快捷键显示"))
+                        .changed();
+
+                    ui.separator();
+                    ui.heading(tr("外部显示"));
+                    if self.render_widget_settings(ui) {
+                        if let Ok(mut config) = self.widget_config.write() {
+                            *config = self.settings.widget.clone();
+                        }
+                        changed = true;
+                    }
+
+                    if tuning_changed {
+                        self.apply_tuning();
+                    } else if changed {
+                        self.settings.save();
+                    }
+                });
+            });
+        self.show_settings = open;
+    }
+
+    /// 设置窗口 · 外部显示：端口与字段；返回设置是否有改动
+    fn render_widget_settings(&mut self, ui: &mut egui::Ui) -> bool {
+        let status = self.widget_status.lock().map(|s| s.clone()).unwrap_or_default();
+        let widget = &mut self.settings.widget;
+        let mut changed = false;
+        ui.label(
+            egui::RichText::new(tr("本机 JSON 接口，供 Stream Deck 插件、键盘小屏等硬件面板轮询显示"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+        ui.horizontal(|ui| {
+            changed |= ui.checkbox(&mut widget.enabled, tr("启用")).changed();
+            ui.label(tr("端口"));
+            changed |= ui.add(egui::DragValue::new(&mut widget.port).range(1024..=65535)).changed();
+            match (&status.error, status.listening) {
+                (Some(e), _) => {
+                    ui.label(egui::RichText::new(format!("⚠ {}", e)).small().color(egui::Color32::YELLOW));
+                }
+                (None, Some(port)) => {
+                    let url = format!("http://127.0.0.1:{}/widget", port);
+                    ui.label(egui::RichText::new(trf("● 已处理 {} 次请求", &[&status.requests])).small().color(egui::Color32::GREEN));
+                    if ui.small_button(tr("📋 复制地址")).on_hover_text(url.as_str()).clicked() {
+                        ui.ctx().output_mut(|o| o.copied_text = url);
+                    }
+                }
+                (None, None) => {}
+            }
+        });
+        ui.label(egui::RichText::new(tr("返回的字段 (也可在地址后加 ?fields=cpu,mem 按请求指定)")).small().color(egui::Color32::GRAY));
+        egui::Grid::new("widget_fields").num_columns(2).show(ui, |ui| {
+            for (i, field) in widget_api::Field::ALL.into_iter().enumerate() {
+                let mut on = widget.fields.contains(&field);
+                if ui.checkbox(&mut on, field.label()).on_hover_text(field.key()).changed() {
+                    if on {
+                        widget.fields.push(field);
+                    } else {
+                        widget.fields.retain(|f| *f != field);
+                    }
+                    changed = true;
+                }
+                if i % 2 == 1 {
+                    ui.end_row();
+                }
+            }
+        });
+        changed
+    }
+
+    /// 记下当前视图、面板与窗口位置，下次启动时恢复；最小化时窗口位置无意义，沿用上次的
+    fn remember_session(&mut self, ctx: &egui::Context) {
+        let (minimized, outer, inner) =
+            ctx.input(|i| (i.viewport().minimized == Some(true), i.viewport().outer_rect, i.viewport().inner_rect));
+        let previous = self.settings.session.take().unwrap_or_default();
+        let profile = self.current_profile(&self.active_profile);
+        let (position, size) = if minimized {
+            (previous.position, previous.size)
+        } else {
+            (outer.map(|r| [r.min.x, r.min.y]), inner.map(|r| [r.width(), r.height()]))
+        };
+        self.settings.session = Some(settings::Session {
+            view: profile.view,
+            panels: profile.panels,
+            position,
+            size,
+        });
+        self.settings.save();
+    }
+
+    /// 访客模式：未开启时是设定时长与密码的菜单，开启后显示剩余时间与解锁框
+    fn render_guest_mode(&mut self, ui: &mut egui::Ui) {
+        let Some(left) = guest_mode::remaining() else {
+            ui.menu_button(tr("🔒 访客模式"), |ui| {
+                ui.label(
+                    egui::RichText::new(tr("借出电脑时禁用终止、强制弹出、格式化等操作并锁定设置"))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+                ui.horizontal(|ui| {
+                    ui.label(tr("时长"));
+                    ui.add(egui::DragValue::new(&mut self.guest_hours).range(1..=guest_mode::MAX_HOURS).suffix(tr(" 小时")));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("解锁密码"));
+                    ui.add(egui::TextEdit::singleline(&mut self.guest_password).password(true).desired_width(120.0));
+                });
+                if ui.add_enabled(!self.guest_password.is_empty(), egui::Button::new(tr("开启"))).clicked() {
+                    let text = match guest_mode::start(self.guest_hours, &self.guest_password) {
+                        Ok(()) => trf("🔒 访客模式已开启，{} 小时后自动结束", &[&self.guest_hours]),
+                        Err(e) => format!("❌ {}", e),
+                    };
+                    self.guest_password.clear();
+                    self.toasts.push((Instant::now(), text));
+                    ui.close_menu();
+                }
+            });
+            return;
+        };
+        ui.label(
+            egui::RichText::new(trf("🔒 访客模式 剩余 {}", &[&system_counters::format_uptime(left)]))
+                .strong()
+                .color(egui::Color32::GOLD),
+        );
+        let edit = ui.add(
+            egui::TextEdit::singleline(&mut self.guest_unlock)
+                .password(true)
+                .hint_text(tr("密码"))
+                .desired_width(80.0),
+        );
+        let enter = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if ui.small_button(tr("解锁")).clicked() || enter {
+            let text = match guest_mode::unlock(&self.guest_unlock) {
+                Ok(()) => tr("🔓 访客模式已结束").to_string(),
+                Err(e) => format!("❌ {}", e),
+            };
+            self.guest_unlock.clear();
+            self.toasts.push((Instant::now(), text));
+        }
+    }
+
+    /// 迷你悬浮窗打开的那一帧隐藏主窗口；关闭时由悬浮窗自己恢复主窗口
+    fn render_mini_hud(&mut self, ctx: &egui::Context) {
+        use std::sync::atomic::Ordering;
+        let open = self.hud_open.load(Ordering::SeqCst);
+        if open {
+            show_mini_hud(ctx, self.snapshot.clone(), self.hud_open.clone());
+            if !self.hud_shown {
+                audit::record("迷你悬浮窗", "打开");
+                tray::hide_main_window();
+            }
+        }
+        self.hud_shown = open;
+    }
+
+    /// 关闭窗口：开启“关闭到托盘”时改为隐藏；真正退出前移除托盘图标
+    fn handle_close(&mut self, ctx: &egui::Context) {
+        use std::sync::atomic::Ordering;
+        if self.quit_requested.swap(false, Ordering::SeqCst) {
+            self.remember_session(ctx);
+            self.release_metered();
+            tray::remove();
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            return;
+        }
+        if !ctx.input(|i| i.viewport().close_requested()) {
+            return;
+        }
+        self.remember_session(ctx);
+        if self.settings.close_to_tray && tray::is_active() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            tray::hide_main_window();
+            tray::balloon(APP_TITLE, tr("已最小化到托盘，后台继续监控；右键托盘图标可弹出 U 盘"), false);
+        } else {
+            self.release_metered();
+            tray::remove();
+        }
+    }
+
+    /// 退出前撤销计费网络的防火墙规则，免得程序不在时名单内的程序一直断网
+    fn release_metered(&self) {
+        if self.metered_status.lock().map(|s| !s.blocked.is_empty()).unwrap_or(false) {
+            if let Err(e) = firewall::remove_rules(metered::RULE_NAME) {
+                audit::record("计费网络", &format!("退出时无法移除防火墙规则：{}", e));
+            }
+        }
+    }
+
+    /// 锁定视图或后台采集跟不上时，提示当前数据的时间
+    fn render_stale_badge(&self, ui: &mut egui::Ui, ctx: &egui::Context, snapshot: &AppSnapshot) {
+        let Some(taken_at) = snapshot.taken_at else {
+            return;
+        };
+        let age = taken_at.elapsed();
+        let limit = if snapshot.is_resource_tight { STALE_AFTER_TIGHT } else { STALE_AFTER };
+        let clock = snapshot.taken_at_local.split(' ').nth(1).unwrap_or(&snapshot.taken_at_local);
+        let (text, color) = if self.paused {
+            (trf("⏸ 数据已暂停 · {}", &[&clock]), egui::Color32::GRAY)
+        } else if age > limit {
+            (trf("⏳ 数据延迟 {}s · {}", &[&format!("{:.1}", age.as_secs_f32()), &clock]), egui::Color32::GOLD)
+        } else {
+            // 后台卡住时不会再有人请求重绘，自己定时检查一次
+            ctx.request_repaint_after(limit - age + Duration::from_millis(100));
+            return;
+        };
+        ui.label(egui::RichText::new(text).small().color(color)).on_hover_text(trf(
+            "快照采集于 {}，上一轮采集耗时 {} ms",
+            &[&snapshot.taken_at_local, &snapshot.tick_cost.as_millis()],
+        ));
+        // 延迟秒数要持续走动
+        ctx.request_repaint_after(Duration::from_millis(500));
+    }
+
+    /// 性能监测：各逻辑核心占用，条形或热力图；单线程瓶颈表现为一个核心长期满载
+    fn render_per_core(&mut self, ui: &mut egui::Ui, cores: &[f32]) {
+        if cores.is_empty() {
+            return;
+        }
+        // 与全局 CPU 进度条一致：绿 -> 金 -> 红；几乎空闲 (可能已停放) 的核心显示为灰
+        let heat = |v: f32| {
+            if v < 1.0 {
+                egui::Color32::from_gray(60)
+            } else if v > 80.0 {
+                egui::Color32::RED
+            } else if v > 50.0 {
+                egui::Color32::GOLD
+            } else {
+                egui::Color32::GREEN.gamma_multiply(0.4 + v / 50.0 * 0.6)
+            }
+        };
+        egui::CollapsingHeader::new(egui::RichText::new(trf("各核心占用 ({} 个逻辑处理器)", &[&cores.len()])).small())
+            .id_source("per_core")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.radio(!self.settings.core_heatmap, tr("条形")).clicked() {
+                        self.settings.core_heatmap = false;
+                        self.settings.save();
+                    }
+                    if ui.radio(self.settings.core_heatmap, tr("热力图")).clicked() {
+                        self.settings.core_heatmap = true;
+                        self.settings.save();
+                    }
+                });
+                if self.settings.core_heatmap {
+                    let cell = egui::vec2(22.0, 22.0);
+                    let per_row = ((ui.available_width() / (cell.x + 2.0)) as usize).max(1);
+                    for (row, chunk) in cores.chunks(per_row).enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.spacing_mut().item_spacing.x = 2.0;
+                            for (i, &v) in chunk.iter().enumerate() {
+                                let (rect, resp) = ui.allocate_exact_size(cell, egui::Sense::hover());
+                                ui.painter().rect_filled(rect, 2.0, heat(v));
+                                resp.on_hover_text(format!("CPU {}: {:.0}%", row * per_row + i, v));
+                            }
+                        });
+                    }
+                } else {
+                    egui::Grid::new("per_core_grid").num_columns(4).spacing([6.0, 2.0]).show(ui, |ui| {
+                        for (i, &v) in cores.iter().enumerate() {
+                            ui.label(egui::RichText::new(format!("CPU {}", i)).small().monospace());
+                            ui.add(
+                                egui::ProgressBar::new(v / 100.0)
+                                    .desired_width(100.0)
+                                    .text(egui::RichText::new(format!("{:.0}%", v)).small())
+                                    .fill(heat(v)),
+                            );
+                            if i % 2 == 1 {
+                                ui.end_row();
+                            }
+                        }
+                    });
+                }
+            });
+    }
+
+    /// 性能监测：最近 10 分钟的 CPU / 内存 / 网络曲线
+    fn render_perf_history(&self, ui: &mut egui::Ui) {
+        let Ok(history) = self.perf_history.lock() else {
+            return;
+        };
+        if history.is_empty() {
+            return;
+        }
+        let now = Instant::now();
+        let span = -perf_history::SPAN.as_secs_f64();
+        let plot = |id: &str| {
+            egui_plot::Plot::new(id.to_string())
+                .height(70.0)
+                .include_x(span)
+                .include_x(0.0)
+                .include_y(0.0)
+                .allow_drag(false)
+                .allow_zoom(false)
+                .allow_scroll(false)
+                .allow_boxed_zoom(false)
+                .show_axes([false, true])
+                .x_axis_formatter(|mark, _| trf("{} 分钟前", &[&format!("{:.0}", -mark.value / 60.0)]))
+        };
+        ui.add_space(5.0);
+        ui.label(egui::RichText::new(tr("最近 10 分钟")).small().color(egui::Color32::GRAY));
+
+        let cpu = history.series(now, |s| s.cpu as f64);
+        let memory = history.series(now, |s| s.memory as f64);
+        plot("perf_history_usage")
+            .include_y(100.0)
+            .legend(egui_plot::Legend::default().position(egui_plot::Corner::LeftTop))
+            .label_formatter(|name, p| trf("{} {}%\n{} 秒前", &[&name, &format!("{:.1}", p.y), &format!("{:.0}", -p.x)]))
+            .show(ui, |p| {
+                p.line(egui_plot::Line::new(egui_plot::PlotPoints::from(cpu)).name("CPU").color(egui::Color32::GOLD));
+                p.line(
+                    egui_plot::Line::new(egui_plot::PlotPoints::from(memory))
+                        .name(tr("内存"))
+                        .color(egui::Color32::from_rgb(100, 180, 255)),
+                );
+            });
+
+        let net_in = history.series(now, |s| s.net_in / 1024.0);
+        let net_out = history.series(now, |s| s.net_out / 1024.0);
+        plot("perf_history_net")
+            .legend(egui_plot::Legend::default().position(egui_plot::Corner::LeftTop))
+            .label_formatter(|name, p| trf("{} {} KB/s\n{} 秒前", &[&name, &format!("{:.1}", p.y), &format!("{:.0}", -p.x)]))
+            .show(ui, |p| {
+                p.line(egui_plot::Line::new(egui_plot::PlotPoints::from(net_in)).name(tr("接收")).color(egui::Color32::GREEN));
+                p.line(
+                    egui_plot::Line::new(egui_plot::PlotPoints::from(net_out))
+                        .name(tr("发送"))
+                        .color(egui::Color32::from_rgb(255, 140, 0)),
+                );
+            });
+    }
+
+    /// 智能诊断 · 看门狗规则：编辑后点“保存”才交给监控线程
+    fn render_watchdog(&mut self, ui: &mut egui::Ui, snapshot: &AppSnapshot) {
+        let enabled = self.watchdog_draft.rules.iter().filter(|r| r.enabled).count();
+        egui::CollapsingHeader::new(egui::RichText::new(trf("🐕 看门狗规则 ({} 条启用)", &[&enabled])).small())
+            .id_source("watchdog")
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(tr("名称支持 * 通配；系统进程不执行作用于进程的动作。触发时弹出托盘通知并写入审计日志"))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+                let mut remove: Option<usize> = None;
+                egui::Grid::new("watchdog_rules").spacing([6.0, 4.0]).show(ui, |ui| {
+                    for (i, rule) in self.watchdog_draft.rules.iter_mut().enumerate() {
+                        ui.checkbox(&mut rule.enabled, "");
+                        ui.add(egui::TextEdit::singleline(&mut rule.pattern).hint_text(tr("进程名，如 chrome*")).desired_width(110.0));
+                        ui.label(egui::RichText::new("CPU >").small());
+                        ui.add(egui::DragValue::new(&mut rule.cpu_above).range(1.0..=1000.0).suffix("%"));
+                        ui.label(egui::RichText::new(tr("持续")).small());
+                        ui.add(egui::DragValue::new(&mut rule.for_secs).range(1..=3600).suffix(tr(" 秒")));
+                        egui::ComboBox::from_id_source(("watchdog_action", i))
+                            .selected_text(rule.action.label())
+                            .width(80.0)
+                            .show_ui(ui, |ui| {
+                                for a in watchdog::Action::ALL {
+                                    ui.selectable_value(&mut rule.action, a, a.label());
+                                }
+                            });
+                        match rule.action {
+                            watchdog::Action::RunScript => {
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut rule.command)
+                                        .hint_text(tr("命令，如 notify.bat"))
+                                        .desired_width(140.0),
+                                )
+                                .on_hover_text(tr("经 cmd /c 以普通用户权限运行 (不继承管理员权限)；环境变量 GK_PROCESS / GK_PIDS / GK_CPU 为触发的进程信息"));
+                            }
+                            watchdog::Action::EjectDrive => {
+                                ui.add(egui::TextEdit::singleline(&mut rule.drive).hint_text(tr("盘符，如 E:")).desired_width(50.0))
+                                    .on_hover_text(tr("先扫描占用再弹出，与托盘菜单中的弹出相同"));
+                            }
+                            _ => {
+                                ui.label("");
+                            }
+                        }
+                        if ui.small_button("🗑").clicked() {
+                            remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(i) = remove {
+                    self.watchdog_draft.rules.remove(i);
+                }
+                let saved = self.watchdog_rules.read().map(|r| r.clone()).unwrap_or_default();
+                ui.horizontal(|ui| {
+                    if ui.small_button(tr("➕ 添加规则")).clicked() {
+                        self.watchdog_draft.rules.push(watchdog::Rule::default());
+                    }
+                    let dirty = self.watchdog_draft != saved;
+                    if ui.add_enabled(dirty, egui::Button::new(tr("保存")).small()).clicked() {
+                        self.watchdog_draft.rules.retain(|r| !r.pattern.trim().is_empty());
+                        self.watchdog_draft.save();
+                        if let Ok(mut rules) = self.watchdog_rules.write() {
+                            *rules = self.watchdog_draft.clone();
+                        }
+                        audit::record("看门狗", &format!("保存 {} 条规则", self.watchdog_draft.rules.len()));
+                    }
+                    if dirty && ui.small_button(tr("放弃修改")).clicked() {
+                        self.watchdog_draft = saved.clone();
+                    }
+                });
+                if !snapshot.watchdog_log.is_empty() {
+                    ui.label(egui::RichText::new(tr("最近触发")).small().strong());
+                    for e in snapshot.watchdog_log.iter().take(10) {
+                        ui.label(egui::RichText::new(format!("{}  {}", e.time, e.message)).small().monospace());
+                    }
+                }
+            });
+    }
+
+    /// 智能诊断 · 操作宏：录制、编辑并执行；编辑后点“保存”才更新快捷键与托盘菜单
+    fn render_macros(&mut self, ui: &mut egui::Ui) {
+        let title = match &self.macro_recording {
+            Some(m) => trf("⏺ 操作宏 (录制中 · {} 步)", &[&m.steps.len()]),
+            None => trf("⏺ 操作宏 ({} 个)", &[&self.macro_draft.len()]),
+        };
+        egui::CollapsingHeader::new(egui::RichText::new(title).small())
+            .id_source("macros")
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(tr("录制期间在界面上终止进程、弹出磁盘都会记为步骤；重启服务需手动添加。执行时逐步进行，失败的步骤不影响后续步骤"))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+                let mut stop = false;
+                let mut cancel = false;
+                match &mut self.macro_recording {
+                    Some(recording) => {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(egui::Color32::RED, tr("⏺ 录制中"));
+                            ui.add(egui::TextEdit::singleline(&mut recording.name).desired_width(110.0));
+                            stop = ui.add_enabled(!recording.steps.is_empty(), egui::Button::new(tr("停止录制")).small()).clicked();
+                            cancel = ui.small_button(tr("取消")).clicked();
+                        });
+                        for step in &recording.steps {
+                            ui.label(egui::RichText::new(format!("  · {}", step.describe())).small());
+                        }
+                    }
+                    None => {
+                        if ui.small_button(tr("⏺ 开始录制")).clicked() {
+                            self.macro_recording = Some(macros::Macro {
+                                name: trf("宏 {}", &[&(self.macro_draft.len() + 1)]),
+                                ..Default::default()
+                            });
+                        }
+                    }
+                }
+                if stop {
+                    if let Some(m) = self.macro_recording.take() {
+                        self.macro_draft.push(m);
+                    }
+                }
+                if cancel {
+                    self.macro_recording = None;
+                }
+
+                let status = self.hotkeys.status.lock().map(|s| s.clone()).unwrap_or_default();
+                let mut remove: Option<usize> = None;
+                let mut run: Option<macros::Macro> = None;
+                for (i, m) in self.macro_draft.iter_mut().enumerate() {
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut m.name).hint_text(tr("名称")).desired_width(110.0));
+                        ui.add(egui::TextEdit::singleline(&mut m.hotkey).hint_text(tr("快捷键")).desired_width(80.0))
+                            .on_hover_text(tr("格式如 Ctrl+Alt+1，留空表示不绑定；保存后生效"));
+                        match status.iter().find(|(a, _)| *a == hotkeys::HotkeyAction::RunMacro(i)).map(|(_, r)| r) {
+                            _ if m.hotkey.trim().is_empty() => {}
+                            Some(Err(e)) => {
+                                ui.colored_label(egui::Color32::RED, "✖").on_hover_text(e);
+                            }
+                            Some(Ok(())) => {
+                                ui.colored_label(egui::Color32::GREEN, "✔").on_hover_text(tr("已注册"));
+                            }
+                            None => {}
+                        }
+                        ui.checkbox(&mut m.in_tray, tr("托盘菜单"));
+                        if ui.add_enabled(!m.steps.is_empty(), egui::Button::new(tr("▶ 执行")).small()).clicked() {
+                            run = Some(m.clone());
+                        }
+                        if ui.small_button("🗑").clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                    let mut remove_step: Option<usize> = None;
+                    for (j, step) in m.steps.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new(format!("  {}.", j + 1)).small());
+                            match step {
+                                macros::Step::RestartService(name) => {
+                                    ui.label(egui::RichText::new(tr("重启服务")).small());
+                                    ui.add(egui::TextEdit::singleline(name).hint_text(tr("服务名，如 Spooler")).desired_width(110.0));
+                                }
+                                other => {
+                                    ui.label(egui::RichText::new(other.describe()).small());
+                                }
+                            }
+                            if ui.small_button("✖").clicked() {
+                                remove_step = Some(j);
+                            }
+                        });
+                    }
+                    if let Some(j) = remove_step {
+                        m.steps.remove(j);
+                    }
+                    if ui.small_button(tr("➕ 重启服务")).clicked() {
+                        m.steps.push(macros::Step::RestartService(String::new()));
+                    }
+                }
+                if let Some(i) = remove {
+                    self.macro_draft.remove(i);
+                }
+                if let Some(m) = run {
+                    audit::record("操作宏", &format!("{} -> 手动执行", m.name));
+                    self.send_usb_cmd(UsbCmd::RunMacro(m));
+                }
+
+                ui.horizontal(|ui| {
+                    let dirty = self.macro_draft != self.settings.macros;
+                    if ui.add_enabled(dirty, egui::Button::new(tr("保存")).small()).clicked() {
+                        for m in self.macro_draft.iter_mut() {
+                            m.steps.retain(|s| !matches!(s, macros::Step::RestartService(n) if n.trim().is_empty()));
+                        }
+                        self.settings.macros = self.macro_draft.clone();
+                        self.settings.save();
+                        if let Ok(mut shared) = self.macros.write() {
+                            *shared = self.macro_draft.clone();
+                        }
+                        self.hotkeys.update(macros::with_hotkeys(&self.settings.hotkeys, &self.settings.macros));
+                        audit::record("操作宏", &format!("保存 {} 个宏", self.macro_draft.len()));
+                    }
+                    if dirty && ui.small_button(tr("放弃修改")).clicked() {
+                        self.macro_draft = self.settings.macros.clone();
+                    }
+                });
+
+                if let Some(progress) = &self.macro_progress {
+                    ui.label(
+                        egui::RichText::new(if progress.is_finished() {
+                            trf("上次执行 · {}", &[&progress.summary()])
+                        } else {
+                            trf("正在执行 {} ({}/{})", &[&progress.name, &progress.results.len(), &progress.total])
+                        })
+                        .small()
+                        .strong(),
+                    );
+                    for (step, result) in &progress.results {
+                        let (color, text) = match result {
+                            Ok(t) => (egui::Color32::GREEN, format!("✔ {}：{}", step, t)),
+                            Err(e) => (egui::Color32::RED, format!("✖ {}：{}", step, e)),
+                        };
+                        ui.colored_label(color, egui::RichText::new(text).small());
+                    }
+                }
+            });
+    }
+
+    /// 智能诊断 · 使用时长：按前台窗口统计的每日应用使用时间
+    fn render_screen_time(&mut self, ui: &mut egui::Ui, snapshot: &AppSnapshot) {
+        egui::CollapsingHeader::new(egui::RichText::new(tr("⏱ 使用时长")).small())
+            .id_source("screen_time")
+            .show(ui, |ui| {
+                let today = screen_time::today();
+                ui.horizontal(|ui| {
+                    if ui
+                        .checkbox(&mut self.settings.screen_time, tr("记录前台应用的使用时长"))
+                        .on_hover_text(tr("按前台窗口计时，键鼠空闲 5 分钟以上或锁屏时不计；数据只保存在本机的进程历史数据库"))
+                        .changed()
+                    {
+                        self.apply_tuning();
+                    }
+                    ui.separator();
+                    if ui.small_button("◀").on_hover_text(tr("前一天")).clicked() {
+                        // 跨夏令时的那天不足 24 小时，取前一天中午所在的日期
+                        self.screen_time_day = screen_time::day_start(self.screen_time_day.saturating_sub(12 * 3600));
+                    }
+                    let date = format_unix_local(self.screen_time_day);
+                    ui.label(egui::RichText::new(date.get(..10).unwrap_or(&date)).small().monospace());
+                    if ui.add_enabled(self.screen_time_day < today, egui::Button::new("▶").small()).on_hover_text(tr("后一天")).clicked() {
+                        self.screen_time_day = screen_time::day_start(self.screen_time_day + 36 * 3600);
+                    }
+                    if self.screen_time_day != today && ui.small_button(tr("今天")).clicked() {
+                        self.screen_time_day = today;
+                    }
+                });
+
+                let stale = match &self.screen_time_rows {
+                    Some((day, at, _)) => *day != self.screen_time_day || at.elapsed() > Duration::from_secs(60),
+                    None => true,
+                };
+                if stale {
+                    let rows = process_history::foreground_on(self.screen_time_day);
+                    self.screen_time_rows = Some((self.screen_time_day, Instant::now(), rows));
+                }
+                let Some((_, _, rows)) = &self.screen_time_rows else {
+                    return;
+                };
+                let rows = match rows {
+                    Ok(rows) => rows,
+                    Err(e) => {
+                        ui.colored_label(egui::Color32::RED, format!("❌ {}", e));
+                        return;
+                    }
+                };
+                if rows.is_empty() {
+                    ui.label(egui::RichText::new(tr("这一天没有记录")).small().color(egui::Color32::GRAY));
+                    return;
+                }
+                let total: u64 = rows.iter().map(|(_, secs)| secs).sum();
+                ui.label(
+                    egui::RichText::new(trf("合计 {} · {} 个应用", &[&screen_time::format_duration(total), &rows.len()]))
+                        .small()
+                        .strong(),
+                );
+                let friendly: HashMap<String, &str> = snapshot
+                    .high_resource
+                    .iter()
+                    .chain(&snapshot.other_groups)
+                    .filter(|g| !g.friendly_name.is_empty())
+                    .map(|g| (g.name.to_lowercase(), g.friendly_name.as_str()))
+                    .collect();
+                let longest = rows.first().map(|(_, secs)| *secs).unwrap_or(1).max(1);
+                egui::ScrollArea::vertical().id_source("screen_time_scroll").max_height(240.0).show(ui, |ui| {
+                    egui::Grid::new("screen_time_grid").spacing([10.0, 2.0]).show(ui, |ui| {
+                        for (name, secs) in rows {
+                            let label = match friendly.get(&name.to_lowercase()) {
+                                Some(f) => format!("{} ({})", f, name),
+                                None => name.clone(),
+                            };
+                            ui.label(egui::RichText::new(label).small());
+                            ui.add(
+                                egui::ProgressBar::new(*secs as f32 / longest as f32)
+                                    .desired_width(160.0)
+                                    .desired_height(8.0)
+                                    .fill(egui::Color32::from_rgb(70, 130, 180)),
+                            );
+                            ui.label(egui::RichText::new(screen_time::format_duration(*secs)).small().monospace());
+                            ui.label(
+                                egui::RichText::new(format!("{:.0}%", *secs as f32 / total.max(1) as f32 * 100.0))
+                                    .small()
+                                    .color(egui::Color32::GRAY),
+                            );
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+    }
+
+    /// 智能诊断 · 两台机器对比：导出本机快照，载入另一台的快照并排比较进程与服务
+    fn render_process_compare(&mut self, ui: &mut egui::Ui, snapshot: &AppSnapshot) {
+        egui::CollapsingHeader::new(egui::RichText::new(tr("⇄ 两台机器对比")).small())
+            .id_source("process_compare")
+            .show(ui, |ui| {
+                let mut load: Option<std::path::PathBuf> = None;
+                ui.horizontal(|ui| {
+                    if ui
+                        .small_button(tr("📤 导出本机快照"))
+                        .on_hover_text(tr("保存本机的进程与自动服务列表，拷到另一台机器上载入即可对比"))
+                        .clicked()
+                    {
+                        let text = match process_compare::save(&machine_snapshot(snapshot)) {
+                            Ok(path) => {
+                                unelevated::reveal_in_explorer(&path.to_string_lossy());
+                                trf("📤 快照已保存到 {}", &[&path.display()])
+                            }
+                            Err(e) => format!("❌ {}", e),
+                        };
+                        self.toasts.push((Instant::now(), text));
+                    }
+                    ui.separator();
+                    let edit = ui.add(
+                        egui::TextEdit::singleline(&mut self.compare_path)
+                            .hint_text(tr("对方快照路径，如 \\\\PC-2\\share\\PC-2.json"))
+                            .desired_width(240.0),
+                    );
+                    let enter = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    ui.menu_button("📂", |ui| {
+                        let saved = process_compare::list_saved();
+                        if saved.is_empty() {
+                            ui.label(egui::RichText::new(tr("快照目录为空")).small().color(egui::Color32::GRAY));
+                        }
+                        for path in saved {
+                            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                            if ui.button(name).clicked() {
+                                self.compare_path = path.to_string_lossy().to_string();
+                                load = Some(path);
+                                ui.close_menu();
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr("从快照目录选择"));
+                    let ready = !self.compare_path.trim().is_empty();
+                    if ui.add_enabled(ready, egui::Button::new(tr("对比")).small()).clicked() || (enter && ready) {
+                        load = Some(std::path::PathBuf::from(self.compare_path.trim().trim_matches('"')));
+                    }
+                });
+                if let Some(path) = load {
+                    self.compare = Some(process_compare::load(&path).map(|other| {
+                        let diff = process_compare::diff(&machine_snapshot(snapshot), &other);
+                        (other, diff)
+                    }));
+                }
+
+                let Some(result) = &self.compare else {
+                    return;
+                };
+                let (other, diff) = match result {
+                    Ok(r) => r,
+                    Err(e) => {
+                        ui.colored_label(egui::Color32::RED, format!("❌ {}", e));
+                        return;
+                    }
+                };
+                const ONLY_LOCAL: egui::Color32 = egui::Color32::from_rgb(100, 170, 255);
+                const ONLY_OTHER: egui::Color32 = egui::Color32::from_rgb(255, 165, 0);
+                let mut clear = false;
+                let local_only = diff.processes.iter().filter(|r| r.other.is_none()).count();
+                let other_only = diff.processes.iter().filter(|r| r.local.is_none()).count();
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(
+                        egui::RichText::new(trf(
+                            "本机 {} ⇄ {} (快照于 {})",
+                            &[&panel_capture::hostname(), &other.host, &other.taken_at],
+                        ))
+                        .small()
+                        .strong(),
+                    );
+                    ui.label(egui::RichText::new(trf("本机独有 {} 个进程", &[&local_only])).small().color(ONLY_LOCAL));
+                    ui.label(
+                        egui::RichText::new(trf("{} 独有 {} 个进程", &[&other.host, &other_only])).small().color(ONLY_OTHER),
+                    );
+                    if ui.small_button(tr("清除")).clicked() {
+                        clear = true;
+                    }
+                });
+                let cell = |e: &Option<process_compare::ProcessEntry>| match e {
+                    Some(e) => format!("×{}  {:.1} MB", e.count, e.memory as f32 / 1024.0 / 1024.0),
+                    None => "—".to_string(),
+                };
+                egui::ScrollArea::vertical().id_source("compare_scroll").max_height(260.0).show(ui, |ui| {
+                    egui::Grid::new("compare_grid").striped(true).spacing([12.0, 2.0]).show(ui, |ui| {
+                        for h in [tr("进程"), tr("本机"), other.host.as_str(), tr("内存差")] {
+                            ui.label(egui::RichText::new(h).small().strong());
+                        }
+                        ui.end_row();
+                        for row in diff.processes.iter().take(400) {
+                            let color = match (&row.local, &row.other) {
+                                (Some(_), None) => ONLY_LOCAL,
+                                (None, Some(_)) => ONLY_OTHER,
+                                _ => ui.visuals().text_color(),
+                            };
+                            ui.label(egui::RichText::new(&row.name).small().color(color));
+                            ui.label(egui::RichText::new(cell(&row.local)).small().monospace());
+                            ui.label(egui::RichText::new(cell(&row.other)).small().monospace());
+                            let delta = row.memory_delta();
+                            let text = if row.local.is_some() && row.other.is_some() && delta != 0 {
+                                format!("{:+.1} MB", delta as f32 / 1024.0 / 1024.0)
+                            } else {
+                                String::new()
+                            };
+                            ui.label(egui::RichText::new(text).small().monospace().color(egui::Color32::GRAY));
+                            ui.end_row();
+                        }
+                    });
+                });
+                ui.label(
+                    egui::RichText::new(trf("自动服务：两边都有 {} 个", &[&diff.services_shared]))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+                ui.columns(2, |cols| {
+                    for (ui, (title, list, color)) in cols.iter_mut().zip([
+                        (tr("本机独有的服务"), &diff.services_only_local, ONLY_LOCAL),
+                        (tr("对方独有的服务"), &diff.services_only_other, ONLY_OTHER),
+                    ]) {
+                        ui.label(egui::RichText::new(format!("{} ({})", title, list.len())).small().strong());
+                        for name in list {
+                            ui.label(egui::RichText::new(name).small().monospace().color(color));
+                        }
+                    }
+                });
+                if clear {
+                    self.compare = None;
+                }
+            });
+    }
+
+    /// 智能诊断 · 进程历史：按名称或路径搜索进程的启动 / 结束时间与峰值占用
+    fn render_process_history(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(egui::RichText::new(tr("🕘 进程历史")).small())
+            .id_source("process_history")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let edit = ui.add(
+                        egui::TextEdit::singleline(&mut self.history_query)
+                            .hint_text(tr("进程名或路径，如 install.exe"))
+                            .desired_width(200.0),
+                    );
+                    let enter = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    if ui.small_button(tr("搜索")).clicked() || enter {
+                        self.history_results = Some(process_history::search(&self.history_query));
+                    }
+                    ui.separator();
+                    for format in [weekly_report::Format::Markdown, weekly_report::Format::Html] {
+                        if ui
+                            .small_button(trf("📄 周报 ({})", &[&format.label()]))
+                            .on_hover_text(trf(
+                                "汇总最近 {} 天的 CPU / 内存排行、网络流量、弹出记录与看门狗告警",
+                                &[&weekly_report::DAYS],
+                            ))
+                            .clicked()
+                        {
+                            let text = match weekly_report::generate(format) {
+                                Ok(path) => {
+                                    unelevated::reveal_in_explorer(&path.to_string_lossy());
+                                    trf("📄 周报已保存到 {}", &[&path.display()])
+                                }
+                                Err(e) => trf("❌ 周报生成失败：{}", &[&e]),
+                            };
+                            self.toasts.push((Instant::now(), text));
+                        }
+                    }
+                });
+                let Some(results) = &self.history_results else {
+                    return;
+                };
+                let runs = match results {
+                    Ok(runs) => runs,
+                    Err(e) => {
+                        ui.colored_label(egui::Color32::RED, format!("❌ {}", e));
+                        return;
+                    }
+                };
+                match runs.first() {
+                    None => {
+                        ui.label(egui::RichText::new(tr("没有记录")).small().color(egui::Color32::GRAY));
+                        return;
+                    }
+                    Some(last) => {
+                        ui.label(
+                            egui::RichText::new(trf(
+                                "{} 最近一次启动于 {}{}",
+                                &[
+                                    &last.name,
+                                    &format_unix_local(last.started),
+                                    &if runs.len() as i64 >= process_history::SEARCH_LIMIT {
+                                        trf("，仅显示最近 {} 条", &[&runs.len()])
+                                    } else {
+                                        trf("，共 {} 条", &[&runs.len()])
+                                    },
+                                ],
+                            ))
+                            .small()
+                            .strong(),
+                        );
+                    }
+                }
+                egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                    egui::Grid::new("process_history_grid").striped(true).spacing([10.0, 2.0]).show(ui, |ui| {
+                        for h in [tr("进程"), tr("启动"), tr("结束"), tr("时长"), tr("峰值内存"), tr("峰值 CPU")] {
+                            ui.label(egui::RichText::new(h).small().strong());
+                        }
+                        ui.end_row();
+                        for run in runs {
+                            ui.label(egui::RichText::new(&run.name).small())
+                                .on_hover_text(format!("{}\nPID {}", run.path, run.pid));
+                            ui.label(egui::RichText::new(format_unix_local(run.started)).small());
+                            ui.label(match run.stopped {
+                                Some(t) => egui::RichText::new(format_unix_local(t)).small(),
+                                None => egui::RichText::new(tr("运行中")).small().color(egui::Color32::GREEN),
+                            });
+                            ui.label(
+                                egui::RichText::new(system_counters::format_uptime(Duration::from_secs(run.duration())))
+                                    .small(),
+                            );
+                            ui.label(
+                                egui::RichText::new(format!("{:.1} MB", run.peak_memory as f32 / 1024.0 / 1024.0)).small(),
+                            );
+                            ui.label(egui::RichText::new(format!("{:.1}%", run.peak_cpu)).small());
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+    }
 
-        egui::Grid::new(format!("grid_{}", if is_high { "high" } else { "norm" }))
-            .num_columns(5)
-            .spacing([15.0, 10.0])
-            .striped(true)
+    /// 智能诊断 · 高级：各阶段耗时与降频建议
+    fn render_tick_health(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        egui::CollapsingHeader::new(egui::RichText::new(tr("⚙ 高级：采集耗时")).small())
+            .id_source("tick_health")
             .show(ui, |ui| {
-                // Headers
-                ui.add_sized(
-                    [40.0, 20.0],
-                    egui::Label::new(egui::RichText::new("数量").strong().color(text_color)),
-                );
-                ui.add_sized(
-                    [name_col_width, 20.0],
-                    egui::Label::new(egui::RichText::new("进程名称").strong().color(text_color)),
+                let h = &self.tick_health;
+                egui::Grid::new("tick_health_grid").striped(true).show(ui, |ui| {
+                    ui.label(egui::RichText::new(tr("阶段")).small().strong());
+                    ui.label(egui::RichText::new(tr("平均")).small().strong());
+                    ui.label(egui::RichText::new(tr("最大")).small().strong());
+                    ui.end_row();
+                    for (name, r) in [
+                        (tr("进程刷新"), &h.refresh),
+                        (tr("分组分类"), &h.classify),
+                        (tr("整轮采集"), &h.total),
+                        (tr("界面帧"), &h.frame),
+                        (tr("快照拷贝"), &h.clone),
+                    ] {
+                        ui.label(egui::RichText::new(name).small());
+                        ui.label(egui::RichText::new(format!("{:.1} ms", r.avg())).small().monospace());
+                        ui.label(egui::RichText::new(format!("{:.1} ms", r.max())).small().monospace());
+                        ui.end_row();
+                    }
+                });
+                ui.label(
+                    egui::RichText::new(trf("当前刷新间隔 {} ms", &[&h.interval.as_millis()]))
+                        .small()
+                        .color(egui::Color32::GRAY),
                 );
-                ui.add_sized(
-                    [90.0, 20.0],
-                    egui::Label::new(egui::RichText::new("总内存").strong().color(text_color)),
+                let mut reduced = self.settings.reduced_cadence;
+                if let Some(tip) = h.suggestion(reduced) {
+                    ui.label(egui::RichText::new(format!("💡 {}", tip)).small().color(egui::Color32::GOLD));
+                }
+                ui.horizontal(|ui| {
+                    if ui
+                        .checkbox(&mut reduced, tr("降低刷新频率"))
+                        .on_hover_text(tr("后台刷新间隔加倍 (默认 0.5 秒 -> 1 秒)，慢机器上减少 CPU 占用"))
+                        .changed()
+                    {
+                        self.settings.reduced_cadence = reduced;
+                        self.apply_tuning();
+                    }
+                    if ui.small_button(tr("📋 复制耗时报告")).clicked() {
+                        let report = self.tick_health.report();
+                        ctx.output_mut(|o| o.copied_text = report);
+                        self.toasts.push((Instant::now(), tr("📋 已复制耗时报告").to_string()));
+                    }
+                });
+            });
+    }
+
+    /// 记录面板在本帧的区域 (从 top 到目前已布局内容的底部)，供截图裁剪
+    fn mark_panel(&mut self, ui: &egui::Ui, panel: panel_capture::Panel, top: f32) {
+        if let Some(c) = self.capture.as_mut().filter(|c| c.panel == panel && !c.sent) {
+            let r = ui.min_rect();
+            c.rect = Some(egui::Rect::from_min_max(egui::pos2(r.left(), top), r.right_bottom()));
+        }
+    }
+
+    /// 所有面板布局完成后：在目标面板上绘制水印并请求整窗截图
+    fn paint_capture(&mut self, ui: &egui::Ui, ctx: &egui::Context) {
+        let Some(c) = self.capture.as_mut() else {
+            return;
+        };
+        let Some(rect) = c.rect else {
+            // 面板已被关闭
+            self.capture = None;
+            return;
+        };
+        panel_capture::paint_watermark(ui.painter(), rect, &c.watermark());
+        if !c.sent {
+            c.sent = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot);
+        }
+        ctx.request_repaint();
+    }
+
+    /// 收到截图事件后裁剪面板，写入剪贴板并按需保存
+    fn finish_capture(&mut self, ctx: &egui::Context) {
+        if !self.capture.as_ref().map(|c| c.sent).unwrap_or(false) {
+            return;
+        }
+        let shot = ctx.input(|i| {
+            i.events.iter().find_map(|e| match e {
+                egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                _ => None,
+            })
+        });
+        let Some(shot) = shot else {
+            // 渲染后端不支持截图时不会有事件，超时放弃
+            if self.capture.as_ref().map(|c| c.started.elapsed() > Duration::from_secs(3)).unwrap_or(false) {
+                self.capture = None;
+                self.toasts.push((Instant::now(), tr("❌ 截图超时，当前渲染后端可能不支持").to_string()));
+            }
+            return;
+        };
+        let Some(capture) = self.capture.take() else {
+            return;
+        };
+        let label = capture.panel.label();
+        let Some(image) = capture.rect.and_then(|r| panel_capture::crop(&shot, r, ctx.pixels_per_point())) else {
+            self.toasts.push((Instant::now(), trf("❌ {} 不在窗口可见范围内，无法截图", &[&label])));
+            return;
+        };
+        let mut text = match panel_capture::copy_to_clipboard(&image) {
+            Ok(()) => trf("📷 {} 已复制到剪贴板", &[&label]),
+            Err(e) => trf("❌ {} 截图复制失败：{}", &[&label, &e]),
+        };
+        if capture.save {
+            let dir = app_data_dir().map(|d| d.join("screenshots")).unwrap_or_else(std::env::temp_dir);
+            match panel_capture::save_png(&image, &dir, &capture) {
+                Ok(path) => text.push_str(&trf(" · 已保存 {}", &[&path.display()])),
+                Err(e) => text.push_str(&trf(" · 保存失败：{}", &[&e])),
+            }
+        }
+        self.toasts.push((Instant::now(), text));
+    }
+
+    /// 单个盘的挂载设置：更改盘符 / 移除盘符 / 挂载到文件夹
+    fn render_mount_editor(&mut self, ui: &mut egui::Ui, mount_point: &str) {
+        let drive = norm_drive(mount_point);
+        let busy = self.usb_busy.contains(&drive);
+        let mut op: Option<mount_manager::MountOp> = None;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            if !self.is_admin {
+                ui.label(egui::RichText::new(tr("⚠ 修改挂载点需要管理员权限")).small().color(egui::Color32::GOLD));
+            }
+            ui.horizontal(|ui| {
+                ui.label(tr("更改为"));
+                egui::ComboBox::from_id_source(("mount_letter", &drive))
+                    .width(50.0)
+                    .selected_text(format!("{}:", self.mount_letter))
+                    .show_ui(ui, |ui| {
+                        for letter in mount_manager::free_letters() {
+                            ui.selectable_value(&mut self.mount_letter, letter, format!("{}:", letter));
+                        }
+                    });
+                if ui.add_enabled(!busy, egui::Button::new(tr("更改盘符"))).clicked() {
+                    op = Some(mount_manager::MountOp::ChangeLetter {
+                        drive: drive.clone(),
+                        letter: self.mount_letter,
+                    });
+                }
+                if ui
+                    .add_enabled(!busy, egui::Button::new(tr("移除盘符")))
+                    .on_hover_text(tr("卷不会被卸载，只是不再分配盘符；可在下方“未分配盘符的卷”中加回"))
+                    .clicked()
+                {
+                    op = Some(mount_manager::MountOp::RemoveLetter(drive.clone()));
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("挂载到"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.mount_folder)
+                        .hint_text(tr("NTFS 上的空文件夹，如 C:\\Mount\\U盘"))
+                        .desired_width(220.0),
                 );
-                ui.add_sized(
-                    [70.0, 20.0],
-                    egui::Label::new(egui::RichText::new("总CPU").strong().color(text_color)),
+                if ui.add_enabled(!busy, egui::Button::new(tr("挂载"))).clicked() {
+                    op = Some(mount_manager::MountOp::MountFolder {
+                        drive: drive.clone(),
+                        folder: self.mount_folder.clone(),
+                    });
+                }
+            });
+            for folder in mount_manager::folder_mounts(&drive) {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(format!("📁 {}", folder)).small().color(egui::Color32::GRAY));
+                    if ui.add_enabled(!busy, egui::Button::new(tr("卸下")).small()).clicked() {
+                        op = Some(mount_manager::MountOp::UnmountFolder(folder.clone()));
+                    }
+                });
+            }
+        });
+        if let Some(op) = op {
+            if matches!(op, mount_manager::MountOp::ChangeLetter { .. } | mount_manager::MountOp::RemoveLetter(_)) {
+                self.mount_editor = None;
+            }
+            self.unmounted_volumes = None;
+            self.send_usb_cmd(UsbCmd::Mount(op));
+        }
+    }
+
+    /// 卷标、格式化与写入镜像：格式化和写入镜像需要二次确认，格式化在执行前展示 format 命令行
+    fn render_format_editor(&mut self, ui: &mut egui::Ui, disk: &DiskData) {
+        let drive = norm_drive(&disk.mount_point);
+        let busy = self.usb_busy.contains(&drive);
+        let mut cmd: Option<UsbCmd> = None;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("卷标"));
+                ui.add(egui::TextEdit::singleline(&mut self.relabel).desired_width(160.0));
+                if ui.add_enabled(!busy, egui::Button::new(tr("改卷标"))).clicked() {
+                    cmd = Some(UsbCmd::Relabel(disk.mount_point.clone(), self.relabel.clone()));
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("格式化为"));
+                egui::ComboBox::from_id_source(("format_fs", &drive))
+                    .width(70.0)
+                    .selected_text(self.format_job.fs.label())
+                    .show_ui(ui, |ui| {
+                        for fs in format_volume::FileSystem::ALL {
+                            ui.add_enabled_ui(fs.fits(disk.total_space), |ui| {
+                                ui.selectable_value(&mut self.format_job.fs, fs, fs.label())
+                                    .on_disabled_hover_text(tr("format 不支持把大于 32 GB 的卷格式化为 FAT32"));
+                            });
+                        }
+                    });
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.format_job.label)
+                        .hint_text(tr("新卷标"))
+                        .desired_width(120.0),
                 );
-                ui.add_sized(
-                    [80.0, 20.0],
-                    egui::Label::new(egui::RichText::new("操作").strong().color(text_color)),
+                ui.checkbox(&mut self.format_job.quick, tr("快速"));
+                let confirming = self.format_confirm.as_deref() == Some(drive.as_str());
+                let text = if confirming {
+                    egui::RichText::new(tr("确认清除全部数据？")).color(egui::Color32::from_rgb(255, 80, 80))
+                } else {
+                    egui::RichText::new(tr("格式化"))
+                };
+                if ui
+                    .add_enabled(!busy && self.format_job.fs.fits(disk.total_space), egui::Button::new(text))
+                    .on_hover_text(tr("卷上的所有文件都会被删除；再点一次确认"))
+                    .clicked()
+                {
+                    if confirming {
+                        self.format_confirm = None;
+                        cmd = Some(UsbCmd::Format(disk.mount_point.clone(), self.format_job.clone()));
+                    } else {
+                        self.format_confirm = Some(drive.clone());
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("写入镜像"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.image_job.path)
+                        .hint_text(tr(".img 或混合 ISO 的完整路径"))
+                        .desired_width(220.0),
+                )
+                .on_hover_text(tr("适用于 Linux 发行版等混合 ISO 与 .img；Windows 安装 ISO 原样写入后无法启动"));
+                ui.checkbox(&mut self.image_job.verify, tr("写后校验"));
+                let confirming = self.image_confirm.as_deref() == Some(drive.as_str());
+                let text = if confirming {
+                    egui::RichText::new(tr("确认覆盖整块磁盘？")).color(egui::Color32::from_rgb(255, 80, 80))
+                } else {
+                    egui::RichText::new(tr("写入"))
+                };
+                let ready = !busy && !self.image_job.path.trim().is_empty();
+                if ui
+                    .add_enabled(ready, egui::Button::new(text))
+                    .on_hover_text(tr("整块 U 盘 (含同一设备上的其他分区) 都会被镜像覆盖；再点一次确认"))
+                    .clicked()
+                {
+                    if confirming {
+                        self.image_confirm = None;
+                        let mut job = self.image_job.clone();
+                        job.path = job.path.trim().trim_matches('"').to_string();
+                        cmd = Some(UsbCmd::WriteImage(disk.mount_point.clone(), job));
+                    } else {
+                        self.image_confirm = Some(drive.clone());
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("安全擦除"));
+                ui.add(
+                    egui::DragValue::new(&mut self.wipe_job.passes)
+                        .range(1..=secure_wipe::MAX_PASSES)
+                        .suffix(tr(" 遍")),
+                )
+                .on_hover_text(tr("依次以 0x00 / 0xFF / 随机覆写，最后一遍固定写零"));
+                ui.checkbox(&mut self.wipe_job.verify, tr("校验"));
+                let expected = format!("{}:", drive);
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.wipe_confirm)
+                        .hint_text(trf("输入 {} 确认", &[&expected]))
+                        .desired_width(90.0),
                 );
+                let ready = !busy && norm_drive(self.wipe_confirm.trim()) == drive;
+                if ui
+                    .add_enabled(ready, egui::Button::new(egui::RichText::new(tr("擦除")).color(egui::Color32::from_rgb(255, 80, 80))))
+                    .on_hover_text(tr("整块 U 盘 (含同一设备上的其他分区) 的数据将被覆写且无法恢复，完成后需重新分区或格式化。闪存的磨损均衡可能保留少量旧数据，涉密介质请物理销毁"))
+                    .on_disabled_hover_text(trf("在左侧输入 {} 以确认", &[&expected]))
+                    .clicked()
+                {
+                    self.wipe_confirm.clear();
+                    let cancel = Arc::new(std::sync::atomic::AtomicBool::new(false));
+                    cmd = Some(UsbCmd::Wipe(disk.mount_point.clone(), self.wipe_job.clone(), cancel));
+                }
+            });
+        });
+        if let Some(cmd) = cmd {
+            self.send_usb_cmd(cmd);
+        }
+    }
+
+    /// 按物理设备记住的偏好：下次插入时自动应用
+    fn render_device_prefs(&mut self, ui: &mut egui::Ui, disk: &DiskData) {
+        let drive = norm_drive(&disk.mount_point);
+        let Some((key, draft)) = &mut self.pref_draft else {
+            ui.label(
+                egui::RichText::new(tr("该设备没有序列号，无法按设备记住偏好"))
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+            return;
+        };
+        let mut save = false;
+        let mut run_backup: Option<backup::BackupJob> = None;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.label(egui::RichText::new(tr("📌 设备偏好 (下次插入自动应用)")).small().strong());
+            ui.label(egui::RichText::new(key.as_str()).small().monospace().color(egui::Color32::GRAY));
+            egui::Grid::new(("device_prefs", &drive)).num_columns(2).spacing([10.0, 4.0]).show(ui, |ui| {
+                ui.label(tr("昵称"));
+                ui.add(egui::TextEdit::singleline(&mut draft.nickname).hint_text(tr("如：公司加密盘")).desired_width(160.0));
                 ui.end_row();
 
-                for group in groups {
-                    ui.add_sized(
-                        [40.0, 20.0],
-                        egui::Label::new(
-                            egui::RichText::new(format!("x{}", group.pids.len())).monospace(),
-                        ),
-                    );
+                ui.label(tr("标识"));
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut draft.emoji).hint_text("🔐").desired_width(36.0))
+                        .on_hover_text(tr("显示在昵称前的表情或符号，留空使用总线图标"));
+                    let mut custom = draft.color.is_some();
+                    if ui.checkbox(&mut custom, tr("自定义颜色")).changed() {
+                        draft.color = custom.then_some([255, 215, 0]);
+                    }
+                    if let Some(color) = &mut draft.color {
+                        ui.color_edit_button_srgb(color);
+                    }
+                });
+                ui.end_row();
 
-                    // Name
-                    ui.add_sized([name_col_width, 20.0], |ui: &mut egui::Ui| {
-                        ui.horizontal(|ui| {
-                            let name_color = if is_high {
-                                egui::Color32::from_rgb(255, 140, 0)
-                            } else {
-                                egui::Color32::from_rgb(200, 180, 150)
-                            };
-                            let display = if group.friendly_name.is_empty() {
-                                group.name.clone()
-                            } else {
-                                format!("{} ({})", group.friendly_name, group.name)
-                            };
+                ui.label(tr("首选盘符"));
+                let mut pin = draft.preferred_letter.is_some();
+                let current = drive.chars().next().unwrap_or('Z');
+                if ui.checkbox(&mut pin, trf("总是使用 {}:", &[&draft.preferred_letter.unwrap_or(current)])).changed() {
+                    draft.preferred_letter = pin.then_some(current);
+                }
+                ui.end_row();
 
-                            if !group.category.is_empty() {
-                                ui.label(
-                                    egui::RichText::new(format!("[{}]", group.category))
-                                        .color(egui::Color32::GRAY)
-                                        .small(),
-                                );
-                            }
-                            ui.add(
-                                egui::Label::new(
-                                    egui::RichText::new(display).color(name_color).strong(),
-                                )
-                                .truncate(),
-                            );
+                ui.label(tr("强力清场"));
+                let text = match draft.graceful_close {
+                    None => tr("跟随全局设置"),
+                    Some(true) => tr("先请求文档程序保存"),
+                    Some(false) => tr("直接终止"),
+                };
+                egui::ComboBox::from_id_source(("pref_graceful", &drive)).selected_text(text).show_ui(ui, |ui| {
+                    ui.selectable_value(&mut draft.graceful_close, None, tr("跟随全局设置"));
+                    ui.selectable_value(&mut draft.graceful_close, Some(true), tr("先请求文档程序保存"));
+                    ui.selectable_value(&mut draft.graceful_close, Some(false), tr("直接终止"));
+                });
+                ui.end_row();
 
-                            if group.is_system {
-                                ui.label(
-                                    egui::RichText::new("SYS")
-                                        .small()
-                                        .color(egui::Color32::BROWN),
-                                );
-                            }
-                            if group.is_not_responding {
-                                ui.label(
-                                    egui::RichText::new("DEAD")
-                                        .small()
-                                        .color(egui::Color32::RED),
-                                );
-                            }
-                        })
-                        .response
-                    });
+                ui.label(tr("保存等待"));
+                let mut secs = draft.graceful_wait_secs.unwrap_or(0);
+                if ui
+                    .add(egui::DragValue::new(&mut secs).range(0..=300).suffix(tr(" 秒")))
+                    .on_hover_text(tr("0 表示跟随全局设置"))
+                    .changed()
+                {
+                    draft.graceful_wait_secs = (secs > 0).then_some(secs);
+                }
+                ui.end_row();
 
-                    // Mem
-                    ui.add_sized(
-                        [90.0, 20.0],
-                        egui::Label::new(format!(
-                            "{:.1} MB",
-                            group.total_memory as f32 / 1024.0 / 1024.0
-                        )),
-                    );
+                ui.label(tr("空闲自动弹出"));
+                let mut mins = draft.auto_eject_idle_mins.unwrap_or(0);
+                if ui
+                    .add(egui::DragValue::new(&mut mins).range(0..=240).suffix(tr(" 分钟")))
+                    .on_hover_text(tr("持续无读写达到该时长后自动安全弹出；0 表示关闭"))
+                    .changed()
+                {
+                    draft.auto_eject_idle_mins = (mins > 0).then_some(mins);
+                }
+                ui.end_row();
 
-                    // CPU
-                    let cpu_c = if group.total_cpu > 20.0 {
-                        egui::Color32::RED
-                    } else {
-                        egui::Color32::GOLD
-                    };
-                    ui.add_sized(
-                        [70.0, 20.0],
-                        egui::Label::new(
-                            egui::RichText::new(format!("{:.1}%", group.total_cpu))
-                                .color(cpu_c)
-                                .monospace(),
-                        ),
-                    );
+                ui.label(tr("插入即备份"));
+                let mut enabled = draft.backup.is_some();
+                if ui
+                    .checkbox(&mut enabled, tr("插入后把文件夹镜像到盘上"))
+                    .on_hover_text(tr("robocopy /MIR：盘上的备份目录会与源文件夹保持一致，多余文件会被删除"))
+                    .changed()
+                {
+                    draft.backup = enabled.then(backup::BackupJob::default);
+                }
+                ui.end_row();
 
-                    // Action
-                    ui.add_sized([80.0, 24.0 * scale], |ui: &mut egui::Ui| {
-                        let btn = egui::Button::new(
-                            egui::RichText::new("终止").color(egui::Color32::WHITE),
-                        )
-                        .fill(egui::Color32::from_rgb(180, 40, 40))
-                        .rounding(rounding / 2.0);
-                        let res = ui.add(btn);
-                        if res.clicked() {
-                            let _ = self
-                                .usb_tx
-                                .send(UsbCmd::ForceEject("".into(), group.pids.clone()));
-                        }
-                        res
-                    });
+                if let Some(job) = &mut draft.backup {
+                    ui.label(tr("源文件夹"));
+                    ui.add(egui::TextEdit::singleline(&mut job.source).hint_text(tr("D:\\工作资料")).desired_width(220.0));
+                    ui.end_row();
+
+                    ui.label(tr("盘上目录"));
+                    ui.add(egui::TextEdit::singleline(&mut job.target_dir).desired_width(160.0))
+                        .on_hover_text(trf("备份到 {}", &[&job.destination(&drive).display()]));
+                    ui.end_row();
+
+                    ui.label("");
+                    ui.checkbox(&mut job.eject_after, tr("校验通过后自动弹出"));
                     ui.end_row();
                 }
             });
+            ui.horizontal(|ui| {
+                save = ui.button(tr("保存设备偏好")).clicked();
+                let job = draft.backup.clone().filter(|j| j.is_configured());
+                if let Some(job) = job {
+                    if ui
+                        .add_enabled(!self.backups.contains_key(&drive), egui::Button::new(tr("立即备份")))
+                        .clicked()
+                    {
+                        run_backup = Some(job);
+                    }
+                }
+            });
+        });
+        if save {
+            let (key, draft) = (key.clone(), draft.clone());
+            self.device_prefs.set(&key, draft.clone());
+            let prefs = (!draft.is_empty()).then_some(draft);
+            let _ = self.usb_tx.send(UsbCmd::DevicePrefs(drive.clone(), prefs));
+            self.auto_ejected.remove(&drive);
+            self.toasts.push((Instant::now(), tr("✅ 设备偏好已保存").to_string()));
+        }
+        if let Some(job) = run_backup {
+            self.start_backup(&drive, job, ui.ctx());
+        }
+    }
+
+    /// 没有盘符的可移动卷 (例如刚被移除盘符)，提供重新分配
+    fn render_unmounted_volumes(&mut self, ui: &mut egui::Ui) {
+        if self
+            .unmounted_volumes
+            .as_ref()
+            .map(|(t, _)| t.elapsed() > Duration::from_secs(5))
+            .unwrap_or(true)
+        {
+            self.unmounted_volumes = Some((Instant::now(), mount_manager::unmounted_removable()));
+        }
+        let volumes = self.unmounted_volumes.as_ref().map(|(_, v)| v.clone()).unwrap_or_default();
+        if volumes.is_empty() {
+            return;
+        }
+        let free = mount_manager::free_letters();
+        let mut op: Option<mount_manager::MountOp> = None;
+        ui.label(egui::RichText::new(tr("未分配盘符的卷")).small().color(egui::Color32::GRAY));
+        for v in &volumes {
+            ui.horizontal(|ui| {
+                let label = if v.label.is_empty() { tr("(无卷标)") } else { v.label.as_str() };
+                ui.label(egui::RichText::new(format!("💿 {}", label)).color(egui::Color32::KHAKI))
+                    .on_hover_text(&v.volume);
+                for folder in &v.folders {
+                    ui.label(egui::RichText::new(format!("📁 {}", folder)).small().color(egui::Color32::GRAY));
+                }
+                if let Some(&letter) = free.last() {
+                    if ui.small_button(trf("分配 {}:", &[&letter])).clicked() {
+                        op = Some(mount_manager::MountOp::AssignLetter {
+                            volume: v.volume.clone(),
+                            letter,
+                        });
+                    }
+                }
+            });
+        }
+        if let Some(op) = op {
+            self.unmounted_volumes = None;
+            self.send_usb_cmd(UsbCmd::Mount(op));
+        }
+    }
+
+    /// 后台读取 winget 软件列表 (耗时数秒，5 分钟内复用)
+    /// 启动项在后台线程读取 (注册表、启动文件夹与计划任务目录)，不阻塞界面
+    fn poll_startup_items(&mut self, ctx: &egui::Context) {
+        if let Some(rx) = &self.startup_rx {
+            if let Ok(items) = rx.try_recv() {
+                self.startup_items = Some(items);
+                self.startup_rx = None;
+            }
+        }
+        // 计划任务经确认框与控制台执行，等两者都结束后再重新读取
+        let settled = !self.tool_running() && self.pending_external.is_none();
+        if (self.startup_items.is_none() || (self.startup_dirty && settled)) && self.startup_rx.is_none() {
+            self.startup_dirty = false;
+            let (tx, rx) = mpsc::channel();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                let _ = tx.send(startup_items::enumerate());
+                ctx.request_repaint();
+            });
+            self.startup_rx = Some(rx);
+        }
+    }
+
+    fn poll_winget(&mut self, ctx: &egui::Context) {
+        if let Some(rx) = &self.winget_rx {
+            if let Ok(result) = rx.try_recv() {
+                self.winget_packages = Some((Instant::now(), result));
+                self.winget_rx = None;
+            }
+        }
+        if let Some(rx) = &self.winget_versions_rx {
+            if let Ok((id, versions)) = rx.try_recv() {
+                self.winget_versions.insert(id, versions);
+                self.winget_versions_rx = None;
+            }
+        }
+        let stale = self
+            .winget_packages
+            .as_ref()
+            .map(|(t, _)| t.elapsed() > Duration::from_secs(300))
+            .unwrap_or(true);
+        // winget 正在升级 / 卸载时读到的列表是过时的
+        if stale && self.winget_rx.is_none() && !self.tool_running() {
+            let (tx, rx) = mpsc::channel();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                let _ = tx.send(winget::list());
+                ctx.request_repaint();
+            });
+            self.winget_rx = Some(rx);
+        }
+    }
+
+    /// 展开的分组：逐个 PID 显示内存 / CPU / 命令行 / 启动时间
+    fn render_pid_rows(
+        &mut self,
+        ui: &mut egui::Ui,
+        group: &ProcessGroup,
+        name_col_width: f32,
+        cols: settings::ProcessColumns,
+    ) {
+        let dim = egui::Color32::from_rgb(150, 140, 120);
+        if self.mitigation_cache.len() > 2048 {
+            self.mitigation_cache.clear();
+        }
+        for m in &group.members {
+            // (pid, 启动时间) 作为键，避免 PID 复用后显示旧进程的数据
+            let mitigation = *self
+                .mitigation_cache
+                .entry((m.pid, m.start_time))
+                .or_insert_with(|| mitigations::query(m.pid));
+
+            ui.label(egui::RichText::new("└").color(egui::Color32::DARK_GRAY).monospace());
+
+            ui.add_sized([name_col_width, 18.0], |ui: &mut egui::Ui| {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(format!("PID {}", m.pid)).monospace().small().color(dim));
+                    if let Some(mit) = mitigation {
+                        let color = if mit.dep && mit.aslr && mit.cfg {
+                            egui::Color32::from_rgb(100, 160, 100)
+                        } else {
+                            egui::Color32::from_rgb(200, 150, 80)
+                        };
+                        ui.label(egui::RichText::new(mit.compact()).monospace().small().color(color))
+                            .on_hover_text(mit.tooltip());
+                    }
+                    if let Some(owner) = &m.owner {
+                        let user = owner.account.rsplit('\\').next().unwrap_or(&owner.account);
+                        let color = if owner.is_other_user(&self.current_sid) {
+                            egui::Color32::from_rgb(180, 120, 255)
+                        } else {
+                            egui::Color32::GRAY
+                        };
+                        let shield = if owner.elevated { " 🛡" } else { "" };
+                        ui.label(egui::RichText::new(format!("{}{}", user, shield)).small().color(color))
+                            .on_hover_text(owner.describe());
+                    }
+                    let started = format_unix_local(m.start_time);
+                    if !started.is_empty() {
+                        ui.label(egui::RichText::new(trf("启动 {}", &[&started])).small().color(egui::Color32::GRAY));
+                    }
+                    if !m.cmd.is_empty() {
+                        ui.add(
+                            egui::Label::new(egui::RichText::new(&m.cmd).small().color(egui::Color32::GRAY))
+                                .truncate(),
+                        )
+                        .on_hover_text(&m.cmd);
+                    }
+                })
+                .response
+            });
+
+            if cols.arch {
+                arch_cell(ui, m.arch);
+            }
+
+            let metric = self.settings.memory_metric;
+            ui.label(egui::RichText::new(format!("{:.1} MB", m.usage.get(metric) as f32 / 1024.0 / 1024.0)).small())
+                .on_hover_text(m.usage.describe());
+
+            let cpu_c = if m.cpu > 20.0 {
+                egui::Color32::RED
+            } else {
+                egui::Color32::GOLD
+            };
+            ui.label(egui::RichText::new(format!("{:.1}%", m.cpu)).color(cpu_c).monospace().small());
+            // 路径列对单个进程没有意义 (与分组相同)，留空
+            optional_cells(ui, settings::ProcessColumns { path: false, ..cols }, m.disk_bps, m.gpu, "");
+            if cols.path {
+                ui.label("");
+            }
+
+            if ui
+                .small_button(tr("终止此进程"))
+                .on_hover_text(trf("只终止 PID {}", &[&m.pid]))
+                .clicked()
+            {
+                self.send_usb_cmd(UsbCmd::KillGroup(vec![m.pid], self.kill_tree));
+            }
+            if ui
+                .small_button(tr("重启"))
+                .on_hover_text(tr("结束后按原命令行与工作目录重新启动"))
+                .clicked()
+            {
+                self.send_usb_cmd(UsbCmd::RestartProcess(m.pid));
+            }
+            if ui.small_button(tr("模块")).on_hover_text(tr("列出已加载的 DLL：路径、版本与签名")).clicked() {
+                self.module_view = Some(modules::View::open(m.pid, &group.name));
+            }
+            ui.end_row();
+        }
     }
 }
 
 impl eframe::App for GeekKillerApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.handle_close(ctx);
+        if std::mem::take(&mut self.start_hidden) && tray::is_active() {
+            tray::hide_main_window();
+        }
+        while let Ok(cmd) = self.gated_rx.try_recv() {
+            self.send_usb_cmd(cmd);
+        }
+        while let Ok((pid, name)) = self.watchdog_suspended_rx.try_recv() {
+            if !self.suspended.iter().any(|(p, _)| *p == pid) {
+                self.suspended.push((pid, name));
+            }
+        }
+
         // 处理 USB 消息
         while let Ok(msg) = self.usb_rx.try_recv() {
-            let UsbMsg::State(s) = msg;
+            let s = match msg {
+                UsbMsg::State(s) => s,
+                UsbMsg::Busy(drive, busy) => {
+                    if busy {
+                        self.usb_busy.insert(drive);
+                    } else {
+                        self.usb_busy.remove(&drive);
+                    }
+                    continue;
+                }
+                UsbMsg::Stats(stats) => {
+                    self.eject_stats = stats;
+                    continue;
+                }
+                UsbMsg::KillProgress(progress) => {
+                    self.kill_progress = Some(progress);
+                    continue;
+                }
+                UsbMsg::MacroProgress(progress) => {
+                    self.macro_progress = Some(progress);
+                    continue;
+                }
+                UsbMsg::ForceClear(drive, list) => {
+                    self.request_force_clear(&drive, &list);
+                    continue;
+                }
+            };
+            // 测速结果留在盘符下显示，状态栏只提示一次
+            let s = match s {
+                UsbState::SpeedTested(result) => {
+                    let msg = format!("✅ {}", result.summary());
+                    self.speed_results.insert(norm_drive(&result.drive), result);
+                    UsbState::Done(msg)
+                }
+                s => s,
+            };
             self.usb_state = s;
+            self.kill_progress = None;
+            self.eject_history = None;
             if let UsbState::Done(ref m) = self.usb_state {
+                self.wipe_cancel = None;
+                // 策略写入可能失败 (非管理员)，以注册表实际值为准
+                self.usb_write_policy = read_only::policy_write_protect();
                 self.usb_status_msg = m.clone();
                 self.usb_msg_time = Some(Instant::now());
             } else {
@@ -1455,22 +8216,37 @@ impl eframe::App for GeekKillerApp {
 
         // 读取快照 (非阻塞 & 零拷贝优化)
         // 1. 尝试获取最新数据 (try_read 避免阻塞 UI 线程)
+        let frame_start = Instant::now();
         if !self.paused {
             if let Ok(guard) = self.snapshot.try_read() {
                 // 这里发生了深拷贝，但频率受限于后台刷新率 (0.5Hz - 2Hz)
+                let clone_start = Instant::now();
+                let fresh = guard.taken_at != self.cached_snapshot.taken_at;
                 self.cached_snapshot = Arc::new(guard.clone());
+                if fresh {
+                    self.tick_health.clone.push(clone_start.elapsed());
+                    self.tick_health.record_tick(&self.cached_snapshot.timings);
+                }
             }
         }
         // Arc Clone，非常廉价，可以在每一帧执行
         let snapshot = self.cached_snapshot.clone();
 
         // 2. 处理极简模式切换 (边缘触发)
-        if snapshot.is_resource_tight && !self.last_tight_state {
+        if snapshot.is_resource_tight && !self.last_tight_state && self.settings.auto_low_power {
             // 进入极简模式：自动折叠耗资源面板
             self.show_performance = false;
             self.show_diagnostics = false;
         }
         self.last_tight_state = snapshot.is_resource_tight;
+        self.track_removable_changes(&snapshot, ctx);
+        self.finish_capture(ctx);
+        self.copy_selected_rows(ctx, &snapshot);
+        if self.view == settings::View::Full {
+            self.render_batch_bar(ctx, &snapshot);
+        }
+        self.render_toasts(ctx);
+        self.render_mini_hud(ctx);
 
         let scale = ctx.pixels_per_point();
         let rounding = ui::UiConstants::ROUNDING * scale;
@@ -1503,7 +8279,7 @@ impl eframe::App for GeekKillerApp {
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     if snapshot.is_resource_tight {
                         ui.label(
-                            egui::RichText::new("⚡ 极简模式")
+                            egui::RichText::new(tr("⚡ 极简模式"))
                                 .color(egui::Color32::YELLOW)
                                 .small()
                                 .strong(),
@@ -1524,30 +8300,112 @@ impl eframe::App for GeekKillerApp {
                     ui.label(egui::RichText::new(mode_text).color(mode_color).strong());
                 });
             });
+            if let Some(c) = snapshot.counters {
+                ui.label(
+                    egui::RichText::new(trf(
+                        "进程 {} · 线程 {} · 句柄 {} · 已开机 {}",
+                        &[&c.processes, &c.threads, &c.handles, &system_counters::format_uptime(c.uptime)],
+                    ))
+                    .small()
+                    .monospace()
+                    .color(egui::Color32::from_rgb(150, 140, 120)),
+                );
+            }
             ui.add_space(15.0);
 
             // Controls
+            let mut search_changed = false;
             ui.horizontal(|ui| {
-                ui.label("扫描器:");
+                self.render_view_menu(ui, ctx);
+                ui.toggle_value(&mut self.show_settings, tr("⚙ 设置"));
+                if ui
+                    .button(tr("🗗 悬浮窗"))
+                    .on_hover_text(tr("无边框置顶的小窗口，显示 CPU / 内存 / 网速与占用最高的进程；单击悬浮窗回到主窗口"))
+                    .clicked()
+                {
+                    self.hud_open.store(true, std::sync::atomic::Ordering::SeqCst);
+                }
+                self.render_guest_mode(ui);
+                if self.view == settings::View::Usb {
+                    // 精简视图只保留 U 盘管理
+                    self.show_usb_manager = true;
+                    self.show_performance = false;
+                    self.show_diagnostics = false;
+                    self.show_security = false;
+                    self.show_startup = false;
+                    self.show_network = false;
+                    return;
+                }
+                ui.label(tr("扫描器:"));
                 ui.add(
                     egui::TextEdit::singleline(&mut self.search_query)
-                        .hint_text("搜索进程...")
+                        .hint_text(tr("搜索进程..."))
                         .desired_width(180.0),
-                );
-                ui.toggle_value(&mut self.show_performance, "性能监测");
-                ui.toggle_value(&mut self.show_diagnostics, "智能诊断");
-                ui.toggle_value(&mut self.show_usb_manager, "U盘管理");
+                )
+                .on_hover_text(tr("按进程名、说明、分类或 PID 过滤下方三组进程"));
+                ui.toggle_value(&mut self.search_regex, ".*").on_hover_text(tr("使用正则表达式 (不区分大小写)"));
+                search_changed = self.search.update(&self.search_query, self.search_regex);
+                if let Some(e) = self.search.error() {
+                    ui.colored_label(egui::Color32::YELLOW, tr("⚠ 正则有误")).on_hover_text(e);
+                } else if self.search.is_active() {
+                    let found = snapshot
+                        .high_resource
+                        .iter()
+                        .chain(&snapshot.other_groups)
+                        .chain(&snapshot.system_groups)
+                        .filter(|g| self.search.matches(g))
+                        .count();
+                    let color = if found == 0 { egui::Color32::from_rgb(255, 80, 80) } else { egui::Color32::GRAY };
+                    ui.label(egui::RichText::new(trf("{} 个匹配", &[&found])).small().color(color));
+                }
+                ui.toggle_value(&mut self.show_performance, tr("性能监测"));
+                ui.toggle_value(&mut self.show_diagnostics, tr("智能诊断"));
+                ui.toggle_value(&mut self.show_usb_manager, tr("U盘管理"));
+                ui.toggle_value(&mut self.show_security, tr("安全检查"));
+                ui.toggle_value(&mut self.show_startup, tr("启动项"));
+                ui.toggle_value(&mut self.show_network, tr("网络连接"));
+                ui.checkbox(&mut self.kill_tree, tr("连同子进程"))
+                    .on_hover_text(tr("终止时自底向上结束整棵进程树，防止子进程重新拉起父进程"));
+                let picking = self.picker_rx.is_some();
+                if ui
+                    .add_enabled(!picking, egui::Button::new(if picking { tr("⌖ 点击目标窗口…") } else { tr("⌖ 点选窗口") }))
+                    .on_hover_text(tr("光标变为十字后点击任意窗口 (如全屏卡死的程序)，可终止或挂起其进程；右键或 Esc 取消"))
+                    .clicked()
+                {
+                    self.picked = None;
+                    self.picker_rx = Some(window_picker::start(ctx.clone()));
+                }
+                if ui
+                    .add_enabled(tray::is_active(), egui::Checkbox::new(&mut self.settings.close_to_tray, tr("关闭到托盘")))
+                    .on_hover_text(tr("关闭窗口时隐藏到系统托盘并继续监控，右键托盘图标可一键弹出 U 盘"))
+                    .changed()
+                {
+                    self.settings.save();
+                }
                 
                 ui.separator();
-                let pause_text = if self.paused { "▶️ 恢复刷新" } else { "⏸️ 锁定视图" };
+                let pause_text = if self.paused { tr("▶️ 恢复刷新") } else { tr("⏸️ 锁定视图") };
                 if ui.toggle_value(&mut self.paused, pause_text).clicked() {
                     // 当点击时，cached_snapshot 逻辑会在下一帧 update 中自动处理
                 }
+                self.render_stale_badge(ui, ctx, &snapshot);
             });
             ui.add_space(20.0);
 
+            self.render_gpu_prompt(ui, ctx);
+            self.render_clear_confirm(ctx);
+            self.render_module_view(ctx, &snapshot);
+            self.render_settings_window(ctx);
+            self.render_admin_prompt(ui, ctx);
+            self.render_external_confirm(ui, ctx);
+            self.advance_repair(ctx);
+            self.advance_net_reset(ctx);
+            self.render_console(ui);
+            self.render_picked(ui);
+
             // USB Manager
             if self.show_usb_manager {
+                let top = ui.cursor().top();
                 egui::Frame::group(ui.style())
                     .fill(egui::Color32::from_rgb(30, 25, 20))
                     .stroke(egui::Stroke::new(
@@ -1559,10 +8417,22 @@ impl eframe::App for GeekKillerApp {
                     .show(ui, |ui| {
                         ui.horizontal(|ui| {
                             ui.label(
-                                egui::RichText::new("💾 外部存储管理")
+                                egui::RichText::new(tr("💾 外部存储管理"))
                                     .strong()
                                     .color(primary_color),
                             );
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                capture_button(ui, panel_capture::Panel::Usb, &mut self.capture, &mut self.capture_save);
+                                ui.checkbox(&mut self.scan_on_insert, tr("插入时检查自动运行威胁"))
+                                    .on_hover_text(tr("新插入的盘会检查根目录下的 autorun.inf、隐藏的可执行文件和快捷方式病毒，只提示不删除"));
+                                if ui
+                                    .checkbox(&mut self.usb_write_policy, tr("全局写保护"))
+                                    .on_hover_text(tr("StorageDevicePolicies\\WriteProtect：之后插入的所有 USB 存储均为只读，已插入的需重新插拔 (需要管理员权限)"))
+                                    .changed()
+                                {
+                                    self.send_usb_cmd(UsbCmd::SetWriteProtectPolicy(self.usb_write_policy));
+                                }
+                            });
                         });
                         
                         if !self.usb_status_msg.is_empty() {
@@ -1584,30 +8454,69 @@ impl eframe::App for GeekKillerApp {
                                 ui.horizontal(|ui| {
                                     ui.spinner();
                                     ui.label(egui::RichText::new(msg).color(primary_color));
+                                    if let Some(cancel) = &self.wipe_cancel {
+                                        let cancelling = cancel.load(std::sync::atomic::Ordering::Relaxed);
+                                        if ui
+                                            .add_enabled(!cancelling, egui::Button::new(tr("取消擦除")))
+                                            .on_hover_text(tr("当前块写完后停止；已擦除的部分无法恢复"))
+                                            .clicked()
+                                        {
+                                            cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+                                        }
+                                    }
                                 });
                                 ui.add_space(10.0);
                             }
+                            UsbState::SpeedTesting(p) => {
+                                ui.add(
+                                    egui::ProgressBar::new(p.fraction())
+                                        .desired_width(320.0)
+                                        .text(trf(
+                                            "{} 测速 · {} {}/{} MB · {} MB/s",
+                                            &[
+                                                &p.drive,
+                                                &p.phase.label(),
+                                                &(p.done / 1024 / 1024),
+                                                &(p.total / 1024 / 1024),
+                                                &format!("{:.1}", p.mbps),
+                                            ],
+                                        )),
+                                );
+                                ui.add_space(10.0);
+                            }
                             _ => {}
                         }
 
                         // 渲染磁盘列表
                         let mut removable = Vec::new();
                         for d in &snapshot.disks {
-                            if d.is_removable && d.mount_point.len() <= 3 {
+                            if d.is_removable {
                                 removable.push(d);
                             }
                         }
+                        // 同一物理磁盘的多个分区排在一起，作为一个设备弹出
+                        removable.sort_by(|a, b| (a.disk_number, &a.mount_point).cmp(&(b.disk_number, &b.mount_point)));
+                        let partitions = |disk: &DiskData| -> Vec<String> {
+                            removable
+                                .iter()
+                                .filter(|d| disk.disk_number.is_some() && d.disk_number == disk.disk_number)
+                                .map(|d| norm_drive(&d.mount_point))
+                                .collect()
+                        };
 
                         if removable.is_empty() {
                             ui.label(
-                                egui::RichText::new("未检测到外部驱动器")
+                                egui::RichText::new(tr("未检测到外部驱动器"))
                                     .color(egui::Color32::GRAY),
                             );
                         } else {
                             // Occupied Panel
                             let mut cancel_action = false;
+                            let mut queued_cmd: Option<UsbCmd> = None;
+                            let mut confirm_clear: Option<clear_confirm::PendingClear> = None;
                             if let UsbState::Occupied { drive, list } = &self.usb_state {
                                 let drive_c = drive.clone();
+                                let drive_busy = self.usb_busy.contains(&norm_drive(drive));
                                 egui::Frame::group(ui.style())
                                     .fill(egui::Color32::from_rgb(45, 40, 35))
                                     .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(200, 100, 100)))
@@ -1616,12 +8525,12 @@ impl eframe::App for GeekKillerApp {
                                     .show(ui, |ui| {
                                         ui.horizontal(|ui| {
                                             ui.label(
-                                                egui::RichText::new(format!("⚠️ {} 被占用", drive))
+                                                egui::RichText::new(trf("⚠️ {} 被占用", &[drive]))
                                                     .color(egui::Color32::GOLD)
                                                     .strong(),
                                             );
                                             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                                if ui.button("取消").clicked() {
+                                                if ui.button(tr("取消")).clicked() {
                                                     cancel_action = true;
                                                 }
                                             });
@@ -1633,31 +8542,92 @@ impl eframe::App for GeekKillerApp {
                                         ui.horizontal(|ui| {
                                             // 1. 强力清场 (C位)
                                             let kill_btn = egui::Button::new(
-                                                egui::RichText::new(" 强力清场 ").color(egui::Color32::WHITE).strong()
+                                                egui::RichText::new(tr(" 强力清场 ")).color(egui::Color32::WHITE).strong()
                                             ).fill(egui::Color32::from_rgb(200, 60, 60)).rounding(rounding); // Redder
 
-                                            if ui.add(kill_btn).on_hover_text("强制终止相关进程并弹出").clicked() {
-                                                let pids = list.iter().map(|o| o.pid).collect();
-                                                let _ = self.usb_tx.send(UsbCmd::ForceEject(drive_c.clone(), pids));
+                                            let kill_enabled = !drive_busy && self.pending_clear.is_none();
+                                            if ui.add_enabled(kill_enabled, kill_btn).on_hover_text(tr("强制终止相关进程并弹出")).clicked() {
+                                                if self.settings.confirm_force_clear && !list.is_empty() {
+                                                    confirm_clear = Some(clear_confirm::PendingClear::new(
+                                                        &drive_c,
+                                                        list.iter().map(|o| (o.pid, o.name.clone())).collect(),
+                                                        list.iter().map(|o| o.desc.clone()).collect(),
+                                                    ));
+                                                } else {
+                                                    let pids = list.iter().map(|o| o.pid).collect();
+                                                    queued_cmd = Some(UsbCmd::ForceEject(drive_c.clone(), pids));
+                                                }
                                             }
                                             
                                             ui.add_space(5.0);
 
                                             // 2. 强制卸载 (fsutil)
                                             let fsutil_btn = egui::Button::new(
-                                                egui::RichText::new(" 强制卸载 ").color(egui::Color32::BLACK).strong()
+                                                egui::RichText::new(tr(" 强制卸载 ")).color(egui::Color32::BLACK).strong()
                                             ).fill(egui::Color32::from_rgb(255, 165, 0)).rounding(rounding);
 
-                                            if ui.add(fsutil_btn).on_hover_text("使用系统 fsutil 工具强制卸载卷").clicked() {
-                                                let _ = self.usb_tx.send(UsbCmd::FsutilDismount(drive_c.clone()));
+                                            if ui.add_enabled(!drive_busy, fsutil_btn).on_hover_text(tr("使用系统 fsutil 工具强制卸载卷")).clicked() {
+                                                queued_cmd = Some(UsbCmd::FsutilDismount(drive_c.clone()));
+                                            }
+
+                                            // 3. 只有资源管理器占用：缩略图 / 压缩包预览等外壳扩展握着句柄
+                                            let pids: Vec<u32> = list.iter().map(|o| o.pid).collect();
+                                            if explorer_restart::only_explorer(&pids) {
+                                                ui.add_space(5.0);
+                                                let restart_btn = egui::Button::new(
+                                                    egui::RichText::new(tr(" 重启资源管理器 ")).color(egui::Color32::WHITE).strong()
+                                                ).fill(egui::Color32::from_rgb(60, 110, 180)).rounding(rounding);
+                                                if ui
+                                                    .add_enabled(!drive_busy, restart_btn)
+                                                    .on_hover_text(tr("占用来自资源管理器 (多为缩略图或压缩包预览)：让其正常退出后弹出，再重新启动资源管理器"))
+                                                    .clicked()
+                                                {
+                                                    queued_cmd = Some(UsbCmd::RestartExplorer(drive_c.clone(), pids));
+                                                }
                                             }
                                         });
 
+                                        if let Some(progress) =
+                                            self.kill_progress.as_ref().filter(|p| norm_drive(&p.drive) == norm_drive(drive))
+                                        {
+                                            ui.add_space(6.0);
+                                            ui.horizontal(|ui| {
+                                                ui.spinner();
+                                                ui.label(egui::RichText::new(&progress.stage).small().color(egui::Color32::GOLD));
+                                                if progress.total > 0 {
+                                                    let detail: Vec<String> = progress
+                                                        .results
+                                                        .iter()
+                                                        .map(|r| format!("{} (PID {})：{}", r.name, r.pid, r.outcome.label()))
+                                                        .collect();
+                                                    let denied = progress.results.iter().any(|r| !r.outcome.is_gone());
+                                                    let color = if denied { egui::Color32::KHAKI } else { egui::Color32::GRAY };
+                                                    ui.label(egui::RichText::new(progress.summary()).small().monospace().color(color))
+                                                        .on_hover_text(if detail.is_empty() { tr("等待结果...").to_string() } else { detail.join("\n") });
+                                                }
+                                            });
+                                        }
+
+                                        if ui
+                                            .checkbox(&mut self.eject_policy.graceful_close, tr("清场前先请求文档程序保存"))
+                                            .on_hover_text(tr("对 Word/WPS/记事本 等先发送关闭请求，触发其保存提示，等待后再强制终止"))
+                                            .changed()
+                                        {
+                                            let _ = self.usb_tx.send(UsbCmd::SetPolicy(self.eject_policy.clone()));
+                                        }
+                                        if ui
+                                            .checkbox(&mut self.settings.confirm_force_clear, tr("清场前确认 (5 秒内可撤销)"))
+                                            .on_hover_text(tr("先列出将被终止的进程；确认后挂起它们并倒计时，期间可撤销"))
+                                            .changed()
+                                        {
+                                            self.settings.save();
+                                        }
+
                                         if !list.is_empty() {
                                             ui.add_space(10.0);
                                             ui.separator();
                                             ui.add_space(5.0);
-                                            ui.label(egui::RichText::new("检测到以下占用进程：").small().color(egui::Color32::GRAY));
+                                            ui.label(egui::RichText::new(tr("检测到以下占用进程：")).small().color(egui::Color32::GRAY));
 
                                             egui::ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
                                                 for occ in list {
@@ -1669,32 +8639,44 @@ impl eframe::App for GeekKillerApp {
                                                             ),
                                                             |ui| {
                                                                 let btn = egui::Button::new(
-                                                                    egui::RichText::new("终止").color(egui::Color32::WHITE),
+                                                                    egui::RichText::new(tr("终止")).color(egui::Color32::WHITE),
                                                                 )
                                                                 .fill(egui::Color32::from_rgb(180, 40, 40))
                                                                 .rounding(rounding / 2.0);
 
-                                                                if ui.add(btn).clicked() {
-                                                                    let _ =
-                                                                        self.usb_tx.send(UsbCmd::KillOne(
-                                                                            occ.pid,
-                                                                            drive_c.clone(),
-                                                                        ));
+                                                                if ui.add_enabled(!drive_busy, btn).clicked() {
+                                                                    queued_cmd = Some(UsbCmd::KillOne(occ.pid, drive_c.clone()));
                                                                 }
                                                             },
                                                         );
                                                     });
+                                                    for path in occ.locked_paths.iter().take(5) {
+                                                        let kind = handles::LockKind::classify(path);
+                                                        ui.horizontal(|ui| {
+                                                            ui.add_space(16.0);
+                                                            ui.label(egui::RichText::new(kind.label()).small().color(kind.color()))
+                                                                .on_hover_text(kind.hint());
+                                                            ui.label(egui::RichText::new(path).small().monospace().color(egui::Color32::GRAY));
+                                                        });
+                                                    }
+                                                    if occ.locked_paths.len() > 5 {
+                                                        ui.label(
+                                                            egui::RichText::new(trf("      …还有 {} 个文件", &[&(occ.locked_paths.len() - 5)]))
+                                                                .small()
+                                                                .color(egui::Color32::GRAY),
+                                                        );
+                                                    }
                                                 }
                                             });
                                         } else {
                                             ui.add_space(10.0);
                                             ui.label(
-                                                egui::RichText::new("⚠️ 未检测到用户程序占用，可能是系统核心组件或驱动锁定。")
+                                                egui::RichText::new(tr("⚠️ 未检测到用户程序占用，可能是系统核心组件或驱动锁定。"))
                                                     .color(egui::Color32::KHAKI)
                                                     .italics()
                                             );
                                             ui.label(
-                                                egui::RichText::new("建议关闭所有窗口，或点击上方【强力清场】。")
+                                                egui::RichText::new(tr("建议关闭所有窗口，或点击上方【强力清场】。"))
                                                     .small()
                                                     .color(egui::Color32::GRAY)
                                             );
@@ -1704,9 +8686,21 @@ impl eframe::App for GeekKillerApp {
                             if cancel_action {
                                 self.usb_state = UsbState::Idle;
                             }
+                            if let Some(cmd) = queued_cmd {
+                                self.send_usb_cmd(cmd);
+                            }
+                            if confirm_clear.is_some() {
+                                self.pending_clear = confirm_clear;
+                            }
 
                             // Disk List
-                            for disk in removable {
+                            let tool_busy = self.tool_running();
+                            let mut tool_request: Option<tool_runner::ToolSpec> = None;
+                            for &disk in &removable {
+                                let siblings: Vec<String> = partitions(disk)
+                                    .into_iter()
+                                    .filter(|v| *v != norm_drive(&disk.mount_point))
+                                    .collect();
                                 ui.horizontal(|ui| {
                                     let free_gb =
                                         disk.available_space as f32 / 1024.0 / 1024.0 / 1024.0;
@@ -1718,17 +8712,181 @@ impl eframe::App for GeekKillerApp {
                                         0.0
                                     };
 
+                                    // 按总线类型区分图标与颜色
+                                    let (bus_icon, bus_color) = match disk.bus.as_ref().map(|b| b.kind) {
+                                        Some(usb_info::BusKind::Usb1 | usb_info::BusKind::Usb2) => {
+                                            ("🐢", egui::Color32::from_rgb(255, 165, 0))
+                                        }
+                                        Some(usb_info::BusKind::Uasp) => ("🚀", egui::Color32::from_rgb(0, 206, 209)),
+                                        Some(usb_info::BusKind::Sd) => ("💳", egui::Color32::from_rgb(186, 140, 255)),
+                                        Some(kind) => (kind.icon(), primary_color),
+                                        None => ("💿", primary_color),
+                                    };
+
+                                    // 有昵称时替代卷名，自定义表情与颜色替代总线配色
+                                    let prefs = self.prefs_for(disk).cloned().unwrap_or_default();
+                                    let display_name = prefs.nickname.trim().to_string();
+                                    let display_name = if display_name.is_empty() { disk.name.clone() } else { display_name };
+                                    let bus_icon = if prefs.emoji.trim().is_empty() { bus_icon } else { prefs.emoji.trim() };
+                                    let name_color = prefs
+                                        .color
+                                        .map(|[r, g, b]| egui::Color32::from_rgb(r, g, b))
+                                        .unwrap_or(bus_color);
+
                                     // 左侧：设备信息与进度条
                                     ui.vertical(|ui| {
-                                        // 1. 蓝色设备名称
-                                        ui.label(
-                                            egui::RichText::new(format!(
-                                                "💿 [{}] {} ({:.1}G/{:.1}G)",
-                                                disk.mount_point, disk.name, free_gb, total_gb
-                                            ))
-                                            .color(primary_color) // 舒适的蓝色
-                                            .strong(),
-                                        );
+                                        // 1. 设备名称 (颜色随总线类型)
+                                        ui.horizontal(|ui| {
+                                            ui.label(
+                                                egui::RichText::new(format!(
+                                                    "{} [{}] {} ({:.1}G/{:.1}G)",
+                                                    bus_icon, disk.mount_point, display_name, free_gb, total_gb
+                                                ))
+                                                .color(name_color)
+                                                .strong(),
+                                            );
+                                            if disk.read_only {
+                                                ui.label(
+                                                    egui::RichText::new(tr("🔒 只读"))
+                                                        .small()
+                                                        .strong()
+                                                        .color(egui::Color32::from_rgb(120, 200, 255)),
+                                                )
+                                                .on_hover_text(tr("该卷以只读方式挂载，不会产生任何写入"));
+                                            }
+                                            if let Some(policy) = disk.removal_policy {
+                                                use removal_policy::Policy;
+                                                let (text, color) = match policy.current {
+                                                    Policy::BetterPerformance => (tr("⚡ 写缓存"), egui::Color32::GOLD),
+                                                    _ => (tr("快速删除"), egui::Color32::GRAY),
+                                                };
+                                                let target = match policy.pending.unwrap_or(policy.current) {
+                                                    Policy::BetterPerformance => Policy::QuickRemoval,
+                                                    _ => Policy::BetterPerformance,
+                                                };
+                                                let mut hint = trf("删除策略：{}\n{}", &[&policy.current.label(), &policy.current.hint()]);
+                                                if let Some(pending) = policy.pending {
+                                                    hint.push_str(&trf("\n已改为“{}”，重新插拔后生效", &[&pending.label()]));
+                                                }
+                                                hint.push_str(&trf("\n点击切换为“{}” (需要管理员权限)", &[&target.label()]));
+                                                let label = if policy.pending.is_some() { trf("{} → 待生效", &[&text]) } else { text.to_string() };
+                                                if ui
+                                                    .add(egui::Label::new(egui::RichText::new(label).small().color(color)).sense(egui::Sense::click()))
+                                                    .on_hover_text(hint)
+                                                    .clicked()
+                                                {
+                                                    self.send_usb_cmd(UsbCmd::SetRemovalPolicy(disk.mount_point.clone(), target));
+                                                }
+                                            }
+                                            if let Some(bus) = &disk.bus {
+                                                let speed = ui.label(
+                                                    egui::RichText::new(&bus.speed_label)
+                                                        .small()
+                                                        .color(bus_color),
+                                                );
+                                                if bus.capable_of_faster {
+                                                    speed.on_hover_text(
+                                                        tr("该设备支持 USB 3.x，但当前以 2.0 速率运行。换到蓝色接口或更换线材可大幅提速。"),
+                                                    );
+                                                    ui.label(
+                                                        egui::RichText::new(tr("⚠ 可提速"))
+                                                            .small()
+                                                            .color(egui::Color32::GOLD),
+                                                    );
+                                                }
+                                            }
+                                        });
+
+                                        if let Some(threats) = self.autorun_threats.get(&norm_drive(&disk.mount_point)) {
+                                            egui::CollapsingHeader::new(
+                                                egui::RichText::new(trf("⚠ 根目录有 {} 个可疑项，打开前请确认", &[&threats.len()]))
+                                                    .small()
+                                                    .color(egui::Color32::from_rgb(255, 80, 80)),
+                                            )
+                                            .id_source(("autorun_threats", &disk.mount_point))
+                                            .show(ui, |ui| {
+                                                for threat in threats {
+                                                    ui.label(egui::RichText::new(format!("• {}", threat.describe())).small());
+                                                }
+                                                ui.label(
+                                                    egui::RichText::new(tr("建议先“只读挂载”，并用杀毒软件检查后再打开"))
+                                                        .small()
+                                                        .color(egui::Color32::GRAY),
+                                                );
+                                            });
+                                        }
+
+                                        if let Some(state) = self.backups.get(&norm_drive(&disk.mount_point)) {
+                                            ui.horizontal(|ui| {
+                                                ui.add(
+                                                    egui::ProgressBar::new(state.ratio())
+                                                        .desired_width(260.0)
+                                                        .desired_height(12.0)
+                                                        .fill(egui::Color32::from_rgb(70, 130, 180))
+                                                        .text(
+                                                            egui::RichText::new(trf(
+                                                                "🗄 {} · {}/{} 个文件",
+                                                                &[&state.stage, &state.done_files, &state.total_files],
+                                                            ))
+                                                            .small()
+                                                            .color(egui::Color32::WHITE),
+                                                        ),
+                                                );
+                                                if ui.small_button(tr("取消")).on_hover_text(tr("停止 robocopy，已复制的文件保留")).clicked() {
+                                                    state.cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+                                                }
+                                            });
+                                        }
+
+                                        // 同型号的几个 U 盘靠 VID/PID 与序列号区分
+                                        if let Some(identity) = disk.identity.as_ref().filter(|i| !i.summary().is_empty()) {
+                                            let full = trf(
+                                                "名称：{}\nVID/PID：{}\n序列号：{}\n连接：{}",
+                                                &[
+                                                    &identity.friendly_name,
+                                                    &identity.vid_pid().unwrap_or_else(|| "-".to_string()),
+                                                    &if identity.serial.is_empty() { tr("无") } else { identity.serial.as_str() },
+                                                    &disk.bus.as_ref().map(|b| b.speed_label.as_str()).unwrap_or("-"),
+                                                ],
+                                            );
+                                            ui.label(
+                                                egui::RichText::new(identity.summary())
+                                                    .small()
+                                                    .monospace()
+                                                    .color(egui::Color32::GRAY),
+                                            )
+                                            .on_hover_text(full);
+                                        }
+
+                                        if let Some(Ok(health)) = &disk.smart {
+                                            smart_label(ui, health);
+                                        }
+
+                                        if let Some(result) = self.speed_results.get(&norm_drive(&disk.mount_point)) {
+                                            let color = if result.mismatched > 0 {
+                                                egui::Color32::from_rgb(255, 80, 80)
+                                            } else {
+                                                egui::Color32::GRAY
+                                            };
+                                            ui.label(
+                                                egui::RichText::new(trf(
+                                                    "⏱ 写 {} MB/s · 读 {} MB/s",
+                                                    &[&format!("{:.1}", result.write_mbps), &format!("{:.1}", result.read_mbps)],
+                                                ))
+                                                .small()
+                                                .color(color),
+                                            )
+                                            .on_hover_text(result.summary());
+                                        }
+
+                                        if !siblings.is_empty() {
+                                            let others: Vec<String> = siblings.iter().map(|v| mount_manager::display_name(v)).collect();
+                                            ui.label(
+                                                egui::RichText::new(trf("🔗 与 {} 同属一个物理设备，弹出时一并卸载", &[&others.join(" ")]))
+                                                    .small()
+                                                    .color(egui::Color32::GRAY),
+                                            );
+                                        }
 
                                         // 2. 容量进度条
                                         ui.add(
@@ -1736,70 +8894,469 @@ impl eframe::App for GeekKillerApp {
                                                 .desired_width(320.0)
                                                 .desired_height(6.0)
                                                 .rounding(rounding)
-                                                .fill(primary_color)
+                                                .fill(bus_color)
                                                 .animate(false)
                                         );
+
+                                        // 3. 实时写入速率：拷贝还在进行时提醒不要急着弹出
+                                        if disk.write_bps > 0 || disk.read_bps > 0 {
+                                            let write_mb = disk.write_bps as f32 / 1024.0 / 1024.0;
+                                            let read_mb = disk.read_bps as f32 / 1024.0 / 1024.0;
+                                            // 以 100 MB/s 为满格，超出即满
+                                            ui.add(
+                                                egui::ProgressBar::new((write_mb / 100.0).min(1.0))
+                                                    .desired_width(320.0)
+                                                    .desired_height(12.0)
+                                                    .fill(egui::Color32::from_rgb(255, 140, 0))
+                                                    .text(
+                                                        egui::RichText::new(trf(
+                                                            "✍ 写入 {} MB/s · 读取 {} MB/s",
+                                                            &[&format!("{:.1}", write_mb), &format!("{:.1}", read_mb)],
+                                                        ))
+                                                        .small()
+                                                        .color(egui::Color32::WHITE),
+                                                    ),
+                                            );
+                                        }
+
+                                        // 4. 上次写入时间：静默足够久才显示可安全弹出
+                                        if let Some(since) = disk.since_last_write {
+                                            let (text, color) = if since >= RECENT_WRITE_GUARD {
+                                                (
+                                                    trf("✅ 可以安全弹出 (上次写入 {} 秒前)", &[&since.as_secs()]),
+                                                    egui::Color32::GREEN,
+                                                )
+                                            } else {
+                                                (tr("⏳ 刚刚还在写入，请稍候再弹出").to_string(), egui::Color32::GOLD)
+                                            };
+                                            ui.label(egui::RichText::new(text).small().color(color));
+                                        }
                                     });
 
                                     // 右侧：安全弹出按钮
                                     ui.with_layout(
                                         egui::Layout::right_to_left(egui::Align::Center),
                                         |ui| {
+                                            let drive_key = norm_drive(&disk.mount_point);
+                                            // 最近几秒有写入：第一次点击只进入确认状态，防止拷贝未完成就弹出
+                                            let recently_written = disk.write_bps > 0
+                                                || disk
+                                                    .since_last_write
+                                                    .map(|t| t < RECENT_WRITE_GUARD)
+                                                    .unwrap_or(false);
+                                            let confirming = self.eject_confirm.as_deref() == Some(drive_key.as_str());
+                                            // 校验未通过或仍在进行：同样需要二次确认
+                                            let verify_failed = self.verifies.contains_key(&drive_key)
+                                                || matches!(self.verify_reports.get(&drive_key), Some(Ok(r)) if !r.is_ok());
+
                                             // 统一“安全弹出”按钮风格
+                                            let (label, fill) = if confirming {
+                                                (tr("  仍要弹出？ "), egui::Color32::from_rgb(255, 140, 0))
+                                            } else {
+                                                (tr("  安全弹出  "), egui::Color32::from_rgb(46, 139, 87)) // SeaGreen
+                                            };
                                             let btn = egui::Button::new(
-                                                egui::RichText::new("  安全弹出  ")
+                                                egui::RichText::new(label)
                                                     .color(egui::Color32::WHITE)
                                                     .strong(),
                                             )
-                                            .fill(egui::Color32::from_rgb(46, 139, 87)) // SeaGreen
+                                            .fill(fill)
                                             .rounding(rounding)
                                             .min_size(egui::vec2(80.0, 28.0));
 
                                             ui.add_space(5.0);
-                                            if ui.add(btn).clicked() {
-                                                let _ = self
-                                                    .usb_tx
-                                                    .send(UsbCmd::Scan(disk.mount_point.clone()));
+                                            // 该盘有操作在执行时禁用，防止重复排队
+                                            let busy = self.usb_busy.contains(&drive_key);
+                                            let res = ui
+                                                .add_enabled(!busy, btn)
+                                                .on_disabled_hover_text(tr("正在处理该驱动器，请稍候..."));
+                                            let res = if confirming && verify_failed {
+                                                res.on_hover_text(tr("拷贝校验未通过或尚未完成，确认不需要这些文件后再点一次"))
+                                            } else if confirming {
+                                                res.on_hover_text(tr("该盘几秒前还在写入，确认数据已拷贝完成后再点一次"))
+                                            } else {
+                                                res
+                                            };
+                                            if ui
+                                                .add_enabled(!tool_busy, egui::Button::new(tr("检查")))
+                                                .on_hover_text(tr("chkdsk 只读检查文件系统错误 (不做修改)"))
+                                                .clicked()
+                                            {
+                                                tool_request = Some(geek_commands::chkdsk_spec(&disk.mount_point));
+                                            }
+                                            if ui
+                                                .add_enabled(!busy, egui::Button::new(tr("测速")))
+                                                .on_hover_text(tr("绕过系统缓存顺序写入再读回最多 256 MB 临时文件，测得真实读写速度并核对读回的数据"))
+                                                .clicked()
+                                            {
+                                                self.send_usb_cmd(UsbCmd::SpeedTest(disk.mount_point.clone()));
+                                            }
+                                            // 插入检查发现可疑项：第一次点击只进入确认状态
+                                            let suspicious = self.autorun_threats.contains_key(&drive_key);
+                                            let open_confirming = self.open_confirm.as_deref() == Some(drive_key.as_str());
+                                            let open_label = if open_confirming {
+                                                egui::RichText::new(tr("仍要打开？")).color(egui::Color32::from_rgb(255, 140, 0))
+                                            } else if suspicious {
+                                                egui::RichText::new(tr("⚠ 打开")).color(egui::Color32::from_rgb(255, 80, 80))
+                                            } else {
+                                                egui::RichText::new(tr("打开"))
+                                            };
+                                            let open_hint = if suspicious {
+                                                tr("根目录有可疑文件，不要双击其中的程序或快捷方式；再点一次确认打开")
+                                            } else {
+                                                tr("在资源管理器中打开 (弹出时会自动关闭这些窗口)")
+                                            };
+                                            if ui.button(open_label).on_hover_text(open_hint).clicked() {
+                                                if suspicious && !open_confirming {
+                                                    self.open_confirm = Some(drive_key.clone());
+                                                } else {
+                                                    self.open_confirm = None;
+                                                    explorer_windows::open_drive(&disk.mount_point);
+                                                }
+                                            }
+                                            if ui
+                                                .add_enabled(!busy, egui::SelectableLabel::new(disk.read_only, tr("只读挂载")))
+                                                .on_hover_text(tr("以只读方式重新挂载，浏览来路不明的 U 盘时不会写入任何数据 (拔出后失效，需要管理员权限)"))
+                                                .clicked()
+                                            {
+                                                self.send_usb_cmd(UsbCmd::SetReadOnly(disk.mount_point.clone(), !disk.read_only));
+                                            }
+                                            if ui
+                                                .add_enabled(!busy, egui::SelectableLabel::new(disk.write_protected, tr("🔒 写保护")))
+                                                .on_hover_text(tr("为整块磁盘设置持久的只读属性 (同 diskpart attributes disk set readonly)，本机重新插入后仍然只读；换到别的电脑不生效"))
+                                                .clicked()
+                                            {
+                                                self.send_usb_cmd(UsbCmd::SetWriteProtect(disk.mount_point.clone(), !disk.write_protected));
+                                            }
+                                            let editing = self.mount_editor.as_deref() == Some(drive_key.as_str());
+                                            if ui
+                                                .selectable_label(editing, tr("设置"))
+                                                .on_hover_text(tr("盘符、挂载到文件夹、按设备记住的偏好"))
+                                                .clicked()
+                                            {
+                                                self.mount_editor = if editing { None } else { Some(drive_key.clone()) };
+                                                self.mount_letter = mount_manager::free_letters().last().copied().unwrap_or('Z');
+                                                self.relabel = disk.name.clone();
+                                                self.format_job = format_volume::FormatJob {
+                                                    label: disk.name.clone(),
+                                                    ..Default::default()
+                                                };
+                                                self.format_confirm = None;
+                                                self.image_confirm = None;
+                                                self.wipe_confirm.clear();
+                                                self.pref_draft = disk.device_key.as_ref().map(|k| {
+                                                    (k.clone(), self.device_prefs.get(k).cloned().unwrap_or_default())
+                                                });
+                                            }
+                                            if res.clicked() {
+                                                if (recently_written || verify_failed) && !confirming {
+                                                    self.eject_confirm = Some(drive_key.clone());
+                                                } else {
+                                                    self.eject_confirm = None;
+                                                    let _ = self
+                                                        .usb_tx
+                                                        .send(UsbCmd::Scan(disk.mount_point.clone()));
+                                                }
                                             }
                                         },
                                     );
                                 });
+                                if self.mount_editor.as_deref() == Some(norm_drive(&disk.mount_point).as_str()) {
+                                    self.render_mount_editor(ui, &disk.mount_point);
+                                    self.render_format_editor(ui, disk);
+                                    self.render_verify(ui, &disk.mount_point);
+                                    self.render_device_prefs(ui, disk);
+                                }
                                 ui.add_space(8.0);
                             }
+                            if let Some(spec) = tool_request {
+                                self.start_tool(spec, ctx);
+                            }
+                        }
+
+                        self.render_unmounted_volumes(ui);
+
+                        // 弹出成功率统计
+                        if self.eject_stats.total_attempts() > 0 {
+                            egui::CollapsingHeader::new(
+                                egui::RichText::new(tr("📈 弹出成功率统计")).small().color(egui::Color32::GRAY),
+                            )
+                            .default_open(false)
+                            .show(ui, |ui| {
+                                egui::Grid::new("eject_stats_grid").num_columns(3).spacing([15.0, 4.0]).show(ui, |ui| {
+                                    for method in EjectMethod::ALL {
+                                        let m = self.eject_stats.get(method);
+                                        ui.label(method.label());
+                                        ui.label(format!("{}/{}", m.successes, m.attempts));
+                                        let rate = m.success_rate() * 100.0;
+                                        let color = if m.attempts == 0 {
+                                            egui::Color32::GRAY
+                                        } else if rate >= 80.0 {
+                                            egui::Color32::GREEN
+                                        } else if rate >= 40.0 {
+                                            egui::Color32::GOLD
+                                        } else {
+                                            egui::Color32::from_rgb(255, 80, 80)
+                                        };
+                                        ui.label(egui::RichText::new(format!("{:.0}%", rate)).color(color).monospace());
+                                        ui.end_row();
+                                    }
+                                });
+                                if self.eject_stats.unresolved > 0 {
+                                    ui.label(
+                                        egui::RichText::new(trf("全部方法均失败：{} 次", &[&self.eject_stats.unresolved]))
+                                            .small()
+                                            .color(egui::Color32::KHAKI),
+                                    );
+                                }
+                            });
                         }
+
+                        // 弹出历史 (只在展开时读取文件)
+                        egui::CollapsingHeader::new(
+                            egui::RichText::new(tr("📜 弹出历史")).small().color(egui::Color32::GRAY),
+                        )
+                        .default_open(false)
+                        .show(ui, |ui| {
+                            let records = self.eject_history.get_or_insert_with(|| eject_history::load_recent(200));
+                            if records.is_empty() {
+                                ui.label(egui::RichText::new(tr("暂无记录")).small().color(egui::Color32::GRAY));
+                                return;
+                            }
+                            egui::ScrollArea::vertical().max_height(220.0).id_source("eject_history_scroll").show(ui, |ui| {
+                                egui::Grid::new("eject_history_grid").num_columns(5).spacing([12.0, 4.0]).striped(true).show(ui, |ui| {
+                                    for r in records.iter() {
+                                        ui.label(egui::RichText::new(&r.time).small().monospace());
+                                        let drive = ui.label(egui::RichText::new(&r.drive).small().strong());
+                                        if r.volume_serial.is_some() || r.device.is_some() {
+                                            drive.on_hover_text(trf(
+                                                "卷序列号：{}\n设备：{}",
+                                                &[&r.volume_serial.as_deref().unwrap_or("-"), &r.device.as_deref().unwrap_or("-")],
+                                            ));
+                                        }
+                                        ui.label(egui::RichText::new(r.method.label()).small());
+                                        let (icon, color) = if r.ok {
+                                            ("✅", egui::Color32::GREEN)
+                                        } else {
+                                            ("❌", egui::Color32::from_rgb(255, 80, 80))
+                                        };
+                                        ui.label(egui::RichText::new(format!("{} {}", icon, r.message)).small().color(color));
+                                        if r.killed.is_empty() {
+                                            ui.label("");
+                                        } else {
+                                            let names: Vec<&str> = r.killed.iter().map(|k| k.name.as_str()).collect();
+                                            ui.label(
+                                                egui::RichText::new(format!("☠ {}", names.join(", ")))
+                                                    .small()
+                                                    .color(egui::Color32::KHAKI),
+                                            )
+                                            .on_hover_text(
+                                                r.killed.iter().map(|k| format!("{} (PID {})", k.name, k.pid)).collect::<Vec<_>>().join("\n"),
+                                            );
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                            });
+                            if let Some(path) = eject_history::log_path() {
+                                ui.label(egui::RichText::new(path.display().to_string()).small().color(egui::Color32::DARK_GRAY));
+                            }
+                        });
                     });
+                self.mark_panel(ui, panel_capture::Panel::Usb, top);
                 ui.add_space(10.0);
             }
 
             // Diagnostics
             if self.show_diagnostics {
+                let top = ui.cursor().top();
                 egui::Frame::group(ui.style()).show(ui, |ui| {
-                    ui.label(
-                        egui::RichText::new("🔍 智能诊断")
-                            .strong()
-                            .color(egui::Color32::GOLD),
-                    );
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(tr("🔍 智能诊断"))
+                                .strong()
+                                .color(egui::Color32::GOLD),
+                        );
+                        capture_button(ui, panel_capture::Panel::Diagnostics, &mut self.capture, &mut self.capture_save);
+                    });
                     if snapshot.is_resource_tight {
                         ui.label(
-                            egui::RichText::new("⚠️ 资源紧张，已进入极简模式")
+                            egui::RichText::new(tr("⚠️ 资源紧张，已进入极简模式"))
                                 .color(egui::Color32::RED),
                         );
                     } else {
                         ui.label(
-                            egui::RichText::new("✨ 系统运行流畅").color(egui::Color32::GREEN),
+                            egui::RichText::new(tr("✨ 系统运行流畅")).color(egui::Color32::GREEN),
                         );
                     }
+                    self.render_memory_trim(ui, ctx, &snapshot);
+
+                    // 提交未知进程：导出为社区数据包模板，方便贡献到进程数据库
+                    ui.horizontal(|ui| {
+                        let count = snapshot.unknown_processes.len();
+                        if ui
+                            .add_enabled(count > 0, egui::Button::new(trf("📤 提交未知进程 ({})", &[&count])))
+                            .on_hover_text(tr("导出未收录进程的名称与文件描述，可分享给维护者或放入 packs 目录"))
+                            .clicked()
+                        {
+                            self.export_msg = Some(match process_db::export_unknown(&snapshot.unknown_processes) {
+                                Ok(path) => {
+                                    unelevated::reveal_in_explorer(&path.to_string_lossy());
+                                    trf("已导出到 {}", &[&path.display()])
+                                }
+                                Err(e) => trf("❌ 导出失败：{}", &[&e]),
+                            });
+                        }
+                        if let Some(msg) = &self.export_msg {
+                            ui.label(egui::RichText::new(msg).small().color(egui::Color32::GRAY));
+                        }
+                    });
+
+                    // 内存与页面文件
+                    ui.separator();
+                    let report = &snapshot.memory_report;
+                    ui.label(egui::RichText::new(tr("🧠 内存与页面文件")).strong().color(egui::Color32::from_rgb(0, 206, 209)));
+                    egui::Grid::new("memory_diag_grid").num_columns(2).spacing([10.0, 4.0]).show(ui, |ui| {
+                        ui.label(tr("页面文件:"));
+                        ui.label(report.pagefile.describe())
+                            .on_hover_text(report.pagefile.entries.join("\n"));
+                        ui.end_row();
+                        if report.commit_limit > 0 {
+                            ui.label(tr("提交内存:"));
+                            ui.label(format!(
+                                "{:.1} GB / {:.1} GB",
+                                report.commit_used as f32 / 1024.0 / 1024.0 / 1024.0,
+                                report.commit_limit as f32 / 1024.0 / 1024.0 / 1024.0
+                            ));
+                            ui.end_row();
+                        }
+                        if let Some(rate) = report.hard_faults_per_sec {
+                            ui.label(tr("硬错误:"));
+                            let color = if report.in_storm { egui::Color32::RED } else { egui::Color32::GRAY };
+                            ui.label(
+                                egui::RichText::new(trf("{} 页/秒 (10 分钟内风暴 {} 次)", &[&format!("{:.0}", rate), &report.recent_storms]))
+                                    .color(color),
+                            );
+                            ui.end_row();
+                        }
+                    });
+                    for a in &report.advice {
+                        let (icon, color) = match a.severity {
+                            memory_advisor::Severity::Info => ("ℹ", egui::Color32::GRAY),
+                            memory_advisor::Severity::Warn => ("⚠", egui::Color32::GOLD),
+                            memory_advisor::Severity::Critical => ("⛔", egui::Color32::RED),
+                        };
+                        ui.label(egui::RichText::new(format!("{} {}", icon, a.text)).small().color(color));
+                    }
+                    self.render_browser_memory(ui, &snapshot);
+                    let hint = if self.is_admin {
+                        tr("下次重启时运行 Windows 内存诊断 (约 10-20 分钟)，用于排查随机蓝屏与程序崩溃")
+                    } else {
+                        tr("需要以管理员身份运行")
+                    };
+                    if ui
+                        .add_enabled(self.is_admin && !self.tool_running(), egui::Button::new(tr("🧪 安排内存诊断")))
+                        .on_hover_text(hint)
+                        .on_disabled_hover_text(hint)
+                        .clicked()
+                    {
+                        self.start_tool(memory_advisor::memdiag_spec(), ctx);
+                    }
+                    self.render_hibernation(ui, ctx);
+                    ui.separator();
+
+                    // 系统修复：DISM RestoreHealth -> sfc /scannow
+                    ui.horizontal(|ui| {
+                        let repairing = self.repair.as_ref().map(|r| !r.is_done()).unwrap_or(false);
+                        let hint = if self.is_admin {
+                            tr("先修复组件存储 (DISM)，再检查系统文件 (sfc)，通常需要 10-30 分钟")
+                        } else {
+                            tr("需要以管理员身份运行")
+                        };
+                        if ui
+                            .add_enabled(
+                                self.is_admin && !repairing && !self.tool_running(),
+                                egui::Button::new(tr("🛠 系统修复")),
+                            )
+                            .on_hover_text(hint)
+                            .on_disabled_hover_text(hint)
+                            .clicked()
+                        {
+                            self.request_repair(ctx);
+                        }
+                        if let Some(session) = &self.repair {
+                            if let Some(step) = session.current {
+                                ui.add(
+                                    egui::ProgressBar::new(session.overall_progress())
+                                        .desired_width(220.0)
+                                        .text(format!("{} {:.0}%", step.label(), session.progress * 100.0)),
+                                );
+                            }
+                        }
+                    });
+                    let mut clear_repair = false;
+                    if let Some(session) = &self.repair {
+                        for r in &session.results {
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(r.step.label()).small().color(egui::Color32::GRAY));
+                                ui.label(egui::RichText::new(&r.summary).small().color(r.verdict.color()));
+                            });
+                        }
+                        if session.is_done() && ui.small_button(tr("清除修复结果")).clicked() {
+                            clear_repair = true;
+                        }
+                    }
+                    if clear_repair {
+                        self.repair = None;
+                    }
+                    self.render_quick_fixes(ui, ctx);
+                    self.render_watchdog(ui, &snapshot);
+                    self.render_macros(ui);
+                    self.render_process_history(ui);
+                    self.render_process_compare(ui, &snapshot);
+                    self.render_screen_time(ui, &snapshot);
+                    self.render_tick_health(ui, ctx);
                 });
+                self.mark_panel(ui, panel_capture::Panel::Diagnostics, top);
+                ui.add_space(10.0);
+            }
+
+            // Security
+            if self.show_security {
+                let top = ui.cursor().top();
+                self.render_security_panel(ui, ctx, &snapshot);
+                self.mark_panel(ui, panel_capture::Panel::Security, top);
+                ui.add_space(10.0);
+            }
+
+            // Startup items
+            if self.show_startup {
+                let top = ui.cursor().top();
+                self.render_startup_panel(ui, ctx, &snapshot);
+                self.mark_panel(ui, panel_capture::Panel::Startup, top);
+                ui.add_space(10.0);
+            }
+
+            // Network connections
+            if self.show_network {
+                let top = ui.cursor().top();
+                self.render_network_panel(ui, &snapshot);
+                self.render_net_reset(ui, ctx);
+                self.mark_panel(ui, panel_capture::Panel::Network, top);
                 ui.add_space(10.0);
             }
 
             // Performance
             if self.show_performance {
+                let top = ui.cursor().top();
                 egui::Frame::group(ui.style())
                     .fill(egui::Color32::from_rgb(25, 20, 20))
                     .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(50, 50, 50)))
                     .show(ui, |ui| {
-                        ui.label(egui::RichText::new("📊 系统遥测面板").strong().color(egui::Color32::GOLD));
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new(tr("📊 系统遥测面板")).strong().color(egui::Color32::GOLD));
+                            capture_button(ui, panel_capture::Panel::Performance, &mut self.capture, &mut self.capture_save);
+                        });
                         ui.add_space(5.0);
 
                         let make_color = |val: f32, warn: f32, crit: f32| {
@@ -1814,14 +9371,14 @@ impl eframe::App for GeekKillerApp {
 
                         egui::Grid::new("perf_grid").num_columns(2).spacing([10.0, 8.0]).show(ui, |ui| {
                             // CPU
-                            ui.label("中央处理器 (CPU):");
+                            ui.label(tr("中央处理器 (CPU):"));
                             let cpu_color = make_color(snapshot.global_cpu, 50.0, 80.0);
                             let cpu_text = egui::RichText::new(format!("{:.1}%", snapshot.global_cpu)).color(egui::Color32::WHITE).strong();
                             ui.add(egui::ProgressBar::new(snapshot.global_cpu / 100.0).text(cpu_text).fill(cpu_color));
                             ui.end_row();
 
                             // RAM
-                            ui.label("物理内存 (RAM):");
+                            ui.label(tr("物理内存 (RAM):"));
                             let mem_pct = snapshot.used_memory as f32 / snapshot.total_memory as f32;
                             let mem_color = make_color(mem_pct * 100.0, 60.0, 85.0);
                             let mem_text = egui::RichText::new(format!(
@@ -1833,7 +9390,7 @@ impl eframe::App for GeekKillerApp {
                             ui.end_row();
 
                             // NET
-                            ui.label("网络流量 (NET):");
+                            ui.label(tr("网络流量 (NET):"));
                             let in_kb = snapshot.network_in as f32 / 1024.0;
                             let out_kb = snapshot.network_out as f32 / 1024.0;
 
@@ -1849,80 +9406,184 @@ impl eframe::App for GeekKillerApp {
                             ui.end_row();
 
                             // DISK
-                            ui.label("磁盘存储 (DISK):");
+                            ui.label(tr("磁盘存储 (DISK):"));
                             if let Some(sys_disk) = snapshot.disks.iter().find(|d| d.mount_point.contains("C:")) {
                                 let total_gb = sys_disk.total_space as f32 / 1024.0 / 1024.0 / 1024.0;
                                 let free_gb = sys_disk.available_space as f32 / 1024.0 / 1024.0 / 1024.0;
-                                ui.label(format!("{:.1}GB 可用 / {:.1}GB 总计", free_gb, total_gb));
+                                ui.label(trf("{}GB 可用 / {}GB 总计", &[&format!("{:.1}", free_gb), &format!("{:.1}", total_gb)]));
                             } else {
                                 ui.label("N/A");
                             }
                             ui.end_row();
+
+                            // SMART：每块物理磁盘一行
+                            let mut seen = HashSet::new();
+                            let physical: Vec<&DiskData> =
+                                snapshot.disks.iter().filter(|d| d.disk_number.is_some_and(|n| seen.insert(n))).collect();
+                            if !physical.is_empty() {
+                                ui.label(tr("磁盘健康 (SMART):"));
+                                ui.vertical(|ui| {
+                                    for d in physical {
+                                        let n = d.disk_number.unwrap_or_default();
+                                        ui.horizontal(|ui| {
+                                            ui.label(
+                                                egui::RichText::new(format!("#{} {}", n, d.mount_point.trim_end_matches('\\')))
+                                                    .small()
+                                                    .monospace(),
+                                            );
+                                            match &d.smart {
+                                                Some(Ok(health)) => smart_label(ui, health),
+                                                Some(Err(e)) => {
+                                                    ui.label(egui::RichText::new(tr("不支持")).small().color(egui::Color32::GRAY))
+                                                        .on_hover_text(e);
+                                                }
+                                                None => {}
+                                            }
+                                        });
+                                    }
+                                });
+                                ui.end_row();
+                            }
                         });
+                        self.render_per_core(ui, &snapshot.per_core);
+                        self.render_perf_history(ui);
                     });
+                self.mark_panel(ui, panel_capture::Panel::Performance, top);
                 ui.add_space(10.0);
             }
+            self.paint_capture(ui, ctx);
 
-            // Process Lists
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                if !snapshot.high_resource.is_empty() {
-                    ui.group(|ui| {
-                        ui.label(
-                            egui::RichText::new("🔥 极高负载任务")
-                                .color(egui::Color32::RED)
-                                .strong(),
-                        );
-                        // 限制高度，避免跳动，支持滚动
-                        egui::ScrollArea::vertical()
-                            .min_scrolled_height(300.0)
-                            .max_height(300.0)
-                            .show(ui, |ui| {
-                                self.render_process_table(ui, ctx, &snapshot.high_resource, true);
-                            });
-                    });
-                    ui.add_space(5.0);
-                }
-
-                if !snapshot.other_groups.is_empty() {
-                    // 极简模式下默认折叠
-                    let default_open = !snapshot.is_resource_tight;
-                    
+            // Process Lists (U 盘精简视图不显示)
+            if self.view == settings::View::Full {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    // 最近启动：按启动时间倒序，与当前 CPU 占用无关
                     egui::CollapsingHeader::new(
-                        egui::RichText::new(format!("👤 活动用户任务 ({})", snapshot.other_groups.len()))
-                            .color(primary_color)
+                        egui::RichText::new(tr("🕒 最近启动"))
+                            .color(egui::Color32::from_rgb(0, 206, 209))
                             .strong(),
                     )
-                    .default_open(default_open)
+                    .default_open(false)
                     .show(ui, |ui| {
-                        ui.add_space(5.0);
                         egui::ScrollArea::vertical()
-                            .max_height(300.0)
+                            .id_source("recent_scroll")
+                            .max_height(250.0)
                             .show(ui, |ui| {
-                                self.render_process_table(ui, ctx, &snapshot.other_groups, false);
+                                self.render_recent_table(ui, &snapshot);
                             });
                     });
                     ui.add_space(5.0);
-                }
 
-                if !snapshot.system_groups.is_empty() {
-                    egui::CollapsingHeader::new(
-                        egui::RichText::new(format!("🛡️ 系统核心服务 ({})", snapshot.system_groups.len()))
-                            .color(egui::Color32::from_rgb(139, 115, 85))
-                            .strong(),
-                    )
-                    .default_open(false)
-                    .show(ui, |ui| {
-                        ui.add_space(5.0);
-                        egui::ScrollArea::vertical()
-                            .max_height(200.0)
-                            .show(ui, |ui| {
-                                self.render_process_table(ui, ctx, &snapshot.system_groups, false);
-                            });
+                    // 已隐藏的分组 (右键进程名隐藏 / 取消隐藏)
+                    let hidden_count = snapshot
+                        .high_resource
+                        .iter()
+                        .chain(&snapshot.other_groups)
+                        .chain(&snapshot.system_groups)
+                        .filter(|g| self.settings.is_hidden(&g.name))
+                        .count();
+                    let hung_count = snapshot
+                        .high_resource
+                        .iter()
+                        .chain(&snapshot.other_groups)
+                        .chain(&snapshot.system_groups)
+                        .filter(|g| g.is_not_responding)
+                        .count();
+                    let other_user_count = snapshot
+                        .high_resource
+                        .iter()
+                        .chain(&snapshot.other_groups)
+                        .chain(&snapshot.system_groups)
+                        .filter(|g| g.other_users)
+                        .count();
+                    ui.horizontal(|ui| {
+                        if hidden_count > 0 || self.show_hidden {
+                            ui.toggle_value(&mut self.show_hidden, trf("显示已隐藏 ({})", &[&hidden_count]))
+                                .on_hover_text(tr("右键进程名可隐藏或取消隐藏分组"));
+                        }
+                        if hung_count > 0 || self.only_hung {
+                            ui.toggle_value(&mut self.only_hung, trf("只看无响应 ({})", &[&hung_count]))
+                                .on_hover_text(tr("只显示有窗口卡死 (不再处理消息) 的程序"));
+                        }
+                        if other_user_count > 0 || self.only_other_users {
+                            ui.toggle_value(&mut self.only_other_users, trf("只看其他用户 ({})", &[&other_user_count]))
+                                .on_hover_text(tr("只显示含其他已登录用户进程的程序；展开后每个 PID 显示所属账户"));
+                        }
                     });
-                }
-            });
+
+                    if !snapshot.high_resource.is_empty() {
+                        ui.group(|ui| {
+                            ui.label(
+                                egui::RichText::new(tr("🔥 极高负载任务"))
+                                    .color(egui::Color32::RED)
+                                    .strong(),
+                            );
+                            // 限制高度，避免跳动，支持滚动
+                            egui::ScrollArea::vertical()
+                                .min_scrolled_height(300.0)
+                                .max_height(300.0)
+                                .show(ui, |ui| {
+                                    self.render_process_table(ui, ctx, &snapshot.high_resource, true);
+                                });
+                        });
+                        ui.add_space(5.0);
+                    }
+
+                    // 搜索时各组标题显示“匹配数/总数”；搜索条件变化且组内有匹配时自动展开
+                    let section = |groups: &[ProcessGroup]| {
+                        if self.search.is_active() {
+                            let found = groups.iter().filter(|g| self.search.matches(g)).count();
+                            (format!("{}/{}", found, groups.len()), (search_changed && found > 0).then_some(true))
+                        } else {
+                            (groups.len().to_string(), None)
+                        }
+                    };
+                    let (other_count, other_open) = section(&snapshot.other_groups);
+                    let (system_count, system_open) = section(&snapshot.system_groups);
+
+                    if !snapshot.other_groups.is_empty() {
+                        // 极简模式下默认折叠
+                        let default_open = !snapshot.is_resource_tight;
+                    
+                        egui::CollapsingHeader::new(
+                            egui::RichText::new(trf("👤 活动用户任务 ({})", &[&other_count]))
+                                .color(primary_color)
+                                .strong(),
+                        )
+                        .default_open(default_open)
+                        .open(other_open)
+                        .show(ui, |ui| {
+                            ui.add_space(5.0);
+                            egui::ScrollArea::vertical()
+                                .max_height(300.0)
+                                .show(ui, |ui| {
+                                    self.render_process_table(ui, ctx, &snapshot.other_groups, false);
+                                });
+                        });
+                        ui.add_space(5.0);
+                    }
+
+                    if !snapshot.system_groups.is_empty() {
+                        egui::CollapsingHeader::new(
+                            egui::RichText::new(trf("🛡️ 系统核心服务 ({})", &[&system_count]))
+                                .color(egui::Color32::from_rgb(139, 115, 85))
+                                .strong(),
+                        )
+                        .default_open(false)
+                        .open(system_open)
+                        .show(ui, |ui| {
+                            ui.add_space(5.0);
+                            egui::ScrollArea::vertical()
+                                .max_height(200.0)
+                                .show(ui, |ui| {
+                                    self.render_process_table(ui, ctx, &snapshot.system_groups, false);
+                                });
+                        });
+                    }
+                });
+            }
             ui.add_space(20.0);
         });
+        self.tick_health.frame.push(frame_start.elapsed());
     }
 }
 
@@ -1938,17 +9599,26 @@ fn main() -> eframe::Result<()> {
         }
     });
 
+    let settings = settings::AppSettings::load();
+    i18n::set_language(&settings.language);
+    let launch = settings.launch_profile(std::env::args().skip(1));
+    let (position, size) = settings.window_geometry(launch.view);
+
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_inner_size(size)
+        .with_min_inner_size(launch.view.min_window_size())
+        .with_icon(icon.unwrap_or_default());
+    if let Some(position) = position {
+        viewport = viewport.with_position(position);
+    }
     let native_options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([650.0, 850.0])
-            .with_min_inner_size([600.0, 500.0])
-            .with_icon(icon.unwrap_or_default()),
+        viewport,
         ..Default::default()
     };
 
     eframe::run_native(
-        "Geek Killer Pro",
+        APP_TITLE,
         native_options,
-        Box::new(|cc| Ok(Box::new(GeekKillerApp::new(cc)))),
+        Box::new(|cc| Ok(Box::new(GeekKillerApp::new(cc, settings, launch)))),
     )
 }
@@ -0,0 +1,277 @@
+// ═══════════════════════════════════════════════════════════════
+//  内存诊断 - 页面文件配置 / 硬错误风暴 / Windows 内存诊断
+// ═══════════════════════════════════════════════════════════════
+//
+// 硬错误 (hard fault) = 需要从磁盘读回的缺页。持续的高硬错误率说明物理内存
+// 不够用，系统在页面文件上反复换入换出，表现为“整机卡顿但 CPU 不高”。
+
+use crate::i18n::{tr, trf};
+use crate::long_path::wide;
+use crate::tool_runner::ToolSpec;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use windows_sys::Win32::System::Performance::{
+    PdhAddEnglishCounterW, PdhCloseQuery, PdhCollectQueryData, PdhGetFormattedCounterValue,
+    PdhOpenQueryW, PDH_FMT_COUNTERVALUE, PDH_FMT_DOUBLE,
+};
+use windows_sys::Win32::System::Registry::{RegGetValueW, HKEY_LOCAL_MACHINE, RRF_RT_REG_MULTI_SZ};
+use windows_sys::Win32::System::SystemInformation::{GlobalMemoryStatusEx, MEMORYSTATUSEX};
+
+/// 每秒换入页数超过该值视为“风暴”中的一个采样 (4KB 页，约 4MB/s)
+const STORM_PAGES_PER_SEC: f64 = 1000.0;
+/// 连续多少个采样超阈值才算一次风暴，过滤掉程序启动时的短暂峰值
+const STORM_MIN_SAMPLES: u32 = 3;
+/// 统计风暴次数的时间窗口
+const STORM_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum PagefileMode {
+    #[default]
+    Unknown,
+    None,          // 未启用页面文件
+    SystemManaged, // 由系统管理大小
+    Custom { initial_mb: u64, max_mb: u64 },
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PagefileConfig {
+    pub mode: PagefileMode,
+    /// 注册表原始条目，例如 "C:\pagefile.sys 0 0"
+    pub entries: Vec<String>,
+}
+
+impl PagefileConfig {
+    pub fn describe(&self) -> String {
+        match &self.mode {
+            PagefileMode::Unknown => tr("无法读取").to_string(),
+            PagefileMode::None => tr("未启用").to_string(),
+            PagefileMode::SystemManaged => tr("系统管理大小").to_string(),
+            PagefileMode::Custom { initial_mb, max_mb } => {
+                trf("自定义 {} - {} MB", &[&initial_mb, &max_mb])
+            }
+        }
+    }
+}
+
+/// 读取 Memory Management\PagingFiles
+pub fn read_pagefile_config() -> PagefileConfig {
+    let subkey = wide("SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Memory Management");
+    let value = wide("PagingFiles");
+    let mut buf = vec![0u16; 1024];
+    let mut size = (buf.len() * 2) as u32;
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_LOCAL_MACHINE,
+            subkey.as_ptr(),
+            value.as_ptr(),
+            RRF_RT_REG_MULTI_SZ,
+            std::ptr::null_mut(),
+            buf.as_mut_ptr() as *mut _,
+            &mut size,
+        )
+    };
+    if status != 0 {
+        return PagefileConfig::default();
+    }
+    buf.truncate(size as usize / 2);
+    let entries: Vec<String> = buf
+        .split(|&c| c == 0)
+        .filter(|s| !s.is_empty())
+        .map(String::from_utf16_lossy)
+        .collect();
+
+    // "?:\pagefile.sys" 为自动管理所有驱动器；"X:\pagefile.sys 0 0" 为系统管理
+    let mut mode = PagefileMode::None;
+    let (mut initial, mut max) = (0u64, 0u64);
+    for e in &entries {
+        let parts: Vec<&str> = e.split_whitespace().collect();
+        let sizes: Vec<u64> = parts.iter().skip(1).filter_map(|p| p.parse().ok()).collect();
+        if e.starts_with('?') || sizes.is_empty() || sizes.iter().all(|&s| s == 0) {
+            mode = PagefileMode::SystemManaged;
+            break;
+        }
+        initial += sizes[0];
+        max += sizes.get(1).copied().unwrap_or(sizes[0]);
+        mode = PagefileMode::Custom {
+            initial_mb: initial,
+            max_mb: max,
+        };
+    }
+    PagefileConfig { mode, entries }
+}
+
+/// 提交内存 (已提交 / 上限)，字节
+pub fn commit_charge() -> Option<(u64, u64)> {
+    unsafe {
+        let mut st: MEMORYSTATUSEX = std::mem::zeroed();
+        st.dwLength = std::mem::size_of::<MEMORYSTATUSEX>() as u32;
+        if GlobalMemoryStatusEx(&mut st) == 0 {
+            return None;
+        }
+        Some((st.ullTotalPageFile - st.ullAvailPageFile, st.ullTotalPageFile))
+    }
+}
+
+/// 通过 PDH 采样 "\Memory\Pages Input/sec"，并识别硬错误风暴
+pub struct HardFaultMonitor {
+    query: isize,
+    counter: isize,
+    streak: u32,
+    storms: VecDeque<Instant>,
+}
+
+impl HardFaultMonitor {
+    pub fn new() -> Option<Self> {
+        let path = wide("\\Memory\\Pages Input/sec");
+        unsafe {
+            let mut query = 0isize;
+            if PdhOpenQueryW(std::ptr::null(), 0, &mut query) != 0 {
+                return None;
+            }
+            let mut counter = 0isize;
+            if PdhAddEnglishCounterW(query, path.as_ptr(), 0, &mut counter) != 0 {
+                PdhCloseQuery(query);
+                return None;
+            }
+            // 速率类计数器需要两次采样，先收集一次作为基准
+            PdhCollectQueryData(query);
+            Some(Self {
+                query,
+                counter,
+                streak: 0,
+                storms: VecDeque::new(),
+            })
+        }
+    }
+
+    /// 采样一次，返回每秒换入页数
+    pub fn sample(&mut self) -> Option<f64> {
+        let rate = unsafe {
+            if PdhCollectQueryData(self.query) != 0 {
+                return None;
+            }
+            let mut value: PDH_FMT_COUNTERVALUE = std::mem::zeroed();
+            if PdhGetFormattedCounterValue(self.counter, PDH_FMT_DOUBLE, std::ptr::null_mut(), &mut value) != 0 {
+                return None;
+            }
+            value.Anonymous.doubleValue
+        };
+
+        if rate >= STORM_PAGES_PER_SEC {
+            self.streak += 1;
+            // 只在刚好达到门槛时计一次，持续的风暴不重复计数
+            if self.streak == STORM_MIN_SAMPLES {
+                self.storms.push_back(Instant::now());
+            }
+        } else {
+            self.streak = 0;
+        }
+        while self.storms.front().map(|t| t.elapsed() > STORM_WINDOW).unwrap_or(false) {
+            self.storms.pop_front();
+        }
+        Some(rate)
+    }
+
+    /// 最近 10 分钟内的风暴次数
+    pub fn recent_storms(&self) -> usize {
+        self.storms.len()
+    }
+
+    pub fn in_storm(&self) -> bool {
+        self.streak >= STORM_MIN_SAMPLES
+    }
+}
+
+impl Drop for HardFaultMonitor {
+    fn drop(&mut self) {
+        unsafe {
+            PdhCloseQuery(self.query);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warn,
+    Critical,
+}
+
+#[derive(Clone, Debug)]
+pub struct Advice {
+    pub severity: Severity,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MemoryReport {
+    pub pagefile: PagefileConfig,
+    pub commit_used: u64,
+    pub commit_limit: u64,
+    pub hard_faults_per_sec: Option<f64>,
+    pub in_storm: bool,
+    pub recent_storms: usize,
+    pub advice: Vec<Advice>,
+}
+
+/// 根据 RAM 大小、页面文件配置与硬错误情况给出建议
+pub fn evaluate(report: &mut MemoryReport, total_ram: u64) {
+    let ram_mb = total_ram / 1024 / 1024;
+    let mut advice = Vec::new();
+    let mut push = |severity, text: String| advice.push(Advice { severity, text });
+
+    match &report.pagefile.mode {
+        PagefileMode::None => push(
+            if ram_mb < 16 * 1024 { Severity::Critical } else { Severity::Warn },
+            tr("未启用页面文件：内存耗尽时程序会直接崩溃，且无法生成蓝屏转储。建议改为“系统管理的大小”").to_string(),
+        ),
+        PagefileMode::Custom { max_mb, .. } => {
+            if *max_mb < 1024 {
+                push(
+                    Severity::Warn,
+                    trf("页面文件上限仅 {} MB，不足以保存内核转储，建议至少 1 GB", &[&max_mb]),
+                );
+            }
+            if ram_mb <= 8 * 1024 && *max_mb < ram_mb {
+                push(
+                    Severity::Warn,
+                    trf(
+                        "内存 {} GB 偏小而页面文件上限只有 {} MB，容易出现“内存不足”提示，建议不低于物理内存大小",
+                        &[&format!("{:.0}", ram_mb as f32 / 1024.0), &max_mb],
+                    ),
+                );
+            }
+        }
+        PagefileMode::SystemManaged => {}
+        PagefileMode::Unknown => push(Severity::Info, tr("无法读取页面文件配置").to_string()),
+    }
+
+    if report.commit_limit > 0 {
+        let ratio = report.commit_used as f64 / report.commit_limit as f64;
+        if ratio > 0.9 {
+            push(
+                Severity::Critical,
+                trf("提交内存已用 {}%，即将触发“内存不足”，请关闭大型程序或增大页面文件", &[&format!("{:.0}", ratio * 100.0)]),
+            );
+        }
+    }
+
+    if report.recent_storms >= 3 {
+        push(
+            Severity::Warn,
+            trf(
+                "最近 10 分钟出现 {} 次硬错误风暴：物理内存不足，系统在频繁读写页面文件。建议减少常驻程序或加装内存",
+                &[&report.recent_storms],
+            ),
+        );
+    } else if report.in_storm {
+        push(Severity::Info, tr("正在发生大量硬错误 (换页)，卡顿可能来自内存不足").to_string());
+    }
+
+    report.advice = advice;
+}
+
+/// 下次启动时运行 Windows 内存诊断 (等同于 mdsched 中的“下次启动时检查”)
+pub fn memdiag_spec() -> ToolSpec {
+    ToolSpec::new(tr("Windows 内存诊断"), "bcdedit.exe", &["/bootsequence", "{memdiag}"])
+}
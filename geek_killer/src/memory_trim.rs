@@ -0,0 +1,119 @@
+// ═══════════════════════════════════════════════════════════════
+//  内存整理 - 裁剪进程工作集 / 清空备用列表
+// ═══════════════════════════════════════════════════════════════
+//
+// 两个手段，都不结束任何进程：
+//   1. 裁剪工作集 (EmptyWorkingSet)：进程暂时不用的页面移出物理内存，
+//      之后再访问时从备用列表 / 页面文件读回，短时间内可能略卡
+//   2. 清空备用列表：NtSetSystemInformation(SystemMemoryListInformation,
+//      MemoryPurgeStandbyList)，丢弃文件缓存，需要管理员并启用
+//      SeProfileSingleProcessPrivilege
+// 备用列表大小用 NtQuerySystemInformation 读取 (同样需要该特权)，
+// 前后相减得到实际释放的量；工作集按进程逐个量取。
+
+use crate::i18n::{tr, trf};
+use crate::long_path::wide;
+use windows_sys::Win32::Foundation::{CloseHandle, GetLastError, ERROR_NOT_ALL_ASSIGNED, LUID};
+use windows_sys::Win32::Security::{
+    AdjustTokenPrivileges, LookupPrivilegeValueW, LUID_AND_ATTRIBUTES, SE_PRIVILEGE_ENABLED, TOKEN_ADJUST_PRIVILEGES,
+    TOKEN_PRIVILEGES, TOKEN_QUERY,
+};
+use windows_sys::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+#[link(name = "ntdll")]
+extern "system" {
+    fn NtSetSystemInformation(class: u32, info: *mut std::ffi::c_void, len: u32) -> i32;
+    fn NtQuerySystemInformation(class: u32, info: *mut std::ffi::c_void, len: u32, ret_len: *mut u32) -> i32;
+}
+
+const SYSTEM_MEMORY_LIST_INFORMATION: u32 = 80;
+const MEMORY_PURGE_STANDBY_LIST: i32 = 4;
+const PAGE_SIZE: u64 = 4096;
+
+/// SYSTEM_MEMORY_LIST_INFORMATION
+#[repr(C)]
+#[derive(Default)]
+struct MemoryListInfo {
+    /// Zero / Free / Modified / ModifiedNoWrite / Bad 页数
+    _lists: [usize; 5],
+    /// 按优先级的备用页数
+    standby_by_priority: [usize; 8],
+    /// RepurposedPagesByPriority[8] 与 ModifiedPageCountPageFile
+    _rest: [usize; 9],
+}
+
+fn mb(bytes: u64) -> String {
+    format!("{:.0} MB", bytes as f64 / 1024.0 / 1024.0)
+}
+
+/// 启用本进程令牌中的 SeProfileSingleProcessPrivilege (管理员令牌才有)
+fn enable_profile_privilege() -> Result<(), String> {
+    unsafe {
+        let mut token = 0;
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_ADJUST_PRIVILEGES | TOKEN_QUERY, &mut token) == 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+        let mut luid: LUID = std::mem::zeroed();
+        let name = wide("SeProfileSingleProcessPrivilege");
+        let mut ok = LookupPrivilegeValueW(std::ptr::null(), name.as_ptr(), &mut luid) != 0;
+        if ok {
+            let privileges = TOKEN_PRIVILEGES {
+                PrivilegeCount: 1,
+                Privileges: [LUID_AND_ATTRIBUTES {
+                    Luid: luid,
+                    Attributes: SE_PRIVILEGE_ENABLED,
+                }],
+            };
+            // 令牌中没有该特权时调用仍然成功，只能从 GetLastError 判断
+            ok = AdjustTokenPrivileges(token, 0, &privileges, 0, std::ptr::null_mut(), std::ptr::null_mut()) != 0
+                && GetLastError() != ERROR_NOT_ALL_ASSIGNED;
+        }
+        CloseHandle(token);
+        if ok {
+            Ok(())
+        } else {
+            Err(tr("需要管理员权限").to_string())
+        }
+    }
+}
+
+/// 备用列表大小，字节
+fn standby_bytes() -> Option<u64> {
+    let mut info = MemoryListInfo::default();
+    let status = unsafe {
+        NtQuerySystemInformation(
+            SYSTEM_MEMORY_LIST_INFORMATION,
+            &mut info as *mut _ as _,
+            std::mem::size_of::<MemoryListInfo>() as u32,
+            std::ptr::null_mut(),
+        )
+    };
+    (status >= 0).then(|| info.standby_by_priority.iter().map(|&p| p as u64).sum::<u64>() * PAGE_SIZE)
+}
+
+/// 清空备用列表，返回释放的字节数 (读不到大小时为 None)
+fn purge_standby() -> Result<Option<u64>, String> {
+    enable_profile_privilege()?;
+    let before = standby_bytes();
+    let mut command = MEMORY_PURGE_STANDBY_LIST;
+    let status = unsafe { NtSetSystemInformation(SYSTEM_MEMORY_LIST_INFORMATION, &mut command as *mut i32 as _, 4) };
+    if status < 0 {
+        return Err(format!("NTSTATUS 0x{:08X}", status as u32));
+    }
+    Ok(before.zip(standby_bytes()).map(|(b, a)| b.saturating_sub(a)))
+}
+
+/// 裁剪 pids 的工作集，可选清空备用列表；返回结果说明 (写入审计日志)
+pub fn run(pids: &[u32], purge: bool) -> String {
+    let (trimmed, freed) = crate::browser_memory::trim(pids);
+    let mut text = trf("内存整理：裁剪 {}/{} 个进程，工作集减少 {}", &[&trimmed, &pids.len(), &mb(freed)]);
+    if purge {
+        text.push_str(&match purge_standby() {
+            Ok(Some(bytes)) => trf("；备用列表释放 {}", &[&mb(bytes)]),
+            Ok(None) => tr("；已清空备用列表").to_string(),
+            Err(e) => trf("；清空备用列表失败：{}", &[&e]),
+        });
+    }
+    crate::audit::record("内存整理", &text);
+    text
+}
@@ -0,0 +1,194 @@
+// ═══════════════════════════════════════════════════════════════
+//  计费网络断网 - 手机热点等按流量计费时阻止同步盘 / 更新程序联网
+// ═══════════════════════════════════════════════════════════════
+//
+// 连接是否计费取自 INetworkCostManager::GetCost (与 WinRT ConnectionCost 同源，
+// 即“设置 → 网络 → 按流量计费的连接”)：固定 / 可变计费、漫游、超出流量上限都算。
+// 开启后后台线程每 POLL_INTERVAL 检查一次：
+//   - 计费中：名单内 (进程名，支持 * 通配，与看门狗相同) 正在运行的程序按 exe 路径
+//     添加防火墙出站阻止规则；之后新启动的程序下一轮补上
+//   - 恢复非计费 (或关闭此功能)：删除全部规则
+// 规则共用 RULE_NAME，启动时先清掉上次异常退出遗留的规则。需要管理员权限。
+
+use crate::i18n::{tr, trf};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ffi::c_void;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use sysinfo::{ProcessRefreshKind, System, UpdateKind};
+use windows_sys::core::{GUID, HRESULT};
+use windows_sys::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_ALL, COINIT_MULTITHREADED};
+
+/// 防火墙规则名
+pub const RULE_NAME: &str = "GeekKiller-Metered-Block";
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+const CLSID_NETWORK_LIST_MANAGER: GUID = GUID {
+    data1: 0xdcb00c01,
+    data2: 0x570f,
+    data3: 0x4a9b,
+    data4: [0x8d, 0x69, 0x19, 0x9f, 0xdb, 0xa5, 0x72, 0x3b],
+};
+const IID_INETWORK_COST_MANAGER: GUID = GUID {
+    data1: 0xdcb00008,
+    data2: 0x570f,
+    data3: 0x4a9b,
+    data4: [0x8d, 0x69, 0x19, 0x9f, 0xdb, 0xa5, 0x72, 0x3b],
+};
+
+// vtable 槽位 (IUnknown 0-2)
+const SLOT_RELEASE: usize = 2;
+const SLOT_GET_COST: usize = 3;
+
+// NLM_CONNECTION_COST
+const COST_FIXED: u32 = 0x2;
+const COST_VARIABLE: u32 = 0x4;
+const COST_OVERDATALIMIT: u32 = 0x10000;
+const COST_ROAMING: u32 = 0x40000;
+
+type ReleaseFn = unsafe extern "system" fn(*mut c_void) -> u32;
+type GetCostFn = unsafe extern "system" fn(*mut c_void, *mut u32, *const c_void) -> HRESULT;
+
+unsafe fn vfn<F: Copy>(obj: *mut c_void, slot: usize) -> F {
+    let vtbl = *(obj as *const *const usize);
+    let f = *vtbl.add(slot);
+    std::mem::transmute_copy(&f)
+}
+
+/// 本机当前连接是否计费 (调用线程须已初始化 COM)
+fn is_metered() -> Result<bool, String> {
+    unsafe {
+        let mut manager = std::ptr::null_mut();
+        let hr = CoCreateInstance(
+            &CLSID_NETWORK_LIST_MANAGER,
+            std::ptr::null_mut(),
+            CLSCTX_ALL,
+            &IID_INETWORK_COST_MANAGER,
+            &mut manager,
+        );
+        if hr < 0 || manager.is_null() {
+            return Err(trf("无法读取网络计费状态 (HRESULT 0x{})", &[&format!("{:08X}", hr as u32)]));
+        }
+        let mut cost = 0u32;
+        // 目标地址为空表示整机的连接
+        let hr = vfn::<GetCostFn>(manager, SLOT_GET_COST)(manager, &mut cost, std::ptr::null());
+        vfn::<ReleaseFn>(manager, SLOT_RELEASE)(manager);
+        if hr < 0 {
+            return Err(trf("无法读取网络计费状态 (HRESULT 0x{})", &[&format!("{:08X}", hr as u32)]));
+        }
+        Ok(cost & (COST_FIXED | COST_VARIABLE | COST_OVERDATALIMIT | COST_ROAMING) != 0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub enabled: bool,
+    /// 计费时阻止联网的进程名
+    pub programs: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            programs: [
+                "OneDrive",
+                "Dropbox",
+                "GoogleDriveFS",
+                "BaiduNetdisk",
+                "steam",
+                "EpicGamesLauncher",
+                "*updater*",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        }
+    }
+}
+
+/// 后台线程的最新状态，供界面显示
+#[derive(Clone, Debug, Default)]
+pub struct Status {
+    /// None 表示尚未检查或读取失败
+    pub metered: Option<bool>,
+    /// 已添加阻止规则的程序路径
+    pub blocked: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// 启动后台检查线程
+pub fn spawn(config: Arc<RwLock<Config>>, status: Arc<Mutex<Status>>) {
+    std::thread::spawn(move || {
+        unsafe {
+            CoInitializeEx(std::ptr::null(), COINIT_MULTITHREADED as _);
+        }
+        let _ = crate::firewall::remove_rules(RULE_NAME);
+        let mut sys = System::new();
+        let mut blocked: Vec<String> = Vec::new();
+        // 添加失败的路径本次计费期间不再重试，免得每轮都写一遍审计日志
+        let mut failed: HashSet<String> = HashSet::new();
+        let is_admin = rust_core_lib::security::is_admin();
+        loop {
+            let config = config.read().map(|c| c.clone()).unwrap_or_default();
+            let metered = is_metered();
+            let mut error = metered.as_ref().err().cloned();
+            let block = config.enabled && metered == Ok(true);
+
+            if block && !is_admin {
+                error = Some(tr("阻止程序联网需要以管理员身份运行").to_string());
+            } else if block {
+                sys.refresh_processes_specifics(
+                    sysinfo::ProcessesToUpdate::All,
+                    true,
+                    ProcessRefreshKind::new().with_exe(UpdateKind::OnlyIfNotSet),
+                );
+                for process in sys.processes().values() {
+                    let Some(path) = process.exe().map(|p| p.to_string_lossy().to_string()) else {
+                        continue;
+                    };
+                    let name = process.name().to_string_lossy();
+                    let listed = config.programs.iter().any(|p| crate::watchdog::matches(p, &name));
+                    if !listed || blocked.contains(&path) || failed.contains(&path) {
+                        continue;
+                    }
+                    match crate::firewall::block_program(RULE_NAME, &path) {
+                        Ok(()) => {
+                            crate::audit::record("计费网络", &format!("阻止 {} 联网", path));
+                            if blocked.is_empty() {
+                                crate::tray::balloon(tr("计费网络"), &trf("当前连接按流量计费，已阻止 {} 联网", &[&name]), false);
+                            }
+                            blocked.push(path);
+                        }
+                        Err(e) => {
+                            error = Some(trf("无法阻止 {}：{}", &[&name, &e]));
+                            failed.insert(path);
+                        }
+                    }
+                }
+            } else if !blocked.is_empty() {
+                match crate::firewall::remove_rules(RULE_NAME) {
+                    Ok(()) => {
+                        crate::audit::record("计费网络", &format!("恢复 {} 个程序联网", blocked.len()));
+                        blocked.clear();
+                    }
+                    Err(e) => error = Some(trf("无法移除防火墙规则：{}", &[&e])),
+                }
+            }
+
+            if !block {
+                failed.clear();
+            }
+            if let Ok(mut s) = status.lock() {
+                *s = Status {
+                    metered: metered.ok(),
+                    blocked: blocked.clone(),
+                    error,
+                };
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
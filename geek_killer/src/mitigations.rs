@@ -0,0 +1,88 @@
+// ═══════════════════════════════════════════════════════════════
+//  进程缓解策略 - DEP / ASLR / CFG / CET 影子栈
+// ═══════════════════════════════════════════════════════════════
+
+use crate::i18n::{tr, trf};
+use windows_sys::Win32::Foundation::CloseHandle;
+use windows_sys::Win32::System::Threading::{
+    GetProcessMitigationPolicy, IsWow64Process, OpenProcess, ProcessASLRPolicy,
+    ProcessControlFlowGuardPolicy, ProcessDEPPolicy, ProcessUserShadowStackPolicy,
+    PROCESS_MITIGATION_POLICY, PROCESS_QUERY_LIMITED_INFORMATION,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Mitigations {
+    pub dep: bool,
+    /// 自底向上随机化 (强制重定位 / 高熵也计入)
+    pub aslr: bool,
+    pub aslr_high_entropy: bool,
+    pub cfg: bool,
+    /// 硬件强制影子栈 (Intel CET / AMD Shadow Stack)
+    pub cet: bool,
+}
+
+impl Mitigations {
+    /// 紧凑展示，例如 "DEP ASLR+ CFG -CET"
+    pub fn compact(&self) -> String {
+        let flag = |on: bool, name: &str| if on { name.to_string() } else { format!("-{}", name) };
+        [
+            flag(self.dep, "DEP"),
+            flag(self.aslr, if self.aslr_high_entropy { "ASLR+" } else { "ASLR" }),
+            flag(self.cfg, "CFG"),
+            flag(self.cet, "CET"),
+        ]
+        .join(" ")
+    }
+
+    pub fn tooltip(&self) -> String {
+        let yes = |on: bool| tr(if on { "已开启" } else { "已关闭" });
+        trf(
+            "DEP 数据执行保护：{}\nASLR 地址随机化：{}{}\nCFG 控制流保护：{}\nCET 硬件影子栈：{}",
+            &[
+                &yes(self.dep),
+                &yes(self.aslr),
+                &if self.aslr_high_entropy { tr(" (高熵)") } else { "" },
+                &yes(self.cfg),
+                &yes(self.cet),
+            ],
+        )
+    }
+}
+
+unsafe fn policy_flags(process: isize, policy: PROCESS_MITIGATION_POLICY) -> Option<u32> {
+    // 各策略结构体都以 32 位 Flags 开头；DEP 结构后面还有 Permanent 字段
+    let mut buf = [0u32; 2];
+    let len = if policy == ProcessDEPPolicy { 8 } else { 4 };
+    (GetProcessMitigationPolicy(process, policy, buf.as_mut_ptr() as _, len) != 0).then_some(buf[0])
+}
+
+/// 查询进程的缓解策略；无权访问时返回 None
+pub fn query(pid: u32) -> Option<Mitigations> {
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process == 0 {
+            return None;
+        }
+        let aslr = policy_flags(process, ProcessASLRPolicy);
+        let result = aslr.map(|aslr| {
+            // 64 位进程的 DEP 恒定开启，策略查询返回的 Enable 位为 0
+            let mut wow64 = 0;
+            IsWow64Process(process, &mut wow64);
+            let dep = cfg!(target_pointer_width = "64") && wow64 == 0
+                || policy_flags(process, ProcessDEPPolicy).map(|f| f & 1 != 0).unwrap_or(false);
+            Mitigations {
+                dep,
+                aslr: aslr & 0b11 != 0,
+                aslr_high_entropy: aslr & 0b100 != 0,
+                cfg: policy_flags(process, ProcessControlFlowGuardPolicy)
+                    .map(|f| f & 1 != 0)
+                    .unwrap_or(false),
+                cet: policy_flags(process, ProcessUserShadowStackPolicy)
+                    .map(|f| f & 1 != 0)
+                    .unwrap_or(false),
+            }
+        });
+        CloseHandle(process);
+        result
+    }
+}
@@ -0,0 +1,135 @@
+// ═══════════════════════════════════════════════════════════════
+//  模块列表 - 进程加载的 DLL：路径、版本与签名
+// ═══════════════════════════════════════════════════════════════
+//
+// EnumProcessModulesEx(LIST_MODULES_ALL) 同时列出 32 / 64 位模块，
+// GetModuleFileNameExW 取完整路径。U 盘上的 DLL 被某个进程加载后会一直占用该盘，
+// 而 RM 只报告“正在运行”，在这里按路径搜索 (如 “E:\”) 即可找到是哪个模块。
+// 版本取自文件版本信息，签名交给后台线程校验。
+// 进程刚启动 (模块表尚未建好) 时枚举会返回 ERROR_PARTIAL_COPY，稍后重试。
+
+use crate::i18n::{tr, trf};
+use crate::signature::SignatureCache;
+use windows_sys::Win32::Foundation::{CloseHandle, GetLastError, ERROR_ACCESS_DENIED, ERROR_PARTIAL_COPY, HMODULE};
+use windows_sys::Win32::System::ProcessStatus::{
+    EnumProcessModulesEx, GetModuleFileNameExW, GetModuleInformation, LIST_MODULES_ALL, MODULEINFO,
+};
+use windows_sys::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ};
+
+/// ERROR_PARTIAL_COPY 时的重试次数
+const RETRIES: usize = 3;
+
+#[derive(Clone, Debug)]
+pub struct Module {
+    pub path: String,
+    pub base: usize,
+    pub size: u32,
+    pub version: Option<String>,
+}
+
+impl Module {
+    pub fn file_name(&self) -> &str {
+        self.path.rsplit(['\\', '/']).next().unwrap_or(&self.path)
+    }
+
+    /// 路径或文件名包含 query (不区分大小写)
+    pub fn matches(&self, query: &str) -> bool {
+        query.is_empty() || self.path.to_lowercase().contains(&query.to_lowercase())
+    }
+}
+
+unsafe fn enum_modules(process: isize) -> Result<Vec<HMODULE>, u32> {
+    let mut modules: Vec<HMODULE> = vec![0; 256];
+    loop {
+        let mut needed = 0u32;
+        let cb = (modules.len() * std::mem::size_of::<HMODULE>()) as u32;
+        if EnumProcessModulesEx(process, modules.as_mut_ptr(), cb, &mut needed, LIST_MODULES_ALL) == 0 {
+            return Err(GetLastError());
+        }
+        let count = needed as usize / std::mem::size_of::<HMODULE>();
+        if needed <= cb {
+            modules.truncate(count);
+            return Ok(modules);
+        }
+        // 缓冲区不够，按返回的大小重来 (期间可能又加载了模块)
+        modules = vec![0; count + 16];
+    }
+}
+
+/// 列出进程加载的模块，按路径排序
+pub fn list(pid: u32) -> Result<Vec<Module>, String> {
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, 0, pid);
+        if process == 0 {
+            return Err(match GetLastError() {
+                ERROR_ACCESS_DENIED => tr("无权读取该进程的模块 (服务或其他用户的进程需要管理员权限)").to_string(),
+                code => std::io::Error::from_raw_os_error(code as i32).to_string(),
+            });
+        }
+        let mut handles = Err(ERROR_PARTIAL_COPY);
+        for _ in 0..RETRIES {
+            handles = enum_modules(process);
+            if !matches!(handles, Err(ERROR_PARTIAL_COPY)) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        let handles = match handles {
+            Ok(h) => h,
+            Err(code) => {
+                CloseHandle(process);
+                return Err(trf("无法枚举模块：{}", &[&(std::io::Error::from_raw_os_error(code as i32))]));
+            }
+        };
+
+        let mut out = Vec::with_capacity(handles.len());
+        for module in handles {
+            let mut buf = [0u16; 1024];
+            let len = GetModuleFileNameExW(process, module, buf.as_mut_ptr(), buf.len() as u32);
+            if len == 0 {
+                continue;
+            }
+            let path = String::from_utf16_lossy(&buf[..len as usize]);
+            let mut info: MODULEINFO = std::mem::zeroed();
+            GetModuleInformation(process, module, &mut info, std::mem::size_of::<MODULEINFO>() as u32);
+            let version = crate::get_exe_version_string(std::path::Path::new(&path), "FileVersion");
+            out.push(Module {
+                path,
+                base: info.lpBaseOfDll as usize,
+                size: info.SizeOfImage,
+                version,
+            });
+        }
+        CloseHandle(process);
+        out.sort_by_key(|m| m.path.to_lowercase());
+        Ok(out)
+    }
+}
+
+/// 模块列表窗口的状态
+pub struct View {
+    pub pid: u32,
+    pub name: String,
+    pub modules: Result<Vec<Module>, String>,
+    pub filter: String,
+    /// 只显示可移动磁盘上的模块
+    pub only_removable: bool,
+    pub signatures: SignatureCache,
+}
+
+impl View {
+    pub fn open(pid: u32, name: &str) -> Self {
+        Self {
+            pid,
+            name: name.to_string(),
+            modules: list(pid),
+            filter: String::new(),
+            only_removable: false,
+            signatures: SignatureCache::new(),
+        }
+    }
+
+    pub fn reload(&mut self) {
+        self.modules = list(self.pid);
+    }
+}
@@ -0,0 +1,378 @@
+// ═══════════════════════════════════════════════════════════════
+//  挂载点管理 - 更改 / 移除盘符，挂载到空文件夹
+// ═══════════════════════════════════════════════════════════════
+//
+// 所有操作都以卷 GUID 路径 (\\?\Volume{...}\) 为准：
+//   更改盘符 = DeleteVolumeMountPoint(旧) + SetVolumeMountPoint(新, GUID)，失败时还原
+// 需要管理员权限；每次操作写入审计日志。
+//
+// 弹出流程中的“卷”可以是盘符 (E:)、文件夹挂载点 (C:\mnt\usb) 或卷 GUID 路径，
+// 统一经 volume_root / device_path 转换后再交给各个 API。
+
+use crate::i18n::{tr, trf};
+use crate::long_path::wide;
+use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+use windows_sys::Win32::Storage::FileSystem::{
+    DeleteVolumeMountPointW, FindFirstVolumeW, FindNextVolumeW, FindVolumeClose, GetDriveTypeW,
+    GetLogicalDrives, GetVolumeInformationW, GetVolumeNameForVolumeMountPointW,
+    GetVolumePathNamesForVolumeNameW, QueryDosDeviceW, SetVolumeMountPointW,
+};
+
+const DRIVE_REMOVABLE: u32 = 2;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MountOp {
+    /// 把盘符换成另一个空闲盘符
+    ChangeLetter { drive: String, letter: char },
+    /// 移除盘符 (卷仍在，只是不再出现在“此电脑”中)
+    RemoveLetter(String),
+    /// 额外挂载到一个空文件夹
+    MountFolder { drive: String, folder: String },
+    /// 为没有盘符的卷分配盘符
+    AssignLetter { volume: String, letter: char },
+    /// 卸下文件夹挂载点
+    UnmountFolder(String),
+}
+
+impl MountOp {
+    /// 操作作用的盘符 (已规范化)
+    pub fn drive(&self) -> Option<String> {
+        match self {
+            MountOp::ChangeLetter { drive, .. }
+            | MountOp::RemoveLetter(drive)
+            | MountOp::MountFolder { drive, .. } => Some(crate::norm_drive(drive)),
+            MountOp::AssignLetter { .. } | MountOp::UnmountFolder(_) => None,
+        }
+    }
+}
+
+/// 没有盘符的可移动卷
+#[derive(Clone, Debug)]
+pub struct UnmountedVolume {
+    pub volume: String,
+    pub label: String,
+    /// 文件夹挂载点 (可能为空)
+    pub folders: Vec<String>,
+}
+
+fn from_wide(buf: &[u16]) -> String {
+    let end = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    String::from_utf16_lossy(&buf[..end])
+}
+
+fn root_of(drive: &str) -> String {
+    format!("{}:\\", crate::norm_drive(drive))
+}
+
+/// "E" / "E:" / "E:\" 形式的盘符
+pub fn is_drive_letter(target: &str) -> bool {
+    let t = target.trim_end_matches([':', '\\', '/']);
+    t.len() == 1 && t.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// \\?\Volume{...}\ 形式的卷 GUID 路径
+pub fn is_volume_guid(target: &str) -> bool {
+    target
+        .get(..11)
+        .map(|head| head.eq_ignore_ascii_case("\\\\?\\Volume{"))
+        .unwrap_or(false)
+}
+
+/// 卷的根路径 (以反斜杠结尾)：盘符根目录、文件夹挂载点或卷 GUID 路径
+pub fn volume_root(target: &str) -> String {
+    if is_drive_letter(target) {
+        root_of(target)
+    } else {
+        with_trailing_slash(target)
+    }
+}
+
+/// 可用 CreateFileW 打开的卷设备路径：\\.\E: 或 \\?\Volume{...} (无结尾反斜杠)
+pub fn device_path(target: &str) -> String {
+    if is_drive_letter(target) {
+        return format!("\\\\.\\{}:", crate::norm_drive(target));
+    }
+    let volume = if is_volume_guid(target) { Some(volume_root(target)) } else { volume_name(target) };
+    volume.unwrap_or_else(|| volume_root(target)).trim_end_matches('\\').to_string()
+}
+
+/// 显示用的卷名：盘符为 "E:"，其余为挂载路径本身
+pub fn display_name(target: &str) -> String {
+    volume_root(target).trim_end_matches('\\').to_string()
+}
+
+/// 挂载点路径必须以反斜杠结尾
+fn with_trailing_slash(path: &str) -> String {
+    format!("{}\\", path.trim_end_matches(['\\', '/']))
+}
+
+fn last_error() -> String {
+    std::io::Error::last_os_error().to_string()
+}
+
+/// 盘符或文件夹挂载点对应的卷 GUID 路径
+pub fn volume_name(drive: &str) -> Option<String> {
+    let mut buf = [0u16; 64];
+    let root = crate::long_path::to_wide(std::ffi::OsStr::new(&volume_root(drive)));
+    let ok = unsafe { GetVolumeNameForVolumeMountPointW(root.as_ptr(), buf.as_mut_ptr(), buf.len() as u32) };
+    (ok != 0).then(|| from_wide(&buf))
+}
+
+/// 卷的全部挂载路径 (盘符根目录与文件夹)
+pub fn mount_paths(volume: &str) -> Vec<String> {
+    let volume = wide(volume);
+    let mut buf = vec![0u16; 1024];
+    let mut needed = 0u32;
+    let mut ok = unsafe {
+        GetVolumePathNamesForVolumeNameW(volume.as_ptr(), buf.as_mut_ptr(), buf.len() as u32, &mut needed)
+    };
+    // 挂载在很深的文件夹下时 1024 不够，按返回的所需长度重试一次
+    if ok == 0 && needed as usize > buf.len() {
+        buf = vec![0u16; needed as usize];
+        ok = unsafe {
+            GetVolumePathNamesForVolumeNameW(volume.as_ptr(), buf.as_mut_ptr(), buf.len() as u32, &mut needed)
+        };
+    }
+    if ok == 0 {
+        return vec![];
+    }
+    // 以 \0 分隔、\0\0 结尾的多字符串
+    buf.split(|&c| c == 0)
+        .take_while(|s| !s.is_empty())
+        .map(String::from_utf16_lossy)
+        .collect()
+}
+
+/// 某个盘的文件夹挂载点 (不含盘符根目录)
+pub fn folder_mounts(drive: &str) -> Vec<String> {
+    volume_name(drive)
+        .map(|v| mount_paths(&v).into_iter().filter(|p| p.len() > 3).collect())
+        .unwrap_or_default()
+}
+
+/// 当前未被占用的盘符 (跳过 A/B)
+pub fn free_letters() -> Vec<char> {
+    let used = unsafe { GetLogicalDrives() };
+    (2..26u32)
+        .filter(|i| used & (1 << i) == 0)
+        .map(|i| (b'A' + i as u8) as char)
+        .collect()
+}
+
+/// 卷标 (volume 为以反斜杠结尾的根路径)
+pub fn volume_label(volume: &str) -> String {
+    let mut label = [0u16; 261];
+    let ok = unsafe {
+        GetVolumeInformationW(
+            wide(volume).as_ptr(),
+            label.as_mut_ptr(),
+            label.len() as u32,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if ok == 0 {
+        String::new()
+    } else {
+        from_wide(&label)
+    }
+}
+
+/// 本机全部卷的 GUID 路径
+pub fn all_volumes() -> Vec<String> {
+    let mut out = Vec::new();
+    unsafe {
+        let mut buf = [0u16; 64];
+        let find = FindFirstVolumeW(buf.as_mut_ptr(), buf.len() as u32);
+        if find == INVALID_HANDLE_VALUE {
+            return out;
+        }
+        loop {
+            out.push(from_wide(&buf));
+            if FindNextVolumeW(find, buf.as_mut_ptr(), buf.len() as u32) == 0 {
+                break;
+            }
+        }
+        FindVolumeClose(find);
+    }
+    out
+}
+
+/// 卷的首选访问目标：有盘符用盘符，否则用第一个文件夹挂载点，都没有时用 GUID 路径
+pub fn preferred_target(volume: &str) -> String {
+    let paths = mount_paths(volume);
+    paths
+        .iter()
+        .find(|p| p.len() <= 3)
+        .map(|p| crate::norm_drive(p))
+        .or_else(|| paths.first().cloned())
+        .unwrap_or_else(|| volume.to_string())
+}
+
+/// 挂载点是否为该卷的首选目标 (有盘符的卷，其文件夹挂载点不是)
+pub fn is_preferred_mount(mount_point: &str) -> bool {
+    if is_drive_letter(mount_point) {
+        return true;
+    }
+    volume_name(mount_point)
+        .map(|v| volume_root(&preferred_target(&v)).eq_ignore_ascii_case(&volume_root(mount_point)))
+        .unwrap_or(true)
+}
+
+/// 一个卷的全部访问路径：盘符、文件夹挂载点、卷 GUID 路径，以及指向其中的 subst 盘符。
+/// 占用扫描与文件锁定查找都用它判断路径是否落在目标卷上，
+/// 而不是对盘符做字符串前缀匹配 ("I:" 会误中 "I:extra"，也认不出 GUID 路径)。
+#[derive(Clone, Debug)]
+pub struct VolumeMatcher {
+    roots: Vec<String>,
+}
+
+impl VolumeMatcher {
+    pub fn new(target: &str) -> Self {
+        let mut roots = vec![volume_root(target)];
+        let volume = if is_volume_guid(target) { Some(volume_root(target)) } else { volume_name(target) };
+        if let Some(volume) = volume {
+            roots.extend(mount_paths(&volume));
+            roots.push(volume);
+        }
+
+        // subst 盘符的 DOS 设备名形如 \??\I:\dir；subst 可以嵌套，直到不再新增为止
+        let substs: Vec<(String, String)> = used_letters()
+            .into_iter()
+            .filter_map(|l| subst_target(l).map(|t| (format!("{}:\\", l), t)))
+            .collect();
+        loop {
+            let before = roots.len();
+            for (root, target) in &substs {
+                let known = roots.iter().any(|r| r.eq_ignore_ascii_case(root));
+                if !known && roots.iter().any(|r| crate::long_path::is_under(std::ffi::OsStr::new(target), r)) {
+                    roots.push(root.clone());
+                }
+            }
+            if roots.len() == before {
+                break;
+            }
+        }
+        Self { roots }
+    }
+
+    pub fn contains_wide(&self, path: &[u16]) -> bool {
+        self.roots.iter().any(|r| crate::long_path::is_under_wide(path, r))
+    }
+
+    pub fn contains(&self, path: &std::ffi::OsStr) -> bool {
+        use std::os::windows::ffi::OsStrExt;
+        self.contains_wide(&path.encode_wide().collect::<Vec<_>>())
+    }
+}
+
+fn used_letters() -> Vec<char> {
+    let used = unsafe { GetLogicalDrives() };
+    (0..26u32)
+        .filter(|i| used & (1 << i) != 0)
+        .map(|i| (b'A' + i as u8) as char)
+        .collect()
+}
+
+/// subst 盘符指向的路径；普通盘符 (\Device\...) 返回 None
+fn subst_target(letter: char) -> Option<String> {
+    let mut buf = [0u16; 1024];
+    let len = unsafe { QueryDosDeviceW(wide(&format!("{}:", letter)).as_ptr(), buf.as_mut_ptr(), buf.len() as u32) };
+    if len == 0 {
+        return None;
+    }
+    from_wide(&buf).strip_prefix("\\??\\").map(|t| t.to_string())
+}
+
+/// 枚举没有盘符的可移动卷，便于把移除的盘符加回去
+pub fn unmounted_removable() -> Vec<UnmountedVolume> {
+    all_volumes()
+        .into_iter()
+        .filter(|volume| unsafe { GetDriveTypeW(wide(volume).as_ptr()) } == DRIVE_REMOVABLE)
+        .filter_map(|volume| {
+            let paths = mount_paths(&volume);
+            (!paths.iter().any(|p| p.len() <= 3)).then(|| UnmountedVolume {
+                label: volume_label(&volume),
+                volume,
+                folders: paths,
+            })
+        })
+        .collect()
+}
+
+fn set_mount(path: &str, volume: &str) -> Result<(), String> {
+    let path = crate::long_path::to_wide(std::ffi::OsStr::new(path));
+    let ok = unsafe { SetVolumeMountPointW(path.as_ptr(), wide(volume).as_ptr()) };
+    if ok == 0 {
+        Err(last_error())
+    } else {
+        Ok(())
+    }
+}
+
+fn delete_mount(path: &str) -> Result<(), String> {
+    let ok = unsafe { DeleteVolumeMountPointW(crate::long_path::to_wide(std::ffi::OsStr::new(path)).as_ptr()) };
+    if ok == 0 {
+        Err(last_error())
+    } else {
+        Ok(())
+    }
+}
+
+fn change_letter(drive: &str, letter: char) -> Result<String, String> {
+    let old = root_of(drive);
+    let new = format!("{}:\\", letter.to_ascii_uppercase());
+    if !free_letters().contains(&letter.to_ascii_uppercase()) {
+        return Err(trf("盘符 {} 已被占用", &[&&new[..2]]));
+    }
+    let volume = volume_name(drive).ok_or_else(|| trf("无法获取 {} 的卷名", &[&&old[..2]]))?;
+    delete_mount(&old).map_err(|e| trf("移除 {} 失败：{}", &[&&old[..2], &e]))?;
+    if let Err(e) = set_mount(&new, &volume) {
+        // 新盘符分配失败时把旧盘符还回去，避免卷“消失”
+        let _ = set_mount(&old, &volume);
+        return Err(trf("分配 {} 失败：{}", &[&&new[..2], &e]));
+    }
+    Ok(trf("{} 已改为 {}", &[&&old[..2], &&new[..2]]))
+}
+
+fn mount_folder(drive: &str, folder: &str) -> Result<String, String> {
+    let folder = folder.trim().trim_matches('"');
+    if folder.is_empty() {
+        return Err(tr("请填写目标文件夹").to_string());
+    }
+    std::fs::create_dir_all(folder).map_err(|e| trf("无法创建文件夹：{}", &[&e]))?;
+    let is_empty = std::fs::read_dir(folder)
+        .map(|mut d| d.next().is_none())
+        .unwrap_or(false);
+    if !is_empty {
+        return Err(tr("挂载目标必须是 NTFS 上的空文件夹").to_string());
+    }
+    let volume = volume_name(drive).ok_or_else(|| tr("无法获取卷名").to_string())?;
+    set_mount(&with_trailing_slash(folder), &volume).map_err(|e| trf("挂载失败：{}", &[&e]))?;
+    Ok(trf("{}: 已挂载到 {}", &[&crate::norm_drive(drive), &folder]))
+}
+
+/// 执行挂载操作，返回给用户看的结果
+pub fn apply(op: &MountOp) -> Result<String, String> {
+    let result = match op {
+        MountOp::ChangeLetter { drive, letter } => change_letter(drive, *letter),
+        MountOp::RemoveLetter(drive) => delete_mount(&root_of(drive))
+            .map(|_| trf("{}: 盘符已移除，可在“未分配盘符的卷”中重新分配", &[&crate::norm_drive(drive)])),
+        MountOp::MountFolder { drive, folder } => mount_folder(drive, folder),
+        MountOp::AssignLetter { volume, letter } => {
+            let root = format!("{}:\\", letter.to_ascii_uppercase());
+            set_mount(&root, volume).map(|_| trf("已分配盘符 {}", &[&&root[..2]]))
+        }
+        MountOp::UnmountFolder(path) => {
+            delete_mount(&with_trailing_slash(path)).map(|_| trf("已卸下 {}", &[&path]))
+        }
+    };
+    crate::audit::record(
+        "挂载点",
+        &format!("{:?} -> {}", op, result.as_ref().map(|s| s.as_str()).unwrap_or_else(|e| e.as_str())),
+    );
+    result
+}
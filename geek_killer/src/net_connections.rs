@@ -0,0 +1,192 @@
+// ═══════════════════════════════════════════════════════════════
+//  网络连接 - TCP/UDP 端点与所属进程 (GetExtendedTcpTable)
+// ═══════════════════════════════════════════════════════════════
+
+use crate::i18n::{tr, trf};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use windows_sys::Win32::NetworkManagement::IpHelper::{
+    GetExtendedTcpTable, GetExtendedUdpTable, SetTcpEntry, MIB_TCP6ROW_OWNER_PID, MIB_TCPROW_LH,
+    MIB_TCPROW_LH_0, MIB_TCPROW_OWNER_PID, MIB_TCP_STATE_DELETE_TCB, MIB_UDP6ROW_OWNER_PID,
+    MIB_UDPROW_OWNER_PID, TCP_TABLE_OWNER_PID_ALL, UDP_TABLE_OWNER_PID,
+};
+
+const AF_INET: u32 = 2;
+const AF_INET6: u32 = 23;
+const ERROR_INSUFFICIENT_BUFFER: u32 = 122;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+impl Protocol {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Protocol::Tcp => "TCP",
+            Protocol::Udp => "UDP",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Connection {
+    pub protocol: Protocol,
+    pub local: IpAddr,
+    pub local_port: u16,
+    /// UDP 没有远端
+    pub remote: Option<(IpAddr, u16)>,
+    /// MIB_TCP_STATE，UDP 为 0
+    pub state: u32,
+    pub pid: u32,
+}
+
+impl Connection {
+    pub fn state_label(&self) -> &'static str {
+        if self.protocol == Protocol::Udp {
+            return "";
+        }
+        match self.state {
+            1 => "CLOSED",
+            2 => "LISTEN",
+            3 => "SYN_SENT",
+            4 => "SYN_RCVD",
+            5 => "ESTABLISHED",
+            6 => "FIN_WAIT1",
+            7 => "FIN_WAIT2",
+            8 => "CLOSE_WAIT",
+            9 => "CLOSING",
+            10 => "LAST_ACK",
+            11 => "TIME_WAIT",
+            _ => "?",
+        }
+    }
+
+    /// 只有 IPv4 且已建立的 TCP 连接可以通过 SetTcpEntry 关闭
+    pub fn can_close(&self) -> bool {
+        self.protocol == Protocol::Tcp && self.local.is_ipv4() && self.state == 5
+    }
+
+    pub fn matches_port(&self, port: u16) -> bool {
+        self.local_port == port || self.remote.map(|(_, p)| p == port).unwrap_or(false)
+    }
+}
+
+/// 表中端口以网络字节序存放在 DWORD 的低 16 位
+fn port(raw: u32) -> u16 {
+    u16::from_be(raw as u16)
+}
+
+fn ipv4(raw: u32) -> IpAddr {
+    IpAddr::V4(Ipv4Addr::from(u32::from_be(raw)))
+}
+
+/// 两次调用：先取所需大小，再取数据
+unsafe fn fetch_table(f: impl Fn(*mut std::ffi::c_void, *mut u32) -> u32) -> Option<Vec<u8>> {
+    let mut size = 0u32;
+    let mut buf: Vec<u8> = Vec::new();
+    for _ in 0..3 {
+        buf.resize(size as usize, 0);
+        let ptr = if buf.is_empty() { std::ptr::null_mut() } else { buf.as_mut_ptr() as _ };
+        match f(ptr, &mut size) {
+            0 => return Some(buf),
+            ERROR_INSUFFICIENT_BUFFER => continue,
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// 解析 MIB_*TABLE_OWNER_PID：DWORD 条目数 + 行数组
+unsafe fn rows<T: Copy>(buf: &[u8]) -> Vec<T> {
+    if buf.len() < 4 {
+        return vec![];
+    }
+    let count = u32::from_ne_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+    // 行数组按自身对齐放在 DWORD 之后
+    let offset = std::mem::align_of::<T>().max(4);
+    let base = buf.as_ptr().add(offset) as *const T;
+    let fit = (buf.len().saturating_sub(offset)) / std::mem::size_of::<T>();
+    (0..count.min(fit)).map(|i| std::ptr::read_unaligned(base.add(i))).collect()
+}
+
+/// 枚举所有 TCP/UDP 端点 (IPv4 + IPv6)
+pub fn list() -> Vec<Connection> {
+    let mut out = Vec::new();
+    unsafe {
+        if let Some(buf) = fetch_table(|p, s| GetExtendedTcpTable(p, s, 1, AF_INET, TCP_TABLE_OWNER_PID_ALL, 0)) {
+            for r in rows::<MIB_TCPROW_OWNER_PID>(&buf) {
+                out.push(Connection {
+                    protocol: Protocol::Tcp,
+                    local: ipv4(r.dwLocalAddr),
+                    local_port: port(r.dwLocalPort),
+                    remote: (r.dwState != 2).then(|| (ipv4(r.dwRemoteAddr), port(r.dwRemotePort))),
+                    state: r.dwState,
+                    pid: r.dwOwningPid,
+                });
+            }
+        }
+        if let Some(buf) = fetch_table(|p, s| GetExtendedTcpTable(p, s, 1, AF_INET6, TCP_TABLE_OWNER_PID_ALL, 0)) {
+            for r in rows::<MIB_TCP6ROW_OWNER_PID>(&buf) {
+                out.push(Connection {
+                    protocol: Protocol::Tcp,
+                    local: IpAddr::V6(Ipv6Addr::from(r.ucLocalAddr)),
+                    local_port: port(r.dwLocalPort),
+                    remote: (r.dwState != 2)
+                        .then(|| (IpAddr::V6(Ipv6Addr::from(r.ucRemoteAddr)), port(r.dwRemotePort))),
+                    state: r.dwState,
+                    pid: r.dwOwningPid,
+                });
+            }
+        }
+        if let Some(buf) = fetch_table(|p, s| GetExtendedUdpTable(p, s, 1, AF_INET, UDP_TABLE_OWNER_PID, 0)) {
+            for r in rows::<MIB_UDPROW_OWNER_PID>(&buf) {
+                out.push(Connection {
+                    protocol: Protocol::Udp,
+                    local: ipv4(r.dwLocalAddr),
+                    local_port: port(r.dwLocalPort),
+                    remote: None,
+                    state: 0,
+                    pid: r.dwOwningPid,
+                });
+            }
+        }
+        if let Some(buf) = fetch_table(|p, s| GetExtendedUdpTable(p, s, 1, AF_INET6, UDP_TABLE_OWNER_PID, 0)) {
+            for r in rows::<MIB_UDP6ROW_OWNER_PID>(&buf) {
+                out.push(Connection {
+                    protocol: Protocol::Udp,
+                    local: IpAddr::V6(Ipv6Addr::from(r.ucLocalAddr)),
+                    local_port: port(r.dwLocalPort),
+                    remote: None,
+                    state: 0,
+                    pid: r.dwOwningPid,
+                });
+            }
+        }
+    }
+    out
+}
+
+/// 通过 SetTcpEntry 断开 IPv4 TCP 连接 (需要管理员权限)
+pub fn close(conn: &Connection) -> Result<(), String> {
+    let (IpAddr::V4(local), Some((IpAddr::V4(remote), remote_port))) = (conn.local, conn.remote) else {
+        return Err(tr("只能断开 IPv4 TCP 连接").to_string());
+    };
+    let row = MIB_TCPROW_LH {
+        Anonymous: MIB_TCPROW_LH_0 {
+            State: MIB_TCP_STATE_DELETE_TCB,
+        },
+        dwLocalAddr: u32::from(local).to_be(),
+        dwLocalPort: conn.local_port.to_be() as u32,
+        dwRemoteAddr: u32::from(remote).to_be(),
+        dwRemotePort: remote_port.to_be() as u32,
+    };
+    let status = unsafe { SetTcpEntry(&row) };
+    let desc = format!("{}:{} -> {}:{}", local, conn.local_port, remote, remote_port);
+    crate::audit::record("断开连接", &format!("{} (PID {}) -> {}", desc, conn.pid, status));
+    match status {
+        0 => Ok(()),
+        5 | 317 => Err(tr("需要管理员权限").to_string()),
+        e => Err(trf("SetTcpEntry 失败 (错误 {})", &[&e])),
+    }
+}
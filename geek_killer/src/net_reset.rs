@@ -0,0 +1,154 @@
+// ═══════════════════════════════════════════════════════════════
+//  网络重置 - ipconfig / netsh 引导流程
+// ═══════════════════════════════════════════════════════════════
+//
+// 断网、DNS 解析异常、“已连接但无法上网”时的标准排查顺序：
+//   0. ipconfig /all        先留存当前配置，作为回退参考
+//   1. ipconfig /release    释放 DHCP 租约 (会短暂断网)
+//   2. ipconfig /renew      重新获取地址
+//   3. ipconfig /flushdns   清空 DNS 缓存
+//   4. netsh winsock reset  重置 Winsock 目录 (可选，需要重启才生效)
+// 各步经由 tool_runner 在控制台中运行 (启动与退出由其写入审计日志)，输出逐步留存；
+// 与 system_repair 相同，某步无法启动或被取消时放弃后续步骤。
+
+use crate::i18n::{tr, trf};
+use crate::tool_runner::{ConsoleLine, ToolSpec};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetStep {
+    Snapshot,
+    Release,
+    Renew,
+    FlushDns,
+    WinsockReset,
+}
+
+impl NetStep {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NetStep::Snapshot => tr("留存当前配置"),
+            NetStep::Release => tr("释放 IP 地址"),
+            NetStep::Renew => tr("重新获取 IP 地址"),
+            NetStep::FlushDns => tr("清空 DNS 缓存"),
+            NetStep::WinsockReset => tr("重置 Winsock"),
+        }
+    }
+
+    pub fn spec(&self) -> ToolSpec {
+        match self {
+            NetStep::Snapshot => ToolSpec::new(self.label(), "ipconfig.exe", &["/all"]),
+            NetStep::Release => ToolSpec::new(self.label(), "ipconfig.exe", &["/release"]),
+            NetStep::Renew => ToolSpec::new(self.label(), "ipconfig.exe", &["/renew"]),
+            NetStep::FlushDns => ToolSpec::new(self.label(), "ipconfig.exe", &["/flushdns"]),
+            NetStep::WinsockReset => ToolSpec::new(self.label(), "netsh.exe", &["winsock", "reset"]),
+        }
+    }
+
+    /// 本次要执行的步骤
+    pub fn plan(winsock: bool) -> Vec<NetStep> {
+        let mut steps = vec![NetStep::Snapshot, NetStep::Release, NetStep::Renew, NetStep::FlushDns];
+        if winsock {
+            steps.push(NetStep::WinsockReset);
+        }
+        steps
+    }
+}
+
+/// 回退说明：这些操作本身无法“撤销”，这里说明各步的影响与恢复办法
+pub const ROLLBACK_NOTE: &str = "静态 IP 不受 release / renew 影响，DHCP 地址会自动重新分配；\
+DNS 缓存会在访问时重建。Winsock 重置会移除第三方分层服务 (部分代理 / VPN / 安全软件)，\
+如这些软件异常，请修复或重新安装它们。原配置已保存到下方文件，可对照手动恢复。";
+
+#[derive(Clone, Debug)]
+pub struct StepResult {
+    pub step: NetStep,
+    pub ok: bool,
+    pub summary: String,
+    /// 该步的完整输出
+    pub output: Vec<String>,
+}
+
+fn summarize(step: NetStep, lines: &[ConsoleLine], exit: &Result<i32, String>) -> StepResult {
+    let output: Vec<String> = lines.iter().map(|l| l.text.clone()).collect();
+    let (ok, summary) = match exit {
+        Err(e) => (false, e.clone()),
+        Ok(0) => (
+            true,
+            match step {
+                NetStep::WinsockReset => tr("已重置，重启计算机后生效").to_string(),
+                _ => tr("完成").to_string(),
+            },
+        ),
+        // 没有启用 DHCP 的适配器时 release / renew 会报错，但不影响后续步骤
+        Ok(code) => (false, trf("退出码 {}", &[&code])),
+    };
+    StepResult {
+        step,
+        ok,
+        summary,
+        output,
+    }
+}
+
+/// 一次网络重置：依次执行各步骤并收集结果
+pub struct NetResetSession {
+    pending: VecDeque<NetStep>,
+    pub current: Option<NetStep>,
+    pub results: Vec<StepResult>,
+    /// ipconfig /all 的留存文件
+    pub snapshot_file: Option<PathBuf>,
+}
+
+impl NetResetSession {
+    pub fn new(steps: &[NetStep]) -> Self {
+        Self {
+            pending: steps.iter().copied().collect(),
+            current: None,
+            results: vec![],
+            snapshot_file: None,
+        }
+    }
+
+    /// 确认对话框中展示的全部命令行
+    pub fn command_lines(steps: &[NetStep]) -> Vec<String> {
+        steps.iter().map(|s| s.spec().command_line()).collect()
+    }
+
+    /// 取出下一步 (None 表示全部完成)
+    pub fn next_step(&mut self) -> Option<ToolSpec> {
+        self.current = self.pending.pop_front();
+        self.current.map(|s| s.spec())
+    }
+
+    /// 当前步骤结束；无法启动或被取消时放弃后续步骤
+    pub fn finish_step(&mut self, lines: &[ConsoleLine], exit: &Result<i32, String>) {
+        if let Some(step) = self.current.take() {
+            let result = summarize(step, lines, exit);
+            if step == NetStep::Snapshot && result.ok {
+                self.snapshot_file = save_snapshot(&result.output);
+            }
+            if exit.is_err() {
+                self.pending.clear();
+            }
+            self.results.push(result);
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.current.is_none() && self.pending.is_empty()
+    }
+
+    /// 执行了 Winsock 重置，需要提示重启
+    pub fn needs_reboot(&self) -> bool {
+        self.results.iter().any(|r| r.step == NetStep::WinsockReset && r.ok)
+    }
+}
+
+fn save_snapshot(output: &[String]) -> Option<PathBuf> {
+    let stamp = crate::local_timestamp().replace([':', ' '], "-");
+    let path = crate::app_data_dir()?.join(format!("ipconfig_{}.txt", stamp));
+    std::fs::write(&path, output.join("\r\n")).ok()?;
+    Some(path)
+}
@@ -0,0 +1,188 @@
+// ═══════════════════════════════════════════════════════════════
+//  面板截图 - 单个面板截图并复制，报障时不必再裁剪整屏截图
+// ═══════════════════════════════════════════════════════════════
+//
+// 流程 (跨两帧)：
+//   1. 点击“截图并复制”：记下面板区域，本帧起在面板右下角绘制水印 (主机名 + 时间)
+//   2. 发送 ViewportCommand::Screenshot，下一帧收到 Event::Screenshot 后按面板区域裁剪
+//   3. 以 CF_DIB 写入剪贴板 (可直接粘贴到工单 / 聊天)；勾选保存时另存 PNG
+// 面板超出窗口可见范围的部分截不到，按窗口边界裁剪。
+
+use crate::i18n::{tr, trf};
+use eframe::egui;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use windows_sys::Win32::Foundation::GlobalFree;
+use windows_sys::Win32::System::DataExchange::{CloseClipboard, EmptyClipboard, OpenClipboard, SetClipboardData};
+use windows_sys::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+
+/// 标准剪贴板格式 CF_DIB
+const CF_DIB: u32 = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Panel {
+    Usb,
+    Diagnostics,
+    Security,
+    Startup,
+    Network,
+    Performance,
+}
+
+impl Panel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Panel::Usb => tr("外部存储管理"),
+            Panel::Diagnostics => tr("智能诊断"),
+            Panel::Security => tr("安全检查"),
+            Panel::Startup => tr("启动项"),
+            Panel::Network => tr("网络连接"),
+            Panel::Performance => tr("系统遥测"),
+        }
+    }
+
+    /// 保存文件名用的英文标识
+    fn slug(&self) -> &'static str {
+        match self {
+            Panel::Usb => "usb",
+            Panel::Diagnostics => "diagnostics",
+            Panel::Security => "security",
+            Panel::Startup => "startup",
+            Panel::Network => "network",
+            Panel::Performance => "performance",
+        }
+    }
+}
+
+/// 进行中的截图
+#[derive(Clone, Debug)]
+pub struct Capture {
+    pub panel: Panel,
+    pub save: bool,
+    /// 点击时的本地时间，用于水印与文件名
+    pub timestamp: String,
+    /// 面板在本帧的区域 (逻辑像素)；发出截图命令后不再更新
+    pub rect: Option<egui::Rect>,
+    /// 已发出 ViewportCommand::Screenshot，等待截图事件
+    pub sent: bool,
+    pub started: Instant,
+}
+
+impl Capture {
+    pub fn new(panel: Panel, save: bool, timestamp: &str) -> Self {
+        Self {
+            panel,
+            save,
+            timestamp: timestamp.to_string(),
+            rect: None,
+            sent: false,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn watermark(&self) -> String {
+        format!("{} · {} · {}", hostname(), self.timestamp, self.panel.label())
+    }
+}
+
+pub fn hostname() -> String {
+    std::env::var("COMPUTERNAME").unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// 在面板右下角绘制半透明底的水印
+pub fn paint_watermark(painter: &egui::Painter, rect: egui::Rect, text: &str) {
+    let galley = painter.layout_no_wrap(
+        text.to_string(),
+        egui::FontId::proportional(11.0),
+        egui::Color32::from_white_alpha(200),
+    );
+    let pos = rect.right_bottom() - galley.size() - egui::vec2(6.0, 4.0);
+    painter.rect_filled(
+        egui::Rect::from_min_size(pos, galley.size()).expand(3.0),
+        3.0,
+        egui::Color32::from_black_alpha(160),
+    );
+    painter.galley(pos, galley, egui::Color32::WHITE);
+}
+
+/// 从整窗截图中裁出面板区域
+pub fn crop(screen: &egui::ColorImage, rect: egui::Rect, pixels_per_point: f32) -> Option<egui::ColorImage> {
+    let bounds = egui::Rect::from_min_size(
+        egui::Pos2::ZERO,
+        egui::vec2(screen.size[0] as f32, screen.size[1] as f32) / pixels_per_point,
+    );
+    let rect = rect.intersect(bounds);
+    if rect.width() < 1.0 || rect.height() < 1.0 {
+        return None;
+    }
+    Some(screen.region(&rect, Some(pixels_per_point)))
+}
+
+/// 以 CF_DIB (32 位自下而上 BGRA) 写入剪贴板
+pub fn copy_to_clipboard(shot: &egui::ColorImage) -> Result<(), String> {
+    let [w, h] = shot.size;
+    let mut dib = Vec::with_capacity(40 + w * h * 4);
+    // BITMAPINFOHEADER
+    dib.extend_from_slice(&40u32.to_le_bytes());
+    dib.extend_from_slice(&(w as i32).to_le_bytes());
+    dib.extend_from_slice(&(h as i32).to_le_bytes()); // 正数 = 自下而上
+    dib.extend_from_slice(&1u16.to_le_bytes());
+    dib.extend_from_slice(&32u16.to_le_bytes());
+    dib.extend_from_slice(&0u32.to_le_bytes()); // BI_RGB
+    dib.extend_from_slice(&((w * h * 4) as u32).to_le_bytes());
+    dib.extend_from_slice(&[0u8; 16]); // 分辨率与调色板
+    for row in shot.pixels.chunks(w).rev() {
+        for p in row {
+            dib.extend_from_slice(&[p.b(), p.g(), p.r(), p.a()]);
+        }
+    }
+
+    unsafe {
+        let mem = GlobalAlloc(GMEM_MOVEABLE, dib.len());
+        if mem == 0 {
+            return Err(tr("无法分配剪贴板内存").to_string());
+        }
+        let ptr = GlobalLock(mem) as *mut u8;
+        if ptr.is_null() {
+            GlobalFree(mem);
+            return Err(tr("无法分配剪贴板内存").to_string());
+        }
+        std::ptr::copy_nonoverlapping(dib.as_ptr(), ptr, dib.len());
+        GlobalUnlock(mem);
+
+        if OpenClipboard(0) == 0 {
+            GlobalFree(mem);
+            return Err(trf("剪贴板被占用：{}", &[&std::io::Error::last_os_error()]));
+        }
+        EmptyClipboard();
+        // 成功后内存归系统所有，不能再释放
+        let ok = SetClipboardData(CF_DIB, mem) != 0;
+        CloseClipboard();
+        if !ok {
+            GlobalFree(mem);
+            return Err(trf("写入剪贴板失败：{}", &[&std::io::Error::last_os_error()]));
+        }
+    }
+    Ok(())
+}
+
+/// 保存为 PNG：<目录>\<面板>_<主机名>_<时间>.png
+pub fn save_png(shot: &egui::ColorImage, dir: &Path, capture: &Capture) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let stamp: String = capture
+        .timestamp
+        .chars()
+        .filter_map(|c| match c {
+            '0'..='9' => Some(c),
+            ' ' => Some('_'),
+            _ => None,
+        })
+        .collect();
+    let path = dir.join(format!("{}_{}_{}.png", capture.panel.slug(), hostname(), stamp));
+    let rgba: Vec<u8> = shot.pixels.iter().flat_map(|p| p.to_array()).collect();
+    image::RgbaImage::from_raw(shot.size[0] as u32, shot.size[1] as u32, rgba)
+        .ok_or_else(|| tr("图像尺寸错误").to_string())?
+        .save(&path)
+        .map_err(|e| e.to_string())?;
+    Ok(path)
+}
@@ -0,0 +1,55 @@
+// ═══════════════════════════════════════════════════════════════
+//  性能历史 - 最近 10 分钟的 CPU / 内存 / 网络曲线
+// ═══════════════════════════════════════════════════════════════
+//
+// 瞬时数字看不出尖峰是偶发还是趋势。后台线程每轮追加一个样本，
+// 按时间 (而非条数) 淘汰：降频或极简模式下间隔变长，仍然覆盖 10 分钟。
+// 与 AppSnapshot 分开存放，避免界面每次深拷贝快照时连带拷贝上千个样本。
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// 保留时长
+pub const SPAN: Duration = Duration::from_secs(600);
+/// 2Hz 下 10 分钟的样本数，作为容量上限
+const CAPACITY: usize = 1200;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sample {
+    pub at: Instant,
+    /// 全局 CPU 占用 (%)
+    pub cpu: f32,
+    /// 物理内存占用 (%)
+    pub memory: f32,
+    /// 网络接收 / 发送 (字节/秒)
+    pub net_in: f64,
+    pub net_out: f64,
+}
+
+#[derive(Default)]
+pub struct History {
+    samples: VecDeque<Sample>,
+}
+
+impl History {
+    pub fn push(&mut self, sample: Sample) {
+        while self.samples.len() >= CAPACITY
+            || self.samples.front().is_some_and(|s| sample.at.duration_since(s.at) > SPAN)
+        {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// 以“距现在的秒数” (负数) 为横轴的曲线点
+    pub fn series(&self, now: Instant, value: impl Fn(&Sample) -> f64) -> Vec<[f64; 2]> {
+        self.samples
+            .iter()
+            .map(|s| [-(now.saturating_duration_since(s.at).as_secs_f64()), value(s)])
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
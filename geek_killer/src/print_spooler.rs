@@ -0,0 +1,209 @@
+// ═══════════════════════════════════════════════════════════════
+//  重置打印后台 - 清除卡住的打印作业
+// ═══════════════════════════════════════════════════════════════
+//
+// 一个损坏的作业会卡住整个队列，“取消”也删不掉。标准处理：
+//   1. 记下各打印机队列中的作业 (停服务后就查不到了)
+//   2. 停止 Spooler 服务
+//   3. 删除 spool\PRINTERS 下的 .SPL / .SHD 文件
+//   4. 启动 Spooler
+// 网络打印机 (PRINTER_ENUM_CONNECTIONS) 的作业排在打印服务器上，本机停服务、删文件
+// 碰不到它们，需要在停服务前逐个 SetJob(JOB_CONTROL_DELETE)，结果以实际返回为准。
+// 移除的作业逐条列出并写入审计日志。
+
+use crate::i18n::trf;
+use crate::service_control::{outcome, Progress, Service};
+use std::path::PathBuf;
+use windows_sys::Win32::Graphics::Printing::{
+    ClosePrinter, EnumJobsW, EnumPrintersW, OpenPrinterW, SetJobW, JOB_CONTROL_DELETE, JOB_INFO_1W,
+    PRINTER_ENUM_CONNECTIONS, PRINTER_ENUM_LOCAL, PRINTER_HANDLE, PRINTER_INFO_4W,
+};
+
+pub const TITLE: &str = "重置打印后台";
+
+unsafe fn pwstr_to_string(p: *const u16) -> String {
+    if p.is_null() {
+        return String::new();
+    }
+    let len = (0..).take_while(|&i| *p.add(i) != 0).count();
+    String::from_utf16_lossy(std::slice::from_raw_parts(p, len))
+}
+
+fn spool_dir() -> PathBuf {
+    let windir = std::env::var("WINDIR").unwrap_or_else(|_| "C:\\Windows".to_string());
+    PathBuf::from(windir).join("System32\\spool\\PRINTERS")
+}
+
+/// 打印机名称，以及是否为网络打印机连接 (作业排在远程打印服务器上)
+fn printers() -> Vec<(String, bool)> {
+    let mut out = Vec::new();
+    let flags = PRINTER_ENUM_LOCAL | PRINTER_ENUM_CONNECTIONS;
+    unsafe {
+        let mut needed = 0u32;
+        let mut returned = 0u32;
+        EnumPrintersW(flags, std::ptr::null(), 4, std::ptr::null_mut(), 0, &mut needed, &mut returned);
+        // 用 u64 缓冲保证结构体中的指针对齐
+        let mut buf = vec![0u64; needed as usize / 8 + 1];
+        if EnumPrintersW(
+            flags,
+            std::ptr::null(),
+            4,
+            buf.as_mut_ptr() as *mut u8,
+            (buf.len() * 8) as u32,
+            &mut needed,
+            &mut returned,
+        ) != 0
+        {
+            let entries = std::slice::from_raw_parts(buf.as_ptr() as *const PRINTER_INFO_4W, returned as usize);
+            // 本机打印机的 pServerName 为空
+            out.extend(entries.iter().map(|e| (pwstr_to_string(e.pPrinterName), !e.pServerName.is_null())));
+        }
+    }
+    out
+}
+
+struct Printer(PRINTER_HANDLE);
+
+impl Printer {
+    fn open(printer: &str) -> Option<Self> {
+        let name: Vec<u16> = printer.encode_utf16().chain(std::iter::once(0)).collect();
+        let mut handle = 0;
+        (unsafe { OpenPrinterW(name.as_ptr(), &mut handle, std::ptr::null()) } != 0).then_some(Self(handle))
+    }
+}
+
+impl Drop for Printer {
+    fn drop(&mut self) {
+        unsafe {
+            ClosePrinter(self.0);
+        }
+    }
+}
+
+/// 某台打印机队列中的作业：(作业 ID, 说明)
+fn jobs(printer: &str) -> Vec<(u32, String)> {
+    let mut out = Vec::new();
+    let Some(handle) = Printer::open(printer) else {
+        return out;
+    };
+    unsafe {
+        let mut needed = 0u32;
+        let mut returned = 0u32;
+        EnumJobsW(handle.0, 0, u32::MAX, 1, std::ptr::null_mut(), 0, &mut needed, &mut returned);
+        let mut buf = vec![0u64; needed as usize / 8 + 1];
+        if needed > 0
+            && EnumJobsW(
+                handle.0,
+                0,
+                u32::MAX,
+                1,
+                buf.as_mut_ptr() as *mut u8,
+                (buf.len() * 8) as u32,
+                &mut needed,
+                &mut returned,
+            ) != 0
+        {
+            let entries = std::slice::from_raw_parts(buf.as_ptr() as *const JOB_INFO_1W, returned as usize);
+            for j in entries {
+                out.push((
+                    j.JobId,
+                    trf(
+                        "{} · {} ({}，{} 页)",
+                        &[&printer, &pwstr_to_string(j.pDocument), &pwstr_to_string(j.pUserName), &j.TotalPages],
+                    ),
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// 通过打印服务器删除网络打印机上的作业
+fn delete_job(printer: &str, job: u32) -> Result<(), String> {
+    let handle = Printer::open(printer).ok_or_else(|| trf("无法打开打印机：{}", &[&std::io::Error::last_os_error()]))?;
+    if unsafe { SetJobW(handle.0, job, 0, std::ptr::null(), JOB_CONTROL_DELETE) } == 0 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    Ok(())
+}
+
+/// 删除残留的作业文件，返回删除数量与失败的文件
+fn clear_spool_dir() -> (usize, Vec<String>) {
+    let mut removed = 0;
+    let mut failed = Vec::new();
+    let Ok(entries) = std::fs::read_dir(spool_dir()) else {
+        return (0, failed);
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => removed += 1,
+            Err(e) => failed.push(format!("{} ({})", path.display(), e)),
+        }
+    }
+    (removed, failed)
+}
+
+pub fn run(report: &dyn Fn(Progress)) -> Result<String, String> {
+    let manager = Service::manager()?;
+    let spooler = Service::open(&manager, "Spooler")?;
+
+    let mut local = Vec::new();
+    let mut remote = Vec::new();
+    for (printer, connection) in printers() {
+        for (id, job) in jobs(&printer) {
+            if connection {
+                remote.push((printer.clone(), id, job));
+            } else {
+                local.push(job);
+            }
+        }
+    }
+    report(Progress::Step(true, trf("队列中共 {} 个作业", &[&(local.len() + remote.len())])));
+
+    // 网络打印机的作业要趁本机 Spooler 还在运行时交给打印服务器删除
+    let mut removed_jobs = Vec::new();
+    for (printer, id, job) in remote {
+        match delete_job(&printer, id) {
+            Ok(()) => {
+                report(Progress::Step(true, trf("已移除：{}", &[&job])));
+                removed_jobs.push(job);
+            }
+            Err(e) => report(Progress::Step(false, trf("无法移除：{} ({})", &[&job, &e]))),
+        }
+    }
+
+    let r = spooler.stop();
+    report(Progress::Step(r.is_ok(), trf("停止 Print Spooler：{}", &[&outcome(&r)])));
+    r.map_err(|e| trf("无法停止 Print Spooler：{}", &[&e]))?;
+
+    let (removed, failed) = clear_spool_dir();
+    report(Progress::Step(
+        failed.is_empty(),
+        trf("清空 {}：删除 {} 个文件", &[&spool_dir().display(), &removed]),
+    ));
+    for f in &failed {
+        report(Progress::Step(false, trf("无法删除 {}", &[&f])));
+    }
+    // 本机作业的文件有删不掉的，无法确认具体哪些作业仍在
+    for job in local {
+        if failed.is_empty() {
+            report(Progress::Step(true, trf("已移除：{}", &[&job])));
+            removed_jobs.push(job);
+        } else {
+            report(Progress::Step(false, trf("可能未移除：{}", &[&job])));
+        }
+    }
+    if !removed_jobs.is_empty() {
+        crate::audit::record(TITLE, &format!("移除作业：{}", removed_jobs.join("；")));
+    }
+
+    let r = spooler.start();
+    report(Progress::Step(r.is_ok(), trf("启动 Print Spooler：{}", &[&outcome(&r)])));
+    r.map_err(|e| trf("Print Spooler 未能启动：{}", &[&e]))?;
+
+    Ok(trf("打印后台已重置，移除 {} 个作业 / {} 个残留文件", &[&removed_jobs.len(), &removed]))
+}
@@ -0,0 +1,157 @@
+// ═══════════════════════════════════════════════════════════════
+//  两台机器的进程对比 - 导出快照，与本机并排比较
+// ═══════════════════════════════════════════════════════════════
+//
+// 排查“同样配置为什么那台慢”时，最常用的办法是把两台机器的进程和服务列出来
+// 逐项对照，找出慢的那台多跑了什么。本程序没有远程代理，改为交换快照文件：
+//   1. 在另一台机器上用 Geek Killer 导出快照 (%APPDATA%\GeekKiller\snapshots\主机名.json)
+//   2. 拷到本机 (或直接填共享路径 \\主机\共享\xxx.json) 载入
+//   3. 与本机当前的进程 / 服务按名称对齐：只在一边存在的条目高亮，
+//      两边都有的按实例数与内存差异排在后面
+// 服务只记录正在运行的“自动”启动服务，与启动项面板的口径一致。
+
+use crate::i18n::{tr, trf};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// 同名进程一组
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessEntry {
+    pub name: String,
+    pub count: usize,
+    pub memory: u64,
+    pub cpu: f32,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MachineSnapshot {
+    pub host: String,
+    /// 本地时间，仅用于显示
+    pub taken_at: String,
+    pub processes: Vec<ProcessEntry>,
+    /// 服务名 (不是显示名)，显示名不同语言的系统不一致
+    pub services: Vec<String>,
+}
+
+impl MachineSnapshot {
+    pub fn capture(host: String, taken_at: String, processes: Vec<ProcessEntry>) -> Self {
+        let mut services: Vec<String> =
+            crate::startup_items::auto_services().into_iter().map(|s| s.name).collect();
+        services.sort_by_key(|s| s.to_lowercase());
+        services.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+        Self { host, taken_at, processes, services }
+    }
+}
+
+fn snapshot_dir() -> Option<PathBuf> {
+    let dir = crate::app_data_dir()?.join("snapshots");
+    std::fs::create_dir_all(&dir).ok()?;
+    Some(dir)
+}
+
+/// 写入快照目录，返回文件路径
+pub fn save(snapshot: &MachineSnapshot) -> Result<PathBuf, String> {
+    let dir = snapshot_dir().ok_or(tr("无法创建快照目录"))?;
+    let file: String = snapshot
+        .host
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let path = dir.join(format!("{}.json", file));
+    let json = serde_json::to_string_pretty(snapshot).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| trf("写入 {} 失败：{}", &[&path.display(), &e]))?;
+    crate::audit::record("进程对比", &format!("导出快照 {}", path.display()));
+    Ok(path)
+}
+
+pub fn load(path: &Path) -> Result<MachineSnapshot, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| trf("读取 {} 失败：{}", &[&path.display(), &e]))?;
+    serde_json::from_str(&text).map_err(|e| trf("{} 不是有效的快照：{}", &[&path.display(), &e]))
+}
+
+/// 快照目录中已有的文件 (新在前)，供下拉选择
+pub fn list_saved() -> Vec<PathBuf> {
+    let Some(dir) = snapshot_dir() else {
+        return Vec::new();
+    };
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().extension().is_some_and(|x| x.eq_ignore_ascii_case("json")))
+                .map(|e| (e.metadata().and_then(|m| m.modified()).unwrap_or(std::time::UNIX_EPOCH), e.path()))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by(|a, b| b.0.cmp(&a.0));
+    files.into_iter().map(|(_, p)| p).collect()
+}
+
+/// 对齐后的一行；某一边没有该进程时为 None
+#[derive(Clone, Debug)]
+pub struct ProcessRow {
+    pub name: String,
+    pub local: Option<ProcessEntry>,
+    pub other: Option<ProcessEntry>,
+}
+
+impl ProcessRow {
+    /// 两边都有时，对方比本机多用的内存 (可为负)
+    pub fn memory_delta(&self) -> i64 {
+        let mem = |e: &Option<ProcessEntry>| e.as_ref().map(|e| e.memory as i64).unwrap_or(0);
+        mem(&self.other) - mem(&self.local)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Diff {
+    /// 先列只在一边的进程 (按内存降序)，再列两边都有、按内存差异绝对值降序
+    pub processes: Vec<ProcessRow>,
+    pub services_only_local: Vec<String>,
+    pub services_only_other: Vec<String>,
+    pub services_shared: usize,
+}
+
+pub fn diff(local: &MachineSnapshot, other: &MachineSnapshot) -> Diff {
+    let mut rows: BTreeMap<String, ProcessRow> = BTreeMap::new();
+    for (side, snapshot) in [(false, local), (true, other)] {
+        for p in &snapshot.processes {
+            let row = rows.entry(p.name.to_lowercase()).or_insert_with(|| ProcessRow {
+                name: p.name.clone(),
+                local: None,
+                other: None,
+            });
+            let slot = if side { &mut row.other } else { &mut row.local };
+            // 快照里同名不同大小写的组合并计数
+            let e = slot.get_or_insert_with(|| ProcessEntry { name: p.name.clone(), ..Default::default() });
+            e.count += p.count;
+            e.memory += p.memory;
+            e.cpu += p.cpu;
+        }
+    }
+    let mut processes: Vec<ProcessRow> = rows.into_values().collect();
+    processes.sort_by_key(|r| {
+        let one_sided = r.local.is_none() || r.other.is_none();
+        let weight = if one_sided {
+            r.local.as_ref().or(r.other.as_ref()).map(|e| e.memory as i64).unwrap_or(0)
+        } else {
+            r.memory_delta().abs()
+        };
+        (!one_sided, std::cmp::Reverse(weight))
+    });
+
+    let lower = |v: &[String]| v.iter().map(|s| s.to_lowercase()).collect::<BTreeSet<_>>();
+    let (a, b) = (lower(&local.services), lower(&other.services));
+    let pick = |v: &[String], exclude: &BTreeSet<String>| {
+        v.iter().filter(|s| !exclude.contains(&s.to_lowercase())).cloned().collect::<Vec<_>>()
+    };
+    Diff {
+        processes,
+        services_only_local: pick(&local.services, &b),
+        services_only_other: pick(&other.services, &a),
+        services_shared: a.intersection(&b).count(),
+    }
+}
@@ -0,0 +1,176 @@
+// ═══════════════════════════════════════════════════════════════
+//  进程名数据库 (多语言 / 社区数据包)
+// ═══════════════════════════════════════════════════════════════
+//
+// 合并优先级 (后者覆盖前者)：
+//   1. 内置 en-US 包   2. 内置 zh-CN 包
+//   3. %APPDATA%\GeekKiller\packs\*.json 社区包，按 priority 升序
+// 与当前界面语言一致的包总是排在语言不一致的包之后，从而优先生效。
+
+use crate::i18n::tr;
+use crate::ProcessInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+/// 数据包文件格式
+///
+/// 各语言包的 `category` 一律填写规范分类键 (即中文名，如 "系统")，
+/// 只在显示时经 [`category_label`] 翻译；识别系统进程等逻辑只认规范键。
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NamePack {
+    pub name: String,
+    pub locale: String,
+    #[serde(default)]
+    pub priority: i32,
+    pub entries: BTreeMap<String, ProcessInfo>,
+}
+
+/// 未能识别的进程，供“提交未知进程”导出
+#[derive(Clone, Debug, Serialize)]
+pub struct UnknownProcess {
+    pub exe: String,
+    pub description: String,
+    pub path: String,
+}
+
+/// 内置的规范分类键 (路径规则兜底的分类也在其中)
+const CATEGORIES: [&str; 8] = ["系统", "浏览器", "通讯", "办公", "开发", "游戏", "驱动", "第三方应用"];
+
+/// 分类的显示文本：规范分类键按界面语言翻译，其余 (用户自定义) 原样显示
+pub fn category_label(category: &str) -> &str {
+    match category {
+        // "应用" 作为界面文案是 "Apply"，分类单独取词
+        "应用" => tr("应用程序"),
+        _ => CATEGORIES.iter().find(|c| **c == category).map_or(category, |c| tr(c)),
+    }
+}
+
+fn builtin_zh_cn() -> NamePack {
+    let mut m = BTreeMap::new();
+    m.insert("svchost.exe".into(), ProcessInfo::new("系统服务宿主", "系统"));
+    m.insert("explorer.exe".into(), ProcessInfo::new("资源管理器", "系统"));
+    m.insert("dwm.exe".into(), ProcessInfo::new("桌面窗口管理器", "系统"));
+    m.insert("searchindexer.exe".into(), ProcessInfo::new("Windows 搜索索引", "系统"));
+    m.insert("msedge.exe".into(), ProcessInfo::new("Edge 浏览器", "浏览器"));
+    m.insert("chrome.exe".into(), ProcessInfo::new("Chrome 浏览器", "浏览器"));
+    m.insert("wechat.exe".into(), ProcessInfo::new("微信", "通讯"));
+    m.insert("qq.exe".into(), ProcessInfo::new("QQ", "通讯"));
+    m.insert("dingtalk.exe".into(), ProcessInfo::new("钉钉", "办公"));
+    m.insert("feishu.exe".into(), ProcessInfo::new("飞书", "办公"));
+    m.insert("code.exe".into(), ProcessInfo::new("VS Code", "开发"));
+    m.insert("steam.exe".into(), ProcessInfo::new("Steam", "游戏"));
+    NamePack {
+        name: "内置 (简体中文)".into(),
+        locale: "zh-CN".into(),
+        priority: 0,
+        entries: m,
+    }
+}
+
+fn builtin_en_us() -> NamePack {
+    let mut m = BTreeMap::new();
+    m.insert("svchost.exe".into(), ProcessInfo::new("Service Host", "系统"));
+    m.insert("explorer.exe".into(), ProcessInfo::new("File Explorer", "系统"));
+    m.insert("dwm.exe".into(), ProcessInfo::new("Desktop Window Manager", "系统"));
+    m.insert("searchindexer.exe".into(), ProcessInfo::new("Windows Search Indexer", "系统"));
+    m.insert("msedge.exe".into(), ProcessInfo::new("Microsoft Edge", "浏览器"));
+    m.insert("chrome.exe".into(), ProcessInfo::new("Google Chrome", "浏览器"));
+    m.insert("wechat.exe".into(), ProcessInfo::new("WeChat", "通讯"));
+    m.insert("qq.exe".into(), ProcessInfo::new("QQ", "通讯"));
+    m.insert("dingtalk.exe".into(), ProcessInfo::new("DingTalk", "办公"));
+    m.insert("feishu.exe".into(), ProcessInfo::new("Feishu / Lark", "办公"));
+    m.insert("code.exe".into(), ProcessInfo::new("VS Code", "开发"));
+    m.insert("steam.exe".into(), ProcessInfo::new("Steam", "游戏"));
+    NamePack {
+        name: "Built-in (English)".into(),
+        locale: "en-US".into(),
+        priority: 0,
+        entries: m,
+    }
+}
+
+pub fn packs_dir() -> Option<PathBuf> {
+    let dir = crate::app_data_dir()?.join("packs");
+    std::fs::create_dir_all(&dir).ok()?;
+    Some(dir)
+}
+
+/// 读取社区数据包，格式错误的文件直接跳过
+fn load_community_packs() -> Vec<NamePack> {
+    let Some(dir) = packs_dir() else {
+        return vec![];
+    };
+    let Ok(rd) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+    rd.flatten()
+        .filter(|e| e.path().extension().map(|x| x == "json").unwrap_or(false))
+        .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+        .filter_map(|s| serde_json::from_str::<NamePack>(&s).ok())
+        .collect()
+}
+
+/// 按优先级合并所有数据包 (Key: 小写 exe 名)
+pub fn load_merged(locale: &str) -> HashMap<String, ProcessInfo> {
+    let mut builtin = vec![builtin_en_us(), builtin_zh_cn()];
+    let mut community = load_community_packs();
+    // 同层内：语言不匹配的先合并，匹配的后合并 (覆盖前者)
+    let rank = |p: &NamePack| (p.locale.eq_ignore_ascii_case(locale), p.priority);
+    builtin.sort_by_key(rank);
+    community.sort_by_key(rank);
+
+    let mut merged = HashMap::new();
+    for pack in builtin.into_iter().chain(community) {
+        for (exe, info) in pack.entries {
+            merged.insert(exe.to_lowercase(), info);
+        }
+    }
+    merged
+}
+
+/// 把未识别的进程导出为社区数据包模板，返回文件路径
+pub fn export_unknown(unknown: &[UnknownProcess]) -> Result<PathBuf, String> {
+    #[derive(Serialize)]
+    struct ExportEntry<'a> {
+        name: &'a str,
+        category: &'a str,
+        description: &'a str,
+        path: &'a str,
+    }
+    #[derive(Serialize)]
+    struct ExportPack<'a> {
+        name: &'a str,
+        locale: &'a str,
+        priority: i32,
+        entries: BTreeMap<&'a str, ExportEntry<'a>>,
+    }
+
+    let entries = unknown
+        .iter()
+        .map(|u| {
+            (
+                u.exe.as_str(),
+                ExportEntry {
+                    // 预填文件描述，贡献者只需校对名称与分类
+                    name: &u.description,
+                    category: "",
+                    description: &u.description,
+                    path: &u.path,
+                },
+            )
+        })
+        .collect();
+    let pack = ExportPack {
+        name: "未知进程提交",
+        locale: "zh-CN",
+        priority: 0,
+        entries,
+    };
+
+    let dir = crate::app_data_dir().ok_or("无法定位 %APPDATA% 目录")?;
+    let path = dir.join("unknown_processes.json");
+    let json = serde_json::to_string_pretty(&pack).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("写入失败：{}", e))?;
+    Ok(path)
+}
@@ -0,0 +1,410 @@
+// ═══════════════════════════════════════════════════════════════
+//  进程历史 - 每个进程的一生：启动 / 结束时间与峰值占用
+// ═══════════════════════════════════════════════════════════════
+//
+// 监控线程每轮把进程列表交给 Recorder：
+//   - 新出现的 (pid, 启动时间) 插入一行
+//   - 仍在运行的只在内存中更新峰值与“最后一次看到”，每分钟批量写回一次
+//   - 消失的写入结束时间 (= 最后一次看到的时间，误差一个采集周期)
+// 程序退出时仍在运行的行保留 stopped 为空；下次启动时重新接管，
+// 已经不在的按最后一次看到的时间补上结束时间。
+// 数据库超过 MAX_DB_BYTES 时删除最早的 1/5 已结束记录并回收空间 (auto_vacuum = INCREMENTAL)。
+// 网络收发字节数按小时累加到 traffic 表，随峰值一起每分钟写回，供周报统计。
+// 前台应用的使用时长 (screen_time) 按本地日期累加到 foreground 表，同样每分钟写回。
+
+use crate::i18n::tr;
+use crate::sqlite::{Connection, Value};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const DB_FILE: &str = "process_history.db";
+/// 数据库大小上限
+const MAX_DB_BYTES: i64 = 64 * 1024 * 1024;
+/// 峰值与最后一次看到的时间写回数据库的间隔
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+/// 检查数据库大小的间隔
+const RETENTION_INTERVAL: Duration = Duration::from_secs(10 * 60);
+/// 搜索最多返回的条数
+pub const SEARCH_LIMIT: i64 = 200;
+
+const SCHEMA: &str = "
+    PRAGMA auto_vacuum = INCREMENTAL;
+    PRAGMA journal_mode = WAL;
+    CREATE TABLE IF NOT EXISTS runs (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        path TEXT NOT NULL,
+        pid INTEGER NOT NULL,
+        started INTEGER NOT NULL,
+        last_seen INTEGER NOT NULL,
+        stopped INTEGER,
+        peak_memory INTEGER NOT NULL,
+        peak_cpu REAL NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS runs_name ON runs (name COLLATE NOCASE);
+    CREATE INDEX IF NOT EXISTS runs_started ON runs (started);
+    CREATE TABLE IF NOT EXISTS traffic (
+        hour INTEGER PRIMARY KEY,
+        received INTEGER NOT NULL,
+        sent INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS foreground (
+        day INTEGER NOT NULL,
+        name TEXT NOT NULL COLLATE NOCASE,
+        seconds INTEGER NOT NULL,
+        PRIMARY KEY (day, name)
+    );
+";
+
+fn db_path() -> Option<std::path::PathBuf> {
+    crate::app_data_dir().map(|d| d.join(DB_FILE))
+}
+
+fn open() -> Result<Connection, String> {
+    let path = db_path().ok_or(tr("无法定位数据目录"))?;
+    let conn = Connection::open(&path)?;
+    conn.execute_batch(SCHEMA)?;
+    Ok(conn)
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or(0)
+}
+
+/// 一轮采集中的一个进程
+pub struct Sample {
+    pub pid: u32,
+    pub start_time: u64,
+    pub name: String,
+    pub path: String,
+    pub memory: u64,
+    pub cpu: f32,
+}
+
+struct Live {
+    id: i64,
+    last_seen: i64,
+    peak_memory: u64,
+    peak_cpu: f32,
+}
+
+pub struct Recorder {
+    conn: Connection,
+    live: HashMap<(u32, u64), Live>,
+    flushed_at: Instant,
+    checked_at: Instant,
+    /// 尚未写回的网络收发字节数
+    traffic: (u64, u64),
+    /// 尚未写回的前台使用时长 (进程名 -> 毫秒)
+    foreground: HashMap<String, u64>,
+}
+
+impl Recorder {
+    /// 打开数据库并接管上次退出时仍在运行的记录
+    pub fn open() -> Result<Self, String> {
+        let conn = open()?;
+        let mut live = HashMap::new();
+        {
+            let mut stmt =
+                conn.prepare("SELECT id, pid, started, last_seen, peak_memory, peak_cpu FROM runs WHERE stopped IS NULL")?;
+            while stmt.step()? {
+                live.insert(
+                    (stmt.int(1) as u32, stmt.int(2) as u64),
+                    Live {
+                        id: stmt.int(0),
+                        last_seen: stmt.int(3),
+                        peak_memory: stmt.int(4) as u64,
+                        peak_cpu: stmt.real(5) as f32,
+                    },
+                );
+            }
+        }
+        Ok(Self {
+            conn,
+            live,
+            flushed_at: Instant::now(),
+            checked_at: Instant::now(),
+            traffic: (0, 0),
+            foreground: HashMap::new(),
+        })
+    }
+
+    /// 累加本轮的网络收发字节数，随下次写回计入当前小时
+    pub fn add_traffic(&mut self, received: u64, sent: u64) {
+        self.traffic.0 += received;
+        self.traffic.1 += sent;
+    }
+
+    /// 累加前台应用的使用时长，随下次写回计入当天
+    pub fn add_foreground(&mut self, name: &str, elapsed: Duration) {
+        *self.foreground.entry(name.to_string()).or_default() += elapsed.as_millis() as u64;
+    }
+
+    /// 记录本轮的进程列表；出错时只写审计日志，不影响监控
+    pub fn observe(&mut self, samples: impl Iterator<Item = Sample>) {
+        if let Err(e) = self.try_observe(samples) {
+            crate::audit::record("进程历史", &e);
+        }
+    }
+
+    fn try_observe(&mut self, samples: impl Iterator<Item = Sample>) -> Result<(), String> {
+        let now = now_secs();
+        let mut seen = HashSet::new();
+        let mut flushed = false;
+        self.conn.execute_batch("BEGIN")?;
+        let result = (|| -> Result<(), String> {
+            let mut insert = self.conn.prepare(
+                "INSERT INTO runs (name, path, pid, started, last_seen, peak_memory, peak_cpu)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+            for s in samples {
+                let key = (s.pid, s.start_time);
+                seen.insert(key);
+                if let Some(live) = self.live.get_mut(&key) {
+                    live.last_seen = now;
+                    live.peak_memory = live.peak_memory.max(s.memory);
+                    live.peak_cpu = live.peak_cpu.max(s.cpu);
+                    continue;
+                }
+                insert.execute(&[
+                    Value::Text(&s.name),
+                    Value::Text(&s.path),
+                    Value::Int(s.pid as i64),
+                    Value::Int(s.start_time as i64),
+                    Value::Int(now),
+                    Value::Int(s.memory as i64),
+                    Value::Real(s.cpu as f64),
+                ])?;
+                self.live.insert(
+                    key,
+                    Live {
+                        id: self.conn.last_insert_rowid(),
+                        last_seen: now,
+                        peak_memory: s.memory,
+                        peak_cpu: s.cpu,
+                    },
+                );
+            }
+
+            let mut stop = self
+                .conn
+                .prepare("UPDATE runs SET stopped = ?2, last_seen = ?2, peak_memory = ?3, peak_cpu = ?4 WHERE id = ?1")?;
+            let gone: Vec<(u32, u64)> = self.live.keys().filter(|k| !seen.contains(*k)).copied().collect();
+            for key in gone {
+                if let Some(l) = self.live.remove(&key) {
+                    stop.execute(&[
+                        Value::Int(l.id),
+                        Value::Int(l.last_seen),
+                        Value::Int(l.peak_memory as i64),
+                        Value::Real(l.peak_cpu as f64),
+                    ])?;
+                }
+            }
+
+            if self.flushed_at.elapsed() >= FLUSH_INTERVAL {
+                let mut update = self
+                    .conn
+                    .prepare("UPDATE runs SET last_seen = ?2, peak_memory = ?3, peak_cpu = ?4 WHERE id = ?1")?;
+                for l in self.live.values() {
+                    update.execute(&[
+                        Value::Int(l.id),
+                        Value::Int(l.last_seen),
+                        Value::Int(l.peak_memory as i64),
+                        Value::Real(l.peak_cpu as f64),
+                    ])?;
+                }
+                if self.traffic != (0, 0) {
+                    self.conn
+                        .prepare(
+                            "INSERT INTO traffic (hour, received, sent) VALUES (?1, ?2, ?3)
+                             ON CONFLICT (hour) DO UPDATE SET
+                                 received = received + excluded.received, sent = sent + excluded.sent",
+                        )?
+                        .execute(&[
+                            Value::Int(now / 3600 * 3600),
+                            Value::Int(self.traffic.0 as i64),
+                            Value::Int(self.traffic.1 as i64),
+                        ])?;
+                }
+                if !self.foreground.is_empty() {
+                    let mut add = self.conn.prepare(
+                        "INSERT INTO foreground (day, name, seconds) VALUES (?1, ?2, ?3)
+                         ON CONFLICT (day, name) DO UPDATE SET seconds = seconds + excluded.seconds",
+                    )?;
+                    let day = crate::screen_time::day_start(now as u64) as i64;
+                    for (name, ms) in &self.foreground {
+                        // 不足一秒的余数留到下次
+                        if *ms >= 1000 {
+                            add.execute(&[Value::Int(day), Value::Text(name), Value::Int((*ms / 1000) as i64)])?;
+                        }
+                    }
+                }
+                self.flushed_at = Instant::now();
+                flushed = true;
+            }
+            Ok(())
+        })();
+        self.conn.execute_batch(if result.is_ok() { "COMMIT" } else { "ROLLBACK" })?;
+        result?;
+        if flushed {
+            self.traffic = (0, 0);
+            self.foreground.retain(|_, ms| {
+                *ms %= 1000;
+                *ms > 0
+            });
+        }
+
+        if self.checked_at.elapsed() >= RETENTION_INTERVAL {
+            self.checked_at = Instant::now();
+            self.enforce_limit()?;
+        }
+        Ok(())
+    }
+
+    /// 超过大小上限时删除最早的 1/5 已结束记录
+    fn enforce_limit(&self) -> Result<(), String> {
+        let size = {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()")?;
+            if stmt.step()? {
+                stmt.int(0)
+            } else {
+                0
+            }
+        };
+        if size <= MAX_DB_BYTES {
+            return Ok(());
+        }
+        self.conn.execute_batch(
+            "DELETE FROM runs WHERE id IN (
+                 SELECT id FROM runs WHERE stopped IS NOT NULL ORDER BY id
+                 LIMIT (SELECT COUNT(*) / 5 FROM runs)
+             );
+             PRAGMA incremental_vacuum;",
+        )?;
+        crate::audit::record(
+            "进程历史",
+            &format!("数据库 {} MB 超过上限，已删除最早的记录", size / 1024 / 1024),
+        );
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Run {
+    pub name: String,
+    pub path: String,
+    pub pid: u32,
+    pub started: u64,
+    /// 为 None 表示仍在运行 (或程序上次退出时仍在运行、尚未确认)
+    pub stopped: Option<u64>,
+    pub last_seen: u64,
+    pub peak_memory: u64,
+    pub peak_cpu: f32,
+}
+
+impl Run {
+    /// 运行时长，秒
+    pub fn duration(&self) -> u64 {
+        self.stopped.unwrap_or(self.last_seen).saturating_sub(self.started)
+    }
+}
+
+/// LIKE 模式中的 % _ \ 按字面匹配
+fn like_pattern(query: &str) -> String {
+    let mut out = String::from("%");
+    for c in query.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('%');
+    out
+}
+
+/// 按进程名或路径搜索，最近启动的在前
+pub fn search(query: &str) -> Result<Vec<Run>, String> {
+    let conn = open()?;
+    let mut stmt = conn.prepare(
+        "SELECT name, path, pid, started, stopped, last_seen, peak_memory, peak_cpu FROM runs
+         WHERE name LIKE ?1 ESCAPE '\\' OR path LIKE ?1 ESCAPE '\\'
+         ORDER BY started DESC LIMIT ?2",
+    )?;
+    stmt.bind(&[Value::Text(&like_pattern(query.trim())), Value::Int(SEARCH_LIMIT)])?;
+    let mut out = Vec::new();
+    while stmt.step()? {
+        out.push(Run {
+            name: stmt.text(0),
+            path: stmt.text(1),
+            pid: stmt.int(2) as u32,
+            started: stmt.int(3) as u64,
+            stopped: (!stmt.is_null(4)).then(|| stmt.int(4) as u64),
+            last_seen: stmt.int(5) as u64,
+            peak_memory: stmt.int(6) as u64,
+            peak_cpu: stmt.real(7) as f32,
+        });
+    }
+    Ok(out)
+}
+
+/// 一段时间内某个进程名的汇总
+#[derive(Clone, Debug)]
+pub struct Consumer {
+    pub name: String,
+    pub runs: i64,
+    /// 期间内累计运行时长，秒 (多个实例重叠时相加)
+    pub total_secs: u64,
+    pub peak_memory: u64,
+    pub peak_cpu: f32,
+}
+
+/// 自 since (Unix 秒) 起运行过的进程，按峰值 CPU 或峰值内存降序取前 limit 个
+pub fn top_consumers(since: u64, by_cpu: bool, limit: i64) -> Result<Vec<Consumer>, String> {
+    let conn = open()?;
+    let order = if by_cpu { "MAX(peak_cpu)" } else { "MAX(peak_memory)" };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT name, COUNT(*), SUM(COALESCE(stopped, last_seen) - MAX(started, ?1)), MAX(peak_memory), MAX(peak_cpu)
+         FROM runs WHERE COALESCE(stopped, last_seen) >= ?1
+         GROUP BY name COLLATE NOCASE ORDER BY {} DESC LIMIT ?2",
+        order
+    ))?;
+    stmt.bind(&[Value::Int(since as i64), Value::Int(limit)])?;
+    let mut out = Vec::new();
+    while stmt.step()? {
+        out.push(Consumer {
+            name: stmt.text(0),
+            runs: stmt.int(1),
+            total_secs: stmt.int(2).max(0) as u64,
+            peak_memory: stmt.int(3) as u64,
+            peak_cpu: stmt.real(4) as f32,
+        });
+    }
+    Ok(out)
+}
+
+/// 自 since (Unix 秒) 起的网络 (接收, 发送) 字节数，按整小时统计
+pub fn traffic_since(since: u64) -> Result<(u64, u64), String> {
+    let conn = open()?;
+    let mut stmt =
+        conn.prepare("SELECT COALESCE(SUM(received), 0), COALESCE(SUM(sent), 0) FROM traffic WHERE hour >= ?1")?;
+    stmt.bind(&[Value::Int((since / 3600 * 3600) as i64)])?;
+    if stmt.step()? {
+        Ok((stmt.int(0) as u64, stmt.int(1) as u64))
+    } else {
+        Ok((0, 0))
+    }
+}
+
+/// 某个本地日期 (screen_time::day_start) 各应用的前台时长 (秒)，用得多的在前
+pub fn foreground_on(day: u64) -> Result<Vec<(String, u64)>, String> {
+    let conn = open()?;
+    let mut stmt = conn.prepare("SELECT name, seconds FROM foreground WHERE day = ?1 ORDER BY seconds DESC")?;
+    stmt.bind(&[Value::Int(day as i64)])?;
+    let mut out = Vec::new();
+    while stmt.step()? {
+        out.push((stmt.text(0), stmt.int(1) as u64));
+    }
+    Ok(out)
+}
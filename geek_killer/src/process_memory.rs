@@ -0,0 +1,114 @@
+// ═══════════════════════════════════════════════════════════════
+//  进程内存明细 - 工作集 / 私有工作集 / 提交大小
+// ═══════════════════════════════════════════════════════════════
+//
+// 工作集包含与其他进程共享的页面 (DLL、共享内存)。Electron / Chromium 系应用
+// 几十个进程共享同一份框架代码，按工作集相加会把共享部分重复计算很多次，
+// 排名明显偏高。三个口径：
+//   - 工作集：当前驻留物理内存的全部页面 (sysinfo 的 memory)
+//   - 私有工作集：驻留物理内存、且只属于该进程的页面，最接近“关掉能省多少内存”
+//     (PROCESS_MEMORY_COUNTERS_EX2，Windows 10 1809 起)
+//   - 提交大小：进程已提交的私有内存 (即“私有字节”，PrivateUsage / sysinfo 的
+//     virtual_memory)，含已换出到页面文件的部分，计入系统提交费用
+// 无权打开的进程读不到私有工作集，按工作集计。
+
+use crate::i18n::{tr, trf};
+use serde::{Deserialize, Serialize};
+use windows_sys::Win32::Foundation::CloseHandle;
+use windows_sys::Win32::System::ProcessStatus::GetProcessMemoryInfo;
+use windows_sys::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+/// PROCESS_MEMORY_COUNTERS_EX2 (windows-sys 未收录)
+#[repr(C)]
+#[derive(Default)]
+struct CountersEx2 {
+    cb: u32,
+    _page_fault_count: u32,
+    /// PeakWorkingSetSize 到 PrivateUsage 共 9 项
+    _counters: [usize; 9],
+    private_working_set_size: usize,
+    _shared_commit_usage: u64,
+}
+
+/// 私有工作集，字节；无权访问或系统不支持时为 None
+pub fn private_working_set(pid: u32) -> Option<u64> {
+    unsafe {
+        let h = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if h == 0 {
+            return None;
+        }
+        let mut counters = CountersEx2 {
+            cb: std::mem::size_of::<CountersEx2>() as u32,
+            ..Default::default()
+        };
+        let ok = GetProcessMemoryInfo(h, &mut counters as *mut CountersEx2 as _, counters.cb) != 0;
+        CloseHandle(h);
+        ok.then_some(counters.private_working_set_size as u64)
+    }
+}
+
+/// 进程表显示 / 排序所用的内存口径
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Metric {
+    #[default]
+    WorkingSet,
+    PrivateWorkingSet,
+    Commit,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 3] = [Metric::WorkingSet, Metric::PrivateWorkingSet, Metric::Commit];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Metric::WorkingSet => tr("工作集"),
+            Metric::PrivateWorkingSet => tr("私有工作集"),
+            Metric::Commit => tr("提交大小"),
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            Metric::WorkingSet => tr("驻留物理内存的全部页面，含与其他进程共享的部分 (多进程应用会重复计算)"),
+            Metric::PrivateWorkingSet => tr("驻留物理内存、只属于该进程的页面，最接近结束进程后能释放的内存"),
+            Metric::Commit => tr("已提交的私有内存 (私有字节)，含已换出到页面文件的部分"),
+        }
+    }
+}
+
+/// 一个进程 (或一组进程之和) 的三种内存口径，字节
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub working_set: u64,
+    pub private_working_set: u64,
+    pub commit: u64,
+}
+
+impl Usage {
+    pub fn get(&self, metric: Metric) -> u64 {
+        match metric {
+            Metric::WorkingSet => self.working_set,
+            Metric::PrivateWorkingSet => self.private_working_set,
+            Metric::Commit => self.commit,
+        }
+    }
+
+    pub fn add(&mut self, other: &Usage) {
+        self.working_set += other.working_set;
+        self.private_working_set += other.private_working_set;
+        self.commit += other.commit;
+    }
+
+    /// 悬停提示：三种口径一并列出
+    pub fn describe(&self) -> String {
+        let mb = |b: u64| b as f64 / 1024.0 / 1024.0;
+        trf(
+            "工作集 {} MB\n私有工作集 {} MB\n提交大小 {} MB",
+            &[
+                &format!("{:.1}", mb(self.working_set)),
+                &format!("{:.1}", mb(self.private_working_set)),
+                &format!("{:.1}", mb(self.commit)),
+            ],
+        )
+    }
+}
@@ -0,0 +1,90 @@
+// ═══════════════════════════════════════════════════════════════
+//  分组覆盖 - 用户手动指定进程的显示名与分类，优先于自动识别
+// ═══════════════════════════════════════════════════════════════
+//
+// 例如“ServiceHub.Host 始终显示为 开发 (VS) · VS 后台服务”。
+// 保存在 %APPDATA%\GeekKiller\process_overrides.json，按可执行文件完整路径 (小写) 索引：
+// 同名但不同位置的程序 (例如两个版本的 node.exe) 可以分别设置。
+// 监控线程每轮读取一次；字段为空表示沿用自动识别的结果。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Override {
+    /// 显示名 (为空沿用自动识别)
+    pub friendly_name: String,
+    /// 分类 (为空沿用自动识别)
+    pub category: String,
+}
+
+impl Override {
+    pub fn is_empty(&self) -> bool {
+        self.friendly_name.trim().is_empty() && self.category.trim().is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverrideStore {
+    by_path: HashMap<String, Override>,
+}
+
+fn key(exe_path: &str) -> String {
+    exe_path.to_lowercase()
+}
+
+impl OverrideStore {
+    fn path() -> Option<PathBuf> {
+        crate::app_data_dir().map(|d| d.join("process_overrides.json"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let (Some(p), Ok(json)) = (Self::path(), serde_json::to_string_pretty(self)) {
+            let _ = std::fs::write(p, json);
+        }
+    }
+
+    pub fn get(&self, exe_path: &str) -> Option<&Override> {
+        if exe_path.is_empty() {
+            return None;
+        }
+        self.by_path.get(&key(exe_path))
+    }
+
+    /// 设置或清除 (空覆盖即清除)
+    pub fn set(&mut self, exe_path: &str, o: Override) {
+        if o.is_empty() {
+            self.by_path.remove(&key(exe_path));
+        } else {
+            self.by_path.insert(
+                key(exe_path),
+                Override {
+                    friendly_name: o.friendly_name.trim().to_string(),
+                    category: o.category.trim().to_string(),
+                },
+            );
+        }
+    }
+
+    /// 在自动识别结果上叠加覆盖
+    pub fn apply(&self, exe_path: &str, friendly_name: &mut String, category: &mut String) {
+        if let Some(o) = self.get(exe_path) {
+            if !o.friendly_name.is_empty() {
+                *friendly_name = o.friendly_name.clone();
+            }
+            if !o.category.is_empty() {
+                *category = o.category.clone();
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+// ═══════════════════════════════════════════════════════════════
+//  进程所有者 - 账户、会话与完整性级别
+// ═══════════════════════════════════════════════════════════════
+//
+// 多人共用的电脑上，同名进程 (例如两个用户各自的 chrome.exe) 会归入同一分组。
+// 从进程令牌读取：
+//   - TokenUser          -> LookupAccountSid 得到 “域\用户名”，ConvertSidToStringSid 得到 SID
+//   - TokenSessionId     会话号 (0 为服务会话)
+//   - TokenIntegrityLevel 完整性级别 (低 / 中 / 高 / 系统)
+//   - TokenElevation     是否以管理员身份运行
+// 非管理员时无法打开其他用户的进程令牌，此时所有者未知。
+// “其他用户”只看 SID：真实账户为 S-1-5-21-*，DWM-* / UMFD-* 等虚拟账户与
+// SYSTEM、LOCAL SERVICE 等内置账户虽然运行在交互会话里，也不算其他用户。
+
+use crate::i18n::{tr, trf};
+use windows_sys::Win32::Foundation::{CloseHandle, LocalFree, HANDLE};
+use windows_sys::Win32::Security::Authorization::ConvertSidToStringSidW;
+use windows_sys::Win32::Security::{
+    GetSidSubAuthority, GetSidSubAuthorityCount, GetTokenInformation, LookupAccountSidW, TokenElevation,
+    TokenIntegrityLevel, TokenSessionId, TokenUser, PSID, SID_NAME_USE, TOKEN_ELEVATION, TOKEN_INFORMATION_CLASS,
+    TOKEN_MANDATORY_LABEL, TOKEN_QUERY, TOKEN_USER,
+};
+use windows_sys::Win32::System::RemoteDesktop::ProcessIdToSessionId;
+use windows_sys::Win32::System::Threading::{
+    GetCurrentProcess, OpenProcess, OpenProcessToken, PROCESS_QUERY_LIMITED_INFORMATION,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Integrity {
+    Untrusted,
+    Low,
+    Medium,
+    High,
+    System,
+}
+
+impl Integrity {
+    fn from_rid(rid: u32) -> Self {
+        match rid {
+            r if r < 0x1000 => Integrity::Untrusted,
+            r if r < 0x2000 => Integrity::Low,
+            r if r < 0x3000 => Integrity::Medium,
+            r if r < 0x4000 => Integrity::High,
+            _ => Integrity::System,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Integrity::Untrusted => tr("不受信任"),
+            Integrity::Low => tr("低"),
+            Integrity::Medium => tr("中"),
+            Integrity::High => tr("高"),
+            Integrity::System => tr("系统"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Owner {
+    /// 域\用户名
+    pub account: String,
+    /// 账户 SID 字符串，例如 S-1-5-21-...-1001
+    pub sid: String,
+    pub session: u32,
+    pub integrity: Option<Integrity>,
+    pub elevated: bool,
+}
+
+impl Owner {
+    /// 简短说明，例如 “DESKTOP\alice · 会话 1 · 高 (管理员)”
+    pub fn describe(&self) -> String {
+        let mut text = trf("{} · 会话 {}", &[&self.account, &self.session]);
+        if let Some(level) = self.integrity {
+            text.push_str(&trf(" · 完整性 {}", &[&level.label()]));
+        }
+        if self.elevated {
+            text.push_str(tr(" (管理员)"));
+        }
+        text
+    }
+
+    /// 其他真实用户的进程 (系统、服务与 DWM-* / UMFD-* 等虚拟账户不算)
+    pub fn is_other_user(&self, my_sid: &str) -> bool {
+        self.sid.starts_with("S-1-5-21-") && !self.sid.eq_ignore_ascii_case(my_sid)
+    }
+}
+
+/// 读取可变长度的令牌信息 (用 u64 缓冲保证对齐)
+unsafe fn token_info(token: HANDLE, class: TOKEN_INFORMATION_CLASS) -> Option<Vec<u64>> {
+    let mut len = 0u32;
+    GetTokenInformation(token, class, std::ptr::null_mut(), 0, &mut len);
+    if len == 0 {
+        return None;
+    }
+    let mut buf = vec![0u64; len as usize / 8 + 1];
+    (GetTokenInformation(token, class, buf.as_mut_ptr() as _, (buf.len() * 8) as u32, &mut len) != 0).then_some(buf)
+}
+
+unsafe fn account_name(sid: PSID) -> Option<String> {
+    let mut name = [0u16; 256];
+    let mut domain = [0u16; 256];
+    let mut name_len = name.len() as u32;
+    let mut domain_len = domain.len() as u32;
+    let mut sid_use: SID_NAME_USE = 0;
+    if LookupAccountSidW(
+        std::ptr::null(),
+        sid,
+        name.as_mut_ptr(),
+        &mut name_len,
+        domain.as_mut_ptr(),
+        &mut domain_len,
+        &mut sid_use,
+    ) == 0
+    {
+        return None;
+    }
+    let name = String::from_utf16_lossy(&name[..name_len as usize]);
+    let domain = String::from_utf16_lossy(&domain[..domain_len as usize]);
+    Some(if domain.is_empty() { name } else { format!("{}\\{}", domain, name) })
+}
+
+unsafe fn sid_string(sid: PSID) -> Option<String> {
+    let mut text: *mut u16 = std::ptr::null_mut();
+    if ConvertSidToStringSidW(sid, &mut text) == 0 || text.is_null() {
+        return None;
+    }
+    let len = (0..).take_while(|&i| *text.add(i) != 0).count();
+    let sid = String::from_utf16_lossy(std::slice::from_raw_parts(text, len));
+    LocalFree(text as _);
+    Some(sid)
+}
+
+unsafe fn from_token(token: HANDLE) -> Option<Owner> {
+    let user = token_info(token, TokenUser)?;
+    let user_sid = (*(user.as_ptr() as *const TOKEN_USER)).User.Sid;
+    let account = account_name(user_sid)?;
+    let sid = sid_string(user_sid)?;
+
+    let integrity = token_info(token, TokenIntegrityLevel).and_then(|buf| {
+        let sid = (*(buf.as_ptr() as *const TOKEN_MANDATORY_LABEL)).Label.Sid;
+        let count = *GetSidSubAuthorityCount(sid);
+        (count > 0).then(|| Integrity::from_rid(*GetSidSubAuthority(sid, count as u32 - 1)))
+    });
+
+    let mut session = 0u32;
+    let mut len = 0u32;
+    GetTokenInformation(token, TokenSessionId, &mut session as *mut u32 as _, 4, &mut len);
+
+    let mut elevation: TOKEN_ELEVATION = std::mem::zeroed();
+    let elevated = GetTokenInformation(
+        token,
+        TokenElevation,
+        &mut elevation as *mut _ as _,
+        std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+        &mut len,
+    ) != 0
+        && elevation.TokenIsElevated != 0;
+
+    Some(Owner {
+        account,
+        sid,
+        session,
+        integrity,
+        elevated,
+    })
+}
+
+/// 读取进程所有者；无权访问时为 None
+pub fn query(pid: u32) -> Option<Owner> {
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process == 0 {
+            return None;
+        }
+        let mut token = 0;
+        let ok = OpenProcessToken(process, TOKEN_QUERY, &mut token) != 0;
+        CloseHandle(process);
+        if !ok {
+            return None;
+        }
+        let owner = from_token(token);
+        CloseHandle(token);
+        owner
+    }
+}
+
+/// 进程所在会话；不需要打开进程令牌，非管理员也能查询服务进程
+pub fn session_of(pid: u32) -> Option<u32> {
+    let mut session = 0u32;
+    (unsafe { ProcessIdToSessionId(pid, &mut session) } != 0).then_some(session)
+}
+
+/// 本程序运行所用账户的 SID
+pub fn current_sid() -> String {
+    unsafe {
+        let mut token = 0;
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+            return String::new();
+        }
+        let owner = from_token(token);
+        CloseHandle(token);
+        owner.map(|o| o.sid).unwrap_or_default()
+    }
+}
@@ -0,0 +1,74 @@
+// ═══════════════════════════════════════════════════════════════
+//  进程优先级 - SetPriorityClass，供进程表批量调整
+// ═══════════════════════════════════════════════════════════════
+//
+// 不提供“实时”：实时优先级会饿死输入与磁盘线程，卡住整个系统。
+// 提升到“高”以上对其他用户的进程需要管理员权限 (SeIncreaseBasePriorityPrivilege)。
+
+use crate::i18n::{tr, trf};
+use windows_sys::Win32::Foundation::CloseHandle;
+use windows_sys::Win32::System::Threading::{
+    OpenProcess, SetPriorityClass, ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS, HIGH_PRIORITY_CLASS,
+    IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, PROCESS_SET_INFORMATION,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Priority {
+    Idle,
+    BelowNormal,
+    Normal,
+    AboveNormal,
+    High,
+}
+
+impl Priority {
+    pub const ALL: [Priority; 5] =
+        [Priority::Idle, Priority::BelowNormal, Priority::Normal, Priority::AboveNormal, Priority::High];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Priority::Idle => tr("低"),
+            Priority::BelowNormal => tr("低于正常"),
+            Priority::Normal => tr("正常"),
+            Priority::AboveNormal => tr("高于正常"),
+            Priority::High => tr("高"),
+        }
+    }
+
+    fn class(&self) -> u32 {
+        match self {
+            Priority::Idle => IDLE_PRIORITY_CLASS,
+            Priority::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+            Priority::Normal => NORMAL_PRIORITY_CLASS,
+            Priority::AboveNormal => ABOVE_NORMAL_PRIORITY_CLASS,
+            Priority::High => HIGH_PRIORITY_CLASS,
+        }
+    }
+}
+
+pub fn set(pid: u32, name: &str, priority: Priority) -> Result<(), String> {
+    let result = unsafe {
+        let process = OpenProcess(PROCESS_SET_INFORMATION, 0, pid);
+        if process == 0 {
+            Err(trf("无法打开进程 {} (可能需要管理员权限)", &[&pid]))
+        } else {
+            let ok = SetPriorityClass(process, priority.class()) != 0;
+            CloseHandle(process);
+            if ok {
+                Ok(())
+            } else {
+                Err(trf("{} (PID {}) 拒绝修改优先级", &[&name, &pid]))
+            }
+        }
+    };
+    crate::audit::record(
+        "调整优先级",
+        &format!(
+            "{} (PID {}) -> {}",
+            name,
+            pid,
+            result.as_ref().err().map(|e| e.as_str()).unwrap_or(priority.label())
+        ),
+    );
+    result
+}
@@ -0,0 +1,167 @@
+// ═══════════════════════════════════════════════════════════════
+//  重启进程 - 结束卡死的程序后按原样重新启动
+// ═══════════════════════════════════════════════════════════════
+//
+// 结束之前从目标进程的 PEB 读出启动信息 (RTL_USER_PROCESS_PARAMETERS)：
+//   - ImagePathName     可执行文件
+//   - CommandLine       完整命令行 (参数原样保留，不经拆分再拼接)
+//   - CurrentDirectory  工作目录
+// 以及令牌是否提权。结束并等待退出后，用同一命令行与工作目录重新启动；
+// 原进程未提权时经 unelevated 以普通用户权限启动，避免继承本程序的管理员身份。
+// 读取 PEB 要求目标与本程序位数一致 (32 位程序的 WOW64 进程同样可读原生 PEB)。
+
+use crate::i18n::{tr, trf};
+use std::time::Duration;
+use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+use windows_sys::Win32::Security::{GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY};
+use windows_sys::Win32::System::Diagnostics::Debug::ReadProcessMemory;
+use windows_sys::Win32::System::Threading::{
+    OpenProcess, OpenProcessToken, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ,
+};
+
+#[link(name = "ntdll")]
+extern "system" {
+    fn NtQueryInformationProcess(
+        process: HANDLE,
+        class: u32,
+        info: *mut std::ffi::c_void,
+        len: u32,
+        ret_len: *mut u32,
+    ) -> i32;
+}
+
+const PROCESS_BASIC_INFORMATION_CLASS: u32 = 0;
+const EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[repr(C)]
+struct ProcessBasicInformation {
+    exit_status: i32,
+    peb_base: usize,
+    affinity_mask: usize,
+    base_priority: i32,
+    unique_pid: usize,
+    parent_pid: usize,
+}
+
+// PEB / RTL_USER_PROCESS_PARAMETERS 中用到的字段偏移 (与本进程位数一致)
+const PTR: usize = std::mem::size_of::<usize>();
+const PEB_PROCESS_PARAMETERS: usize = 4 * PTR;
+#[cfg(target_pointer_width = "64")]
+const PARAMS_CURRENT_DIRECTORY: usize = 0x38;
+#[cfg(target_pointer_width = "32")]
+const PARAMS_CURRENT_DIRECTORY: usize = 0x24;
+#[cfg(target_pointer_width = "64")]
+const PARAMS_IMAGE_PATH_NAME: usize = 0x60;
+#[cfg(target_pointer_width = "32")]
+const PARAMS_IMAGE_PATH_NAME: usize = 0x38;
+#[cfg(target_pointer_width = "64")]
+const PARAMS_COMMAND_LINE: usize = 0x70;
+#[cfg(target_pointer_width = "32")]
+const PARAMS_COMMAND_LINE: usize = 0x40;
+
+/// 重新启动所需的信息
+#[derive(Clone, Debug)]
+pub struct LaunchInfo {
+    pub exe: String,
+    pub command_line: String,
+    pub cwd: String,
+    pub elevated: bool,
+}
+
+unsafe fn read_usize(h: HANDLE, addr: usize) -> Option<usize> {
+    let mut v = 0usize;
+    let mut read = 0usize;
+    let ok = ReadProcessMemory(h, addr as _, &mut v as *mut usize as _, PTR, &mut read);
+    (ok != 0 && read == PTR).then_some(v)
+}
+
+/// 读取 UNICODE_STRING { Length: u16, MaximumLength: u16, Buffer: ptr (对齐到指针宽度) }
+unsafe fn read_unicode_string(h: HANDLE, addr: usize) -> Option<String> {
+    let mut len = 0u16;
+    let mut read = 0usize;
+    if ReadProcessMemory(h, addr as _, &mut len as *mut u16 as _, 2, &mut read) == 0 {
+        return None;
+    }
+    let buffer = read_usize(h, addr + PTR)?;
+    let mut text = vec![0u16; len as usize / 2];
+    if !text.is_empty() && ReadProcessMemory(h, buffer as _, text.as_mut_ptr() as _, len as usize, &mut read) == 0 {
+        return None;
+    }
+    Some(String::from_utf16_lossy(&text))
+}
+
+unsafe fn is_elevated(h: HANDLE) -> bool {
+    let mut token = 0;
+    if OpenProcessToken(h, TOKEN_QUERY, &mut token) == 0 {
+        return false;
+    }
+    let mut elevation: TOKEN_ELEVATION = std::mem::zeroed();
+    let mut len = 0u32;
+    let ok = GetTokenInformation(
+        token,
+        TokenElevation,
+        &mut elevation as *mut _ as _,
+        std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+        &mut len,
+    ) != 0;
+    CloseHandle(token);
+    ok && elevation.TokenIsElevated != 0
+}
+
+/// 读取进程的启动信息
+pub fn capture(pid: u32) -> Result<LaunchInfo, String> {
+    unsafe {
+        let h = OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, 0, pid);
+        if h == 0 {
+            return Err(tr("无法读取进程信息 (可能需要管理员权限)").to_string());
+        }
+        let info = (|| {
+            let mut pbi: ProcessBasicInformation = std::mem::zeroed();
+            let mut ret = 0u32;
+            if NtQueryInformationProcess(
+                h,
+                PROCESS_BASIC_INFORMATION_CLASS,
+                &mut pbi as *mut _ as _,
+                std::mem::size_of::<ProcessBasicInformation>() as u32,
+                &mut ret,
+            ) < 0
+                || pbi.peb_base == 0
+            {
+                return None;
+            }
+            let params = read_usize(h, pbi.peb_base + PEB_PROCESS_PARAMETERS)?;
+            Some(LaunchInfo {
+                exe: read_unicode_string(h, params + PARAMS_IMAGE_PATH_NAME)?,
+                command_line: read_unicode_string(h, params + PARAMS_COMMAND_LINE)?,
+                cwd: read_unicode_string(h, params + PARAMS_CURRENT_DIRECTORY)?,
+                elevated: is_elevated(h),
+            })
+        })();
+        CloseHandle(h);
+        info.filter(|i| !i.exe.is_empty())
+            .ok_or_else(|| tr("无法读取命令行 (位数不同或受保护的进程)").to_string())
+    }
+}
+
+/// 结束进程并按原命令行重新启动
+pub fn restart(pid: u32) -> Result<LaunchInfo, String> {
+    let result = (|| {
+        let info = capture(pid)?;
+        rust_core_lib::process::kill(pid).map_err(|_| tr("无法结束进程").to_string())?;
+        if !crate::graceful::wait_exit(pid, EXIT_TIMEOUT) {
+            return Err(tr("进程未在 5 秒内退出").to_string());
+        }
+        let cwd = (!info.cwd.is_empty()).then_some(info.cwd.as_str());
+        crate::unelevated::spawn_command_line(&info.exe, &info.command_line, cwd, info.elevated)
+            .map_err(|e| trf("重新启动失败：{}", &[&e]))?;
+        Ok(info)
+    })();
+    crate::audit::record(
+        "重启进程",
+        &match &result {
+            Ok(info) => format!("PID {} -> 已重启：{} (工作目录 {})", pid, info.command_line, info.cwd),
+            Err(e) => format!("PID {} -> {}", pid, e),
+        },
+    );
+    result
+}
@@ -0,0 +1,122 @@
+// ═══════════════════════════════════════════════════════════════
+//  进程树 - 连同子进程一起终止，防止子进程把父进程拉起来
+// ═══════════════════════════════════════════════════════════════
+//
+// rust_core_lib::process 只提供单 PID 终止；进程树枚举放在本工程内实现，
+// 基于 CreateToolhelp32Snapshot 的 (PID, 父 PID) 快照。
+// 父 PID 只是创建时记下的数字：父进程退出后 PID 可能被复用，快照里看起来的
+// “子进程”其实与新进程毫无关系。因此只认创建时间不早于父进程的子进程，
+// 取不到创建时间 (无权打开) 的一律不算；桌面合成 / 显卡驱动进程不随树终止。
+
+use std::collections::{HashMap, HashSet};
+use windows_sys::Win32::Foundation::{CloseHandle, FILETIME, INVALID_HANDLE_VALUE};
+use windows_sys::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+    TH32CS_SNAPPROCESS,
+};
+use windows_sys::Win32::System::Threading::{GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+/// 当前所有进程的 (PID, 父 PID, 映像名)
+fn processes() -> Vec<(u32, u32, String)> {
+    let mut out = Vec::new();
+    unsafe {
+        let snap = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        if snap == INVALID_HANDLE_VALUE {
+            return out;
+        }
+        let mut entry: PROCESSENTRY32W = std::mem::zeroed();
+        entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+        if Process32FirstW(snap, &mut entry) != 0 {
+            loop {
+                let len = entry.szExeFile.iter().position(|&c| c == 0).unwrap_or(entry.szExeFile.len());
+                out.push((
+                    entry.th32ProcessID,
+                    entry.th32ParentProcessID,
+                    String::from_utf16_lossy(&entry.szExeFile[..len]),
+                ));
+                if Process32NextW(snap, &mut entry) == 0 {
+                    break;
+                }
+            }
+        }
+        CloseHandle(snap);
+    }
+    out
+}
+
+/// 进程创建时间 (FILETIME 刻度)；无权打开时为 None
+fn created_at(pid: u32) -> Option<u64> {
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process == 0 {
+            return None;
+        }
+        let mut times: [FILETIME; 4] = std::mem::zeroed();
+        let [created, exited, kernel, user] = &mut times;
+        let ok = GetProcessTimes(process, created, exited, kernel, user) != 0;
+        CloseHandle(process);
+        ok.then(|| ((created.dwHighDateTime as u64) << 32) | created.dwLowDateTime as u64)
+    }
+}
+
+/// 当前所有进程的 父 PID -> 子 (PID, 映像名)；只保留创建时间不早于父进程的子进程
+fn children_map() -> HashMap<u32, Vec<(u32, String)>> {
+    let list = processes();
+    let mut times: HashMap<u32, Option<u64>> = HashMap::new();
+    let mut time = |pid: u32| *times.entry(pid).or_insert_with(|| created_at(pid));
+    let mut map: HashMap<u32, Vec<(u32, String)>> = HashMap::new();
+    for (pid, parent, name) in list {
+        // PID 0 (Idle) 的父进程是自己，跳过避免成环
+        if pid == parent {
+            continue;
+        }
+        let genuine = match (time(parent), time(pid)) {
+            (Some(p), Some(c)) => c >= p,
+            _ => false,
+        };
+        if genuine {
+            map.entry(parent).or_default().push((pid, name));
+        }
+    }
+    map
+}
+
+/// 按映像名 (不区分大小写) 查找进程
+pub fn pids_by_name(name: &str) -> Vec<u32> {
+    processes()
+        .into_iter()
+        .filter(|(_, _, n)| n.eq_ignore_ascii_case(name))
+        .map(|(pid, _, _)| pid)
+        .collect()
+}
+
+/// 后序遍历收集子孙进程：子在前、父在后；显示关键进程及其子树不收集
+fn collect_post_order(
+    pid: u32,
+    map: &HashMap<u32, Vec<(u32, String)>>,
+    seen: &mut HashSet<u32>,
+    out: &mut Vec<u32>,
+) {
+    if !seen.insert(pid) {
+        return;
+    }
+    if let Some(children) = map.get(&pid) {
+        for (c, name) in children {
+            if !crate::gpu_guard::is_display_critical(name) {
+                collect_post_order(*c, map, seen, out);
+            }
+        }
+    }
+    out.push(pid);
+}
+
+/// 自底向上终止进程及其所有子孙进程，返回成功终止的数量
+pub fn kill_tree(pid: u32) -> usize {
+    let map = children_map();
+    let mut order = Vec::new();
+    collect_post_order(pid, &map, &mut HashSet::new(), &mut order);
+    order
+        .into_iter()
+        .filter(|p| rust_core_lib::process::kill(*p).is_ok())
+        .count()
+}
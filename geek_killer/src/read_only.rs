@@ -0,0 +1,306 @@
+// ═══════════════════════════════════════════════════════════════
+//  只读挂载 - 浏览来路不明的 U 盘而不留下任何写入
+// ═══════════════════════════════════════════════════════════════
+//
+// 同一物理磁盘上的每个卷先刷新缓存、独占锁定 (FSCTL_LOCK_VOLUME，有程序占用则放弃)
+// 并卸载 (FSCTL_DISMOUNT_VOLUME)，再对磁盘设置非持久的 DISK_ATTRIBUTE_READ_ONLY
+// (IOCTL_DISK_SET_DISK_ATTRIBUTES)；解锁后文件系统下次访问时按新属性重新挂载。
+// 先卸载再改属性，缓存中未写完的数据不会因为磁盘突然变只读而丢失。
+// 属性不写入磁盘，拔出后自动失效；需要管理员权限。
+//
+// 写保护 (交给别人之前用)：
+//   - 单个磁盘：同一属性加 Persist，等同 diskpart "attributes disk set readonly"，
+//     由本机记住，重新插入后仍然只读
+//   - 全局策略：HKLM\...\StorageDevicePolicies\WriteProtect = 1，
+//     对之后插入的所有 USB 存储生效 (已插入的需重新插拔)
+// 两者都只在本机生效，U 盘拿到别的电脑上仍可写。
+// 监控线程每轮都要显示写保护状态，但磁盘属性只会在插入或本程序修改时变化：
+// 调用方按设备缓存 is_write_protected 的结果，attributes_generation 变化时重新查询。
+
+use crate::i18n::{tr, trf};
+use crate::long_path::wide;
+use std::sync::atomic::{AtomicU64, Ordering};
+use windows_sys::Win32::Foundation::{CloseHandle, GENERIC_READ, GENERIC_WRITE, HANDLE, INVALID_HANDLE_VALUE};
+use windows_sys::Win32::Storage::FileSystem::{
+    CreateFileW, FlushFileBuffers, GetVolumeInformationW, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows_sys::Win32::System::Ioctl::{
+    DISK_ATTRIBUTE_READ_ONLY, FSCTL_DISMOUNT_VOLUME, FSCTL_LOCK_VOLUME, GET_DISK_ATTRIBUTES, IOCTL_DISK_GET_DISK_ATTRIBUTES,
+    IOCTL_DISK_SET_DISK_ATTRIBUTES, SET_DISK_ATTRIBUTES,
+};
+use windows_sys::Win32::System::Registry::{
+    RegCloseKey, RegCreateKeyExW, RegGetValueW, RegSetValueExW, HKEY, HKEY_LOCAL_MACHINE, KEY_SET_VALUE,
+    REG_DWORD, RRF_RT_REG_DWORD,
+};
+use windows_sys::Win32::System::IO::DeviceIoControl;
+
+/// GetVolumeInformationW 文件系统标志：卷为只读
+const FILE_READ_ONLY_VOLUME: u32 = 0x0008_0000;
+
+const POLICY_KEY: &str = "SYSTEM\\CurrentControlSet\\Control\\StorageDevicePolicies";
+const POLICY_VALUE: &str = "WriteProtect";
+
+/// 每次修改磁盘属性或写保护策略后递增
+static ATTRIBUTES_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 磁盘属性的修改计数；与上次不同说明缓存的写保护状态已过期
+pub fn attributes_generation() -> u64 {
+    ATTRIBUTES_GENERATION.load(Ordering::Relaxed)
+}
+
+fn open_rw(path: &str) -> Result<HANDLE, String> {
+    open_device(path, GENERIC_READ | GENERIC_WRITE)
+}
+
+fn open_device(path: &str, access: u32) -> Result<HANDLE, String> {
+    let h = unsafe {
+        CreateFileW(
+            wide(path).as_ptr(),
+            access,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            std::ptr::null(),
+            OPEN_EXISTING,
+            0,
+            0,
+        )
+    };
+    if h == INVALID_HANDLE_VALUE {
+        Err(trf("无法打开 {}：{}", &[&path, &std::io::Error::last_os_error()]))
+    } else {
+        Ok(h)
+    }
+}
+
+/// 卷当前是否以只读方式挂载
+pub fn is_read_only(drive: &str) -> bool {
+    let root = wide(&crate::mount_manager::volume_root(drive));
+    let mut flags = 0u32;
+    let ok = unsafe {
+        GetVolumeInformationW(
+            root.as_ptr(),
+            std::ptr::null_mut(),
+            0,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &mut flags,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    ok != 0 && flags & FILE_READ_ONLY_VOLUME != 0
+}
+
+/// 所在物理磁盘是否带只读属性 (只读挂载或写保护)；只查询属性，不需要读写权限
+pub fn is_write_protected(drive: &str) -> bool {
+    let Some(number) = crate::usb_info::disk_number(drive) else {
+        return false;
+    };
+    let Ok(disk) = open_device(&format!("\\\\.\\PhysicalDrive{}", number), 0) else {
+        return false;
+    };
+    let mut attrs: GET_DISK_ATTRIBUTES = unsafe { std::mem::zeroed() };
+    let mut bytes = 0u32;
+    let ok = unsafe {
+        let ok = DeviceIoControl(
+            disk,
+            IOCTL_DISK_GET_DISK_ATTRIBUTES,
+            std::ptr::null(),
+            0,
+            &mut attrs as *mut _ as _,
+            std::mem::size_of::<GET_DISK_ATTRIBUTES>() as u32,
+            &mut bytes,
+            std::ptr::null_mut(),
+        );
+        CloseHandle(disk);
+        ok
+    };
+    ok != 0 && attrs.Attributes & DISK_ATTRIBUTE_READ_ONLY != 0
+}
+
+/// 切换只读：卸载卷后设置磁盘属性，让文件系统重新挂载
+pub fn set_read_only(drive: &str, on: bool) -> Result<String, String> {
+    let d = crate::norm_drive(drive);
+    let result = apply_disk_attribute(&d, on, false);
+    // 无论成败都让缓存的写保护状态重新查询
+    ATTRIBUTES_GENERATION.fetch_add(1, Ordering::Relaxed);
+    let result = result.map(|_| {
+        if on {
+            trf("{}: 已切换为只读挂载 (拔出后失效)", &[&d])
+        } else {
+            trf("{}: 已恢复可写", &[&d])
+        }
+    });
+    crate::audit::record(
+        "只读挂载",
+        &format!("{}: {} -> {}", d, if on { "只读" } else { "可写" }, result.as_ref().map(|s| s.as_str()).unwrap_or_else(|e| e.as_str())),
+    );
+    result
+}
+
+/// 磁盘写保护：持久的只读属性，重新插入后仍然有效
+pub fn set_write_protect(drive: &str, on: bool) -> Result<String, String> {
+    let d = crate::norm_drive(drive);
+    let result = apply_disk_attribute(&d, on, true);
+    ATTRIBUTES_GENERATION.fetch_add(1, Ordering::Relaxed);
+    let result = result.map(|_| {
+        if on {
+            trf("{}: 已开启写保护 (本机记住，重新插入后仍只读)", &[&d])
+        } else {
+            trf("{}: 已解除写保护", &[&d])
+        }
+    });
+    crate::audit::record(
+        "写保护",
+        &format!("{}: {} -> {}", d, if on { "开启" } else { "解除" }, result.as_ref().map(|s| s.as_str()).unwrap_or_else(|e| e.as_str())),
+    );
+    result
+}
+
+/// 已锁定并卸载的卷；句柄关闭即解锁
+struct LockedVolume(HANDLE);
+
+impl Drop for LockedVolume {
+    fn drop(&mut self) {
+        unsafe { CloseHandle(self.0) };
+    }
+}
+
+fn volume_ioctl(volume: HANDLE, code: u32) -> bool {
+    let mut bytes = 0u32;
+    unsafe {
+        DeviceIoControl(
+            volume,
+            code,
+            std::ptr::null(),
+            0,
+            std::ptr::null_mut(),
+            0,
+            &mut bytes,
+            std::ptr::null_mut(),
+        ) != 0
+    }
+}
+
+/// 刷新 -> 锁定 -> 卸载，任何一步失败都放弃
+fn lock_and_dismount(target: &str) -> Result<LockedVolume, String> {
+    let name = crate::mount_manager::display_name(target);
+    // 已是只读挂载的卷没有待写数据，刷新会因写保护失败，跳过
+    let read_only = is_read_only(target);
+    let volume = LockedVolume(open_rw(&crate::mount_manager::device_path(target))?);
+    if !read_only && unsafe { FlushFileBuffers(volume.0) } == 0 {
+        return Err(trf("刷新 {} 的写入缓存失败：{}", &[&name, &std::io::Error::last_os_error()]));
+    }
+    let locked = (0..5).any(|i| {
+        if i > 0 {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+        }
+        volume_ioctl(volume.0, FSCTL_LOCK_VOLUME)
+    });
+    if !locked {
+        return Err(trf("{} 正被其他程序使用，无法独占锁定；请先关闭占用程序", &[&name]));
+    }
+    if !volume_ioctl(volume.0, FSCTL_DISMOUNT_VOLUME) {
+        return Err(trf("卸载 {} 失败：{}", &[&name, &std::io::Error::last_os_error()]));
+    }
+    Ok(volume)
+}
+
+fn apply_disk_attribute(d: &str, on: bool, persist: bool) -> Result<(), String> {
+    let number = crate::usb_info::disk_number(d).ok_or(tr("无法确定所在物理磁盘"))?;
+    // 改属性期间卷保持锁定，防止系统在中途以旧属性重新挂载
+    let locks = crate::usb_info::volumes_on_same_disk(d)
+        .iter()
+        .map(|v| lock_and_dismount(v))
+        .collect::<Result<Vec<LockedVolume>, String>>()?;
+    let disk = open_rw(&format!("\\\\.\\PhysicalDrive{}", number))?;
+    let attrs = SET_DISK_ATTRIBUTES {
+        Version: std::mem::size_of::<SET_DISK_ATTRIBUTES>() as u32,
+        Persist: persist as _,
+        Reserved1: [0; 3],
+        Attributes: if on { DISK_ATTRIBUTE_READ_ONLY } else { 0 },
+        AttributesMask: DISK_ATTRIBUTE_READ_ONLY,
+        Reserved2: [0; 4],
+    };
+    let mut bytes = 0u32;
+    let ok = unsafe {
+        let ok = DeviceIoControl(
+            disk,
+            IOCTL_DISK_SET_DISK_ATTRIBUTES,
+            &attrs as *const _ as _,
+            std::mem::size_of::<SET_DISK_ATTRIBUTES>() as u32,
+            std::ptr::null_mut(),
+            0,
+            &mut bytes,
+            std::ptr::null_mut(),
+        );
+        CloseHandle(disk);
+        ok
+    };
+    if ok == 0 {
+        return Err(trf("设置磁盘属性失败：{}", &[&std::io::Error::last_os_error()]));
+    }
+    // 解锁：下次访问时按新属性重新挂载
+    drop(locks);
+    Ok(())
+}
+
+/// 全局 USB 存储写保护策略是否开启
+pub fn policy_write_protect() -> bool {
+    let mut value = 0u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_LOCAL_MACHINE,
+            wide(POLICY_KEY).as_ptr(),
+            wide(POLICY_VALUE).as_ptr(),
+            RRF_RT_REG_DWORD,
+            std::ptr::null_mut(),
+            &mut value as *mut u32 as _,
+            &mut size,
+        )
+    };
+    status == 0 && value != 0
+}
+
+/// 设置全局写保护策略 (需要管理员权限，对之后插入的设备生效)
+pub fn set_policy_write_protect(on: bool) -> Result<String, String> {
+    let result = (|| {
+        let mut key: HKEY = 0;
+        let status = unsafe {
+            RegCreateKeyExW(
+                HKEY_LOCAL_MACHINE,
+                wide(POLICY_KEY).as_ptr(),
+                0,
+                std::ptr::null(),
+                0,
+                KEY_SET_VALUE,
+                std::ptr::null(),
+                &mut key,
+                std::ptr::null_mut(),
+            )
+        };
+        if status != 0 {
+            return Err(trf("无法打开 StorageDevicePolicies (错误 {})，需要管理员权限", &[&status]));
+        }
+        let data = (on as u32).to_le_bytes();
+        let status = unsafe {
+            let status = RegSetValueExW(key, wide(POLICY_VALUE).as_ptr(), 0, REG_DWORD, data.as_ptr(), data.len() as u32);
+            RegCloseKey(key);
+            status
+        };
+        if status != 0 {
+            return Err(trf("写入 WriteProtect 失败 (错误 {})", &[&status]));
+        }
+        Ok(if on {
+            tr("已开启全局 USB 写保护，重新插拔后生效").to_string()
+        } else {
+            tr("已关闭全局 USB 写保护，重新插拔后恢复可写").to_string()
+        })
+    })();
+    ATTRIBUTES_GENERATION.fetch_add(1, Ordering::Relaxed);
+    crate::audit::record(
+        "写保护",
+        &format!("StorageDevicePolicies {} -> {}", on, result.as_ref().map(|s| s.as_str()).unwrap_or_else(|e| e.as_str())),
+    );
+    result
+}
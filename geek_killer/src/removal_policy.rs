@@ -0,0 +1,107 @@
+// ═══════════════════════════════════════════════════════════════
+//  删除策略 - “快速删除” 与 “更好的性能”
+// ═══════════════════════════════════════════════════════════════
+//
+// 磁盘设备节点上的两个属性：
+//   CM_DRP_REMOVAL_POLICY          当前生效的策略
+//   CM_DRP_REMOVAL_POLICY_OVERRIDE 用户覆盖值 (设备管理器“策略”页写的就是它)
+// “更好的性能”会开启写缓存，不先安全弹出就拔盘容易丢数据，弹出时也更容易被
+// 否决 (VetoType 6)。覆盖值在设备重新插拔后才生效；修改需要管理员权限。
+
+use crate::i18n::{tr, trf};
+use windows_sys::Win32::Devices::DeviceAndDriverInstallation::{
+    CM_Set_DevNode_Registry_PropertyW, CM_DRP_REMOVAL_POLICY, CM_DRP_REMOVAL_POLICY_OVERRIDE, CR_SUCCESS,
+};
+
+// CM_REMOVAL_POLICY 取值
+const EXPECT_NO_REMOVAL: u32 = 1;
+const EXPECT_ORDERLY_REMOVAL: u32 = 2;
+const EXPECT_SURPRISE_REMOVAL: u32 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// 快速删除：无写缓存，可直接拔出
+    QuickRemoval,
+    /// 更好的性能：开启写缓存，必须先安全弹出
+    BetterPerformance,
+    /// 固定磁盘，不支持热插拔
+    Fixed,
+}
+
+impl Policy {
+    fn from_raw(raw: u32) -> Option<Self> {
+        match raw {
+            EXPECT_SURPRISE_REMOVAL => Some(Policy::QuickRemoval),
+            EXPECT_ORDERLY_REMOVAL => Some(Policy::BetterPerformance),
+            EXPECT_NO_REMOVAL => Some(Policy::Fixed),
+            _ => None,
+        }
+    }
+
+    fn raw(&self) -> u32 {
+        match self {
+            Policy::QuickRemoval => EXPECT_SURPRISE_REMOVAL,
+            Policy::BetterPerformance => EXPECT_ORDERLY_REMOVAL,
+            Policy::Fixed => EXPECT_NO_REMOVAL,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Policy::QuickRemoval => tr("快速删除"),
+            Policy::BetterPerformance => tr("更好的性能"),
+            Policy::Fixed => tr("固定磁盘"),
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            Policy::QuickRemoval => tr("未开启写缓存，复制完成即可拔出；写入速度稍慢"),
+            Policy::BetterPerformance => tr("已开启写缓存：必须先安全弹出再拔，否则可能丢数据；弹出也更容易被占用否决"),
+            Policy::Fixed => tr("系统视其为固定磁盘"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RemovalPolicy {
+    pub current: Policy,
+    /// 已设置但尚未生效 (需重新插拔) 的覆盖值
+    pub pending: Option<Policy>,
+}
+
+/// 查询磁盘设备节点的删除策略
+pub fn query(dev_inst: u32) -> Option<RemovalPolicy> {
+    let current = crate::usb_info::devnode_u32_property(dev_inst, CM_DRP_REMOVAL_POLICY).and_then(Policy::from_raw)?;
+    let pending = crate::usb_info::devnode_u32_property(dev_inst, CM_DRP_REMOVAL_POLICY_OVERRIDE)
+        .and_then(Policy::from_raw)
+        .filter(|p| *p != current);
+    Some(RemovalPolicy { current, pending })
+}
+
+/// 写入删除策略覆盖值，重新插拔后生效
+pub fn set(drive: &str, policy: Policy) -> Result<String, String> {
+    let d = crate::mount_manager::display_name(drive);
+    let result = (|| {
+        let dev_inst = crate::usb_info::disk_devinst(drive).ok_or(tr("无法定位所在磁盘设备"))?;
+        let value = policy.raw();
+        let rc = unsafe {
+            CM_Set_DevNode_Registry_PropertyW(
+                dev_inst,
+                CM_DRP_REMOVAL_POLICY_OVERRIDE,
+                &value as *const u32 as _,
+                std::mem::size_of::<u32>() as u32,
+                0,
+            )
+        };
+        if rc != CR_SUCCESS {
+            return Err(trf("{} 设置删除策略失败 (CONFIGRET {})，需要管理员权限", &[&d, &rc]));
+        }
+        Ok(trf("{} 删除策略改为“{}”，重新插拔后生效", &[&d, &policy.label()]))
+    })();
+    crate::audit::record(
+        "删除策略",
+        &format!("{} {} -> {}", d, policy.label(), result.as_ref().map(|s| s.as_str()).unwrap_or_else(|e| e.as_str())),
+    );
+    result
+}
@@ -0,0 +1,41 @@
+// ═══════════════════════════════════════════════════════════════
+//  重绘节流 - 后台线程连续推送状态时合并成一次重绘
+// ═══════════════════════════════════════════════════════════════
+//
+// 弹出流程会在几十毫秒内连发 Scanning -> Occupied -> Ejecting -> Done 等状态，
+// 每次都立即重绘会闪烁并浪费帧。前沿触发：距上次重绘超过 WINDOW 时立即重绘，
+// 否则把重绘推迟到窗口结束，UI 届时一次性取走通道里的全部消息，只画最终状态。
+// 状态本身没有变化的推送由调用方丢弃 (见 usb_worker 的 send)。
+
+use eframe::egui;
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// 合并窗口：约两帧
+const WINDOW: Duration = Duration::from_millis(40);
+
+pub struct Throttle {
+    ctx: egui::Context,
+    last: Cell<Option<Instant>>,
+}
+
+impl Throttle {
+    pub fn new(ctx: egui::Context) -> Self {
+        Self {
+            ctx,
+            last: Cell::new(None),
+        }
+    }
+
+    /// 请求重绘；窗口内的后续请求合并到窗口结束时
+    pub fn request(&self) {
+        let now = Instant::now();
+        match self.last.get().map(|t| now.duration_since(t)) {
+            Some(since) if since < WINDOW => self.ctx.request_repaint_after(WINDOW - since),
+            _ => {
+                self.last.set(Some(now));
+                self.ctx.request_repaint();
+            }
+        }
+    }
+}
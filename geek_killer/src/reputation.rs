@@ -0,0 +1,281 @@
+// ═══════════════════════════════════════════════════════════════
+//  信誉查询 - exe 的 SHA-256 与 VirusTotal 检出率
+// ═══════════════════════════════════════════════════════════════
+//
+// SHA-256 用系统 CNG (BCrypt) 计算，后台线程进行并按路径缓存。
+// “查询”：
+//   - 配置了 VirusTotal API Key：WinHTTP 请求 /api/v3/files/{hash}，
+//     读取 last_analysis_stats 显示“检出 x / 引擎总数”；404 表示 VT 上没有该文件
+//   - 未配置：在浏览器中打开该哈希的 VirusTotal 页面
+// 只上传哈希，不上传文件本身。查询结果按哈希缓存，写入审计日志。
+
+use crate::i18n::{tr, trf};
+use crate::long_path::wide;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::sync::{mpsc, Arc, Mutex};
+use windows_sys::Win32::Networking::WinHttp::{
+    WinHttpCloseHandle, WinHttpConnect, WinHttpOpen, WinHttpOpenRequest, WinHttpQueryHeaders, WinHttpReadData,
+    WinHttpReceiveResponse, WinHttpSendRequest, WinHttpSetTimeouts, INTERNET_DEFAULT_HTTPS_PORT,
+    WINHTTP_ACCESS_TYPE_DEFAULT_PROXY, WINHTTP_FLAG_SECURE, WINHTTP_QUERY_FLAG_NUMBER, WINHTTP_QUERY_STATUS_CODE,
+};
+use windows_sys::Win32::Security::Cryptography::{
+    BCryptCloseAlgorithmProvider, BCryptCreateHash, BCryptDestroyHash, BCryptFinishHash, BCryptHashData,
+    BCryptOpenAlgorithmProvider, BCRYPT_SHA256_ALGORITHM,
+};
+
+const VT_HOST: &str = "www.virustotal.com";
+/// 连接 / 收发超时 (毫秒)
+const HTTP_TIMEOUT_MS: i32 = 15_000;
+
+/// 计算文件的 SHA-256 (小写十六进制)
+pub fn sha256_file(path: &str) -> Result<String, String> {
+    let mut file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut digest = [0u8; 32];
+    unsafe {
+        let mut alg = std::mem::zeroed();
+        if BCryptOpenAlgorithmProvider(&mut alg, BCRYPT_SHA256_ALGORITHM, std::ptr::null(), 0) < 0 {
+            return Err(tr("无法初始化 SHA-256").to_string());
+        }
+        let mut hash = std::mem::zeroed();
+        let result = (|| {
+            if BCryptCreateHash(alg, &mut hash, std::ptr::null_mut(), 0, std::ptr::null(), 0, 0) < 0 {
+                return Err(tr("无法初始化 SHA-256").to_string());
+            }
+            let mut buf = vec![0u8; 1 << 20];
+            loop {
+                let n = file.read(&mut buf).map_err(|e| e.to_string())?;
+                if n == 0 {
+                    break;
+                }
+                if BCryptHashData(hash, buf.as_ptr(), n as u32, 0) < 0 {
+                    return Err(tr("计算 SHA-256 失败").to_string());
+                }
+            }
+            if BCryptFinishHash(hash, digest.as_mut_ptr(), digest.len() as u32, 0) < 0 {
+                return Err(tr("计算 SHA-256 失败").to_string());
+            }
+            Ok(())
+        })();
+        BCryptDestroyHash(hash);
+        BCryptCloseAlgorithmProvider(alg, 0);
+        result?;
+    }
+    Ok(digest.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// 按路径缓存的 SHA-256；未知路径交给后台线程计算
+pub struct HashCache {
+    results: Arc<Mutex<HashMap<String, Result<String, String>>>>,
+    requested: HashSet<String>,
+    tx: mpsc::Sender<String>,
+}
+
+impl HashCache {
+    pub fn new() -> Self {
+        let results: Arc<Mutex<HashMap<String, Result<String, String>>>> = Arc::new(Mutex::new(HashMap::new()));
+        let (tx, rx) = mpsc::channel::<String>();
+        let shared = results.clone();
+        std::thread::spawn(move || {
+            for path in rx {
+                let hash = sha256_file(&path);
+                if let Ok(mut map) = shared.lock() {
+                    map.insert(path, hash);
+                }
+            }
+        });
+        Self {
+            results,
+            requested: HashSet::new(),
+            tx,
+        }
+    }
+
+    /// 已算出的结果；尚未计算时排队并返回 None
+    pub fn get(&mut self, path: &str) -> Option<Result<String, String>> {
+        if let Some(r) = self.results.lock().ok().and_then(|m| m.get(path).cloned()) {
+            return Some(r);
+        }
+        if self.requested.insert(path.to_string()) {
+            let _ = self.tx.send(path.to_string());
+        }
+        None
+    }
+}
+
+impl Default for HashCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// 检出统计
+    Scanned { malicious: u64, suspicious: u64, total: u64 },
+    /// VirusTotal 上没有该文件
+    Unknown,
+}
+
+impl Verdict {
+    pub fn describe(&self) -> String {
+        match self {
+            Verdict::Scanned { malicious, suspicious, total } if *suspicious > 0 => {
+                trf("检出 {} / {} (另有 {} 个可疑)", &[&malicious, &total, &suspicious])
+            }
+            Verdict::Scanned { malicious, total, .. } => trf("检出 {} / {}", &[&malicious, &total]),
+            Verdict::Unknown => tr("VirusTotal 上没有该文件的记录").to_string(),
+        }
+    }
+
+    pub fn is_flagged(&self) -> bool {
+        matches!(self, Verdict::Scanned { malicious, .. } if *malicious > 0)
+    }
+}
+
+/// 哈希在 VirusTotal 网页上的地址
+pub fn web_url(sha256: &str) -> String {
+    format!("https://{}/gui/file/{}", VT_HOST, sha256)
+}
+
+/// GET https://www.virustotal.com{path}，返回 (状态码, 正文)
+fn https_get(path: &str, headers: &str) -> Result<(u32, Vec<u8>), String> {
+    struct Handle(*mut std::ffi::c_void);
+    impl Drop for Handle {
+        fn drop(&mut self) {
+            if !self.0.is_null() {
+                unsafe {
+                    WinHttpCloseHandle(self.0);
+                }
+            }
+        }
+    }
+    let last_error = || std::io::Error::last_os_error().to_string();
+
+    unsafe {
+        let session = Handle(WinHttpOpen(
+            wide("GeekKiller").as_ptr(),
+            WINHTTP_ACCESS_TYPE_DEFAULT_PROXY,
+            std::ptr::null(),
+            std::ptr::null(),
+            0,
+        ));
+        if session.0.is_null() {
+            return Err(last_error());
+        }
+        WinHttpSetTimeouts(session.0, HTTP_TIMEOUT_MS, HTTP_TIMEOUT_MS, HTTP_TIMEOUT_MS, HTTP_TIMEOUT_MS);
+        let connect = Handle(WinHttpConnect(session.0, wide(VT_HOST).as_ptr(), INTERNET_DEFAULT_HTTPS_PORT, 0));
+        if connect.0.is_null() {
+            return Err(last_error());
+        }
+        let request = Handle(WinHttpOpenRequest(
+            connect.0,
+            wide("GET").as_ptr(),
+            wide(path).as_ptr(),
+            std::ptr::null(),
+            std::ptr::null(),
+            std::ptr::null(),
+            WINHTTP_FLAG_SECURE,
+        ));
+        if request.0.is_null() {
+            return Err(last_error());
+        }
+        let headers = wide(headers);
+        if WinHttpSendRequest(request.0, headers.as_ptr(), u32::MAX, std::ptr::null(), 0, 0, 0) == 0
+            || WinHttpReceiveResponse(request.0, std::ptr::null_mut()) == 0
+        {
+            return Err(last_error());
+        }
+        let mut status = 0u32;
+        let mut len = std::mem::size_of::<u32>() as u32;
+        WinHttpQueryHeaders(
+            request.0,
+            WINHTTP_QUERY_STATUS_CODE | WINHTTP_QUERY_FLAG_NUMBER,
+            std::ptr::null(),
+            &mut status as *mut u32 as _,
+            &mut len,
+            std::ptr::null_mut(),
+        );
+        let mut body = Vec::new();
+        let mut chunk = [0u8; 8192];
+        loop {
+            let mut read = 0u32;
+            if WinHttpReadData(request.0, chunk.as_mut_ptr() as _, chunk.len() as u32, &mut read) == 0 {
+                return Err(last_error());
+            }
+            if read == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..read as usize]);
+        }
+        Ok((status, body))
+    }
+}
+
+/// 用 VirusTotal API 查询哈希
+pub fn lookup(api_key: &str, sha256: &str) -> Result<Verdict, String> {
+    let (status, body) = https_get(&format!("/api/v3/files/{}", sha256), &format!("x-apikey: {}", api_key.trim()))
+        .map_err(|e| trf("无法连接 VirusTotal：{}", &[&e]))?;
+    match status {
+        200 => {}
+        404 => return Ok(Verdict::Unknown),
+        401 => return Err(tr("API Key 无效").to_string()),
+        429 => return Err(tr("查询过于频繁 (免费 Key 每分钟 4 次)").to_string()),
+        code => return Err(trf("VirusTotal 返回 HTTP {}", &[&code])),
+    }
+    let json: serde_json::Value = serde_json::from_slice(&body).map_err(|e| trf("无法解析结果：{}", &[&e]))?;
+    let stats = &json["data"]["attributes"]["last_analysis_stats"];
+    let count = |key: &str| stats[key].as_u64().unwrap_or(0);
+    let total = ["malicious", "suspicious", "undetected", "harmless"].iter().map(|k| count(k)).sum();
+    Ok(Verdict::Scanned {
+        malicious: count("malicious"),
+        suspicious: count("suspicious"),
+        total,
+    })
+}
+
+#[derive(Clone)]
+pub enum LookupState {
+    Pending,
+    Done(Result<Verdict, String>),
+}
+
+/// 按哈希缓存的查询结果
+#[derive(Default)]
+pub struct Lookups {
+    results: Arc<Mutex<HashMap<String, LookupState>>>,
+}
+
+impl Lookups {
+    /// 后台查询，完成后写入审计日志并请求重绘
+    pub fn start(&self, api_key: String, sha256: String, exe_path: String, ctx: eframe::egui::Context) {
+        if let Ok(mut map) = self.results.lock() {
+            map.insert(sha256.clone(), LookupState::Pending);
+        }
+        let results = self.results.clone();
+        std::thread::spawn(move || {
+            let result = lookup(&api_key, &sha256);
+            crate::audit::record(
+                "信誉查询",
+                &format!(
+                    "{} ({}) -> {}",
+                    exe_path,
+                    sha256,
+                    match &result {
+                        Ok(v) => v.describe(),
+                        Err(e) => e.clone(),
+                    }
+                ),
+            );
+            if let Ok(mut map) = results.lock() {
+                map.insert(sha256, LookupState::Done(result));
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    /// None 表示尚未查询
+    pub fn get(&self, sha256: &str) -> Option<LookupState> {
+        self.results.lock().ok()?.get(sha256).cloned()
+    }
+}
@@ -0,0 +1,117 @@
+// ═══════════════════════════════════════════════════════════════
+//  使用时长 - 按前台窗口统计每个应用每天用了多久
+// ═══════════════════════════════════════════════════════════════
+//
+// 监控线程每轮调用 Tracker::sample()：取 GetForegroundWindow 所属进程，
+// 把距上一轮的时间记到它名下，累加结果交给进程历史数据库 (按本地日期分行)。
+// 键鼠空闲超过 IDLE_AFTER (离开电脑、看视频时没有操作) 或锁屏时不计时；
+// 两轮间隔超过 MAX_GAP (睡眠 / 休眠唤醒) 时只计 MAX_GAP，避免把睡眠算进去。
+// 默认关闭，在诊断面板的“使用时长”里开启。
+
+use crate::i18n::trf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use windows_sys::Win32::System::StationsAndDesktops::{
+    CloseDesktop, OpenInputDesktop, SwitchDesktop, DESKTOP_SWITCHDESKTOP,
+};
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+use windows_sys::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+/// 键鼠空闲超过此时长不再计时
+const IDLE_AFTER: Duration = Duration::from_secs(5 * 60);
+/// 单轮最多计入的时长
+const MAX_GAP: Duration = Duration::from_secs(10);
+
+pub struct Tracker {
+    last: Instant,
+}
+
+impl Default for Tracker {
+    fn default() -> Self {
+        Self { last: Instant::now() }
+    }
+}
+
+impl Tracker {
+    /// 本轮前台进程的 PID 与应计入的时长；空闲、锁屏或没有前台窗口时为 None
+    pub fn sample(&mut self) -> Option<(u32, Duration)> {
+        let elapsed = self.last.elapsed().min(MAX_GAP);
+        self.last = Instant::now();
+        if idle_for() >= IDLE_AFTER || is_locked() {
+            return None;
+        }
+        unsafe {
+            let hwnd = GetForegroundWindow();
+            if hwnd == 0 {
+                return None;
+            }
+            let mut pid = 0u32;
+            GetWindowThreadProcessId(hwnd, &mut pid);
+            (pid != 0).then_some((pid, elapsed))
+        }
+    }
+}
+
+/// 锁屏时输入桌面切换为 Winlogon 的安全桌面，普通进程打不开或无法切换到它
+fn is_locked() -> bool {
+    unsafe {
+        let desk = OpenInputDesktop(0, 0, DESKTOP_SWITCHDESKTOP);
+        if desk == 0 {
+            return true;
+        }
+        let locked = SwitchDesktop(desk) == 0;
+        CloseDesktop(desk);
+        locked
+    }
+}
+
+/// 距最后一次键盘 / 鼠标输入的时长
+fn idle_for() -> Duration {
+    unsafe {
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+        if GetLastInputInfo(&mut info) == 0 {
+            return Duration::ZERO;
+        }
+        // 两者都是 32 位毫秒计数，约 49.7 天回绕一次，用回绕减法
+        let now = windows_sys::Win32::System::SystemInformation::GetTickCount();
+        Duration::from_millis(now.wrapping_sub(info.dwTime) as u64)
+    }
+}
+
+/// secs (Unix 秒) 所在本地日期的零点，作为按天统计的键
+pub fn day_start(secs: u64) -> u64 {
+    use windows_sys::Win32::Foundation::FILETIME;
+    use windows_sys::Win32::Storage::FileSystem::FileTimeToLocalFileTime;
+
+    let ticks = (secs + 11_644_473_600) * 10_000_000;
+    let utc = FILETIME {
+        dwLowDateTime: ticks as u32,
+        dwHighDateTime: (ticks >> 32) as u32,
+    };
+    let offset = unsafe {
+        let mut local: FILETIME = std::mem::zeroed();
+        if FileTimeToLocalFileTime(&utc, &mut local) == 0 {
+            0
+        } else {
+            let local_ticks = ((local.dwHighDateTime as i64) << 32) | local.dwLowDateTime as i64;
+            (local_ticks - ticks as i64) / 10_000_000
+        }
+    };
+    let local = secs as i64 + offset;
+    (local - local.rem_euclid(86_400) - offset).max(0) as u64
+}
+
+pub fn today() -> u64 {
+    day_start(SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0))
+}
+
+/// 使用时长的简短写法：“2 小时 05 分”、“12 分”、“40 秒”
+pub fn format_duration(secs: u64) -> String {
+    match (secs / 3600, secs / 60 % 60) {
+        (0, 0) => trf("{} 秒", &[&secs]),
+        (0, m) => trf("{} 分", &[&m]),
+        (h, m) => trf("{} 小时 {} 分", &[&h, &format!("{:02}", m)]),
+    }
+}
@@ -0,0 +1,220 @@
+// ═══════════════════════════════════════════════════════════════
+//  安全擦除 - 多遍覆写整块 U 盘，转手或报废前清除可恢复的数据
+// ═══════════════════════════════════════════════════════════════
+//
+// 独占流程与镜像写入相同 (image_writer::ExclusiveDisk)：锁定并卸载同盘所有卷，
+// 再从头到尾顺序覆写 \\.\PhysicalDriveN。每一遍的图案依次为 0x00 / 0xFF / 随机
+// 循环，最后一遍固定为 0x00，擦完的盘是一块“全零”的未初始化磁盘，需要在磁盘管理
+// 里重新分区或直接用格式化功能。可选校验读回最后一遍。
+//
+// 注意：U 盘 / SSD 有磨损均衡与预留块，覆写逻辑地址无法保证触及所有闪存单元；
+// 对机械硬盘与一般用途的 U 盘足够，涉密介质应物理销毁。
+// 可随时取消 (每块检查一次)，取消后磁盘处于部分擦除状态。需要管理员权限。
+
+use crate::i18n::{tr, trf};
+use crate::image_writer::{AlignedBuf, ExclusiveDisk, CHUNK, REPORT_EVERY};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// 最多覆写遍数
+pub const MAX_PASSES: u32 = 7;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WipeJob {
+    /// 覆写遍数 (1..=MAX_PASSES)
+    pub passes: u32,
+    /// 读回校验最后一遍
+    pub verify: bool,
+}
+
+impl Default for WipeJob {
+    fn default() -> Self {
+        Self { passes: 3, verify: true }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    Zero,
+    One,
+    Random,
+}
+
+impl Pattern {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Pattern::Zero => "0x00",
+            Pattern::One => "0xFF",
+            Pattern::Random => tr("随机"),
+        }
+    }
+
+    /// 第 pass 遍 (从 0 开始) 使用的图案；最后一遍固定写零
+    pub fn for_pass(pass: u32, passes: u32) -> Pattern {
+        if pass + 1 == passes {
+            return Pattern::Zero;
+        }
+        [Pattern::Zero, Pattern::One, Pattern::Random][(pass % 3) as usize]
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Progress {
+    /// 当前遍 (从 1 开始)；校验阶段为 None
+    pub pass: Option<u32>,
+    pub passes: u32,
+    pub pattern: Pattern,
+    pub done: u64,
+    pub total: u64,
+    pub bytes_per_sec: f64,
+}
+
+impl Progress {
+    pub fn text(&self) -> String {
+        let stage = match self.pass {
+            Some(pass) => trf("正在擦除 第 {}/{} 遍 ({})", &[&pass, &self.passes, &self.pattern.label()]),
+            None => tr("正在校验").to_string(),
+        };
+        format!(
+            "{} {:.0}% · {:.1} MB/s",
+            stage,
+            self.done as f64 / self.total.max(1) as f64 * 100.0,
+            self.bytes_per_sec / 1024.0 / 1024.0
+        )
+    }
+}
+
+/// xorshift64：随机图案只需不可预测、足够快，不需要密码学强度
+struct Noise(u64);
+
+impl Noise {
+    fn seeded() -> Self {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0);
+        Self((nanos ^ ((std::process::id() as u64) << 32)) | 1)
+    }
+
+    fn fill(&mut self, buf: &mut [u8]) {
+        for chunk in buf.chunks_mut(8) {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            chunk.copy_from_slice(&self.0.to_le_bytes()[..chunk.len()]);
+        }
+    }
+}
+
+pub fn wipe(
+    drive: &str,
+    job: &WipeJob,
+    cancel: &Arc<AtomicBool>,
+    mut on_progress: impl FnMut(Progress),
+) -> Result<String, String> {
+    let d = crate::norm_drive(drive);
+    let result = wipe_inner(&d, job, cancel, &mut on_progress);
+    crate::audit::record(
+        "安全擦除",
+        &format!(
+            "{} ({} 遍): {}",
+            d,
+            job.passes,
+            result.as_ref().map(|s| s.as_str()).unwrap_or_else(|e| e.as_str())
+        ),
+    );
+    result
+}
+
+fn wipe_inner(
+    d: &str,
+    job: &WipeJob,
+    cancel: &Arc<AtomicBool>,
+    on_progress: &mut impl FnMut(Progress),
+) -> Result<String, String> {
+    let passes = job.passes.clamp(1, MAX_PASSES);
+    let exclusive = ExclusiveDisk::open(d)?;
+    let (number, total) = (exclusive.number, exclusive.size);
+    // 容量不是整块时，末尾不足一块的部分按扇区写
+    let total = total / exclusive.sector as u64 * exclusive.sector as u64;
+    let mut disk = &exclusive.file;
+    let mut buf = AlignedBuf::new();
+    let cancelled = || -> Result<(), String> {
+        if cancel.load(Ordering::Relaxed) {
+            Err(trf("{}: 已取消，磁盘处于部分擦除状态，需要重新分区或再次擦除", &[&d]))
+        } else {
+            Ok(())
+        }
+    };
+
+    for pass in 0..passes {
+        let pattern = Pattern::for_pass(pass, passes);
+        let mut noise = Noise::seeded();
+        match pattern {
+            Pattern::Zero => buf.get(CHUNK).fill(0x00),
+            Pattern::One => buf.get(CHUNK).fill(0xFF),
+            Pattern::Random => {}
+        }
+        disk.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+        let started = Instant::now();
+        let mut reported = Instant::now();
+        let mut done = 0u64;
+        while done < total {
+            cancelled()?;
+            let n = (total - done).min(CHUNK as u64) as usize;
+            let block = buf.get(n);
+            if pattern == Pattern::Random {
+                noise.fill(block);
+            }
+            disk.write_all(block)
+                .map_err(|e| trf("第 {} 遍写入偏移 {} MB 处失败：{}", &[&(pass + 1), &(done / 1024 / 1024), &e]))?;
+            done += n as u64;
+            if reported.elapsed() >= REPORT_EVERY || done == total {
+                reported = Instant::now();
+                on_progress(Progress {
+                    pass: Some(pass + 1),
+                    passes,
+                    pattern,
+                    done,
+                    total,
+                    bytes_per_sec: done as f64 / started.elapsed().as_secs_f64().max(0.001),
+                });
+            }
+        }
+        disk.sync_all().map_err(|e| trf("刷新写入缓存失败：{}", &[&e]))?;
+    }
+
+    if job.verify {
+        // 最后一遍固定为 0x00
+        disk.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+        let started = Instant::now();
+        let mut reported = Instant::now();
+        let mut done = 0u64;
+        while done < total {
+            cancelled()?;
+            let n = (total - done).min(CHUNK as u64) as usize;
+            let block = buf.get(n);
+            disk.read_exact(block)
+                .map_err(|e| trf("读回偏移 {} MB 处失败：{}", &[&(done / 1024 / 1024), &e]))?;
+            if block.iter().any(|&b| b != 0) {
+                return Err(trf("校验失败：偏移 {} MB 处仍有残留数据，磁盘可能已损坏", &[&(done / 1024 / 1024)]));
+            }
+            done += n as u64;
+            if reported.elapsed() >= REPORT_EVERY || done == total {
+                reported = Instant::now();
+                on_progress(Progress {
+                    pass: None,
+                    passes,
+                    pattern: Pattern::Zero,
+                    done,
+                    total,
+                    bytes_per_sec: done as f64 / started.elapsed().as_secs_f64().max(0.001),
+                });
+            }
+        }
+    }
+
+    Ok(trf(
+        "{} (PhysicalDrive{}): 已覆写 {} 遍 {} GB{}；请重新分区或格式化后使用",
+        &[&d, &number, &passes, &format!("{:.1}", total as f64 / 1e9), &if job.verify { tr("，校验通过") } else { "" }],
+    ))
+}
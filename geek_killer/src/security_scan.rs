@@ -0,0 +1,294 @@
+// ═══════════════════════════════════════════════════════════════
+//  安全检查 - 进程镂空 (Hollowing) / 代码注入启发式
+// ═══════════════════════════════════════════════════════════════
+//
+// 检查项：
+//   1. PEB.ImageBaseAddress 处的内存不是文件映射 (MEM_IMAGE) -> 原映像被卸载替换
+//   2. 映像基址映射的文件 != 进程的可执行文件              -> 映像被替换
+//   3. PEB 中的 ImagePathName != 实际可执行文件路径         -> 路径伪装
+//   4. 私有内存中存在可读写可执行 (RWX) 区域                -> shellcode / 注入
+// 浏览器、Java、.NET 等 JIT 宿主天然会有 RWX 区域，只做低级别提示。
+
+use crate::i18n::{tr, trf};
+use windows_sys::Win32::Foundation::{CloseHandle, GetLastError, ERROR_INSUFFICIENT_BUFFER, HANDLE};
+use windows_sys::Win32::System::Diagnostics::Debug::ReadProcessMemory;
+use windows_sys::Win32::System::Memory::{
+    VirtualQueryEx, MEMORY_BASIC_INFORMATION, MEM_COMMIT, MEM_IMAGE, MEM_PRIVATE,
+    PAGE_EXECUTE_READWRITE,
+};
+use windows_sys::Win32::System::ProcessStatus::GetMappedFileNameW;
+use windows_sys::Win32::System::Threading::{
+    OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_NATIVE, PROCESS_NAME_WIN32,
+    PROCESS_QUERY_INFORMATION, PROCESS_VM_READ,
+};
+
+#[link(name = "ntdll")]
+extern "system" {
+    fn NtQueryInformationProcess(
+        process: HANDLE,
+        class: u32,
+        info: *mut std::ffi::c_void,
+        len: u32,
+        ret_len: *mut u32,
+    ) -> i32;
+}
+
+const PROCESS_BASIC_INFORMATION_CLASS: u32 = 0;
+
+#[repr(C)]
+struct ProcessBasicInformation {
+    exit_status: i32,
+    peb_base: usize,
+    affinity_mask: usize,
+    base_priority: i32,
+    unique_pid: usize,
+    parent_pid: usize,
+}
+
+// PEB / RTL_USER_PROCESS_PARAMETERS 中用到的字段偏移 (与本进程位数一致)
+const PTR: usize = std::mem::size_of::<usize>();
+const PEB_IMAGE_BASE: usize = 2 * PTR;
+const PEB_PROCESS_PARAMETERS: usize = 4 * PTR;
+#[cfg(target_pointer_width = "64")]
+const PARAMS_IMAGE_PATH_NAME: usize = 0x60;
+#[cfg(target_pointer_width = "32")]
+const PARAMS_IMAGE_PATH_NAME: usize = 0x38;
+
+/// 常见 JIT 宿主：RWX 内存属于正常现象
+const JIT_HOSTS: &[&str] = &[
+    "chrome.exe", "msedge.exe", "firefox.exe", "brave.exe", "opera.exe",
+    "java.exe", "javaw.exe", "node.exe", "code.exe", "electron.exe",
+    "powershell.exe", "pwsh.exe", "devenv.exe", "dotnet.exe",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FindingKind {
+    /// 映像基址处不是文件映射内存
+    ImageNotMapped,
+    /// 映像基址映射的文件与可执行文件不一致
+    MappedImageMismatch { mapped: String },
+    /// PEB 中的路径与实际路径不一致
+    PebPathMismatch { peb_path: String },
+    /// 私有 RWX 区域
+    RwxPrivate { regions: usize, bytes: usize, jit_host: bool },
+}
+
+#[derive(Clone, Debug)]
+pub struct Finding {
+    pub pid: u32,
+    pub name: String,
+    pub image_path: String,
+    pub kind: FindingKind,
+}
+
+impl Finding {
+    /// 高危：除 JIT 宿主中的 RWX 区域外，其余发现都不应出现在正常程序中
+    pub fn is_high(&self) -> bool {
+        !matches!(self.kind, FindingKind::RwxPrivate { jit_host: true, .. })
+    }
+
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            FindingKind::ImageNotMapped => tr("映像基址处为私有内存，原映像可能已被卸载替换 (疑似进程镂空)").to_string(),
+            FindingKind::MappedImageMismatch { mapped } => {
+                trf("映像基址映射的文件与进程文件不一致：{}", &[&mapped])
+            }
+            FindingKind::PebPathMismatch { peb_path } => {
+                trf("PEB 记录的路径与实际文件不一致：{}", &[&peb_path])
+            }
+            FindingKind::RwxPrivate { regions, bytes, jit_host } => trf(
+                "{} 个可读写可执行的私有内存区域 (共 {} KB){}",
+                &[&regions, &(bytes / 1024), &tr(if *jit_host { "，JIT 宿主中较常见" } else { "，可能存在注入代码" })],
+            ),
+        }
+    }
+}
+
+/// 扫描结果
+#[derive(Clone, Debug, Default)]
+pub struct ScanReport {
+    pub scanned: usize,
+    /// 无权限打开 (受保护进程 / 非管理员)，或 32 位构建下的 64 位进程
+    pub skipped: usize,
+    pub findings: Vec<Finding>,
+}
+
+struct ProcHandle(HANDLE);
+impl Drop for ProcHandle {
+    fn drop(&mut self) {
+        unsafe {
+            CloseHandle(self.0);
+        }
+    }
+}
+
+/// 统一大小写并去掉 \??\ 与 \\?\ 前缀，便于比较
+fn normalize_path(p: &str) -> String {
+    p.trim_start_matches("\\??\\")
+        .trim_start_matches("\\\\?\\")
+        .to_lowercase()
+}
+
+unsafe fn query_image_name(h: HANDLE, format: u32) -> Option<String> {
+    // 长路径下 1024 个字符会被截断，进而误报路径不一致
+    let path = crate::long_path::read_growing(|buf| {
+        let mut len = buf.len() as u32;
+        if QueryFullProcessImageNameW(h, format, buf.as_mut_ptr(), &mut len) != 0 {
+            len
+        } else if GetLastError() == ERROR_INSUFFICIENT_BUFFER {
+            buf.len() as u32
+        } else {
+            0
+        }
+    })?;
+    Some(String::from_utf16_lossy(&path))
+}
+
+unsafe fn read_usize(h: HANDLE, addr: usize) -> Option<usize> {
+    let mut v = 0usize;
+    let mut read = 0usize;
+    let ok = ReadProcessMemory(h, addr as _, &mut v as *mut usize as _, PTR, &mut read);
+    (ok != 0 && read == PTR).then_some(v)
+}
+
+/// 读取 PEB：返回 (映像基址, ImagePathName)
+unsafe fn read_peb(h: HANDLE) -> Option<(usize, String)> {
+    let mut pbi: ProcessBasicInformation = std::mem::zeroed();
+    let mut ret = 0u32;
+    if NtQueryInformationProcess(
+        h,
+        PROCESS_BASIC_INFORMATION_CLASS,
+        &mut pbi as *mut _ as _,
+        std::mem::size_of::<ProcessBasicInformation>() as u32,
+        &mut ret,
+    ) < 0
+        || pbi.peb_base == 0
+    {
+        return None;
+    }
+    let image_base = read_usize(h, pbi.peb_base + PEB_IMAGE_BASE)?;
+    let params = read_usize(h, pbi.peb_base + PEB_PROCESS_PARAMETERS)?;
+
+    // UNICODE_STRING { Length: u16, MaximumLength: u16, Buffer: ptr (对齐到指针宽度) }
+    let ustr = params + PARAMS_IMAGE_PATH_NAME;
+    let mut len = 0u16;
+    let mut read = 0usize;
+    if ReadProcessMemory(h, ustr as _, &mut len as *mut u16 as _, 2, &mut read) == 0 {
+        return None;
+    }
+    let buffer = read_usize(h, ustr + PTR)?;
+    let mut path = vec![0u16; len as usize / 2];
+    if !path.is_empty()
+        && ReadProcessMemory(h, buffer as _, path.as_mut_ptr() as _, len as usize, &mut read) == 0
+    {
+        return None;
+    }
+    Some((image_base, String::from_utf16_lossy(&path)))
+}
+
+/// 遍历地址空间，统计私有 RWX 区域
+unsafe fn rwx_private_regions(h: HANDLE) -> (usize, usize) {
+    let mut addr = 0usize;
+    let (mut regions, mut bytes) = (0usize, 0usize);
+    let mut mbi: MEMORY_BASIC_INFORMATION = std::mem::zeroed();
+    while VirtualQueryEx(h, addr as _, &mut mbi, std::mem::size_of::<MEMORY_BASIC_INFORMATION>()) != 0 {
+        if mbi.State == MEM_COMMIT
+            && mbi.Type == MEM_PRIVATE
+            && (mbi.Protect & 0xFF) == PAGE_EXECUTE_READWRITE
+        {
+            regions += 1;
+            bytes += mbi.RegionSize;
+        }
+        let next = (mbi.BaseAddress as usize).saturating_add(mbi.RegionSize);
+        if next <= addr {
+            break;
+        }
+        addr = next;
+    }
+    (regions, bytes)
+}
+
+/// 检查单个进程；无法打开时返回 None
+fn inspect(pid: u32, name: &str) -> Option<Vec<Finding>> {
+    // 32 位构建看不到 64 位进程的 PEB 与高地址内存，结果不可信，按跳过处理
+    if !crate::arch::can_read_peb(pid) {
+        return None;
+    }
+    unsafe {
+        let raw = OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, 0, pid);
+        if raw == 0 {
+            return None;
+        }
+        let h = ProcHandle(raw);
+        let win32_path = query_image_name(h.0, PROCESS_NAME_WIN32)?;
+        let native_path = query_image_name(h.0, PROCESS_NAME_NATIVE).unwrap_or_default();
+
+        let mut findings = Vec::new();
+        let mut push = |kind| {
+            findings.push(Finding {
+                pid,
+                name: name.to_string(),
+                image_path: win32_path.clone(),
+                kind,
+            })
+        };
+
+        if let Some((image_base, peb_path)) = read_peb(h.0) {
+            let mut mbi: MEMORY_BASIC_INFORMATION = std::mem::zeroed();
+            let queried = VirtualQueryEx(
+                h.0,
+                image_base as _,
+                &mut mbi,
+                std::mem::size_of::<MEMORY_BASIC_INFORMATION>(),
+            ) != 0;
+            if queried && mbi.Type != MEM_IMAGE {
+                push(FindingKind::ImageNotMapped);
+            } else if queried && !native_path.is_empty() {
+                // 缓冲区不足时返回值等于缓冲区长度 (已截断)，read_growing 会扩容重试
+                let mapped = crate::long_path::read_growing(|buf| {
+                    GetMappedFileNameW(h.0, image_base as _, buf.as_mut_ptr(), buf.len() as u32)
+                });
+                if let Some(mapped) = mapped {
+                    let mapped = String::from_utf16_lossy(&mapped);
+                    if normalize_path(&mapped) != normalize_path(&native_path) {
+                        push(FindingKind::MappedImageMismatch { mapped });
+                    }
+                }
+            }
+            if !peb_path.is_empty() && normalize_path(&peb_path) != normalize_path(&win32_path) {
+                push(FindingKind::PebPathMismatch { peb_path });
+            }
+        }
+
+        let (regions, bytes) = rwx_private_regions(h.0);
+        if regions > 0 {
+            push(FindingKind::RwxPrivate {
+                regions,
+                bytes,
+                jit_host: JIT_HOSTS.contains(&name.to_lowercase().as_str()),
+            });
+        }
+        Some(findings)
+    }
+}
+
+/// 扫描给定进程 (pid, 进程名)，耗时较长，应在后台线程调用
+pub fn scan(processes: &[(u32, String)]) -> ScanReport {
+    let me = std::process::id();
+    let mut report = ScanReport::default();
+    for (pid, name) in processes {
+        if *pid <= 4 || *pid == me {
+            continue;
+        }
+        match inspect(*pid, name) {
+            Some(f) => {
+                report.scanned += 1;
+                report.findings.extend(f);
+            }
+            None => report.skipped += 1,
+        }
+    }
+    // 高危在前
+    report.findings.sort_by_key(|f| !f.is_high());
+    report
+}
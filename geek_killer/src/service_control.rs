@@ -0,0 +1,187 @@
+// ═══════════════════════════════════════════════════════════════
+//  服务控制 - 一键修复类操作共用的服务启停与进度回报
+// ═══════════════════════════════════════════════════════════════
+//
+// “修复声音”“重置打印后台”都是：停服务 -> 清理 -> 启服务，每步结果逐条显示。
+// Service 封装 SCM 句柄 (Drop 时关闭)，启停后轮询状态直到到位或超时；
+// QuickFix 在后台线程执行一个修复流程，界面每帧取走新的进度。
+// 修改服务需要管理员权限。
+
+use crate::i18n::{tr, trf};
+use crate::long_path::wide;
+use eframe::egui;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use windows_sys::Win32::System::Services::{
+    CloseServiceHandle, ControlService, OpenSCManagerW, OpenServiceW, QueryServiceStatus, StartServiceW,
+    SC_MANAGER_CONNECT, SERVICE_CONTROL_STOP, SERVICE_QUERY_STATUS, SERVICE_RUNNING, SERVICE_START,
+    SERVICE_STATUS, SERVICE_STOP, SERVICE_STOPPED,
+};
+
+const STATE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// SCM 或服务句柄
+pub struct Service(isize);
+
+impl Drop for Service {
+    fn drop(&mut self) {
+        unsafe {
+            CloseServiceHandle(self.0);
+        }
+    }
+}
+
+impl Service {
+    /// 连接本机服务管理器
+    pub fn manager() -> Result<Self, String> {
+        let scm = unsafe { OpenSCManagerW(std::ptr::null(), std::ptr::null(), SC_MANAGER_CONNECT) };
+        if scm == 0 {
+            return Err(trf("无法连接服务管理器：{}", &[&std::io::Error::last_os_error()]));
+        }
+        Ok(Self(scm))
+    }
+
+    pub fn open(manager: &Service, name: &str) -> Result<Self, String> {
+        let handle = unsafe {
+            OpenServiceW(
+                manager.0,
+                wide(name).as_ptr(),
+                SERVICE_QUERY_STATUS | SERVICE_STOP | SERVICE_START,
+            )
+        };
+        if handle == 0 {
+            return Err(trf("无法打开服务 {}：{}", &[&name, &std::io::Error::last_os_error()]));
+        }
+        Ok(Self(handle))
+    }
+
+    fn state(&self) -> Option<u32> {
+        let mut status: SERVICE_STATUS = unsafe { std::mem::zeroed() };
+        (unsafe { QueryServiceStatus(self.0, &mut status) } != 0).then_some(status.dwCurrentState)
+    }
+
+    fn wait_for(&self, state: u32) -> bool {
+        let start = Instant::now();
+        while start.elapsed() < STATE_TIMEOUT {
+            if self.state() == Some(state) {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(200));
+        }
+        false
+    }
+
+    pub fn stop(&self) -> Result<(), String> {
+        if self.state() == Some(SERVICE_STOPPED) {
+            return Ok(());
+        }
+        let mut status: SERVICE_STATUS = unsafe { std::mem::zeroed() };
+        if unsafe { ControlService(self.0, SERVICE_CONTROL_STOP, &mut status) } == 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+        if self.wait_for(SERVICE_STOPPED) {
+            Ok(())
+        } else {
+            Err(tr("等待停止超时").to_string())
+        }
+    }
+
+    pub fn start(&self) -> Result<(), String> {
+        if self.state() == Some(SERVICE_RUNNING) {
+            return Ok(());
+        }
+        if unsafe { StartServiceW(self.0, 0, std::ptr::null()) } == 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+        if self.wait_for(SERVICE_RUNNING) {
+            Ok(())
+        } else {
+            Err(tr("等待启动超时").to_string())
+        }
+    }
+}
+
+/// 一步结果的说明文字：成功显示“完成”，失败显示原因
+pub fn outcome(r: &Result<(), String>) -> &str {
+    r.as_ref().map(|_| tr("完成")).unwrap_or_else(|e| e.as_str())
+}
+
+pub enum Progress {
+    /// 一步的结果 (是否成功, 说明)
+    Step(bool, String),
+    /// 全部完成，附最终状态
+    Done(Result<String, String>),
+}
+
+/// 修复流程：通过 report 逐步回报，返回值作为最终结果
+pub type FixFn = fn(&dyn Fn(Progress)) -> Result<String, String>;
+
+/// 后台执行的修复流程及其逐步结果
+pub struct QuickFix {
+    /// 审计日志分类
+    title: &'static str,
+    run: FixFn,
+    rx: Option<mpsc::Receiver<Progress>>,
+    pub log: Vec<(bool, String)>,
+}
+
+impl QuickFix {
+    pub fn new(title: &'static str, run: FixFn) -> Self {
+        Self {
+            title,
+            run,
+            rx: None,
+            log: Vec::new(),
+        }
+    }
+
+    /// 在后台线程启动流程，最终结果写入审计日志
+    pub fn start(&mut self, ctx: egui::Context) {
+        let (title, run) = (self.title, self.run);
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let report = |p: Progress| {
+                let _ = tx.send(p);
+                ctx.request_repaint();
+            };
+            let result = run(&report);
+            let text = match &result {
+                Ok(s) => s.as_str(),
+                Err(e) => e.as_str(),
+            };
+            crate::audit::record(title, text);
+            report(Progress::Done(result));
+        });
+        self.log.clear();
+        self.rx = Some(rx);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    /// 取走新的进度
+    pub fn poll(&mut self) {
+        let Some(rx) = &self.rx else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(Progress::Step(ok, text)) => self.log.push((ok, text)),
+                Ok(Progress::Done(result)) => {
+                    self.log.push(match result {
+                        Ok(s) => (true, format!("✅ {}", s)),
+                        Err(e) => (false, format!("❌ {}", e)),
+                    });
+                    self.rx = None;
+                    break;
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.rx = None;
+                    break;
+                }
+            }
+        }
+    }
+}
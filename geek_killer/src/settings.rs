@@ -0,0 +1,382 @@
+// ═══════════════════════════════════════════════════════════════
+//  程序设置 - 跨次启动保留的界面选项
+// ═══════════════════════════════════════════════════════════════
+//
+// 保存在 %APPDATA%\GeekKiller\settings.json；缺失或损坏时使用默认值，
+// 新增字段靠 #[serde(default)] 兼容旧文件。
+//
+// 启动配置 (Profile)：启动时的视图与展开的面板。默认配置可在界面中选择，
+// 命令行优先：--profile 名称 选配置，--view usb|full 再覆盖视图
+// (例如前台电脑的快捷方式加 --view usb，只显示 U 盘管理的小窗口)。
+// 退出时记下当前面板与窗口位置 (Session)，未指定 --profile 时下次启动原样恢复。
+
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 刷新间隔的可选范围 (毫秒)
+pub const MIN_REFRESH_MS: u64 = 200;
+pub const MAX_REFRESH_MS: u64 = 5000;
+
+/// 主窗口视图
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum View {
+    /// 进程列表 + 全部面板
+    #[default]
+    Full,
+    /// 只有 U 盘管理的紧凑小窗口
+    Usb,
+}
+
+impl View {
+    pub fn label(&self) -> &'static str {
+        match self {
+            View::Full => tr("完整视图"),
+            View::Usb => tr("U 盘精简视图"),
+        }
+    }
+
+    /// 命令行参数值
+    pub fn from_arg(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "full" | "process" => Some(View::Full),
+            "usb" => Some(View::Usb),
+            _ => None,
+        }
+    }
+
+    pub fn window_size(&self) -> [f32; 2] {
+        match self {
+            View::Full => [650.0, 850.0],
+            View::Usb => [480.0, 420.0],
+        }
+    }
+
+    pub fn min_window_size(&self) -> [f32; 2] {
+        match self {
+            View::Full => [600.0, 500.0],
+            View::Usb => [400.0, 300.0],
+        }
+    }
+}
+
+/// 启动时展开的面板
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Panels {
+    pub performance: bool,
+    pub diagnostics: bool,
+    pub usb_manager: bool,
+    pub security: bool,
+    pub startup: bool,
+    pub network: bool,
+}
+
+/// 配色主题
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Theme::Dark => tr("深色"),
+            Theme::Light => tr("浅色"),
+        }
+    }
+}
+
+/// 弹出失败 (快速弹出被否决) 后的处理方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EjectMode {
+    /// 只列出占用，由用户决定
+    #[default]
+    Quick,
+    /// 没有发现占用进程时自动卸载卷再弹出
+    Smart,
+    /// 直接结束占用进程并强制弹出
+    Force,
+}
+
+impl EjectMode {
+    pub const ALL: [EjectMode; 3] = [EjectMode::Quick, EjectMode::Smart, EjectMode::Force];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EjectMode::Quick => tr("快速：失败时列出占用"),
+            EjectMode::Smart => tr("智能：无占用时自动卸载弹出"),
+            EjectMode::Force => tr("强制：自动结束占用进程"),
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            EjectMode::Quick => tr("快速弹出被拒绝后扫描占用并列出，由你选择结束哪些进程"),
+            EjectMode::Smart => tr("未找到占用进程 (多为缓存或驱动未释放) 时自动执行卸载卷 + 弹出；有占用时仍列出"),
+            EjectMode::Force => tr("快速弹出被拒绝后直接对占用进程执行强力清场 (文档程序仍先请求保存；开启“清场前确认”时同样先倒计时)"),
+        }
+    }
+}
+
+/// 可选的界面语言 (代码, 名称)，同时决定进程名数据库优先使用的语言包
+pub const LANGUAGES: [(&str, &str); 2] = [("zh-CN", "简体中文"), ("en-US", "English")];
+
+/// 进程表的可选列；名称、内存、CPU 与操作列始终显示
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessColumns {
+    pub count: bool,
+    pub arch: bool,
+    pub disk_io: bool,
+    pub gpu: bool,
+    pub path: bool,
+}
+
+impl Default for ProcessColumns {
+    fn default() -> Self {
+        Self {
+            count: true,
+            arch: true,
+            disk_io: false,
+            gpu: false,
+            path: false,
+        }
+    }
+}
+
+/// 上次退出时的视图、面板与窗口位置，下次启动时恢复
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub view: View,
+    pub panels: Panels,
+    /// 窗口外框左上角 (逻辑像素)
+    pub position: Option<[f32; 2]>,
+    /// 窗口客户区大小 (逻辑像素)
+    pub size: Option<[f32; 2]>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub name: String,
+    pub view: View,
+    pub panels: Panels,
+}
+
+impl Profile {
+    fn builtin() -> Vec<Profile> {
+        vec![
+            Profile {
+                name: "完整".to_string(),
+                view: View::Full,
+                panels: Panels::default(),
+            },
+            Profile {
+                name: "U 盘".to_string(),
+                view: View::Usb,
+                panels: Panels {
+                    usb_manager: true,
+                    ..Default::default()
+                },
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// 关闭窗口时隐藏到托盘，后台继续监控
+    pub close_to_tray: bool,
+    pub profiles: Vec<Profile>,
+    /// 未指定 --profile 时使用的配置名
+    pub default_profile: String,
+    /// 全局快捷键
+    pub hotkeys: Vec<crate::hotkeys::Binding>,
+    /// 后台降低刷新频率 (慢机器)
+    pub reduced_cadence: bool,
+    /// 各核心占用以热力图而非条形显示
+    pub core_heatmap: bool,
+    /// 默认视图中隐藏的进程分组 (进程名，小写)；仍计入各列表总数
+    pub hidden_groups: Vec<String>,
+    /// 强力清场前确认，并留撤销倒计时
+    pub confirm_force_clear: bool,
+    /// VirusTotal API Key，为空时“查询”只打开网页
+    pub virustotal_api_key: String,
+    /// 操作宏
+    pub macros: Vec<crate::macros::Macro>,
+    /// 进程表显示与排序所用的内存口径
+    pub memory_metric: crate::process_memory::Metric,
+    pub process_columns: ProcessColumns,
+    pub theme: Theme,
+    /// 进入极简模式 (资源紧张) 时自动折叠性能与诊断面板
+    pub auto_low_power: bool,
+    /// 未指定 --profile 时恢复上次退出时的面板与窗口位置
+    pub restore_session: bool,
+    pub session: Option<Session>,
+    /// 正常刷新间隔 (毫秒)
+    pub refresh_ms: u64,
+    /// 资源紧张判定：全局 CPU 超过此值 (%)
+    pub tight_cpu_percent: f32,
+    /// 资源紧张判定：可用内存低于此值 (MB)
+    pub tight_free_mb: u64,
+    pub eject_mode: EjectMode,
+    /// 界面语言，即时生效；进程名数据库重启后生效
+    pub language: String,
+    /// 启动后直接隐藏到托盘
+    pub start_in_tray: bool,
+    /// 计费网络时阻止联网的程序
+    pub metered: crate::metered::Config,
+    /// 供 Stream Deck / 键盘小屏读取的本机 JSON 接口
+    pub widget: crate::widget_api::Config,
+    /// 网络唤醒与远程关机的局域网主机
+    pub lan_machines: Vec<crate::lan_power::Machine>,
+    /// 记录前台应用的使用时长
+    pub screen_time: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            close_to_tray: false,
+            profiles: Profile::builtin(),
+            default_profile: "完整".to_string(),
+            hotkeys: crate::hotkeys::default_bindings(),
+            reduced_cadence: false,
+            core_heatmap: false,
+            hidden_groups: Vec::new(),
+            confirm_force_clear: true,
+            virustotal_api_key: String::new(),
+            macros: Vec::new(),
+            memory_metric: crate::process_memory::Metric::default(),
+            process_columns: ProcessColumns::default(),
+            theme: Theme::default(),
+            auto_low_power: true,
+            restore_session: true,
+            session: None,
+            refresh_ms: crate::tick_health::NORMAL_INTERVAL.as_millis() as u64,
+            tight_cpu_percent: 90.0,
+            tight_free_mb: 500,
+            eject_mode: EjectMode::default(),
+            language: "zh-CN".to_string(),
+            start_in_tray: false,
+            metered: crate::metered::Config::default(),
+            widget: crate::widget_api::Config::default(),
+            lan_machines: Vec::new(),
+            screen_time: false,
+        }
+    }
+}
+
+impl AppSettings {
+    fn path() -> Option<PathBuf> {
+        crate::app_data_dir().map(|d| d.join("settings.json"))
+    }
+
+    pub fn load() -> Self {
+        let mut settings: Self = Self::path()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        if settings.profiles.is_empty() {
+            settings.profiles = Profile::builtin();
+        }
+        settings
+    }
+
+    /// 访客模式期间设置锁定，不写入磁盘
+    pub fn save(&self) {
+        if crate::guest_mode::is_active() {
+            return;
+        }
+        if let (Some(p), Ok(json)) = (Self::path(), serde_json::to_string_pretty(self)) {
+            let _ = std::fs::write(p, json);
+        }
+    }
+
+    /// 与监控线程共享的刷新与资源紧张参数
+    pub fn tuning(&self) -> crate::tick_health::Tuning {
+        crate::tick_health::Tuning {
+            interval: std::time::Duration::from_millis(self.refresh_ms.clamp(MIN_REFRESH_MS, MAX_REFRESH_MS)),
+            reduced: self.reduced_cadence,
+            tight_cpu: self.tight_cpu_percent,
+            tight_free_bytes: self.tight_free_mb * 1024 * 1024,
+            sample_gpu: self.process_columns.gpu,
+            track_foreground: self.screen_time,
+        }
+    }
+
+    pub fn profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    pub fn is_hidden(&self, group: &str) -> bool {
+        let group = group.to_lowercase();
+        self.hidden_groups.iter().any(|g| *g == group)
+    }
+
+    pub fn set_hidden(&mut self, group: &str, hidden: bool) {
+        let group = group.to_lowercase();
+        self.hidden_groups.retain(|g| *g != group);
+        if hidden {
+            self.hidden_groups.push(group);
+        }
+    }
+
+    /// 新增或覆盖同名配置
+    pub fn upsert(&mut self, profile: Profile) {
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(p) => *p = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
+    /// 按命令行决定本次启动的配置：--profile 名称 / --view usb|full，也接受 --view=usb 写法。
+    /// 未指定 --profile 且开启了恢复时，面板与视图取自上次退出时的状态
+    pub fn launch_profile(&self, args: impl IntoIterator<Item = String>) -> Profile {
+        let mut profile_arg = None;
+        let mut view_arg = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (key, inline) = match arg.split_once('=') {
+                Some((k, v)) => (k.to_string(), Some(v.to_string())),
+                None => (arg, None),
+            };
+            match key.as_str() {
+                "--profile" => profile_arg = inline.or_else(|| args.next()),
+                "--view" => view_arg = inline.or_else(|| args.next()),
+                _ => {}
+            }
+        }
+        let mut profile = profile_arg
+            .as_deref()
+            .and_then(|name| self.profile(name))
+            .or_else(|| self.profile(&self.default_profile))
+            .or(self.profiles.first())
+            .cloned()
+            .unwrap_or_default();
+        if let (None, true, Some(session)) = (&profile_arg, self.restore_session, &self.session) {
+            profile.view = session.view;
+            profile.panels = session.panels.clone();
+        }
+        if let Some(view) = view_arg.as_deref().and_then(View::from_arg) {
+            profile.view = view;
+            if view == View::Usb {
+                profile.panels.usb_manager = true;
+            }
+        }
+        profile
+    }
+
+    /// 启动时的窗口 (位置, 大小)：上次退出时是同一视图才沿用其位置与大小
+    pub fn window_geometry(&self, view: View) -> (Option<[f32; 2]>, [f32; 2]) {
+        match &self.session {
+            Some(s) if self.restore_session && s.view == view => (s.position, s.size.unwrap_or(view.window_size())),
+            _ => (None, view.window_size()),
+        }
+    }
+}
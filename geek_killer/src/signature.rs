@@ -0,0 +1,276 @@
+// ═══════════════════════════════════════════════════════════════
+//  数字签名 - Authenticode 校验与签名者
+// ═══════════════════════════════════════════════════════════════
+//
+// WinVerifyTrust (WINTRUST_ACTION_GENERIC_VERIFY_V2) 校验 exe 的签名，
+// 成功时从签名链首个证书取主题显示名作为“签名者”。
+// 系统自带程序大多没有内嵌签名，而是登记在系统目录 (catalog) 中：
+// 内嵌签名缺失时再按文件哈希查找所属 .cat 并以目录方式校验。
+// 不做在线吊销检查 (避免网络超时拖慢采集)；校验在后台线程进行，结果按路径缓存。
+// “未签名且从临时目录运行”是恶意程序的典型特征，界面上标红提示。
+
+use crate::i18n::{tr, trf};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{mpsc, Arc, Mutex};
+use windows_sys::Win32::Foundation::{CloseHandle, GENERIC_READ, HANDLE, INVALID_HANDLE_VALUE};
+use windows_sys::Win32::Security::Cryptography::Catalog::{
+    CryptCATAdminAcquireContext2, CryptCATAdminCalcHashFromFileHandle2, CryptCATAdminEnumCatalogFromHash,
+    CryptCATAdminReleaseCatalogContext, CryptCATAdminReleaseContext, CryptCATCatalogInfoFromContext, CATALOG_INFO,
+};
+use windows_sys::Win32::Security::Cryptography::{CertGetNameStringW, CERT_NAME_SIMPLE_DISPLAY_TYPE};
+use windows_sys::Win32::Security::WinTrust::{
+    WTHelperGetProvSignerFromChain, WTHelperProvDataFromStateData, WinVerifyTrust, WINTRUST_ACTION_GENERIC_VERIFY_V2,
+    WINTRUST_CATALOG_INFO, WINTRUST_DATA, WINTRUST_DATA_UNION_CHOICE, WINTRUST_FILE_INFO,
+    WTD_CACHE_ONLY_URL_RETRIEVAL, WTD_CHOICE_CATALOG, WTD_CHOICE_FILE, WTD_REVOKE_NONE, WTD_STATEACTION_CLOSE,
+    WTD_STATEACTION_VERIFY, WTD_UI_NONE,
+};
+use windows_sys::Win32::Storage::FileSystem::{
+    CreateFileW, FILE_SHARE_DELETE, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+
+// WinVerifyTrust 返回的 HRESULT
+const TRUST_E_NOSIGNATURE: i32 = 0x800B0100u32 as i32;
+const TRUST_E_SUBJECT_FORM_UNKNOWN: i32 = 0x800B0003u32 as i32;
+const TRUST_E_PROVIDER_UNKNOWN: i32 = 0x800B0001u32 as i32;
+const TRUST_E_BAD_DIGEST: i32 = 0x80096010u32 as i32;
+const TRUST_E_EXPLICIT_DISTRUST: i32 = 0x800B0111u32 as i32;
+const CERT_E_UNTRUSTEDROOT: i32 = 0x800B0109u32 as i32;
+const CERT_E_EXPIRED: i32 = 0x800B0101u32 as i32;
+const CERT_E_REVOKED: i32 = 0x800B010Cu32 as i32;
+const CERT_E_CHAINING: i32 = 0x800B010Au32 as i32;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Signature {
+    /// 签名有效，附签名者 (证书主题显示名)
+    Signed(String),
+    Unsigned,
+    /// 有签名但校验失败，附原因
+    Invalid(String),
+}
+
+impl Signature {
+    pub fn label(&self) -> String {
+        match self {
+            Signature::Signed(signer) if signer.is_empty() => tr("已签名").to_string(),
+            Signature::Signed(signer) => signer.clone(),
+            Signature::Unsigned => tr("未签名").to_string(),
+            Signature::Invalid(reason) => trf("签名无效：{}", &[&reason]),
+        }
+    }
+
+    /// 未通过校验且从临时目录运行
+    pub fn is_suspicious(&self, exe_path: &str) -> bool {
+        !matches!(self, Signature::Signed(_)) && in_temp_dir(exe_path)
+    }
+}
+
+/// 是否位于临时目录 (%TEMP%、用户 AppData\Local\Temp、Windows\Temp)
+pub fn in_temp_dir(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    let temp = std::env::temp_dir().to_string_lossy().to_lowercase();
+    let temp = temp.trim_end_matches('\\');
+    (!temp.is_empty() && lower.starts_with(&format!("{}\\", temp)))
+        || lower.contains("\\appdata\\local\\temp\\")
+        || lower.contains("\\windows\\temp\\")
+}
+
+fn reason(status: i32) -> String {
+    match status {
+        TRUST_E_BAD_DIGEST => tr("文件内容与签名不符 (可能被篡改)").to_string(),
+        TRUST_E_EXPLICIT_DISTRUST => tr("证书已被明确禁止").to_string(),
+        CERT_E_UNTRUSTEDROOT | CERT_E_CHAINING => tr("证书链不受信任").to_string(),
+        CERT_E_EXPIRED => tr("证书已过期").to_string(),
+        CERT_E_REVOKED => tr("证书已吊销").to_string(),
+        _ => trf("校验失败 0x{}", &[&format!("{:08X}", status as u32)]),
+    }
+}
+
+unsafe fn trust_data(choice: WINTRUST_DATA_UNION_CHOICE) -> WINTRUST_DATA {
+    let mut data: WINTRUST_DATA = std::mem::zeroed();
+    data.cbStruct = std::mem::size_of::<WINTRUST_DATA>() as u32;
+    data.dwUIChoice = WTD_UI_NONE;
+    data.fdwRevocationChecks = WTD_REVOKE_NONE;
+    data.dwUnionChoice = choice;
+    data.dwProvFlags = WTD_CACHE_ONLY_URL_RETRIEVAL;
+    data
+}
+
+/// 签名链首个证书的主题显示名
+unsafe fn signer_name(state: HANDLE) -> String {
+    let prov = WTHelperProvDataFromStateData(state);
+    if prov.is_null() {
+        return String::new();
+    }
+    let signer = WTHelperGetProvSignerFromChain(prov, 0, 0, 0);
+    if signer.is_null() || (*signer).csCertChain == 0 || (*signer).pasCertChain.is_null() {
+        return String::new();
+    }
+    let cert = (*(*signer).pasCertChain).pCert;
+    if cert.is_null() {
+        return String::new();
+    }
+    let mut buf = [0u16; 256];
+    let len = CertGetNameStringW(
+        cert,
+        CERT_NAME_SIMPLE_DISPLAY_TYPE,
+        0,
+        std::ptr::null(),
+        buf.as_mut_ptr(),
+        buf.len() as u32,
+    );
+    // 返回值含结尾的 0
+    String::from_utf16_lossy(&buf[..(len as usize).saturating_sub(1)])
+}
+
+/// 校验并取签名者，结束后释放校验状态
+unsafe fn verify_with(data: &mut WINTRUST_DATA) -> (i32, String) {
+    let mut action = WINTRUST_ACTION_GENERIC_VERIFY_V2;
+    data.dwStateAction = WTD_STATEACTION_VERIFY;
+    let status = WinVerifyTrust(0, &mut action, data as *mut _ as _);
+    let signer = if status == 0 { signer_name(data.hWVTStateData) } else { String::new() };
+    data.dwStateAction = WTD_STATEACTION_CLOSE;
+    WinVerifyTrust(0, &mut action, data as *mut _ as _);
+    (status, signer)
+}
+
+unsafe fn verify_embedded(path: &[u16]) -> (i32, String) {
+    let mut file: WINTRUST_FILE_INFO = std::mem::zeroed();
+    file.cbStruct = std::mem::size_of::<WINTRUST_FILE_INFO>() as u32;
+    file.pcwszFilePath = path.as_ptr();
+    let mut data = trust_data(WTD_CHOICE_FILE);
+    data.Anonymous.pFile = &mut file;
+    verify_with(&mut data)
+}
+
+/// 以指定哈希算法 (null 为 SHA1) 在系统目录中查找并校验
+unsafe fn verify_catalog_with(file: HANDLE, path: &[u16], algorithm: *const u16) -> Option<(i32, String)> {
+    let mut admin = 0isize;
+    if CryptCATAdminAcquireContext2(&mut admin, std::ptr::null(), algorithm, std::ptr::null(), 0) == 0 {
+        return None;
+    }
+    let mut result = None;
+    let mut hash = [0u8; 64];
+    let mut hash_len = hash.len() as u32;
+    if CryptCATAdminCalcHashFromFileHandle2(admin, file, &mut hash_len, hash.as_mut_ptr(), 0) != 0 {
+        let catalog = CryptCATAdminEnumCatalogFromHash(admin, hash.as_ptr(), hash_len, 0, std::ptr::null_mut());
+        if catalog != 0 {
+            let mut info: CATALOG_INFO = std::mem::zeroed();
+            info.cbStruct = std::mem::size_of::<CATALOG_INFO>() as u32;
+            if CryptCATCatalogInfoFromContext(catalog, &mut info, 0) != 0 {
+                // 目录中的成员标记是哈希的大写十六进制
+                let tag: Vec<u16> = hash[..hash_len as usize]
+                    .iter()
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<String>()
+                    .encode_utf16()
+                    .chain(std::iter::once(0))
+                    .collect();
+                let mut member: WINTRUST_CATALOG_INFO = std::mem::zeroed();
+                member.cbStruct = std::mem::size_of::<WINTRUST_CATALOG_INFO>() as u32;
+                member.pcwszCatalogFilePath = info.wszCatalogFile.as_ptr();
+                member.pcwszMemberTag = tag.as_ptr();
+                member.pcwszMemberFilePath = path.as_ptr();
+                member.hMemberFile = file;
+                member.pbCalculatedFileHash = hash.as_mut_ptr();
+                member.cbCalculatedFileHash = hash_len;
+                member.hCatAdmin = admin;
+                let mut data = trust_data(WTD_CHOICE_CATALOG);
+                data.Anonymous.pCatalog = &mut member;
+                result = Some(verify_with(&mut data));
+            }
+            CryptCATAdminReleaseCatalogContext(admin, catalog, 0);
+        }
+    }
+    CryptCATAdminReleaseContext(admin, 0);
+    result
+}
+
+unsafe fn verify_catalog(path: &[u16]) -> Option<(i32, String)> {
+    let file = CreateFileW(
+        path.as_ptr(),
+        GENERIC_READ,
+        FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+        std::ptr::null(),
+        OPEN_EXISTING,
+        0,
+        0,
+    );
+    if file == INVALID_HANDLE_VALUE {
+        return None;
+    }
+    // 新目录按 SHA256 登记，旧目录只有 SHA1
+    let sha256: Vec<u16> = "SHA256\0".encode_utf16().collect();
+    let result = verify_catalog_with(file, path, sha256.as_ptr())
+        .filter(|(status, _)| *status == 0)
+        .or_else(|| verify_catalog_with(file, path, std::ptr::null()));
+    CloseHandle(file);
+    result
+}
+
+/// 校验可执行文件的签名 (可能耗时数百毫秒，勿在界面线程调用)
+pub fn verify(exe_path: &str) -> Signature {
+    let wide = crate::long_path::to_wide(crate::arch::native_path(Path::new(exe_path)).as_os_str());
+    unsafe {
+        let (status, signer) = verify_embedded(&wide);
+        match status {
+            0 => Signature::Signed(signer),
+            TRUST_E_NOSIGNATURE | TRUST_E_SUBJECT_FORM_UNKNOWN | TRUST_E_PROVIDER_UNKNOWN => {
+                match verify_catalog(&wide) {
+                    Some((0, signer)) => Signature::Signed(signer),
+                    Some((TRUST_E_NOSIGNATURE, _)) | None => Signature::Unsigned,
+                    Some((status, _)) => Signature::Invalid(reason(status)),
+                }
+            }
+            status => Signature::Invalid(reason(status)),
+        }
+    }
+}
+
+/// 按路径缓存的签名结果；未知路径交给后台线程校验，结果在之后的采集中取到
+pub struct SignatureCache {
+    results: Arc<Mutex<HashMap<String, Signature>>>,
+    requested: HashSet<String>,
+    tx: mpsc::Sender<String>,
+}
+
+impl SignatureCache {
+    pub fn new() -> Self {
+        let results: Arc<Mutex<HashMap<String, Signature>>> = Arc::new(Mutex::new(HashMap::new()));
+        let (tx, rx) = mpsc::channel::<String>();
+        let shared = results.clone();
+        std::thread::spawn(move || {
+            for path in rx {
+                let signature = verify(&path);
+                if let Ok(mut map) = shared.lock() {
+                    map.insert(path, signature);
+                }
+            }
+        });
+        Self {
+            results,
+            requested: HashSet::new(),
+            tx,
+        }
+    }
+
+    /// 已校验的结果；尚未校验时排队并返回 None
+    pub fn get(&mut self, exe_path: &str) -> Option<Signature> {
+        if exe_path.is_empty() {
+            return None;
+        }
+        if let Some(s) = self.results.lock().ok().and_then(|m| m.get(exe_path).cloned()) {
+            return Some(s);
+        }
+        if self.requested.insert(exe_path.to_string()) {
+            let _ = self.tx.send(exe_path.to_string());
+        }
+        None
+    }
+}
+
+impl Default for SignatureCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,290 @@
+// ═══════════════════════════════════════════════════════════════
+//  SMART 健康 - 温度 / 重映射扇区 / 剩余寿命
+// ═══════════════════════════════════════════════════════════════
+//
+// 按物理磁盘 (\\.\PhysicalDriveN) 查询，依次尝试：
+//   1. SMART_RCV_DRIVE_DATA (READ ATTRIBUTES)   -> SATA/ATA，需要读写权限 (管理员)
+//   2. IOCTL_STORAGE_PREDICT_FAILURE            -> ATA 的预警标志 + 同格式属性表，无需权限
+//   3. IOCTL_STORAGE_QUERY_PROPERTY (NVMe 健康日志 02h)
+// 多数 U 盘和读卡器的 USB 桥不转发 SMART，查询失败时如实显示“不支持”。
+
+use crate::i18n::{tr, trf};
+use crate::long_path::wide;
+use windows_sys::Win32::Foundation::{CloseHandle, GENERIC_READ, GENERIC_WRITE, HANDLE, INVALID_HANDLE_VALUE};
+use windows_sys::Win32::Storage::FileSystem::{CreateFileW, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING};
+use windows_sys::Win32::System::Ioctl::{
+    NVMeDataTypeLogPage, ProtocolTypeNvme, StorageDeviceProtocolSpecificProperty, IOCTL_STORAGE_PREDICT_FAILURE,
+    IOCTL_STORAGE_QUERY_PROPERTY, READ_ATTRIBUTES, SMART_CMD, SMART_RCV_DRIVE_DATA,
+};
+use windows_sys::Win32::System::IO::DeviceIoControl;
+
+/// ATA 属性表：512 字节数据，偏移 2 起 30 项，每项 12 字节
+const ATTR_TABLE_OFFSET: usize = 2;
+const ATTR_ENTRY_SIZE: usize = 12;
+const ATTR_ENTRIES: usize = 30;
+
+const ATTR_REALLOCATED: u8 = 5;
+const ATTR_TEMPERATURE: u8 = 194;
+const ATTR_AIRFLOW_TEMPERATURE: u8 = 190;
+const ATTR_PENDING: u8 = 197;
+const ATTR_UNCORRECTABLE: u8 = 198;
+/// 以归一化值表示剩余寿命 (%) 的属性，各厂商不同，按顺序取第一个
+const ATTR_WEAR: &[u8] = &[231, 233, 177, 202, 169];
+
+/// SENDCMDINPARAMS (pack 1) 长度，bBuffer[1] 计入
+const SENDCMD_IN_SIZE: usize = 33;
+/// SENDCMDOUTPARAMS 头部：cBufferSize + DRIVERSTATUS
+const SENDCMD_OUT_HEADER: usize = 16;
+const SMART_DATA_SIZE: usize = 512;
+
+/// NVMe SMART / Health Information 日志页
+const NVME_LOG_PAGE_HEALTH_INFO: u32 = 2;
+/// STORAGE_PROTOCOL_SPECIFIC_DATA：10 个 u32
+const PROTOCOL_DATA_SIZE: usize = 40;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Health {
+    Good,
+    Warning,
+    Failing,
+}
+
+impl Health {
+    pub fn label(self) -> &'static str {
+        match self {
+            Health::Good => tr("良好"),
+            Health::Warning => tr("注意"),
+            Health::Failing => tr("危险"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SmartHealth {
+    /// "ATA" / "NVMe"
+    pub protocol: &'static str,
+    pub temperature_c: Option<i32>,
+    pub reallocated: Option<u64>,
+    pub pending: Option<u64>,
+    pub uncorrectable: Option<u64>,
+    /// NVMe 介质错误数
+    pub media_errors: Option<u64>,
+    /// 剩余寿命百分比 (SSD)
+    pub wear_left: Option<u8>,
+    /// 固件自身给出的故障预警
+    pub predict_failure: bool,
+}
+
+impl SmartHealth {
+    /// 综合评级与原因
+    pub fn assess(&self) -> (Health, Vec<String>) {
+        let mut level = Health::Good;
+        let mut reasons = Vec::new();
+        let mut flag = |h: Health, reason: String| {
+            level = level.max(h);
+            reasons.push(reason);
+        };
+        if self.predict_failure {
+            flag(Health::Failing, tr("固件报告即将故障").to_string());
+        }
+        if let Some(n) = self.pending.filter(|&n| n > 0) {
+            flag(Health::Failing, trf("{} 个待映射扇区", &[&n]));
+        }
+        if let Some(n) = self.uncorrectable.filter(|&n| n > 0) {
+            flag(Health::Failing, trf("{} 个无法校正的扇区", &[&n]));
+        }
+        if let Some(n) = self.media_errors.filter(|&n| n > 0) {
+            flag(Health::Failing, trf("{} 次介质错误", &[&n]));
+        }
+        if let Some(n) = self.reallocated.filter(|&n| n > 0) {
+            let h = if n > 100 { Health::Failing } else { Health::Warning };
+            flag(h, trf("已重映射 {} 个扇区", &[&n]));
+        }
+        if let Some(w) = self.wear_left.filter(|&w| w <= 20) {
+            let h = if w <= 5 { Health::Failing } else { Health::Warning };
+            flag(h, trf("剩余寿命 {}%", &[&w]));
+        }
+        if let Some(t) = self.temperature_c.filter(|&t| t >= 60) {
+            flag(Health::Warning, trf("温度过高 {}°C", &[&t]));
+        }
+        (level, reasons)
+    }
+
+    /// 一行摘要：🌡 38°C · 重映射 0 · 寿命 97%
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(t) = self.temperature_c {
+            parts.push(format!("🌡 {}°C", t));
+        }
+        if let Some(n) = self.reallocated {
+            parts.push(trf("重映射 {}", &[&n]));
+        }
+        if let Some(n) = self.media_errors {
+            parts.push(trf("介质错误 {}", &[&n]));
+        }
+        if let Some(w) = self.wear_left {
+            parts.push(trf("寿命 {}%", &[&w]));
+        }
+        if parts.is_empty() {
+            parts.push(tr(if self.predict_failure { "预警" } else { "无异常" }).to_string());
+        }
+        parts.join(" · ")
+    }
+}
+
+struct Disk(HANDLE);
+
+impl Drop for Disk {
+    fn drop(&mut self) {
+        unsafe {
+            CloseHandle(self.0);
+        }
+    }
+}
+
+fn open_disk(number: u32, access: u32) -> Option<Disk> {
+    let path = wide(&format!("\\\\.\\PhysicalDrive{}", number));
+    let h = unsafe {
+        CreateFileW(path.as_ptr(), access, FILE_SHARE_READ | FILE_SHARE_WRITE, std::ptr::null(), OPEN_EXISTING, 0, 0)
+    };
+    (h != INVALID_HANDLE_VALUE).then_some(Disk(h))
+}
+
+/// 原地进出的 DeviceIoControl，返回写入的字节数
+fn ioctl(disk: &Disk, code: u32, input: &[u8], output: &mut [u8]) -> Option<usize> {
+    let mut bytes = 0u32;
+    let ok = unsafe {
+        DeviceIoControl(
+            disk.0,
+            code,
+            input.as_ptr() as _,
+            input.len() as u32,
+            output.as_mut_ptr() as _,
+            output.len() as u32,
+            &mut bytes,
+            std::ptr::null_mut(),
+        )
+    };
+    (ok != 0).then_some(bytes as usize)
+}
+
+fn le_u64(bytes: &[u8]) -> u64 {
+    bytes.iter().rev().fold(0, |acc, &b| (acc << 8) | b as u64)
+}
+
+/// 解析 ATA 属性表；表为空 (全 0) 时返回 None
+fn parse_attributes(data: &[u8]) -> Option<SmartHealth> {
+    let mut health = SmartHealth { protocol: "ATA", ..Default::default() };
+    let mut any = false;
+    let mut wear: Vec<(u8, u8)> = Vec::new();
+    for i in 0..ATTR_ENTRIES {
+        let start = ATTR_TABLE_OFFSET + i * ATTR_ENTRY_SIZE;
+        let Some(entry) = data.get(start..start + ATTR_ENTRY_SIZE) else { break };
+        let (id, current, raw) = (entry[0], entry[3], &entry[5..11]);
+        if id == 0 {
+            continue;
+        }
+        any = true;
+        // 计数类属性只取低 32 位，高位常被厂商塞入其他信息
+        let count = le_u64(&raw[..4]);
+        match id {
+            ATTR_REALLOCATED => health.reallocated = Some(count),
+            ATTR_PENDING => health.pending = Some(count),
+            ATTR_UNCORRECTABLE => health.uncorrectable = Some(count),
+            ATTR_TEMPERATURE => health.temperature_c = Some(raw[0] as i32),
+            ATTR_AIRFLOW_TEMPERATURE if health.temperature_c.is_none() => {
+                health.temperature_c = Some(raw[0] as i32)
+            }
+            _ if ATTR_WEAR.contains(&id) => wear.push((id, current.min(100))),
+            _ => {}
+        }
+    }
+    health.wear_left = ATTR_WEAR
+        .iter()
+        .find_map(|w| wear.iter().find(|(id, _)| id == w).map(|&(_, v)| v));
+    any.then_some(health)
+}
+
+fn read_ata_smart(disk: &Disk, number: u32) -> Option<SmartHealth> {
+    let mut input = [0u8; SENDCMD_IN_SIZE];
+    input[..4].copy_from_slice(&(SMART_DATA_SIZE as u32).to_le_bytes());
+    // IDEREGS：Features, SectorCount, SectorNumber, CylLow, CylHigh, DriveHead, Command
+    input[4] = READ_ATTRIBUTES as u8;
+    input[5] = 1;
+    input[6] = 1;
+    input[7] = 0x4F;
+    input[8] = 0xC2;
+    input[9] = 0xA0 | (((number & 1) as u8) << 4);
+    input[10] = SMART_CMD as u8;
+    input[12] = number as u8;
+    let mut output = vec![0u8; SENDCMD_OUT_HEADER + SMART_DATA_SIZE];
+    ioctl(disk, SMART_RCV_DRIVE_DATA, &input, &mut output)?;
+    parse_attributes(&output[SENDCMD_OUT_HEADER..])
+}
+
+/// STORAGE_PREDICT_FAILURE { PredictFailure: u32, VendorSpecific: [u8; 512] }
+fn read_predict_failure(disk: &Disk) -> Option<(bool, Option<SmartHealth>)> {
+    let mut output = vec![0u8; 4 + SMART_DATA_SIZE];
+    ioctl(disk, IOCTL_STORAGE_PREDICT_FAILURE, &[], &mut output)?;
+    let predict = u32::from_le_bytes([output[0], output[1], output[2], output[3]]) != 0;
+    Some((predict, parse_attributes(&output[4..])))
+}
+
+fn read_nvme_health(disk: &Disk) -> Option<SmartHealth> {
+    // STORAGE_PROPERTY_QUERY { PropertyId, QueryType, STORAGE_PROTOCOL_SPECIFIC_DATA } + 数据区
+    let mut buf = vec![0u8; 8 + PROTOCOL_DATA_SIZE + SMART_DATA_SIZE];
+    let fields = [
+        StorageDeviceProtocolSpecificProperty as u32,
+        0, // PropertyStandardQuery
+        ProtocolTypeNvme as u32,
+        NVMeDataTypeLogPage as u32,
+        NVME_LOG_PAGE_HEALTH_INFO,
+        0,
+        PROTOCOL_DATA_SIZE as u32, // ProtocolDataOffset：相对 ProtocolSpecificData 起点
+        SMART_DATA_SIZE as u32,
+    ];
+    for (i, v) in fields.iter().enumerate() {
+        buf[i * 4..i * 4 + 4].copy_from_slice(&v.to_le_bytes());
+    }
+    let input = buf.clone();
+    ioctl(disk, IOCTL_STORAGE_QUERY_PROPERTY, &input, &mut buf)?;
+
+    // 返回 STORAGE_PROTOCOL_DATA_DESCRIPTOR { Version, Size, ProtocolSpecificData }
+    let offset = u32::from_le_bytes(buf[24..28].try_into().ok()?) as usize;
+    let log = buf.get(8 + offset..8 + offset + SMART_DATA_SIZE)?;
+    let kelvin = u16::from_le_bytes([log[1], log[2]]) as i32;
+    Some(SmartHealth {
+        protocol: "NVMe",
+        temperature_c: (kelvin > 0).then_some(kelvin - 273),
+        media_errors: Some(le_u64(&log[160..168])),
+        wear_left: Some(100 - log[5].min(100)),
+        predict_failure: log[0] != 0,
+        ..Default::default()
+    })
+}
+
+/// 查询物理磁盘的 SMART 健康信息
+pub fn query_disk(number: u32) -> Result<SmartHealth, String> {
+    if let Some(disk) = open_disk(number, GENERIC_READ | GENERIC_WRITE) {
+        if let Some(mut health) = read_ata_smart(&disk, number) {
+            if let Some((predict, _)) = read_predict_failure(&disk) {
+                health.predict_failure = predict;
+            }
+            return Ok(health);
+        }
+    }
+
+    // 没有管理员权限时只能用无需访问权限的查询
+    let disk = open_disk(number, 0).ok_or_else(|| trf("无法打开 PhysicalDrive{}", &[&number]))?;
+    if let Some(health) = read_nvme_health(&disk) {
+        return Ok(health);
+    }
+    match read_predict_failure(&disk) {
+        Some((predict, Some(mut health))) => {
+            health.predict_failure = predict;
+            Ok(health)
+        }
+        Some((predict, None)) => Ok(SmartHealth { protocol: "ATA", predict_failure: predict, ..Default::default() }),
+        None => Err(tr("设备不支持 SMART (多数 U 盘 / 读卡器不转发)").to_string()),
+    }
+}
@@ -0,0 +1,252 @@
+// ═══════════════════════════════════════════════════════════════
+//  U 盘测速 - 直接 I/O 顺序读写，顺带核对读回的数据
+// ═══════════════════════════════════════════════════════════════
+//
+// 在卷根目录写入临时文件再读回：
+//   - FILE_FLAG_NO_BUFFERING 绕过系统缓存，写入另加 WRITE_THROUGH，测到的是设备本身
+//   - 直接 I/O 要求缓冲区按扇区对齐，用 VirtualAlloc 分配 (页对齐)
+//   - 每个 4 KB 扇区开头写入块号 / 扇区号，读回时核对，不一致说明测试区域内有坏块
+// 只测试最多 256 MB，远小于扩容盘的真实容量，识别不了扩容盘；要查扩容需写满整盘，
+// 这里不做。
+// 测试文件大小取 256 MB 与剩余空间一半中较小者，结束后删除。
+
+use crate::i18n::{tr, trf};
+use crate::long_path::wide;
+use std::time::Instant;
+use windows_sys::Win32::Foundation::{CloseHandle, HANDLE, INVALID_HANDLE_VALUE};
+use windows_sys::Win32::Storage::FileSystem::{
+    CreateFileW, FlushFileBuffers, GetDiskFreeSpaceExW, ReadFile, WriteFile, CREATE_ALWAYS,
+    FILE_ATTRIBUTE_TEMPORARY, FILE_FLAG_NO_BUFFERING, FILE_FLAG_SEQUENTIAL_SCAN, FILE_FLAG_WRITE_THROUGH,
+    FILE_GENERIC_READ, FILE_GENERIC_WRITE, OPEN_EXISTING,
+};
+use windows_sys::Win32::System::Memory::{VirtualAlloc, VirtualFree, MEM_COMMIT, MEM_RELEASE, MEM_RESERVE, PAGE_READWRITE};
+
+const BLOCK: usize = 4 * 1024 * 1024;
+const SECTOR: usize = 4096;
+const MAX_BYTES: u64 = 256 * 1024 * 1024;
+const MIN_BYTES: u64 = 16 * 1024 * 1024;
+const FILE_NAME: &str = "GeekKiller_speedtest.tmp";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Phase {
+    Write,
+    Read,
+}
+
+impl Phase {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Phase::Write => tr("写入"),
+            Phase::Read => tr("读取"),
+        }
+    }
+}
+
+/// 发给 UI 的测速进度
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpeedProgress {
+    pub drive: String,
+    pub phase: Phase,
+    pub done: u64,
+    pub total: u64,
+    /// 本阶段到目前为止的平均速度
+    pub mbps: f64,
+}
+
+impl SpeedProgress {
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.done as f32 / self.total as f32
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpeedResult {
+    pub drive: String,
+    pub bytes: u64,
+    pub write_mbps: f64,
+    pub read_mbps: f64,
+    /// 读回内容与写入不一致的扇区数
+    pub mismatched: usize,
+}
+
+impl SpeedResult {
+    pub fn summary(&self) -> String {
+        let mut s = trf(
+            "{} 顺序写 {} MB/s · 顺序读 {} MB/s ({} MB)",
+            &[
+                &self.drive,
+                &format!("{:.1}", self.write_mbps),
+                &format!("{:.1}", self.read_mbps),
+                &(self.bytes / 1024 / 1024),
+            ],
+        );
+        if self.mismatched > 0 {
+            s.push_str(&trf(" · ⚠ {} 个扇区读回不一致，U 盘可能有坏块", &[&self.mismatched]));
+        }
+        s
+    }
+}
+
+/// VirtualAlloc 分配的页对齐缓冲区
+struct AlignedBuf {
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl AlignedBuf {
+    fn new(len: usize) -> Result<Self, String> {
+        let ptr = unsafe { VirtualAlloc(std::ptr::null(), len, MEM_COMMIT | MEM_RESERVE, PAGE_READWRITE) } as *mut u8;
+        if ptr.is_null() {
+            return Err(tr("无法分配缓冲区").to_string());
+        }
+        Ok(Self { ptr, len })
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl Drop for AlignedBuf {
+    fn drop(&mut self) {
+        unsafe {
+            VirtualFree(self.ptr as _, 0, MEM_RELEASE);
+        }
+    }
+}
+
+struct File(HANDLE);
+
+impl Drop for File {
+    fn drop(&mut self) {
+        unsafe {
+            CloseHandle(self.0);
+        }
+    }
+}
+
+fn open(path: &str, write: bool) -> Result<File, String> {
+    let (access, disposition, flags) = if write {
+        (FILE_GENERIC_WRITE, CREATE_ALWAYS, FILE_FLAG_NO_BUFFERING | FILE_FLAG_WRITE_THROUGH | FILE_ATTRIBUTE_TEMPORARY)
+    } else {
+        (FILE_GENERIC_READ, OPEN_EXISTING, FILE_FLAG_NO_BUFFERING | FILE_FLAG_SEQUENTIAL_SCAN)
+    };
+    let h = unsafe {
+        CreateFileW(wide(path).as_ptr(), access, 0, std::ptr::null(), disposition, flags, 0)
+    };
+    if h == INVALID_HANDLE_VALUE {
+        Err(trf("无法创建测试文件：{}", &[&std::io::Error::last_os_error()]))
+    } else {
+        Ok(File(h))
+    }
+}
+
+fn free_bytes(root: &str) -> u64 {
+    let mut free = 0u64;
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(wide(root).as_ptr(), &mut free, std::ptr::null_mut(), std::ptr::null_mut())
+    };
+    if ok == 0 {
+        0
+    } else {
+        free
+    }
+}
+
+/// 扇区标记：块号与扇区号，回绕覆盖后对不上
+fn stamp(block: usize, sector: usize) -> [u8; 8] {
+    (((block as u64) << 32) | (sector as u64 ^ 0x4B49_4C4C)).to_le_bytes()
+}
+
+fn mbps(bytes: u64, started: Instant) -> f64 {
+    let secs = started.elapsed().as_secs_f64().max(1e-6);
+    bytes as f64 / 1024.0 / 1024.0 / secs
+}
+
+/// 顺序写入再读回，每完成一块回调一次进度
+pub fn run(drive: &str, mut on_progress: impl FnMut(SpeedProgress)) -> Result<SpeedResult, String> {
+    let d = crate::mount_manager::display_name(drive);
+    let root = crate::mount_manager::volume_root(drive);
+    let total = (free_bytes(&root) / 2).min(MAX_BYTES) / BLOCK as u64 * BLOCK as u64;
+    if total < MIN_BYTES {
+        return Err(trf("{} 剩余空间不足，至少需要 {} MB", &[&d, &(MIN_BYTES * 2 / 1024 / 1024)]));
+    }
+    let blocks = (total / BLOCK as u64) as usize;
+    let path = format!("{}{}", root, FILE_NAME);
+
+    let mut buf = AlignedBuf::new(BLOCK)?;
+    for (i, b) in buf.as_mut_slice().iter_mut().enumerate() {
+        *b = (i * 31 + 7) as u8;
+    }
+
+    let result = (|| {
+        let progress = |phase, done: u64, started| SpeedProgress {
+            drive: d.clone(),
+            phase,
+            done,
+            total,
+            mbps: mbps(done, started),
+        };
+
+        // 写入
+        let file = open(&path, true)?;
+        let started = Instant::now();
+        for block in 0..blocks {
+            let data = buf.as_mut_slice();
+            for sector in 0..BLOCK / SECTOR {
+                data[sector * SECTOR..sector * SECTOR + 8].copy_from_slice(&stamp(block, sector));
+            }
+            let mut written = 0u32;
+            let ok = unsafe { WriteFile(file.0, data.as_ptr() as _, BLOCK as u32, &mut written, std::ptr::null_mut()) };
+            if ok == 0 || written as usize != BLOCK {
+                return Err(trf("写入失败：{}", &[&std::io::Error::last_os_error()]));
+            }
+            on_progress(progress(Phase::Write, ((block + 1) * BLOCK) as u64, started));
+        }
+        unsafe {
+            FlushFileBuffers(file.0);
+        }
+        let write_mbps = mbps(total, started);
+        drop(file);
+
+        // 读回并核对扇区标记
+        let file = open(&path, false)?;
+        let started = Instant::now();
+        let mut mismatched = 0;
+        for block in 0..blocks {
+            let data = buf.as_mut_slice();
+            let mut read = 0u32;
+            let ok = unsafe { ReadFile(file.0, data.as_mut_ptr() as _, BLOCK as u32, &mut read, std::ptr::null_mut()) };
+            if ok == 0 || read as usize != BLOCK {
+                return Err(trf("读取失败：{}", &[&std::io::Error::last_os_error()]));
+            }
+            mismatched += (0..BLOCK / SECTOR)
+                .filter(|&sector| data[sector * SECTOR..sector * SECTOR + 8] != stamp(block, sector))
+                .count();
+            on_progress(progress(Phase::Read, ((block + 1) * BLOCK) as u64, started));
+        }
+        let read_mbps = mbps(total, started);
+
+        Ok(SpeedResult {
+            drive: d.clone(),
+            bytes: total,
+            write_mbps,
+            read_mbps,
+            mismatched,
+        })
+    })();
+    let _ = std::fs::remove_file(&path);
+
+    crate::audit::record(
+        "测速",
+        &match &result {
+            Ok(r) => r.summary(),
+            Err(e) => format!("{} -> {}", d, e),
+        },
+    );
+    result
+}
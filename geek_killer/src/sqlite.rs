@@ -0,0 +1,205 @@
+// ═══════════════════════════════════════════════════════════════
+//  SQLite - 系统自带 winsqlite3.dll 的最小封装
+// ═══════════════════════════════════════════════════════════════
+//
+// Windows 10 起系统目录自带 winsqlite3.dll (导入库在 Windows SDK 中)，
+// 不必再打包一份 SQLite。导出函数为 __stdcall，用 extern "system" 声明。
+// 只封装用得到的部分：打开、执行、预编译语句的绑定 / 单步 / 取列。
+// Connection 与 Statement 都不跨线程使用，各线程自行打开连接 (WAL 模式下读写互不阻塞)。
+
+use crate::i18n::{tr, trf};
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::path::Path;
+
+const SQLITE_OK: i32 = 0;
+const SQLITE_ROW: i32 = 100;
+const SQLITE_DONE: i32 = 101;
+const SQLITE_NULL: i32 = 5;
+const SQLITE_OPEN_READWRITE: i32 = 0x2;
+const SQLITE_OPEN_CREATE: i32 = 0x4;
+/// 绑定文本时让 SQLite 复制一份
+const SQLITE_TRANSIENT: isize = -1;
+/// 数据库被其他连接锁住时的等待时长 (毫秒)
+const BUSY_TIMEOUT_MS: i32 = 2000;
+
+#[link(name = "winsqlite3")]
+extern "system" {
+    fn sqlite3_open_v2(filename: *const c_char, db: *mut *mut c_void, flags: i32, vfs: *const c_char) -> i32;
+    fn sqlite3_close_v2(db: *mut c_void) -> i32;
+    fn sqlite3_busy_timeout(db: *mut c_void, ms: i32) -> i32;
+    fn sqlite3_errmsg(db: *mut c_void) -> *const c_char;
+    fn sqlite3_exec(
+        db: *mut c_void,
+        sql: *const c_char,
+        callback: *const c_void,
+        arg: *mut c_void,
+        errmsg: *mut *mut c_char,
+    ) -> i32;
+    fn sqlite3_last_insert_rowid(db: *mut c_void) -> i64;
+    fn sqlite3_prepare_v2(
+        db: *mut c_void,
+        sql: *const c_char,
+        len: i32,
+        stmt: *mut *mut c_void,
+        tail: *mut *const c_char,
+    ) -> i32;
+    fn sqlite3_finalize(stmt: *mut c_void) -> i32;
+    fn sqlite3_reset(stmt: *mut c_void) -> i32;
+    fn sqlite3_clear_bindings(stmt: *mut c_void) -> i32;
+    fn sqlite3_step(stmt: *mut c_void) -> i32;
+    fn sqlite3_bind_int64(stmt: *mut c_void, index: i32, value: i64) -> i32;
+    fn sqlite3_bind_double(stmt: *mut c_void, index: i32, value: f64) -> i32;
+    fn sqlite3_bind_text(stmt: *mut c_void, index: i32, text: *const c_char, len: i32, destructor: isize) -> i32;
+    fn sqlite3_column_type(stmt: *mut c_void, col: i32) -> i32;
+    fn sqlite3_column_int64(stmt: *mut c_void, col: i32) -> i64;
+    fn sqlite3_column_double(stmt: *mut c_void, col: i32) -> f64;
+    fn sqlite3_column_text(stmt: *mut c_void, col: i32) -> *const u8;
+    fn sqlite3_column_bytes(stmt: *mut c_void, col: i32) -> i32;
+}
+
+/// 绑定到语句参数的值
+pub enum Value<'a> {
+    Int(i64),
+    Real(f64),
+    Text(&'a str),
+}
+
+pub struct Connection(*mut c_void);
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        unsafe {
+            sqlite3_close_v2(self.0);
+        }
+    }
+}
+
+fn c_string(s: &str) -> Result<CString, String> {
+    CString::new(s).map_err(|_| tr("SQL 中包含 NUL 字符").to_string())
+}
+
+impl Connection {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let name = c_string(&path.to_string_lossy())?;
+        let mut db = std::ptr::null_mut();
+        let rc = unsafe {
+            sqlite3_open_v2(name.as_ptr(), &mut db, SQLITE_OPEN_READWRITE | SQLITE_OPEN_CREATE, std::ptr::null())
+        };
+        // 打开失败时通常也会返回句柄 (用于读取错误信息)，同样要关闭
+        let conn = Connection(db);
+        if rc != SQLITE_OK {
+            return Err(if db.is_null() { trf("无法打开数据库 (错误 {})", &[&rc]) } else { conn.error() });
+        }
+        unsafe {
+            sqlite3_busy_timeout(db, BUSY_TIMEOUT_MS);
+        }
+        Ok(conn)
+    }
+
+    fn error(&self) -> String {
+        unsafe { CStr::from_ptr(sqlite3_errmsg(self.0)).to_string_lossy().to_string() }
+    }
+
+    /// 执行一段不返回结果的 SQL (可包含多条语句)
+    pub fn execute_batch(&self, sql: &str) -> Result<(), String> {
+        let sql = c_string(sql)?;
+        let rc = unsafe {
+            sqlite3_exec(self.0, sql.as_ptr(), std::ptr::null(), std::ptr::null_mut(), std::ptr::null_mut())
+        };
+        if rc == SQLITE_OK {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    pub fn prepare(&self, sql: &str) -> Result<Statement<'_>, String> {
+        let sql = c_string(sql)?;
+        let mut stmt = std::ptr::null_mut();
+        let rc = unsafe { sqlite3_prepare_v2(self.0, sql.as_ptr(), -1, &mut stmt, std::ptr::null_mut()) };
+        if rc != SQLITE_OK {
+            return Err(self.error());
+        }
+        Ok(Statement { conn: self, raw: stmt })
+    }
+
+    pub fn last_insert_rowid(&self) -> i64 {
+        unsafe { sqlite3_last_insert_rowid(self.0) }
+    }
+}
+
+pub struct Statement<'a> {
+    conn: &'a Connection,
+    raw: *mut c_void,
+}
+
+impl Drop for Statement<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            sqlite3_finalize(self.raw);
+        }
+    }
+}
+
+impl Statement<'_> {
+    /// 重置语句并按顺序绑定参数 (?1, ?2 …)
+    pub fn bind(&mut self, params: &[Value]) -> Result<(), String> {
+        unsafe {
+            sqlite3_reset(self.raw);
+            sqlite3_clear_bindings(self.raw);
+            for (i, value) in params.iter().enumerate() {
+                let index = i as i32 + 1;
+                let rc = match value {
+                    Value::Int(v) => sqlite3_bind_int64(self.raw, index, *v),
+                    Value::Real(v) => sqlite3_bind_double(self.raw, index, *v),
+                    Value::Text(s) => {
+                        sqlite3_bind_text(self.raw, index, s.as_ptr() as _, s.len() as i32, SQLITE_TRANSIENT)
+                    }
+                };
+                if rc != SQLITE_OK {
+                    return Err(self.conn.error());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 执行一步：有结果行时返回 true，执行完毕返回 false
+    pub fn step(&mut self) -> Result<bool, String> {
+        match unsafe { sqlite3_step(self.raw) } {
+            SQLITE_ROW => Ok(true),
+            SQLITE_DONE => Ok(false),
+            _ => Err(self.conn.error()),
+        }
+    }
+
+    /// 绑定参数并执行到结束 (INSERT / UPDATE / DELETE)
+    pub fn execute(&mut self, params: &[Value]) -> Result<(), String> {
+        self.bind(params)?;
+        while self.step()? {}
+        Ok(())
+    }
+
+    pub fn is_null(&self, col: i32) -> bool {
+        unsafe { sqlite3_column_type(self.raw, col) == SQLITE_NULL }
+    }
+
+    pub fn int(&self, col: i32) -> i64 {
+        unsafe { sqlite3_column_int64(self.raw, col) }
+    }
+
+    pub fn real(&self, col: i32) -> f64 {
+        unsafe { sqlite3_column_double(self.raw, col) }
+    }
+
+    pub fn text(&self, col: i32) -> String {
+        unsafe {
+            let ptr = sqlite3_column_text(self.raw, col);
+            if ptr.is_null() {
+                return String::new();
+            }
+            let len = sqlite3_column_bytes(self.raw, col) as usize;
+            String::from_utf8_lossy(std::slice::from_raw_parts(ptr, len)).to_string()
+        }
+    }
+}
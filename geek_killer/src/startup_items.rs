@@ -0,0 +1,561 @@
+// ═══════════════════════════════════════════════════════════════
+//  启动项管理 - Run 键 / 启动文件夹 / 登录触发的计划任务
+// ═══════════════════════════════════════════════════════════════
+//
+// Run 键与启动文件夹的启用状态保存在 Explorer\StartupApproved 下
+// (与任务管理器“启动”页相同)，禁用只是改标记，不删除原始条目，随时可恢复。
+// 计划任务通过 schtasks /change 启用 / 禁用。
+
+use crate::i18n::{tr, trf};
+use crate::long_path::wide;
+use crate::tool_runner::ToolSpec;
+use std::path::{Path, PathBuf};
+use windows_sys::Win32::Foundation::{GetLastError, ERROR_MORE_DATA};
+use windows_sys::Win32::System::Services::{
+    CloseServiceHandle, EnumServicesStatusExW, OpenSCManagerW, OpenServiceW, QueryServiceConfigW,
+    ENUM_SERVICE_STATUS_PROCESSW, QUERY_SERVICE_CONFIGW, SC_ENUM_PROCESS_INFO,
+    SC_MANAGER_ENUMERATE_SERVICE, SERVICE_ACTIVE, SERVICE_AUTO_START, SERVICE_QUERY_CONFIG,
+    SERVICE_WIN32,
+};
+use windows_sys::Win32::System::Registry::{
+    RegCloseKey, RegCreateKeyExW, RegEnumValueW, RegOpenKeyExW, RegQueryValueExW, RegSetValueExW,
+    HKEY, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, KEY_READ, KEY_SET_VALUE, REG_BINARY,
+};
+
+const RUN_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Run";
+const RUN32_KEY: &str = "Software\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Run";
+const APPROVED_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\StartupApproved";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StartupSource {
+    UserRun,
+    MachineRun,
+    MachineRun32,
+    UserStartupFolder,
+    CommonStartupFolder,
+    ScheduledTask,
+}
+
+impl StartupSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StartupSource::UserRun => "HKCU Run",
+            StartupSource::MachineRun => "HKLM Run",
+            StartupSource::MachineRun32 => tr("HKLM Run (32 位)"),
+            StartupSource::UserStartupFolder => tr("启动文件夹"),
+            StartupSource::CommonStartupFolder => tr("公共启动文件夹"),
+            StartupSource::ScheduledTask => tr("计划任务"),
+        }
+    }
+
+    /// 修改是否需要管理员权限
+    pub fn needs_admin(&self) -> bool {
+        !matches!(self, StartupSource::UserRun | StartupSource::UserStartupFolder)
+    }
+
+    /// StartupApproved 中对应的 (根键, 子键)
+    fn approved_key(&self) -> Option<(HKEY, String)> {
+        let (root, leaf) = match self {
+            StartupSource::UserRun => (HKEY_CURRENT_USER, "Run"),
+            StartupSource::MachineRun => (HKEY_LOCAL_MACHINE, "Run"),
+            StartupSource::MachineRun32 => (HKEY_LOCAL_MACHINE, "Run32"),
+            StartupSource::UserStartupFolder => (HKEY_CURRENT_USER, "StartupFolder"),
+            StartupSource::CommonStartupFolder => (HKEY_LOCAL_MACHINE, "StartupFolder"),
+            StartupSource::ScheduledTask => return None,
+        };
+        Some((root, format!("{}\\{}", APPROVED_KEY, leaf)))
+    }
+}
+
+/// 启动影响 (按当前运行时的资源占用估算)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Impact {
+    High,
+    Medium,
+    Low,
+    NotRunning,
+}
+
+impl Impact {
+    pub const ALL: [Impact; 4] = [Impact::High, Impact::Medium, Impact::Low, Impact::NotRunning];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Impact::High => tr("高影响"),
+            Impact::Medium => tr("中等影响"),
+            Impact::Low => tr("低影响"),
+            Impact::NotRunning => tr("当前未运行"),
+        }
+    }
+
+    pub fn estimate(memory: u64, cpu: f32) -> Impact {
+        if memory > 300 * 1024 * 1024 || cpu > 10.0 {
+            Impact::High
+        } else if memory > 80 * 1024 * 1024 || cpu > 2.0 {
+            Impact::Medium
+        } else {
+            Impact::Low
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StartupItem {
+    /// 注册表值名 / 快捷方式文件名 / 任务路径
+    pub name: String,
+    pub command: String,
+    pub source: StartupSource,
+    pub enabled: bool,
+}
+
+impl StartupItem {
+    /// 命令行中可执行文件的文件名 (小写)，用于匹配运行中的进程
+    pub fn exe_name(&self) -> Option<String> {
+        if matches!(
+            self.source,
+            StartupSource::UserStartupFolder | StartupSource::CommonStartupFolder
+        ) {
+            // 快捷方式不解析目标，按文件名猜测
+            let stem = Path::new(&self.name).file_stem()?.to_string_lossy().to_lowercase();
+            return Some(format!("{}.exe", stem));
+        }
+        let path = command_executable(&self.command)?;
+        Path::new(&path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+    }
+}
+
+/// 展开 %VAR% 形式的环境变量
+fn expand_env(s: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find('%') {
+        let Some(len) = rest[start + 1..].find('%') else {
+            break;
+        };
+        let var = &rest[start + 1..start + 1 + len];
+        out.push_str(&rest[..start]);
+        match std::env::var(var) {
+            Ok(v) => out.push_str(&v),
+            Err(_) => out.push_str(&rest[start..start + len + 2]),
+        }
+        rest = &rest[start + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 从命令行中取出可执行文件路径
+pub fn command_executable(command: &str) -> Option<String> {
+    let cmd = expand_env(command.trim());
+    if let Some(rest) = cmd.strip_prefix('"') {
+        return rest.split('"').next().map(|s| s.to_string());
+    }
+    match cmd.as_bytes().windows(4).position(|w| w.eq_ignore_ascii_case(b".exe")) {
+        Some(i) => Some(cmd[..i + 4].to_string()),
+        None => cmd.split_whitespace().next().map(|s| s.to_string()),
+    }
+}
+
+struct RegKey(HKEY);
+impl Drop for RegKey {
+    fn drop(&mut self) {
+        unsafe {
+            RegCloseKey(self.0);
+        }
+    }
+}
+
+fn open_key(root: HKEY, subkey: &str, access: u32) -> Option<RegKey> {
+    let subkey_w = wide(subkey);
+    let mut key: HKEY = 0;
+    let status = unsafe { RegOpenKeyExW(root, subkey_w.as_ptr(), 0, access, &mut key) };
+    (status == 0).then_some(RegKey(key))
+}
+
+/// 枚举某个键下的所有值：(名称, 数据)；数据超出缓冲区时按返回的所需大小重试
+fn enum_values(key: &RegKey) -> Vec<(String, Vec<u8>)> {
+    let mut out = Vec::new();
+    let mut data = vec![0u8; 4096];
+    let mut index = 0;
+    loop {
+        // 值名称最长 16383 个字符
+        let mut name = vec![0u16; 16384];
+        let mut name_len = name.len() as u32;
+        let mut data_len = data.len() as u32;
+        let mut kind = 0u32;
+        let status = unsafe {
+            RegEnumValueW(
+                key.0,
+                index,
+                name.as_mut_ptr(),
+                &mut name_len,
+                std::ptr::null(),
+                &mut kind,
+                data.as_mut_ptr(),
+                &mut data_len,
+            )
+        };
+        if status == ERROR_MORE_DATA && data_len as usize > data.len() {
+            data.resize(data_len as usize, 0);
+            continue;
+        }
+        if status != 0 {
+            break;
+        }
+        out.push((
+            String::from_utf16_lossy(&name[..name_len as usize]),
+            data[..data_len as usize].to_vec(),
+        ));
+        index += 1;
+    }
+    out
+}
+
+fn reg_string(data: &[u8]) -> String {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&c| c != 0)
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// StartupApproved 标记：首字节为偶数表示启用，奇数表示禁用；没有记录视为启用
+fn approved_state(source: StartupSource, name: &str) -> bool {
+    let Some((root, subkey)) = source.approved_key() else {
+        return true;
+    };
+    let Some(key) = open_key(root, &subkey, KEY_READ) else {
+        return true;
+    };
+    let name_w = wide(name);
+    let mut data = [0u8; 12];
+    let mut len = data.len() as u32;
+    let status = unsafe {
+        RegQueryValueExW(
+            key.0,
+            name_w.as_ptr(),
+            std::ptr::null(),
+            std::ptr::null_mut(),
+            data.as_mut_ptr(),
+            &mut len,
+        )
+    };
+    status != 0 || len == 0 || data[0] % 2 == 0
+}
+
+fn read_run_key(root: HKEY, subkey: &str, source: StartupSource, out: &mut Vec<StartupItem>) {
+    let Some(key) = open_key(root, subkey, KEY_READ) else {
+        return;
+    };
+    for (name, data) in enum_values(&key) {
+        let enabled = approved_state(source, &name);
+        out.push(StartupItem {
+            name,
+            command: reg_string(&data),
+            source,
+            enabled,
+        });
+    }
+}
+
+fn startup_folder(common: bool) -> Option<PathBuf> {
+    let base = if common {
+        std::env::var_os("ProgramData")?
+    } else {
+        std::env::var_os("APPDATA")?
+    };
+    Some(PathBuf::from(base).join("Microsoft\\Windows\\Start Menu\\Programs\\Startup"))
+}
+
+fn read_startup_folder(source: StartupSource, out: &mut Vec<StartupItem>) {
+    let common = source == StartupSource::CommonStartupFolder;
+    let Some(dir) = startup_folder(common) else {
+        return;
+    };
+    let Ok(rd) = std::fs::read_dir(&dir) else {
+        return;
+    };
+    for entry in rd.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.eq_ignore_ascii_case("desktop.ini") {
+            continue;
+        }
+        out.push(StartupItem {
+            enabled: approved_state(source, &name),
+            command: entry.path().to_string_lossy().to_string(),
+            name,
+            source,
+        });
+    }
+}
+
+/// 任务 XML 通常是带 BOM 的 UTF-16LE
+fn decode_task_xml(bytes: &[u8]) -> String {
+    if bytes.starts_with(&[0xFF, 0xFE]) {
+        let units: Vec<u16> = bytes[2..]
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        String::from_utf8_lossy(bytes).trim_start_matches('\u{feff}').to_string()
+    }
+}
+
+fn xml_tag<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = xml.find(&open)? + open.len();
+    let len = xml[start..].find(&close)?;
+    Some(xml[start..start + len].trim())
+}
+
+fn read_tasks_dir(dir: &Path, prefix: &str, out: &mut Vec<StartupItem>) {
+    let Ok(rd) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in rd.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let task_name = format!("{}\\{}", prefix, file_name);
+        let path = entry.path();
+        if path.is_dir() {
+            // 系统自带任务数量庞大且不应禁用，直接跳过
+            if prefix.is_empty() && file_name.eq_ignore_ascii_case("Microsoft") {
+                continue;
+            }
+            read_tasks_dir(&path, &task_name, out);
+            continue;
+        }
+        let Ok(bytes) = std::fs::read(&path) else {
+            continue;
+        };
+        let xml = decode_task_xml(&bytes);
+        if !xml.contains("<LogonTrigger") && !xml.contains("<BootTrigger") {
+            continue;
+        }
+        let Some(command) = xml_tag(&xml, "Command") else {
+            continue;
+        };
+        let command = match xml_tag(&xml, "Arguments") {
+            Some(args) => format!("\"{}\" {}", command, args),
+            None => format!("\"{}\"", command),
+        };
+        // 触发器里也有 <Enabled>，只看 <Settings> 段
+        let enabled = xml_tag(&xml, "Settings")
+            .and_then(|s| xml_tag(s, "Enabled"))
+            .map(|v| !v.eq_ignore_ascii_case("false"))
+            .unwrap_or(true);
+        out.push(StartupItem {
+            name: task_name,
+            command,
+            source: StartupSource::ScheduledTask,
+            enabled,
+        });
+    }
+}
+
+/// 枚举所有启动项
+pub fn enumerate() -> Vec<StartupItem> {
+    let mut items = Vec::new();
+    read_run_key(HKEY_CURRENT_USER, RUN_KEY, StartupSource::UserRun, &mut items);
+    read_run_key(HKEY_LOCAL_MACHINE, RUN_KEY, StartupSource::MachineRun, &mut items);
+    read_run_key(HKEY_LOCAL_MACHINE, RUN32_KEY, StartupSource::MachineRun32, &mut items);
+    read_startup_folder(StartupSource::UserStartupFolder, &mut items);
+    read_startup_folder(StartupSource::CommonStartupFolder, &mut items);
+    if let Some(root) = std::env::var_os("SystemRoot") {
+        read_tasks_dir(&PathBuf::from(root).join("System32\\Tasks"), "", &mut items);
+    }
+    items
+}
+
+/// 写入 StartupApproved 标记 (Run 键 / 启动文件夹)
+pub fn set_enabled(item: &StartupItem, enabled: bool) -> Result<(), String> {
+    use windows_sys::Win32::System::SystemInformation::GetSystemTimeAsFileTime;
+
+    let (root, subkey) = item
+        .source
+        .approved_key()
+        .ok_or(tr("计划任务需通过 schtasks 修改"))?;
+    let subkey_w = wide(&subkey);
+    let mut key: HKEY = 0;
+    let status = unsafe {
+        RegCreateKeyExW(
+            root,
+            subkey_w.as_ptr(),
+            0,
+            std::ptr::null(),
+            0,
+            KEY_SET_VALUE,
+            std::ptr::null(),
+            &mut key,
+            std::ptr::null_mut(),
+        )
+    };
+    if status != 0 {
+        return Err(if item.source.needs_admin() {
+            tr("需要管理员权限").to_string()
+        } else {
+            trf("无法打开注册表 (错误 {})", &[&status])
+        });
+    }
+    let key = RegKey(key);
+
+    // 12 字节：状态 (u32) + 禁用时间 (FILETIME)
+    let mut data = [0u8; 12];
+    if enabled {
+        data[0] = 0x02;
+    } else {
+        data[0] = 0x03;
+        let ft = unsafe {
+            let mut ft = std::mem::zeroed();
+            GetSystemTimeAsFileTime(&mut ft);
+            ft
+        };
+        data[4..8].copy_from_slice(&ft.dwLowDateTime.to_le_bytes());
+        data[8..12].copy_from_slice(&ft.dwHighDateTime.to_le_bytes());
+    }
+    let name = wide(&item.name);
+    let status = unsafe { RegSetValueExW(key.0, name.as_ptr(), 0, REG_BINARY, data.as_ptr(), data.len() as u32) };
+    if status != 0 {
+        return Err(trf("写入失败 (错误 {})", &[&status]));
+    }
+    crate::audit::record(
+        "启动项",
+        &format!("{} {} [{}]", if enabled { "启用" } else { "禁用" }, item.name, item.source.label()),
+    );
+    Ok(())
+}
+
+/// 计划任务的启用 / 禁用命令
+pub fn task_toggle_spec(item: &StartupItem, enabled: bool) -> ToolSpec {
+    ToolSpec::new(
+        tr(if enabled { "启用计划任务" } else { "禁用计划任务" }),
+        "schtasks.exe",
+        &["/change", "/tn", &item.name, if enabled { "/enable" } else { "/disable" }],
+    )
+}
+
+// ═══════════════════════════════════════════════════════════════
+//  自启来源反查 - 给定进程，找出让它开机启动的条目
+// ═══════════════════════════════════════════════════════════════
+
+/// 正在运行且为“自动”启动类型的服务
+#[derive(Clone, Debug)]
+pub struct AutostartService {
+    pub name: String,
+    pub display_name: String,
+    pub pid: u32,
+}
+
+unsafe fn pwstr_to_string(p: *const u16) -> String {
+    if p.is_null() {
+        return String::new();
+    }
+    let len = (0..).take_while(|&i| *p.add(i) != 0).count();
+    String::from_utf16_lossy(std::slice::from_raw_parts(p, len))
+}
+
+/// 服务的启动类型；配置超出缓冲区时按返回的所需大小重试
+unsafe fn start_type(scm: isize, name: *const u16) -> Option<u32> {
+    let svc = OpenServiceW(scm, name, SERVICE_QUERY_CONFIG);
+    if svc == 0 {
+        return None;
+    }
+    let mut buf = vec![0u64; 1024];
+    let mut needed = 0u32;
+    let mut ok = QueryServiceConfigW(svc, buf.as_mut_ptr() as *mut QUERY_SERVICE_CONFIGW, (buf.len() * 8) as u32, &mut needed) != 0;
+    if !ok && needed as usize > buf.len() * 8 {
+        buf.resize(needed as usize / 8 + 1, 0);
+        ok = QueryServiceConfigW(svc, buf.as_mut_ptr() as *mut QUERY_SERVICE_CONFIGW, (buf.len() * 8) as u32, &mut needed) != 0;
+    }
+    CloseServiceHandle(svc);
+    ok.then(|| (*(buf.as_ptr() as *const QUERY_SERVICE_CONFIGW)).dwStartType)
+}
+
+/// 枚举运行中的自动启动服务 (按 PID 与进程关联，svchost 也能区分)
+/// 服务多时一次调用返回不完，ERROR_MORE_DATA 时按 resume 句柄继续取下一批
+pub fn auto_services() -> Vec<AutostartService> {
+    let mut out = Vec::new();
+    unsafe {
+        let scm = OpenSCManagerW(std::ptr::null(), std::ptr::null(), SC_MANAGER_ENUMERATE_SERVICE);
+        if scm == 0 {
+            return out;
+        }
+        // 用 u64 缓冲保证 ENUM_SERVICE_STATUS_PROCESSW 的指针对齐
+        let mut buf = vec![0u64; 32 * 1024];
+        let mut resume = 0u32;
+        loop {
+            let mut needed = 0u32;
+            let mut returned = 0u32;
+            let ok = EnumServicesStatusExW(
+                scm,
+                SC_ENUM_PROCESS_INFO,
+                SERVICE_WIN32,
+                SERVICE_ACTIVE,
+                buf.as_mut_ptr() as *mut u8,
+                (buf.len() * 8) as u32,
+                &mut needed,
+                &mut returned,
+                &mut resume,
+                std::ptr::null(),
+            ) != 0;
+            if !ok && GetLastError() != ERROR_MORE_DATA {
+                break;
+            }
+            let entries = std::slice::from_raw_parts(
+                buf.as_ptr() as *const ENUM_SERVICE_STATUS_PROCESSW,
+                returned as usize,
+            );
+            for e in entries {
+                if start_type(scm, e.lpServiceName) == Some(SERVICE_AUTO_START) {
+                    out.push(AutostartService {
+                        name: pwstr_to_string(e.lpServiceName),
+                        display_name: pwstr_to_string(e.lpDisplayName),
+                        pid: e.ServiceStatusProcess.dwProcessId,
+                    });
+                }
+            }
+            if ok {
+                break;
+            }
+            // 缓冲区连一项都放不下时按所需大小扩大，否则从 resume 处继续
+            if returned == 0 {
+                buf.resize(needed as usize / 8 + 1, 0);
+            }
+        }
+        CloseServiceHandle(scm);
+    }
+    out
+}
+
+/// 一个进程的自启来源
+#[derive(Clone, Debug)]
+pub enum Origin {
+    /// 启动项管理面板中的条目 (下标对应 enumerate() 的结果)
+    Item(usize),
+    Service { name: String, display_name: String },
+}
+
+/// 按可执行文件名匹配启动项，按 PID 匹配自动启动服务
+pub fn origins_for(
+    exe: &str,
+    pids: &[u32],
+    items: &[StartupItem],
+    services: &[AutostartService],
+) -> Vec<Origin> {
+    let exe = exe.to_lowercase();
+    let mut out: Vec<Origin> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.exe_name().as_deref() == Some(exe.as_str()))
+        .map(|(i, _)| Origin::Item(i))
+        .collect();
+    out.extend(services.iter().filter(|s| pids.contains(&s.pid)).map(|s| Origin::Service {
+        name: s.name.clone(),
+        display_name: s.display_name.clone(),
+    }));
+    out
+}
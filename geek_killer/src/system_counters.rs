@@ -0,0 +1,47 @@
+// ═══════════════════════════════════════════════════════════════
+//  系统计数 - 标题栏下方的进程 / 线程 / 句柄总数与开机时长
+// ═══════════════════════════════════════════════════════════════
+//
+// 与任务管理器“性能 -> CPU”页底部的数字同源：GetPerformanceInfo 一次调用
+// 返回三项总数；开机时长取 GetTickCount64 (含睡眠时间)。
+
+use crate::i18n::trf;
+use std::time::Duration;
+use windows_sys::Win32::System::ProcessStatus::{GetPerformanceInfo, PERFORMANCE_INFORMATION};
+use windows_sys::Win32::System::SystemInformation::GetTickCount64;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Counters {
+    pub processes: u32,
+    pub threads: u32,
+    pub handles: u32,
+    pub uptime: Duration,
+}
+
+pub fn query() -> Option<Counters> {
+    unsafe {
+        let mut info: PERFORMANCE_INFORMATION = std::mem::zeroed();
+        let size = std::mem::size_of::<PERFORMANCE_INFORMATION>() as u32;
+        info.cb = size;
+        if GetPerformanceInfo(&mut info, size) == 0 {
+            return None;
+        }
+        Some(Counters {
+            processes: info.ProcessCount,
+            threads: info.ThreadCount,
+            handles: info.HandleCount,
+            uptime: Duration::from_millis(GetTickCount64()),
+        })
+    }
+}
+
+/// "3 天 04:12:05"，不足一天省略天数
+pub fn format_uptime(d: Duration) -> String {
+    let secs = d.as_secs();
+    let (days, h, m, s) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        trf("{} 天 {}:{}:{}", &[&days, &format!("{:02}", h), &format!("{:02}", m), &format!("{:02}", s)])
+    } else {
+        format!("{:02}:{:02}:{:02}", h, m, s)
+    }
+}
@@ -0,0 +1,216 @@
+// ═══════════════════════════════════════════════════════════════
+//  系统修复 - DISM RestoreHealth + sfc /scannow 引导流程
+// ═══════════════════════════════════════════════════════════════
+//
+// 顺序按微软建议：先用 DISM 修好组件存储，再让 sfc 以它为源修复系统文件。
+// 两步都经由 tool_runner 在控制台中运行，这里只负责进度解析与结果归纳。
+
+use crate::i18n::{tr, trf};
+use crate::tool_runner::{ConsoleLine, ToolSpec};
+use eframe::egui;
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RepairStep {
+    Dism,
+    Sfc,
+}
+
+impl RepairStep {
+    pub const ORDER: [RepairStep; 2] = [RepairStep::Dism, RepairStep::Sfc];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RepairStep::Dism => tr("DISM 组件存储修复"),
+            RepairStep::Sfc => tr("SFC 系统文件检查"),
+        }
+    }
+
+    pub fn spec(&self) -> ToolSpec {
+        match self {
+            RepairStep::Dism => ToolSpec::new(
+                self.label(),
+                "DISM.exe",
+                &["/Online", "/Cleanup-Image", "/RestoreHealth"],
+            ),
+            RepairStep::Sfc => ToolSpec::new(self.label(), "sfc.exe", &["/scannow"]),
+        }
+    }
+}
+
+/// 单步结果的严重程度
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Healthy,  // 没发现问题
+    Repaired, // 发现并修复
+    Failed,   // 有无法修复的损坏 / 工具报错
+    Unknown,  // 输出无法识别 (多见于非中英文系统)
+}
+
+impl Verdict {
+    pub fn color(&self) -> egui::Color32 {
+        match self {
+            Verdict::Healthy => egui::Color32::GREEN,
+            Verdict::Repaired => egui::Color32::from_rgb(0, 206, 209),
+            Verdict::Failed => egui::Color32::from_rgb(255, 80, 80),
+            Verdict::Unknown => egui::Color32::GOLD,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StepResult {
+    pub step: RepairStep,
+    pub verdict: Verdict,
+    pub summary: String,
+}
+
+/// 从一行输出中解析百分比进度
+///   DISM: "[=====   10.0%   ]"
+///   sfc : "验证 45% 已完成。" / "Verification 45% complete."
+pub fn parse_progress(line: &str) -> Option<f32> {
+    let pct = line.find('%')?;
+    let head = &line[..pct];
+    let start = head
+        .rfind(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .map(|i| i + head[i..].chars().next().map(|c| c.len_utf8()).unwrap_or(1))
+        .unwrap_or(0);
+    let value: f32 = head[start..].parse().ok()?;
+    (0.0..=100.0).contains(&value).then_some(value / 100.0)
+}
+
+fn contains_any(text: &str, needles: &[&str]) -> bool {
+    needles.iter().any(|n| text.contains(n))
+}
+
+/// 提取 DISM 输出中的错误码，例如 "Error: 0x800f081f" / "错误: 0x800f081f"
+fn dism_error_code(text: &str) -> Option<String> {
+    let idx = text.find("0x")?;
+    let code: String = text[idx..]
+        .chars()
+        .take_while(|c| c.is_ascii_hexdigit() || *c == 'x')
+        .collect();
+    (code.len() > 2).then_some(code)
+}
+
+/// 根据输出与退出码归纳单步结果
+pub fn summarize(step: RepairStep, lines: &[ConsoleLine], exit: &Result<i32, String>) -> StepResult {
+    let text = lines
+        .iter()
+        .map(|l| l.text.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let (verdict, summary) = match exit {
+        Err(e) => (Verdict::Failed, e.clone()),
+        Ok(code) => match step {
+            RepairStep::Dism => {
+                if contains_any(&text, &["0x800f081f", "0x800f0906", "0x800f0907"]) {
+                    (
+                        Verdict::Failed,
+                        tr("找不到修复源文件，请联网或挂载同版本 ISO 后重试 (/Source)").to_string(),
+                    )
+                } else if contains_any(&text, &["还原操作已成功完成", "restore operation completed successfully"]) {
+                    if contains_any(&text, &["组件存储损坏已修复", "component store corruption was repaired"]) {
+                        (Verdict::Repaired, tr("组件存储的损坏已修复").to_string())
+                    } else {
+                        (Verdict::Healthy, tr("组件存储健康").to_string())
+                    }
+                } else if *code == 0 {
+                    (Verdict::Healthy, tr("DISM 已完成").to_string())
+                } else {
+                    let detail = dism_error_code(&text).unwrap_or_else(|| trf("退出码 {}", &[&code]));
+                    (Verdict::Failed, trf("DISM 失败 ({})", &[&detail]))
+                }
+            }
+            RepairStep::Sfc => {
+                if contains_any(&text, &["未找到任何完整性冲突", "did not find any integrity violations"]) {
+                    (Verdict::Healthy, tr("未发现系统文件损坏").to_string())
+                } else if contains_any(&text, &["成功修复了它们", "successfully repaired them"]) {
+                    (Verdict::Repaired, tr("发现损坏文件并已全部修复，建议重启").to_string())
+                } else if contains_any(&text, &["其中某些文件无法修复", "unable to fix some of them"]) {
+                    (
+                        Verdict::Failed,
+                        tr("部分文件无法修复，详见 %WINDIR%\\Logs\\CBS\\CBS.log").to_string(),
+                    )
+                } else if contains_any(&text, &["无法执行请求的操作", "could not perform the requested operation"]) {
+                    (Verdict::Failed, tr("sfc 无法执行，可尝试在安全模式下运行").to_string())
+                } else if contains_any(&text, &["挂起的系统修复", "pending system repair"]) {
+                    (Verdict::Failed, tr("有挂起的系统修复，请先重启").to_string())
+                } else {
+                    (Verdict::Unknown, trf("sfc 已结束 (退出码 {})，未能识别结果", &[&code]))
+                }
+            }
+        },
+    };
+    StepResult {
+        step,
+        verdict,
+        summary,
+    }
+}
+
+/// 一次引导式修复：依次执行各步骤并收集结果
+pub struct RepairSession {
+    pending: VecDeque<RepairStep>,
+    pub current: Option<RepairStep>,
+    pub progress: f32,
+    pub results: Vec<StepResult>,
+}
+
+impl Default for RepairSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RepairSession {
+    pub fn new() -> Self {
+        Self {
+            pending: RepairStep::ORDER.into_iter().collect(),
+            current: None,
+            progress: 0.0,
+            results: vec![],
+        }
+    }
+
+    /// 确认对话框中展示的全部命令行
+    pub fn command_lines() -> Vec<String> {
+        RepairStep::ORDER.iter().map(|s| s.spec().command_line()).collect()
+    }
+
+    /// 取出下一步 (None 表示全部完成)
+    pub fn next_step(&mut self) -> Option<ToolSpec> {
+        self.current = self.pending.pop_front();
+        self.progress = 0.0;
+        self.current.map(|s| s.spec())
+    }
+
+    /// 当前步骤结束；被取消或无法启动时放弃后续步骤，返回非零退出码仍继续执行后续步骤
+    pub fn finish_step(&mut self, lines: &[ConsoleLine], exit: &Result<i32, String>) {
+        if let Some(step) = self.current.take() {
+            let result = summarize(step, lines, exit);
+            if exit.is_err() {
+                // 被取消或无法启动：后面的步骤没有意义
+                self.pending.clear();
+            }
+            self.results.push(result);
+        }
+    }
+
+    pub fn update_progress(&mut self, lines: &[ConsoleLine]) {
+        if let Some(p) = lines.iter().rev().take(5).find_map(|l| parse_progress(&l.text)) {
+            self.progress = p;
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.current.is_none() && self.pending.is_empty()
+    }
+
+    /// 总体进度 (已完成步骤 + 当前步骤进度)
+    pub fn overall_progress(&self) -> f32 {
+        let total = RepairStep::ORDER.len() as f32;
+        (self.results.len() as f32 + if self.current.is_some() { self.progress } else { 0.0 }) / total
+    }
+}
@@ -0,0 +1,78 @@
+// ═══════════════════════════════════════════════════════════════
+//  表格导出 - 选中行复制为对齐的纯文本或 Markdown 表格
+// ═══════════════════════════════════════════════════════════════
+//
+// 纯文本按显示宽度补空格对齐 (中文等宽字符按 2 列计)，适合粘贴到聊天窗口的等宽代码块；
+// Markdown 表格适合工单与文档，单元格中的 | 与换行会被转义。
+// 数值列 (右对齐) 由调用方通过 numeric 标出。
+
+/// 终端 / 等宽字体下的显示宽度：CJK 与全角字符占 2 列
+fn display_width(s: &str) -> usize {
+    s.chars()
+        .map(|c| match c as u32 {
+            0x1100..=0x115F | 0x2E80..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFE30..=0xFE4F | 0xFF00..=0xFF60
+            | 0xFFE0..=0xFFE6 => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+fn pad(s: &str, width: usize, right: bool) -> String {
+    let fill = " ".repeat(width.saturating_sub(display_width(s)));
+    if right {
+        format!("{}{}", fill, s)
+    } else {
+        format!("{}{}", s, fill)
+    }
+}
+
+fn plain_line(cells: &[&str], widths: &[usize], numeric: &[bool]) -> String {
+    cells
+        .iter()
+        .enumerate()
+        .map(|(i, c)| pad(c, widths.get(i).copied().unwrap_or(0), numeric.get(i).copied().unwrap_or(false)))
+        .collect::<Vec<_>>()
+        .join("  ")
+        .trim_end()
+        .to_string()
+}
+
+/// 列对齐的纯文本表格，表头下加一行分隔线
+pub fn plain(headers: &[&str], numeric: &[bool], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| {
+            rows.iter()
+                .map(|r| r.get(i).map(|c| display_width(c)).unwrap_or(0))
+                .chain(std::iter::once(display_width(headers[i])))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let mut out = vec![plain_line(headers, &widths, numeric)];
+    out.push(widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>().join("  "));
+    for r in rows {
+        let cells: Vec<&str> = r.iter().map(|c| c.as_str()).collect();
+        out.push(plain_line(&cells, &widths, numeric));
+    }
+    out.join("\r\n")
+}
+
+fn escape_md(s: &str) -> String {
+    s.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// GitHub 风格 Markdown 表格，数值列右对齐
+pub fn markdown(headers: &[&str], numeric: &[bool], rows: &[Vec<String>]) -> String {
+    let mut out = vec![format!("| {} |", headers.iter().map(|h| escape_md(h)).collect::<Vec<_>>().join(" | "))];
+    out.push(format!(
+        "|{}|",
+        (0..headers.len())
+            .map(|i| if numeric.get(i).copied().unwrap_or(false) { " ---: " } else { " --- " })
+            .collect::<Vec<_>>()
+            .join("|")
+    ));
+    for r in rows {
+        out.push(format!("| {} |", r.iter().map(|c| escape_md(c)).collect::<Vec<_>>().join(" | ")));
+    }
+    out.join("\r\n")
+}
@@ -0,0 +1,154 @@
+// ═══════════════════════════════════════════════════════════════
+//  清理临时文件 - 跳过正在使用的文件并说明占用者
+// ═══════════════════════════════════════════════════════════════
+//
+// 清理 %TEMP% 与 Windows\Temp。逐个删除文件：
+//   - 共享冲突 / 锁冲突：文件正在使用，跳过，并用 Restart Manager 查出占用进程
+//   - 拒绝访问：无权限 (Windows\Temp 需要管理员)，跳过
+//   - 最近 1 小时内修改的文件视为安装程序等仍可能用到，不删除
+// 不会在中途因某个文件失败而中止；最后删除清空的子目录并汇总释放的空间。
+
+use crate::i18n::trf;
+use crate::service_control::Progress;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+pub const TITLE: &str = "清理临时文件";
+
+/// 修改时间在此之内的文件不删除
+const RECENT: Duration = Duration::from_secs(3600);
+/// 最多为多少个被占用的文件查询占用进程 (每个文件一次 RM 会话)
+const MAX_HOLDER_LOOKUPS: usize = 50;
+
+const ERROR_SHARING_VIOLATION: i32 = 32;
+const ERROR_LOCK_VIOLATION: i32 = 33;
+
+fn temp_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![std::env::temp_dir()];
+    if let Ok(windir) = std::env::var("WINDIR") {
+        let sys_temp = PathBuf::from(windir).join("Temp");
+        if !dirs.iter().any(|d| d.to_string_lossy().eq_ignore_ascii_case(&sys_temp.to_string_lossy())) {
+            dirs.push(sys_temp);
+        }
+    }
+    dirs
+}
+
+fn mb(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / 1024.0 / 1024.0)
+}
+
+#[derive(Default)]
+struct Tally {
+    removed: usize,
+    freed: u64,
+    recent: usize,
+    denied: usize,
+    in_use: Vec<PathBuf>,
+    /// 子目录，按深度由浅到深
+    dirs: Vec<PathBuf>,
+}
+
+fn clean_dir(dir: &Path, tally: &mut Tally) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let now = SystemTime::now();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        // 不跟随目录联接 / 符号链接，避免删到临时目录以外
+        if meta.file_type().is_symlink() {
+            continue;
+        }
+        if meta.is_dir() {
+            tally.dirs.push(path.clone());
+            clean_dir(&path, tally);
+            continue;
+        }
+        let recent = meta
+            .modified()
+            .ok()
+            .and_then(|m| now.duration_since(m).ok())
+            .map(|age| age < RECENT)
+            .unwrap_or(false);
+        if recent {
+            tally.recent += 1;
+            continue;
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                tally.removed += 1;
+                tally.freed += meta.len();
+            }
+            Err(e) => match e.raw_os_error() {
+                Some(ERROR_SHARING_VIOLATION) | Some(ERROR_LOCK_VIOLATION) => tally.in_use.push(path),
+                // 其余多为拒绝访问
+                _ => tally.denied += 1,
+            },
+        }
+    }
+}
+
+/// 占用进程 -> 被占用的文件数
+fn holders(in_use: &[PathBuf]) -> BTreeMap<String, usize> {
+    let mut out = BTreeMap::new();
+    for path in in_use.iter().take(MAX_HOLDER_LOOKUPS) {
+        let Ok(occupants) = crate::rm::file_holders(&[path.to_string_lossy().to_string()]) else {
+            continue;
+        };
+        for o in occupants {
+            *out.entry(format!("{} (PID {})", o.name, o.pid)).or_insert(0) += 1;
+        }
+    }
+    out
+}
+
+pub fn run(report: &dyn Fn(Progress)) -> Result<String, String> {
+    let mut removed = 0;
+    let mut freed = 0u64;
+    let mut in_use: Vec<PathBuf> = Vec::new();
+
+    for dir in temp_dirs() {
+        if !dir.is_dir() {
+            continue;
+        }
+        let mut tally = Tally::default();
+        clean_dir(&dir, &mut tally);
+        // 由深到浅删除已清空的子目录，非空的删除失败即保留
+        for sub in tally.dirs.iter().rev() {
+            let _ = std::fs::remove_dir(sub);
+        }
+        let mut text = trf("{}：删除 {} 个文件，释放 {}", &[&dir.display(), &tally.removed, &mb(tally.freed)]);
+        if !tally.in_use.is_empty() {
+            text.push_str(&trf("；{} 个正在使用", &[&tally.in_use.len()]));
+        }
+        if tally.recent > 0 {
+            text.push_str(&trf("；{} 个最近修改，保留", &[&tally.recent]));
+        }
+        if tally.denied > 0 {
+            text.push_str(&trf("；{} 个无权限", &[&tally.denied]));
+        }
+        report(Progress::Step(true, text));
+        removed += tally.removed;
+        freed += tally.freed;
+        in_use.extend(tally.in_use);
+    }
+
+    if !in_use.is_empty() {
+        for (holder, count) in holders(&in_use) {
+            report(Progress::Step(false, trf("已跳过 {} 个被 {} 占用的文件", &[&count, &holder])));
+        }
+        if in_use.len() > MAX_HOLDER_LOOKUPS {
+            report(Progress::Step(
+                false,
+                trf("另有 {} 个被占用的文件未查询占用进程", &[&(in_use.len() - MAX_HOLDER_LOOKUPS)]),
+            ));
+        }
+    }
+
+    Ok(trf("共释放 {}，删除 {} 个文件，跳过 {} 个正在使用的文件", &[&mb(freed), &removed, &in_use.len()]))
+}
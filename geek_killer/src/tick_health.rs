@@ -0,0 +1,162 @@
+// ═══════════════════════════════════════════════════════════════
+//  采集耗时 - 每轮监控与每帧界面的时间花在哪里
+// ═══════════════════════════════════════════════════════════════
+//
+// 后台线程每轮记录各阶段耗时 (TickTimings) 随快照送到界面；界面侧再加上
+// 每帧绘制与快照深拷贝的耗时，各自取最近若干次的滚动平均与最大值。
+// 慢机器上用户可据此报告瓶颈；采集耗时占满刷新间隔或帧耗时过长时
+// 建议切换到“降低刷新频率” (刷新间隔加倍，默认 500ms 一轮 -> 1000ms)。
+// 刷新间隔与“资源紧张”的判定阈值可在设置窗口调整，经 Tuning 与监控线程共享。
+
+use crate::i18n::{tr, trf};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// 默认刷新间隔
+pub const NORMAL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 监控线程的可调参数
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tuning {
+    /// 正常刷新间隔
+    pub interval: Duration,
+    /// 降低刷新频率：间隔加倍
+    pub reduced: bool,
+    /// 全局 CPU 超过此值 (%) 或可用内存低于 tight_free_bytes 时视为资源紧张
+    pub tight_cpu: f32,
+    pub tight_free_bytes: u64,
+    /// 进程表显示 GPU 列时才采样各进程的 GPU 占用
+    pub sample_gpu: bool,
+    /// 记录前台应用的使用时长
+    pub track_foreground: bool,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self {
+            interval: NORMAL_INTERVAL,
+            reduced: false,
+            tight_cpu: 90.0,
+            tight_free_bytes: 500 * 1024 * 1024,
+            sample_gpu: false,
+            track_foreground: false,
+        }
+    }
+}
+
+impl Tuning {
+    /// 本轮使用的刷新间隔
+    pub fn effective_interval(&self) -> Duration {
+        if self.reduced {
+            self.interval * 2
+        } else {
+            self.interval
+        }
+    }
+}
+
+/// 采集耗时超过刷新间隔的这个比例，就建议降频
+const BUSY_RATIO: f32 = 0.5;
+/// 帧耗时超过约 30fps 的预算
+const SLOW_FRAME_MS: f32 = 33.0;
+/// 滚动统计保留的样本数
+const WINDOW: usize = 30;
+
+/// 后台监控一轮的分阶段耗时
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TickTimings {
+    /// CPU / 内存 / 进程列表刷新
+    pub refresh: Duration,
+    /// 进程分组、排序与分类
+    pub classify: Duration,
+    /// 整轮 (含磁盘、网络、内存诊断)
+    pub total: Duration,
+    /// 本轮使用的目标刷新间隔
+    pub interval: Duration,
+}
+
+/// 最近 WINDOW 个样本 (毫秒)
+#[derive(Default)]
+pub struct Rolling {
+    samples: VecDeque<f32>,
+}
+
+impl Rolling {
+    pub fn push(&mut self, d: Duration) {
+        if self.samples.len() == WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(d.as_secs_f32() * 1000.0);
+    }
+
+    pub fn avg(&self) -> f32 {
+        if self.samples.is_empty() {
+            0.0
+        } else {
+            self.samples.iter().sum::<f32>() / self.samples.len() as f32
+        }
+    }
+
+    pub fn max(&self) -> f32 {
+        self.samples.iter().copied().fold(0.0, f32::max)
+    }
+}
+
+#[derive(Default)]
+pub struct TickHealth {
+    pub refresh: Rolling,
+    pub classify: Rolling,
+    pub total: Rolling,
+    pub frame: Rolling,
+    pub clone: Rolling,
+    /// 最近一轮的目标间隔
+    pub interval: Duration,
+}
+
+impl TickHealth {
+    /// 收到新一轮快照时调用
+    pub fn record_tick(&mut self, t: &TickTimings) {
+        self.refresh.push(t.refresh);
+        self.classify.push(t.classify);
+        self.total.push(t.total);
+        self.interval = t.interval;
+    }
+
+    /// 降频建议；已降频或数据不足时为 None
+    pub fn suggestion(&self, reduced: bool) -> Option<String> {
+        if reduced || self.total.samples.len() < WINDOW / 2 {
+            return None;
+        }
+        let budget = self.interval.as_secs_f32() * 1000.0;
+        if budget > 0.0 && self.total.avg() > budget * BUSY_RATIO {
+            return Some(trf(
+                "每轮采集平均 {} ms，占刷新间隔 {} ms 的 {}%，建议降低刷新频率",
+                &[
+                    &format!("{:.0}", self.total.avg()),
+                    &format!("{:.0}", budget),
+                    &format!("{:.0}", self.total.avg() / budget * 100.0),
+                ],
+            ));
+        }
+        if self.frame.avg() > SLOW_FRAME_MS {
+            return Some(trf("界面每帧平均 {} ms，建议降低刷新频率并收起不用的面板", &[&format!("{:.0}", self.frame.avg())]));
+        }
+        None
+    }
+
+    /// 纯文本报告，便于贴到反馈里
+    pub fn report(&self) -> String {
+        let rows = [
+            (tr("进程刷新"), &self.refresh),
+            (tr("分组分类"), &self.classify),
+            (tr("整轮采集"), &self.total),
+            (tr("界面帧"), &self.frame),
+            (tr("快照拷贝"), &self.clone),
+        ];
+        let mut out = trf("刷新间隔 {} ms\r\n", &[&self.interval.as_millis()]);
+        for (name, r) in rows {
+            out.push_str(&trf("{}: 平均 {} ms, 最大 {} ms\r\n", &[&name, &format!("{:.1}", r.avg()), &format!("{:.1}", r.max())]));
+        }
+        out
+    }
+}
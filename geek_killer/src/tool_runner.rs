@@ -0,0 +1,359 @@
+// ═══════════════════════════════════════════════════════════════
+//  外部工具运行器 - 实时输出 / 取消 / 审计
+// ═══════════════════════════════════════════════════════════════
+//
+// fsutil / chkdsk / sfc / DISM 等系统工具统一从这里启动：
+//   - stdout/stderr 由后台线程逐行读取，推送到控制台面板
+//   - 自动识别 UTF-8 / UTF-16LE (sfc) / OEM 代码页 (GBK) 输出
+//   - 剥离 ANSI 转义序列，保留前景色
+//   - 每次启动与退出都写入审计日志
+
+use crate::i18n::{tr, trf};
+use eframe::egui;
+use std::io::Read;
+use std::os::windows::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc, Mutex};
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// 要运行的外部工具
+#[derive(Clone, Debug)]
+pub struct ToolSpec {
+    pub title: String,
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl ToolSpec {
+    pub fn new(title: &str, program: &str, args: &[&str]) -> Self {
+        Self {
+            title: title.to_string(),
+            program: program.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    /// 展示给用户、写入审计日志的完整命令行
+    pub fn command_line(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(|a| a.as_str()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// 未启动的命令 (不弹出控制台窗口)，需要自行接管 stdin 时使用
+    pub fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.args(&self.args).creation_flags(CREATE_NO_WINDOW);
+        cmd
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ConsoleLine {
+    pub text: String,
+    pub color: Option<egui::Color32>,
+    pub is_stderr: bool,
+}
+
+enum ToolEvent {
+    Line(ConsoleLine),
+    Exited(Option<i32>),
+}
+
+/// 一次正在运行 (或已结束) 的工具
+pub struct ToolRun {
+    pub spec: ToolSpec,
+    pub lines: Vec<ConsoleLine>,
+    /// None: 运行中；Some(Ok(code)) 正常退出；Some(Err) 启动失败或被取消
+    pub exit: Option<Result<i32, String>>,
+    rx: mpsc::Receiver<ToolEvent>,
+    pid: Option<u32>,
+    cancelled: Arc<Mutex<bool>>,
+}
+
+impl ToolRun {
+    pub fn start(spec: ToolSpec, ctx: egui::Context) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let cancelled = Arc::new(Mutex::new(false));
+        crate::audit::record("外部命令", &format!("启动 {}", spec.command_line()));
+
+        let mut child = match spec
+            .command()
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+        {
+            Ok(c) => c,
+            Err(e) => {
+                crate::audit::record("外部命令", &format!("{} -> 启动失败: {}", spec.command_line(), e));
+                return Self {
+                    spec,
+                    lines: vec![],
+                    exit: Some(Err(trf("无法启动：{}", &[&e]))),
+                    rx,
+                    pid: None,
+                    cancelled,
+                };
+            }
+        };
+
+        let pid = child.id();
+        let readers: Vec<_> = [
+            child.stdout.take().map(|s| Box::new(s) as Box<dyn Read + Send>),
+            child.stderr.take().map(|s| Box::new(s) as Box<dyn Read + Send>),
+        ]
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, r)| r.map(|r| (i == 1, r)))
+        .map(|(is_stderr, r)| {
+            let tx = tx.clone();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || stream_lines(r, is_stderr, tx, ctx))
+        })
+        .collect();
+
+        let line = spec.command_line();
+        std::thread::spawn(move || {
+            for r in readers {
+                let _ = r.join();
+            }
+            let code = child.wait().ok().and_then(|s| s.code());
+            crate::audit::record("外部命令", &format!("{} -> 退出码 {:?}", line, code));
+            let _ = tx.send(ToolEvent::Exited(code));
+            ctx.request_repaint();
+        });
+
+        Self {
+            spec,
+            lines: vec![],
+            exit: None,
+            rx,
+            pid: Some(pid),
+            cancelled,
+        }
+    }
+
+    /// 在 UI 线程每帧调用，收取新输出
+    pub fn poll(&mut self) {
+        while let Ok(ev) = self.rx.try_recv() {
+            match ev {
+                ToolEvent::Line(l) => self.lines.push(l),
+                ToolEvent::Exited(code) => {
+                    let cancelled = self.cancelled.lock().map(|c| *c).unwrap_or(false);
+                    self.exit = Some(if cancelled {
+                        Err(tr("已取消").to_string())
+                    } else {
+                        Ok(code.unwrap_or(-1))
+                    });
+                }
+            }
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.exit.is_none()
+    }
+
+    /// 终止工具及其子进程；重复调用 (例如轮询中每轮都检查取消标志) 只终止、记录一次
+    pub fn cancel(&self) {
+        if let Some(pid) = self.pid {
+            if let Ok(mut c) = self.cancelled.lock() {
+                if *c {
+                    return;
+                }
+                *c = true;
+            }
+            crate::process_tree::kill_tree(pid);
+            crate::audit::record("外部命令", &format!("取消 {}", self.spec.command_line()));
+        }
+    }
+}
+
+/// 同步运行并收集全部输出 (工作线程内使用)，返回 (是否成功, stdout, stderr)
+pub fn run_to_completion(spec: &ToolSpec) -> Result<(bool, String, String), String> {
+    let result = spec.command().output();
+    match &result {
+        Ok(o) => crate::audit::record(
+            "外部命令",
+            &format!("{} -> 退出码 {:?}", spec.command_line(), o.status.code()),
+        ),
+        Err(e) => crate::audit::record(
+            "外部命令",
+            &format!("{} -> 启动失败: {}", spec.command_line(), e),
+        ),
+    }
+    let o = result.map_err(|e| trf("无法启动 {}: {}", &[&spec.program, &e]))?;
+    Ok((
+        o.status.success(),
+        decode_console(&o.stdout),
+        decode_console(&o.stderr),
+    ))
+}
+
+fn looks_utf16(bytes: &[u8]) -> bool {
+    bytes.len() >= 2 && {
+        let odd_zeros = bytes.iter().skip(1).step_by(2).filter(|&&b| b == 0).count();
+        odd_zeros * 3 > bytes.len() / 2
+    }
+}
+
+/// 控制台输出解码：UTF-8 / UTF-16LE / OEM 代码页
+pub fn decode_console(bytes: &[u8]) -> String {
+    use windows_sys::Win32::Globalization::{MultiByteToWideChar, CP_OEMCP};
+
+    if bytes.is_empty() {
+        return String::new();
+    }
+    if looks_utf16(bytes) {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    if let Ok(s) = std::str::from_utf8(bytes) {
+        return s.to_string();
+    }
+    unsafe {
+        let len = MultiByteToWideChar(
+            CP_OEMCP,
+            0,
+            bytes.as_ptr(),
+            bytes.len() as i32,
+            std::ptr::null_mut(),
+            0,
+        );
+        if len <= 0 {
+            return String::from_utf8_lossy(bytes).to_string();
+        }
+        let mut wide = vec![0u16; len as usize];
+        MultiByteToWideChar(
+            CP_OEMCP,
+            0,
+            bytes.as_ptr(),
+            bytes.len() as i32,
+            wide.as_mut_ptr(),
+            len,
+        );
+        String::from_utf16_lossy(&wide)
+    }
+}
+
+fn ansi_color(code: u32) -> Option<egui::Color32> {
+    Some(match code {
+        30 | 90 => egui::Color32::GRAY,
+        31 | 91 => egui::Color32::from_rgb(255, 80, 80),
+        32 | 92 => egui::Color32::GREEN,
+        33 | 93 => egui::Color32::GOLD,
+        34 | 94 => egui::Color32::from_rgb(100, 180, 255),
+        35 | 95 => egui::Color32::from_rgb(220, 120, 255),
+        36 | 96 => egui::Color32::from_rgb(0, 206, 209),
+        37 | 97 => egui::Color32::WHITE,
+        _ => return None,
+    })
+}
+
+/// 剥离 ANSI 转义序列，返回纯文本与最后一次设置的前景色
+pub fn strip_ansi(line: &str) -> (String, Option<egui::Color32>) {
+    let mut out = String::with_capacity(line.len());
+    let mut color = None;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        if chars.peek() != Some(&'[') {
+            continue;
+        }
+        chars.next();
+        let mut params = String::new();
+        let mut final_byte = None;
+        for p in chars.by_ref() {
+            if ('@'..='~').contains(&p) {
+                final_byte = Some(p);
+                break;
+            }
+            params.push(p);
+        }
+        if final_byte == Some('m') {
+            for code in params.split(';').filter_map(|p| p.parse::<u32>().ok()) {
+                if code == 0 || code == 39 {
+                    color = None;
+                } else if let Some(c) = ansi_color(code) {
+                    color = Some(c);
+                }
+            }
+        }
+    }
+    (out, color)
+}
+
+/// 后台读取输出流：按 \n 和 \r 断行 (进度条常用 \r 原地刷新)
+fn stream_lines(
+    mut reader: Box<dyn Read + Send>,
+    is_stderr: bool,
+    tx: mpsc::Sender<ToolEvent>,
+    ctx: egui::Context,
+) {
+    let mut pending: Vec<u8> = Vec::new();
+    let mut utf16: Option<bool> = None;
+    let mut chunk = [0u8; 4096];
+
+    let emit = |raw: &[u8], wide: bool| {
+        // UTF-16 模式已在流开头确定，逐行再猜会把纯中文行误判
+        let text = if wide {
+            let units: Vec<u16> = raw
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        } else {
+            decode_console(raw)
+        };
+        let text = text.trim_end();
+        if text.is_empty() {
+            return;
+        }
+        let (text, color) = strip_ansi(text);
+        let _ = tx.send(ToolEvent::Line(ConsoleLine {
+            text,
+            color,
+            is_stderr,
+        }));
+        ctx.request_repaint();
+    };
+
+    loop {
+        let n = match reader.read(&mut chunk) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        pending.extend_from_slice(&chunk[..n]);
+        let wide = *utf16.get_or_insert_with(|| looks_utf16(&pending));
+        let unit = if wide { 2 } else { 1 };
+
+        // 在完整的编码单元边界上找换行
+        let mut start = 0;
+        let mut i = 0;
+        while i + unit <= pending.len() {
+            let is_break = if wide {
+                (pending[i] == b'\n' || pending[i] == b'\r') && pending[i + 1] == 0
+            } else {
+                pending[i] == b'\n' || pending[i] == b'\r'
+            };
+            if is_break {
+                emit(&pending[start..i], wide);
+                start = i + unit;
+            }
+            i += unit;
+        }
+        pending.drain(..start);
+    }
+    if !pending.is_empty() {
+        emit(&pending, utf16.unwrap_or(false));
+    }
+}
@@ -0,0 +1,391 @@
+// ═══════════════════════════════════════════════════════════════
+//  系统托盘 - 最小化到托盘，右键一键弹出 U 盘 / 终止 CPU 占用最高的进程 / 执行操作宏 / 迷你悬浮窗
+// ═══════════════════════════════════════════════════════════════
+//
+// 托盘图标由独立线程上的隐藏窗口承载 (Shell_NotifyIconW + 自己的消息循环)：
+//   - 主窗口隐藏后 eframe 不再调用 update()，托盘菜单的弹出 / 终止不能依赖 UI 线程，
+//     由调用方提供的回调直接把命令发给后台线程
+//   - 主窗口的显示 / 隐藏一律用 ShowWindow 完成，不经过 ViewportCommand，
+//     避免 winit 记录的可见状态与实际不一致
+//   - 资源管理器重启后会广播 TaskbarCreated，收到后重新添加图标
+// 弹出结果用气泡通知：轮询卷根目录是否消失，超时则提示回到窗口处理。
+
+use crate::i18n::{tr, trf};
+use crate::long_path::wide;
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicIsize, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, POINT, WPARAM};
+use windows_sys::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows_sys::Win32::System::Threading::GetCurrentProcessId;
+use windows_sys::Win32::UI::Shell::{
+    Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_MESSAGE, NIF_TIP, NIIF_INFO, NIIF_WARNING, NIM_ADD, NIM_DELETE,
+    NIM_MODIFY, NOTIFYICONDATAW,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    AppendMenuW, CreatePopupMenu, CreateWindowExW, DefWindowProcW, DestroyMenu, DispatchMessageW, FindWindowExW,
+    GetCursorPos, GetMessageW, GetWindowThreadProcessId, IsIconic, LoadIconW, PostMessageW, RegisterClassW,
+    RegisterWindowMessageW, SetForegroundWindow, ShowWindow, TrackPopupMenu, TranslateMessage, IDI_APPLICATION,
+    MF_CHECKED, MF_GRAYED, MF_SEPARATOR, MF_STRING, SW_HIDE, SW_RESTORE, SW_SHOW, TPM_RETURNCMD, TPM_RIGHTBUTTON, WM_APP,
+    WM_CONTEXTMENU, WM_LBUTTONUP, WM_NULL, WM_RBUTTONUP, WM_USER, WNDCLASSW,
+};
+
+const WM_TRAY: u32 = WM_APP + 1;
+/// 用户点击了气泡通知
+const NIN_BALLOONUSERCLICK: u32 = WM_USER + 5;
+const TRAY_ID: u32 = 1;
+
+const ID_SHOW: usize = 1;
+const ID_EXIT: usize = 2;
+const ID_KILL_TOP: usize = 3;
+const ID_HUD: usize = 4;
+const ID_EJECT_BASE: usize = 100;
+const ID_MACRO_BASE: usize = 200;
+
+/// 弹出后等待卷消失的时长
+const EJECT_WATCH: Duration = Duration::from_secs(15);
+
+pub struct TrayDrive {
+    pub drive: String,
+    pub label: String,
+}
+
+pub struct TopProcess {
+    pub name: String,
+    pub cpu: f32,
+    pub pids: Vec<u32>,
+}
+
+/// 右键菜单的内容，每次弹出菜单时重新获取
+pub struct TrayMenu {
+    pub drives: Vec<TrayDrive>,
+    pub top_cpu: Option<TopProcess>,
+    /// 放进托盘的操作宏：(下标, 名称)
+    pub macros: Vec<(usize, String)>,
+    /// 迷你悬浮窗当前是否打开
+    pub hud: bool,
+}
+
+pub struct TrayHandlers {
+    pub menu: Box<dyn Fn() -> TrayMenu + Send>,
+    pub eject: Box<dyn Fn(&str) + Send>,
+    pub kill: Box<dyn Fn(&[u32]) + Send>,
+    /// 参数为宏的下标
+    pub run_macro: Box<dyn Fn(usize) + Send>,
+    /// 菜单中的“退出”：主窗口已重新显示，由 UI 线程完成关闭
+    pub quit: Box<dyn Fn() + Send>,
+    /// 打开 / 关闭迷你悬浮窗：主窗口已重新显示，由 UI 线程创建或关闭悬浮窗
+    pub toggle_hud: Box<dyn Fn() + Send>,
+}
+
+static TRAY_HWND: AtomicIsize = AtomicIsize::new(0);
+static MAIN_TITLE: OnceLock<String> = OnceLock::new();
+
+thread_local! {
+    static HANDLERS: RefCell<Option<TrayHandlers>> = const { RefCell::new(None) };
+    static TASKBAR_CREATED: Cell<u32> = const { Cell::new(0) };
+}
+
+/// 复制到定长缓冲区，超长截断并保证以 0 结尾
+fn copy_wide(dst: &mut [u16], s: &str) {
+    let src: Vec<u16> = s.encode_utf16().take(dst.len() - 1).collect();
+    dst[..src.len()].copy_from_slice(&src);
+    dst[src.len()] = 0;
+}
+
+fn notify_data(hwnd: HWND) -> NOTIFYICONDATAW {
+    let mut nid: NOTIFYICONDATAW = unsafe { std::mem::zeroed() };
+    nid.cbSize = std::mem::size_of::<NOTIFYICONDATAW>() as u32;
+    nid.hWnd = hwnd;
+    nid.uID = TRAY_ID;
+    nid
+}
+
+fn add_icon(hwnd: HWND) -> bool {
+    let mut nid = notify_data(hwnd);
+    nid.uFlags = NIF_MESSAGE | NIF_ICON | NIF_TIP;
+    nid.uCallbackMessage = WM_TRAY;
+    unsafe {
+        // winres 写入的程序图标资源 ID 为 1
+        nid.hIcon = LoadIconW(GetModuleHandleW(std::ptr::null()), 1 as _);
+        if nid.hIcon == 0 {
+            nid.hIcon = LoadIconW(0, IDI_APPLICATION);
+        }
+    }
+    copy_wide(&mut nid.szTip, MAIN_TITLE.get().map(|s| s.as_str()).unwrap_or("Geek Killer"));
+    unsafe { Shell_NotifyIconW(NIM_ADD, &nid) != 0 }
+}
+
+/// 在后台线程创建托盘图标；title 为主窗口标题，用于查找主窗口
+pub fn spawn(title: &str, handlers: TrayHandlers) -> Result<(), String> {
+    let _ = MAIN_TITLE.set(title.to_string());
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || unsafe {
+        let instance = GetModuleHandleW(std::ptr::null());
+        let class = wide("GeekKillerTray");
+        let wc = WNDCLASSW {
+            lpfnWndProc: Some(wndproc),
+            hInstance: instance,
+            lpszClassName: class.as_ptr(),
+            ..std::mem::zeroed()
+        };
+        RegisterClassW(&wc);
+        // 普通的隐藏顶层窗口 (不是 message-only)，才能收到 TaskbarCreated 广播
+        let hwnd = CreateWindowExW(
+            0,
+            class.as_ptr(),
+            class.as_ptr(),
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            instance,
+            std::ptr::null(),
+        );
+        if hwnd == 0 {
+            let _ = tx.send(Err(trf("无法创建托盘窗口：{}", &[&std::io::Error::last_os_error()])));
+            return;
+        }
+        TASKBAR_CREATED.with(|c| c.set(RegisterWindowMessageW(wide("TaskbarCreated").as_ptr())));
+        HANDLERS.with(|h| *h.borrow_mut() = Some(handlers));
+        TRAY_HWND.store(hwnd, Ordering::SeqCst);
+        if !add_icon(hwnd) {
+            let _ = tx.send(Err(tr("无法添加托盘图标").to_string()));
+            return;
+        }
+        let _ = tx.send(Ok(()));
+
+        let mut msg = std::mem::zeroed();
+        while GetMessageW(&mut msg, 0, 0, 0) > 0 {
+            TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    });
+    rx.recv().unwrap_or_else(|_| Err(tr("托盘线程异常退出").to_string()))
+}
+
+/// 托盘图标是否已创建
+pub fn is_active() -> bool {
+    TRAY_HWND.load(Ordering::SeqCst) != 0
+}
+
+/// 移除托盘图标 (程序退出前调用，否则图标要等鼠标划过才消失)
+pub fn remove() {
+    let hwnd = TRAY_HWND.swap(0, Ordering::SeqCst);
+    if hwnd != 0 {
+        let nid = notify_data(hwnd);
+        unsafe {
+            Shell_NotifyIconW(NIM_DELETE, &nid);
+        }
+    }
+}
+
+/// 气泡通知，可在任意线程调用
+pub fn balloon(title: &str, text: &str, warn: bool) {
+    let hwnd = TRAY_HWND.load(Ordering::SeqCst);
+    if hwnd == 0 {
+        return;
+    }
+    let mut nid = notify_data(hwnd);
+    nid.uFlags = NIF_INFO;
+    nid.dwInfoFlags = if warn { NIIF_WARNING } else { NIIF_INFO };
+    copy_wide(&mut nid.szInfoTitle, title);
+    copy_wide(&mut nid.szInfo, text);
+    unsafe {
+        Shell_NotifyIconW(NIM_MODIFY, &nid);
+    }
+}
+
+/// 本进程的主窗口 (按标题查找，排除其他实例)
+fn main_window() -> HWND {
+    let Some(title) = MAIN_TITLE.get() else {
+        return 0;
+    };
+    let title = wide(title);
+    let mut prev = 0;
+    unsafe {
+        loop {
+            let hwnd = FindWindowExW(0, prev, std::ptr::null(), title.as_ptr());
+            if hwnd == 0 {
+                return 0;
+            }
+            let mut pid = 0u32;
+            GetWindowThreadProcessId(hwnd, &mut pid);
+            if pid == GetCurrentProcessId() {
+                return hwnd;
+            }
+            prev = hwnd;
+        }
+    }
+}
+
+pub fn show_main_window() {
+    let hwnd = main_window();
+    if hwnd == 0 {
+        return;
+    }
+    unsafe {
+        ShowWindow(hwnd, SW_SHOW);
+        if IsIconic(hwnd) != 0 {
+            ShowWindow(hwnd, SW_RESTORE);
+        }
+        SetForegroundWindow(hwnd);
+    }
+}
+
+pub fn hide_main_window() {
+    let hwnd = main_window();
+    if hwnd != 0 {
+        unsafe {
+            ShowWindow(hwnd, SW_HIDE);
+        }
+    }
+}
+
+/// 等卷根目录消失后通知可以拔出，超时提示回到窗口处理
+pub fn watch_eject(drive: String, label: String) {
+    std::thread::spawn(move || {
+        let root = crate::mount_manager::volume_root(&drive);
+        let started = Instant::now();
+        while started.elapsed() < EJECT_WATCH {
+            std::thread::sleep(Duration::from_millis(500));
+            if !std::path::Path::new(&root).exists() {
+                balloon(tr("可以安全拔出"), &trf("{} 已弹出", &[&label]), false);
+                return;
+            }
+        }
+        balloon(tr("未能弹出"), &trf("{} 仍被占用，点击打开窗口处理", &[&label]), true);
+    });
+}
+
+/// 终止后确认进程是否真的退出
+fn watch_kill(name: String, pids: Vec<u32>) {
+    std::thread::spawn(move || {
+        let alive = pids
+            .iter()
+            .filter(|&&pid| !crate::graceful::wait_exit(pid, Duration::from_secs(2)))
+            .count();
+        if alive == 0 {
+            balloon(tr("已终止"), &name, false);
+        } else {
+            balloon(tr("终止失败"), &trf("{} 有 {} 个进程未退出，可能需要管理员权限", &[&name, &alive]), true);
+        }
+    });
+}
+
+unsafe fn append(menu: isize, id: usize, text: &str, enabled: bool) {
+    let flags = if enabled { MF_STRING } else { MF_STRING | MF_GRAYED };
+    AppendMenuW(menu, flags, id, wide(text).as_ptr());
+}
+
+unsafe fn show_menu(hwnd: HWND) {
+    // 菜单弹出期间会重入窗口过程，不能一直借用 HANDLERS
+    let Some(data) = HANDLERS.with(|h| h.borrow().as_ref().map(|h| (h.menu)())) else {
+        return;
+    };
+    let menu = CreatePopupMenu();
+    append(menu, ID_SHOW, tr("显示主窗口"), true);
+    let hud_flags = if data.hud { MF_STRING | MF_CHECKED } else { MF_STRING };
+    AppendMenuW(menu, hud_flags, ID_HUD, wide(tr("迷你悬浮窗")).as_ptr());
+    AppendMenuW(menu, MF_SEPARATOR, 0, std::ptr::null());
+    if data.drives.is_empty() {
+        append(menu, 0, tr("没有可移动磁盘"), false);
+    }
+    for (i, d) in data.drives.iter().enumerate() {
+        append(menu, ID_EJECT_BASE + i, &trf("弹出 {}", &[&d.label]), true);
+    }
+    AppendMenuW(menu, MF_SEPARATOR, 0, std::ptr::null());
+    match &data.top_cpu {
+        Some(p) => append(menu, ID_KILL_TOP, &trf("终止 CPU 占用最高：{} ({}%)", &[&p.name, &format!("{:.0}", p.cpu)]), true),
+        None => append(menu, 0, tr("没有明显占用 CPU 的进程"), false),
+    }
+    if !data.macros.is_empty() {
+        AppendMenuW(menu, MF_SEPARATOR, 0, std::ptr::null());
+        for (i, (_, name)) in data.macros.iter().enumerate() {
+            append(menu, ID_MACRO_BASE + i, &format!("▶ {}", name), true);
+        }
+    }
+    AppendMenuW(menu, MF_SEPARATOR, 0, std::ptr::null());
+    append(menu, ID_EXIT, tr("退出"), true);
+
+    let mut pt = POINT { x: 0, y: 0 };
+    GetCursorPos(&mut pt);
+    // 不先置前，点击菜单外部时菜单不会消失
+    SetForegroundWindow(hwnd);
+    let cmd = TrackPopupMenu(menu, TPM_RETURNCMD | TPM_RIGHTBUTTON, pt.x, pt.y, 0, hwnd, std::ptr::null()) as usize;
+    PostMessageW(hwnd, WM_NULL, 0, 0);
+    DestroyMenu(menu);
+
+    match cmd {
+        ID_SHOW => show_main_window(),
+        ID_HUD => {
+            // 主窗口隐藏时 update() 不运行，先显示出来；悬浮窗打开后 UI 线程会再隐藏主窗口
+            show_main_window();
+            HANDLERS.with(|h| {
+                if let Some(h) = h.borrow().as_ref() {
+                    (h.toggle_hud)();
+                }
+            });
+        }
+        ID_EXIT => {
+            show_main_window();
+            HANDLERS.with(|h| {
+                if let Some(h) = h.borrow().as_ref() {
+                    (h.quit)();
+                }
+            });
+        }
+        ID_KILL_TOP => {
+            if let Some(p) = data.top_cpu {
+                HANDLERS.with(|h| {
+                    if let Some(h) = h.borrow().as_ref() {
+                        (h.kill)(&p.pids);
+                    }
+                });
+                watch_kill(p.name, p.pids);
+            }
+        }
+        c if c >= ID_MACRO_BASE => {
+            if let Some((index, name)) = data.macros.into_iter().nth(c - ID_MACRO_BASE) {
+                HANDLERS.with(|h| {
+                    if let Some(h) = h.borrow().as_ref() {
+                        (h.run_macro)(index);
+                    }
+                });
+                balloon(tr("正在执行操作宏"), &name, false);
+            }
+        }
+        c if c >= ID_EJECT_BASE => {
+            if let Some(d) = data.drives.into_iter().nth(c - ID_EJECT_BASE) {
+                HANDLERS.with(|h| {
+                    if let Some(h) = h.borrow().as_ref() {
+                        (h.eject)(&d.drive);
+                    }
+                });
+                watch_eject(d.drive, d.label);
+            }
+        }
+        _ => {}
+    }
+}
+
+unsafe extern "system" fn wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    let taskbar_created = TASKBAR_CREATED.with(|c| c.get());
+    if taskbar_created != 0 && msg == taskbar_created {
+        add_icon(hwnd);
+        return 0;
+    }
+    if msg == WM_TRAY {
+        match lparam as u32 {
+            WM_LBUTTONUP | NIN_BALLOONUSERCLICK => show_main_window(),
+            WM_RBUTTONUP | WM_CONTEXTMENU => show_menu(hwnd),
+            _ => {}
+        }
+        return 0;
+    }
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
@@ -0,0 +1,193 @@
+// ═══════════════════════════════════════════════════════════════
+//  降权启动 - 管理员身份运行时，打开的资源管理器 / 用户程序不继承提权
+// ═══════════════════════════════════════════════════════════════
+//
+// 以管理员运行时直接 spawn 的子进程同样是管理员：资源管理器窗口里拖放、
+// 用户程序的配置目录与自动更新都会出问题。做法是借用外壳 (桌面 explorer.exe) 的令牌：
+//   GetShellWindow -> 外壳进程 -> OpenProcessToken -> DuplicateTokenEx (主令牌)
+//   -> CreateProcessWithTokenW
+// 外壳令牌就是用户登录时的非提权令牌。非管理员运行时直接启动；
+// 找不到外壳 (例如资源管理器已退出) 时退回直接启动，并写入审计日志。
+// 要结束外壳本身时 (重启资源管理器)，先用 capture_shell_token 保存令牌再结束。
+
+use crate::i18n::{tr, trf};
+use crate::long_path::wide;
+use rust_core_lib::security;
+use windows_sys::Win32::Foundation::{CloseHandle, GetLastError, HANDLE};
+use windows_sys::Win32::Security::{
+    DuplicateTokenEx, SecurityImpersonation, TokenPrimary, TOKEN_ADJUST_DEFAULT, TOKEN_ADJUST_SESSIONID,
+    TOKEN_ASSIGN_PRIMARY, TOKEN_DUPLICATE, TOKEN_QUERY,
+};
+use windows_sys::Win32::System::Threading::{
+    CreateProcessWithTokenW, OpenProcess, OpenProcessToken, PROCESS_INFORMATION, PROCESS_QUERY_LIMITED_INFORMATION,
+    STARTUPINFOW,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{GetShellWindow, GetWindowThreadProcessId};
+
+fn quote(arg: &str) -> String {
+    if arg.contains(' ') && !arg.contains('"') {
+        format!("\"{}\"", arg)
+    } else {
+        arg.to_string()
+    }
+}
+
+/// 用户 (非提权) 主令牌；释放时关闭句柄
+pub struct UserToken(HANDLE);
+
+impl Drop for UserToken {
+    fn drop(&mut self) {
+        unsafe { CloseHandle(self.0) };
+    }
+}
+
+/// 保存外壳令牌，供外壳退出后降权启动
+pub fn capture_shell_token() -> Result<UserToken, String> {
+    unsafe { shell_token() }
+}
+
+/// 外壳进程令牌的主令牌副本
+unsafe fn shell_token() -> Result<UserToken, String> {
+    let shell = GetShellWindow();
+    if shell == 0 {
+        return Err(tr("资源管理器外壳未运行").to_string());
+    }
+    let mut pid = 0;
+    GetWindowThreadProcessId(shell, &mut pid);
+    let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+    if process == 0 {
+        return Err(trf("无法打开外壳进程 (错误 {})", &[&GetLastError()]));
+    }
+    let mut token = 0;
+    let opened = OpenProcessToken(process, TOKEN_DUPLICATE, &mut token);
+    CloseHandle(process);
+    if opened == 0 {
+        return Err(trf("无法读取外壳令牌 (错误 {})", &[&GetLastError()]));
+    }
+    let mut primary = 0;
+    let duplicated = DuplicateTokenEx(
+        token,
+        TOKEN_QUERY | TOKEN_ASSIGN_PRIMARY | TOKEN_DUPLICATE | TOKEN_ADJUST_DEFAULT | TOKEN_ADJUST_SESSIONID,
+        std::ptr::null(),
+        SecurityImpersonation,
+        TokenPrimary,
+        &mut primary,
+    );
+    CloseHandle(token);
+    if duplicated == 0 {
+        return Err(trf("无法复制外壳令牌 (错误 {})", &[&GetLastError()]));
+    }
+    Ok(UserToken(primary))
+}
+
+unsafe fn spawn_with_shell_token(application: Option<&str>, command_line: &str, cwd: Option<&str>) -> Result<(), String> {
+    spawn_as(&shell_token()?, application, command_line, cwd)
+}
+
+unsafe fn spawn_as(token: &UserToken, application: Option<&str>, command_line: &str, cwd: Option<&str>) -> Result<(), String> {
+    let application = application.map(wide);
+    let cwd = cwd.map(wide);
+    let mut cmd = wide(command_line);
+    let mut si: STARTUPINFOW = std::mem::zeroed();
+    si.cb = std::mem::size_of::<STARTUPINFOW>() as u32;
+    let mut pi: PROCESS_INFORMATION = std::mem::zeroed();
+    let ok = CreateProcessWithTokenW(
+        token.0,
+        0,
+        application.as_ref().map(|a| a.as_ptr()).unwrap_or(std::ptr::null()),
+        cmd.as_mut_ptr(),
+        0,
+        std::ptr::null(),
+        cwd.as_ref().map(|c| c.as_ptr()).unwrap_or(std::ptr::null()),
+        &si,
+        &mut pi,
+    );
+    let err = GetLastError();
+    if ok == 0 {
+        return Err(trf("CreateProcessWithTokenW 失败 (错误 {})", &[&err]));
+    }
+    CloseHandle(pi.hThread);
+    CloseHandle(pi.hProcess);
+    Ok(())
+}
+
+/// 直接启动；参数按原样拼接 (explorer 的 /select,"路径" 不能被转义)
+fn spawn_direct(program: &str, args: &[String]) -> Result<(), String> {
+    use std::os::windows::process::CommandExt;
+    let mut cmd = std::process::Command::new(program);
+    for a in args {
+        cmd.raw_arg(quote(a));
+    }
+    cmd.spawn().map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// 以普通用户权限启动程序；本程序未提权时等同于直接启动
+pub fn spawn(program: &str, args: &[String]) -> Result<(), String> {
+    if !security::is_admin() {
+        return spawn_direct(program, args);
+    }
+    let command_line = std::iter::once(quote(program))
+        .chain(args.iter().map(|a| quote(a)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    match unsafe { spawn_with_shell_token(None, &command_line, None) } {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            crate::audit::record("降权启动", &format!("{} -> {}，改为直接启动 (继承管理员权限)", command_line, e));
+            spawn_direct(program, args)
+        }
+    }
+}
+
+/// 用事先保存的用户令牌启动程序 (无参数)
+pub fn spawn_with_token(token: &UserToken, program: &str) -> Result<(), String> {
+    unsafe { spawn_as(token, Some(program), &quote(program), None) }
+}
+
+/// 去掉命令行开头的程序名 (可能带引号)，返回其后的参数部分
+fn args_of(command_line: &str) -> &str {
+    let rest = match command_line.strip_prefix('"') {
+        Some(quoted) => quoted.find('"').map(|i| &quoted[i + 1..]).unwrap_or(""),
+        None => command_line.find(' ').map(|i| &command_line[i..]).unwrap_or(""),
+    };
+    rest.trim_start()
+}
+
+/// 按原始命令行启动 (重启进程时沿用原进程的命令行与工作目录)；
+/// keep_elevation 为 true (原进程本就以管理员运行) 时直接启动，否则同样不继承提权
+pub fn spawn_command_line(program: &str, command_line: &str, cwd: Option<&str>, keep_elevation: bool) -> Result<(), String> {
+    let direct = || {
+        use std::os::windows::process::CommandExt;
+        let mut cmd = std::process::Command::new(program);
+        cmd.raw_arg(args_of(command_line));
+        if let Some(dir) = cwd {
+            cmd.current_dir(dir);
+        }
+        cmd.spawn().map(|_| ()).map_err(|e| e.to_string())
+    };
+    if keep_elevation || !security::is_admin() {
+        return direct();
+    }
+    match unsafe { spawn_with_shell_token(Some(program), command_line, cwd) } {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            crate::audit::record("降权启动", &format!("{} -> {}，改为直接启动 (继承管理员权限)", command_line, e));
+            direct()
+        }
+    }
+}
+
+pub fn explorer_path() -> String {
+    let windir = std::env::var("WINDIR").unwrap_or_else(|_| "C:\\Windows".to_string());
+    format!("{}\\explorer.exe", windir)
+}
+
+/// 在资源管理器中打开路径
+pub fn open_in_explorer(path: &str) {
+    let _ = spawn(&explorer_path(), &[path.to_string()]);
+}
+
+/// 在资源管理器中打开所在文件夹并选中该文件
+pub fn reveal_in_explorer(path: &str) {
+    let _ = spawn(&explorer_path(), &[format!("/select,\"{}\"", path)]);
+}
@@ -0,0 +1,245 @@
+// ═══════════════════════════════════════════════════════════════
+//  卸载集成 - 从进程反查“程序和功能”中的安装条目
+// ═══════════════════════════════════════════════════════════════
+//
+// 匹配顺序：
+//   1. 可执行文件位于 InstallLocation / DisplayIcon / 卸载程序所在目录之下 (取最长匹配)
+//   2. 发布者与文件版本信息中的公司名一致，且软件名包含程序所在文件夹名
+
+use crate::i18n::trf;
+use crate::long_path::wide;
+use crate::tool_runner::ToolSpec;
+use windows_sys::Win32::System::Registry::{
+    RegCloseKey, RegEnumKeyExW, RegGetValueW, RegOpenKeyExW, HKEY, HKEY_CURRENT_USER,
+    HKEY_LOCAL_MACHINE, KEY_READ, RRF_RT_REG_DWORD, RRF_RT_REG_SZ,
+};
+
+const UNINSTALL_KEYS: [(HKEY, &str); 3] = [
+    (HKEY_LOCAL_MACHINE, "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall"),
+    (HKEY_LOCAL_MACHINE, "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall"),
+    (HKEY_CURRENT_USER, "Software\\Microsoft\\Windows\\CurrentVersion\\Uninstall"),
+];
+
+/// 过于宽泛、不能作为安装目录参与匹配的路径
+const GENERIC_DIRS: &[&str] = &[
+    "c:\\windows",
+    "c:\\windows\\system32",
+    "c:\\program files",
+    "c:\\program files (x86)",
+    "c:\\programdata",
+];
+
+#[derive(Clone, Debug, Default)]
+pub struct InstalledApp {
+    pub display_name: String,
+    pub publisher: String,
+    pub install_location: String,
+    pub display_icon: String,
+    pub uninstall_string: String,
+}
+
+fn reg_sz(root: HKEY, subkey: &[u16], name: &str) -> String {
+    let name = wide(name);
+    let mut buf = vec![0u16; 2048];
+    let mut size = (buf.len() * 2) as u32;
+    let status = unsafe {
+        RegGetValueW(
+            root,
+            subkey.as_ptr(),
+            name.as_ptr(),
+            RRF_RT_REG_SZ,
+            std::ptr::null_mut(),
+            buf.as_mut_ptr() as _,
+            &mut size,
+        )
+    };
+    if status != 0 {
+        return String::new();
+    }
+    let len = (size as usize / 2).saturating_sub(1);
+    String::from_utf16_lossy(&buf[..len]).trim().to_string()
+}
+
+fn reg_dword(root: HKEY, subkey: &[u16], name: &str) -> Option<u32> {
+    let name = wide(name);
+    let mut value = 0u32;
+    let mut size = 4u32;
+    let status = unsafe {
+        RegGetValueW(
+            root,
+            subkey.as_ptr(),
+            name.as_ptr(),
+            RRF_RT_REG_DWORD,
+            std::ptr::null_mut(),
+            &mut value as *mut u32 as _,
+            &mut size,
+        )
+    };
+    (status == 0).then_some(value)
+}
+
+fn subkeys(root: HKEY, path: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    unsafe {
+        let mut key: HKEY = 0;
+        if RegOpenKeyExW(root, wide(path).as_ptr(), 0, KEY_READ, &mut key) != 0 {
+            return out;
+        }
+        for index in 0.. {
+            let mut name = [0u16; 256];
+            let mut len = name.len() as u32;
+            if RegEnumKeyExW(
+                key,
+                index,
+                name.as_mut_ptr(),
+                &mut len,
+                std::ptr::null(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            ) != 0
+            {
+                break;
+            }
+            out.push(String::from_utf16_lossy(&name[..len as usize]));
+        }
+        RegCloseKey(key);
+    }
+    out
+}
+
+/// 枚举“程序和功能”中可见的已安装软件
+pub fn installed_apps() -> Vec<InstalledApp> {
+    let mut apps = Vec::new();
+    for (root, path) in UNINSTALL_KEYS {
+        for sub in subkeys(root, path) {
+            let full = wide(&format!("{}\\{}", path, sub));
+            // 系统组件与补丁不在“程序和功能”中显示，也不应从这里卸载
+            if reg_dword(root, &full, "SystemComponent") == Some(1) {
+                continue;
+            }
+            let app = InstalledApp {
+                display_name: reg_sz(root, &full, "DisplayName"),
+                publisher: reg_sz(root, &full, "Publisher"),
+                install_location: reg_sz(root, &full, "InstallLocation"),
+                display_icon: reg_sz(root, &full, "DisplayIcon"),
+                uninstall_string: reg_sz(root, &full, "UninstallString"),
+            };
+            if !app.display_name.is_empty() && !app.uninstall_string.is_empty() {
+                apps.push(app);
+            }
+        }
+    }
+    apps
+}
+
+/// 规范化目录：小写、去引号、去掉图标索引与末尾分隔符
+fn normalize_dir(path: &str) -> String {
+    let p = path.trim().trim_matches('"');
+    let p = p.split(',').next().unwrap_or(p);
+    p.trim_end_matches(['\\', '/']).to_lowercase()
+}
+
+fn parent_dir(path: &str) -> String {
+    let p = normalize_dir(path);
+    match p.rfind('\\') {
+        Some(i) => p[..i].to_string(),
+        None => String::new(),
+    }
+}
+
+impl InstalledApp {
+    /// 可用于路径匹配的候选目录
+    fn candidate_dirs(&self) -> Vec<String> {
+        let mut dirs = vec![normalize_dir(&self.install_location), parent_dir(&self.display_icon)];
+        if let Some(exe) = crate::startup_items::command_executable(&self.uninstall_string) {
+            if !exe.to_lowercase().ends_with("msiexec.exe") {
+                dirs.push(parent_dir(&exe));
+            }
+        }
+        dirs.retain(|d| d.len() > 3 && !GENERIC_DIRS.contains(&d.as_str()));
+        dirs
+    }
+}
+
+/// 为进程的可执行文件找到对应的安装条目
+pub fn find_for_exe<'a>(exe_path: &str, company: Option<&str>, apps: &'a [InstalledApp]) -> Option<&'a InstalledApp> {
+    let exe = exe_path.to_lowercase();
+    if exe.is_empty() {
+        return None;
+    }
+
+    let by_path = apps
+        .iter()
+        .filter_map(|app| {
+            app.candidate_dirs()
+                .into_iter()
+                .filter(|d| exe.starts_with(&format!("{}\\", d)))
+                .map(|d| d.len())
+                .max()
+                .map(|len| (len, app))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, app)| app);
+    if by_path.is_some() {
+        return by_path;
+    }
+
+    let company = company?.trim();
+    let folder = parent_dir(&exe);
+    let folder = folder.rsplit('\\').next().unwrap_or_default();
+    if company.is_empty() || folder.len() < 3 {
+        return None;
+    }
+    apps.iter().find(|app| {
+        app.publisher.eq_ignore_ascii_case(company) && app.display_name.to_lowercase().contains(folder)
+    })
+}
+
+/// 按 Windows 命令行规则拆分参数：双引号内的空格不分隔，引号本身去掉
+fn split_args(rest: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut started = false;
+    for c in rest.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                started = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if started {
+                    args.push(std::mem::take(&mut current));
+                    started = false;
+                }
+            }
+            c => {
+                current.push(c);
+                started = true;
+            }
+        }
+    }
+    if started {
+        args.push(current);
+    }
+    args
+}
+
+/// 启动卸载程序的命令
+pub fn uninstall_spec(app: &InstalledApp) -> ToolSpec {
+    let cmd = app.uninstall_string.trim();
+    let (program, rest) = match cmd.strip_prefix('"') {
+        Some(r) => r.split_once('"').unwrap_or((r, "")),
+        // 在原串上按字节查找 ASCII 的 ".exe"，下标必然落在字符边界上
+        None => match cmd.as_bytes().windows(4).position(|w| w.eq_ignore_ascii_case(b".exe")) {
+            Some(i) => cmd.split_at(i + 4),
+            None => cmd.split_once(' ').unwrap_or((cmd, "")),
+        },
+    };
+    ToolSpec {
+        title: trf("卸载 {}", &[&app.display_name]),
+        program: program.to_string(),
+        args: split_args(rest),
+    }
+}
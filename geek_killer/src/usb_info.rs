@@ -0,0 +1,495 @@
+// ═══════════════════════════════════════════════════════════════
+//  总线类型与 USB 协商速率
+// ═══════════════════════════════════════════════════════════════
+//
+// 复制慢往往只是因为 U 盘插在了 2.0 口上。这里查询：
+//   1. IOCTL_STORAGE_QUERY_PROPERTY -> BusType (USB / SD / MMC)
+//   2. 磁盘父设备的驱动服务 (UASPStor 即 UASP 协议)
+//   3. 所在 Hub 端口的连接信息 -> 实际协商速率及是否支持更高速率
+// 另外从设备树读取 VID/PID、友好名称与序列号，用于区分外观相同的多个 U 盘。
+
+use crate::i18n::tr;
+use windows_sys::Win32::Devices::DeviceAndDriverInstallation::{
+    CM_Get_DevNode_Registry_PropertyW, CM_Get_Device_IDW, CM_Get_Device_Interface_ListW,
+    CM_Get_Device_Interface_List_SizeW, CM_Get_Parent, CM_DRP_ADDRESS, CM_DRP_FRIENDLYNAME, CM_DRP_SERVICE,
+    CM_GET_DEVICE_INTERFACE_LIST_PRESENT, CR_SUCCESS,
+};
+use windows_sys::Win32::Foundation::{CloseHandle, HANDLE, INVALID_HANDLE_VALUE};
+use windows_sys::Win32::Storage::FileSystem::{
+    CreateFileW, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows_sys::Win32::System::Ioctl::{
+    PropertyStandardQuery, StorageDeviceProperty, IOCTL_STORAGE_GET_DEVICE_NUMBER,
+    IOCTL_STORAGE_QUERY_PROPERTY, STORAGE_DEVICE_DESCRIPTOR, STORAGE_DEVICE_NUMBER,
+    STORAGE_PROPERTY_QUERY,
+};
+use windows_sys::Win32::System::IO::DeviceIoControl;
+
+const GUID_DEVINTERFACE_USB_HUB: windows_sys::core::GUID = windows_sys::core::GUID {
+    data1: 0xf18a0e88,
+    data2: 0xc30c,
+    data3: 0x11d0,
+    data4: [0x88, 0x15, 0x00, 0xa0, 0xc9, 0x06, 0xbe, 0xd8],
+};
+
+const IOCTL_USB_GET_NODE_CONNECTION_INFORMATION_EX: u32 = 0x220448;
+const IOCTL_USB_GET_NODE_CONNECTION_INFORMATION_EX_V2: u32 = 0x22045C;
+
+// STORAGE_BUS_TYPE
+const BUS_TYPE_USB: i32 = 0x07;
+const BUS_TYPE_SD: i32 = 0x0C;
+const BUS_TYPE_MMC: i32 = 0x0D;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BusKind {
+    Usb1,
+    Usb2,
+    Usb3,
+    Uasp, // USB 3.x + UASP 协议
+    Sd,
+    Other,
+}
+
+impl BusKind {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            BusKind::Usb1 | BusKind::Usb2 => "🐢",
+            BusKind::Usb3 => "⚡",
+            BusKind::Uasp => "🚀",
+            BusKind::Sd => "💳",
+            BusKind::Other => "💿",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BusInfo {
+    pub kind: BusKind,
+    /// 如 "USB 3.2 Gen1 5Gbps"
+    pub speed_label: String,
+    /// 设备支持 USB 3.x 但当前以 2.0 速率运行 (插错口/线材问题)
+    pub capable_of_faster: bool,
+}
+
+/// USB 设备身份：同容量、同型号的几个 U 盘靠序列号区分
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DeviceIdentity {
+    pub vid: Option<u16>,
+    pub pid: Option<u16>,
+    /// 设备管理器中的名称，如 "SanDisk Ultra USB 3.0 USB Device"
+    pub friendly_name: String,
+    /// 序列号；设备没有序列号时为空
+    pub serial: String,
+}
+
+impl DeviceIdentity {
+    /// "VID_0781 PID_5581"
+    pub fn vid_pid(&self) -> Option<String> {
+        Some(format!("VID_{:04X} PID_{:04X}", self.vid?, self.pid?))
+    }
+
+    /// 列表中显示的一行：VID/PID · 名称 · 序列号 (过长时截断)
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(ids) = self.vid_pid() {
+            parts.push(ids);
+        }
+        if !self.friendly_name.is_empty() {
+            parts.push(self.friendly_name.clone());
+        }
+        if !self.serial.is_empty() {
+            let serial: String = self.serial.chars().take(16).collect();
+            let ellipsis = if self.serial.chars().count() > 16 { "…" } else { "" };
+            parts.push(format!("SN {}{}", serial, ellipsis));
+        }
+        parts.join(" · ")
+    }
+}
+
+fn w(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+fn from_wide(buf: &[u16]) -> String {
+    let end = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    String::from_utf16_lossy(&buf[..end])
+}
+
+fn open_device(path: &str) -> Option<HANDLE> {
+    let h = unsafe {
+        CreateFileW(
+            w(path).as_ptr(),
+            0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            std::ptr::null(),
+            OPEN_EXISTING,
+            0,
+            0,
+        )
+    };
+    if h == INVALID_HANDLE_VALUE {
+        None
+    } else {
+        Some(h)
+    }
+}
+
+/// IOCTL_STORAGE_QUERY_PROPERTY(StorageDeviceProperty)：描述符及其后的厂商 / 型号 / 序列号字符串
+unsafe fn read_descriptor(h: HANDLE) -> Option<[u8; 1024]> {
+    let mut query: STORAGE_PROPERTY_QUERY = std::mem::zeroed();
+    query.PropertyId = StorageDeviceProperty;
+    query.QueryType = PropertyStandardQuery;
+    let mut desc_buf = [0u8; 1024];
+    let mut bytes = 0u32;
+    let ok = DeviceIoControl(
+        h,
+        IOCTL_STORAGE_QUERY_PROPERTY,
+        &query as *const _ as _,
+        std::mem::size_of::<STORAGE_PROPERTY_QUERY>() as u32,
+        desc_buf.as_mut_ptr() as _,
+        desc_buf.len() as u32,
+        &mut bytes,
+        std::ptr::null_mut(),
+    );
+    (ok != 0).then_some(desc_buf)
+}
+
+/// 描述符中以偏移量引用的 ASCII 字符串 (偏移为 0 表示不存在)
+fn descriptor_string(buf: &[u8], offset: u32) -> String {
+    let start = offset as usize;
+    if start == 0 || start >= buf.len() {
+        return String::new();
+    }
+    let end = buf[start..].iter().position(|&b| b == 0).map(|i| start + i).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[start..end]).trim().to_string()
+}
+
+/// 物理设备的稳定标识 "厂商 型号 #序列号"，用于按设备记住偏好；
+/// 没有序列号的廉价 U 盘无法区分同型号的多个设备，返回 None
+pub fn device_key(drive_letter: &str) -> Option<String> {
+    let h = open_device(&crate::mount_manager::device_path(drive_letter))?;
+    let buf = unsafe {
+        let buf = read_descriptor(h);
+        CloseHandle(h);
+        buf?
+    };
+    let desc = unsafe { (buf.as_ptr() as *const STORAGE_DEVICE_DESCRIPTOR).read_unaligned() };
+    let serial = descriptor_string(&buf, desc.SerialNumberOffset);
+    // 部分设备返回全 0 或全空格的占位序列号
+    if serial.is_empty() || serial.chars().all(|c| c == '0') {
+        return None;
+    }
+    let vendor = descriptor_string(&buf, desc.VendorIdOffset);
+    let product = descriptor_string(&buf, desc.ProductIdOffset);
+    Some(format!("{} {} #{}", vendor, product, serial).trim().to_string())
+}
+
+/// 卷所在物理磁盘的编号 (\\.\PhysicalDriveN 中的 N)
+pub fn disk_number(drive_letter: &str) -> Option<u32> {
+    query_storage(drive_letter).map(|(_, sdn)| sdn.DeviceNumber)
+}
+
+/// 卷所在物理磁盘的设备实例 (DEVINST)
+pub fn disk_devinst(drive_letter: &str) -> Option<u32> {
+    let (_, sdn) = query_storage(drive_letter)?;
+    crate::find_disk_devinst(sdn.DeviceNumber, sdn.DeviceType).ok()
+}
+
+/// 与给定卷位于同一物理磁盘上的全部卷 (含自身)
+///
+/// 多分区的移动硬盘只弹出其中一个卷时，另一个卷仍在挂载，PnP 弹出必然被否决。
+/// 有盘符的卷返回盘符，只挂载到文件夹的卷返回文件夹路径，未挂载的卷返回 GUID 路径。
+pub fn volumes_on_same_disk(drive_letter: &str) -> Vec<String> {
+    let own = crate::norm_drive(drive_letter);
+    let Some((_, target)) = query_storage(&own) else {
+        return vec![own];
+    };
+    let own_volume = crate::mount_manager::volume_name(&own);
+    let mut out = vec![own.clone()];
+    for volume in crate::mount_manager::all_volumes() {
+        if own_volume.as_deref().map(|v| v.eq_ignore_ascii_case(&volume)).unwrap_or(false) {
+            continue;
+        }
+        let same_disk = query_storage(&volume)
+            .map(|(_, sdn)| sdn.DeviceType == target.DeviceType && sdn.DeviceNumber == target.DeviceNumber)
+            .unwrap_or(false);
+        if same_disk {
+            out.push(crate::norm_drive(&crate::mount_manager::preferred_target(&volume)));
+        }
+    }
+    out.sort();
+    out
+}
+
+/// 读取卷所在磁盘的 BusType 与设备号
+fn query_storage(drive_letter: &str) -> Option<(i32, STORAGE_DEVICE_NUMBER)> {
+    let h = open_device(&crate::mount_manager::device_path(drive_letter))?;
+    unsafe {
+        let desc_buf = read_descriptor(h);
+        let mut bytes = 0u32;
+        let mut sdn: STORAGE_DEVICE_NUMBER = std::mem::zeroed();
+        let ok_sdn = DeviceIoControl(
+            h,
+            IOCTL_STORAGE_GET_DEVICE_NUMBER,
+            std::ptr::null(),
+            0,
+            &mut sdn as *mut _ as _,
+            std::mem::size_of::<STORAGE_DEVICE_NUMBER>() as u32,
+            &mut bytes,
+            std::ptr::null_mut(),
+        );
+        CloseHandle(h);
+
+        let desc_buf = desc_buf?;
+        if ok_sdn == 0 {
+            return None;
+        }
+        let desc = (desc_buf.as_ptr() as *const STORAGE_DEVICE_DESCRIPTOR).read_unaligned();
+        Some((desc.BusType, sdn))
+    }
+}
+
+fn devnode_string_property(dev_inst: u32, prop: u32) -> Option<String> {
+    let mut buf = [0u16; 256];
+    let mut len = (buf.len() * 2) as u32;
+    let rc = unsafe {
+        CM_Get_DevNode_Registry_PropertyW(
+            dev_inst,
+            prop,
+            std::ptr::null_mut(),
+            buf.as_mut_ptr() as _,
+            &mut len,
+            0,
+        )
+    };
+    (rc == CR_SUCCESS).then(|| from_wide(&buf))
+}
+
+pub fn devnode_u32_property(dev_inst: u32, prop: u32) -> Option<u32> {
+    let mut value = 0u32;
+    let mut len = 4u32;
+    let rc = unsafe {
+        CM_Get_DevNode_Registry_PropertyW(
+            dev_inst,
+            prop,
+            std::ptr::null_mut(),
+            &mut value as *mut u32 as _,
+            &mut len,
+            0,
+        )
+    };
+    (rc == CR_SUCCESS).then_some(value)
+}
+
+fn parent_of(dev_inst: u32) -> Option<u32> {
+    let mut parent = 0u32;
+    (unsafe { CM_Get_Parent(&mut parent, dev_inst, 0) } == CR_SUCCESS).then_some(parent)
+}
+
+/// Hub 设备实例 -> Hub 设备接口路径 (用于发送 USB IOCTL)
+fn hub_interface_path(hub_inst: u32) -> Option<String> {
+    unsafe {
+        let mut id = [0u16; 512];
+        if CM_Get_Device_IDW(hub_inst, id.as_mut_ptr(), id.len() as u32, 0) != CR_SUCCESS {
+            return None;
+        }
+        let mut len = 0u32;
+        if CM_Get_Device_Interface_List_SizeW(
+            &mut len,
+            &GUID_DEVINTERFACE_USB_HUB,
+            id.as_ptr(),
+            CM_GET_DEVICE_INTERFACE_LIST_PRESENT,
+        ) != CR_SUCCESS
+            || len <= 1
+        {
+            return None;
+        }
+        let mut list = vec![0u16; len as usize];
+        if CM_Get_Device_Interface_ListW(
+            &GUID_DEVINTERFACE_USB_HUB,
+            id.as_ptr(),
+            list.as_mut_ptr(),
+            len,
+            CM_GET_DEVICE_INTERFACE_LIST_PRESENT,
+        ) != CR_SUCCESS
+        {
+            return None;
+        }
+        let first = from_wide(&list);
+        (!first.is_empty()).then_some(first)
+    }
+}
+
+/// 查询 Hub 端口上设备的 (Speed, bcdUSB, V2 Flags)
+fn query_port(hub_path: &str, port: u32) -> Option<(u8, u16, u32)> {
+    let h = open_device(hub_path)?;
+    unsafe {
+        // USB_NODE_CONNECTION_INFORMATION_EX 为 1 字节对齐，直接按偏移读取：
+        //   0: ConnectionIndex(u32)  4: DeviceDescriptor(18B, bcdUSB 在 +2)  23: Speed(u8)
+        let mut info = [0u8; 512];
+        info[..4].copy_from_slice(&port.to_le_bytes());
+        let mut bytes = 0u32;
+        let ok = DeviceIoControl(
+            h,
+            IOCTL_USB_GET_NODE_CONNECTION_INFORMATION_EX,
+            info.as_ptr() as _,
+            info.len() as u32,
+            info.as_mut_ptr() as _,
+            info.len() as u32,
+            &mut bytes,
+            std::ptr::null_mut(),
+        );
+
+        // USB_NODE_CONNECTION_INFORMATION_EX_V2:
+        //   ConnectionIndex, Length, SupportedUsbProtocols(in), Flags(out)
+        let mut v2 = [0u32; 4];
+        v2[0] = port;
+        v2[1] = 16;
+        v2[2] = 0x7; // Usb110 | Usb200 | Usb300
+        let ok_v2 = DeviceIoControl(
+            h,
+            IOCTL_USB_GET_NODE_CONNECTION_INFORMATION_EX_V2,
+            v2.as_ptr() as _,
+            16,
+            v2.as_mut_ptr() as _,
+            16,
+            &mut bytes,
+            std::ptr::null_mut(),
+        );
+        CloseHandle(h);
+
+        if ok == 0 {
+            return None;
+        }
+        let bcd_usb = u16::from_le_bytes([info[6], info[7]]);
+        let speed = info[23];
+        let flags = if ok_v2 != 0 { v2[3] } else { 0 };
+        Some((speed, bcd_usb, flags))
+    }
+}
+
+fn speed_label(speed: u8, flags: u32) -> (BusKind, String) {
+    const OPERATING_SUPER_SPEED: u32 = 0x1;
+    const OPERATING_SUPER_SPEED_PLUS: u32 = 0x4;
+    match speed {
+        0 => (BusKind::Usb1, "USB 1.1 Low 1.5Mbps".into()),
+        1 => (BusKind::Usb1, "USB 1.1 Full 12Mbps".into()),
+        2 => (BusKind::Usb2, "USB 2.0 480Mbps".into()),
+        _ if flags & OPERATING_SUPER_SPEED_PLUS != 0 => {
+            (BusKind::Usb3, "USB 3.2 Gen2 10Gbps".into())
+        }
+        _ if speed >= 3 || flags & OPERATING_SUPER_SPEED != 0 => {
+            (BusKind::Usb3, "USB 3.2 Gen1 5Gbps".into())
+        }
+        _ => (BusKind::Other, "USB".into()),
+    }
+}
+
+fn device_id(dev_inst: u32) -> Option<String> {
+    let mut id = [0u16; 512];
+    let rc = unsafe { CM_Get_Device_IDW(dev_inst, id.as_mut_ptr(), id.len() as u32, 0) };
+    (rc == CR_SUCCESS).then(|| from_wide(&id))
+}
+
+/// "USB\VID_0781&PID_5581\4C530001" 中的 (VID, PID)
+fn parse_vid_pid(id: &str) -> Option<(u16, u16)> {
+    let upper = id.to_ascii_uppercase();
+    let hex_after = |key: &str| {
+        let start = upper.find(key)? + key.len();
+        u16::from_str_radix(upper.get(start..start + 4)?, 16).ok()
+    };
+    Some((hex_after("VID_")?, hex_after("PID_")?))
+}
+
+/// 查询卷所在 USB 设备的身份 (较慢，调用方应缓存)
+pub fn identity(drive_letter: &str) -> Option<DeviceIdentity> {
+    let (_, sdn) = query_storage(drive_letter)?;
+    let disk = crate::find_disk_devinst(sdn.DeviceNumber, sdn.DeviceType).ok()?;
+    let mut identity = DeviceIdentity {
+        friendly_name: devnode_string_property(disk, CM_DRP_FRIENDLYNAME).unwrap_or_default(),
+        ..Default::default()
+    };
+
+    // 磁盘 -> (复合设备的接口) -> USB 设备，向上找第一个带 VID/PID 的节点
+    let mut node = disk;
+    for _ in 0..3 {
+        let Some(parent) = parent_of(node) else { break };
+        node = parent;
+        let Some(id) = device_id(node) else { break };
+        if let Some((vid, pid)) = parse_vid_pid(&id) {
+            identity.vid = Some(vid);
+            identity.pid = Some(pid);
+            // 复合设备的接口节点实例号由系统生成，继续向上找真正的设备节点
+            if id.to_ascii_uppercase().contains("&MI_") {
+                continue;
+            }
+            // 实例号含 '&' 说明设备没有序列号，是系统按端口生成的
+            let instance = id.rsplit('\\').next().unwrap_or_default();
+            if !instance.contains('&') {
+                identity.serial = instance.to_string();
+            }
+            break;
+        }
+    }
+
+    if identity.serial.is_empty() {
+        identity.serial = device_key(drive_letter)
+            .and_then(|k| k.rsplit_once(" #").map(|(_, s)| s.to_string()))
+            .unwrap_or_default();
+    }
+    Some(identity)
+}
+
+/// 查询驱动器所在设备的总线类型与协商速率 (较慢，调用方应缓存)
+pub fn query(drive_letter: &str) -> Option<BusInfo> {
+    const SUPER_SPEED_CAPABLE: u32 = 0x2;
+
+    let (bus_type, sdn) = query_storage(drive_letter)?;
+    match bus_type {
+        BUS_TYPE_SD | BUS_TYPE_MMC => {
+            return Some(BusInfo {
+                kind: BusKind::Sd,
+                speed_label: tr("SD 读卡器").into(),
+                capable_of_faster: false,
+            })
+        }
+        BUS_TYPE_USB => {}
+        _ => return None,
+    }
+
+    let disk = crate::find_disk_devinst(sdn.DeviceNumber, sdn.DeviceType).ok()?;
+    let usb_dev = parent_of(disk)?;
+    let is_uasp = devnode_string_property(usb_dev, CM_DRP_SERVICE)
+        .map(|s| s.eq_ignore_ascii_case("UASPStor"))
+        .unwrap_or(false);
+
+    let fallback = BusInfo {
+        kind: if is_uasp { BusKind::Uasp } else { BusKind::Other },
+        speed_label: if is_uasp { "USB 3.x UASP".into() } else { "USB".into() },
+        capable_of_faster: false,
+    };
+
+    let port = match devnode_u32_property(usb_dev, CM_DRP_ADDRESS) {
+        Some(p) => p,
+        None => return Some(fallback),
+    };
+    let Some((speed, bcd_usb, flags)) = parent_of(usb_dev)
+        .and_then(hub_interface_path)
+        .and_then(|hub| query_port(&hub, port))
+    else {
+        return Some(fallback);
+    };
+
+    let (mut kind, mut label) = speed_label(speed, flags);
+    if is_uasp && kind == BusKind::Usb3 {
+        kind = BusKind::Uasp;
+        label.push_str(" UASP");
+    }
+    let capable_of_faster =
+        speed <= 2 && (flags & SUPER_SPEED_CAPABLE != 0 || bcd_usb >= 0x0300);
+    Some(BusInfo {
+        kind,
+        speed_label: label,
+        capable_of_faster,
+    })
+}
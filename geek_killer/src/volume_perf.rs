@@ -0,0 +1,56 @@
+// ═══════════════════════════════════════════════════════════════
+//  卷 I/O 计数器 (IOCTL_DISK_PERFORMANCE) - 判断拷贝是否还在进行
+// ═══════════════════════════════════════════════════════════════
+
+use windows_sys::Win32::Foundation::{CloseHandle, INVALID_HANDLE_VALUE};
+use windows_sys::Win32::Storage::FileSystem::{
+    CreateFileW, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows_sys::Win32::System::Ioctl::{DISK_PERFORMANCE, IOCTL_DISK_PERFORMANCE};
+use windows_sys::Win32::System::IO::DeviceIoControl;
+
+/// 卷自挂载以来的累计读写字节数
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IoCounters {
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+}
+
+pub fn query(drive_letter: &str) -> Option<IoCounters> {
+    let path: Vec<u16> = crate::mount_manager::device_path(drive_letter)
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    unsafe {
+        // 不需要读写权限，避免干扰正在进行的拷贝
+        let h = CreateFileW(
+            path.as_ptr(),
+            0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            std::ptr::null(),
+            OPEN_EXISTING,
+            0,
+            0,
+        );
+        if h == INVALID_HANDLE_VALUE {
+            return None;
+        }
+        let mut perf: DISK_PERFORMANCE = std::mem::zeroed();
+        let mut bytes = 0u32;
+        let ok = DeviceIoControl(
+            h,
+            IOCTL_DISK_PERFORMANCE,
+            std::ptr::null(),
+            0,
+            &mut perf as *mut _ as _,
+            std::mem::size_of::<DISK_PERFORMANCE>() as u32,
+            &mut bytes,
+            std::ptr::null_mut(),
+        );
+        CloseHandle(h);
+        (ok != 0).then(|| IoCounters {
+            bytes_read: perf.BytesRead.max(0) as u64,
+            bytes_written: perf.BytesWritten.max(0) as u64,
+        })
+    }
+}
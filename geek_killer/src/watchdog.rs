@@ -0,0 +1,347 @@
+// ═══════════════════════════════════════════════════════════════
+//  看门狗规则 - 无人值守时自动处理失控进程
+// ═══════════════════════════════════════════════════════════════
+//
+// 规则形如“进程名匹配 X 且 CPU 持续 > Y% 达 Z 秒 -> 动作”，动作可以是：
+//   通知 / 降低优先级 / 挂起 / 终止 / 裁剪内存 (作用于匹配的进程)
+//   运行脚本 (cmd /c，以普通用户权限运行，进程信息经环境变量 GK_PROCESS / GK_PIDS / GK_CPU 传入)
+//   弹出磁盘 (交给 USB 线程，与托盘菜单“弹出”相同：先扫描占用再弹出)
+// 保存在 %APPDATA%\GeekKiller\watchdog.json，由监控线程每轮按进程分组评估：
+//   - 名称匹配不区分大小写，支持 * 通配 (例如 "chrome*"、"*updater*")；不含 * 时为完整名称，
+//     可省略 .exe；同时比对进程名与显示名
+//   - 超阈值开始计时，回落即清零；触发后须先回落到阈值以下才会再次触发，
+//     避免同一个失控进程每轮都被处理一次
+//   - 系统分组、显示关键进程与本程序自身只通知，不执行作用于进程的动作
+// 每次触发都写审计日志并弹出托盘气泡 (窗口隐藏时也能看到)。
+
+use crate::i18n::{tr, trf};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use windows_sys::Win32::Foundation::CloseHandle;
+use windows_sys::Win32::System::Threading::{
+    OpenProcess, SetPriorityClass, IDLE_PRIORITY_CLASS, PROCESS_SET_INFORMATION,
+};
+
+/// 界面显示的最近触发记录条数
+const LOG_LEN: usize = 50;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    #[default]
+    Notify,
+    LowerPriority,
+    Suspend,
+    Kill,
+    TrimMemory,
+    RunScript,
+    EjectDrive,
+}
+
+impl Action {
+    pub const ALL: [Action; 7] = [
+        Action::Notify,
+        Action::LowerPriority,
+        Action::Suspend,
+        Action::Kill,
+        Action::TrimMemory,
+        Action::RunScript,
+        Action::EjectDrive,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::Notify => tr("通知"),
+            Action::LowerPriority => tr("降低优先级"),
+            Action::Suspend => tr("挂起"),
+            Action::Kill => tr("终止"),
+            Action::TrimMemory => tr("裁剪内存"),
+            Action::RunScript => tr("运行脚本"),
+            Action::EjectDrive => tr("弹出磁盘"),
+        }
+    }
+
+    /// 直接作用于匹配的进程 (受保护进程不执行)
+    pub fn targets_process(&self) -> bool {
+        matches!(self, Action::LowerPriority | Action::Suspend | Action::Kill | Action::TrimMemory)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rule {
+    pub enabled: bool,
+    /// 进程名 / 显示名，支持 * 通配
+    pub pattern: String,
+    /// CPU 阈值 (%，与进程表“总CPU”一致，多核满载可超过 100)
+    pub cpu_above: f32,
+    /// 持续秒数
+    pub for_secs: u64,
+    pub action: Action,
+    /// 运行脚本：cmd /c 执行的命令行
+    pub command: String,
+    /// 弹出磁盘：盘符，如 "E:"
+    pub drive: String,
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pattern: String::new(),
+            cpu_above: 80.0,
+            for_secs: 60,
+            action: Action::Notify,
+            command: String::new(),
+            drive: String::new(),
+        }
+    }
+}
+
+impl Rule {
+    pub fn summary(&self) -> String {
+        let target = match self.action {
+            Action::RunScript => format!(" ({})", self.command),
+            Action::EjectDrive => format!(" {}", self.drive),
+            _ => String::new(),
+        };
+        trf(
+            "{} CPU > {}% 持续 {} 秒 -> {}{}",
+            &[&self.pattern, &format!("{:.0}", self.cpu_above), &self.for_secs, &self.action.label(), &target],
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleSet {
+    pub rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    fn path() -> Option<PathBuf> {
+        crate::app_data_dir().map(|d| d.join("watchdog.json"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let (Some(p), Ok(json)) = (Self::path(), serde_json::to_string_pretty(self)) {
+            let _ = std::fs::write(p, json);
+        }
+    }
+}
+
+/// 不区分大小写的 * 通配匹配；不含 * 时按完整名称匹配 (可省略 .exe)
+pub fn matches(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    let text = text.to_lowercase();
+    if pattern.is_empty() {
+        return false;
+    }
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return text == pattern || text.strip_suffix(".exe") == Some(pattern.as_str());
+    }
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// 进程分组在本轮的数据 (由监控线程提供)
+pub struct Sample<'a> {
+    pub name: &'a str,
+    pub friendly_name: &'a str,
+    pub cpu: f32,
+    pub pids: &'a [u32],
+    pub is_system: bool,
+}
+
+/// 一次触发记录
+#[derive(Clone, Debug)]
+pub struct Event {
+    pub time: String,
+    pub message: String,
+}
+
+/// 弹出磁盘的回调 (由 USB 线程执行)
+pub type EjectFn = Box<dyn Fn(&str) + Send>;
+/// 挂起成功的 (PID, 进程名) 交给界面，加入可恢复列表
+pub type SuspendedFn = Box<dyn Fn(u32, &str) + Send>;
+
+pub struct Watchdog {
+    /// 与界面共享的规则
+    rules: Arc<RwLock<RuleSet>>,
+    eject: EjectFn,
+    suspended: SuspendedFn,
+    /// (规则序号, 分组名) -> 开始超阈值的时刻
+    over_since: HashMap<(usize, String), Instant>,
+    /// 已触发、等待回落的 (规则序号, 分组名)
+    fired: HashSet<(usize, String)>,
+    pub log: VecDeque<Event>,
+}
+
+impl Watchdog {
+    pub fn new(rules: Arc<RwLock<RuleSet>>, eject: EjectFn, suspended: SuspendedFn) -> Self {
+        Self {
+            rules,
+            eject,
+            suspended,
+            over_since: HashMap::new(),
+            fired: HashSet::new(),
+            log: VecDeque::new(),
+        }
+    }
+
+    /// 没有规则时监控线程跳过采样
+    pub fn has_rules(&self) -> bool {
+        self.rules.read().map(|r| !r.rules.is_empty()).unwrap_or(false)
+    }
+
+    /// 评估一轮，触发的记录追加到 log (新在前)
+    pub fn evaluate(&mut self, samples: &[Sample]) {
+        let Ok(rules) = self.rules.read().map(|r| r.clone()) else {
+            return;
+        };
+        let now = Instant::now();
+        let own_pid = std::process::id();
+        let mut seen = HashSet::new();
+        let mut events = Vec::new();
+        for (i, rule) in rules.rules.iter().enumerate().filter(|(_, r)| r.enabled) {
+            for s in samples
+                .iter()
+                .filter(|s| matches(&rule.pattern, s.name) || matches(&rule.pattern, s.friendly_name))
+            {
+                let key = (i, s.name.to_string());
+                seen.insert(key.clone());
+                if s.cpu <= rule.cpu_above {
+                    self.over_since.remove(&key);
+                    self.fired.remove(&key);
+                    continue;
+                }
+                let since = *self.over_since.entry(key.clone()).or_insert(now);
+                if self.fired.contains(&key) || now.duration_since(since) < Duration::from_secs(rule.for_secs) {
+                    continue;
+                }
+                self.fired.insert(key);
+                let protected =
+                    s.is_system || s.pids.contains(&own_pid) || crate::gpu_guard::is_display_critical(s.name);
+                let result = if protected && rule.action.targets_process() {
+                    tr("受保护进程，仅通知").to_string()
+                } else {
+                    self.apply(rule, s)
+                };
+                let message = trf(
+                    "{} CPU {}% 持续 {} 秒 -> {}：{}",
+                    &[&s.name, &format!("{:.0}", s.cpu), &rule.for_secs, &rule.action.label(), &result],
+                );
+                crate::audit::record("看门狗", &format!("规则「{}」 {}", rule.summary(), message));
+                crate::tray::balloon(tr("看门狗"), &message, rule.action != Action::Notify);
+                events.push(Event {
+                    time: crate::local_timestamp(),
+                    message,
+                });
+            }
+        }
+        // 规则或进程消失后清理计时
+        self.over_since.retain(|k, _| seen.contains(k));
+        self.fired.retain(|k| seen.contains(k));
+        for e in events {
+            if self.log.len() == LOG_LEN {
+                self.log.pop_back();
+            }
+            self.log.push_front(e);
+        }
+    }
+}
+
+fn lower_priority(pid: u32) -> bool {
+    unsafe {
+        let process = OpenProcess(PROCESS_SET_INFORMATION, 0, pid);
+        if process == 0 {
+            return false;
+        }
+        let ok = SetPriorityClass(process, IDLE_PRIORITY_CLASS) != 0;
+        CloseHandle(process);
+        ok
+    }
+}
+
+/// 运行脚本，不等待其结束。命令来自用户可写的配置，以普通用户权限运行 (不继承本程序的
+/// 管理员权限)；降权启动不能传入环境变量，GK_* 用 set 写在命令前面
+fn run_script(command: &str, s: &Sample) -> Result<(), String> {
+    if command.trim().is_empty() {
+        return Err(tr("未填写命令").to_string());
+    }
+    let pids: Vec<String> = s.pids.iter().map(|p| p.to_string()).collect();
+    // 进程名中的 & | ^ 等会被 cmd 解释，只保留安全字符
+    let name: String = s.name.chars().filter(|c| c.is_alphanumeric() || " ._-()".contains(*c)).collect();
+    let windir = std::env::var("WINDIR").unwrap_or_else(|_| "C:\\Windows".to_string());
+    let cmd = format!("{}\\System32\\cmd.exe", windir);
+    let command_line = format!(
+        "\"{}\" /c set \"GK_PROCESS={}\"&& set \"GK_PIDS={}\"&& set \"GK_CPU={:.0}\"&& {}",
+        cmd,
+        name,
+        pids.join(","),
+        s.cpu,
+        command
+    );
+    crate::unelevated::spawn_command_line(&cmd, &command_line, None, false)
+}
+
+impl Watchdog {
+    fn apply(&self, rule: &Rule, s: &Sample) -> String {
+        let total = s.pids.len();
+        // 访客模式下只保留通知与普通弹出 (有占用时只报告)，其余动作与界面操作一样被挡下
+        if crate::guest_mode::is_active() && !matches!(rule.action, Action::Notify | Action::EjectDrive) {
+            return tr("访客模式中，仅通知").to_string();
+        }
+        let ok = match rule.action {
+            Action::Notify => return tr("已通知").to_string(),
+            Action::RunScript => {
+                return match run_script(&rule.command, s) {
+                    Ok(()) => trf("已启动 {}", &[&rule.command]),
+                    Err(e) => trf("无法运行脚本：{}", &[&e]),
+                }
+            }
+            Action::EjectDrive => {
+                if rule.drive.trim().is_empty() {
+                    return tr("未填写盘符").to_string();
+                }
+                (self.eject)(rule.drive.trim());
+                return trf("已请求弹出 {}", &[&rule.drive.trim()]);
+            }
+            Action::TrimMemory => {
+                let (trimmed, freed) = crate::browser_memory::trim(s.pids);
+                return trf(
+                    "{}/{} 个进程已裁剪，工作集减少 {} MB",
+                    &[&trimmed, &total, &format!("{:.0}", freed as f64 / 1024.0 / 1024.0)],
+                );
+            }
+            Action::LowerPriority => s.pids.iter().filter(|&&pid| lower_priority(pid)).count(),
+            Action::Suspend => s
+                .pids
+                .iter()
+                .filter(|&&pid| crate::window_picker::suspend(pid, s.name).is_ok())
+                .inspect(|&&pid| (self.suspended)(pid, s.name))
+                .count(),
+            Action::Kill => s.pids.iter().filter(|&&pid| rust_core_lib::process::kill(pid).is_ok()).count(),
+        };
+        trf("{}/{} 个进程成功", &[&ok, &total])
+    }
+}
@@ -0,0 +1,247 @@
+// ═══════════════════════════════════════════════════════════════
+//  周报 - 最近 7 天的资源占用、网络流量、弹出与告警汇总
+// ═══════════════════════════════════════════════════════════════
+//
+// 公用电脑的管理员不会天天盯着界面，周报把一周的情况汇总成一个文件：
+//   - CPU / 内存占用最高的进程 (进程历史数据库，按峰值排名)
+//   - 网络收发总量 (进程历史数据库的 traffic 表，按整小时统计)
+//   - 弹出记录 (eject_history.jsonl)
+//   - 看门狗告警 (审计日志中“看门狗 | 规则「…」”的行)
+// 输出 Markdown 或 HTML，保存在 %APPDATA%\GeekKiller\reports。
+
+use crate::i18n::{tr, trf};
+use crate::process_history;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 统计的天数
+pub const DAYS: u64 = 7;
+/// 每个排行取前几名
+const TOP: i64 = 10;
+/// 弹出与告警最多列出的条数
+const MAX_EVENTS: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+impl Format {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Format::Markdown => "Markdown",
+            Format::Html => "HTML",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            Format::Markdown => "md",
+            Format::Html => "html",
+        }
+    }
+}
+
+/// 报告中的一节：一句说明加一张表
+struct Section {
+    title: String,
+    summary: String,
+    headers: Vec<&'static str>,
+    numeric: Vec<bool>,
+    rows: Vec<Vec<String>>,
+}
+
+fn mb(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / 1024.0 / 1024.0)
+}
+
+fn gb(bytes: u64) -> String {
+    format!("{:.2} GB", bytes as f64 / 1024.0 / 1024.0 / 1024.0)
+}
+
+fn consumers_section(title: &str, since: u64, by_cpu: bool) -> Section {
+    let headers = vec![tr("进程"), tr("运行次数"), tr("累计时长"), tr("峰值内存"), tr("峰值 CPU")];
+    let numeric = vec![false, true, true, true, true];
+    match process_history::top_consumers(since, by_cpu, TOP) {
+        Ok(list) => Section {
+            title: title.to_string(),
+            summary: if list.is_empty() { tr("期间没有进程记录").to_string() } else { String::new() },
+            headers,
+            numeric,
+            rows: list
+                .into_iter()
+                .map(|c| {
+                    vec![
+                        c.name,
+                        c.runs.to_string(),
+                        crate::system_counters::format_uptime(std::time::Duration::from_secs(c.total_secs)),
+                        mb(c.peak_memory),
+                        format!("{:.1}%", c.peak_cpu),
+                    ]
+                })
+                .collect(),
+        },
+        Err(e) => Section {
+            title: title.to_string(),
+            summary: trf("无法读取进程历史：{}", &[&e]),
+            headers,
+            numeric,
+            rows: vec![],
+        },
+    }
+}
+
+fn traffic_section(since: u64) -> Section {
+    let summary = match process_history::traffic_since(since) {
+        Ok((received, sent)) => trf("接收 {}，发送 {}，合计 {}", &[&gb(received), &gb(sent), &gb(received + sent)]),
+        Err(e) => trf("无法读取流量记录：{}", &[&e]),
+    };
+    Section {
+        title: tr("网络流量").to_string(),
+        summary,
+        headers: vec![],
+        numeric: vec![],
+        rows: vec![],
+    }
+}
+
+fn ejects_section(since: &str) -> Section {
+    let records: Vec<_> = crate::eject_history::load_recent(usize::MAX)
+        .into_iter()
+        .take_while(|r| r.time.as_str() >= since)
+        .collect();
+    let failed = records.iter().filter(|r| !r.ok).count();
+    let killed: usize = records.iter().map(|r| r.killed.len()).sum();
+    Section {
+        title: tr("弹出记录").to_string(),
+        summary: trf(
+            "共 {} 次，失败 {} 次，为弹出结束进程 {} 个{}",
+            &[
+                &records.len(),
+                &failed,
+                &killed,
+                &if records.len() > MAX_EVENTS { trf("；仅列出最近 {} 次", &[&MAX_EVENTS]) } else { String::new() },
+            ],
+        ),
+        headers: vec![tr("时间"), tr("驱动器"), tr("方式"), tr("结果"), tr("结束的进程")],
+        numeric: vec![false; 5],
+        rows: records
+            .into_iter()
+            .take(MAX_EVENTS)
+            .map(|r| {
+                vec![
+                    r.time,
+                    r.drive,
+                    r.method.label().to_string(),
+                    if r.ok { tr("成功").to_string() } else { trf("失败：{}", &[&r.message]) },
+                    r.killed.iter().map(|k| k.name.as_str()).collect::<Vec<_>>().join(", "),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// 审计日志中的看门狗告警：[时间] 看门狗 | 规则「…」 …
+fn alerts_section(since: &str) -> Section {
+    let content = crate::audit::log_path().and_then(|p| std::fs::read_to_string(p).ok()).unwrap_or_default();
+    let alerts: Vec<(String, String)> = content
+        .lines()
+        .filter_map(|line| {
+            let (time, rest) = line.strip_prefix('[')?.split_once("] ")?;
+            let detail = rest.strip_prefix("看门狗 | ")?;
+            (detail.starts_with("规则「") && time >= since).then(|| (time.to_string(), detail.to_string()))
+        })
+        .collect();
+    Section {
+        title: tr("看门狗告警").to_string(),
+        summary: trf(
+            "共 {} 次{}",
+            &[&alerts.len(), &if alerts.len() > MAX_EVENTS { trf("；仅列出最近 {} 次", &[&MAX_EVENTS]) } else { String::new() }],
+        ),
+        headers: vec![tr("时间"), tr("内容")],
+        numeric: vec![false; 2],
+        rows: alerts.into_iter().rev().take(MAX_EVENTS).map(|(t, d)| vec![t, d]).collect(),
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn render(format: Format, title: &str, sections: &[Section]) -> String {
+    match format {
+        Format::Markdown => {
+            let mut out = vec![format!("# {}", title)];
+            for s in sections {
+                out.push(format!("## {}", s.title));
+                if !s.summary.is_empty() {
+                    out.push(s.summary.clone());
+                }
+                if !s.rows.is_empty() {
+                    out.push(crate::table_export::markdown(&s.headers, &s.numeric, &s.rows));
+                }
+            }
+            out.join("\r\n\r\n") + "\r\n"
+        }
+        Format::Html => {
+            let mut out = format!(
+                "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{0}</title>\n<style>\
+                 body{{font-family:sans-serif;margin:2em}}table{{border-collapse:collapse;margin-bottom:1em}}\
+                 th,td{{border:1px solid #ccc;padding:4px 8px}}th{{background:#eee}}td.n{{text-align:right}}\
+                 </style></head><body>\n<h1>{0}</h1>\n",
+                escape_html(title)
+            );
+            for s in sections {
+                out.push_str(&format!("<h2>{}</h2>\n", escape_html(&s.title)));
+                if !s.summary.is_empty() {
+                    out.push_str(&format!("<p>{}</p>\n", escape_html(&s.summary)));
+                }
+                if s.rows.is_empty() {
+                    continue;
+                }
+                out.push_str("<table><tr>");
+                for h in &s.headers {
+                    out.push_str(&format!("<th>{}</th>", escape_html(h)));
+                }
+                out.push_str("</tr>\n");
+                for row in &s.rows {
+                    out.push_str("<tr>");
+                    for (i, cell) in row.iter().enumerate() {
+                        let class = if s.numeric.get(i).copied().unwrap_or(false) { " class=\"n\"" } else { "" };
+                        out.push_str(&format!("<td{}>{}</td>", class, escape_html(cell)));
+                    }
+                    out.push_str("</tr>\n");
+                }
+                out.push_str("</table>\n");
+            }
+            out.push_str("</body></html>\n");
+            out
+        }
+    }
+}
+
+/// 生成最近 DAYS 天的报告并保存，返回文件路径
+pub fn generate(format: Format) -> Result<PathBuf, String> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let since = now.saturating_sub(DAYS * 24 * 3600);
+    // 弹出记录与审计日志的时间是本地时间字符串，按字典序比较
+    let since_local = crate::format_unix_local(since);
+    let until_local = crate::format_unix_local(now);
+    let title = trf("{} 周报 ({} ~ {})", &[&crate::panel_capture::hostname(), &since_local, &until_local]);
+    let sections = [
+        consumers_section(tr("CPU 占用最高"), since, true),
+        consumers_section(tr("内存占用最高"), since, false),
+        traffic_section(since),
+        ejects_section(&since_local),
+        alerts_section(&since_local),
+    ];
+
+    let dir = crate::app_data_dir().ok_or(tr("无法定位数据目录"))?.join("reports");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let date: String = until_local.chars().take(10).filter(|c| c.is_ascii_digit()).collect();
+    let path = dir.join(format!("weekly-{}.{}", date, format.extension()));
+    std::fs::write(&path, render(format, &title, &sections)).map_err(|e| e.to_string())?;
+    crate::audit::record("周报", &format!("{} -> {}", format.label(), path.display()));
+    Ok(path)
+}
@@ -0,0 +1,257 @@
+// ═══════════════════════════════════════════════════════════════
+//  外部显示接口 - 供 Stream Deck 插件 / 键盘小屏读取的本机 JSON
+// ═══════════════════════════════════════════════════════════════
+//
+// 硬件面板每秒轮询一次、屏幕只有几个字，需要的是极小的扁平 JSON：
+//   GET http://127.0.0.1:<端口>/widget            -> {"cpu":12,"mem":48,"down":35.2}
+//   GET http://127.0.0.1:<端口>/widget?fields=cpu,top
+// 返回哪些字段在设置里勾选，也可用 ?fields= 按请求指定 (不认识的名称忽略)。
+// 数值已取整到适合小屏显示的精度，单位固定：百分比、MB、KB/s。
+// 只监听回环地址，不需要鉴权。进程名等信息不能让任意网站读到：
+// 只对来自本机 (127.0.0.1 / localhost) 的 Origin 回显 Access-Control-Allow-Origin，
+// 本机网页插件仍可直接 fetch；Host 不是本机的请求 (DNS 重绑定) 一律拒绝。
+// 后台线程持有监听套接字，设置里改端口或关闭后下一轮 (POLL) 生效。
+
+use crate::i18n::{tr, trf};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+const POLL: Duration = Duration::from_millis(200);
+/// 读取请求的超时，防止半开连接卡住唯一的服务线程
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 可输出的字段
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Field {
+    Cpu,
+    Memory,
+    MemoryUsed,
+    NetDown,
+    NetUp,
+    TopProcess,
+    TopCpu,
+    Removable,
+    Processes,
+    Tight,
+}
+
+impl Field {
+    pub const ALL: [Field; 10] = [
+        Field::Cpu,
+        Field::Memory,
+        Field::MemoryUsed,
+        Field::NetDown,
+        Field::NetUp,
+        Field::TopProcess,
+        Field::TopCpu,
+        Field::Removable,
+        Field::Processes,
+        Field::Tight,
+    ];
+
+    /// JSON 键名，也是 ?fields= 中使用的名称
+    pub fn key(&self) -> &'static str {
+        match self {
+            Field::Cpu => "cpu",
+            Field::Memory => "mem",
+            Field::MemoryUsed => "mem_mb",
+            Field::NetDown => "down",
+            Field::NetUp => "up",
+            Field::TopProcess => "top",
+            Field::TopCpu => "top_cpu",
+            Field::Removable => "usb",
+            Field::Processes => "procs",
+            Field::Tight => "tight",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Field::Cpu => tr("CPU 占用 (%)"),
+            Field::Memory => tr("内存占用 (%)"),
+            Field::MemoryUsed => tr("已用内存 (MB)"),
+            Field::NetDown => tr("下载速率 (KB/s)"),
+            Field::NetUp => tr("上传速率 (KB/s)"),
+            Field::TopProcess => tr("CPU 占用最高的进程"),
+            Field::TopCpu => tr("该进程的 CPU (%)"),
+            Field::Removable => tr("可移动磁盘数"),
+            Field::Processes => tr("进程数"),
+            Field::Tight => tr("资源紧张"),
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Field> {
+        Field::ALL.into_iter().find(|f| f.key() == key)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub enabled: bool,
+    pub port: u16,
+    /// 未指定 ?fields= 时返回的字段
+    pub fields: Vec<Field>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 47821,
+            fields: vec![Field::Cpu, Field::Memory, Field::NetDown, Field::NetUp],
+        }
+    }
+}
+
+/// 由界面所在的快照换算出的一组读数
+#[derive(Clone, Debug, Default)]
+pub struct Metrics {
+    pub cpu: f32,
+    pub memory_percent: f32,
+    pub memory_used: u64,
+    pub net_down: u64,
+    pub net_up: u64,
+    pub top_process: Option<(String, f32)>,
+    pub removable: usize,
+    pub processes: Option<u32>,
+    pub tight: bool,
+}
+
+/// 服务线程的状态，供设置窗口显示
+#[derive(Clone, Debug, Default)]
+pub struct Status {
+    /// 正在监听的端口
+    pub listening: Option<u16>,
+    pub error: Option<String>,
+    pub requests: u64,
+}
+
+fn render(metrics: &Metrics, fields: &[Field]) -> String {
+    let mut map = serde_json::Map::new();
+    let one_decimal = |v: f64| (v * 10.0).round() / 10.0;
+    for field in fields {
+        let value = match field {
+            Field::Cpu => serde_json::json!(metrics.cpu.round()),
+            Field::Memory => serde_json::json!(metrics.memory_percent.round()),
+            Field::MemoryUsed => serde_json::json!(metrics.memory_used / 1024 / 1024),
+            Field::NetDown => serde_json::json!(one_decimal(metrics.net_down as f64 / 1024.0)),
+            Field::NetUp => serde_json::json!(one_decimal(metrics.net_up as f64 / 1024.0)),
+            Field::TopProcess => serde_json::json!(metrics.top_process.as_ref().map(|(name, _)| name)),
+            Field::TopCpu => serde_json::json!(metrics.top_process.as_ref().map(|(_, cpu)| cpu.round())),
+            Field::Removable => serde_json::json!(metrics.removable),
+            Field::Processes => serde_json::json!(metrics.processes),
+            Field::Tight => serde_json::json!(metrics.tight),
+        };
+        map.insert(field.key().to_string(), value);
+    }
+    serde_json::Value::Object(map).to_string()
+}
+
+/// 解析请求行中的路径，返回要输出的字段；路径不对时为 None
+fn parse_request(request: &str, configured: &[Field]) -> Option<Vec<Field>> {
+    let target = request.lines().next()?.strip_prefix("GET ")?.split(' ').next()?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    if path != "/widget" && path != "/" {
+        return None;
+    }
+    let requested = query
+        .split('&')
+        .find_map(|kv| kv.strip_prefix("fields="))
+        .map(|list| list.split(',').filter_map(Field::from_key).collect::<Vec<_>>());
+    Some(requested.unwrap_or_else(|| configured.to_vec()))
+}
+
+/// 请求头的值 (名称不区分大小写)
+fn header<'a>(request: &'a str, name: &str) -> Option<&'a str> {
+    request.lines().skip(1).find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim().eq_ignore_ascii_case(name).then(|| value.trim())
+    })
+}
+
+/// host[:port] 是否指向本机
+fn is_loopback_host(host: &str) -> bool {
+    let host = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or(rest),
+        None => host.split(':').next().unwrap_or(host),
+    };
+    host.eq_ignore_ascii_case("localhost") || host == "127.0.0.1" || host == "::1"
+}
+
+/// 允许跨域读取的 Origin：只有本机页面
+fn allowed_origin(request: &str) -> Option<&str> {
+    let origin = header(request, "Origin")?;
+    let host = origin.strip_prefix("http://").or_else(|| origin.strip_prefix("https://"))?;
+    is_loopback_host(host).then_some(origin)
+}
+
+fn respond(mut stream: TcpStream, configured: &[Field], source: &dyn Fn() -> Metrics) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let mut buf = [0u8; 1024];
+    let n = stream.read(&mut buf).unwrap_or(0);
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let (status, body) = if header(&request, "Host").is_some_and(|h| !is_loopback_host(h)) {
+        ("403 Forbidden", r#"{"error":"forbidden"}"#.to_string())
+    } else {
+        match parse_request(&request, configured) {
+            Some(fields) => ("200 OK", render(&source(), &fields)),
+            None => ("404 Not Found", r#"{"error":"use /widget"}"#.to_string()),
+        }
+    };
+    let cors = allowed_origin(&request)
+        .map(|origin| format!("Access-Control-Allow-Origin: {}\r\nVary: Origin\r\n", origin))
+        .unwrap_or_default();
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\n\
+         {}Cache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        cors,
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+/// 启动服务线程；source 在每次请求时读取最新快照
+pub fn spawn(config: Arc<RwLock<Config>>, status: Arc<Mutex<Status>>, source: Box<dyn Fn() -> Metrics + Send>) {
+    std::thread::spawn(move || {
+        let mut listener: Option<(u16, TcpListener)> = None;
+        loop {
+            let config = config.read().map(|c| c.clone()).unwrap_or_default();
+            let wanted = config.enabled.then_some(config.port);
+            if listener.as_ref().map(|(port, _)| *port) != wanted {
+                listener = None;
+                let mut error = None;
+                if let Some(port) = wanted {
+                    match TcpListener::bind((Ipv4Addr::LOCALHOST, port)).and_then(|l| l.set_nonblocking(true).map(|_| l)) {
+                        Ok(l) => {
+                            crate::audit::record("外部显示", &format!("监听 127.0.0.1:{}", port));
+                            listener = Some((port, l));
+                        }
+                        Err(e) => error = Some(trf("无法监听端口 {}：{}", &[&port, &e])),
+                    }
+                }
+                if let Ok(mut s) = status.lock() {
+                    s.listening = listener.as_ref().map(|(port, _)| *port);
+                    s.error = error;
+                }
+            }
+
+            if let Some((_, l)) = &listener {
+                while let Ok((stream, _)) = l.accept() {
+                    // 监听套接字是非阻塞的，接受到的连接改回阻塞再读写
+                    let _ = stream.set_nonblocking(false);
+                    respond(stream, &config.fields, source.as_ref());
+                    if let Ok(mut s) = status.lock() {
+                        s.requests += 1;
+                    }
+                }
+            }
+            std::thread::sleep(POLL);
+        }
+    });
+}
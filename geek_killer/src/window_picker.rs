@@ -0,0 +1,227 @@
+// ═══════════════════════════════════════════════════════════════
+//  点选窗口 - 十字光标点击任意窗口，找到所属进程后终止或挂起
+// ═══════════════════════════════════════════════════════════════
+//
+// 全屏卡死的程序挡住一切时最快的处理方式：
+//   - 拾取期间在独立线程装低级鼠标 / 键盘钩子 (WH_MOUSE_LL / WH_KEYBOARD_LL)，
+//     左键点击被吞掉不会传给目标窗口；右键、Esc 或 30 秒无操作取消
+//   - 系统光标临时换成十字，结束后用 SPI_SETCURSORS 恢复用户方案
+//   - WindowFromPoint -> 顶层窗口 -> GetWindowThreadProcessId 得到 PID；
+//     卡死的窗口会被 DWM 换成“幽灵窗口” (属于 dwm.exe)，需用 HungWindowFromGhostWindow 还原
+// 挂起 / 恢复用 ntdll 的 NtSuspendProcess / NtResumeProcess (未公开但自 XP 起稳定)。
+
+use crate::i18n::{tr, trf};
+use crate::long_path::wide;
+use std::cell::Cell;
+use std::sync::mpsc;
+use std::time::Duration;
+use windows_sys::Win32::Foundation::{CloseHandle, HWND, LPARAM, LRESULT, POINT, WPARAM};
+use windows_sys::Win32::System::LibraryLoader::{GetModuleHandleW, GetProcAddress};
+use windows_sys::Win32::System::Threading::{GetCurrentProcessId, OpenProcess, PROCESS_SUSPEND_RESUME};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    CallNextHookEx, CopyIcon, GetAncestor, GetClassNameW, GetMessageW, GetWindowTextW, GetWindowThreadProcessId,
+    IsHungAppWindow, KillTimer, LoadCursorW, PostQuitMessage, SetSystemCursor, SetTimer, SetWindowsHookExW,
+    SystemParametersInfoW, UnhookWindowsHookEx, WindowFromPoint, GA_ROOT, IDC_CROSS, KBDLLHOOKSTRUCT,
+    MSLLHOOKSTRUCT, OCR_NORMAL, SPI_SETCURSORS, WH_KEYBOARD_LL, WH_MOUSE_LL, WM_KEYDOWN, WM_LBUTTONDOWN,
+    WM_LBUTTONUP, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_TIMER,
+};
+
+/// 无操作自动取消
+const PICK_TIMEOUT: Duration = Duration::from_secs(30);
+const VK_ESCAPE: u32 = 0x1B;
+
+/// 拾取到的窗口及其进程
+#[derive(Clone, Debug)]
+pub struct Picked {
+    pub pid: u32,
+    pub name: String,
+    pub title: String,
+    /// 窗口未响应 (卡死)
+    pub hung: bool,
+}
+
+enum Outcome {
+    Clicked(POINT),
+    Cancelled,
+}
+
+thread_local! {
+    static PRESSED: Cell<Option<POINT>> = const { Cell::new(None) };
+    static OUTCOME: Cell<Option<Outcome>> = const { Cell::new(None) };
+}
+
+fn finish(outcome: Outcome) {
+    OUTCOME.with(|o| o.set(Some(outcome)));
+    unsafe { PostQuitMessage(0) };
+}
+
+unsafe extern "system" fn mouse_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code >= 0 {
+        let info = &*(lparam as *const MSLLHOOKSTRUCT);
+        match wparam as u32 {
+            // 按下时记位置，抬起时结束：两者都吞掉，目标窗口收不到半个点击
+            WM_LBUTTONDOWN => {
+                PRESSED.with(|p| p.set(Some(info.pt)));
+                return 1;
+            }
+            WM_LBUTTONUP => {
+                if let Some(pt) = PRESSED.with(|p| p.take()) {
+                    finish(Outcome::Clicked(pt));
+                }
+                return 1;
+            }
+            WM_RBUTTONDOWN => return 1,
+            WM_RBUTTONUP => {
+                finish(Outcome::Cancelled);
+                return 1;
+            }
+            _ => {}
+        }
+    }
+    CallNextHookEx(0, code, wparam, lparam)
+}
+
+unsafe extern "system" fn keyboard_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code >= 0 && wparam as u32 == WM_KEYDOWN {
+        let info = &*(lparam as *const KBDLLHOOKSTRUCT);
+        if info.vkCode == VK_ESCAPE {
+            finish(Outcome::Cancelled);
+            return 1;
+        }
+    }
+    CallNextHookEx(0, code, wparam, lparam)
+}
+
+/// 开始拾取；结果 (或取消原因) 通过返回的通道送回，并唤醒 UI
+pub fn start(ctx: egui::Context) -> mpsc::Receiver<Result<Picked, String>> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(pick());
+        ctx.request_repaint();
+    });
+    rx
+}
+
+fn pick() -> Result<Picked, String> {
+    unsafe {
+        let module = GetModuleHandleW(std::ptr::null());
+        let mouse = SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_proc), module, 0);
+        if mouse == 0 {
+            return Err(tr("无法安装鼠标钩子").to_string());
+        }
+        let keyboard = SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_proc), module, 0);
+        SetSystemCursor(CopyIcon(LoadCursorW(0, IDC_CROSS)), OCR_NORMAL);
+        let timer = SetTimer(0, 0, PICK_TIMEOUT.as_millis() as u32, None);
+
+        OUTCOME.with(|o| o.set(None));
+        let mut msg = std::mem::zeroed();
+        while GetMessageW(&mut msg, 0, 0, 0) > 0 {
+            if msg.message == WM_TIMER {
+                finish(Outcome::Cancelled);
+            }
+        }
+
+        KillTimer(0, timer);
+        UnhookWindowsHookEx(mouse);
+        if keyboard != 0 {
+            UnhookWindowsHookEx(keyboard);
+        }
+        // 重新加载用户的光标方案，撤销 SetSystemCursor
+        SystemParametersInfoW(SPI_SETCURSORS, 0, std::ptr::null_mut(), 0);
+
+        match OUTCOME.with(|o| o.take()) {
+            Some(Outcome::Clicked(pt)) => resolve(pt),
+            _ => Err(tr("已取消拾取").to_string()),
+        }
+    }
+}
+
+unsafe fn class_name(hwnd: HWND) -> String {
+    let mut buf = [0u16; 64];
+    let len = GetClassNameW(hwnd, buf.as_mut_ptr(), buf.len() as i32);
+    String::from_utf16_lossy(&buf[..len.max(0) as usize])
+}
+
+/// 幽灵窗口 -> 背后真正卡死的窗口 (user32 导出但未公开声明)
+pub unsafe fn unghost(hwnd: HWND) -> HWND {
+    if class_name(hwnd) != "Ghost" {
+        return hwnd;
+    }
+    let user32 = GetModuleHandleW(wide("user32.dll").as_ptr());
+    match GetProcAddress(user32, b"HungWindowFromGhostWindow\0".as_ptr()) {
+        Some(f) => {
+            let f: unsafe extern "system" fn(HWND) -> HWND = std::mem::transmute(f);
+            match f(hwnd) {
+                0 => hwnd,
+                hung => hung,
+            }
+        }
+        None => hwnd,
+    }
+}
+
+unsafe fn resolve(pt: POINT) -> Result<Picked, String> {
+    let hwnd = WindowFromPoint(pt);
+    if hwnd == 0 {
+        return Err(tr("该位置没有窗口").to_string());
+    }
+    let hwnd = unghost(GetAncestor(hwnd, GA_ROOT));
+    let mut pid = 0;
+    GetWindowThreadProcessId(hwnd, &mut pid);
+    if pid == 0 {
+        return Err(tr("无法获取窗口所属进程").to_string());
+    }
+    if pid == GetCurrentProcessId() {
+        return Err(tr("点中的是本程序自己的窗口").to_string());
+    }
+    let mut buf = [0u16; 256];
+    let len = GetWindowTextW(hwnd, buf.as_mut_ptr(), buf.len() as i32);
+    let title = String::from_utf16_lossy(&buf[..len.max(0) as usize]);
+    let name = crate::handles::process_image_name(pid)
+        .and_then(|p| p.rsplit('\\').next().map(|s| s.to_string()))
+        .unwrap_or_else(|| format!("PID {}", pid));
+    Ok(Picked {
+        pid,
+        name,
+        title,
+        hung: IsHungAppWindow(hwnd) != 0,
+    })
+}
+
+/// 调用 ntdll 中形如 NTSTATUS f(HANDLE) 的进程函数
+fn nt_process_call(pid: u32, func: &[u8]) -> Result<(), String> {
+    unsafe {
+        let ntdll = GetModuleHandleW(wide("ntdll.dll").as_ptr());
+        let f = GetProcAddress(ntdll, func.as_ptr()).ok_or(tr("系统不支持挂起进程"))?;
+        let f: unsafe extern "system" fn(isize) -> i32 = std::mem::transmute(f);
+        let process = OpenProcess(PROCESS_SUSPEND_RESUME, 0, pid);
+        if process == 0 {
+            return Err(trf("无法打开进程 {} (可能需要管理员权限)", &[&pid]));
+        }
+        let status = f(process);
+        CloseHandle(process);
+        if status < 0 {
+            Err(format!("NTSTATUS 0x{:08X}", status as u32))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+pub fn suspend(pid: u32, name: &str) -> Result<(), String> {
+    let result = nt_process_call(pid, b"NtSuspendProcess\0");
+    crate::audit::record(
+        "挂起进程",
+        &format!("{} (PID {}) -> {}", name, pid, result.as_ref().err().map(|e| e.as_str()).unwrap_or("已挂起")),
+    );
+    result
+}
+
+pub fn resume(pid: u32, name: &str) -> Result<(), String> {
+    let result = nt_process_call(pid, b"NtResumeProcess\0");
+    crate::audit::record(
+        "恢复进程",
+        &format!("{} (PID {}) -> {}", name, pid, result.as_ref().err().map(|e| e.as_str()).unwrap_or("已恢复")),
+    );
+    result
+}
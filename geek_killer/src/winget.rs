@@ -0,0 +1,180 @@
+// ═══════════════════════════════════════════════════════════════
+//  winget 集成 - 识别由 winget 管理的软件，提供升级 / 卸载
+// ═══════════════════════════════════════════════════════════════
+//
+// winget 没有稳定的机器可读输出 (--output json 仅新版本支持)，
+// 这里解析 `winget list` 的表格：按表头各列的起始位置切分每一行。
+// 列宽按控制台显示宽度计算 (中文字符占两列)。
+// 过长的 ID 会被截断为 “…”，截断的 ID 无法用 --exact 定位，这类条目直接跳过。
+
+use crate::i18n::{tr, trf};
+use crate::tool_runner::{run_to_completion, ToolSpec};
+use crate::uninstall::InstalledApp;
+
+const COMMON_ARGS: [&str; 2] = ["--accept-source-agreements", "--disable-interactivity"];
+
+#[derive(Clone, Debug, Default)]
+pub struct WingetPackage {
+    pub name: String,
+    pub id: String,
+    pub version: String,
+    /// 可升级到的版本，无更新时为空
+    pub available: String,
+    /// winget / msstore；为空表示本机安装但不在任何源中，无法升级
+    pub source: String,
+}
+
+impl WingetPackage {
+    pub fn can_upgrade(&self) -> bool {
+        !self.available.is_empty() && !self.source.is_empty()
+    }
+}
+
+fn char_width(c: char) -> usize {
+    if (c as u32) >= 0x1100 && !('\u{2026}'..='\u{2027}').contains(&c) {
+        2
+    } else {
+        1
+    }
+}
+
+/// 按显示宽度截取 [start, end) 列
+fn slice_columns(line: &str, start: usize, end: Option<usize>) -> String {
+    let mut col = 0;
+    let mut out = String::new();
+    for c in line.chars() {
+        if end.map(|e| col >= e).unwrap_or(false) {
+            break;
+        }
+        if col >= start {
+            out.push(c);
+        }
+        col += char_width(c);
+    }
+    out.trim().to_string()
+}
+
+/// 表头中每列的起始显示位置
+fn header_columns(header: &str) -> Vec<usize> {
+    let mut cols = Vec::new();
+    let mut col = 0;
+    let mut prev_space = true;
+    for c in header.chars() {
+        let space = c == ' ';
+        if prev_space && !space {
+            cols.push(col);
+        }
+        prev_space = space;
+        col += char_width(c);
+    }
+    cols
+}
+
+/// 解析 `winget list` 输出
+pub fn parse_list(output: &str) -> Vec<WingetPackage> {
+    // 进度动画用 \r 原地刷新，只保留每行最后一段
+    let lines: Vec<&str> = output
+        .lines()
+        .map(|l| l.rsplit('\r').next().unwrap_or(l))
+        .collect();
+    let Some(sep) = lines.iter().position(|l| l.starts_with("---")) else {
+        return vec![];
+    };
+    let Some(header) = sep.checked_sub(1).map(|i| lines[i]) else {
+        return vec![];
+    };
+    let cols = header_columns(header);
+    if cols.len() < 3 {
+        return vec![];
+    }
+
+    lines[sep + 1..]
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|line| {
+            let field = |i: usize| match cols.get(i) {
+                Some(&start) => slice_columns(line, start, cols.get(i + 1).copied()),
+                None => String::new(),
+            };
+            let pkg = WingetPackage {
+                name: field(0),
+                id: field(1),
+                version: field(2),
+                // 只有 4 列时第 4 列是“源”，没有“可用”列
+                available: if cols.len() >= 5 { field(3) } else { String::new() },
+                source: if cols.len() >= 5 { field(4) } else { field(3) },
+            };
+            let truncated = pkg.id.ends_with('…');
+            (!pkg.id.is_empty() && !truncated && !pkg.name.is_empty()).then_some(pkg)
+        })
+        .collect()
+}
+
+/// 列出本机所有已安装软件在 winget 中的对应条目 (耗时数秒，应在后台线程调用)
+pub fn list() -> Result<Vec<WingetPackage>, String> {
+    let mut args = vec!["list"];
+    args.extend(COMMON_ARGS);
+    let (_, stdout, stderr) = run_to_completion(&ToolSpec::new("winget list", "winget", &args))?;
+    let packages = parse_list(&stdout);
+    if packages.is_empty() {
+        let msg = stderr.trim();
+        return Err(if msg.is_empty() { tr("winget 未返回任何软件").to_string() } else { msg.to_string() });
+    }
+    Ok(packages)
+}
+
+/// 可用版本列表 (新 -> 旧) 或错误信息
+pub type Versions = Result<Vec<String>, String>;
+
+/// 查询某个包在源中的全部可用版本
+pub fn versions(id: &str) -> Versions {
+    let mut args = vec!["show", "--id", id, "--exact", "--versions"];
+    args.extend(COMMON_ARGS);
+    let (ok, stdout, stderr) = run_to_completion(&ToolSpec::new("winget show", "winget", &args))?;
+    if !ok {
+        let msg = if stderr.trim().is_empty() { stdout } else { stderr };
+        return Err(msg.lines().last().unwrap_or_default().trim().to_string());
+    }
+    let lines: Vec<&str> = stdout.lines().map(|l| l.rsplit('\r').next().unwrap_or(l)).collect();
+    let Some(sep) = lines.iter().position(|l| l.starts_with("---")) else {
+        return Ok(vec![]);
+    };
+    Ok(lines[sep + 1..]
+        .iter()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect())
+}
+
+fn normalize_name(name: &str) -> String {
+    name.trim_end_matches('…').trim().to_lowercase()
+}
+
+/// 为“程序和功能”中的条目找到对应的 winget 包；过长的名称会被 winget 截断为 “…”
+pub fn find_for_app<'a>(app: &InstalledApp, packages: &'a [WingetPackage]) -> Option<&'a WingetPackage> {
+    let target = normalize_name(&app.display_name);
+    if target.is_empty() {
+        return None;
+    }
+    packages
+        .iter()
+        .find(|p| normalize_name(&p.name) == target)
+        .or_else(|| {
+            packages.iter().find(|p| {
+                let name = normalize_name(&p.name);
+                p.name.ends_with('…') && name.len() >= 8 && target.starts_with(&name)
+            })
+        })
+}
+
+pub fn upgrade_spec(pkg: &WingetPackage) -> ToolSpec {
+    let mut args = vec!["upgrade", "--id", pkg.id.as_str(), "--exact", "--accept-package-agreements"];
+    args.extend(COMMON_ARGS);
+    ToolSpec::new(&trf("winget 升级 {}", &[&pkg.name]), "winget", &args)
+}
+
+pub fn uninstall_spec(pkg: &WingetPackage) -> ToolSpec {
+    let mut args = vec!["uninstall", "--id", pkg.id.as_str(), "--exact"];
+    args.extend(COMMON_ARGS);
+    ToolSpec::new(&trf("winget 卸载 {}", &[&pkg.name]), "winget", &args)
+}